@@ -121,6 +121,9 @@ pub struct ParachainHeader {
 	pub extrinsic_proof: Vec<Vec<u8>>,
 	/// Timestamp extrinsic
 	pub timestamp_extrinsic: Vec<u8>,
+	/// Storage proof of `pallet_timestamp::Now` against the state root of the parachain header,
+	/// used instead of the timestamp extrinsic with the storage timestamp proof type.
+	pub timestamp_storage_proof: Vec<Vec<u8>>,
 }
 
 #[derive(sp_std::fmt::Debug, Clone, PartialEq, Eq, Encode, Decode)]
@@ -17,6 +17,7 @@ use crate::{error::Error, Crypto};
 use beefy_light_client_primitives::{MerkleHasher, SignatureWithAuthorityIndex};
 use codec::{Decode, Encode};
 use frame_support::sp_runtime::traits::Convert;
+use light_client_common::{timestamp_storage_key, TimestampProofType};
 use sp_core::keccak_256;
 use sp_runtime::traits::BlakeTwo256;
 use sp_trie::{generate_trie_proof, TrieDBMutBuilder, TrieMut};
@@ -32,6 +33,18 @@ pub struct TimeStampExtWithProof {
 	pub proof: Vec<Vec<u8>>,
 }
 
+/// Holds the timestamp proofs of a block. Only the proofs of the requested
+/// [`TimestampProofType`] are set, the others are left empty.
+#[derive(Default)]
+pub struct TimestampProofs {
+	/// The timestamp inherent SCALE-encoded bytes, for [`TimestampProofType::Extrinsic`]
+	pub extrinsic: Vec<u8>,
+	/// Merkle-patricia trie existence proof for the timestamp inherent
+	pub extrinsic_proof: Vec<Vec<u8>>,
+	/// Storage proof of `pallet_timestamp::Now`, for [`TimestampProofType::Storage`]
+	pub storage_proof: Vec<Vec<u8>>,
+}
+
 /// This holds the signatures of a BEEFY commitment, along side a merkle multi-proof of the
 /// existence of the ethereum addresses associated with the signatures.
 pub struct AuthorityProofWithSignatures {
@@ -96,6 +109,45 @@ pub async fn fetch_timestamp_extrinsic_with_proof<T: Config>(
 	Ok(TimeStampExtWithProof { ext, proof })
 }
 
+/// Fetch the storage proof of `pallet_timestamp::Now` at the given block
+pub async fn fetch_timestamp_storage_proof<T: Config>(
+	client: &OnlineClient<T>,
+	block_hash: Option<T::Hash>,
+) -> Result<Vec<Vec<u8>>, Error> {
+	let key = timestamp_storage_key();
+	let proof = client
+		.rpc()
+		.read_proof(vec![key.as_slice()], block_hash)
+		.await?
+		.proof
+		.into_iter()
+		.map(|bytes| bytes.0)
+		.collect();
+
+	Ok(proof)
+}
+
+/// Fetch the timestamp proofs of the given block, as selected by `timestamp_proof_type`
+pub async fn fetch_timestamp_proofs<T: Config>(
+	client: &OnlineClient<T>,
+	block_hash: Option<T::Hash>,
+	timestamp_proof_type: TimestampProofType,
+) -> Result<TimestampProofs, Error> {
+	let proofs = match timestamp_proof_type {
+		TimestampProofType::Extrinsic => {
+			let TimeStampExtWithProof { ext, proof } =
+				fetch_timestamp_extrinsic_with_proof(client, block_hash).await?;
+			TimestampProofs { extrinsic: ext, extrinsic_proof: proof, ..Default::default() }
+		},
+		TimestampProofType::Storage => TimestampProofs {
+			storage_proof: fetch_timestamp_storage_proof(client, block_hash).await?,
+			..Default::default()
+		},
+	};
+
+	Ok(proofs)
+}
+
 /// Parachain idenitfier type
 pub type ParaId = u32;
 /// SCALE-encoded parachain header
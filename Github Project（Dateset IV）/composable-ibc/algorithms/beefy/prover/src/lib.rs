@@ -26,20 +26,25 @@ pub mod helpers;
 pub mod relay_chain_queries;
 
 use beefy_light_client_primitives::{
-	ClientState, HostFunctions, MmrUpdateProof, ParachainHeader, PartialMmrLeaf,
+	ClientState, HostFunctions, MerkleHasher, MmrUpdateProof, ParachainHeader, PartialMmrLeaf,
+	SignedCommitment,
 };
-use beefy_primitives::mmr::{BeefyNextAuthoritySet, MmrLeaf};
-use codec::Decode;
+use beefy_primitives::{
+	known_payloads::MMR_ROOT_ID,
+	mmr::{BeefyNextAuthoritySet, MmrLeaf},
+};
+use codec::{Decode, Encode};
 use error::Error;
 use helpers::{
-	fetch_timestamp_extrinsic_with_proof, prove_parachain_headers, ParaHeadsProof,
-	TimeStampExtWithProof,
+	fetch_timestamp_proofs, hash_authority_addresses, prove_authority_set, prove_parachain_headers,
+	AuthorityProofWithSignatures, ParaHeadsProof, TimestampProofs,
 };
 use hex_literal::hex;
 use pallet_mmr_primitives::Proof;
 use sp_core::{hexdisplay::AsBytesRef, keccak_256, H256};
 use sp_io::crypto;
 use sp_runtime::traits::BlakeTwo256;
+use std::future::Future;
 use subxt::{
 	config::{Header as HeaderT, Header},
 	rpc::rpc_params,
@@ -47,8 +52,14 @@ use subxt::{
 };
 
 use crate::relay_chain_queries::parachain_header_storage_key;
-use light_client_common::config::{AsInner, BeefyAuthoritySetT, RuntimeStorage};
-use relay_chain_queries::{fetch_finalized_parachain_heads, fetch_mmr_proof, FinalizedParaHeads};
+use light_client_common::{
+	config::{AsInner, BeefyAuthoritySetT, RuntimeStorage},
+	TimestampProofType,
+};
+use relay_chain_queries::{
+	fetch_beefy_justification, fetch_beefy_justification_at, fetch_finalized_parachain_heads,
+	fetch_mmr_proof, FinalizedParaHeads,
+};
 
 /// Host function implementation for beefy light client.
 #[derive(Clone, PartialEq, Eq, Debug, Default)]
@@ -81,6 +92,8 @@ pub struct Prover<T: Config> {
 	pub para_client: OnlineClient<T>,
 	/// Para Id for the associated parachain.
 	pub para_id: u32,
+	/// How the timestamps of the parachain headers are proven
+	pub timestamp_proof_type: TimestampProofType,
 }
 
 impl<T: light_client_common::config::Config> Prover<T>
@@ -265,12 +278,16 @@ where
 			} = prove_parachain_headers(&para_headers, self.para_id)?;
 
 			let decoded_para_head = T::Header::decode(&mut &para_head[..])?;
-			let TimeStampExtWithProof { ext: timestamp_extrinsic, proof: extrinsic_proof } =
-				fetch_timestamp_extrinsic_with_proof(
-					&self.para_client,
-					Some(decoded_para_head.hash()),
-				)
-				.await?;
+			let TimestampProofs {
+				extrinsic: timestamp_extrinsic,
+				extrinsic_proof,
+				storage_proof: timestamp_storage_proof,
+			} = fetch_timestamp_proofs(
+				&self.para_client,
+				Some(decoded_para_head.hash()),
+				self.timestamp_proof_type,
+			)
+			.await?;
 
 			let header = ParachainHeader {
 				parachain_header: para_head,
@@ -285,6 +302,7 @@ where
 				heads_total_count,
 				extrinsic_proof,
 				timestamp_extrinsic,
+				timestamp_storage_proof,
 			};
 
 			parachain_headers.push(header);
@@ -298,13 +316,11 @@ where
 	/// mmr root hash.
 	pub async fn fetch_mmr_update_proof_for(
 		&self,
-		_signed_commitment: beefy_primitives::SignedCommitment<
+		signed_commitment: beefy_primitives::SignedCommitment<
 			u32,
 			beefy_primitives::crypto::Signature,
 		>,
 	) -> Result<MmrUpdateProof, Error> {
-		todo!("fetch beefy authorities")
-		/*
 		let subxt_block_number: subxt::rpc::types::BlockNumber =
 			signed_commitment.commitment.block_number.into();
 		let block_hash =
@@ -317,7 +333,9 @@ where
 				},
 			)?;
 
-		let current_authorities: Vec<Public> = {
+		// The authorities at the commitment block are the ones that signed it, this also holds for
+		// the mandatory block of a new authority set.
+		let current_authorities = {
 			let key = T::Storage::beefy_authorities();
 			self.relay_client
 				.storage()
@@ -332,10 +350,16 @@ where
 		let leaf_proof =
 			fetch_mmr_proof(&self.relay_client, vec![block_number.into()], Some(block_hash))
 				.await?;
-		let leaves: Vec<Vec<u8>> = codec::Decode::decode(&mut &*leaf_proof.leaves.0)?;
-		let latest_leaf: MmrLeaf<u32, H256, H256, H256> = codec::Decode::decode(&mut &*leaves[0])?;
-		let mmr_proof: pallet_mmr_primitives::Proof<H256> =
-			codec::Decode::decode(&mut &*leaf_proof.proof.0)?;
+		let leaves: Vec<Vec<u8>> = Decode::decode(&mut &*leaf_proof.leaves.0)?;
+		let latest_leaf: MmrLeaf<u32, H256, H256, H256> = Decode::decode(
+			&mut &*leaves
+				.first()
+				.ok_or_else(|| {
+					Error::Custom(format!("No mmr leaf found for block {block_number}"))
+				})?
+				.as_slice(),
+		)?;
+		let mmr_proof: Proof<H256> = Decode::decode(&mut &*leaf_proof.proof.0)?;
 
 		let authority_address_hashes = hash_authority_addresses(
 			current_authorities.into_iter().map(|x| x.encode()).collect(),
@@ -349,17 +373,14 @@ where
 				commitment: signed_commitment.commitment.clone(),
 				signatures,
 			},
-			latest_mmr_leaf: latest_leaf.clone(),
+			latest_mmr_leaf: latest_leaf,
 			mmr_proof,
 			authority_proof,
 		})
-		 */
 	}
 
 	/// Construct a beefy client state to be submitted to the counterparty chain
 	pub async fn construct_beefy_client_state(&self) -> Result<ClientState, Error> {
-		todo!("fetch beefy authorities")
-		/*
 		let (signed_commitment, latest_beefy_finalized) =
 			fetch_beefy_justification(&self.relay_client).await?;
 
@@ -371,20 +392,19 @@ where
 				.at(latest_beefy_finalized)
 				.fetch(&key)
 				.await?
-				.expect("Should retrieve next authority set")
+				.ok_or_else(|| Error::Custom(format!("No next authority set found!")))?
 				.encode()
 		};
-		let next_authority_set = BeefyNextAuthoritySet::decode(&mut &*next_authority_set)
-			.expect("Should decode next authority set correctly");
+		let next_authority_set = BeefyNextAuthoritySet::decode(&mut &*next_authority_set)?;
 
-		let current_authorities: Vec<Public> = {
+		let current_authorities = {
 			let key = T::Storage::beefy_authorities();
 			self.relay_client
 				.storage()
 				.at(latest_beefy_finalized)
 				.fetch(&key)
 				.await?
-				.expect("Should retrieve next authority set")
+				.ok_or_else(|| Error::Custom(format!("No beefy authorities found!")))?
 		};
 
 		let authority_address_hashes = hash_authority_addresses(
@@ -393,10 +413,12 @@ where
 		let tree =
 			rs_merkle::MerkleTree::<MerkleHasher<Crypto>>::from_leaves(&authority_address_hashes);
 
-		let authority_root = tree.root().expect("Should generate root");
-		let authority_root: H256 = authority_root.into();
+		let authority_root: H256 = tree
+			.root()
+			.ok_or_else(|| Error::Custom(format!("Empty beefy authority set")))?
+			.into();
 		let current_authority_set = BeefyNextAuthoritySet {
-			id: next_authority_set.id - 1,
+			id: next_authority_set.id.saturating_sub(1),
 			len: authority_address_hashes.len() as u32,
 			root: authority_root,
 		};
@@ -405,16 +427,193 @@ where
 			.commitment
 			.payload
 			.get_decoded::<H256>(&MMR_ROOT_ID)
-			.expect("Mmr root hash should decode correctly");
+			.ok_or_else(|| Error::Custom(format!("No mmr root hash in the commitment")))?;
 
 		let client_state = ClientState {
 			mmr_root_hash,
 			latest_beefy_height: signed_commitment.commitment.block_number as u32,
-			current_authorities: current_authority_set.clone(),
-			next_authorities: next_authority_set.clone(),
+			current_authorities: current_authority_set,
+			next_authorities: next_authority_set,
 		};
 
 		Ok(client_state)
-		 */
+	}
+
+	/// Returns the signed commitments of the mandatory blocks of every authority set that was
+	/// enacted after the authority set with the given id, up to the latest BEEFY finalized block.
+	/// The light client has to be updated with these commitments in order, as it can only follow a
+	/// single authority set rotation per update.
+	pub async fn query_mandatory_commitments(
+		&self,
+		validator_set_id: u64,
+		latest_beefy_height: u32,
+	) -> Result<
+		Vec<beefy_primitives::SignedCommitment<u32, beefy_primitives::crypto::Signature>>,
+		Error,
+	> {
+		let (latest_block_number, latest_set_id) = self.latest_beefy_finalized_set_id().await?;
+		let mandatory_blocks = find_mandatory_blocks(
+			validator_set_id,
+			latest_set_id,
+			latest_beefy_height,
+			latest_block_number,
+			move |block_number| self.validator_set_id_at(block_number),
+		)
+		.await?;
+
+		let mut commitments = vec![];
+		for (set_id, block_number) in ((validator_set_id + 1)..).zip(mandatory_blocks) {
+			let block_hash = self
+				.relay_client
+				.rpc()
+				.block_hash(Some(block_number.into()))
+				.await?
+				.ok_or_else(|| {
+				Error::Custom(format!("Block hash not found for block {block_number}"))
+			})?;
+			let signed_commitment = fetch_beefy_justification_at(&self.relay_client, block_hash)
+				.await?
+				.ok_or_else(|| {
+					Error::Custom(format!(
+						"No beefy justification found for the mandatory block {block_number} of authority set {set_id}"
+					))
+				})?;
+			commitments.push(signed_commitment);
+		}
+
+		Ok(commitments)
+	}
+
+	/// Returns the latest BEEFY finalized relay chain block number and the id of the authority set
+	/// at that block.
+	pub async fn latest_beefy_finalized_set_id(&self) -> Result<(u32, u64), Error> {
+		let latest_beefy_finalized: <T as Config>::Hash =
+			self.relay_client.rpc().request("beefy_getFinalizedHead", rpc_params!()).await?;
+		let latest_header =
+			self.relay_client.rpc().header(Some(latest_beefy_finalized)).await?.ok_or_else(
+				|| Error::Custom(format!("Header not found for {latest_beefy_finalized:?}")),
+			)?;
+		let latest_block_number = u32::from(latest_header.number());
+		let latest_set_id = self.validator_set_id_at(latest_block_number).await?;
+		Ok((latest_block_number, latest_set_id))
+	}
+
+	/// Returns the id of the BEEFY authority set at the given relay chain block.
+	async fn validator_set_id_at(&self, block_number: u32) -> Result<u64, Error> {
+		let block_hash = self
+			.relay_client
+			.rpc()
+			.block_hash(Some(block_number.into()))
+			.await?
+			.ok_or_else(|| {
+				Error::Custom(format!("Block hash not found for block {block_number}"))
+			})?;
+		let key = T::Storage::beefy_validator_set_id();
+		let set_id =
+			self.relay_client.storage().at(block_hash).fetch(&key).await?.ok_or_else(|| {
+				Error::Custom(format!("No validator set id at block {block_number}"))
+			})?;
+		Ok(set_id)
+	}
+}
+
+/// Binary searches `from..=latest_block_number` for the first block of every authority set enacted
+/// after the authority set with the given id, up to `latest_set_id`. The first block of an
+/// authority set is its mandatory block.
+pub async fn find_mandatory_blocks<F, Fut>(
+	validator_set_id: u64,
+	latest_set_id: u64,
+	mut from: u32,
+	latest_block_number: u32,
+	mut validator_set_id_at: F,
+) -> Result<Vec<u32>, Error>
+where
+	F: FnMut(u32) -> Fut,
+	Fut: Future<Output = Result<u64, Error>>,
+{
+	let mut mandatory_blocks = vec![];
+	for set_id in (validator_set_id + 1)..=latest_set_id {
+		let (mut low, mut high) = (from, latest_block_number);
+		while low < high {
+			let mid = low + (high - low) / 2;
+			if validator_set_id_at(mid).await? < set_id {
+				low = mid + 1;
+			} else {
+				high = mid;
+			}
+		}
+		mandatory_blocks.push(low);
+		from = low;
+	}
+
+	Ok(mandatory_blocks)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	/// Authority sets 1, 2 and 3 are enacted at blocks 10, 25 and 40.
+	const ROTATIONS: [u32; 3] = [10, 25, 40];
+
+	fn set_id_at(block_number: u32) -> u64 {
+		ROTATIONS.iter().filter(|rotation| block_number >= **rotation).count() as u64
+	}
+
+	async fn mandatory_blocks(
+		validator_set_id: u64,
+		latest_set_id: u64,
+		from: u32,
+		latest_block_number: u32,
+	) -> Vec<u32> {
+		find_mandatory_blocks(
+			validator_set_id,
+			latest_set_id,
+			from,
+			latest_block_number,
+			|block_number| async move { Ok(set_id_at(block_number)) },
+		)
+		.await
+		.unwrap()
+	}
+
+	#[tokio::test]
+	async fn finds_mandatory_block_of_every_rotated_authority_set() {
+		assert_eq!(mandatory_blocks(0, 3, 5, 50).await, ROTATIONS);
+		assert_eq!(mandatory_blocks(1, 3, 12, 50).await, [25, 40]);
+		// the latest authority set was enacted at the latest finalized block
+		assert_eq!(mandatory_blocks(2, 3, 30, 40).await, [40]);
+	}
+
+	#[tokio::test]
+	async fn finds_no_mandatory_blocks_without_rotation() {
+		assert!(mandatory_blocks(3, 3, 41, 50).await.is_empty());
+		assert!(mandatory_blocks(1, 1, 12, 24).await.is_empty());
+	}
+
+	#[tokio::test]
+	async fn binary_searches_the_finalized_blocks_once_per_authority_set() {
+		let mut queried = vec![];
+		let blocks = find_mandatory_blocks(0, 3, 5, 50, |block_number| {
+			queried.push(block_number);
+			async move { Ok(set_id_at(block_number)) }
+		})
+		.await
+		.unwrap();
+
+		assert_eq!(blocks, ROTATIONS);
+		assert!(queried.iter().all(|block_number| (5..=50).contains(block_number)));
+		// at most log2(46) rounded up queries per authority set
+		assert!(queried.len() <= 3 * 6);
+	}
+
+	#[tokio::test]
+	async fn propagates_set_id_query_errors() {
+		let result = find_mandatory_blocks(0, 1, 5, 50, |block_number| async move {
+			Err::<u64, _>(Error::Custom(format!("No validator set id at block {block_number}")))
+		})
+		.await;
+
+		assert!(result.is_err());
 	}
 }
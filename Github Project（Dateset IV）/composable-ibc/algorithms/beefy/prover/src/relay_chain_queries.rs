@@ -159,6 +159,30 @@ pub async fn fetch_beefy_justification<T: Config>(
 	Ok((signed_commitment, latest_beefy_finalized))
 }
 
+/// Get the beefy justification of the block with the given hash, if any. Only mandatory blocks,
+/// the first blocks of each authority set, are guaranteed to have one.
+pub async fn fetch_beefy_justification_at<T: Config>(
+	client: &OnlineClient<T>,
+	block_hash: T::Hash,
+) -> Result<Option<SignedCommitment<u32, beefy_primitives::crypto::Signature>>, Error> {
+	let block = client
+		.rpc()
+		.block(Some(block_hash))
+		.await?
+		.ok_or_else(|| Error::Custom(format!("Block not found for hash {block_hash:?}")))?;
+
+	let beefy_justification = block.justifications.into_iter().flatten().find_map(|justfication| {
+		(justfication.0 == beefy_primitives::BEEFY_ENGINE_ID).then(|| justfication.1)
+	});
+	let Some(beefy_justification) = beefy_justification else { return Ok(None) };
+	let VersionedFinalityProof::V1(signed_commitment) = VersionedFinalityProof::<
+		u32,
+		beefy_primitives::crypto::Signature,
+	>::decode(&mut &*beefy_justification)?;
+
+	Ok(Some(signed_commitment))
+}
+
 /// Query a mmr  proof
 pub async fn fetch_mmr_proof<T: Config>(
 	client: &OnlineClient<T>,
@@ -30,6 +30,7 @@ use beefy_light_client_primitives::{
 use beefy_primitives::{known_payloads::MMR_ROOT_ID, mmr::MmrLeaf};
 use codec::{Decode, Encode};
 use frame_support::sp_runtime::{app_crypto::ByteArray, traits::Convert};
+use light_client_common::{verify_timestamp_storage_proof, TimestampProofType};
 use sp_core::H256;
 
 use alloc::{format, string::ToString};
@@ -192,10 +193,12 @@ where
 }
 
 /// Takes the updated client state and parachains headers update proof
-/// and verifies inclusion in mmr
+/// and verifies inclusion in mmr. The timestamps of the parachain headers are verified with the
+/// proofs selected by `timestamp_proof_type`.
 pub fn verify_parachain_headers<H>(
 	trusted_client_state: ClientState,
 	ParachainsUpdateProof { mmr_proof, parachain_headers }: ParachainsUpdateProof,
+	timestamp_proof_type: TimestampProofType,
 ) -> Result<(), BeefyClientError>
 where
 	H: HostFunctions + Clone,
@@ -219,16 +222,29 @@ where
 			))?
 		}
 
-		// Verify timestamp extrinsic
-		// Timestamp extrinsic should be the first inherent and hence the first extrinsic
-		// https://github.com/paritytech/substrate/blob/d602397a0bbb24b5d627795b797259a44a5e29e9/primitives/trie/src/lib.rs#L99-L101
-		let timestamp_ext_key = codec::Compact(0u64).encode();
-		sp_trie::verify_trie_proof::<LayoutV0<H::BlakeTwo256>, _, _, _>(
-			&decoded_para_header.extrinsics_root,
-			&&*parachain_header.extrinsic_proof,
-			&vec![(timestamp_ext_key, Some(&*parachain_header.timestamp_extrinsic))],
-		)
-		.map_err(|_| BeefyClientError::Custom(format!("Invalid extrinsic proof")))?;
+		match timestamp_proof_type {
+			TimestampProofType::Extrinsic => {
+				// Verify timestamp extrinsic
+				// Timestamp extrinsic should be the first inherent and hence the first extrinsic
+				// https://github.com/paritytech/substrate/blob/d602397a0bbb24b5d627795b797259a44a5e29e9/primitives/trie/src/lib.rs#L99-L101
+				let timestamp_ext_key = codec::Compact(0u64).encode();
+				sp_trie::verify_trie_proof::<LayoutV0<H::BlakeTwo256>, _, _, _>(
+					&decoded_para_header.extrinsics_root,
+					&&*parachain_header.extrinsic_proof,
+					&vec![(timestamp_ext_key, Some(&*parachain_header.timestamp_extrinsic))],
+				)
+				.map_err(|_| BeefyClientError::Custom(format!("Invalid extrinsic proof")))?;
+			},
+			TimestampProofType::Storage => {
+				verify_timestamp_storage_proof::<H::BlakeTwo256>(
+					&decoded_para_header.state_root,
+					parachain_header.timestamp_storage_proof,
+				)
+				.map_err(|err| {
+					BeefyClientError::Custom(format!("Invalid timestamp storage proof: {err}"))
+				})?;
+			},
+		}
 
 		let pair = (parachain_header.para_id, parachain_header.parachain_header);
 		let leaf_bytes = pair.encode();
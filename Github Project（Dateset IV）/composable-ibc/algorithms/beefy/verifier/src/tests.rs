@@ -59,7 +59,12 @@ async fn test_verify_mmr_with_proof() {
 		.await
 		.unwrap();
 
-	let parachain_client = Prover { relay_client: client, para_client, para_id: 2000 };
+	let parachain_client = Prover {
+		relay_client: client,
+		para_client,
+		para_id: 2000,
+		timestamp_proof_type: Default::default(),
+	};
 
 	let mut subscription_stream = subscription.enumerate().take(100);
 	while let Some((count, Ok(encoded_versioned_finality_proof))) = subscription_stream.next().await
@@ -234,7 +239,12 @@ async fn verify_parachain_headers() {
 		.await
 		.unwrap();
 
-	let parachain_client = Prover { relay_client: client, para_client, para_id: 2000 };
+	let parachain_client = Prover {
+		relay_client: client,
+		para_client,
+		para_id: 2000,
+		timestamp_proof_type: Default::default(),
+	};
 
 	let mut subscription_stream = subscription.enumerate().take(100);
 	while let Some((count, Ok(encoded_versioned_finality_proof))) = subscription_stream.next().await
@@ -287,8 +297,12 @@ async fn verify_parachain_headers() {
 		client_state = crate::verify_mmr_root_with_proof::<Crypto>(client_state, mmr_update)
 			.expect("verify_mmr_root_with_proof should not panic!");
 
-		crate::verify_parachain_headers::<Crypto>(client_state.clone(), parachain_update_proof)
-			.expect("verify_parachain_headers should not panic!");
+		crate::verify_parachain_headers::<Crypto>(
+			client_state.clone(),
+			parachain_update_proof,
+			parachain_client.timestamp_proof_type,
+		)
+		.expect("verify_parachain_headers should not panic!");
 
 		println!(
 			"\nSuccessfully verified parachain headers for block number: {}\n",
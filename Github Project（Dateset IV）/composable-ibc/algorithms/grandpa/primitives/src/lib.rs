@@ -24,6 +24,7 @@ extern crate alloc;
 use alloc::collections::BTreeMap;
 use codec::{Decode, Encode};
 use core::fmt::Debug;
+use light_client_common::TimestampProofType;
 use sp_consensus_grandpa::{AuthorityId, AuthorityList, AuthoritySignature};
 use sp_core::{ed25519, sp_std, H256};
 use sp_runtime::traits::Header;
@@ -66,12 +67,14 @@ pub struct ClientState {
 	pub current_set_id: u64,
 	/// latest finalized height on the relay chain.
 	pub latest_relay_height: u32,
-	/// latest finalized height on the parachain.
+	/// latest finalized height on the parachain, or on the chain itself for standalone chains.
 	pub latest_para_height: u32,
 	/// latest finalized hash on the relay chain.
 	pub latest_relay_hash: Hash,
 	/// para_id of associated parachain
 	pub para_id: u32,
+	/// How the timestamps of the headers are proven
+	pub timestamp_proof_type: TimestampProofType,
 }
 
 /// Holds relavant parachain proofs for both header and timestamp extrinsic.
@@ -83,6 +86,9 @@ pub struct ParachainHeaderProofs {
 	pub extrinsic: Vec<u8>,
 	/// Timestamp extrinsic proof for previously proven parachain header.
 	pub extrinsic_proof: Vec<Vec<u8>>,
+	/// Storage proof of `pallet_timestamp::Now` against the state root of the parachain header,
+	/// used instead of the timestamp extrinsic with [`TimestampProofType::Storage`].
+	pub timestamp_storage_proof: Vec<Vec<u8>>,
 }
 
 /// Parachain headers with a Grandpa finality proof.
@@ -99,6 +105,29 @@ pub struct ParachainHeadersWithFinalityProof<H: codec::Codec> {
 	pub latest_para_height: u32,
 }
 
+/// Holds the timestamp proofs of a standalone chain header.
+#[derive(Clone, Debug, Encode, Decode)]
+pub struct SolochainHeaderProofs {
+	/// Timestamp extrinsic for ibc
+	pub extrinsic: Vec<u8>,
+	/// Timestamp extrinsic proof against the extrinsics root of the header.
+	pub extrinsic_proof: Vec<Vec<u8>>,
+	/// Storage proof of `pallet_timestamp::Now` against the state root of the header, used
+	/// instead of the timestamp extrinsic with [`TimestampProofType::Storage`].
+	pub timestamp_storage_proof: Vec<Vec<u8>>,
+}
+
+/// Standalone chain headers with a Grandpa finality proof.
+#[derive(Clone, Encode, Decode)]
+pub struct SolochainHeadersWithFinalityProof<H: codec::Codec> {
+	/// The grandpa finality proof: contains the chain headers from the last known finalized
+	/// grandpa block.
+	pub finality_proof: FinalityProof<H>,
+	/// Contains a map of header hashes to the timestamp extrinsic proofs of these headers. The
+	/// headers themselves are part of the unknown headers of the finality proof.
+	pub solochain_headers: BTreeMap<Hash, SolochainHeaderProofs>,
+}
+
 /// Host functions that allow the light client perform cryptographic operations in native.
 pub trait HostFunctions: light_client_common::HostFunctions + 'static {
 	/// RelayChain header type.
@@ -19,14 +19,17 @@
 
 use anyhow::anyhow;
 pub use beefy_prover;
-use beefy_prover::helpers::{fetch_timestamp_extrinsic_with_proof, TimeStampExtWithProof};
+use beefy_prover::helpers::{fetch_timestamp_proofs, TimestampProofs};
 use codec::{Decode, Encode};
 use finality_grandpa_rpc::GrandpaApiClient;
 use jsonrpsee::{async_client::Client, tracing::log, ws_client::WsClientBuilder};
-use light_client_common::config::{AsInner, RuntimeStorage};
+use light_client_common::{
+	config::{AsInner, RuntimeStorage},
+	TimestampProofType,
+};
 use primitives::{
 	parachain_header_storage_key, ClientState, FinalityProof, ParachainHeaderProofs,
-	ParachainHeadersWithFinalityProof,
+	ParachainHeadersWithFinalityProof, SolochainHeaderProofs, SolochainHeadersWithFinalityProof,
 };
 use rand::Rng;
 use serde::{Deserialize, Serialize};
@@ -66,6 +69,8 @@ pub struct GrandpaProver<T: Config> {
 	pub para_id: u32,
 	/// Delay between rpc calls to the RPC
 	pub rpc_call_delay: Duration,
+	/// How the timestamps of the proven headers are proven
+	pub timestamp_proof_type: TimestampProofType,
 }
 
 // We redefine these here because we want the header to be bounded by subxt::config::Header in the
@@ -98,6 +103,7 @@ impl<T: Config> Clone for GrandpaProver<T> {
 			para_ws_client: self.para_ws_client.clone(),
 			para_id: self.para_id,
 			rpc_call_delay: self.rpc_call_delay,
+			timestamp_proof_type: self.timestamp_proof_type,
 		}
 	}
 }
@@ -128,6 +134,27 @@ where
 			para_client,
 			para_id,
 			rpc_call_delay,
+			timestamp_proof_type: Default::default(),
+		})
+	}
+
+	/// Initializes the clients of a standalone chain given its ws url. Both the relay chain and the
+	/// parachain clients point to the chain itself.
+	pub async fn new_solochain(
+		ws_url: &str,
+		rpc_call_delay: Duration,
+	) -> Result<Self, anyhow::Error> {
+		let ws_client = Arc::new(WsClientBuilder::default().build(ws_url).await?);
+		let client = OnlineClient::<T>::from_rpc_client(ws_client.clone()).await?;
+
+		Ok(Self {
+			relay_ws_client: ws_client.clone(),
+			relay_client: client.clone(),
+			para_ws_client: ws_client,
+			para_client: client,
+			para_id: 0,
+			rpc_call_delay,
+			timestamp_proof_type: Default::default(),
 		})
 	}
 
@@ -136,6 +163,27 @@ where
 	where
 		<T as subxt::Config>::Header: Decode,
 	{
+		let mut client_state = self.initialize_finalized_state().await?;
+		let finalized_para_header = self
+			.query_latest_finalized_parachain_header(client_state.latest_relay_height)
+			.await?;
+		client_state.latest_para_height = u32::from(finalized_para_header.number());
+
+		Ok(client_state)
+	}
+
+	/// Construct the inital client state of a standalone chain, whose own headers are finalized by
+	/// GRANDPA.
+	pub async fn initialize_solochain_client_state(&self) -> Result<ClientState, anyhow::Error> {
+		let mut client_state = self.initialize_finalized_state().await?;
+		client_state.latest_para_height = client_state.latest_relay_height;
+
+		Ok(client_state)
+	}
+
+	/// Returns the latest finalized header of the GRANDPA chain together with the authority set
+	/// that finalized it.
+	async fn initialize_finalized_state(&self) -> Result<ClientState, anyhow::Error> {
 		use sp_consensus_grandpa::AuthorityList;
 		let latest_relay_hash = self.relay_client.rpc().finalized_head().await.unwrap();
 		log::debug!(target: "hyperspace", "Latest relay hash: {:?}", latest_relay_hash);
@@ -187,18 +235,15 @@ where
 			}
 		}
 
-		let latest_relay_height = u32::from(header.number());
-		let finalized_para_header =
-			self.query_latest_finalized_parachain_header(latest_relay_height).await.unwrap();
-
 		Ok(ClientState {
 			current_authorities,
 			current_set_id,
-			latest_relay_height,
+			latest_relay_height: u32::from(header.number()),
 			latest_relay_hash: latest_relay_hash.into(),
 			para_id: self.para_id,
-			// we'll set this below
-			latest_para_height: u32::from(finalized_para_header.number()),
+			// set by the caller
+			latest_para_height: 0,
+			timestamp_proof_type: self.timestamp_proof_type,
 		})
 	}
 
@@ -238,7 +283,7 @@ where
 	pub async fn query_finalized_parachain_headers_with_proof<H>(
 		&self,
 		previous_finalized_height: u32,
-		mut latest_finalized_height: u32,
+		latest_finalized_height: u32,
 		latest_justification: Option<Vec<u8>>,
 		header_numbers: Vec<<<T as subxt::Config>::Header as Header>::Number>,
 	) -> Result<ParachainHeadersWithFinalityProof<H>, anyhow::Error>
@@ -251,36 +296,13 @@ where
 		<<T as subxt::Config>::Header as Header>::Number: One + Clone + Sync + Send,
 		<T as subxt::Config>::Header: Decode + Sync,
 	{
-		let mut finality_proof = if let Some(justification) = latest_justification {
-			let justification = GrandpaJustification::<H>::decode(&mut &*justification)?;
-
-			FinalityProof::<H> {
-				block: justification.commit.target_hash,
-				justification: justification.encode(),
-				unknown_headers: vec![],
-			}
-		} else {
-			let encoded = GrandpaApiClient::<JustificationNotification, H256, u32>::prove_finality(
-				// we cast between the same type but different crate versions.
-				&*self.relay_ws_client.clone(),
+		let (finality_proof, latest_finalized_height) = self
+			.query_finality_proof::<H>(
+				previous_finalized_height,
 				latest_finalized_height,
+				latest_justification,
 			)
-			.await?
-			.ok_or_else(|| {
-				anyhow!("No justification found for block: {:?}", latest_finalized_height)
-			})?
-			.0;
-
-			let mut finality_proof = FinalityProof::<H>::decode(&mut &encoded[..])?;
-
-			let justification =
-				GrandpaJustification::<H>::decode(&mut &finality_proof.justification[..])?;
-
-			finality_proof.block = justification.commit.target_hash;
-
-			latest_finalized_height = u32::from(justification.commit.target_number);
-			finality_proof
-		};
+			.await?;
 
 		let start = self
 			.relay_client
@@ -296,42 +318,6 @@ where
 			.await?
 			.ok_or_else(|| anyhow!("Failed to fetch previous finalized hash + 1"))?;
 
-		let mut unknown_headers = vec![];
-		let mut unknown_headers_join_set: JoinSet<Result<_, anyhow::Error>> = JoinSet::new();
-		let heights = (previous_finalized_height..=latest_finalized_height).collect::<Vec<_>>();
-		for heights in heights.chunks(PROCESS_BLOCKS_BATCH_SIZE) {
-			for height in heights.to_owned() {
-				log::trace!(target: "hyperspace", "Processing height: {height}");
-
-				let prover = self.clone();
-				let to = self.rpc_call_delay.as_millis();
-				let duration = Duration::from_millis(rand::thread_rng().gen_range(1..to) as u64);
-				unknown_headers_join_set.spawn(async move {
-					sleep(duration).await;
-					let hash = prover
-						.relay_client
-						.rpc()
-						.block_hash(Some(height.into()))
-						.await?
-						.ok_or_else(|| anyhow!("Failed to fetch block has for height {height}"))?;
-
-					let header = prover
-						.relay_client
-						.rpc()
-						.header(Some(hash))
-						.await?
-						.ok_or_else(|| anyhow!("Header with hash: {hash:?} not found!"))?;
-
-					H::decode(&mut &header.encode()[..]).map_err(|e| e.into())
-				});
-			}
-
-			// TODO: change this to a binary tree with sorting over the header height
-			while let Some(header) = unknown_headers_join_set.join_next().await {
-				unknown_headers.push(header??);
-			}
-		}
-
 		// we are interested only in the blocks where our parachain header changes.
 		let para_storage_key = parachain_header_storage_key(self.para_id);
 		let keys = vec![para_storage_key.as_ref()];
@@ -407,14 +393,20 @@ where
 						.map(|p| p.0)
 						.collect();
 
-					let TimeStampExtWithProof { ext: extrinsic, proof: extrinsic_proof } =
-						fetch_timestamp_extrinsic_with_proof(
+					let TimestampProofs { extrinsic, extrinsic_proof, storage_proof } =
+						fetch_timestamp_proofs(
 							&client.para_client,
 							Some(para_header.hash()),
+							client.timestamp_proof_type,
 						)
 						.await
 						.map_err(|err| anyhow!("Error fetching timestamp with proof: {err:?}"))?;
-					let proofs = ParachainHeaderProofs { state_proof, extrinsic, extrinsic_proof };
+					let proofs = ParachainHeaderProofs {
+						state_proof,
+						extrinsic,
+						extrinsic_proof,
+						timestamp_storage_proof: storage_proof,
+					};
 					latest_para_height.fetch_max(u32::from(para_block_number), Ordering::SeqCst);
 					Ok(Some((H256::from(header.hash()), proofs)))
 				});
@@ -427,10 +419,6 @@ where
 			}
 		}
 
-		unknown_headers.sort_by_key(|header| header.number());
-		// overwrite unknown headers
-		finality_proof.unknown_headers = unknown_headers;
-
 		Ok(ParachainHeadersWithFinalityProof {
 			finality_proof,
 			parachain_headers: parachain_headers_with_proof,
@@ -438,6 +426,168 @@ where
 		})
 	}
 
+	/// Returns the finality proof for the given standalone chain `header_numbers` finalized by
+	/// `latest_finalized_height`, together with the timestamp proofs of these headers.
+	pub async fn query_finalized_solochain_headers_with_proof<H>(
+		&self,
+		previous_finalized_height: u32,
+		latest_finalized_height: u32,
+		latest_justification: Option<Vec<u8>>,
+		header_numbers: Vec<<<T as subxt::Config>::Header as Header>::Number>,
+	) -> Result<SolochainHeadersWithFinalityProof<H>, anyhow::Error>
+	where
+		H: Header + codec::Decode + Send + 'static,
+		u32: From<<H as Header>::Number>,
+		<H::Hasher as subxt::config::Hasher>::Output: From<T::Hash>,
+		T::Hash: From<<H::Hasher as subxt::config::Hasher>::Output>,
+		H::Number: finality_grandpa::BlockNumberOps,
+		<<T as subxt::Config>::Header as Header>::Number: One + Clone + Sync + Send,
+		<T as subxt::Config>::Header: Decode + Sync,
+	{
+		let (finality_proof, _) = self
+			.query_finality_proof::<H>(
+				previous_finalized_height,
+				latest_finalized_height,
+				latest_justification,
+			)
+			.await?;
+
+		let header_numbers = header_numbers.into_iter().map(u32::from).collect::<BTreeSet<_>>();
+		let mut solochain_headers_with_proof = BTreeMap::<H256, SolochainHeaderProofs>::default();
+		let mut join_set: JoinSet<Result<_, anyhow::Error>> = JoinSet::new();
+		let headers = finality_proof
+			.unknown_headers
+			.iter()
+			.filter(|header| header_numbers.contains(&u32::from(header.number())))
+			.map(|header| T::Hash::from(header.hash()))
+			.collect::<Vec<_>>();
+		for hashes in headers.chunks(PROCESS_BLOCKS_BATCH_SIZE) {
+			for hash in hashes.to_owned() {
+				let client = self.clone();
+				let to = self.rpc_call_delay.as_millis();
+				let duration = Duration::from_millis(rand::thread_rng().gen_range(1..to) as u64);
+				join_set.spawn(async move {
+					sleep(duration).await;
+					let TimestampProofs { extrinsic, extrinsic_proof, storage_proof } =
+						fetch_timestamp_proofs(
+							&client.relay_client,
+							Some(hash),
+							client.timestamp_proof_type,
+						)
+						.await
+						.map_err(|err| anyhow!("Error fetching timestamp with proof: {err:?}"))?;
+					let proofs = SolochainHeaderProofs {
+						extrinsic,
+						extrinsic_proof,
+						timestamp_storage_proof: storage_proof,
+					};
+					Ok((H256::from(hash), proofs))
+				});
+			}
+
+			while let Some(res) = join_set.join_next().await {
+				let (hash, proofs) = res??;
+				solochain_headers_with_proof.insert(hash, proofs);
+			}
+		}
+
+		Ok(SolochainHeadersWithFinalityProof {
+			finality_proof,
+			solochain_headers: solochain_headers_with_proof,
+		})
+	}
+
+	/// Returns the finality proof of `latest_finalized_height`, or of the block finalized by
+	/// `latest_justification` if given, whose unknown headers are all the headers since
+	/// `previous_finalized_height`. Also returns the height of the finalized block.
+	async fn query_finality_proof<H>(
+		&self,
+		previous_finalized_height: u32,
+		mut latest_finalized_height: u32,
+		latest_justification: Option<Vec<u8>>,
+	) -> Result<(FinalityProof<H>, u32), anyhow::Error>
+	where
+		H: Header + codec::Decode + Send + 'static,
+		u32: From<<H as Header>::Number>,
+		<H::Hasher as subxt::config::Hasher>::Output: From<T::Hash>,
+		T::Hash: From<<H::Hasher as subxt::config::Hasher>::Output>,
+		H::Number: finality_grandpa::BlockNumberOps,
+		<T as subxt::Config>::Header: Decode + Sync,
+	{
+		let mut finality_proof = if let Some(justification) = latest_justification {
+			let justification = GrandpaJustification::<H>::decode(&mut &*justification)?;
+
+			FinalityProof::<H> {
+				block: justification.commit.target_hash,
+				justification: justification.encode(),
+				unknown_headers: vec![],
+			}
+		} else {
+			let encoded = GrandpaApiClient::<JustificationNotification, H256, u32>::prove_finality(
+				// we cast between the same type but different crate versions.
+				&*self.relay_ws_client.clone(),
+				latest_finalized_height,
+			)
+			.await?
+			.ok_or_else(|| {
+				anyhow!("No justification found for block: {:?}", latest_finalized_height)
+			})?
+			.0;
+
+			let mut finality_proof = FinalityProof::<H>::decode(&mut &encoded[..])?;
+
+			let justification =
+				GrandpaJustification::<H>::decode(&mut &finality_proof.justification[..])?;
+
+			finality_proof.block = justification.commit.target_hash;
+
+			latest_finalized_height = u32::from(justification.commit.target_number);
+			finality_proof
+		};
+
+		let mut unknown_headers = vec![];
+		let mut unknown_headers_join_set: JoinSet<Result<_, anyhow::Error>> = JoinSet::new();
+		let heights = (previous_finalized_height..=latest_finalized_height).collect::<Vec<_>>();
+		for heights in heights.chunks(PROCESS_BLOCKS_BATCH_SIZE) {
+			for height in heights.to_owned() {
+				log::trace!(target: "hyperspace", "Processing height: {height}");
+
+				let prover = self.clone();
+				let to = self.rpc_call_delay.as_millis();
+				let duration = Duration::from_millis(rand::thread_rng().gen_range(1..to) as u64);
+				unknown_headers_join_set.spawn(async move {
+					sleep(duration).await;
+					let hash = prover
+						.relay_client
+						.rpc()
+						.block_hash(Some(height.into()))
+						.await?
+						.ok_or_else(|| anyhow!("Failed to fetch block has for height {height}"))?;
+
+					let header = prover
+						.relay_client
+						.rpc()
+						.header(Some(hash))
+						.await?
+						.ok_or_else(|| anyhow!("Header with hash: {hash:?} not found!"))?;
+
+					H::decode(&mut &header.encode()[..]).map_err(|e| e.into())
+				});
+			}
+
+			// TODO: change this to a binary tree with sorting over the header height
+			while let Some(header) = unknown_headers_join_set.join_next().await {
+				unknown_headers.push(header??);
+			}
+		}
+
+		unknown_headers.sort_by_key(|header| header.number());
+		// overwrite unknown headers
+		finality_proof.unknown_headers = unknown_headers;
+
+		Ok((finality_proof, latest_finalized_height))
+	}
+
 	/// Queries the block at which the epoch for the given block belongs to ends.
 	pub async fn session_start_and_end_for_block(
 		&self,
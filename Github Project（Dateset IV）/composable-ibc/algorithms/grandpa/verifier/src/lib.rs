@@ -26,12 +26,12 @@ use anyhow::anyhow;
 use codec::{Decode, Encode};
 use finality_grandpa::Chain;
 use hash_db::Hasher;
-use light_client_common::state_machine;
+use light_client_common::{state_machine, verify_timestamp_storage_proof, TimestampProofType};
 use primitives::{
 	error,
 	justification::{find_scheduled_change, AncestryChain, GrandpaJustification},
-	parachain_header_storage_key, ClientState, HostFunctions, ParachainHeaderProofs,
-	ParachainHeadersWithFinalityProof,
+	parachain_header_storage_key, ClientState, FinalityProof, HostFunctions, ParachainHeaderProofs,
+	ParachainHeadersWithFinalityProof, SolochainHeaderProofs, SolochainHeadersWithFinalityProof,
 };
 use sp_core::H256;
 use sp_runtime::traits::Header;
@@ -57,6 +57,120 @@ where
 	let ParachainHeadersWithFinalityProof { finality_proof, parachain_headers, latest_para_height } =
 		proof;
 
+	// 1. & 2. verify the finality proof of the relay chain headers.
+	let (headers, finalized) =
+		verify_grandpa_finality_proof::<H, Host>(&client_state, &finality_proof)?;
+
+	// 3. verify state proofs of parachain headers in finalized relay chain headers.
+	let mut para_heights = vec![];
+	for (hash, proofs) in parachain_headers {
+		if finalized.binary_search(&hash).is_err() {
+			// seems relay hash isn't in the finalized chain.
+			continue
+		}
+		let relay_chain_header =
+			headers.header(&hash).expect("Headers have been checked by AncestryChain; qed");
+
+		let ParachainHeaderProofs {
+			extrinsic_proof,
+			extrinsic,
+			state_proof,
+			timestamp_storage_proof,
+		} = proofs;
+		let proof = StorageProof::new(state_proof);
+		let key = parachain_header_storage_key(client_state.para_id);
+		// verify patricia-merkle state proofs
+		let header = state_machine::read_proof_check::<Host::BlakeTwo256, _>(
+			relay_chain_header.state_root(),
+			proof,
+			&[key.as_ref()],
+		)
+		.map_err(|err| anyhow!("error verifying parachain header state proof: {err}"))?
+		.remove(key.as_ref())
+		.flatten()
+		.ok_or_else(|| anyhow!("Invalid proof, parachain header not found"))?;
+		let parachain_header = H::decode(&mut &header[..])?;
+		para_heights.push(parachain_header.number().clone().into());
+		verify_timestamp_proof::<H, Host>(
+			client_state.timestamp_proof_type,
+			&parachain_header,
+			&extrinsic,
+			&extrinsic_proof,
+			timestamp_storage_proof,
+		)?;
+	}
+
+	// 4. set new client state, optionally rotating authorities
+	if let Some(max_height) = para_heights.into_iter().max() {
+		if max_height != latest_para_height {
+			Err(anyhow!("Latest parachain header height doesn't match the one in the proof"))?;
+		}
+		client_state.latest_para_height = max_height;
+	}
+	apply_finalized_target(&mut client_state, &headers, finality_proof.block);
+
+	Ok(client_state)
+}
+
+/// This function verifies the GRANDPA finality proof for the headers of a standalone chain.
+///
+/// Unlike parachains, the finalized headers are the chain's own headers, so their state roots are
+/// taken directly from the headers and only their timestamps have to be proven.
+pub fn verify_solochain_headers_with_grandpa_finality_proof<H, Host>(
+	mut client_state: ClientState,
+	proof: SolochainHeadersWithFinalityProof<H>,
+) -> Result<ClientState, error::Error>
+where
+	H: Header<Hash = H256, Number = u32>,
+	H::Number: finality_grandpa::BlockNumberOps + Into<u32>,
+	Host: HostFunctions,
+	Host::BlakeTwo256: Hasher<Out = H256>,
+{
+	let SolochainHeadersWithFinalityProof { finality_proof, solochain_headers } = proof;
+
+	let (headers, finalized) =
+		verify_grandpa_finality_proof::<H, Host>(&client_state, &finality_proof)?;
+
+	let mut heights = vec![];
+	for (hash, proofs) in solochain_headers {
+		if finalized.binary_search(&hash).is_err() {
+			// seems the header isn't in the finalized chain.
+			continue
+		}
+		let header =
+			headers.header(&hash).expect("Headers have been checked by AncestryChain; qed");
+
+		let SolochainHeaderProofs { extrinsic, extrinsic_proof, timestamp_storage_proof } = proofs;
+		verify_timestamp_proof::<H, Host>(
+			client_state.timestamp_proof_type,
+			header,
+			&extrinsic,
+			&extrinsic_proof,
+			timestamp_storage_proof,
+		)?;
+		heights.push(*header.number());
+	}
+
+	if let Some(max_height) = heights.into_iter().max() {
+		client_state.latest_para_height = max_height;
+	}
+	apply_finalized_target(&mut client_state, &headers, finality_proof.block);
+
+	Ok(client_state)
+}
+
+/// Validates the unknown headers of the finality proof and verifies its justification against the
+/// current authority set. Returns the ancestry chain of the unknown headers together with the
+/// sorted hashes of the newly finalized headers.
+fn verify_grandpa_finality_proof<H, Host>(
+	client_state: &ClientState,
+	finality_proof: &FinalityProof<H>,
+) -> Result<(AncestryChain<H>, Vec<H256>), error::Error>
+where
+	H: Header<Hash = H256, Number = u32>,
+	H::Number: finality_grandpa::BlockNumberOps + Into<u32>,
+	Host: HostFunctions,
+{
 	// 1. First validate unknown headers.
 	let headers = AncestryChain::<H>::new(&finality_proof.unknown_headers);
 
@@ -88,69 +202,86 @@ where
 	if base.number() < &client_state.latest_relay_height {
 		headers.ancestry(base.hash(), client_state.latest_relay_hash).map_err(|_| {
 			anyhow!(
-				"[verify_parachain_headers_with_grandpa_finality_proof] Invalid ancestry (base -> latest relay block)!"
+				"[verify_grandpa_finality_proof] Invalid ancestry (base -> latest relay block)!"
 			)
 		})?;
 	}
 
-	let mut finalized = headers.ancestry(from, target.hash()).map_err(|_| {
-		anyhow!("[verify_parachain_headers_with_grandpa_finality_proof] Invalid ancestry!")
-	})?;
+	let mut finalized = headers
+		.ancestry(from, target.hash())
+		.map_err(|_| anyhow!("[verify_grandpa_finality_proof] Invalid ancestry!"))?;
 	finalized.sort();
 
 	// 2. verify justification.
 	justification.verify::<Host>(client_state.current_set_id, &client_state.current_authorities)?;
 
-	// 3. verify state proofs of parachain headers in finalized relay chain headers.
-	let mut para_heights = vec![];
-	for (hash, proofs) in parachain_headers {
-		if finalized.binary_search(&hash).is_err() {
-			// seems relay hash isn't in the finalized chain.
-			continue
-		}
-		let relay_chain_header =
-			headers.header(&hash).expect("Headers have been checked by AncestryChain; qed");
+	Ok((headers, finalized))
+}
 
-		let ParachainHeaderProofs { extrinsic_proof, extrinsic, state_proof } = proofs;
-		let proof = StorageProof::new(state_proof);
-		let key = parachain_header_storage_key(client_state.para_id);
-		// verify patricia-merkle state proofs
-		let header = state_machine::read_proof_check::<Host::BlakeTwo256, _>(
-			relay_chain_header.state_root(),
-			proof,
-			&[key.as_ref()],
-		)
-		.map_err(|err| anyhow!("error verifying parachain header state proof: {err}"))?
-		.remove(key.as_ref())
-		.flatten()
-		.ok_or_else(|| anyhow!("Invalid proof, parachain header not found"))?;
-		let parachain_header = H::decode(&mut &header[..])?;
-		para_heights.push(parachain_header.number().clone().into());
-		// Timestamp extrinsic should be the first inherent and hence the first extrinsic
-		// https://github.com/paritytech/substrate/blob/d602397a0bbb24b5d627795b797259a44a5e29e9/primitives/trie/src/lib.rs#L99-L101
-		let key = codec::Compact(0u64).encode();
-		// verify extrinsic proof for timestamp extrinsic
-		sp_trie::verify_trie_proof::<LayoutV0<Host::BlakeTwo256>, _, _, _>(
-			parachain_header.extrinsics_root(),
-			&extrinsic_proof,
-			&vec![(key, Some(&extrinsic[..]))],
-		)
-		.map_err(|_| anyhow!("Invalid extrinsic proof"))?;
+/// Verifies the timestamp proof of the given header, as selected by `timestamp_proof_type`.
+fn verify_timestamp_proof<H, Host>(
+	timestamp_proof_type: TimestampProofType,
+	header: &H,
+	extrinsic: &[u8],
+	extrinsic_proof: &[Vec<u8>],
+	timestamp_storage_proof: Vec<Vec<u8>>,
+) -> Result<(), error::Error>
+where
+	H: Header<Hash = H256, Number = u32>,
+	Host: HostFunctions,
+	Host::BlakeTwo256: Hasher<Out = H256>,
+{
+	match timestamp_proof_type {
+		TimestampProofType::Extrinsic =>
+			verify_timestamp_extrinsic::<Host>(header.extrinsics_root(), extrinsic, extrinsic_proof),
+		TimestampProofType::Storage => {
+			verify_timestamp_storage_proof::<Host::BlakeTwo256>(
+				header.state_root(),
+				timestamp_storage_proof,
+			)?;
+			Ok(())
+		},
 	}
+}
 
-	// 4. set new client state, optionally rotating authorities
+/// Verifies the trie proof of the timestamp extrinsic against the extrinsics root of its header.
+fn verify_timestamp_extrinsic<Host>(
+	extrinsics_root: &H256,
+	extrinsic: &[u8],
+	extrinsic_proof: &[Vec<u8>],
+) -> Result<(), error::Error>
+where
+	Host: HostFunctions,
+	Host::BlakeTwo256: Hasher<Out = H256>,
+{
+	// Timestamp extrinsic should be the first inherent and hence the first extrinsic
+	// https://github.com/paritytech/substrate/blob/d602397a0bbb24b5d627795b797259a44a5e29e9/primitives/trie/src/lib.rs#L99-L101
+	let key = codec::Compact(0u64).encode();
+	// verify extrinsic proof for timestamp extrinsic
+	sp_trie::verify_trie_proof::<LayoutV0<Host::BlakeTwo256>, _, _, _>(
+		extrinsics_root,
+		extrinsic_proof,
+		&vec![(key, Some(extrinsic))],
+	)
+	.map_err(|_| anyhow!("Invalid extrinsic proof"))?;
+
+	Ok(())
+}
+
+/// Moves the client state to the finalized target block, rotating the authorities if the target
+/// schedules an authority set change.
+fn apply_finalized_target<H>(
+	client_state: &mut ClientState,
+	headers: &AncestryChain<H>,
+	target: H256,
+) where
+	H: Header<Hash = H256, Number = u32>,
+{
+	let target = headers.header(&target).expect("Target has been checked by AncestryChain; qed");
 	client_state.latest_relay_hash = target.hash();
-	client_state.latest_relay_height = (*target.number()).into();
-	if let Some(max_height) = para_heights.into_iter().max() {
-		if max_height != latest_para_height {
-			Err(anyhow!("Latest parachain header height doesn't match the one in the proof"))?;
-		}
-		client_state.latest_para_height = max_height;
-	}
-	if let Some(scheduled_change) = find_scheduled_change::<H>(&target) {
+	client_state.latest_relay_height = *target.number();
+	if let Some(scheduled_change) = find_scheduled_change::<H>(target) {
 		client_state.current_set_id += 1;
 		client_state.current_authorities = scheduled_change.next_authorities;
 	}
-
-	Ok(client_state)
 }
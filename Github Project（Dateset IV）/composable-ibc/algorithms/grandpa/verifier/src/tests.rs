@@ -13,19 +13,21 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use crate::verify_parachain_headers_with_grandpa_finality_proof;
+use crate::{verify_parachain_headers_with_grandpa_finality_proof, verify_timestamp_proof};
 use codec::{Decode, Encode};
 use futures::StreamExt;
 use grandpa_prover::{
 	beefy_prover::helpers::unsafe_arc_cast, host_functions::HostFunctionsProvider, GrandpaProver,
 };
 use hyperspace_core::substrate::DefaultConfig as PolkadotConfig;
+use light_client_common::{timestamp_storage_key, TimestampProofType};
 use polkadot_core_primitives::Header;
 use primitives::{
 	justification::GrandpaJustification, FinalityProof, ParachainHeadersWithFinalityProof,
 };
 use serde::{Deserialize, Serialize};
 use sp_core::H256;
+use sp_trie::{LayoutV0, MemoryDB, TrieDBMutBuilder, TrieMut};
 use std::time::Duration;
 use subxt::{
 	config::substrate::{BlakeTwo256, SubstrateHeader},
@@ -151,3 +153,79 @@ async fn follow_grandpa_justifications() {
 		println!("========= Successfully verified grandpa justification =========");
 	}
 }
+
+/// Builds a state trie out of `entries`, returning its root and a storage proof of all of its
+/// nodes.
+fn state_trie(entries: &[(Vec<u8>, Vec<u8>)]) -> (H256, Vec<Vec<u8>>) {
+	type Blake2 = sp_runtime::traits::BlakeTwo256;
+	let mut db = MemoryDB::<Blake2>::default();
+	let mut root = Default::default();
+	let root = {
+		let mut trie = TrieDBMutBuilder::<LayoutV0<Blake2>>::new(&mut db, &mut root).build();
+		for (key, value) in entries {
+			trie.insert(key, value).unwrap();
+		}
+		*trie.root()
+	};
+	let proof = db.drain().into_iter().map(|(_, (node, ..))| node.to_vec()).collect();
+	(root, proof)
+}
+
+fn header_with_state_root(state_root: H256) -> Header {
+	Header {
+		parent_hash: Default::default(),
+		number: 1,
+		state_root,
+		extrinsics_root: Default::default(),
+		digest: Default::default(),
+	}
+}
+
+#[test]
+fn verifies_timestamp_storage_proof_against_header_state_root() {
+	let (state_root, proof) =
+		state_trie(&[(timestamp_storage_key(), 1_650_894_363_000u64.encode())]);
+	let header = header_with_state_root(state_root);
+	verify_timestamp_proof::<Header, HostFunctionsProvider>(
+		TimestampProofType::Storage,
+		&header,
+		&[],
+		&[],
+		proof.clone(),
+	)
+	.unwrap();
+
+	// the proof has to be against the state root of the header
+	let other_header = header_with_state_root(H256::repeat_byte(1));
+	assert!(verify_timestamp_proof::<Header, HostFunctionsProvider>(
+		TimestampProofType::Storage,
+		&other_header,
+		&[],
+		&[],
+		proof.clone(),
+	)
+	.is_err());
+
+	// the timestamp extrinsic is proven instead with the extrinsic proof type
+	assert!(verify_timestamp_proof::<Header, HostFunctionsProvider>(
+		TimestampProofType::Extrinsic,
+		&header,
+		&[],
+		&[],
+		proof,
+	)
+	.is_err());
+}
+
+#[test]
+fn rejects_timestamp_storage_proof_without_timestamp() {
+	let (state_root, proof) = state_trie(&[(b"other".to_vec(), 1_650_894_363_000u64.encode())]);
+	assert!(verify_timestamp_proof::<Header, HostFunctionsProvider>(
+		TimestampProofType::Storage,
+		&header_with_state_root(state_root),
+		&[],
+		&[],
+		proof,
+	)
+	.is_err());
+}
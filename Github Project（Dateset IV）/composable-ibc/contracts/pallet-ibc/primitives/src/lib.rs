@@ -179,6 +179,29 @@ pub struct IdentifiedConnection {
 	pub connection_end: Vec<u8>,
 }
 
+/// Number of items returned by a list query when the request doesn't set a limit
+pub const DEFAULT_PAGE_LIMIT: u32 = 100;
+/// Maximum number of items a list query returns in a single page
+pub const MAX_PAGE_LIMIT: u32 = 1000;
+
+/// Pagination parameters of the paginated list queries.
+#[derive(Clone, Default, codec::Encode, codec::Decode, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+pub struct PageRequest {
+	/// Trie key to resume from, as returned in [`PageResponse::next_key`]
+	pub key: Option<Vec<u8>>,
+	/// Maximum number of items to return, [`DEFAULT_PAGE_LIMIT`] if zero and capped at
+	/// [`MAX_PAGE_LIMIT`]
+	pub limit: u32,
+}
+
+#[derive(
+	Clone, Default, codec::Encode, codec::Decode, PartialEq, Eq, Ord, PartialOrd, TypeInfo,
+)]
+pub struct PageResponse {
+	/// Trie key of the first item of the next page, `None` on the last page
+	pub next_key: Option<Vec<u8>>,
+}
+
 #[derive(Clone, codec::Encode, codec::Decode, PartialEq, Eq, Ord, PartialOrd, TypeInfo)]
 pub struct QueryClientStateResponse {
 	/// Protobuf encoded `AnyClientState`
@@ -194,6 +217,12 @@ pub struct QueryClientStatesResponse {
 	pub trie_key: Vec<u8>,
 }
 
+#[derive(Clone, codec::Encode, codec::Decode, PartialEq, Eq, Ord, PartialOrd, TypeInfo)]
+pub struct QueryClientsResponse {
+	pub clients: Vec<IdentifiedClientState>,
+	pub height: u64,
+}
+
 #[derive(Clone, codec::Encode, codec::Decode, PartialEq, Eq, Ord, PartialOrd, TypeInfo)]
 pub struct QueryConsensusStateResponse {
 	pub consensus_state: Vec<u8>,
@@ -217,6 +246,22 @@ pub struct QueryChannelResponse {
 	pub trie_key: Vec<u8>,
 }
 
+#[derive(Clone, codec::Encode, codec::Decode, PartialEq, Eq, Ord, PartialOrd, TypeInfo)]
+pub struct QueryChannelUpgradeResponse {
+	/// Protobuf encoded `ibc::core::ics04_channel::upgrade::Upgrade`
+	pub upgrade: Vec<u8>,
+	pub height: u64,
+	pub trie_key: Vec<u8>,
+}
+
+#[derive(Clone, codec::Encode, codec::Decode, PartialEq, Eq, Ord, PartialOrd, TypeInfo)]
+pub struct QueryUpgradeErrorResponse {
+	/// Protobuf encoded `ibc::core::ics04_channel::upgrade::ErrorReceipt`
+	pub error_receipt: Vec<u8>,
+	pub height: u64,
+	pub trie_key: Vec<u8>,
+}
+
 #[derive(Clone, codec::Encode, codec::Decode, PartialEq, Eq, Ord, PartialOrd, TypeInfo)]
 pub struct QueryChannelsResponse {
 	pub channels: Vec<IdentifiedChannel>,
@@ -289,6 +334,62 @@ pub struct QueryDenomTracesResponse {
 	pub total: Option<u64>,
 }
 
+#[derive(Clone, codec::Encode, codec::Decode, PartialEq, Eq, Ord, PartialOrd, TypeInfo)]
+pub struct EscrowedAmount {
+	/// Port id as utf8 string bytes
+	pub port_id: Vec<u8>,
+	/// Channel id as utf8 string bytes
+	pub channel_id: Vec<u8>,
+	/// Base denom as utf8 string bytes
+	pub denom: Vec<u8>,
+	/// Amount tracked by the pallet as outstanding on the counterparty chain
+	pub tracked: u128,
+	/// Balance held by the channel escrow account
+	pub balance: u128,
+}
+
+#[derive(Clone, codec::Encode, codec::Decode, PartialEq, Eq, Ord, PartialOrd, TypeInfo)]
+pub struct QueryEscrowInvariantResponse {
+	pub escrows: Vec<EscrowedAmount>,
+	pub height: u64,
+}
+
+/// Where the tokens of a received transfer were forwarded by its memo
+#[derive(Clone, codec::Encode, codec::Decode, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+pub enum ForwardDestination {
+	/// Forwarded over ibc in the packet with this sequence
+	Ibc { port_id: Vec<u8>, channel_id: Vec<u8>, sequence: u64 },
+	/// Forwarded over xcm to the parachain, or to the relay chain if `para_id` is `None`
+	Xcm { para_id: Option<u32>, receiver: Vec<u8> },
+}
+
+#[derive(Clone, Copy, codec::Encode, codec::Decode, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+pub enum ForwardStatus {
+	/// Waiting for the forwarded packet to be acknowledged
+	Pending,
+	/// The forwarded tokens were delivered
+	Succeeded,
+	/// The forwarded packet was acknowledged with an error
+	Failed,
+	/// The forwarded packet timed out
+	TimedOut,
+	/// The forward was given up and the received transfer refunded to its sender
+	Refunded,
+}
+
+#[derive(Clone, codec::Encode, codec::Decode, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+pub struct ForwardAttempt {
+	pub destination: ForwardDestination,
+	pub status: ForwardStatus,
+}
+
+#[derive(Clone, codec::Encode, codec::Decode, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+pub struct QueryForwardStatusResponse {
+	/// Forward attempts in the order they were made
+	pub attempts: Vec<ForwardAttempt>,
+	pub height: u64,
+}
+
 #[derive(Clone, codec::Encode, codec::Decode, PartialEq, Eq, Ord, PartialOrd, TypeInfo)]
 pub struct ConnectionHandshake {
 	pub client_state: Vec<u8>,
@@ -355,6 +456,7 @@ impl TryFrom<&OpenChannelParams> for Order {
 		match value.order {
 			1 => Ok(Order::Unordered),
 			2 => Ok(Order::Ordered),
+			3 => Ok(Order::OrderedAllowTimeout),
 			_ => Err(Error::Other { msg: None }),
 		}
 	}
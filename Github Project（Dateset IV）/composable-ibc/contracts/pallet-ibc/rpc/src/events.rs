@@ -1,10 +1,252 @@
 //! Relayer events.
-use ibc::{core::ics04_channel::packet::Packet, events::IbcEvent as RawIbcEvent};
+use crate::PaginatedEvents;
+use codec::{Decode, Encode};
+use ibc::{
+	applications::transfer::{
+		packet::{PacketData, PacketDataV2},
+		VERSION_V2,
+	},
+	core::{
+		ics04_channel::{packet::Packet, Version},
+		ics24_host::identifier::{ChannelId, PortId},
+	},
+	events::IbcEvent as RawIbcEvent,
+};
+use ibc_proto::cosmos::base::query::v1beta1::{PageRequest, PageResponse};
 use ibc_runtime_api::IbcRuntimeApi;
 use pallet_ibc::events::IbcEvent;
 use sc_client_api::HeaderBackend;
+use serde::{Deserialize, Serialize};
 use sp_api::{ApiRef, BlockT, ProvideRuntimeApi};
 
+/// Default number of events returned in a page when no limit is given
+pub const DEFAULT_EVENTS_PAGE_LIMIT: u64 = 100;
+/// Maximum number of events that can be requested in a single page
+pub const MAX_EVENTS_PAGE_LIMIT: u64 = 1000;
+/// Maximum number of blocks that can be scanned in a single request
+pub const MAX_EVENTS_BLOCK_RANGE: u32 = 10_000;
+
+/// Filter for ibc events, all fields that are set must match for an event to be returned.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EventFilter {
+	/// First block to scan, inclusive
+	pub from_block: u32,
+	/// Last block to scan, inclusive. Defaults to the best block
+	pub to_block: Option<u32>,
+	/// Event types to return, e.g. `send_packet`. All event types are returned if empty
+	#[serde(default)]
+	pub event_types: Vec<String>,
+	/// Port id on either side of a packet or channel
+	pub port_id: Option<String>,
+	/// Channel id on either side of a packet or channel
+	pub channel_id: Option<String>,
+	/// Lowest packet sequence, inclusive
+	pub min_sequence: Option<u64>,
+	/// Highest packet sequence, inclusive
+	pub max_sequence: Option<u64>,
+	/// Sender of an ics20 packet
+	pub sender: Option<String>,
+	/// Receiver of an ics20 packet
+	pub receiver: Option<String>,
+}
+
+impl EventFilter {
+	/// Returns true if the event satisfies every criteria set on this filter
+	pub fn matches(&self, event: &RawIbcEvent) -> bool {
+		if !self.event_types.is_empty() &&
+			!self.event_types.iter().any(|ty| ty == event.event_type().as_str())
+		{
+			return false
+		}
+
+		if self.port_id.is_some() || self.channel_id.is_some() {
+			let ends = channel_ends(event);
+			let port_matches = |port_id: &PortId| {
+				self.port_id.as_ref().map(|p| p == port_id.as_str()).unwrap_or(true)
+			};
+			let channel_matches = |channel_id: &Option<ChannelId>| match &self.channel_id {
+				Some(c) => channel_id.map(|id| &id.to_string() == c).unwrap_or(false),
+				None => true,
+			};
+			if !ends
+				.iter()
+				.any(|(port_id, channel_id)| port_matches(port_id) && channel_matches(channel_id))
+			{
+				return false
+			}
+		}
+
+		if self.min_sequence.is_some() || self.max_sequence.is_some() {
+			let sequence = match event.packet() {
+				Some(packet) => u64::from(packet.sequence),
+				None => return false,
+			};
+			if self.min_sequence.map(|min| sequence < min).unwrap_or(false) ||
+				self.max_sequence.map(|max| sequence > max).unwrap_or(false)
+			{
+				return false
+			}
+		}
+
+		if self.sender.is_some() || self.receiver.is_some() {
+			let packet_data =
+				match event.packet().and_then(|packet| decode_packet_data(&packet.data)) {
+					Some(packet_data) => packet_data,
+					None => return false,
+				};
+			if self.sender.as_ref().map(|s| s != packet_data.sender.as_ref()).unwrap_or(false) ||
+				self.receiver
+					.as_ref()
+					.map(|r| r != packet_data.receiver.as_ref())
+					.unwrap_or(false)
+			{
+				return false
+			}
+		}
+
+		true
+	}
+}
+
+/// Decodes ics20 packet data, `ics20-1` packets are json encoded while `ics20-2` packets are
+/// protobuf encoded.
+fn decode_packet_data(data: &[u8]) -> Option<PacketDataV2> {
+	serde_json::from_slice::<PacketData>(data)
+		.map(Into::into)
+		.ok()
+		.or_else(|| PacketDataV2::decode(&Version::new(VERSION_V2.to_string()), data).ok())
+}
+
+/// Returns the (port, channel) pairs an event refers to, packets refer to both their source and
+/// destination ends.
+fn channel_ends(event: &RawIbcEvent) -> Vec<(PortId, Option<ChannelId>)> {
+	if let Some(packet) = event.packet() {
+		return vec![
+			(packet.source_port.clone(), Some(packet.source_channel)),
+			(packet.destination_port.clone(), Some(packet.destination_channel)),
+		]
+	}
+	match event {
+		RawIbcEvent::OpenInitChannel(ev) => vec![(ev.port_id.clone(), ev.channel_id)],
+		RawIbcEvent::OpenTryChannel(ev) => vec![(ev.port_id.clone(), ev.channel_id)],
+		RawIbcEvent::OpenAckChannel(ev) => vec![(ev.port_id.clone(), ev.channel_id)],
+		RawIbcEvent::OpenConfirmChannel(ev) => vec![(ev.port_id.clone(), ev.channel_id)],
+		RawIbcEvent::CloseInitChannel(ev) => vec![(ev.port_id.clone(), Some(ev.channel_id))],
+		RawIbcEvent::CloseConfirmChannel(ev) => vec![(ev.port_id.clone(), ev.channel_id)],
+		ev => ev
+			.upgrade_attributes()
+			.map(|attrs| vec![(attrs.port_id.clone(), Some(attrs.channel_id.clone()))])
+			.unwrap_or_default(),
+	}
+}
+
+/// Position of an event on chain, used as the pagination key for paginated event queries.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Encode, Decode)]
+pub struct EventCursor {
+	/// Block number the event was emitted in
+	pub block_number: u32,
+	/// Index of the event among the ibc events of the block
+	pub index: u32,
+}
+
+/// A page of events being collected, events are fed in the order the query visits them.
+pub struct EventPage {
+	/// Position of the first event of the page, `None` on the first page
+	cursor: Option<EventCursor>,
+	reverse: bool,
+	limit: u64,
+	/// Matching events left to skip before the page starts
+	skip: u64,
+	count_total: bool,
+	total: u64,
+	events: Vec<IbcEventWithHeight>,
+	next_key: Vec<u8>,
+}
+
+impl EventPage {
+	/// Starts the page requested by `pagination`, holding at most `limit` events.
+	pub fn new(pagination: &PageRequest, limit: u64) -> Result<Self, codec::Error> {
+		let cursor = if pagination.key.is_empty() {
+			None
+		} else {
+			Some(EventCursor::decode(&mut &*pagination.key)?)
+		};
+		Ok(Self {
+			cursor,
+			reverse: pagination.reverse,
+			limit,
+			// Offsets are only honoured on the first page, subsequent pages resume from the key
+			skip: if cursor.is_some() { 0 } else { pagination.offset },
+			// Totals are only counted on the first page
+			count_total: pagination.count_total && cursor.is_none(),
+			total: 0,
+			events: vec![],
+			next_key: vec![],
+		})
+	}
+
+	/// Returns true if the page starts after every event of the block.
+	pub fn skips_block(&self, block_number: u32) -> bool {
+		match self.cursor {
+			Some(cursor) if self.reverse => block_number > cursor.block_number,
+			Some(cursor) => block_number < cursor.block_number,
+			None => false,
+		}
+	}
+
+	/// Adds the event at `position` to the page if it matches the filter.
+	/// Returns false once no more events need to be visited.
+	pub fn push(
+		&mut self,
+		filter: &EventFilter,
+		position: EventCursor,
+		event: RawIbcEvent,
+	) -> bool {
+		let reached = match self.cursor {
+			Some(cursor) if self.reverse => position <= cursor,
+			Some(cursor) => position >= cursor,
+			None => true,
+		};
+		if !reached || !filter.matches(&event) {
+			return true
+		}
+		self.total += 1;
+		if self.skip > 0 {
+			self.skip -= 1;
+			return true
+		}
+		if self.events.len() as u64 == self.limit {
+			if self.next_key.is_empty() {
+				self.next_key = position.encode();
+			}
+			return self.count_total
+		}
+		self.events
+			.push(IbcEventWithHeight { block_number: position.block_number, event });
+		true
+	}
+
+	/// Returns the collected events, `next_key` is set if more events match the filter.
+	pub fn finish(self) -> PaginatedEvents {
+		PaginatedEvents {
+			events: self.events,
+			pagination: Some(PageResponse {
+				next_key: self.next_key,
+				total: if self.count_total { self.total } else { 0 },
+			}),
+		}
+	}
+}
+
+/// Ibc event with the block it was emitted in
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct IbcEventWithHeight {
+	/// Block number the event was emitted in
+	pub block_number: u32,
+	/// The event
+	pub event: RawIbcEvent,
+}
+
 /// Filter out none relayer events and modify
 /// Fetch actual packet and acknowledgements from off chain storage and modify packets
 pub fn filter_map_pallet_event<C, Block, AssetId>(
@@ -135,3 +377,178 @@ where
 		_ => Some(event),
 	}
 }
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use ibc::{
+		applications::transfer::{Amount, PrefixedCoin, PrefixedDenom},
+		core::ics04_channel::{
+			events::{ReceivePacket, SendPacket},
+			packet::Sequence,
+		},
+		signer::Signer,
+		Height,
+	};
+	use std::str::FromStr;
+
+	fn packet_data(sender: &str, receiver: &str) -> PacketData {
+		PacketData {
+			token: PrefixedCoin {
+				denom: PrefixedDenom::from_str("transfer/channel-1/PICA").unwrap(),
+				amount: Amount::from_str("1000").unwrap(),
+			},
+			sender: Signer::from_str(sender).unwrap(),
+			receiver: Signer::from_str(receiver).unwrap(),
+			memo: String::new(),
+		}
+	}
+
+	fn send_packet(sequence: u64, channel: u64, data: Vec<u8>) -> RawIbcEvent {
+		RawIbcEvent::SendPacket(SendPacket {
+			height: Height::new(0, 1),
+			packet: Packet {
+				sequence: Sequence::from(sequence),
+				source_port: PortId::transfer(),
+				source_channel: ChannelId::new(channel),
+				destination_port: PortId::transfer(),
+				destination_channel: ChannelId::new(channel + 1),
+				data,
+				..Default::default()
+			},
+		})
+	}
+
+	fn sequence(event: &IbcEventWithHeight) -> u64 {
+		u64::from(event.event.packet().unwrap().sequence)
+	}
+
+	/// Events with sequences 1..=5, two per block starting at block 10
+	fn events() -> Vec<(EventCursor, RawIbcEvent)> {
+		(0..5u64)
+			.map(|i| {
+				let position = EventCursor { block_number: 10 + i as u32 / 2, index: i as u32 % 2 };
+				(position, send_packet(i + 1, 0, vec![]))
+			})
+			.collect()
+	}
+
+	fn page(filter: &EventFilter, pagination: PageRequest, limit: u64) -> PaginatedEvents {
+		let mut page = EventPage::new(&pagination, limit).unwrap();
+		let mut events = events();
+		if pagination.reverse {
+			events.reverse();
+		}
+		for (position, event) in events {
+			if page.skips_block(position.block_number) {
+				continue
+			}
+			if !page.push(filter, position, event) {
+				break
+			}
+		}
+		page.finish()
+	}
+
+	#[test]
+	fn filter_matches_sender_and_receiver_of_both_packet_encodings() {
+		let filter = EventFilter {
+			sender: Some("alice".to_string()),
+			receiver: Some("bob".to_string()),
+			..Default::default()
+		};
+		let v1 = serde_json::to_vec(&packet_data("alice", "bob")).unwrap();
+		let v2 = PacketDataV2::from(packet_data("alice", "bob"))
+			.encode(&Version::new(VERSION_V2.to_string()))
+			.unwrap();
+		assert!(filter.matches(&send_packet(1, 0, v1)));
+		assert!(filter.matches(&send_packet(1, 0, v2)));
+
+		let other = PacketDataV2::from(packet_data("alice", "charlie"))
+			.encode(&Version::new(VERSION_V2.to_string()))
+			.unwrap();
+		assert!(!filter.matches(&send_packet(1, 0, other)));
+		assert!(!filter.matches(&send_packet(1, 0, b"not a transfer".to_vec())));
+	}
+
+	#[test]
+	fn filter_matches_event_type_channel_and_sequence_range() {
+		let filter = EventFilter {
+			event_types: vec!["send_packet".to_string()],
+			channel_id: Some("channel-1".to_string()),
+			min_sequence: Some(2),
+			max_sequence: Some(3),
+			..Default::default()
+		};
+		// The destination end of the packet is on channel-1
+		assert!(filter.matches(&send_packet(2, 0, vec![])));
+		assert!(filter.matches(&send_packet(3, 1, vec![])));
+		assert!(!filter.matches(&send_packet(2, 5, vec![])));
+		assert!(!filter.matches(&send_packet(1, 0, vec![])));
+		assert!(!filter.matches(&send_packet(4, 0, vec![])));
+
+		let RawIbcEvent::SendPacket(SendPacket { height, packet }) = send_packet(2, 0, vec![])
+		else {
+			unreachable!()
+		};
+		assert!(!filter.matches(&RawIbcEvent::ReceivePacket(ReceivePacket { height, packet })));
+	}
+
+	#[test]
+	fn pages_resume_from_the_returned_key() {
+		let filter = EventFilter::default();
+		let first = page(&filter, PageRequest::default(), 2);
+		assert_eq!(first.events.iter().map(sequence).collect::<Vec<_>>(), vec![1, 2]);
+		let next_key = first.pagination.unwrap().next_key;
+		assert_eq!(next_key, EventCursor { block_number: 11, index: 0 }.encode());
+
+		let second = page(&filter, PageRequest { key: next_key, ..Default::default() }, 2);
+		assert_eq!(second.events.iter().map(sequence).collect::<Vec<_>>(), vec![3, 4]);
+		assert_eq!(second.events[0].block_number, 11);
+		let next_key = second.pagination.unwrap().next_key;
+
+		let last = page(&filter, PageRequest { key: next_key, ..Default::default() }, 2);
+		assert_eq!(last.events.iter().map(sequence).collect::<Vec<_>>(), vec![5]);
+		assert!(last.pagination.unwrap().next_key.is_empty());
+	}
+
+	#[test]
+	fn first_page_honours_offset_and_counts_total() {
+		let filter = EventFilter { min_sequence: Some(2), ..Default::default() };
+		let pagination = PageRequest { offset: 1, count_total: true, ..Default::default() };
+		let first = page(&filter, pagination, 2);
+		assert_eq!(first.events.iter().map(sequence).collect::<Vec<_>>(), vec![3, 4]);
+		let response = first.pagination.unwrap();
+		assert_eq!(response.total, 4);
+		assert_eq!(response.next_key, EventCursor { block_number: 12, index: 0 }.encode());
+
+		// The offset and total only apply to the first page
+		let pagination = PageRequest {
+			key: response.next_key,
+			offset: 1,
+			count_total: true,
+			..Default::default()
+		};
+		let last = page(&filter, pagination, 2);
+		assert_eq!(last.events.iter().map(sequence).collect::<Vec<_>>(), vec![5]);
+		assert_eq!(last.pagination.unwrap().total, 0);
+	}
+
+	#[test]
+	fn reverse_pages_walk_back_from_the_key() {
+		let filter = EventFilter::default();
+		let first = page(&filter, PageRequest { reverse: true, ..Default::default() }, 2);
+		assert_eq!(first.events.iter().map(sequence).collect::<Vec<_>>(), vec![5, 4]);
+		let next_key = first.pagination.unwrap().next_key;
+		assert_eq!(next_key, EventCursor { block_number: 11, index: 0 }.encode());
+
+		let second =
+			page(&filter, PageRequest { key: next_key, reverse: true, ..Default::default() }, 2);
+		assert_eq!(second.events.iter().map(sequence).collect::<Vec<_>>(), vec![3, 2]);
+	}
+
+	#[test]
+	fn invalid_page_key_is_rejected() {
+		assert!(EventPage::new(&PageRequest { key: vec![1], ..Default::default() }, 2).is_err());
+	}
+}
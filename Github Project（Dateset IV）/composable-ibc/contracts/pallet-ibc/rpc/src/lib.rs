@@ -11,9 +11,12 @@ use ibc::{
 	},
 	events::IbcEvent as RawIbcEvent,
 };
-use ibc_primitives::PacketInfo as RawPacketInfo;
+use ibc_primitives::{PacketInfo as RawPacketInfo, DEFAULT_PAGE_LIMIT, MAX_PAGE_LIMIT};
 use ibc_proto::{
-	cosmos::base::{query::v1beta1::PageResponse, v1beta1::Coin},
+	cosmos::base::{
+		query::v1beta1::{PageRequest, PageResponse},
+		v1beta1::Coin,
+	},
 	ibc::{
 		applications::transfer::v1::{QueryDenomTraceResponse, QueryDenomTracesResponse},
 		core::{
@@ -22,9 +25,10 @@ use ibc_proto::{
 				QueryNextSequenceReceiveResponse, QueryPacketAcknowledgementResponse,
 				QueryPacketAcknowledgementsResponse, QueryPacketCommitmentResponse,
 				QueryPacketCommitmentsResponse, QueryPacketReceiptResponse,
+				QueryUpgradeErrorResponse, QueryUpgradeResponse,
 			},
 			client::v1::{
-				Height, IdentifiedClientState, QueryClientStateResponse,
+				Height, IdentifiedClientState, QueryClientStateResponse, QueryClientStatesResponse,
 				QueryConsensusStateResponse,
 			},
 			connection::v1::{
@@ -47,17 +51,20 @@ use pallet_ibc::{
 use sc_chain_spec::Properties;
 use sc_client_api::{BlockBackend, ProofProvider};
 use serde::{Deserialize, Serialize};
-use sp_api::ProvideRuntimeApi;
+use sp_api::{ApiError, ApiExt, ProvideRuntimeApi};
 use sp_blockchain::HeaderBackend;
 use sp_core::{blake2_256, storage::ChildInfo};
 use sp_runtime::{
 	generic::{BlockId, SignedBlock},
-	traits::{Block as BlockT, Header as HeaderT},
+	traits::{Block as BlockT, Header as HeaderT, UniqueSaturatedInto},
 };
 use std::{collections::HashMap, fmt::Display, str::FromStr, sync::Arc};
 use tendermint_proto::Protobuf;
 pub mod events;
-use events::filter_map_pallet_event;
+use events::{
+	filter_map_pallet_event, EventCursor, EventFilter, EventPage, IbcEventWithHeight,
+	DEFAULT_EVENTS_PAGE_LIMIT, MAX_EVENTS_BLOCK_RANGE, MAX_EVENTS_PAGE_LIMIT,
+};
 use ibc_proto::ibc::core::channel::v1::IdentifiedChannel;
 use pallet_ibc::errors::IbcError;
 
@@ -160,6 +167,90 @@ impl TryFrom<RawPacketInfo> for PacketInfo {
 	}
 }
 
+/// Escrowed amount for a port, channel and base denom
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EscrowedAmount {
+	/// Port id
+	pub port_id: String,
+	/// Channel id
+	pub channel_id: String,
+	/// Base denom
+	pub denom: String,
+	/// Amount tracked by the pallet as outstanding on the counterparty chain
+	pub tracked: String,
+	/// Balance held by the channel escrow account
+	pub balance: String,
+	/// True if the escrow account holds at least the tracked amount
+	pub holds: bool,
+}
+
+/// Escrow accounting for all channels
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct EscrowInvariant {
+	/// Escrowed amounts per (port, channel, base denom)
+	pub escrows: Vec<EscrowedAmount>,
+	/// Height at which the escrows were queried
+	pub height: Height,
+}
+
+/// Where the tokens of a received transfer were forwarded
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ForwardDestination {
+	/// Forwarded over ibc in the packet with this sequence
+	Ibc { port_id: String, channel_id: String, sequence: u64 },
+	/// Forwarded over xcm to the parachain, or to the relay chain if `para_id` is empty.
+	/// `receiver` is 0x prefixed hex.
+	Xcm { para_id: Option<u32>, receiver: String },
+}
+
+/// Status of a forward attempt
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ForwardStatus {
+	Pending,
+	Succeeded,
+	Failed,
+	TimedOut,
+	Refunded,
+}
+
+impl From<ibc_primitives::ForwardStatus> for ForwardStatus {
+	fn from(status: ibc_primitives::ForwardStatus) -> Self {
+		match status {
+			ibc_primitives::ForwardStatus::Pending => Self::Pending,
+			ibc_primitives::ForwardStatus::Succeeded => Self::Succeeded,
+			ibc_primitives::ForwardStatus::Failed => Self::Failed,
+			ibc_primitives::ForwardStatus::TimedOut => Self::TimedOut,
+			ibc_primitives::ForwardStatus::Refunded => Self::Refunded,
+		}
+	}
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ForwardAttempt {
+	pub destination: ForwardDestination,
+	pub status: ForwardStatus,
+}
+
+/// Forward attempts made for a received transfer
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ForwardAttempts {
+	/// Attempts in the order they were made, a timed out attempt is followed by its retry
+	pub attempts: Vec<ForwardAttempt>,
+	/// Height at which the attempts were queried
+	pub height: Height,
+}
+
+/// A page of filtered ibc events
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct PaginatedEvents {
+	/// Events matching the filter, in block order
+	pub events: Vec<IbcEventWithHeight>,
+	/// Pagination response, `next_key` is empty when there are no more events
+	pub pagination: Option<PageResponse>,
+}
+
 /// IBC RPC methods.
 #[rpc(client, server)]
 pub trait IbcApi<BlockNumber, Hash, AssetId>
@@ -168,6 +259,7 @@ where
 	AssetId: codec::Codec,
 {
 	/// Query packet data
+	/// At most [`MAX_PAGE_LIMIT`] sequences can be queried at once, larger requests are rejected.
 	#[method(name = "ibc_querySendPackets")]
 	fn query_send_packets(
 		&self,
@@ -176,6 +268,7 @@ where
 		seqs: Vec<u64>,
 	) -> Result<Vec<PacketInfo>>;
 	/// Query Recv Packet
+	/// At most [`MAX_PAGE_LIMIT`] sequences can be queried at once, larger requests are rejected.
 	#[method(name = "ibc_queryRecvPackets")]
 	fn query_recv_packets(
 		&self,
@@ -238,9 +331,11 @@ where
 	#[method(name = "ibc_queryUpgradedConnectionState")]
 	fn query_upgraded_cons_state(&self, height: u32) -> Result<QueryConsensusStateResponse>;
 
-	/// Query all client states
+	/// Query a page of client states.
+	/// The `next_key` of the returned `PageResponse` should be passed back as the `key` of the
+	/// next request, it is empty on the last page.
 	#[method(name = "ibc_queryClients")]
-	fn query_clients(&self) -> Result<Vec<IdentifiedClientState>>;
+	fn query_clients(&self, pagination: Option<PageRequest>) -> Result<QueryClientStatesResponse>;
 
 	/// Query a connection state
 	#[method(name = "ibc_queryConnection")]
@@ -250,9 +345,12 @@ where
 		connection_id: String,
 	) -> Result<QueryConnectionResponse>;
 
-	/// Query all connection states
+	/// Query a page of connection states
 	#[method(name = "ibc_queryConnections")]
-	fn query_connections(&self) -> Result<QueryConnectionsResponse>;
+	fn query_connections(
+		&self,
+		pagination: Option<PageRequest>,
+	) -> Result<QueryConnectionsResponse>;
 
 	/// Query all connection states for associated client
 	#[method(name = "ibc_queryConnectionUsingClient")]
@@ -280,6 +378,24 @@ where
 		port_id: String,
 	) -> Result<QueryChannelResponse>;
 
+	/// Query the upgrade proposed by a channel
+	#[method(name = "ibc_queryChannelUpgrade")]
+	fn query_channel_upgrade(
+		&self,
+		height: u32,
+		channel_id: String,
+		port_id: String,
+	) -> Result<QueryUpgradeResponse>;
+
+	/// Query the error receipt of the last aborted upgrade of a channel
+	#[method(name = "ibc_queryChannelUpgradeError")]
+	fn query_channel_upgrade_error(
+		&self,
+		height: u32,
+		channel_id: String,
+		port_id: String,
+	) -> Result<QueryUpgradeErrorResponse>;
+
 	/// Query client state for channel and port id
 	#[method(name = "ibc_queryChannelClient")]
 	fn query_channel_client(
@@ -297,26 +413,28 @@ where
 		connection_id: String,
 	) -> Result<QueryChannelsResponse>;
 
-	/// Query all channel states
+	/// Query a page of channel states
 	#[method(name = "ibc_queryChannels")]
-	fn query_channels(&self) -> Result<QueryChannelsResponse>;
+	fn query_channels(&self, pagination: Option<PageRequest>) -> Result<QueryChannelsResponse>;
 
-	/// Query packet commitments
+	/// Query a page of packet commitments
 	#[method(name = "ibc_queryPacketCommitments")]
 	fn query_packet_commitments(
 		&self,
 		height: u32,
 		channel_id: String,
 		port_id: String,
+		pagination: Option<PageRequest>,
 	) -> Result<QueryPacketCommitmentsResponse>;
 
-	/// Query packet acknowledgements
+	/// Query a page of packet acknowledgements
 	#[method(name = "ibc_queryPacketAcknowledgements")]
 	fn query_packet_acknowledgements(
 		&self,
 		height: u32,
 		channel_id: String,
 		port_id: String,
+		pagination: Option<PageRequest>,
 	) -> Result<QueryPacketAcknowledgementsResponse>;
 
 	/// Given a list of counterparty packet commitments, the querier checks if the packet
@@ -449,6 +567,38 @@ where
 		&self,
 		block_numbers: Vec<BlockNumberOrHash<Hash>>,
 	) -> Result<HashMap<String, Vec<RawIbcEvent>>>;
+
+	/// Query Ibc Events deposited in a block range, filtered by event type, port, channel,
+	/// packet sequence and ics20 sender/receiver.
+	/// Results are returned in pages, the `next_key` of the returned `PageResponse` should be
+	/// passed back as the `key` of the next request. Requests spanning more than
+	/// [`MAX_EVENTS_BLOCK_RANGE`] blocks are rejected.
+	#[method(name = "ibc_queryEventsPaginated")]
+	fn query_events_paginated(
+		&self,
+		filter: EventFilter,
+		pagination: Option<PageRequest>,
+	) -> Result<PaginatedEvents>;
+
+	/// Query the escrowed balances per (channel, base denom) alongside the amounts tracked by the
+	/// pallet as outstanding vouchers on the counterparty chain.
+	/// An entry with `holds` set to false means the escrow account was drained below what can
+	/// still be redeemed.
+	#[method(name = "ibc_escrowInvariant")]
+	fn query_escrow_invariant(&self, height: Option<u32>) -> Result<EscrowInvariant>;
+
+	/// Query the attempts made to forward the tokens of the transfer received in the packet with
+	/// sequence `seq` on `port_id` and `channel_id`, as requested by its memo.
+	/// Follow `ibc` destinations on the next chain to trace multihop transfers end to end. The
+	/// attempts are kept for a number of blocks after the received packet is acknowledged.
+	#[method(name = "ibc_queryForwardStatus")]
+	fn query_forward_status(
+		&self,
+		port_id: String,
+		channel_id: String,
+		seq: u64,
+		height: Option<u32>,
+	) -> Result<ForwardAttempts>;
 }
 
 /// Converts a runtime trap into an RPC error.
@@ -460,6 +610,49 @@ fn runtime_error_into_rpc_error(e: impl std::fmt::Display) -> RpcError {
 	)))
 }
 
+/// Converts the pagination of a list query into the one understood by the runtime api.
+/// Pages are resumed from the trie key of their first item, which can only be iterated in
+/// ascending order, so offsets, totals and `reverse` are rejected.
+fn into_runtime_page_request(
+	pagination: Option<PageRequest>,
+) -> Result<ibc_primitives::PageRequest> {
+	let pagination = pagination.unwrap_or_default();
+	if pagination.reverse {
+		return Err(runtime_error_into_rpc_error("Reverse pagination is not supported"))
+	}
+	if pagination.offset != 0 || pagination.count_total {
+		return Err(runtime_error_into_rpc_error(
+			"Only key based pagination is supported, offset and count_total must not be set",
+		))
+	}
+	let limit = if pagination.limit == 0 { DEFAULT_PAGE_LIMIT as u64 } else { pagination.limit };
+	if limit > MAX_PAGE_LIMIT as u64 {
+		return Err(runtime_error_into_rpc_error(format!(
+			"Page limit {limit} exceeds maximum of {MAX_PAGE_LIMIT}"
+		)))
+	}
+	Ok(ibc_primitives::PageRequest {
+		key: (!pagination.key.is_empty()).then_some(pagination.key),
+		limit: limit as u32,
+	})
+}
+
+/// Rejects packet queries for more sequences than fit in a page instead of returning a partial
+/// response.
+fn ensure_sequences_bounded(seqs: &[u64]) -> Result<()> {
+	if seqs.len() > MAX_PAGE_LIMIT as usize {
+		return Err(runtime_error_into_rpc_error(format!(
+			"Cannot query {} packets at once, the maximum is {MAX_PAGE_LIMIT}",
+			seqs.len()
+		)))
+	}
+	Ok(())
+}
+
+fn from_runtime_page_response(pagination: ibc_primitives::PageResponse) -> PageResponse {
+	PageResponse { next_key: pagination.next_key.unwrap_or_default(), total: 0 }
+}
+
 /// An implementation of IBC specific RPC methods.
 pub struct IbcRpcHandler<C, B, AssetId> {
 	client: Arc<C>,
@@ -475,6 +668,48 @@ impl<C, B, AssetId> IbcRpcHandler<C, B, AssetId> {
 	}
 }
 
+impl<C, Block, AssetId> IbcRpcHandler<C, Block, AssetId>
+where
+	Block: BlockT,
+	C: ProvideRuntimeApi<Block>,
+	C::Api: IbcRuntimeApi<Block, AssetId>,
+	AssetId: codec::Codec,
+{
+	/// Runs the paginated version of a list query if the runtime at `at` provides it, which it
+	/// does from version 2 of the runtime api. Older runtimes only provide the unpaginated query,
+	/// which returns all the items at once, so a page can't be resumed from a key on them.
+	fn query_page<R>(
+		&self,
+		at: Block::Hash,
+		pagination: Option<PageRequest>,
+		paginated: impl FnOnce(
+			ibc_primitives::PageRequest,
+		) -> std::result::Result<
+			Option<(R, ibc_primitives::PageResponse)>,
+			ApiError,
+		>,
+		unpaginated: impl FnOnce() -> std::result::Result<Option<R>, ApiError>,
+	) -> Result<Option<(R, Option<PageResponse>)>> {
+		let pagination = into_runtime_page_request(pagination)?;
+		let api_version = self
+			.client
+			.runtime_api()
+			.api_version::<dyn IbcRuntimeApi<Block, AssetId>>(at)
+			.map_err(runtime_error_into_rpc_error)?
+			.unwrap_or_default();
+		if api_version >= 2 {
+			return Ok(paginated(pagination)
+				.ok()
+				.flatten()
+				.map(|(result, pagination)| (result, Some(from_runtime_page_response(pagination)))))
+		}
+		if pagination.key.is_some() {
+			return Err(runtime_error_into_rpc_error("Runtime does not support pagination"))
+		}
+		Ok(unpaginated().ok().flatten().map(|result| (result, None)))
+	}
+}
+
 impl<C, Block, AssetId>
 	IbcApiServer<<<Block as BlockT>::Header as HeaderT>::Number, Block::Hash, AssetId>
 	for IbcRpcHandler<C, Block, AssetId>
@@ -496,6 +731,7 @@ where
 		port_id: String,
 		seqs: Vec<u64>,
 	) -> Result<Vec<PacketInfo>> {
+		ensure_sequences_bounded(&seqs)?;
 		let api = self.client.runtime_api();
 		let packets: Vec<ibc_primitives::PacketInfo> = api
 			.query_send_packet_info(
@@ -553,6 +789,7 @@ where
 		port_id: String,
 		seqs: Vec<u64>,
 	) -> Result<Vec<PacketInfo>> {
+		ensure_sequences_bounded(&seqs)?;
 		let api = self.client.runtime_api();
 		let at = self.client.info().best_hash;
 		let packets: Vec<ibc_primitives::PacketInfo> = api
@@ -804,28 +1041,44 @@ where
 		Err(runtime_error_into_rpc_error("Unimplemented"))
 	}
 
-	fn query_clients(&self) -> Result<Vec<IdentifiedClientState>> {
+	fn query_clients(&self, pagination: Option<PageRequest>) -> Result<QueryClientStatesResponse> {
 		let api = self.client.runtime_api();
+		let at = self.client.info().best_hash;
 
-		let client_states: Option<Vec<(Vec<u8>, Vec<u8>)>> =
-			api.clients(self.client.info().best_hash).ok().flatten();
-		match client_states {
-			Some(client_states) => client_states
-				.into_iter()
-				.map(|(client_id, client_state)| {
-					let client_state = AnyClientState::decode_vec(&client_state).map_err(|_| {
-						runtime_error_into_rpc_error("Failed to decode client state")
-					})?;
-					Ok(IdentifiedClientState {
-						client_id: String::from_utf8(client_id).map_err(|_| {
-							runtime_error_into_rpc_error("Failed to decode client id")
-						})?,
-						client_state: Some(client_state.into()),
-					})
+		let (result, pagination) = self
+			.query_page(
+				at,
+				pagination,
+				|pagination| api.clients_paginated(at, pagination),
+				|| {
+					let clients = api.clients(at)?.map(|clients| {
+						clients
+							.into_iter()
+							.map(|(client_id, client_state)| {
+								ibc_primitives::IdentifiedClientState { client_id, client_state }
+							})
+							.collect::<Vec<_>>()
+					});
+					Ok(clients
+						.map(|clients| ibc_primitives::QueryClientsResponse { clients, height: 0 }))
+				},
+			)?
+			.ok_or_else(|| runtime_error_into_rpc_error("Failed to fetch client states"))?;
+		let client_states = result
+			.clients
+			.into_iter()
+			.map(|identified_client| {
+				let client_state = AnyClientState::decode_vec(&identified_client.client_state)
+					.map_err(|_| runtime_error_into_rpc_error("Failed to decode client state"))?;
+				Ok(IdentifiedClientState {
+					client_id: String::from_utf8(identified_client.client_id)
+						.map_err(|_| runtime_error_into_rpc_error("Failed to decode client id"))?,
+					client_state: Some(client_state.into()),
 				})
-				.collect(),
-			_ => Err(runtime_error_into_rpc_error("Failed to fetch client states")),
-		}
+			})
+			.collect::<Result<Vec<_>>>()?;
+
+		Ok(QueryClientStatesResponse { client_states, pagination })
 	}
 
 	fn query_connection(
@@ -874,7 +1127,10 @@ where
 		})
 	}
 
-	fn query_connections(&self) -> Result<QueryConnectionsResponse> {
+	fn query_connections(
+		&self,
+		pagination: Option<PageRequest>,
+	) -> Result<QueryConnectionsResponse> {
 		let api = self.client.runtime_api();
 
 		let at = BlockId::Hash(self.client.info().best_hash);
@@ -883,10 +1139,13 @@ where
 			.block_hash_from_id(&at)
 			.map_err(|_| RpcError::Custom("Unknown block".into()))?
 			.ok_or_else(|| RpcError::Custom("Unknown block".into()))?;
-		let result: ibc_primitives::QueryConnectionsResponse = api
-			.connections(hash_at)
-			.ok()
-			.flatten()
+		let (result, pagination): (ibc_primitives::QueryConnectionsResponse, _) = self
+			.query_page(
+				hash_at,
+				pagination,
+				|pagination| api.connections_paginated(hash_at, pagination),
+				|| api.connections(hash_at),
+			)?
 			.ok_or_else(|| runtime_error_into_rpc_error("Failed to fetch connections"))?;
 		let para_id = api
 			.para_id(hash_at)
@@ -914,7 +1173,7 @@ where
 			.collect::<Result<Vec<_>>>()?;
 		Ok(QueryConnectionsResponse {
 			connections,
-			pagination: None,
+			pagination,
 			height: Some(ibc_proto::ibc::core::client::v1::Height {
 				revision_number: para_id.into(),
 				revision_height: result.height,
@@ -1063,6 +1322,105 @@ where
 		})
 	}
 
+	fn query_channel_upgrade(
+		&self,
+		height: u32,
+		channel_id: String,
+		port_id: String,
+	) -> Result<QueryUpgradeResponse> {
+		let api = self.client.runtime_api();
+
+		let at = BlockId::Number(height.into());
+		let hash_at = self
+			.client
+			.block_hash_from_id(&at)
+			.map_err(|_| RpcError::Custom("Unknown block".into()))?
+			.ok_or_else(|| RpcError::Custom("Unknown block".into()))?;
+		let para_id = api
+			.para_id(hash_at)
+			.map_err(|_| runtime_error_into_rpc_error("Error getting para id"))?;
+		let result: ibc_primitives::QueryChannelUpgradeResponse = api
+			.channel_upgrade(hash_at, channel_id.as_bytes().to_vec(), port_id.as_bytes().to_vec())
+			.ok()
+			.flatten()
+			.ok_or_else(|| runtime_error_into_rpc_error("Failed to fetch channel upgrade"))?;
+		let upgrade = ibc::core::ics04_channel::upgrade::Upgrade::decode_vec(&result.upgrade)
+			.map_err(|_| runtime_error_into_rpc_error("Failed to decode channel upgrade"))?;
+		let mut keys = vec![result.trie_key];
+		let child_trie_key = api
+			.child_trie_key(hash_at)
+			.map_err(|_| runtime_error_into_rpc_error("Failed to get child trie key"))?;
+		let child_info = ChildInfo::new_default(&child_trie_key);
+		let proof = self
+			.client
+			.read_child_proof(hash_at, &child_info, &mut keys.iter_mut().map(|nodes| &nodes[..]))
+			.map_err(runtime_error_into_rpc_error)?
+			.iter_nodes()
+			.collect::<Vec<_>>()
+			.encode();
+		Ok(QueryUpgradeResponse {
+			upgrade: Some(upgrade.into()),
+			proof,
+			proof_height: Some(ibc_proto::ibc::core::client::v1::Height {
+				revision_number: para_id.into(),
+				revision_height: result.height,
+			}),
+		})
+	}
+
+	fn query_channel_upgrade_error(
+		&self,
+		height: u32,
+		channel_id: String,
+		port_id: String,
+	) -> Result<QueryUpgradeErrorResponse> {
+		let api = self.client.runtime_api();
+
+		let at = BlockId::Number(height.into());
+		let hash_at = self
+			.client
+			.block_hash_from_id(&at)
+			.map_err(|_| RpcError::Custom("Unknown block".into()))?
+			.ok_or_else(|| RpcError::Custom("Unknown block".into()))?;
+		let para_id = api
+			.para_id(hash_at)
+			.map_err(|_| runtime_error_into_rpc_error("Error getting para id"))?;
+		let result: ibc_primitives::QueryUpgradeErrorResponse = api
+			.channel_upgrade_error(
+				hash_at,
+				channel_id.as_bytes().to_vec(),
+				port_id.as_bytes().to_vec(),
+			)
+			.ok()
+			.flatten()
+			.ok_or_else(|| runtime_error_into_rpc_error("Failed to fetch upgrade error receipt"))?;
+		let error_receipt =
+			ibc::core::ics04_channel::upgrade::ErrorReceipt::decode_vec(&result.error_receipt)
+				.map_err(|_| {
+					runtime_error_into_rpc_error("Failed to decode upgrade error receipt")
+				})?;
+		let mut keys = vec![result.trie_key];
+		let child_trie_key = api
+			.child_trie_key(hash_at)
+			.map_err(|_| runtime_error_into_rpc_error("Failed to get child trie key"))?;
+		let child_info = ChildInfo::new_default(&child_trie_key);
+		let proof = self
+			.client
+			.read_child_proof(hash_at, &child_info, &mut keys.iter_mut().map(|nodes| &nodes[..]))
+			.map_err(runtime_error_into_rpc_error)?
+			.iter_nodes()
+			.collect::<Vec<_>>()
+			.encode();
+		Ok(QueryUpgradeErrorResponse {
+			error_receipt: Some(error_receipt.into()),
+			proof,
+			proof_height: Some(ibc_proto::ibc::core::client::v1::Height {
+				revision_number: para_id.into(),
+				revision_height: result.height,
+			}),
+		})
+	}
+
 	fn query_channel_client(
 		&self,
 		height: u32,
@@ -1149,7 +1507,7 @@ where
 		})
 	}
 
-	fn query_channels(&self) -> Result<QueryChannelsResponse> {
+	fn query_channels(&self, pagination: Option<PageRequest>) -> Result<QueryChannelsResponse> {
 		let api = self.client.runtime_api();
 		let at = BlockId::Hash(self.client.info().best_hash);
 		let hash_at = self
@@ -1160,10 +1518,13 @@ where
 		let para_id = api
 			.para_id(hash_at)
 			.map_err(|_| runtime_error_into_rpc_error("Error getting para id"))?;
-		let result: ibc_primitives::QueryChannelsResponse = api
-			.channels(hash_at)
-			.ok()
-			.flatten()
+		let (result, pagination): (ibc_primitives::QueryChannelsResponse, _) = self
+			.query_page(
+				hash_at,
+				pagination,
+				|pagination| api.channels_paginated(hash_at, pagination),
+				|| api.channels(hash_at),
+			)?
 			.ok_or_else(|| runtime_error_into_rpc_error("Failed to fetch channels"))?;
 		let channels = result
 			.channels
@@ -1191,7 +1552,7 @@ where
 
 		Ok(QueryChannelsResponse {
 			channels,
-			pagination: None,
+			pagination,
 			height: Some(ibc_proto::ibc::core::client::v1::Height {
 				revision_number: para_id.into(),
 				revision_height: result.height,
@@ -1204,6 +1565,7 @@ where
 		height: u32,
 		channel_id: String,
 		port_id: String,
+		pagination: Option<PageRequest>,
 	) -> Result<QueryPacketCommitmentsResponse> {
 		let api = self.client.runtime_api();
 
@@ -1216,14 +1578,21 @@ where
 		let para_id = api
 			.para_id(hash_at)
 			.map_err(|_| runtime_error_into_rpc_error("Error getting para id"))?;
-		let result: ibc_primitives::QueryPacketCommitmentsResponse = api
-			.packet_commitments(
+		let (channel_id, port_id) = (channel_id.as_bytes().to_vec(), port_id.as_bytes().to_vec());
+		let (result, pagination): (ibc_primitives::QueryPacketCommitmentsResponse, _) = self
+			.query_page(
 				hash_at,
-				channel_id.as_bytes().to_vec(),
-				port_id.as_bytes().to_vec(),
-			)
-			.ok()
-			.flatten()
+				pagination,
+				|pagination| {
+					api.packet_commitments_paginated(
+						hash_at,
+						channel_id.clone(),
+						port_id.clone(),
+						pagination,
+					)
+				},
+				|| api.packet_commitments(hash_at, channel_id.clone(), port_id.clone()),
+			)?
 			.ok_or_else(|| runtime_error_into_rpc_error("Failed to fetch commitments"))?;
 		let commitments = result
 			.commitments
@@ -1243,7 +1612,7 @@ where
 			.collect::<Result<Vec<_>>>()?;
 		Ok(QueryPacketCommitmentsResponse {
 			commitments,
-			pagination: None,
+			pagination,
 			height: Some(ibc_proto::ibc::core::client::v1::Height {
 				revision_number: para_id.into(),
 				revision_height: result.height,
@@ -1256,6 +1625,7 @@ where
 		height: u32,
 		channel_id: String,
 		port_id: String,
+		pagination: Option<PageRequest>,
 	) -> Result<QueryPacketAcknowledgementsResponse> {
 		let api = self.client.runtime_api();
 
@@ -1269,14 +1639,21 @@ where
 		let para_id = api
 			.para_id(hash_at)
 			.map_err(|_| runtime_error_into_rpc_error("Error getting para id"))?;
-		let result: ibc_primitives::QueryPacketAcknowledgementsResponse = api
-			.packet_acknowledgements(
+		let (channel_id, port_id) = (channel_id.as_bytes().to_vec(), port_id.as_bytes().to_vec());
+		let (result, pagination): (ibc_primitives::QueryPacketAcknowledgementsResponse, _) = self
+			.query_page(
 				hash_at,
-				channel_id.as_bytes().to_vec(),
-				port_id.as_bytes().to_vec(),
-			)
-			.ok()
-			.flatten()
+				pagination,
+				|pagination| {
+					api.packet_acknowledgements_paginated(
+						hash_at,
+						channel_id.clone(),
+						port_id.clone(),
+						pagination,
+					)
+				},
+				|| api.packet_acknowledgements(hash_at, channel_id.clone(), port_id.clone()),
+			)?
 			.ok_or_else(|| runtime_error_into_rpc_error("Failed to fetch acknowledgements"))?;
 		let acknowledgements = result
 			.acks
@@ -1296,7 +1673,7 @@ where
 			.collect::<Result<Vec<_>>>()?;
 		Ok(QueryPacketAcknowledgementsResponse {
 			acknowledgements,
-			pagination: None,
+			pagination,
 			height: Some(ibc_proto::ibc::core::client::v1::Height {
 				revision_number: para_id.into(),
 				revision_height: result.height,
@@ -1766,6 +2143,174 @@ where
 		}
 		Ok(events)
 	}
+
+	fn query_events_paginated(
+		&self,
+		filter: EventFilter,
+		pagination: Option<PageRequest>,
+	) -> Result<PaginatedEvents> {
+		let api = self.client.runtime_api();
+		let pagination = pagination.unwrap_or_default();
+		let limit =
+			if pagination.limit == 0 { DEFAULT_EVENTS_PAGE_LIMIT } else { pagination.limit };
+		if limit > MAX_EVENTS_PAGE_LIMIT {
+			return Err(runtime_error_into_rpc_error(format!(
+				"Page limit {limit} exceeds maximum of {MAX_EVENTS_PAGE_LIMIT}"
+			)))
+		}
+
+		let best_number: u32 = self.client.info().best_number.unique_saturated_into();
+		let to_block = filter.to_block.unwrap_or(best_number).min(best_number);
+		if filter.from_block > to_block {
+			return Ok(PaginatedEvents { events: vec![], pagination: None })
+		}
+		if to_block - filter.from_block >= MAX_EVENTS_BLOCK_RANGE {
+			return Err(runtime_error_into_rpc_error(format!(
+				"Block range {}..={} exceeds maximum of {MAX_EVENTS_BLOCK_RANGE} blocks",
+				filter.from_block, to_block
+			)))
+		}
+
+		let mut page = EventPage::new(&pagination, limit)
+			.map_err(|_| runtime_error_into_rpc_error("Invalid pagination key"))?;
+		let block_numbers: Box<dyn Iterator<Item = u32>> = if pagination.reverse {
+			Box::new((filter.from_block..=to_block).rev())
+		} else {
+			Box::new(filter.from_block..=to_block)
+		};
+
+		'blocks: for block_number in block_numbers {
+			if page.skips_block(block_number) {
+				continue
+			}
+			let hash_at = self
+				.client
+				.block_hash_from_id(&BlockId::Number(block_number.into()))
+				.map_err(|_| RpcError::Custom("Unknown block".into()))?
+				.ok_or_else(|| RpcError::Custom("Unknown block".into()))?;
+			let block_events = api.block_events(hash_at, None).map_err(|_| {
+				runtime_error_into_rpc_error("[ibc_rpc]: failed to read block events")
+			})?;
+			let mut block_events = block_events
+				.into_iter()
+				.filter_map(|event| {
+					filter_map_pallet_event::<C, Block, AssetId>(hash_at, &api, event.ok()?)
+				})
+				.enumerate()
+				.collect::<Vec<_>>();
+			if pagination.reverse {
+				block_events.reverse();
+			}
+
+			for (index, event) in block_events {
+				let position = EventCursor { block_number, index: index as u32 };
+				if !page.push(&filter, position, event) {
+					break 'blocks
+				}
+			}
+		}
+
+		Ok(page.finish())
+	}
+
+	fn query_escrow_invariant(&self, height: Option<u32>) -> Result<EscrowInvariant> {
+		let api = self.client.runtime_api();
+		let at = if let Some(height) = height {
+			BlockId::Number(height.into())
+		} else {
+			BlockId::Hash(self.client.info().best_hash)
+		};
+		let hash_at = self
+			.client
+			.block_hash_from_id(&at)
+			.map_err(|_| RpcError::Custom("Unknown block".into()))?
+			.ok_or_else(|| RpcError::Custom("Unknown block".into()))?;
+		let para_id = api
+			.para_id(hash_at)
+			.map_err(|_| runtime_error_into_rpc_error("Error getting para id"))?;
+		let result: ibc_primitives::QueryEscrowInvariantResponse = api
+			.escrow_invariant(hash_at)
+			.ok()
+			.flatten()
+			.ok_or_else(|| runtime_error_into_rpc_error("Failed to fetch escrowed amounts"))?;
+		let escrows = result
+			.escrows
+			.into_iter()
+			.map(|escrow| {
+				Ok(EscrowedAmount {
+					port_id: String::from_utf8(escrow.port_id)
+						.map_err(|_| runtime_error_into_rpc_error("Failed to decode port id"))?,
+					channel_id: String::from_utf8(escrow.channel_id)
+						.map_err(|_| runtime_error_into_rpc_error("Failed to decode channel id"))?,
+					denom: String::from_utf8(escrow.denom)
+						.map_err(|_| runtime_error_into_rpc_error("Failed to decode denom"))?,
+					tracked: escrow.tracked.to_string(),
+					balance: escrow.balance.to_string(),
+					holds: escrow.balance >= escrow.tracked,
+				})
+			})
+			.collect::<Result<Vec<_>>>()?;
+		Ok(EscrowInvariant {
+			escrows,
+			height: Height { revision_number: para_id.into(), revision_height: result.height },
+		})
+	}
+
+	fn query_forward_status(
+		&self,
+		port_id: String,
+		channel_id: String,
+		seq: u64,
+		height: Option<u32>,
+	) -> Result<ForwardAttempts> {
+		let api = self.client.runtime_api();
+		let at = if let Some(height) = height {
+			BlockId::Number(height.into())
+		} else {
+			BlockId::Hash(self.client.info().best_hash)
+		};
+		let hash_at = self
+			.client
+			.block_hash_from_id(&at)
+			.map_err(|_| RpcError::Custom("Unknown block".into()))?
+			.ok_or_else(|| RpcError::Custom("Unknown block".into()))?;
+		let para_id = api
+			.para_id(hash_at)
+			.map_err(|_| runtime_error_into_rpc_error("Error getting para id"))?;
+		let result: ibc_primitives::QueryForwardStatusResponse = api
+			.forward_status(hash_at, port_id.into_bytes(), channel_id.into_bytes(), seq)
+			.ok()
+			.flatten()
+			.ok_or_else(|| runtime_error_into_rpc_error("Failed to fetch forward attempts"))?;
+		let attempts = result
+			.attempts
+			.into_iter()
+			.map(|attempt| {
+				let destination = match attempt.destination {
+					ibc_primitives::ForwardDestination::Ibc { port_id, channel_id, sequence } =>
+						ForwardDestination::Ibc {
+							port_id: String::from_utf8(port_id).map_err(|_| {
+								runtime_error_into_rpc_error("Failed to decode port id")
+							})?,
+							channel_id: String::from_utf8(channel_id).map_err(|_| {
+								runtime_error_into_rpc_error("Failed to decode channel id")
+							})?,
+							sequence,
+						},
+					ibc_primitives::ForwardDestination::Xcm { para_id, receiver } =>
+						ForwardDestination::Xcm {
+							para_id,
+							receiver: sp_core::bytes::to_hex(&receiver, false),
+						},
+				};
+				Ok(ForwardAttempt { destination, status: attempt.status.into() })
+			})
+			.collect::<Result<Vec<_>>>()?;
+		Ok(ForwardAttempts {
+			attempts,
+			height: Height { revision_number: para_id.into(), revision_height: result.height },
+		})
+	}
 }
 
 impl<C, Block, AssetId> IbcRpcHandler<C, Block, AssetId>
@@ -7,6 +7,7 @@ use sp_std::vec::Vec;
 
 sp_api::decl_runtime_apis! {
 	/// IBC Runtime Apis
+	#[api_version(2)]
 	pub trait IbcRuntimeApi<AssetId: codec::Codec> {
 		/// Get Ibc child trie prefix
 		fn child_trie_key() -> Vec<u8>;
@@ -34,17 +35,29 @@ sp_api::decl_runtime_apis! {
 		/// Returns client states for all clients on chain
 		fn clients() -> Option<Vec<(Vec<u8>, Vec<u8>)>>;
 
+		/// Returns a page of the client states of all clients on chain
+		fn clients_paginated(pagination: PageRequest) -> Option<(QueryClientsResponse, PageResponse)>;
+
 		/// Query the given connection state with proof
 		fn connection(connection_id: Vec<u8>) -> Option<QueryConnectionResponse>;
 
 		/// Returns all connections registered on chain
 		fn connections() -> Option<QueryConnectionsResponse>;
 
+		/// Returns a page of the connections registered on chain
+		fn connections_paginated(pagination: PageRequest) -> Option<(QueryConnectionsResponse, PageResponse)>;
+
 		/// Returns all connections associated with the given client
 		fn connection_using_client(client_id: Vec<u8>) -> Option<Vec<IdentifiedConnection>>;
 
 		fn channel(channel_id: Vec<u8>, port_id: Vec<u8>) -> Option<QueryChannelResponse>;
 
+		/// Returns the upgrade proposed by the channel, if an upgrade is in progress
+		fn channel_upgrade(channel_id: Vec<u8>, port_id: Vec<u8>) -> Option<QueryChannelUpgradeResponse>;
+
+		/// Returns the error receipt of the last aborted upgrade of the channel
+		fn channel_upgrade_error(channel_id: Vec<u8>, port_id: Vec<u8>) -> Option<QueryUpgradeErrorResponse>;
+
 		/// Should return the client state for the client supporting this channel
 		fn channel_client(channel_id: Vec<u8>, port_id: Vec<u8>) -> Option<IdentifiedClientState>;
 
@@ -54,12 +67,21 @@ sp_api::decl_runtime_apis! {
 		/// Returns all channels registered on chain
 		fn channels() -> Option<QueryChannelsResponse>;
 
+		/// Returns a page of the channels registered on chain
+		fn channels_paginated(pagination: PageRequest) -> Option<(QueryChannelsResponse, PageResponse)>;
+
 		fn connection_handshake(client_id: Vec<u8>, connection_id: Vec<u8>) -> Option<ConnectionHandshake>;
 
 		fn packet_commitments(channel_id: Vec<u8>, port_id: Vec<u8>) -> Option<QueryPacketCommitmentsResponse>;
 
+		/// Returns a page of the packet commitments of the channel
+		fn packet_commitments_paginated(channel_id: Vec<u8>, port_id: Vec<u8>, pagination: PageRequest) -> Option<(QueryPacketCommitmentsResponse, PageResponse)>;
+
 		fn packet_acknowledgements(channel_id: Vec<u8>, port_id: Vec<u8>) -> Option<QueryPacketAcknowledgementsResponse>;
 
+		/// Returns a page of the packet acknowledgements of the channel
+		fn packet_acknowledgements_paginated(channel_id: Vec<u8>, port_id: Vec<u8>, pagination: PageRequest) -> Option<(QueryPacketAcknowledgementsResponse, PageResponse)>;
+
 		fn unreceived_packets(channel_id: Vec<u8>, port_id: Vec<u8>, seqs: Vec<u64>) -> Option<Vec<u64>>;
 
 		fn unreceived_acknowledgements(channel_id: Vec<u8>, port_id: Vec<u8>, seqs: Vec<u64>) -> Option<Vec<u64>>;
@@ -77,6 +99,12 @@ sp_api::decl_runtime_apis! {
 		/// Key is the asset id from which to start looking up results
 		fn denom_traces(key: Option<AssetId>, offset: Option<u32>, limit: u64, count_total: bool) -> QueryDenomTracesResponse;
 
+		/// Returns the tracked escrowed amount and escrow account balance for every channel and denom
+		fn escrow_invariant() -> Option<QueryEscrowInvariantResponse>;
+
+		/// Returns the attempts made to forward the tokens received in a packet as requested by its memo
+		fn forward_status(port_id: Vec<u8>, channel_id: Vec<u8>, seq: u64) -> Option<QueryForwardStatusResponse>;
+
 		fn block_events(extrinsic_index: Option<u32>) -> Vec<Result<pallet_ibc::events::IbcEvent, pallet_ibc::errors::IbcError>>;
 	}
 }
@@ -138,6 +138,7 @@ pub fn generate_finality_proof(
 				state_proof: state_proof.clone(),
 				extrinsic: timestamp_extrinsic.clone(),
 				extrinsic_proof: extrinsic_proof.clone(),
+				timestamp_storage_proof: vec![],
 			},
 		);
 	}
@@ -145,6 +146,7 @@ pub fn generate_finality_proof(
 	let grandpa_header = GrandpaHeader {
 		finality_proof,
 		parachain_headers,
+		solochain_headers: Default::default(),
 		height: Height::new(para_id.into(), parachain_header.number.into()),
 	};
 	let client_message = AnyClientMessage::Grandpa(ClientMessage::Header(grandpa_header));
@@ -158,6 +160,8 @@ pub fn generate_finality_proof(
 		para_id,
 		current_set_id: set_id,
 		current_authorities: authorities.into_iter().map(|authority| (authority, 100)).collect(),
+		chain_type: Default::default(),
+		timestamp_proof_type: Default::default(),
 		_phantom: Default::default(),
 	};
 
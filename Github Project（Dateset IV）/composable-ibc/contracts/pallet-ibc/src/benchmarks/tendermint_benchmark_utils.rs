@@ -297,6 +297,7 @@ pub(crate) fn create_mock_beefy_client_state(
 		para_id: 2087,
 		authority: Default::default(),
 		next_authority_set: Default::default(),
+		timestamp_proof_type: Default::default(),
 		_phantom: Default::default(),
 	};
 
@@ -319,6 +320,8 @@ pub(crate) fn create_mock_grandpa_client_state() -> (
 		para_id: 2087,
 		current_set_id: 0,
 		current_authorities: vec![],
+		chain_type: Default::default(),
+		timestamp_proof_type: Default::default(),
 		_phantom: Default::default(),
 	};
 
@@ -7,9 +7,11 @@ use sp_core::crypto::AccountId32;
 
 use crate::{
 	ics23::{
-		acknowledgements::Acknowledgements, channels::Channels, next_seq_ack::NextSequenceAck,
+		acknowledgements::Acknowledgements, channel_upgrades::ChannelUpgrades, channels::Channels,
+		counterparty_upgrades::CounterpartyChannelUpgrades, next_seq_ack::NextSequenceAck,
 		next_seq_recv::NextSequenceRecv, next_seq_send::NextSequenceSend,
 		packet_commitments::PacketCommitment, receipts::PacketReceipt,
+		upgrade_errors::UpgradeErrors,
 	},
 	impls::host_height,
 	routing::Context,
@@ -24,6 +26,7 @@ use ibc::{
 			error::Error as ICS04Error,
 			msgs::acknowledgement::Acknowledgement,
 			packet::{Receipt, Sequence},
+			upgrade::{ErrorReceipt, Upgrade},
 		},
 		ics24_host::identifier::{ChannelId, ClientId, ConnectionId, PortId},
 	},
@@ -161,6 +164,7 @@ where
 			})?;
 			let data = match data.as_ref() {
 				"Ok" => Receipt::Ok,
+				"Timeout" => Receipt::Timeout,
 				_ => return Err(ICS04Error::packet_receipt_not_found(seq.into())),
 			};
 			log::trace!(target: "pallet_ibc", "in channel : [get_packet_receipt] >> packet_receipt = {:?}", data);
@@ -253,10 +257,66 @@ where
 		Ok(count.into())
 	}
 
+	fn channel_upgrade(
+		&self,
+		port_channel_id: &(PortId, ChannelId),
+	) -> Result<Upgrade, ICS04Error> {
+		let data = <ChannelUpgrades<T>>::get(port_channel_id.0.clone(), port_channel_id.1)
+			.ok_or_else(|| {
+				ICS04Error::missing_upgrade(port_channel_id.0.clone(), port_channel_id.1)
+			})?;
+		Upgrade::decode_vec(&data).map_err(|_| {
+			ICS04Error::implementation_specific("failed to decode channel upgrade".to_string())
+		})
+	}
+
+	fn counterparty_channel_upgrade(
+		&self,
+		port_channel_id: &(PortId, ChannelId),
+	) -> Result<Upgrade, ICS04Error> {
+		let data =
+			<CounterpartyChannelUpgrades<T>>::get(port_channel_id.0.clone(), port_channel_id.1)
+				.ok_or_else(|| {
+					ICS04Error::missing_upgrade(port_channel_id.0.clone(), port_channel_id.1)
+				})?;
+		Upgrade::decode_vec(&data).map_err(|_| {
+			ICS04Error::implementation_specific(
+				"failed to decode counterparty channel upgrade".to_string(),
+			)
+		})
+	}
+
+	fn upgrade_error_receipt(
+		&self,
+		port_channel_id: &(PortId, ChannelId),
+	) -> Result<ErrorReceipt, ICS04Error> {
+		let data = <UpgradeErrors<T>>::get(port_channel_id.0.clone(), port_channel_id.1)
+			.ok_or_else(ICS04Error::missing_error_receipt)?;
+		ErrorReceipt::decode_vec(&data).map_err(|_| {
+			ICS04Error::implementation_specific(
+				"failed to decode upgrade error receipt".to_string(),
+			)
+		})
+	}
+
+	fn has_inflight_packets(
+		&self,
+		port_channel_id: &(PortId, ChannelId),
+	) -> Result<bool, ICS04Error> {
+		Ok(<PacketCommitment<T>>::contains_channel_commitments(
+			port_channel_id.0.clone(),
+			port_channel_id.1,
+		))
+	}
+
 	fn max_expected_time_per_block(&self) -> Duration {
 		let expected = T::ExpectedBlockTime::get();
 		Duration::from_millis(expected)
 	}
+
+	fn is_upgrade_authority(&self) -> bool {
+		self.upgrade_authority
+	}
 }
 
 impl<T: Config + Sync + Send> ChannelKeeper for Context<T>
@@ -340,6 +400,7 @@ where
 	) -> Result<(), ICS04Error> {
 		let receipt = match receipt {
 			Receipt::Ok => b"Ok".to_vec(),
+			Receipt::Timeout => b"Timeout".to_vec(),
 		};
 
 		<PacketReceipt<T>>::insert((key.0.clone(), key.1, key.2), receipt);
@@ -389,6 +450,29 @@ where
 		Ok(())
 	}
 
+	fn store_pending_acknowledgement(
+		&mut self,
+		key: (PortId, ChannelId, Sequence),
+	) -> Result<(), ICS04Error> {
+		<PendingAcknowledgements<T>>::insert(
+			(key.0.as_bytes().to_vec(), key.1.to_string().into_bytes()),
+			u64::from(key.2),
+			<frame_system::Pallet<T>>::block_number(),
+		);
+		Ok(())
+	}
+
+	fn delete_pending_acknowledgement(
+		&mut self,
+		key: (PortId, ChannelId, Sequence),
+	) -> Result<(), ICS04Error> {
+		<PendingAcknowledgements<T>>::remove(
+			(key.0.as_bytes().to_vec(), key.1.to_string().into_bytes()),
+			u64::from(key.2),
+		);
+		Ok(())
+	}
+
 	fn store_connection_channels(
 		&mut self,
 		conn_id: ConnectionId,
@@ -463,6 +547,49 @@ where
 		Ok(())
 	}
 
+	fn store_channel_upgrade(
+		&mut self,
+		port_channel_id: (PortId, ChannelId),
+		upgrade: Upgrade,
+	) -> Result<(), ICS04Error> {
+		<ChannelUpgrades<T>>::insert(port_channel_id.0, port_channel_id.1, &upgrade);
+		Ok(())
+	}
+
+	fn delete_channel_upgrade(
+		&mut self,
+		port_channel_id: (PortId, ChannelId),
+	) -> Result<(), ICS04Error> {
+		<ChannelUpgrades<T>>::remove(port_channel_id.0, port_channel_id.1);
+		Ok(())
+	}
+
+	fn store_counterparty_channel_upgrade(
+		&mut self,
+		port_channel_id: (PortId, ChannelId),
+		upgrade: Upgrade,
+	) -> Result<(), ICS04Error> {
+		<CounterpartyChannelUpgrades<T>>::insert(port_channel_id.0, port_channel_id.1, &upgrade);
+		Ok(())
+	}
+
+	fn delete_counterparty_channel_upgrade(
+		&mut self,
+		port_channel_id: (PortId, ChannelId),
+	) -> Result<(), ICS04Error> {
+		<CounterpartyChannelUpgrades<T>>::remove(port_channel_id.0, port_channel_id.1);
+		Ok(())
+	}
+
+	fn store_upgrade_error_receipt(
+		&mut self,
+		port_channel_id: (PortId, ChannelId),
+		error_receipt: ErrorReceipt,
+	) -> Result<(), ICS04Error> {
+		<UpgradeErrors<T>>::insert(port_channel_id.0, port_channel_id.1, &error_receipt);
+		Ok(())
+	}
+
 	/// Called upon channel identifier creation (Init or Try message processing).
 	/// Increases the counter which keeps track of how many channels have been created.
 	/// Should never fail.
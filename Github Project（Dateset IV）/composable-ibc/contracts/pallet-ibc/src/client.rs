@@ -263,35 +263,39 @@ where
 		};
 
 		// now this header can be trusted
-		let consensus_state = match <T as Config>::LightClientProtocol::get() {
-			crate::LightClientProtocol::Beefy => {
-				let cs_state = ics11_beefy::consensus_state::ConsensusState {
+		// The counterparty may track this chain with either finality protocol, the client state
+		// it holds for this chain tells which one, the configured protocol is only a fallback.
+		let protocol = match client_state.unpack_recursive() {
+			AnyClientState::Beefy(_) => crate::LightClientProtocol::Beefy,
+			AnyClientState::Grandpa(_) => crate::LightClientProtocol::Grandpa,
+			_ => <T as Config>::LightClientProtocol::get(),
+		};
+		let root = header.state_root().as_ref().to_vec().into();
+		let cs = match protocol {
+			crate::LightClientProtocol::Beefy =>
+				AnyConsensusState::Beefy(ics11_beefy::consensus_state::ConsensusState {
 					timestamp,
-					root: header.state_root().as_ref().to_vec().into(),
-				};
-				AnyConsensusState::Beefy(cs_state)
-			},
-			crate::LightClientProtocol::Grandpa => {
-				let cs_state = ics10_grandpa::consensus_state::ConsensusState {
+					root,
+				}),
+			crate::LightClientProtocol::Grandpa =>
+				AnyConsensusState::Grandpa(ics10_grandpa::consensus_state::ConsensusState {
 					timestamp,
-					root: header.state_root().as_ref().to_vec().into(),
-				};
-				let cs = AnyConsensusState::Grandpa(cs_state);
-
-				match &client_state {
-					AnyClientState::Wasm(_wasm) => {
-						log::trace!(target: "pallet_ibc", "in client : [host_consensus_state] >> using wasm code id" );
-						AnyConsensusState::wasm(cs).map_err(ICS02Error::encode)?
-					},
-					_ =>
-						if connection_proof.checksum.is_some() {
-							log::trace!(target: "pallet_ibc", "in client : [host_consensus_state] >> using wasm code id");
-							AnyConsensusState::wasm(cs).map_err(ICS02Error::encode)?
-						} else {
-							cs
-						},
-				}
+					root,
+				}),
+		};
+
+		let consensus_state = match &client_state {
+			AnyClientState::Wasm(_wasm) => {
+				log::trace!(target: "pallet_ibc", "in client : [host_consensus_state] >> using wasm code id" );
+				AnyConsensusState::wasm(cs).map_err(ICS02Error::encode)?
 			},
+			_ =>
+				if connection_proof.checksum.is_some() {
+					log::trace!(target: "pallet_ibc", "in client : [host_consensus_state] >> using wasm code id");
+					AnyConsensusState::wasm(cs).map_err(ICS02Error::encode)?
+				} else {
+					cs
+				},
 		};
 		Ok(consensus_state)
 	}
@@ -424,14 +428,22 @@ where
 					Err(ICS02Error::implementation_specific(format!("client state is frozen")))?
 				}
 
-				(client_state.relay_chain, client_state.para_id, client_state.latest_para_height)
+				(
+					client_state.relay_chain.clone(),
+					client_state.para_id,
+					client_state.latest_para_height,
+				)
 			},
 			AnyClientState::Grandpa(client_state) => {
 				if client_state.frozen_height.is_some() {
 					Err(ICS02Error::implementation_specific(format!("client state is frozen")))?
 				}
 
-				(client_state.relay_chain, client_state.para_id, client_state.latest_para_height)
+				(
+					client_state.relay_chain.clone(),
+					client_state.para_id,
+					client_state.latest_para_height,
+				)
 			},
 			client => Err(ICS02Error::unknown_client_type(format!("{}", client.client_type())))?,
 		};
@@ -11,7 +11,7 @@ use ibc::{
 		ics24_host::identifier::{ChannelId, ClientId, ConnectionId, PortId},
 		ics26_routing::{context::ModuleId, error::Error as RoutingError},
 	},
-	events::{IbcEvent as RawIbcEvent, ModuleEvent},
+	events::{IbcEvent as RawIbcEvent, IbcEventType, ModuleEvent},
 	timestamp::Timestamp,
 	Height,
 };
@@ -219,6 +219,18 @@ pub enum IbcEvent {
 	AppModule { kind: Vec<u8>, module_id: Vec<u8> },
 	/// Push WASM Code
 	PushWasmCode { wasm_checksum: Checksum },
+	/// Channel upgrade handshake step, `kind` is the ibc event type (e.g.
+	/// `channel_upgrade_try`)
+	ChannelUpgrade {
+		kind: Vec<u8>,
+		revision_height: u64,
+		revision_number: u64,
+		port_id: Vec<u8>,
+		channel_id: Vec<u8>,
+		counterparty_port_id: Vec<u8>,
+		counterparty_channel_id: Option<Vec<u8>>,
+		upgrade_sequence: u64,
+	},
 }
 
 impl From<RawIbcEvent> for IbcEvent {
@@ -432,6 +444,30 @@ impl From<RawIbcEvent> for IbcEvent {
 				let wasm_checksum = ev.0;
 				IbcEvent::PushWasmCode { wasm_checksum }
 			},
+			ev @ (RawIbcEvent::UpgradeInitChannel(_) |
+			RawIbcEvent::UpgradeTryChannel(_) |
+			RawIbcEvent::UpgradeAckChannel(_) |
+			RawIbcEvent::UpgradeConfirmChannel(_) |
+			RawIbcEvent::UpgradeOpenChannel(_) |
+			RawIbcEvent::UpgradeTimeoutChannel(_) |
+			RawIbcEvent::UpgradeCancelChannel(_) |
+			RawIbcEvent::UpgradeErrorChannel(_)) => {
+				let kind = ev.event_type().as_str().as_bytes().to_vec();
+				let attrs = ev.upgrade_attributes().expect("matched an upgrade event; qed");
+				IbcEvent::ChannelUpgrade {
+					kind,
+					revision_height: attrs.height.revision_height,
+					revision_number: attrs.height.revision_number,
+					port_id: attrs.port_id.as_bytes().to_vec(),
+					channel_id: attrs.channel_id.to_string().into_bytes(),
+					counterparty_port_id: attrs.counterparty_port_id.as_bytes().to_vec(),
+					counterparty_channel_id: attrs
+						.counterparty_channel_id
+						.as_ref()
+						.map(|val| val.to_string().into_bytes()),
+					upgrade_sequence: attrs.upgrade_sequence,
+				}
+			},
 		}
 	}
 }
@@ -1040,6 +1076,40 @@ impl TryFrom<IbcEvent> for RawIbcEvent {
 				.map_err(|_| ERROR_STR)?,
 				attributes: Default::default(),
 			})),
+			IbcEvent::ChannelUpgrade {
+				kind,
+				revision_height,
+				revision_number,
+				port_id,
+				channel_id,
+				counterparty_port_id,
+				counterparty_channel_id,
+				upgrade_sequence,
+			} => {
+				let event_type =
+					IbcEventType::from_str(&String::from_utf8(kind).map_err(|_| ERROR_STR)?)
+						.map_err(|_| ERROR_STR)?;
+				let attrs = ChannelEvents::UpgradeAttributes {
+					height: Height::new(revision_number, revision_height),
+					port_id: PortId::from_str(&String::from_utf8(port_id).map_err(|_| ERROR_STR)?)
+						.map_err(|_| ERROR_STR)?,
+					channel_id: ChannelId::from_str(
+						&String::from_utf8(channel_id).map_err(|_| ERROR_STR)?,
+					)
+					.map_err(|_| ERROR_STR)?,
+					counterparty_port_id: PortId::from_str(
+						&String::from_utf8(counterparty_port_id).map_err(|_| ERROR_STR)?,
+					)
+					.map_err(|_| ERROR_STR)?,
+					counterparty_channel_id: counterparty_channel_id.and_then(|channel_id| {
+						String::from_utf8(channel_id)
+							.ok()
+							.and_then(|channel_id| ChannelId::from_str(&channel_id).ok())
+					}),
+					upgrade_sequence,
+				};
+				ChannelEvents::upgrade_event(event_type, attrs).ok_or(ERROR_STR)
+			},
 		}
 	}
 }
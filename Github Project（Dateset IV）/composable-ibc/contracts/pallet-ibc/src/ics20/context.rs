@@ -79,7 +79,8 @@ where
 		to: &Self::AccountId,
 		amt: &ibc::applications::transfer::PrefixedCoin,
 	) -> Result<(), Ics20Error> {
-		let amount: T::Balance = amt.amount.as_u256().as_u128().into();
+		let raw_amount = amt.amount.as_u256().as_u128();
+		let amount: T::Balance = raw_amount.into();
 		let denom = amt.denom.to_string();
 		// Token should be registered already if sending an ibc asset
 		let asset_id = T::IbcDenomToAssetIdConversion::from_denom_to_asset_id(&denom)
@@ -110,6 +111,13 @@ where
 			})?;
 		}
 
+		Pallet::<T>::track_escrowed_amount(
+			&from.clone().into_account(),
+			&to.clone().into_account(),
+			&amt.denom,
+			raw_amount,
+		);
+
 		Ok(())
 	}
 
@@ -1,13 +1,11 @@
-use crate::{ics20::HandleMemo, Config};
-use alloc::{
-	format,
-	string::{String, ToString},
+use crate::{
+	ics20::{HandleMemo, IbcModule, MemoExecution},
+	Config,
 };
+use alloc::{format, string::String};
 use core::{fmt::Debug, str::FromStr};
 use ibc::{
-	applications::transfer::{
-		acknowledgement::Acknowledgement as Ics20Acknowledgement, error::Error as Ics20Error,
-	},
+	applications::transfer::acknowledgement::Acknowledgement as Ics20Acknowledgement,
 	core::{
 		ics04_channel::{
 			channel::{Counterparty, Order},
@@ -17,10 +15,13 @@ use ibc::{
 			Version,
 		},
 		ics24_host::identifier::{ChannelId, ConnectionId, PortId},
-		ics26_routing::context::{Module, ModuleCallbackContext, ModuleOutputBuilder},
+		ics26_routing::context::{
+			Module, ModuleCallbackContext, ModuleOutputBuilder, OnRecvPacketAck,
+		},
 	},
 	signer::Signer,
 };
+use sp_core::crypto::AccountId32;
 
 /// This middleware should be used to wrap ics20 to execute memo
 /// We chose to use this as a middleware so that we can easily choose
@@ -53,6 +54,9 @@ impl<T: Config + Send + Sync, S: Module + Clone + Default + PartialEq + Eq + Deb
 
 impl<T: Config + Send + Sync, S: Module + Clone + Default + PartialEq + Eq + Debug> Module
 	for Memo<T, S>
+where
+	u32: From<<T as frame_system::Config>::BlockNumber>,
+	AccountId32: From<<T as frame_system::Config>::AccountId>,
 {
 	fn on_chan_open_init(
 		&mut self,
@@ -152,14 +156,95 @@ impl<T: Config + Send + Sync, S: Module + Clone + Default + PartialEq + Eq + Deb
 		self.inner.on_chan_close_confirm(ctx, output, port_id, channel_id, relayer)
 	}
 
+	fn on_chan_upgrade_init(
+		&mut self,
+		ctx: &dyn ModuleCallbackContext,
+		output: &mut ModuleOutputBuilder,
+		port_id: &PortId,
+		channel_id: &ChannelId,
+		proposed_order: Order,
+		proposed_connection_hops: &[ConnectionId],
+		proposed_version: &Version,
+	) -> Result<Version, Error> {
+		self.inner.on_chan_upgrade_init(
+			ctx,
+			output,
+			port_id,
+			channel_id,
+			proposed_order,
+			proposed_connection_hops,
+			proposed_version,
+		)
+	}
+
+	fn on_chan_upgrade_try(
+		&mut self,
+		ctx: &dyn ModuleCallbackContext,
+		output: &mut ModuleOutputBuilder,
+		port_id: &PortId,
+		channel_id: &ChannelId,
+		proposed_order: Order,
+		proposed_connection_hops: &[ConnectionId],
+		counterparty_version: &Version,
+	) -> Result<Version, Error> {
+		self.inner.on_chan_upgrade_try(
+			ctx,
+			output,
+			port_id,
+			channel_id,
+			proposed_order,
+			proposed_connection_hops,
+			counterparty_version,
+		)
+	}
+
+	fn on_chan_upgrade_ack(
+		&mut self,
+		ctx: &dyn ModuleCallbackContext,
+		output: &mut ModuleOutputBuilder,
+		port_id: &PortId,
+		channel_id: &ChannelId,
+		counterparty_version: &Version,
+	) -> Result<(), Error> {
+		self.inner
+			.on_chan_upgrade_ack(ctx, output, port_id, channel_id, counterparty_version)
+	}
+
+	fn on_chan_upgrade_open(
+		&mut self,
+		ctx: &dyn ModuleCallbackContext,
+		output: &mut ModuleOutputBuilder,
+		port_id: &PortId,
+		channel_id: &ChannelId,
+		order: Order,
+		connection_hops: &[ConnectionId],
+		version: &Version,
+	) -> Result<(), Error> {
+		self.inner.on_chan_upgrade_open(
+			ctx,
+			output,
+			port_id,
+			channel_id,
+			order,
+			connection_hops,
+			version,
+		)
+	}
+
 	fn on_recv_packet(
 		&self,
 		ctx: &dyn ModuleCallbackContext,
 		output: &mut ModuleOutputBuilder,
 		packet: &mut Packet,
 		relayer: &Signer,
-	) -> Result<Acknowledgement, Error> {
-		let ack = self.inner.on_recv_packet(ctx, output, packet, relayer)?;
+	) -> Result<OnRecvPacketAck, Error> {
+		// The inner modules may rewrite the packet, e.g. with the amount left after a service fee
+		let received = packet.clone();
+		let ack = match self.inner.on_recv_packet(ctx, output, packet, relayer)? {
+			OnRecvPacketAck::Sync(ack) => ack,
+			// The memo is only executed once the transfer is known to have succeeded
+			OnRecvPacketAck::Async => return Ok(OnRecvPacketAck::Async),
+		};
 		let ics20_ack = Ics20Acknowledgement::from_str(&String::from_utf8_lossy(ack.as_ref()))
 			.map_err(|_| Error::invalid_acknowledgement())?;
 
@@ -167,26 +252,36 @@ impl<T: Config + Send + Sync, S: Module + Clone + Default + PartialEq + Eq + Deb
 		// does not contain an error
 
 		if !ics20_ack.is_successful() {
-			return Ok(ack)
+			return Ok(ack.into())
 		}
 
 		// We want the whole chain of calls to fail only if the ics20 transfer fails, because
-		// the other modules are not part of ics-20 standard
+		// the other modules are not part of ics-20 standard. The received tokens stay with the
+		// receiver, so the transfer is still acknowledged successfully.
 		match Self::process_memo(packet) {
-			Ok(_) => Ok(ack),
+			Ok(MemoExecution::Completed) => Ok(ack.into()),
+			// Acknowledged once the forwarded transfer settles
+			Ok(MemoExecution::Forwarded) => {
+				IbcModule::<T>::record_received_amount(&received);
+				Ok(OnRecvPacketAck::Async)
+			},
+			Ok(MemoExecution::Rejected(reason)) =>
+				Ok(IbcModule::<T>::reject_transfer(&received, packet, &reason).into()),
 			Err(err) => {
 				log::error!(target: "pallet_ibc", "Error while handling memo: {:?}", err);
-				Ok(Acknowledgement::from_bytes(
-					Ics20Acknowledgement::from_error(Ics20Error::implementation_specific(
-						err.to_string(),
-					))
-					.to_string()
-					.into_bytes(),
-				))
+				Ok(ack.into())
 			},
 		}
 	}
 
+	fn on_write_acknowledgement(
+		&self,
+		packet: &Packet,
+		ack: Acknowledgement,
+	) -> Result<Acknowledgement, Error> {
+		self.inner.on_write_acknowledgement(packet, ack)
+	}
+
 	fn on_acknowledgement_packet(
 		&mut self,
 		ctx: &dyn ModuleCallbackContext,
@@ -196,7 +291,11 @@ impl<T: Config + Send + Sync, S: Module + Clone + Default + PartialEq + Eq + Deb
 		relayer: &Signer,
 	) -> Result<(), Error> {
 		self.inner
-			.on_acknowledgement_packet(ctx, output, packet, acknowledgement, relayer)
+			.on_acknowledgement_packet(ctx, output, packet, acknowledgement, relayer)?;
+		let success = serde_json::from_slice::<Ics20Acknowledgement>(acknowledgement.as_ref())
+			.map(|ack| ack.is_successful())
+			.unwrap_or_default();
+		IbcModule::<T>::on_forward_acknowledged(packet, success)
 	}
 
 	fn on_timeout_packet(
@@ -206,15 +305,15 @@ impl<T: Config + Send + Sync, S: Module + Clone + Default + PartialEq + Eq + Deb
 		packet: &mut Packet,
 		relayer: &Signer,
 	) -> Result<(), Error> {
-		self.inner.on_timeout_packet(ctx, output, packet, relayer)
+		self.inner.on_timeout_packet(ctx, output, packet, relayer)?;
+		IbcModule::<T>::on_forward_timed_out(packet)
 	}
 }
 
 impl<T: Config + Send + Sync, S: Module + Clone + Default + PartialEq + Eq + Debug> Memo<T, S> {
-	fn process_memo(packet: &mut Packet) -> Result<(), Error> {
-		<T as Config>::HandleMemo::default().execute_memo(packet).map_err(|e| {
-			Error::implementation_specific(format!("Failed to execute memo {:?}", e))
-		})?;
-		Ok(())
+	fn process_memo(packet: &mut Packet) -> Result<MemoExecution, Error> {
+		<T as Config>::HandleMemo::default()
+			.execute_memo(packet)
+			.map_err(|e| Error::implementation_specific(format!("Failed to execute memo {:?}", e)))
 	}
 }
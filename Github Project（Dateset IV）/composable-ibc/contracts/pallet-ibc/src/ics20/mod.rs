@@ -2,25 +2,34 @@ pub mod context;
 pub mod memo;
 
 use crate::{
-	routing::Context, ChannelIds, Config, DenomToAssetId, Event, Pallet, SequenceFee, WeightInfo,
+	routing::Context, ChannelIds, Config, DenomToAssetId, Event, ExpiringForwardAttempts,
+	ForwardAttempts, InFlightForward, InFlightForwards, Pallet, SequenceFee, SequenceFees,
+	WeightInfo,
 };
 use alloc::{
 	format,
 	str::FromStr,
 	string::{String, ToString},
+	vec,
+	vec::Vec,
 };
 
-use frame_support::weights::Weight;
+use frame_support::{
+	storage::{with_transaction_unchecked, TransactionOutcome},
+	traits::Get,
+	weights::Weight,
+};
 pub use ibc::applications::transfer::{MODULE_ID_STR, PORT_ID_STR};
 use ibc::{
 	applications::transfer::{
 		acknowledgement::{Acknowledgement as Ics20Acknowledgement, ACK_ERR_STR},
 		context::{
-			on_chan_close_confirm, on_chan_close_init, on_chan_open_ack, on_chan_open_confirm,
-			on_chan_open_init, on_chan_open_try, BankKeeper,
+			decode_packet_data, on_chan_close_confirm, on_chan_close_init, on_chan_open_ack,
+			on_chan_open_confirm, on_chan_open_init, on_chan_open_try, on_chan_upgrade_ack,
+			on_chan_upgrade_init, on_chan_upgrade_try, BankKeeper,
 		},
 		is_receiver_chain_source, is_sender_chain_source,
-		packet::PacketData,
+		packet::{PacketData, PacketDataV2},
 		relay::{
 			on_ack_packet::process_ack_packet, on_recv_packet::process_recv_packet,
 			on_timeout_packet::process_timeout_packet,
@@ -30,19 +39,25 @@ use ibc::{
 	core::{
 		ics04_channel::{
 			channel::{Counterparty, Order},
+			context::ChannelReader,
 			error::Error as Ics04Error,
 			msgs::acknowledgement::Acknowledgement,
 			packet::Packet,
 			Version,
 		},
 		ics24_host::identifier::{ChannelId, ConnectionId, PortId},
-		ics26_routing::context::{Module, ModuleCallbackContext, ModuleOutputBuilder},
+		ics26_routing::context::{
+			Module, ModuleCallbackContext, ModuleOutputBuilder, OnRecvPacketAck,
+		},
 	},
 	signer::Signer,
 };
-use ibc_primitives::{CallbackWeight, HandlerMessage, IbcHandler};
+use ibc_primitives::{
+	CallbackWeight, ForwardAttempt, ForwardDestination, ForwardStatus, HandlerMessage, IbcHandler,
+	PacketInfo,
+};
 use sp_core::crypto::AccountId32;
-use sp_runtime::traits::IdentifyAccount;
+use sp_runtime::traits::{IdentifyAccount, One, Saturating};
 use sp_std::marker::PhantomData;
 
 pub type Ics20TransferMsg = ibc::applications::transfer::msgs::transfer::MsgTransfer<
@@ -173,6 +188,65 @@ where
 			.map_err(|e| Ics04Error::implementation_specific(e.to_string()))
 	}
 
+	fn on_chan_upgrade_init(
+		&mut self,
+		_ctx: &dyn ModuleCallbackContext,
+		output: &mut ModuleOutputBuilder,
+		port_id: &PortId,
+		channel_id: &ChannelId,
+		proposed_order: Order,
+		proposed_connection_hops: &[ConnectionId],
+		proposed_version: &Version,
+	) -> Result<Version, Ics04Error> {
+		let mut ctx = Context::<T>::default();
+		on_chan_upgrade_init(
+			&mut ctx,
+			output,
+			port_id,
+			channel_id,
+			proposed_order,
+			proposed_connection_hops,
+			proposed_version,
+		)
+		.map_err(|e| Ics04Error::implementation_specific(e.to_string()))
+	}
+
+	fn on_chan_upgrade_try(
+		&mut self,
+		_ctx: &dyn ModuleCallbackContext,
+		output: &mut ModuleOutputBuilder,
+		port_id: &PortId,
+		channel_id: &ChannelId,
+		proposed_order: Order,
+		proposed_connection_hops: &[ConnectionId],
+		counterparty_version: &Version,
+	) -> Result<Version, Ics04Error> {
+		let mut ctx = Context::<T>::default();
+		on_chan_upgrade_try(
+			&mut ctx,
+			output,
+			port_id,
+			channel_id,
+			proposed_order,
+			proposed_connection_hops,
+			counterparty_version,
+		)
+		.map_err(|e| Ics04Error::implementation_specific(e.to_string()))
+	}
+
+	fn on_chan_upgrade_ack(
+		&mut self,
+		_ctx: &dyn ModuleCallbackContext,
+		output: &mut ModuleOutputBuilder,
+		port_id: &PortId,
+		channel_id: &ChannelId,
+		counterparty_version: &Version,
+	) -> Result<(), Ics04Error> {
+		let mut ctx = Context::<T>::default();
+		on_chan_upgrade_ack(&mut ctx, output, port_id, channel_id, counterparty_version)
+			.map_err(|e| Ics04Error::implementation_specific(e.to_string()))
+	}
+
 	fn on_chan_close_confirm(
 		&mut self,
 		_ctx: &dyn ModuleCallbackContext,
@@ -200,85 +274,81 @@ where
 		output: &mut ModuleOutputBuilder,
 		packet: &mut Packet,
 		_relayer: &Signer,
-	) -> Result<Acknowledgement, Ics04Error> {
+	) -> Result<OnRecvPacketAck, Ics04Error> {
 		let mut ctx = Context::<T>::default();
 
-		let result = serde_json::from_slice(packet.data.as_slice())
-			.map_err(|e| {
-				Ics04Error::implementation_specific(format!("Failed to decode packet data {e:?}"))
-			})
-			.and_then(|packet_data: PacketData| {
+		let result = decode_packet_data(
+			&ctx,
+			&packet.destination_port,
+			&packet.destination_channel,
+			packet.data.as_slice(),
+		)
+		.and_then(|packet_data| packet_data.validate().map(|_| packet_data))
+		.map_err(|e| {
+			Ics04Error::implementation_specific(format!("Failed to decode packet data {e:?}"))
+		})
+		.and_then(|packet_data: PacketDataV2| {
+			for token in &packet_data.tokens {
 				// We need to reject transaction amounts that are larger than u128 since we expect
 				// the balance type of the runtime to be a u128; For a U256 to be converted to a
 				// u128 without truncating, the last two words should be zero
-
-				// in order to properly calculate rate limits, we need to adjust the full denom,
-				// which wan not included as part of the token in the packet data.
-
-				let mut token = packet_data.token.clone();
-				let denom = full_ibc_denom(packet, packet_data.token.clone());
-				token.denom = PrefixedDenom::from_str(&denom).map_err(|_| {
-					Ics04Error::implementation_specific("Failed to parse token denom".to_string())
-				})?;
-
-				let amount = packet_data.token.amount.as_u256();
+				let amount = token.amount.as_u256();
 				u128::try_from(amount)
 					.map_err(|e| Ics04Error::implementation_specific(format!("{e:?}")))?;
-				process_recv_packet(&mut ctx, output, packet, packet_data.clone())
-					.map(|_| packet_data)
-					.map_err(|e| {
-						log::trace!(target: "pallet_ibc", "[on_recv_packet]: token: {}, error: {:?}", denom, e);
-						Ics04Error::implementation_specific(e.to_string())
-					})
-			});
+			}
+			// Tokens credited before a failing one must not be kept since the sender is refunded
+			with_transaction_unchecked(|| {
+				match process_recv_packet(&mut ctx, output, packet, packet_data.clone()) {
+					Ok(()) => TransactionOutcome::Commit(Ok(packet_data)),
+					Err(e) => TransactionOutcome::Rollback(Err(e)),
+				}
+			})
+			.map_err(|e| {
+				log::trace!(target: "pallet_ibc", "[on_recv_packet]: packet: {:?}, error: {:?}", packet.sequence, e);
+				Ics04Error::implementation_specific(e.to_string())
+			})
+		});
 
+		// The acknowledgement is written by the router once the middlewares have processed it
 		let ack = match result {
 			Err(err) => {
 				log::trace!(target: "pallet_ibc", "Acknowledgement error: {:?}", err);
-				let ack = Ics20Acknowledgement::Error(format!("{ACK_ERR_STR}: {err:?}"))
+				Ics20Acknowledgement::Error(format!("{ACK_ERR_STR}: {err:?}"))
 					.to_string()
-					.into_bytes();
-				Pallet::<T>::handle_message(HandlerMessage::WriteAck {
-					packet: packet.clone(),
-					ack: ack.clone(),
-				})
-				.map_err(|e| {
-					Ics04Error::implementation_specific(format!("[on_recv_packet] {e:#?}"))
-				})?;
-				ack
+					.into_bytes()
 			},
 			Ok(packet_data) => {
-				let denom = full_ibc_denom(packet, packet_data.token.clone());
-				let prefixed_denom = PrefixedDenom::from_str(&denom).map_err(|_| {
-					Ics04Error::implementation_specific("Failed to parse token denom".to_string())
-				})?;
-				Pallet::<T>::deposit_event(Event::<T>::TokenReceived {
-					from: packet_data.sender,
-					to: packet_data.receiver,
-					ibc_denom: denom.as_bytes().to_vec(),
-					local_asset_id: T::IbcDenomToAssetIdConversion::from_denom_to_asset_id(&denom)
+				for token in packet_data.tokens {
+					// in order to properly calculate rate limits, we need to adjust the full
+					// denom, which wan not included as part of the token in the packet data.
+					let denom = full_ibc_denom(packet, token.clone());
+					let prefixed_denom = PrefixedDenom::from_str(&denom).map_err(|_| {
+						Ics04Error::implementation_specific(
+							"Failed to parse token denom".to_string(),
+						)
+					})?;
+					Pallet::<T>::deposit_event(Event::<T>::TokenReceived {
+						from: packet_data.sender.clone(),
+						to: packet_data.receiver.clone(),
+						ibc_denom: denom.as_bytes().to_vec(),
+						local_asset_id: T::IbcDenomToAssetIdConversion::from_denom_to_asset_id(
+							&denom,
+						)
 						.ok(),
-					amount: packet_data.token.amount.as_u256().as_u128().into(),
-					is_receiver_source: is_receiver_chain_source(
-						packet.source_port.clone(),
-						packet.source_channel,
-						&prefixed_denom,
-					),
-					source_channel: packet.source_channel.to_string().into_bytes(),
-					destination_channel: packet.destination_channel.to_string().into_bytes(),
-				});
-				let packet = packet.clone();
-				Pallet::<T>::handle_message(HandlerMessage::WriteAck {
-					packet,
-					ack: Ics20Acknowledgement::success().to_string().into_bytes(),
-				})
-				.map_err(|e| {
-					Ics04Error::implementation_specific(format!("[on_recv_packet] {e:#?}"))
-				})?;
+						amount: token.amount.as_u256().as_u128().into(),
+						is_receiver_source: is_receiver_chain_source(
+							packet.source_port.clone(),
+							packet.source_channel,
+							&prefixed_denom,
+						),
+						source_channel: packet.source_channel.to_string().into_bytes(),
+						destination_channel: packet.destination_channel.to_string().into_bytes(),
+					});
+				}
 				Ics20Acknowledgement::success().to_string().into_bytes()
 			},
 		};
-		Ok(Acknowledgement::from_bytes(ack))
+		Ok(Acknowledgement::from_bytes(ack).into())
 	}
 
 	fn on_acknowledgement_packet(
@@ -290,10 +360,15 @@ where
 		_relayer: &Signer,
 	) -> Result<(), Ics04Error> {
 		let mut ctx = Context::<T>::default();
-		let packet_data: PacketData =
-			serde_json::from_slice(packet.data.as_slice()).map_err(|e| {
-				Ics04Error::implementation_specific(format!("Failed to decode packet data {e:?}"))
-			})?;
+		let packet_data = decode_packet_data(
+			&ctx,
+			&packet.source_port,
+			&packet.source_channel,
+			packet.data.as_slice(),
+		)
+		.map_err(|e| {
+			Ics04Error::implementation_specific(format!("Failed to decode packet data {e:?}"))
+		})?;
 		let ack = serde_json::from_slice::<Ics20Acknowledgement>(acknowledgement.as_ref())
 			.map_err(|e| {
 				Ics04Error::implementation_specific(format!(
@@ -305,27 +380,33 @@ where
 			.map_err(|e| Ics04Error::implementation_specific(e.to_string()))?;
 		match ack.into_result() {
 			Ok(_) => {
-				if SequenceFee::<T>::contains_key(sequence) {
-					SequenceFee::<T>::remove(sequence);
+				let key = sequence_fee_key(packet);
+				if SequenceFee::<T>::contains_key(&key, sequence) ||
+					SequenceFees::<T>::contains_key(&key, sequence)
+				{
+					SequenceFee::<T>::remove(&key, sequence);
+					SequenceFees::<T>::remove(&key, sequence);
 					Pallet::<T>::deposit_event(Event::<T>::ChargingFeeConfirmed { sequence });
 				}
-				Pallet::<T>::deposit_event(Event::<T>::TokenTransferCompleted {
-					from: packet_data.sender,
-					to: packet_data.receiver,
-					ibc_denom: packet_data.token.denom.to_string().into_bytes(),
-					local_asset_id: T::IbcDenomToAssetIdConversion::from_denom_to_asset_id(
-						&packet_data.token.denom.to_string(),
-					)
-					.ok(),
-					amount: packet_data.token.amount.as_u256().as_u128().into(),
-					is_sender_source: is_sender_chain_source(
-						packet.source_port.clone(),
-						packet.source_channel,
-						&packet_data.token.denom,
-					),
-					source_channel: packet.source_channel.to_string().into_bytes(),
-					destination_channel: packet.destination_channel.to_string().into_bytes(),
-				})
+				for token in packet_data.tokens {
+					Pallet::<T>::deposit_event(Event::<T>::TokenTransferCompleted {
+						from: packet_data.sender.clone(),
+						to: packet_data.receiver.clone(),
+						ibc_denom: token.denom.to_string().into_bytes(),
+						local_asset_id: T::IbcDenomToAssetIdConversion::from_denom_to_asset_id(
+							&token.denom.to_string(),
+						)
+						.ok(),
+						amount: token.amount.as_u256().as_u128().into(),
+						is_sender_source: is_sender_chain_source(
+							packet.source_port.clone(),
+							packet.source_channel,
+							&token.denom,
+						),
+						source_channel: packet.source_channel.to_string().into_bytes(),
+						destination_channel: packet.destination_channel.to_string().into_bytes(),
+					})
+				}
 			},
 			Err(e) => {
 				log::trace!(
@@ -337,23 +418,25 @@ where
 					sequence,
 				});
 
-				Pallet::<T>::deposit_event(Event::<T>::TokenTransferFailed {
-					from: packet_data.sender,
-					to: packet_data.receiver,
-					ibc_denom: packet_data.token.denom.to_string().into_bytes(),
-					local_asset_id: T::IbcDenomToAssetIdConversion::from_denom_to_asset_id(
-						&packet_data.token.denom.to_string(),
-					)
-					.ok(),
-					amount: packet_data.token.amount.as_u256().as_u128().into(),
-					is_sender_source: is_sender_chain_source(
-						packet.source_port.clone(),
-						packet.source_channel,
-						&packet_data.token.denom,
-					),
-					source_channel: packet.source_channel.to_string().into_bytes(),
-					destination_channel: packet.destination_channel.to_string().into_bytes(),
-				})
+				for token in packet_data.tokens {
+					Pallet::<T>::deposit_event(Event::<T>::TokenTransferFailed {
+						from: packet_data.sender.clone(),
+						to: packet_data.receiver.clone(),
+						ibc_denom: token.denom.to_string().into_bytes(),
+						local_asset_id: T::IbcDenomToAssetIdConversion::from_denom_to_asset_id(
+							&token.denom.to_string(),
+						)
+						.ok(),
+						amount: token.amount.as_u256().as_u128().into(),
+						is_sender_source: is_sender_chain_source(
+							packet.source_port.clone(),
+							packet.source_channel,
+							&token.denom,
+						),
+						source_channel: packet.source_channel.to_string().into_bytes(),
+						destination_channel: packet.destination_channel.to_string().into_bytes(),
+					})
+				}
 			},
 		}
 
@@ -368,31 +451,38 @@ where
 		_relayer: &Signer,
 	) -> Result<(), Ics04Error> {
 		let mut ctx = Context::<T>::default();
-		let packet_data: PacketData = serde_json::from_slice(packet.data.as_slice())
-			.map_err(|e| Ics04Error::app_module(format!("Failed to decode packet data {e:?}")))?;
+		let packet_data = decode_packet_data(
+			&ctx,
+			&packet.source_port,
+			&packet.source_channel,
+			packet.data.as_slice(),
+		)
+		.map_err(|e| Ics04Error::app_module(format!("Failed to decode packet data {e:?}")))?;
 		process_timeout_packet(&mut ctx, packet, &packet_data)
 			.map_err(|e| Ics04Error::app_module(e.to_string()))?;
 		let sequence: u64 = packet.sequence.into();
 		Self::refund_fee(packet, &packet_data)?;
 		Pallet::<T>::deposit_event(Event::<T>::ChargingFeeTimeout { sequence });
 
-		Pallet::<T>::deposit_event(Event::<T>::TokenTransferTimeout {
-			from: packet_data.sender,
-			to: packet_data.receiver,
-			ibc_denom: packet_data.token.denom.to_string().into_bytes(),
-			local_asset_id: T::IbcDenomToAssetIdConversion::from_denom_to_asset_id(
-				&packet_data.token.denom.to_string(),
-			)
-			.ok(),
-			amount: packet_data.token.amount.as_u256().as_u128().into(),
-			is_sender_source: is_sender_chain_source(
-				packet.source_port.clone(),
-				packet.source_channel,
-				&packet_data.token.denom,
-			),
-			source_channel: packet.source_channel.to_string().into_bytes(),
-			destination_channel: packet.destination_channel.to_string().into_bytes(),
-		});
+		for token in packet_data.tokens {
+			Pallet::<T>::deposit_event(Event::<T>::TokenTransferTimeout {
+				from: packet_data.sender.clone(),
+				to: packet_data.receiver.clone(),
+				ibc_denom: token.denom.to_string().into_bytes(),
+				local_asset_id: T::IbcDenomToAssetIdConversion::from_denom_to_asset_id(
+					&token.denom.to_string(),
+				)
+				.ok(),
+				amount: token.amount.as_u256().as_u128().into(),
+				is_sender_source: is_sender_chain_source(
+					packet.source_port.clone(),
+					packet.source_channel,
+					&token.denom,
+				),
+				source_channel: packet.source_channel.to_string().into_bytes(),
+				destination_channel: packet.destination_channel.to_string().into_bytes(),
+			});
+		}
 		Ok(())
 	}
 }
@@ -424,21 +514,21 @@ where
 	///
 	/// - The fee cannot be refunded to the sender's account. ctx.send_coins failed.
 	/// - The sender's account cannot be parsed from the packet data.
-	fn refund_fee(packet: &Packet, packet_data: &PacketData) -> Result<(), Ics04Error> {
+	fn refund_fee(packet: &Packet, packet_data: &PacketDataV2) -> Result<(), Ics04Error> {
 		use ibc::bigint::U256;
-		use sp_core::Get;
 		let sequence: u64 = packet.sequence.into();
-		if !SequenceFee::<T>::contains_key(sequence) {
+		let key = sequence_fee_key(packet);
+		let fees = if SequenceFee::<T>::contains_key(&key, sequence) {
+			vec![SequenceFee::<T>::take(&key, sequence)]
+		} else if SequenceFees::<T>::contains_key(&key, sequence) {
+			SequenceFees::<T>::take(&key, sequence)
+		} else {
 			return Ok(()) //there is nothing to refund.
-		}
-		let fee = SequenceFee::<T>::take(sequence);
+		};
 
 		let fee_account = T::FeeAccount::get();
 
 		let mut ctx = Context::<T>::default();
-		let mut fee_coin = packet_data.token.clone();
-
-		fee_coin.amount = U256::from(fee).into();
 
 		let signer_from = packet_data.sender.clone();
 		let refund_to_account_id =
@@ -448,12 +538,17 @@ where
 				))
 			})?;
 
-		ctx.send_coins(&fee_account, &refund_to_account_id, &fee_coin).map_err(|e| {
+		for (token, fee) in packet_data.tokens.iter().zip(fees) {
+			let mut fee_coin = token.clone();
+			fee_coin.amount = U256::from(fee).into();
+
+			ctx.send_coins(&fee_account, &refund_to_account_id, &fee_coin).map_err(|e| {
 				log::debug!(target: "pallet_ibc", "[{}]: error when refund the fee : {:?} for sequence {}", &e, fee, sequence);
 				Ics04Error::implementation_specific(format!(
 					"Failed to refund fee to sender account {signer_from:?}, fee : {fee} , sequence : {sequence} "
 				))
 			})?;
+		}
 		Ok(())
 	}
 }
@@ -492,7 +587,9 @@ impl<T: Config> CallbackWeight for WeightHandler<T> {
 	}
 
 	fn on_recv_packet(&self, _packet: &Packet) -> Weight {
+		// The memo is only parsed when the packet is executed, charge the most it can weigh
 		<T as Config>::WeightInfo::on_recv_packet()
+			.saturating_add(<T as Config>::HandleMemo::default().max_weight())
 	}
 
 	fn on_acknowledgement_packet(
@@ -508,6 +605,11 @@ impl<T: Config> CallbackWeight for WeightHandler<T> {
 	}
 }
 
+/// Key of the fees charged for a sent `packet` in `SequenceFee` and `SequenceFees`
+fn sequence_fee_key(packet: &Packet) -> (Vec<u8>, Vec<u8>) {
+	(packet.source_port.as_bytes().to_vec(), packet.source_channel.to_string().into_bytes())
+}
+
 pub fn full_ibc_denom(packet: &Packet, mut token: PrefixedCoin) -> String {
 	if is_receiver_chain_source(packet.source_port.clone(), packet.source_channel, &token.denom) {
 		let prefix = TracePrefix::new(packet.source_port.clone(), packet.source_channel);
@@ -524,16 +626,109 @@ pub fn full_ibc_denom(packet: &Packet, mut token: PrefixedCoin) -> String {
 
 use ibc::applications::transfer::error::Error as Ics20Error;
 
+/// Reason the memo of a received transfer could not be executed
+#[derive(Clone, Copy, Debug, PartialEq, Eq, codec::Encode, codec::Decode, scale_info::TypeInfo)]
+pub enum MemoFailureReason {
+	/// The memo is not valid json of a supported format
+	ParseError,
+	/// No asset is registered for the received denom
+	UnknownAsset,
+	/// A field required to forward the tokens is missing
+	MissingField,
+	/// The channel to forward the tokens on is not a valid channel identifier
+	InvalidChannel,
+	/// The xcm receiver is not a hex string
+	BadHex,
+	/// The xcm receiver or the memo of the next hop could not be decoded
+	DecodeFailure,
+	/// Sending the tokens over xcm failed
+	XcmFailure,
+	/// Sending the tokens over ibc failed
+	IbcSendFailure,
+	/// The forward asks for more retries than attempts are tracked for a transfer
+	TooManyRetries,
+}
+
+/// Outcome of a successfully executed memo
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MemoExecution {
+	/// The memo has been fully executed, the packet can be acknowledged right away
+	Completed,
+	/// The tokens have been forwarded over ibc, the packet is acknowledged once the forwarded
+	/// packet is acknowledged or has run out of retries
+	Forwarded,
+	/// The memo action failed, the transfer is reverted and acknowledged with an error so that
+	/// the sender is refunded on the source chain
+	Rejected(String),
+}
+
 pub trait HandleMemo<T: Config> {
-	fn execute_memo(&self, packet: &Packet) -> Result<(), Ics20Error>;
+	fn execute_memo(&self, packet: &Packet) -> Result<MemoExecution, Ics20Error>;
+
+	/// Most weight executing the memo of a received transfer can take on top of the transfer
+	fn max_weight(&self) -> Weight {
+		Weight::zero()
+	}
 }
 
 impl<T: Config> HandleMemo<T> for () {
-	fn execute_memo(&self, _packet: &Packet) -> Result<(), Ics20Error> {
-		Ok(())
+	fn execute_memo(&self, _packet: &Packet) -> Result<MemoExecution, Ics20Error> {
+		Ok(MemoExecution::Completed)
+	}
+}
+
+/// An action requested by the memo of a received transfer, other than forwarding the tokens.
+///
+/// Actions are registered in [`IbcMemoHandler`] as a tuple and tried in order, the first one
+/// that recognises the memo executes it.
+pub trait MemoAction<T: Config> {
+	/// Executes the memo of a received transfer, returns `None` if the memo does not request
+	/// this action.
+	fn execute(
+		packet: &Packet,
+		packet_data: &PacketData,
+	) -> Option<Result<MemoExecution, Ics20Error>>;
+
+	/// Most weight executing this action can take
+	fn max_weight() -> Weight {
+		Weight::zero()
+	}
+}
+
+impl<T: Config> MemoAction<T> for () {
+	fn execute(_: &Packet, _: &PacketData) -> Option<Result<MemoExecution, Ics20Error>> {
+		None
 	}
 }
 
+macro_rules! impl_memo_action_for_tuples {
+	($($action:ident),+) => {
+		impl<T: Config, $($action: MemoAction<T>),+> MemoAction<T> for ($($action,)+) {
+			fn execute(
+				packet: &Packet,
+				packet_data: &PacketData,
+			) -> Option<Result<MemoExecution, Ics20Error>> {
+				$(
+					if let Some(result) = $action::execute(packet, packet_data) {
+						return Some(result)
+					}
+				)+
+				None
+			}
+
+			fn max_weight() -> Weight {
+				// At most one of the actions is executed
+				Weight::zero()$(.max($action::max_weight()))+
+			}
+		}
+	};
+}
+
+impl_memo_action_for_tuples!(A);
+impl_memo_action_for_tuples!(A, B);
+impl_memo_action_for_tuples!(A, B, C);
+impl_memo_action_for_tuples!(A, B, C, D);
+
 pub trait SubstrateMultihopXcmHandler {
 	type AccountId;
 
@@ -657,32 +852,36 @@ impl Forward {
 	}
 }
 
-impl<H: Default, T> Default for IbcMemoHandler<H, T> {
+impl<H: Default, T, A> Default for IbcMemoHandler<H, T, A> {
 	fn default() -> Self {
 		Self { inner: H::default(), _phantom: PhantomData }
 	}
 }
 
-pub struct IbcMemoHandler<H, T> {
+/// Executes the memo of received transfers, forwarding the tokens over ibc or xcm or running
+/// one of the [`MemoAction`]s registered in `A`.
+pub struct IbcMemoHandler<H, T, A = ()> {
 	pub inner: H,
-	pub _phantom: PhantomData<T>,
+	pub _phantom: PhantomData<(T, A)>,
 }
 
 use codec::Decode;
-impl<T, H: HandleMemo<T>> HandleMemo<T> for IbcMemoHandler<H, T>
+impl<T, H: HandleMemo<T>, A: MemoAction<T>> HandleMemo<T> for IbcMemoHandler<H, T, A>
 where
 	T: Config + Send + Sync + pallet_timestamp::Config,
 	u32: From<<T as frame_system::Config>::BlockNumber>,
 	AccountId32: From<<T as frame_system::Config>::AccountId>,
 	u128: From<T::AssetId>,
 {
-	fn execute_memo(&self, packet: &Packet) -> Result<(), Ics20Error> {
+	fn execute_memo(&self, packet: &Packet) -> Result<MemoExecution, Ics20Error> {
 		self.inner.execute_memo(packet)?;
 
-		let packet_data: PacketData =
-			serde_json::from_slice(packet.data.as_slice()).map_err(|e| {
-				Ics20Error::implementation_specific(format!("Failed to decode packet data {:?}", e))
-			})?;
+		let packet_data = IbcModule::<T>::received_packet_data(packet)?;
+		// Memos are only executed for transfers of a single token
+		let packet_data = match PacketData::try_from(packet_data) {
+			Ok(packet_data) => packet_data,
+			Err(_) => return Ok(MemoExecution::Completed),
+		};
 		let receiver = <T as Config>::AccountIdConversion::try_from(packet_data.receiver.clone())
 			.map_err(|_| {
 				Ics20Error::implementation_specific(format!("Failed to parse receiver account"))
@@ -691,7 +890,7 @@ where
 
 		//Handle only memo with IBC forward.
 		if packet_data.memo.is_empty() {
-			return Ok(())
+			return Ok(MemoExecution::Completed)
 		}
 
 		crate::Pallet::<T>::deposit_event(Event::<T>::ExecuteMemoStarted {
@@ -699,16 +898,24 @@ where
 			memo: Some(packet_data.memo.clone()),
 		});
 
-		let memo: MemoData = serde_json::from_str(&packet_data.memo).map_err(|_| {
-			IbcModule::<T>::emit_memo_execution_failed_event(
+		if let Some(result) = A::execute(packet, &packet_data) {
+			return result
+		}
+
+		let failed = |reason, msg: String| {
+			IbcModule::<T>::memo_execution_failed(
 				receiver.clone(),
 				packet_data.memo.clone(),
-				0,
-			);
-			Ics20Error::implementation_specific(format!(
-				"Failed to parse memo : {:?} ",
-				packet_data.memo
-			))
+				reason,
+				msg,
+			)
+		};
+
+		let memo: MemoData = serde_json::from_str(&packet_data.memo).map_err(|_| {
+			failed(
+				MemoFailureReason::ParseError,
+				format!("Failed to parse memo : {:?} ", packet_data.memo),
+			)
 		})?;
 
 		let prefixed_coin = if is_receiver_chain_source(
@@ -735,49 +942,28 @@ where
 			)
 			.map_err(|_| {
 				log::warn!(target: "pallet_ibc", "Asset does not exist for denom: {}", prefixed_coin.denom.to_string());
-				IbcModule::<T>::emit_memo_execution_failed_event(receiver.clone(), packet_data.memo.clone(), 1);
-				Ics20Error::implementation_specific("asset does not exist".to_string())
+				failed(MemoFailureReason::UnknownAsset, "asset does not exist".to_string())
 			})?;
 
 		let amount = packet_data.token.amount.as_u256().low_u128();
 
-		let memo_forward = memo.forward.get_memo().map_err(|_| {
-			IbcModule::<T>::emit_memo_execution_failed_event(
-				receiver.clone(),
-				packet_data.memo.clone(),
-				2,
-			);
-			Ics20Error::implementation_specific("Failed to get memo".to_string())
-		})?;
+		let memo_forward = memo
+			.forward
+			.get_memo()
+			.map_err(|e| failed(MemoFailureReason::MissingField, e.to_string()))?;
 
 		let memo_forward = match memo_forward {
 			MemoType::IBC(memo_forward) => memo_forward,
 			MemoType::XCM(memo_forward) => {
 				let s = memo_forward.receiver.strip_prefix("0x").ok_or_else(|| {
-					IbcModule::<T>::emit_memo_execution_failed_event(
-						receiver.clone(),
-						packet_data.memo.clone(),
-						11,
-					);
-					Ics20Error::implementation_specific("failed strip_prefix.".to_string())
+					failed(MemoFailureReason::BadHex, "failed strip_prefix.".to_string())
 				})?;
 
-				let decoded_accout = hex::decode(s).map_err(|_| {
-					IbcModule::<T>::emit_memo_execution_failed_event(
-						receiver.clone(),
-						packet_data.memo.clone(),
-						12,
-					);
-					Ics20Error::implementation_specific("hex::decode".to_string())
-				})?;
+				let decoded_accout = hex::decode(s)
+					.map_err(|_| failed(MemoFailureReason::BadHex, "hex::decode".to_string()))?;
 
 				let account_to = T::AccountId::decode(&mut &*decoded_accout).map_err(|_| {
-					IbcModule::<T>::emit_memo_execution_failed_event(
-						receiver.clone(),
-						packet_data.memo.clone(),
-						13,
-					);
-					Ics20Error::implementation_specific("T::AccountId::decode".to_string())
+					failed(MemoFailureReason::DecodeFailure, "T::AccountId::decode".to_string())
 				})?;
 
 				T::SubstrateMultihopXcmHandler::transfer_xcm(
@@ -795,11 +981,24 @@ where
 						amount,
 						asset_id: asset_id.clone(),
 					});
-					Ics20Error::implementation_specific(
+					failed(
+						MemoFailureReason::XcmFailure,
 						"Faield to execute SubstrateMultihopXcmHandler::transfer_xcm.".to_string(),
 					)
 				})?;
 
+				let received = PacketInfo::from(packet.clone());
+				IbcModule::<T>::record_forward_attempt(
+					&received,
+					ForwardAttempt {
+						destination: ForwardDestination::Xcm {
+							para_id: memo_forward.para_id,
+							receiver: decoded_accout,
+						},
+						status: ForwardStatus::Succeeded,
+					},
+				);
+				IbcModule::<T>::expire_forward_attempts(&received);
 				crate::Pallet::<T>::deposit_event(Event::<T>::ExecuteMemoXcmSuccess {
 					from: receiver.clone(),
 					to: account_to.clone(),
@@ -808,84 +1007,72 @@ where
 					asset_id: asset_id.clone(),
 				});
 
-				return Ok(())
+				return Ok(MemoExecution::Completed)
 			},
 		};
 
 		let raw_bytes = memo_forward.receiver.into_bytes();
 
-		let transfer_ibc_account_to =
-			crate::MultiAddress::<<T as frame_system::Config>::AccountId>::Raw(raw_bytes.clone());
-
-		let origin = RawOrigin::Signed(receiver.clone());
 		let channel_id = memo_forward
 			.channel
 			.split('-')
 			.last()
 			.ok_or_else(|| {
-				IbcModule::<T>::emit_memo_execution_failed_event(
-					receiver.clone(),
-					packet_data.memo.clone(),
-					3,
-				);
-				Ics20Error::implementation_specific(format!(
-					"Failed to extract channel number from channel ID: {:?}",
-					memo_forward.channel
-				))
+				failed(
+					MemoFailureReason::InvalidChannel,
+					format!(
+						"Failed to extract channel number from channel ID: {:?}",
+						memo_forward.channel
+					),
+				)
 			})?
 			.parse()
 			.map_err(|_| {
-				IbcModule::<T>::emit_memo_execution_failed_event(
-					receiver.clone(),
-					packet_data.memo.clone(),
-					4,
-				);
-				Ics20Error::implementation_specific("Failed to parse channel ID".to_string())
+				failed(MemoFailureReason::InvalidChannel, "Failed to parse channel ID".to_string())
 			})?;
 
-		let params = crate::TransferParams::<<T as frame_system::Config>::AccountId> {
-			to: transfer_ibc_account_to,
-			source_channel: channel_id,
-			timeout: ibc_primitives::Timeout::Offset {
-				timestamp: Some(memo_forward.timeout),
-				height: Some(1000),
-			},
-		};
+		if memo_forward.retries >= u64::from(T::MaxForwardAttempts::get()) {
+			return Err(failed(
+				MemoFailureReason::TooManyRetries,
+				format!("at most {} retries", T::MaxForwardAttempts::get().saturating_sub(1)),
+			))
+		}
 
 		let mut next_memo: Option<T::MemoMessage> = None;
 		if let Some(memo) = memo.forward.next {
 			let memo_result = <T as crate::Config>::MemoMessage::try_from(*memo).map_err(|_| {
-				IbcModule::<T>::emit_memo_execution_failed_event(
-					receiver.clone(),
-					packet_data.memo.clone(),
-					5,
-				);
-				Ics20Error::implementation_specific("failed to try_from memo".to_string())
+				failed(MemoFailureReason::DecodeFailure, "failed to try_from memo".to_string())
 			})?;
 
 			next_memo = Some(memo_result);
 		}
 
-		crate::Pallet::<T>::transfer(
-			origin.into(),
-			params,
-			asset_id.clone(),
-			amount.into(),
-			next_memo.clone(),
-		)
-		.map_err(|_| {
+		let forward = InFlightForward {
+			packet: packet.clone().into(),
+			sender: receiver.clone(),
+			receiver: raw_bytes.clone(),
+			channel: channel_id,
+			timeout: memo_forward.timeout,
+			retries: memo_forward.retries,
+			asset_id: asset_id.clone(),
+			amount,
+			received_amount: amount,
+			next_memo: next_memo.clone(),
+		};
+		// Nothing was forwarded, so the transfer is rejected and the sender refunded
+		if IbcModule::<T>::send_forward(forward).is_err() {
 			crate::Pallet::<T>::deposit_event(Event::<T>::ExecuteMemoIbcTokenTransferFailed {
 				from: receiver.clone(),
-				to: raw_bytes.clone(),
-				asset_id: asset_id.clone(),
+				to: raw_bytes,
+				asset_id,
 				amount: amount.into(),
 				channel: channel_id,
-				next_memo: next_memo.clone(),
+				next_memo,
 			});
-			Ics20Error::implementation_specific(
-				"Pallet ibc transfer failed to send message".to_string(),
-			)
-		})?;
+			let reason = "Pallet ibc transfer failed to send message".to_string();
+			failed(MemoFailureReason::IbcSendFailure, reason.clone());
+			return Ok(MemoExecution::Rejected(reason))
+		}
 
 		crate::Pallet::<T>::deposit_event(Event::<T>::ExecuteMemoIbcTokenTransferSuccess {
 			from: receiver,
@@ -895,7 +1082,11 @@ where
 			channel: channel_id,
 			next_memo,
 		});
-		Ok(())
+		Ok(MemoExecution::Forwarded)
+	}
+
+	fn max_weight(&self) -> Weight {
+		self.inner.max_weight().saturating_add(A::max_weight())
 	}
 }
 
@@ -905,11 +1096,351 @@ where
 	u32: From<<T as frame_system::Config>::BlockNumber>,
 	AccountId32: From<<T as frame_system::Config>::AccountId>,
 {
-	//function that takes account and memo and emit event that memo execution failed
-	fn emit_memo_execution_failed_event(account: T::AccountId, memo: String, reason: u8) {
+	/// Emits an event that the memo of a transfer received by `account` could not be executed and
+	/// returns the corresponding error.
+	fn memo_execution_failed(
+		account: T::AccountId,
+		memo: String,
+		reason: MemoFailureReason,
+		msg: String,
+	) -> Ics20Error {
 		crate::Pallet::<T>::deposit_event(
 			Event::<T>::ExecuteMemoIbcTokenTransferFailedWithReason { from: account, memo, reason },
 		);
+		Ics20Error::implementation_specific(format!("{reason:?}: {msg}"))
+	}
+
+	/// Sends the transfer of a memo forward and tracks it in `InFlightForwards` until the
+	/// forwarded packet is acknowledged or times out.
+	fn send_forward(
+		forward: InFlightForward<T::AccountId, T::AssetId, T::MemoMessage>,
+	) -> Result<u64, Ics04Error> {
+		let channel_id = ChannelId::new(forward.channel);
+		let attempts = ForwardAttempts::<T>::decode_len(
+			(forward.packet.destination_port.clone(), forward.packet.destination_channel.clone()),
+			forward.packet.sequence,
+		)
+		.unwrap_or_default();
+		if attempts >= T::MaxForwardAttempts::get() as usize {
+			return Err(Ics04Error::implementation_specific(
+				"Too many attempts to forward the transfer".to_string(),
+			))
+		}
+		let sequence: u64 = Context::<T>::default()
+			.get_next_sequence_send(&(PortId::transfer(), channel_id))?
+			.into();
+		Pallet::<T>::transfer(
+			RawOrigin::Signed(forward.sender.clone()).into(),
+			crate::TransferParams {
+				to: crate::MultiAddress::Raw(forward.receiver.clone()),
+				source_channel: forward.channel,
+				timeout: ibc_primitives::Timeout::Offset {
+					timestamp: Some(forward.timeout),
+					height: Some(1000),
+				},
+			},
+			forward.asset_id.clone(),
+			forward.amount.into(),
+			forward.next_memo.clone(),
+		)
+		.map_err(|e| Ics04Error::implementation_specific(format!("{e:?}")))?;
+		Self::record_forward_attempt(
+			&forward.packet,
+			ForwardAttempt {
+				destination: ForwardDestination::Ibc {
+					port_id: PortId::transfer().as_bytes().to_vec(),
+					channel_id: channel_id.to_string().into_bytes(),
+					sequence,
+				},
+				status: ForwardStatus::Pending,
+			},
+		);
+		InFlightForwards::<T>::insert(
+			(PortId::transfer().as_bytes().to_vec(), channel_id.to_string().into_bytes()),
+			sequence,
+			forward,
+		);
+		Ok(sequence)
+	}
+
+	/// Records an attempt to forward the tokens received in `packet`, the attempts are bounded by
+	/// the retries checked in `execute_memo` and `send_forward`
+	fn record_forward_attempt(packet: &PacketInfo, attempt: ForwardAttempt) {
+		let recorded = ForwardAttempts::<T>::try_append(
+			(packet.destination_port.clone(), packet.destination_channel.clone()),
+			packet.sequence,
+			attempt,
+		);
+		if recorded.is_err() {
+			log::warn!(target: "pallet_ibc", "Forward attempts of packet {} are full", packet.sequence);
+		}
+	}
+
+	/// Records the amount received in `received` before the service fee was charged in the
+	/// forward that was just sent for it
+	pub(crate) fn record_received_amount(received: &Packet) {
+		let received_amount =
+			match Self::received_packet_data(received).and_then(PacketData::try_from) {
+				Ok(packet_data) => packet_data.token.amount.as_u256().low_u128(),
+				Err(_) => return,
+			};
+		let attempts = ForwardAttempts::<T>::get(
+			(
+				received.destination_port.as_bytes().to_vec(),
+				received.destination_channel.to_string().into_bytes(),
+			),
+			u64::from(received.sequence),
+		);
+		if let Some(ForwardAttempt {
+			destination: ForwardDestination::Ibc { port_id, channel_id, sequence },
+			..
+		}) = attempts.last()
+		{
+			InFlightForwards::<T>::mutate(
+				(port_id.clone(), channel_id.clone()),
+				sequence,
+				|forward| {
+					if let Some(forward) = forward {
+						forward.received_amount = received_amount;
+					}
+				},
+			);
+		}
+	}
+
+	/// Updates the status of the attempt to forward the tokens received in `packet` that was sent
+	/// in the `forwarded` packet
+	fn update_forward_status(packet: &PacketInfo, forwarded: &Packet, status: ForwardStatus) {
+		let destination = ForwardDestination::Ibc {
+			port_id: forwarded.source_port.as_bytes().to_vec(),
+			channel_id: forwarded.source_channel.to_string().into_bytes(),
+			sequence: forwarded.sequence.into(),
+		};
+		ForwardAttempts::<T>::mutate(
+			(packet.destination_port.clone(), packet.destination_channel.clone()),
+			packet.sequence,
+			|attempts| {
+				if let Some(attempt) =
+					attempts.iter_mut().find(|attempt| attempt.destination == destination)
+				{
+					attempt.status = status;
+				}
+			},
+		);
+	}
+
+	/// Settles a forwarded transfer once the forwarded packet has been acknowledged by
+	/// releasing the acknowledgement of the packet it was received with.
+	pub(crate) fn on_forward_acknowledged(
+		packet: &Packet,
+		success: bool,
+	) -> Result<(), Ics04Error> {
+		let forward = match Self::take_forward(packet) {
+			Some(forward) => forward,
+			None => return Ok(()),
+		};
+		let status = if success { ForwardStatus::Succeeded } else { ForwardStatus::Failed };
+		Self::update_forward_status(&forward.packet, packet, status);
+		if success {
+			let ack = Ics20Acknowledgement::success().to_string().into_bytes();
+			return Self::write_forward_ack(forward.packet, ack)
+		}
+		Self::refund_forward(forward, "forwarded transfer failed")
+	}
+
+	/// Sends a timed out forwarded transfer again, or refunds it once it has run out of retries.
+	pub(crate) fn on_forward_timed_out(packet: &Packet) -> Result<(), Ics04Error> {
+		let mut forward = match Self::take_forward(packet) {
+			Some(forward) => forward,
+			None => return Ok(()),
+		};
+		Self::update_forward_status(&forward.packet, packet, ForwardStatus::TimedOut);
+		if forward.retries == 0 {
+			return Self::refund_forward(forward, "forwarded transfer timed out")
+		}
+		forward.retries -= 1;
+		match Self::send_forward(forward.clone()) {
+			Ok(sequence) => {
+				Pallet::<T>::deposit_event(Event::<T>::ExecuteMemoIbcTokenTransferRetried {
+					from: forward.sender,
+					to: forward.receiver,
+					channel: forward.channel,
+					sequence,
+					retries_left: forward.retries,
+				});
+				Ok(())
+			},
+			Err(e) => {
+				log::debug!(target: "pallet_ibc", "Failed to resend forwarded transfer: {e:?}");
+				Self::refund_forward(forward, "forwarded transfer could not be sent again")
+			},
+		}
+	}
+
+	fn take_forward(
+		packet: &Packet,
+	) -> Option<InFlightForward<T::AccountId, T::AssetId, T::MemoMessage>> {
+		InFlightForwards::<T>::take(
+			(
+				packet.source_port.as_bytes().to_vec(),
+				packet.source_channel.to_string().into_bytes(),
+			),
+			u64::from(packet.sequence),
+		)
+	}
+
+	/// Reverts the receipt of a transfer whose forward failed and writes an error
+	/// acknowledgement for it, so that the source chain refunds the original sender.
+	///
+	/// Tokens released from escrow on receive are escrowed again and vouchers minted on receive
+	/// are burnt. If that is not possible the tokens are left with the receiver and the transfer
+	/// is acknowledged successfully, since an error acknowledgement would refund them twice.
+	fn refund_forward(
+		forward: InFlightForward<T::AccountId, T::AssetId, T::MemoMessage>,
+		reason: &str,
+	) -> Result<(), Ics04Error> {
+		let packet = Packet::from(forward.packet.clone());
+		let packet_data = Self::received_packet_data(&packet)
+			.and_then(PacketData::try_from)
+			.map_err(|e| {
+				Ics04Error::implementation_specific(format!("Failed to decode packet data {e:?}"))
+			})?;
+		let fee = forward.received_amount.saturating_sub(forward.amount);
+		let reverted = Self::revert_receipt(&packet, &packet_data, forward.received_amount, fee);
+		let ack = match reverted {
+			Ok(()) => {
+				ForwardAttempts::<T>::mutate(
+					(
+						forward.packet.destination_port.clone(),
+						forward.packet.destination_channel.clone(),
+					),
+					forward.packet.sequence,
+					|attempts| {
+						if let Some(attempt) = attempts.iter_mut().last() {
+							attempt.status = ForwardStatus::Refunded;
+						}
+					},
+				);
+				Pallet::<T>::deposit_event(Event::<T>::ExecuteMemoIbcTokenTransferRefunded {
+					from: forward.sender.clone(),
+					asset_id: forward.asset_id.clone(),
+					amount: forward.received_amount,
+					source_channel: packet.source_channel.to_string().into_bytes(),
+					sequence: packet.sequence.into(),
+				});
+				Ics20Acknowledgement::Error(format!("{ACK_ERR_STR}: {reason}"))
+			},
+			Err(e) => {
+				log::error!(target: "pallet_ibc", "Failed to refund forwarded transfer: {e:?}");
+				Ics20Acknowledgement::success()
+			},
+		};
+		Self::write_forward_ack(forward.packet, ack.to_string().into_bytes())
+	}
+
+	/// Reverts the receipt of `amount` tokens of a received transfer. The service `fee` charged
+	/// on receipt is part of `amount`, it is returned to the receiver from the fee account first.
+	fn revert_receipt(
+		packet: &Packet,
+		packet_data: &PacketData,
+		amount: u128,
+		fee: u128,
+	) -> Result<(), Ics20Error> {
+		use ibc::{applications::transfer::context::Ics20Reader, bigint::U256};
+		let mut ctx = Context::<T>::default();
+		let denom = PrefixedDenom::from_str(&full_ibc_denom(packet, packet_data.token.clone()))
+			.map_err(|_| Ics20Error::invalid_token())?;
+		let coin =
+			|amount: u128| PrefixedCoin { denom: denom.clone(), amount: U256::from(amount).into() };
+		let receiver = <T as Config>::AccountIdConversion::try_from(packet_data.receiver.clone())
+			.map_err(|_| Ics20Error::parse_account_failure())?;
+		let is_source = is_receiver_chain_source(
+			packet.source_port.clone(),
+			packet.source_channel,
+			&packet_data.token.denom,
+		);
+		with_transaction_unchecked(|| {
+			let result = if fee == 0 {
+				Ok(())
+			} else {
+				ctx.send_coins(&T::FeeAccount::get(), &receiver, &coin(fee))
+			}
+			.and_then(|_| {
+				if is_source {
+					let escrow = ctx.get_channel_escrow_address(
+						&packet.destination_port,
+						packet.destination_channel,
+					)?;
+					ctx.send_coins(&receiver, &escrow, &coin(amount))
+				} else {
+					ctx.burn_coins(&receiver, &coin(amount))
+				}
+			});
+			match result {
+				Ok(()) => TransactionOutcome::Commit(Ok(())),
+				Err(e) => TransactionOutcome::Rollback(Err(e)),
+			}
+		})
+	}
+
+	/// Reverts a received transfer whose memo was rejected and returns the error acknowledgement
+	/// refunding the sender on the source chain. `received` is the packet as it was received and
+	/// `packet` the packet rewritten with the amount left after the service fee, the full received
+	/// amount is reverted. If the receipt cannot be reverted the tokens are left with the receiver
+	/// and the transfer is acknowledged successfully.
+	pub(crate) fn reject_transfer(
+		received: &Packet,
+		packet: &Packet,
+		reason: &str,
+	) -> Acknowledgement {
+		let amount = |packet: &Packet| {
+			Self::received_packet_data(packet)
+				.and_then(PacketData::try_from)
+				.map(|packet_data| (packet_data.token.amount.as_u256().low_u128(), packet_data))
+		};
+		let reverted = amount(received).and_then(|(received_amount, packet_data)| {
+			let (amount, _) = amount(packet)?;
+			let fee = received_amount.saturating_sub(amount);
+			Self::revert_receipt(received, &packet_data, received_amount, fee)
+		});
+		let ack = match reverted {
+			Ok(()) => Ics20Acknowledgement::Error(format!("{ACK_ERR_STR}: {reason}")),
+			Err(e) => {
+				log::error!(target: "pallet_ibc", "Failed to refund rejected transfer: {e:?}");
+				Ics20Acknowledgement::success()
+			},
+		};
+		Acknowledgement::from_bytes(ack.to_string().into_bytes())
+	}
+
+	/// Decodes the data of a packet received on this chain according to the version of its
+	/// destination channel
+	fn received_packet_data(packet: &Packet) -> Result<PacketDataV2, Ics20Error> {
+		decode_packet_data(
+			&Context::<T>::default(),
+			&packet.destination_port,
+			&packet.destination_channel,
+			packet.data.as_slice(),
+		)
+	}
+
+	/// Schedules the forward attempts of the received `packet` to be pruned once they have been
+	/// kept for `ForwardStatusRetention` blocks
+	fn expire_forward_attempts(packet: &PacketInfo) {
+		let key = (packet.destination_port.clone(), packet.destination_channel.clone());
+		if !ForwardAttempts::<T>::contains_key(&key, packet.sequence) {
+			return
+		}
+		let expiry = frame_system::Pallet::<T>::block_number()
+			.saturating_add(T::ForwardStatusRetention::get().max(One::one()));
+		ExpiringForwardAttempts::<T>::insert(expiry, (key, packet.sequence), ());
+	}
+
+	/// Acknowledges the received `packet` once its forward has settled
+	fn write_forward_ack(packet: PacketInfo, ack: Vec<u8>) -> Result<(), Ics04Error> {
+		Self::expire_forward_attempts(&packet);
+		Pallet::<T>::handle_message(HandlerMessage::WriteAck { packet: packet.into(), ack })
+			.map_err(|e| Ics04Error::implementation_specific(format!("{e:?}")))
 	}
 }
 
@@ -4,22 +4,26 @@ use alloc::{
 	string::{String, ToString},
 };
 use core::{fmt::Debug, marker::PhantomData, str::FromStr};
+use frame_support::storage::{with_transaction_unchecked, TransactionOutcome};
 use ibc::{
 	applications::transfer::{
 		acknowledgement::Acknowledgement as Ics20Ack, context::BankKeeper,
-		is_receiver_chain_source, packet::PacketData, TracePrefix,
+		is_receiver_chain_source, packet::PacketDataV2, PrefixedCoin, TracePrefix,
 	},
 	bigint::U256,
 	core::{
 		ics04_channel::{
 			channel::{Counterparty, Order},
+			context::ChannelReader,
 			error::Error as Ics04Error,
 			msgs::acknowledgement::Acknowledgement,
 			packet::Packet,
 			Version,
 		},
 		ics24_host::identifier::{ChannelId, ConnectionId, PortId},
-		ics26_routing::context::{Module as IbcModule, ModuleCallbackContext, ModuleOutputBuilder},
+		ics26_routing::context::{
+			Module as IbcModule, ModuleCallbackContext, ModuleOutputBuilder, OnRecvPacketAck,
+		},
 	},
 	signer::Signer,
 };
@@ -289,25 +293,110 @@ where
 		self.inner.on_chan_close_confirm(ctx, output, port_id, channel_id, relayer)
 	}
 
+	fn on_chan_upgrade_init(
+		&mut self,
+		ctx: &dyn ModuleCallbackContext,
+		output: &mut ModuleOutputBuilder,
+		port_id: &PortId,
+		channel_id: &ChannelId,
+		proposed_order: Order,
+		proposed_connection_hops: &[ConnectionId],
+		proposed_version: &Version,
+	) -> Result<Version, Ics04Error> {
+		self.inner.on_chan_upgrade_init(
+			ctx,
+			output,
+			port_id,
+			channel_id,
+			proposed_order,
+			proposed_connection_hops,
+			proposed_version,
+		)
+	}
+
+	fn on_chan_upgrade_try(
+		&mut self,
+		ctx: &dyn ModuleCallbackContext,
+		output: &mut ModuleOutputBuilder,
+		port_id: &PortId,
+		channel_id: &ChannelId,
+		proposed_order: Order,
+		proposed_connection_hops: &[ConnectionId],
+		counterparty_version: &Version,
+	) -> Result<Version, Ics04Error> {
+		self.inner.on_chan_upgrade_try(
+			ctx,
+			output,
+			port_id,
+			channel_id,
+			proposed_order,
+			proposed_connection_hops,
+			counterparty_version,
+		)
+	}
+
+	fn on_chan_upgrade_ack(
+		&mut self,
+		ctx: &dyn ModuleCallbackContext,
+		output: &mut ModuleOutputBuilder,
+		port_id: &PortId,
+		channel_id: &ChannelId,
+		counterparty_version: &Version,
+	) -> Result<(), Ics04Error> {
+		self.inner
+			.on_chan_upgrade_ack(ctx, output, port_id, channel_id, counterparty_version)
+	}
+
+	fn on_chan_upgrade_open(
+		&mut self,
+		ctx: &dyn ModuleCallbackContext,
+		output: &mut ModuleOutputBuilder,
+		port_id: &PortId,
+		channel_id: &ChannelId,
+		order: Order,
+		connection_hops: &[ConnectionId],
+		version: &Version,
+	) -> Result<(), Ics04Error> {
+		self.inner.on_chan_upgrade_open(
+			ctx,
+			output,
+			port_id,
+			channel_id,
+			order,
+			connection_hops,
+			version,
+		)
+	}
+
 	fn on_recv_packet(
 		&self,
 		_ctx: &dyn ModuleCallbackContext,
 		output: &mut ModuleOutputBuilder,
 		packet: &mut Packet,
 		relayer: &Signer,
-	) -> Result<Acknowledgement, Ics04Error> {
+	) -> Result<OnRecvPacketAck, Ics04Error> {
 		// Module ModuleCallbackContext does not have the ics20 context as part of its trait bounds
 		// so we define a new context
 		let mut ctx = Context::<T>::default();
 		// We want the whole chain of calls to fail only if the ics20 transfer fails, because
 		// the other modules are not part of ics-20 standard
 		let ack = self.inner.on_recv_packet(&ctx, output, packet, relayer)?;
-		let _ = Self::process_fee(&mut ctx, packet, &ack).map_err(|e| {
-			log::error!(target: "pallet_ibc", "Error processing fee: {:?}", e);
-		});
+		if let OnRecvPacketAck::Sync(ack) = &ack {
+			let _ = Self::process_fee(&mut ctx, packet, ack).map_err(|e| {
+				log::error!(target: "pallet_ibc", "Error processing fee: {:?}", e);
+			});
+		}
 		Ok(ack)
 	}
 
+	fn on_write_acknowledgement(
+		&self,
+		packet: &Packet,
+		ack: Acknowledgement,
+	) -> Result<Acknowledgement, Ics04Error> {
+		self.inner.on_write_acknowledgement(packet, ack)
+	}
+
 	fn on_acknowledgement_packet(
 		&mut self,
 		ctx: &dyn ModuleCallbackContext,
@@ -343,8 +432,12 @@ where
 		packet: &mut Packet,
 		ack: &Acknowledgement,
 	) -> Result<(), Ics04Error> {
-		let mut packet_data = serde_json::from_slice::<PacketData>(packet.data.as_slice())
-			.map_err(|e| {
+		let version = ctx
+			.channel_end(&(packet.destination_port.clone(), packet.destination_channel))?
+			.version()
+			.clone();
+		let mut packet_data =
+			PacketDataV2::decode(&version, packet.data.as_slice()).map_err(|e| {
 				Ics04Error::implementation_specific(format!("Failed to decode packet data {e:?}"))
 			})?;
 
@@ -357,7 +450,6 @@ where
 			return Ok(())
 		}
 
-		let percent = ServiceChargeIn::<T>::get().unwrap_or(T::ServiceChargeIn::get());
 		let parsed_ack = String::from_utf8(ack.clone().into_bytes())
 			.map_err(|e| {
 				Ics04Error::implementation_specific(format!(
@@ -374,72 +466,93 @@ where
 		// Send full amount to receiver using the default ics20 logic
 		// We only take the fee charge if the acknowledgement is not an error
 		if parsed_ack.is_successful() {
-			let mut prefixed_coin = if is_receiver_chain_source(
-				packet.source_port.clone(),
-				packet.source_channel,
-				&packet_data.token.denom,
-			) {
-				let prefix = TracePrefix::new(packet.source_port.clone(), packet.source_channel);
-				let mut c = packet_data.token.clone();
-				c.denom.remove_trace_prefix(&prefix);
-				c
-			} else {
-				let prefix =
-					TracePrefix::new(packet.destination_port.clone(), packet.destination_channel);
-				let mut c = packet_data.token.clone();
-				c.denom.add_trace_prefix(prefix);
-				c
-			};
-
-			// At this point the asset SHOULD exist
-			let asset_id =
-				<T as crate::Config>::IbcDenomToAssetIdConversion::from_denom_to_asset_id(
-					&prefixed_coin.denom.to_string(),
-				)
-				.map_err(|_| {
-					log::warn!(target: "pallet_ibc", "Asset does not exist for denom: {}", prefixed_coin.denom.to_string());
-					Ics04Error::implementation_specific("asset does not exist".to_string())
-				})?;
-			let amount = packet_data.token.amount.as_u256().low_u128();
-			let mut fee = {
-				let fee_asset_id = T::FlatFeeAssetId::get();
-				let fee_asset_amount = T::FlatFeeAmount::get();
-
-				T::FlatFeeConverter::get_flat_fee(asset_id.clone(), fee_asset_id, fee_asset_amount)
-					.unwrap_or_else(|| {
-						// We have ensured that token amounts larger than the max value for
-						// a u128 are rejected in the ics20 on_recv_packet callback so we
-						// can multiply safely. Percent does Non-Overflowing multiplication
-						// so this is infallible
-						percent * amount
-					})
-			};
-
-			fee = fee.min(amount);
-
 			let receiver =
 				<T as crate::Config>::AccountIdConversion::try_from(packet_data.receiver.clone())
 					.map_err(|_| {
 					Ics04Error::implementation_specific("Failed to receiver account".to_string())
 				})?;
-			prefixed_coin.amount = fee.into();
-			// Now we proceed to send the service fee from the receiver's account to the pallet
-			// account
-			let fee_account = T::FeeAccount::get();
-			ctx.send_coins(&receiver, &fee_account, &prefixed_coin)
-				.map_err(|e| Ics04Error::app_module(e.to_string()))?;
+			// The fee is charged on every token of the packet or on none of them
+			with_transaction_unchecked(|| {
+				let result = packet_data
+					.tokens
+					.iter_mut()
+					.try_for_each(|token| Self::charge_fee(ctx, packet, &receiver, token));
+				match result {
+					Ok(()) => TransactionOutcome::Commit(Ok(())),
+					Err(e) => TransactionOutcome::Rollback(Err(e)),
+				}
+			})?;
 			// We modify the packet data to remove the fee so any other middleware has access to the
 			// correct amount deposited in the receiver's account
-			packet_data.token.amount =
-				(packet_data.token.amount.as_u256() - U256::from(fee)).into();
-			packet.data = serde_json::to_vec(&packet_data).map_err(|_| {
-				Ics04Error::implementation_specific("Failed to receiver account".to_string())
+			packet.data = packet_data.encode(&version).map_err(|e| {
+				Ics04Error::implementation_specific(format!("Failed to encode packet data {e:?}"))
 			})?;
-			Pallet::<T>::deposit_event(Event::<T>::IbcTransferFeeCollected {
-				amount: fee.into(),
-				asset_id: asset_id.clone(),
-			})
 		}
 		Ok(())
 	}
+
+	/// Sends the service fee on `token` from the receiver to the fee account and deducts it from
+	/// the token amount.
+	fn charge_fee(
+		ctx: &mut Context<T>,
+		packet: &Packet,
+		receiver: &<T as crate::Config>::AccountIdConversion,
+		token: &mut PrefixedCoin,
+	) -> Result<(), Ics04Error> {
+		let percent = ServiceChargeIn::<T>::get().unwrap_or(T::ServiceChargeIn::get());
+		let mut prefixed_coin = if is_receiver_chain_source(
+			packet.source_port.clone(),
+			packet.source_channel,
+			&token.denom,
+		) {
+			let prefix = TracePrefix::new(packet.source_port.clone(), packet.source_channel);
+			let mut c = token.clone();
+			c.denom.remove_trace_prefix(&prefix);
+			c
+		} else {
+			let prefix =
+				TracePrefix::new(packet.destination_port.clone(), packet.destination_channel);
+			let mut c = token.clone();
+			c.denom.add_trace_prefix(prefix);
+			c
+		};
+
+		// At this point the asset SHOULD exist
+		let asset_id = <T as crate::Config>::IbcDenomToAssetIdConversion::from_denom_to_asset_id(
+			&prefixed_coin.denom.to_string(),
+		)
+		.map_err(|_| {
+			log::warn!(target: "pallet_ibc", "Asset does not exist for denom: {}", prefixed_coin.denom.to_string());
+			Ics04Error::implementation_specific("asset does not exist".to_string())
+		})?;
+		let amount = token.amount.as_u256().low_u128();
+		let mut fee = {
+			let fee_asset_id = T::FlatFeeAssetId::get();
+			let fee_asset_amount = T::FlatFeeAmount::get();
+
+			T::FlatFeeConverter::get_flat_fee(asset_id.clone(), fee_asset_id, fee_asset_amount)
+				.unwrap_or_else(|| {
+					// We have ensured that token amounts larger than the max value for
+					// a u128 are rejected in the ics20 on_recv_packet callback so we
+					// can multiply safely. Percent does Non-Overflowing multiplication
+					// so this is infallible
+					percent * amount
+				})
+		};
+
+		fee = fee.min(amount);
+
+		prefixed_coin.amount = fee.into();
+		// Now we proceed to send the service fee from the receiver's account to the pallet
+		// account
+		let fee_account = T::FeeAccount::get();
+		ctx.send_coins(receiver, &fee_account, &prefixed_coin)
+			.map_err(|e| Ics04Error::app_module(e.to_string()))?;
+		token.amount = (token.amount.as_u256() - U256::from(fee)).into();
+		Pallet::<T>::deposit_event(Event::<T>::IbcTransferFeeCollected {
+			amount: fee.into(),
+			asset_id,
+		});
+		Ok(())
+	}
 }
@@ -0,0 +1,161 @@
+//! Runtime call hooks for ICS-20 transfers.
+//!
+//! A transfer received with a `{"call": "0x<scale encoded runtime call>"}` memo dispatches the
+//! call once the tokens are received. The call is dispatched from an account derived from the
+//! destination channel and the sender of the transfer, see [`Pallet::hooks_account`], which must
+//! also be the receiver of the tokens so the call can spend them.
+//!
+//! Only calls allowed by [`Config::CallFilter`] whose weight does not exceed
+//! [`Config::MaxCallWeight`] are dispatched. If the call is rejected or fails the transfer is
+//! acknowledged with an error so that the sender is refunded on the source chain.
+use crate::ics20::{MemoAction, MemoExecution};
+use alloc::{
+	format,
+	string::{String, ToString},
+	vec::Vec,
+};
+use codec::{Decode, Encode};
+use core::marker::PhantomData;
+use frame_support::{
+	dispatch::{GetDispatchInfo, PostDispatchInfo},
+	storage::{with_transaction_unchecked, TransactionOutcome},
+	traits::Contains,
+	weights::Weight,
+};
+use ibc::{
+	applications::transfer::{error::Error as Ics20Error, packet::PacketData},
+	core::{ics04_channel::packet::Packet, ics24_host::identifier::ChannelId},
+};
+use sp_core::hashing::blake2_256;
+use sp_runtime::traits::{Dispatchable, Get, IdentifyAccount, TrailingZeroInput};
+
+pub use pallet::*;
+
+#[frame_support::pallet]
+pub mod pallet {
+	use super::*;
+	use frame_support::pallet_prelude::*;
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config + crate::Config {
+		/// The overarching event type.
+		type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
+		/// Runtime calls memos dispatch
+		type RuntimeCall: Parameter
+			+ Dispatchable<
+				RuntimeOrigin = <Self as frame_system::Config>::RuntimeOrigin,
+				PostInfo = PostDispatchInfo,
+			> + GetDispatchInfo;
+		/// Runtime calls memos are allowed to dispatch
+		type CallFilter: Contains<<Self as Config>::RuntimeCall>;
+		/// Maximum weight of the call dispatched by a single memo
+		#[pallet::constant]
+		type MaxCallWeight: Get<Weight>;
+	}
+
+	#[pallet::pallet]
+	#[pallet::without_storage_info]
+	pub struct Pallet<T>(_);
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub (super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// The call in the memo of a received transfer was dispatched
+		CallDispatched { account: T::AccountId, channel_id: Vec<u8>, sequence: u64 },
+		/// The call in the memo of a received transfer was rejected or failed, the transfer is
+		/// refunded
+		CallRejected { account: T::AccountId, channel_id: Vec<u8>, sequence: u64, reason: Vec<u8> },
+	}
+
+	impl<T: Config> Pallet<T> {
+		/// Account memo calls of transfers sent by `sender` to `channel_id` on this chain are
+		/// dispatched from
+		pub fn hooks_account(channel_id: &ChannelId, sender: &str) -> T::AccountId {
+			let entropy = (b"ibc-hooks", channel_id.to_string(), sender).using_encoded(blake2_256);
+			T::AccountId::decode(&mut TrailingZeroInput::new(entropy.as_ref()))
+				.expect("infinite length input; no invalid inputs for type; qed")
+		}
+	}
+}
+
+#[derive(serde::Deserialize)]
+struct CallMemo {
+	/// Hex encoded runtime call
+	call: String,
+}
+
+/// [`MemoAction`] dispatching the runtime call of a `{"call": ..}` memo
+pub struct RuntimeCallMemo<T>(PhantomData<T>);
+
+impl<T: Config> RuntimeCallMemo<T> {
+	fn dispatch(
+		account: &T::AccountId,
+		packet_data: &PacketData,
+		call: &str,
+	) -> Result<(), String> {
+		let receiver =
+			<T as crate::Config>::AccountIdConversion::try_from(packet_data.receiver.clone())
+				.map_err(|_| "invalid receiver".to_string())?
+				.into_account();
+		if &receiver != account {
+			return Err("receiver is not the hooks account of the sender".to_string())
+		}
+		let call = call
+			.strip_prefix("0x")
+			.and_then(|call| hex::decode(call).ok())
+			.ok_or_else(|| "call is not hex encoded".to_string())?;
+		let call = <T as Config>::RuntimeCall::decode(&mut call.as_slice())
+			.map_err(|e| format!("failed to decode call: {e:?}"))?;
+		if !T::CallFilter::contains(&call) {
+			return Err("call is not allowed".to_string())
+		}
+		if call.get_dispatch_info().weight.any_gt(T::MaxCallWeight::get()) {
+			return Err("call exceeds the weight limit".to_string())
+		}
+		with_transaction_unchecked(|| {
+			let origin = frame_system::RawOrigin::Signed(account.clone()).into();
+			match call.dispatch(origin) {
+				Ok(_) => TransactionOutcome::Commit(Ok(())),
+				Err(e) => TransactionOutcome::Rollback(Err(format!("{:?}", e.error))),
+			}
+		})
+	}
+}
+
+impl<T: Config> MemoAction<T> for RuntimeCallMemo<T> {
+	fn execute(
+		packet: &Packet,
+		packet_data: &PacketData,
+	) -> Option<Result<MemoExecution, Ics20Error>> {
+		let memo = serde_json::from_str::<CallMemo>(&packet_data.memo).ok()?;
+		let account =
+			Pallet::<T>::hooks_account(&packet.destination_channel, packet_data.sender.as_ref());
+		let channel_id = packet.destination_channel.to_string().into_bytes();
+		let sequence = packet.sequence.into();
+		let execution = match RuntimeCallMemo::<T>::dispatch(&account, packet_data, &memo.call) {
+			Ok(()) => {
+				Pallet::<T>::deposit_event(Event::<T>::CallDispatched {
+					account,
+					channel_id,
+					sequence,
+				});
+				MemoExecution::Completed
+			},
+			Err(reason) => {
+				log::debug!(target: "pallet_ibc", "[ics20_hooks] call rejected: {reason}");
+				Pallet::<T>::deposit_event(Event::<T>::CallRejected {
+					account,
+					channel_id,
+					sequence,
+					reason: reason.as_bytes().to_vec(),
+				});
+				MemoExecution::Rejected(reason)
+			},
+		};
+		Some(Ok(execution))
+	}
+
+	fn max_weight() -> Weight {
+		T::MaxCallWeight::get()
+	}
+}
@@ -5,13 +5,89 @@
 //! just to recalculate its root hash.
 
 pub mod acknowledgements;
+pub mod channel_upgrades;
 pub mod channels;
 pub mod client_states;
 pub mod clients;
 pub mod connections;
 pub mod consensus_states;
+pub mod counterparty_upgrades;
 pub mod next_seq_ack;
 pub mod next_seq_recv;
 pub mod next_seq_send;
 pub mod packet_commitments;
 pub mod receipts;
+pub mod upgrade_errors;
+
+use crate::Config;
+use alloc::string::String;
+use frame_support::storage::{child, child::ChildInfo};
+use ibc::core::ics24_host::Path;
+use ibc_primitives::{apply_prefix, PageRequest, PageResponse, DEFAULT_PAGE_LIMIT, MAX_PAGE_LIMIT};
+use sp_core::Get;
+use sp_std::{prelude::*, str::FromStr};
+
+/// Iterates over the entries of the ibc child trie under a key prefix, in key order.
+/// Unlike `ChildTriePrefixIterator` it yields the full trie keys, so that an iteration can be
+/// resumed from any of them.
+pub struct KeyedPrefixIterator {
+	child_info: ChildInfo,
+	prefix: Vec<u8>,
+	next_key: Option<Vec<u8>>,
+}
+
+impl KeyedPrefixIterator {
+	/// Iterates over the keys starting with `prefix_path`, starting at `start_key` (inclusive) if
+	/// it is set. A `start_key` outside of the prefix yields nothing.
+	pub fn new<T: Config>(prefix_path: String, start_key: Option<Vec<u8>>) -> Self {
+		let prefix = apply_prefix(T::PalletPrefix::get(), vec![prefix_path]);
+		let next_key = match start_key {
+			Some(key) => key.starts_with(&prefix).then_some(key),
+			None => Some(prefix.clone()),
+		};
+		Self { child_info: ChildInfo::new_default(T::PalletPrefix::get()), prefix, next_key }
+	}
+}
+
+impl Iterator for KeyedPrefixIterator {
+	type Item = (Vec<u8>, Vec<u8>);
+
+	fn next(&mut self) -> Option<Self::Item> {
+		loop {
+			let key = self.next_key.take()?;
+			self.next_key =
+				sp_io::default_child_storage::next_key(self.child_info.storage_key(), &key)
+					.filter(|next_key| next_key.starts_with(&self.prefix));
+			// The start key itself may not be stored
+			if let Some(value) = child::get_raw(&self.child_info, &key) {
+				return Some((key, value))
+			}
+		}
+	}
+}
+
+/// Parses the ibc path a trie key was derived from.
+pub fn trie_key_path<T: Config>(key: &[u8]) -> Option<Path> {
+	let path = core::str::from_utf8(key.strip_prefix(T::PalletPrefix::get())?).ok()?;
+	Path::from_str(path).ok()
+}
+
+/// Collects a single page of the items yielded by `iter_from`, which is called with the trie key
+/// to resume from. Items are paired with their trie key, the key of the first item left out is
+/// returned as the cursor of the next page. At most [`MAX_PAGE_LIMIT`] + 1 items are read.
+pub fn paginate<I, Iter>(
+	page: PageRequest,
+	iter_from: impl FnOnce(Option<Vec<u8>>) -> Iter,
+) -> (Vec<I>, PageResponse)
+where
+	Iter: Iterator<Item = (Vec<u8>, I)>,
+{
+	let limit = match page.limit {
+		0 => DEFAULT_PAGE_LIMIT,
+		limit => limit.min(MAX_PAGE_LIMIT),
+	};
+	let mut iter = iter_from(page.key);
+	let items = iter.by_ref().take(limit as usize).map(|(_, item)| item).collect();
+	let next_key = iter.next().map(|(key, _)| key);
+	(items, PageResponse { next_key })
+}
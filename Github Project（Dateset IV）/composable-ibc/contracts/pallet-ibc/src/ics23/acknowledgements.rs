@@ -1,4 +1,8 @@
-use crate::{format, Config};
+use crate::{
+	format,
+	ics23::{trie_key_path, KeyedPrefixIterator},
+	Config,
+};
 use alloc::string::{String, ToString};
 use frame_support::storage::{child, child::ChildInfo, ChildTriePrefixIterator};
 use ibc::core::{
@@ -49,6 +53,22 @@ impl<T: Config> Acknowledgements<T> {
 		child::exists(&ChildInfo::new_default(T::PalletPrefix::get()), &ack_key)
 	}
 
+	/// Iterates over the acknowledgements of a channel end in trie key order, starting at
+	/// `start_key` if it is set. Items are paired with their trie key.
+	pub fn iter_channel_from(
+		port_id: PortId,
+		channel_id: ChannelId,
+		start_key: Option<Vec<u8>>,
+	) -> impl Iterator<Item = (Vec<u8>, (Sequence, Vec<u8>))> {
+		let prefix = format!("acks/ports/{port_id}/channels/{channel_id}/sequences/");
+		KeyedPrefixIterator::new::<T>(prefix, start_key).filter_map(|(key, value)| {
+			if let Path::Acks(AcksPath { sequence, .. }) = trie_key_path::<T>(&key)? {
+				return Some((key, (sequence, value)))
+			}
+			None
+		})
+	}
+
 	// WARNING: too expensive to be called from an on-chain context, only here for rpc layer.
 	pub fn iter() -> impl Iterator<Item = ((PortId, ChannelId, Sequence), Vec<u8>)> {
 		let prefix = "acks/ports/".to_string();
@@ -1,6 +1,10 @@
-use crate::{format, Config};
-use alloc::string::{String, ToString};
-use frame_support::storage::{child, child::ChildInfo, ChildTriePrefixIterator};
+use crate::{
+	format,
+	ics23::{trie_key_path, KeyedPrefixIterator},
+	Config,
+};
+use alloc::string::ToString;
+use frame_support::storage::{child, child::ChildInfo};
 use ibc::core::{
 	ics04_channel::channel::ChannelEnd,
 	ics24_host::{
@@ -11,7 +15,7 @@ use ibc::core::{
 };
 use ibc_primitives::apply_prefix;
 use sp_core::Get;
-use sp_std::{marker::PhantomData, prelude::*, str::FromStr};
+use sp_std::{marker::PhantomData, prelude::*};
 use tendermint_proto::Protobuf;
 
 // todo: pruning
@@ -38,21 +42,25 @@ impl<T: Config> Channels<T> {
 
 	// WARNING: too expensive to be called from an on-chain context, only here for rpc layer.
 	pub fn iter() -> impl Iterator<Item = (Vec<u8>, Vec<u8>, Vec<u8>)> {
-		let prefix = "channelEnds/ports/".to_string();
-		let key = apply_prefix(T::PalletPrefix::get(), vec![prefix.clone()]);
-		ChildTriePrefixIterator::with_prefix(&ChildInfo::new_default(T::PalletPrefix::get()), &key)
-			.filter_map(move |(key, value)| {
-				let path = format!("{prefix}{}", String::from_utf8(key).ok()?);
+		Self::iter_from(None).map(|(_, channel)| channel)
+	}
+
+	/// Iterates over the channel ends in trie key order, starting at `start_key` if it is set.
+	/// Items are paired with their trie key.
+	pub fn iter_from(
+		start_key: Option<Vec<u8>>,
+	) -> impl Iterator<Item = (Vec<u8>, (Vec<u8>, Vec<u8>, Vec<u8>))> {
+		KeyedPrefixIterator::new::<T>("channelEnds/ports/".to_string(), start_key).filter_map(
+			|(key, value)| {
 				if let Path::ChannelEnds(ChannelEndsPath(port_id, channel_id)) =
-					Path::from_str(&path).ok()?
+					trie_key_path::<T>(&key)?
 				{
-					return Some((
-						port_id.as_bytes().to_vec(),
-						channel_id.to_string().into_bytes(),
-						value,
-					))
+					let channel =
+						(port_id.as_bytes().to_vec(), channel_id.to_string().into_bytes(), value);
+					return Some((key, channel))
 				}
 				None
-			})
+			},
+		)
 	}
 }
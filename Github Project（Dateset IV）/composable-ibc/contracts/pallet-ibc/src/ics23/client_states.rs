@@ -1,10 +1,14 @@
-use crate::{format, Config};
-use alloc::string::{String, ToString};
-use frame_support::storage::{child, child::ChildInfo, ChildTriePrefixIterator};
+use crate::{
+	format,
+	ics23::{trie_key_path, KeyedPrefixIterator},
+	Config,
+};
+use alloc::string::ToString;
+use frame_support::storage::{child, child::ChildInfo};
 use ibc::core::ics24_host::{identifier::ClientId, path::ClientStatePath, Path};
 use ibc_primitives::apply_prefix;
 use sp_core::Get;
-use sp_std::{marker::PhantomData, prelude::*, str::FromStr};
+use sp_std::{marker::PhantomData, prelude::*};
 
 /// client_id => client_states
 /// trie key path: "clients/{client_id}/clientState"
@@ -36,15 +40,21 @@ impl<T: Config> ClientStates<T> {
 	// WARNING: too expensive to be called from an on-chain context, only here for rpc layer.
 	// client_id => client_state
 	pub fn iter() -> impl Iterator<Item = (ClientId, Vec<u8>)> {
-		let prefix_path = "clients/".to_string();
-		let key = apply_prefix(T::PalletPrefix::get(), vec![prefix_path.clone()]);
-		ChildTriePrefixIterator::with_prefix(&ChildInfo::new_default(T::PalletPrefix::get()), &key)
-			.filter_map(move |(remaining_key, value)| {
-				let path = format!("{prefix_path}{}", String::from_utf8(remaining_key).ok()?);
-				if let Path::ClientState(ClientStatePath(client_id)) = Path::from_str(&path).ok()? {
-					return Some((client_id, value))
+		Self::iter_from(None).map(|(_, client_state)| client_state)
+	}
+
+	/// Iterates over the client states in trie key order, starting at `start_key` if it is set.
+	/// Items are paired with their trie key.
+	pub fn iter_from(
+		start_key: Option<Vec<u8>>,
+	) -> impl Iterator<Item = (Vec<u8>, (ClientId, Vec<u8>))> {
+		KeyedPrefixIterator::new::<T>("clients/".to_string(), start_key).filter_map(
+			|(key, value)| {
+				if let Path::ClientState(ClientStatePath(client_id)) = trie_key_path::<T>(&key)? {
+					return Some((key, (client_id, value)))
 				}
 				None
-			})
+			},
+		)
 	}
 }
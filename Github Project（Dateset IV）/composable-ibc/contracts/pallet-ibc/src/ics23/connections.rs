@@ -1,9 +1,13 @@
-use crate::{format, Config};
+use crate::{
+	format,
+	ics23::{trie_key_path, KeyedPrefixIterator},
+	Config,
+};
 use alloc::string::ToString;
-use frame_support::storage::{child, child::ChildInfo, ChildTriePrefixIterator};
+use frame_support::storage::{child, child::ChildInfo};
 use ibc::core::{
 	ics03_connection::connection::ConnectionEnd,
-	ics24_host::{identifier::ConnectionId, path::ConnectionsPath},
+	ics24_host::{identifier::ConnectionId, path::ConnectionsPath, Path},
 };
 use ibc_primitives::apply_prefix;
 use sp_core::Get;
@@ -32,10 +36,19 @@ impl<T: Config> Connections<T> {
 		);
 	}
 
-	// WARNING: too expensive to be called from an on-chain context, only here for rpc layer.
-	pub fn iter() -> ChildTriePrefixIterator<(Vec<u8>, Vec<u8>)> {
-		let prefix_path = "connections/".to_string();
-		let key = apply_prefix(T::PalletPrefix::get(), vec![prefix_path]);
-		ChildTriePrefixIterator::with_prefix(&ChildInfo::new_default(T::PalletPrefix::get()), &key)
+	/// Iterates over the connection ends in trie key order, starting at `start_key` if it is set.
+	/// Items are paired with their trie key.
+	pub fn iter_from(
+		start_key: Option<Vec<u8>>,
+	) -> impl Iterator<Item = (Vec<u8>, (ConnectionId, Vec<u8>))> {
+		KeyedPrefixIterator::new::<T>("connections/".to_string(), start_key).filter_map(
+			|(key, value)| {
+				if let Path::Connections(ConnectionsPath(connection_id)) = trie_key_path::<T>(&key)?
+				{
+					return Some((key, (connection_id, value)))
+				}
+				None
+			},
+		)
 	}
 }
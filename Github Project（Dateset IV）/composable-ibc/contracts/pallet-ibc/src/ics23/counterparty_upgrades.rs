@@ -0,0 +1,41 @@
+use crate::{format, Config};
+use frame_support::storage::{child, child::ChildInfo};
+use ibc::core::{
+	ics04_channel::upgrade::Upgrade,
+	ics24_host::{
+		identifier::{ChannelId, PortId},
+		path::CounterpartyChannelUpgradePath,
+	},
+};
+use ibc_primitives::apply_prefix;
+use sp_core::Get;
+use sp_std::{marker::PhantomData, prelude::*};
+use tendermint_proto::Protobuf;
+
+/// (port_id, channel_id) => Upgrade
+/// trie key path: "channelUpgrades/counterpartyUpgrade/ports/{port_id}/channels/{channel_id}"
+pub struct CounterpartyChannelUpgrades<T>(PhantomData<T>);
+
+impl<T: Config> CounterpartyChannelUpgrades<T> {
+	pub fn get(port_id: PortId, channel_id: ChannelId) -> Option<Vec<u8>> {
+		let path = format!("{}", CounterpartyChannelUpgradePath(port_id, channel_id));
+		let key = apply_prefix(T::PalletPrefix::get(), vec![path]);
+		child::get(&ChildInfo::new_default(T::PalletPrefix::get()), &key)
+	}
+
+	pub fn insert(port_id: PortId, channel_id: ChannelId, upgrade: &Upgrade) {
+		let path = format!("{}", CounterpartyChannelUpgradePath(port_id, channel_id));
+		let key = apply_prefix(T::PalletPrefix::get(), vec![path]);
+		child::put(
+			&ChildInfo::new_default(T::PalletPrefix::get()),
+			&key,
+			&upgrade.encode_vec().expect("encode counterparty channel upgrade"),
+		);
+	}
+
+	pub fn remove(port_id: PortId, channel_id: ChannelId) {
+		let path = format!("{}", CounterpartyChannelUpgradePath(port_id, channel_id));
+		let key = apply_prefix(T::PalletPrefix::get(), vec![path]);
+		child::kill(&ChildInfo::new_default(T::PalletPrefix::get()), &key)
+	}
+}
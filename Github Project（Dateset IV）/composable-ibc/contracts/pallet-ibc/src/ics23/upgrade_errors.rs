@@ -0,0 +1,35 @@
+use crate::{format, Config};
+use frame_support::storage::{child, child::ChildInfo};
+use ibc::core::{
+	ics04_channel::upgrade::ErrorReceipt,
+	ics24_host::{
+		identifier::{ChannelId, PortId},
+		path::ChannelUpgradeErrorPath,
+	},
+};
+use ibc_primitives::apply_prefix;
+use sp_core::Get;
+use sp_std::{marker::PhantomData, prelude::*};
+use tendermint_proto::Protobuf;
+
+/// (port_id, channel_id) => ErrorReceipt
+/// trie key path: "channelUpgrades/upgradeError/ports/{port_id}/channels/{channel_id}"
+pub struct UpgradeErrors<T>(PhantomData<T>);
+
+impl<T: Config> UpgradeErrors<T> {
+	pub fn get(port_id: PortId, channel_id: ChannelId) -> Option<Vec<u8>> {
+		let path = format!("{}", ChannelUpgradeErrorPath(port_id, channel_id));
+		let key = apply_prefix(T::PalletPrefix::get(), vec![path]);
+		child::get(&ChildInfo::new_default(T::PalletPrefix::get()), &key)
+	}
+
+	pub fn insert(port_id: PortId, channel_id: ChannelId, error_receipt: &ErrorReceipt) {
+		let path = format!("{}", ChannelUpgradeErrorPath(port_id, channel_id));
+		let key = apply_prefix(T::PalletPrefix::get(), vec![path]);
+		child::put(
+			&ChildInfo::new_default(T::PalletPrefix::get()),
+			&key,
+			&error_receipt.encode_vec().expect("encode upgrade error receipt"),
+		);
+	}
+}
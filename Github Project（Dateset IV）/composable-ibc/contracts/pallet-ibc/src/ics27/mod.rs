@@ -0,0 +1,889 @@
+//! ICS-27 interchain accounts.
+//!
+//! As a host, controllers on counterparty chains get an account derived from their connection and
+//! port and can dispatch the runtime calls allowed by [`Config::CallFilter`] from it. Calls are
+//! sent as `Any` messages with the [`RUNTIME_CALL_TYPE_URL`] type url and a scale encoded runtime
+//! call as value. The calls of a packet may not weigh more than
+//! [`crate::Config::IcaMaxTxWeight`], which is charged for every packet received on the host port.
+//!
+//! As a controller, accounts on this chain can register interchain accounts on counterparty
+//! chains and send them transactions to execute.
+use crate::{routing::Context, WeightInfo};
+use alloc::{
+	format,
+	string::{String, ToString},
+	vec,
+	vec::Vec,
+};
+use codec::{Decode, Encode};
+use core::{marker::PhantomData, str::FromStr};
+use frame_support::{
+	dispatch::{GetDispatchInfo, PostDispatchInfo},
+	storage::{with_transaction_unchecked, TransactionOutcome},
+	traits::Contains,
+	weights::Weight,
+};
+use ibc::{
+	applications::{
+		interchain_accounts::{
+			context::{InterchainAccountControllerContext, InterchainAccountHostContext},
+			controller,
+			error::Error as IcaError,
+			host,
+		},
+		transfer::acknowledgement::Acknowledgement as GenericAck,
+	},
+	core::{
+		ics03_connection::context::ConnectionReader,
+		ics04_channel::{
+			channel::{ChannelEnd, Counterparty, Order, State},
+			context::ChannelReader,
+			error::Error as Ics04Error,
+			msgs::acknowledgement::Acknowledgement,
+			packet::Packet,
+			Version,
+		},
+		ics24_host::identifier::{ChannelId, ConnectionId, PortId},
+		ics26_routing::context::{
+			Module as IbcModule, ModuleCallbackContext, ModuleOutputBuilder, OnRecvPacketAck,
+		},
+	},
+	signer::Signer,
+};
+use ibc_primitives::CallbackWeight;
+use ibc_proto::google::protobuf::Any;
+use sp_core::{crypto::AccountId32, hashing::blake2_256};
+use sp_runtime::traits::{Dispatchable, Get, TrailingZeroInput};
+
+pub use pallet::*;
+
+/// Type url of the messages carrying a scale encoded runtime call
+pub const RUNTIME_CALL_TYPE_URL: &str = "/pallet_ibc.ics27.RuntimeCall";
+
+#[frame_support::pallet]
+pub mod pallet {
+	use super::*;
+	use frame_support::pallet_prelude::*;
+	use frame_system::pallet_prelude::*;
+	use ibc::applications::interchain_accounts::{controller_port_id, metadata::Metadata};
+	use ibc_primitives::{HandlerMessage, IbcHandler, Timeout};
+
+	/// A message sent to an interchain account, the value is encoded as expected by the host chain
+	#[derive(Clone, Encode, Decode, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+	pub struct InterchainAccountMessage {
+		/// Protobuf type url of the message as utf8 string bytes
+		pub type_url: Vec<u8>,
+		/// Encoded message
+		pub value: Vec<u8>,
+	}
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config + crate::Config {
+		/// The overarching event type.
+		type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
+		/// Runtime calls interchain accounts dispatch
+		type RuntimeCall: Parameter
+			+ Dispatchable<
+				RuntimeOrigin = <Self as frame_system::Config>::RuntimeOrigin,
+				PostInfo = PostDispatchInfo,
+			> + GetDispatchInfo;
+		/// Runtime calls controllers are allowed to dispatch from their interchain accounts
+		type CallFilter: Contains<<Self as Config>::RuntimeCall>;
+		/// Whether counterparty controllers can register accounts on this chain
+		#[pallet::constant]
+		type IsHostEnabled: Get<bool>;
+		/// Whether accounts on this chain can register interchain accounts on counterparty chains
+		#[pallet::constant]
+		type IsControllerEnabled: Get<bool>;
+	}
+
+	#[pallet::pallet]
+	#[pallet::without_storage_info]
+	pub struct Pallet<T>(_);
+
+	#[pallet::storage]
+	#[allow(clippy::disallowed_types)]
+	/// Interchain accounts hosted on this chain, keyed by (connection_id, controller_port_id)
+	pub type HostAccounts<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		Vec<u8>,
+		Blake2_128Concat,
+		Vec<u8>,
+		T::AccountId,
+		OptionQuery,
+	>;
+
+	#[pallet::storage]
+	#[allow(clippy::disallowed_types)]
+	/// Channel used by a controller port on the host port, keyed by (connection_id,
+	/// controller_port_id)
+	pub type HostActiveChannels<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		Vec<u8>,
+		Blake2_128Concat,
+		Vec<u8>,
+		Vec<u8>,
+		OptionQuery,
+	>;
+
+	#[pallet::storage]
+	#[allow(clippy::disallowed_types)]
+	/// Channel and interchain account address of a controller port, keyed by (connection_id,
+	/// controller_port_id)
+	pub type ControllerActiveChannels<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		Vec<u8>,
+		Blake2_128Concat,
+		Vec<u8>,
+		(Vec<u8>, Vec<u8>),
+		OptionQuery,
+	>;
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub (super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// A counterparty controller registered an interchain account on this chain
+		HostAccountRegistered {
+			connection_id: Vec<u8>,
+			controller_port_id: Vec<u8>,
+			account: T::AccountId,
+		},
+		/// A transaction sent by a counterparty controller was executed
+		HostTxExecuted { port_id: Vec<u8>, channel_id: Vec<u8>, sequence: u64, success: bool },
+		/// An interchain account was registered on a counterparty chain
+		ControllerAccountRegistered {
+			connection_id: Vec<u8>,
+			port_id: Vec<u8>,
+			channel_id: Vec<u8>,
+			address: Vec<u8>,
+		},
+		/// A transaction was sent to an interchain account
+		ControllerTxSent { connection_id: Vec<u8>, port_id: Vec<u8>, channel_id: Vec<u8> },
+		/// A transaction sent to an interchain account was acknowledged
+		ControllerTxAcknowledged {
+			port_id: Vec<u8>,
+			channel_id: Vec<u8>,
+			sequence: u64,
+			success: bool,
+		},
+		/// A transaction sent to an interchain account timed out, the channel is now closed
+		ControllerTxTimedOut { port_id: Vec<u8>, channel_id: Vec<u8>, sequence: u64 },
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// Connection identifier is invalid or the connection does not exist
+		InvalidConnection,
+		/// The controller is disabled
+		ControllerDisabled,
+		/// Failed to derive the controller port of the owner
+		InvalidOwner,
+		/// Failed to open the controller channel
+		ChannelInitError,
+		/// No open channel for the interchain account
+		NoActiveChannel,
+		/// The transaction has no messages or an invalid type url
+		InvalidTx,
+		/// Failed to send the transaction packet
+		SendPacketError,
+	}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T>
+	where
+		T: Send + Sync,
+		AccountId32: From<<T as frame_system::Config>::AccountId>,
+		u32: From<<T as frame_system::Config>::BlockNumber>,
+	{
+		/// Register an interchain account owned by the caller on the chain at the other end of
+		/// `connection_id`. The account address is available once the channel handshake
+		/// completes.
+		#[pallet::call_index(0)]
+		#[pallet::weight(<T as crate::Config>::WeightInfo::register_interchain_account())]
+		pub fn register_interchain_account(
+			origin: OriginFor<T>,
+			connection_id: Vec<u8>,
+		) -> DispatchResult {
+			let owner = ensure_signed(origin)?;
+			ensure!(T::IsControllerEnabled::get(), Error::<T>::ControllerDisabled);
+			let connection_id = Self::connection_id(&connection_id)?;
+			let connection_end = Context::<T>::default()
+				.connection_end(&connection_id)
+				.map_err(|_| Error::<T>::InvalidConnection)?;
+			let host_connection_id = connection_end
+				.counterparty()
+				.connection_id()
+				.ok_or(Error::<T>::InvalidConnection)?;
+			let port_id = Self::controller_port(&owner)?;
+			let channel_end = ChannelEnd::new(
+				State::Init,
+				Order::Ordered,
+				Counterparty::new(ibc::applications::interchain_accounts::host_port_id(), None),
+				vec![connection_id.clone()],
+				Metadata::new(&connection_id, host_connection_id).into(),
+			);
+			<crate::Pallet<T> as IbcHandler<T::AccountId>>::handle_message(
+				HandlerMessage::OpenChannel { port_id, channel_end },
+			)
+			.map_err(|e| {
+				log::trace!(target: "pallet_ibc", "[ics27] failed to open channel: {:?}", e);
+				Error::<T>::ChannelInitError
+			})?;
+			Ok(())
+		}
+
+		/// Send `messages` to be executed atomically by the interchain account of the caller on
+		/// the chain at the other end of `connection_id`.
+		#[pallet::call_index(1)]
+		#[pallet::weight(<T as crate::Config>::WeightInfo::send_interchain_account_tx(messages.encoded_size() as u32))]
+		pub fn send_tx(
+			origin: OriginFor<T>,
+			connection_id: Vec<u8>,
+			messages: Vec<InterchainAccountMessage>,
+			memo: Vec<u8>,
+			timeout: Timeout,
+		) -> DispatchResult {
+			let owner = ensure_signed(origin)?;
+			let connection = Self::connection_id(&connection_id)?;
+			let port_id = Self::controller_port(&owner)?;
+			let messages = messages
+				.into_iter()
+				.map(|msg| {
+					Ok(Any {
+						type_url: String::from_utf8(msg.type_url)
+							.map_err(|_| Error::<T>::InvalidTx)?,
+						value: msg.value,
+					})
+				})
+				.collect::<Result<Vec<_>, Error<T>>>()?;
+			let memo = String::from_utf8(memo).map_err(|_| Error::<T>::InvalidTx)?;
+			let (channel_id, data) = controller::send_tx(
+				&IcaContext::<T>::default(),
+				&connection,
+				&port_id,
+				messages,
+				memo,
+			)
+			.map_err(|e| {
+				log::trace!(target: "pallet_ibc", "[ics27] failed to build tx: {:?}", e);
+				match e.detail() {
+					ibc::applications::interchain_accounts::error::ErrorDetail::NoActiveChannel(
+						_,
+					) => Error::<T>::NoActiveChannel,
+					ibc::applications::interchain_accounts::error::ErrorDetail::ControllerDisabled(
+						_,
+					) => Error::<T>::ControllerDisabled,
+					_ => Error::<T>::InvalidTx,
+				}
+			})?;
+			<crate::Pallet<T> as IbcHandler<T::AccountId>>::handle_message(
+				HandlerMessage::SendPacket { data, timeout, port_id: port_id.clone(), channel_id },
+			)
+			.map_err(|e| {
+				log::trace!(target: "pallet_ibc", "[ics27] failed to send packet: {:?}", e);
+				Error::<T>::SendPacketError
+			})?;
+			Self::deposit_event(Event::<T>::ControllerTxSent {
+				connection_id,
+				port_id: port_id.as_bytes().to_vec(),
+				channel_id: channel_id.to_string().into_bytes(),
+			});
+			Ok(())
+		}
+	}
+
+	impl<T: Config> Pallet<T> {
+		/// Controller port of an interchain account owner
+		pub fn controller_port(owner: &T::AccountId) -> Result<PortId, Error<T>> {
+			controller_port_id(&hex::encode(owner.encode())).map_err(|_| Error::<T>::InvalidOwner)
+		}
+
+		/// Address of the interchain account owned by `owner` on the chain at the other end of
+		/// `connection_id`
+		pub fn controller_account(owner: &T::AccountId, connection_id: Vec<u8>) -> Option<Vec<u8>> {
+			let port_id = Self::controller_port(owner).ok()?;
+			ControllerActiveChannels::<T>::get(connection_id, port_id.as_bytes().to_vec())
+				.map(|(_, address)| address)
+		}
+
+		/// Account derived for the controller port on the connection
+		pub fn host_account(connection_id: &[u8], controller_port_id: &[u8]) -> T::AccountId {
+			let entropy =
+				(b"ics27-host", connection_id, controller_port_id).using_encoded(blake2_256);
+			T::AccountId::decode(&mut TrailingZeroInput::new(entropy.as_ref()))
+				.expect("infinite length input; no invalid inputs for type; qed")
+		}
+
+		fn connection_id(connection_id: &[u8]) -> Result<ConnectionId, Error<T>> {
+			core::str::from_utf8(connection_id)
+				.ok()
+				.and_then(|connection_id| ConnectionId::from_str(connection_id).ok())
+				.ok_or(Error::<T>::InvalidConnection)
+		}
+	}
+}
+
+/// Interchain accounts state backed by the pallet storage
+pub struct IcaContext<T: Config>(PhantomData<T>);
+
+impl<T: Config> Default for IcaContext<T> {
+	fn default() -> Self {
+		Self(PhantomData)
+	}
+}
+
+impl<T: Config + Send + Sync> IcaContext<T>
+where
+	u32: From<<T as frame_system::Config>::BlockNumber>,
+	AccountId32: From<<T as frame_system::Config>::AccountId>,
+{
+	fn is_open(port_id: &PortId, channel_id: &[u8]) -> Option<ChannelId> {
+		let channel_id = ChannelId::from_str(core::str::from_utf8(channel_id).ok()?).ok()?;
+		Context::<T>::default()
+			.channel_end(&(port_id.clone(), channel_id))
+			.ok()
+			.filter(|channel_end| channel_end.state_matches(&State::Open))
+			.map(|_| channel_id)
+	}
+
+	fn account_address(account: &T::AccountId) -> String {
+		format!("0x{}", hex::encode(account.encode()))
+	}
+}
+
+impl<T: Config + Send + Sync> InterchainAccountHostContext for IcaContext<T>
+where
+	u32: From<<T as frame_system::Config>::BlockNumber>,
+	AccountId32: From<<T as frame_system::Config>::AccountId>,
+{
+	fn is_host_enabled(&self) -> bool {
+		T::IsHostEnabled::get()
+	}
+
+	fn host_active_channel(
+		&self,
+		connection_id: &ConnectionId,
+		controller_port_id: &PortId,
+	) -> Option<ChannelId> {
+		let channel_id =
+			HostActiveChannels::<T>::get(connection_id.as_bytes(), controller_port_id.as_bytes())?;
+		Self::is_open(&ibc::applications::interchain_accounts::host_port_id(), &channel_id)
+	}
+
+	fn store_host_active_channel(
+		&mut self,
+		connection_id: &ConnectionId,
+		controller_port_id: &PortId,
+		channel_id: &ChannelId,
+	) -> Result<(), IcaError> {
+		HostActiveChannels::<T>::insert(
+			connection_id.as_bytes(),
+			controller_port_id.as_bytes(),
+			channel_id.to_string().into_bytes(),
+		);
+		Ok(())
+	}
+
+	fn interchain_account(
+		&self,
+		connection_id: &ConnectionId,
+		controller_port_id: &PortId,
+	) -> Option<String> {
+		HostAccounts::<T>::get(connection_id.as_bytes(), controller_port_id.as_bytes())
+			.map(|account| Self::account_address(&account))
+	}
+
+	fn register_interchain_account(
+		&mut self,
+		connection_id: &ConnectionId,
+		controller_port_id: &PortId,
+	) -> Result<String, IcaError> {
+		let account =
+			Pallet::<T>::host_account(connection_id.as_bytes(), controller_port_id.as_bytes());
+		HostAccounts::<T>::insert(
+			connection_id.as_bytes(),
+			controller_port_id.as_bytes(),
+			account.clone(),
+		);
+		frame_system::Pallet::<T>::inc_providers(&account);
+		Pallet::<T>::deposit_event(Event::<T>::HostAccountRegistered {
+			connection_id: connection_id.as_bytes().to_vec(),
+			controller_port_id: controller_port_id.as_bytes().to_vec(),
+			account: account.clone(),
+		});
+		Ok(Self::account_address(&account))
+	}
+
+	fn execute_tx(&mut self, address: &str, messages: Vec<Any>) -> Result<Vec<Any>, IcaError> {
+		let account = address
+			.strip_prefix("0x")
+			.and_then(|address| hex::decode(address).ok())
+			.and_then(|address| T::AccountId::decode(&mut address.as_slice()).ok())
+			.ok_or_else(|| IcaError::implementation("invalid interchain account".to_string()))?;
+		let calls = messages
+			.iter()
+			.map(|msg| {
+				if msg.type_url != RUNTIME_CALL_TYPE_URL {
+					return Err(IcaError::message_not_allowed(msg.type_url.clone()))
+				}
+				let call = <T as Config>::RuntimeCall::decode(&mut msg.value.as_slice())
+					.map_err(|e| IcaError::execution_failed(format!("{e:?}")))?;
+				if !T::CallFilter::contains(&call) {
+					return Err(IcaError::message_not_allowed(msg.type_url.clone()))
+				}
+				Ok(call)
+			})
+			.collect::<Result<Vec<_>, _>>()?;
+		let weight = calls
+			.iter()
+			.fold(Weight::zero(), |acc, call| acc.saturating_add(call.get_dispatch_info().weight));
+		if weight.any_gt(<T as crate::Config>::IcaMaxTxWeight::get()) {
+			return Err(IcaError::execution_failed(
+				"transaction exceeds the weight limit".to_string(),
+			))
+		}
+
+		// All calls are dispatched atomically
+		with_transaction_unchecked(|| {
+			for call in calls {
+				let origin = frame_system::RawOrigin::Signed(account.clone()).into();
+				if let Err(e) = call.dispatch(origin) {
+					return TransactionOutcome::Rollback(Err(IcaError::execution_failed(format!(
+						"{:?}",
+						e.error
+					))))
+				}
+			}
+			TransactionOutcome::Commit(Ok(()))
+		})?;
+
+		Ok(messages
+			.into_iter()
+			.map(|msg| Any { type_url: format!("{}Response", msg.type_url), value: vec![] })
+			.collect())
+	}
+}
+
+impl<T: Config + Send + Sync> InterchainAccountControllerContext for IcaContext<T>
+where
+	u32: From<<T as frame_system::Config>::BlockNumber>,
+	AccountId32: From<<T as frame_system::Config>::AccountId>,
+{
+	fn is_controller_enabled(&self) -> bool {
+		T::IsControllerEnabled::get()
+	}
+
+	fn controller_active_channel(
+		&self,
+		connection_id: &ConnectionId,
+		port_id: &PortId,
+	) -> Option<ChannelId> {
+		let (channel_id, _) =
+			ControllerActiveChannels::<T>::get(connection_id.as_bytes(), port_id.as_bytes())?;
+		Self::is_open(port_id, &channel_id)
+	}
+
+	fn store_controller_active_channel(
+		&mut self,
+		connection_id: &ConnectionId,
+		port_id: &PortId,
+		channel_id: &ChannelId,
+		address: String,
+	) -> Result<(), IcaError> {
+		ControllerActiveChannels::<T>::insert(
+			connection_id.as_bytes(),
+			port_id.as_bytes(),
+			(channel_id.to_string().into_bytes(), address.clone().into_bytes()),
+		);
+		Pallet::<T>::deposit_event(Event::<T>::ControllerAccountRegistered {
+			connection_id: connection_id.as_bytes().to_vec(),
+			port_id: port_id.as_bytes().to_vec(),
+			channel_id: channel_id.to_string().into_bytes(),
+			address: address.into_bytes(),
+		});
+		Ok(())
+	}
+}
+
+fn to_ics04_error(e: IcaError) -> Ics04Error {
+	Ics04Error::implementation_specific(e.to_string())
+}
+
+fn unsupported(callback: &str) -> Ics04Error {
+	Ics04Error::implementation_specific(format!("{callback} is not supported"))
+}
+
+/// Returns the connection and counterparty port of a channel
+fn channel_connection<T: Config + Send + Sync>(
+	port_id: &PortId,
+	channel_id: &ChannelId,
+) -> Result<(ConnectionId, PortId), Ics04Error>
+where
+	u32: From<<T as frame_system::Config>::BlockNumber>,
+	AccountId32: From<<T as frame_system::Config>::AccountId>,
+{
+	let channel_end = Context::<T>::default().channel_end(&(port_id.clone(), *channel_id))?;
+	let connection_id = channel_end
+		.connection_hops()
+		.first()
+		.cloned()
+		.ok_or_else(Ics04Error::missing_connection_hops)?;
+	Ok((connection_id, channel_end.counterparty().port_id().clone()))
+}
+
+/// Host module, should be routed the `icahost` port
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct IcaHost<T: Config>(PhantomData<T>);
+
+impl<T: Config> Default for IcaHost<T> {
+	fn default() -> Self {
+		Self(PhantomData)
+	}
+}
+
+impl<T: Config + Send + Sync> IbcModule for IcaHost<T>
+where
+	u32: From<<T as frame_system::Config>::BlockNumber>,
+	AccountId32: From<<T as frame_system::Config>::AccountId>,
+{
+	fn on_chan_open_init(
+		&mut self,
+		_ctx: &dyn ModuleCallbackContext,
+		_output: &mut ModuleOutputBuilder,
+		_order: Order,
+		_connection_hops: &[ConnectionId],
+		_port_id: &PortId,
+		_channel_id: &ChannelId,
+		_counterparty: &Counterparty,
+		_version: &Version,
+		_relayer: &Signer,
+	) -> Result<(), Ics04Error> {
+		Err(unsupported("Channel opening on the host port"))
+	}
+
+	fn on_chan_open_try(
+		&mut self,
+		_ctx: &dyn ModuleCallbackContext,
+		_output: &mut ModuleOutputBuilder,
+		order: Order,
+		connection_hops: &[ConnectionId],
+		port_id: &PortId,
+		_channel_id: &ChannelId,
+		counterparty: &Counterparty,
+		_version: &Version,
+		counterparty_version: &Version,
+		_relayer: &Signer,
+	) -> Result<Version, Ics04Error> {
+		host::on_chan_open_try(
+			&mut IcaContext::<T>::default(),
+			order,
+			connection_hops,
+			port_id,
+			counterparty,
+			counterparty_version,
+		)
+		.map_err(to_ics04_error)
+	}
+
+	fn on_chan_open_ack(
+		&mut self,
+		_ctx: &dyn ModuleCallbackContext,
+		_output: &mut ModuleOutputBuilder,
+		_port_id: &PortId,
+		_channel_id: &ChannelId,
+		_counterparty_version: &Version,
+		_relayer: &Signer,
+	) -> Result<(), Ics04Error> {
+		Err(unsupported("Channel opening on the host port"))
+	}
+
+	fn on_chan_open_confirm(
+		&mut self,
+		_ctx: &dyn ModuleCallbackContext,
+		_output: &mut ModuleOutputBuilder,
+		port_id: &PortId,
+		channel_id: &ChannelId,
+		_relayer: &Signer,
+	) -> Result<(), Ics04Error> {
+		let (connection_id, controller_port_id) = channel_connection::<T>(port_id, channel_id)?;
+		host::on_chan_open_confirm(
+			&mut IcaContext::<T>::default(),
+			&connection_id,
+			&controller_port_id,
+			channel_id,
+		)
+		.map_err(to_ics04_error)
+	}
+
+	fn on_chan_close_init(
+		&mut self,
+		_ctx: &dyn ModuleCallbackContext,
+		_output: &mut ModuleOutputBuilder,
+		_port_id: &PortId,
+		_channel_id: &ChannelId,
+		_relayer: &Signer,
+	) -> Result<(), Ics04Error> {
+		Err(unsupported("Closing interchain account channels"))
+	}
+
+	fn on_chan_close_confirm(
+		&mut self,
+		_ctx: &dyn ModuleCallbackContext,
+		_output: &mut ModuleOutputBuilder,
+		_port_id: &PortId,
+		_channel_id: &ChannelId,
+		_relayer: &Signer,
+	) -> Result<(), Ics04Error> {
+		Ok(())
+	}
+
+	fn on_recv_packet(
+		&self,
+		_ctx: &dyn ModuleCallbackContext,
+		_output: &mut ModuleOutputBuilder,
+		packet: &mut Packet,
+		_relayer: &Signer,
+	) -> Result<OnRecvPacketAck, Ics04Error> {
+		let (connection_id, _) =
+			channel_connection::<T>(&packet.destination_port, &packet.destination_channel)?;
+		let ack = host::on_recv_packet(&mut IcaContext::<T>::default(), &connection_id, packet);
+		Pallet::<T>::deposit_event(Event::<T>::HostTxExecuted {
+			port_id: packet.source_port.as_bytes().to_vec(),
+			channel_id: packet.source_channel.to_string().into_bytes(),
+			sequence: packet.sequence.into(),
+			success: ack.is_successful(),
+		});
+		Ok(Acknowledgement::from_bytes(ack.to_string().into_bytes()).into())
+	}
+
+	fn on_acknowledgement_packet(
+		&mut self,
+		_ctx: &dyn ModuleCallbackContext,
+		_output: &mut ModuleOutputBuilder,
+		_packet: &mut Packet,
+		_acknowledgement: &Acknowledgement,
+		_relayer: &Signer,
+	) -> Result<(), Ics04Error> {
+		Err(unsupported("Sending packets from the host port"))
+	}
+
+	fn on_timeout_packet(
+		&mut self,
+		_ctx: &dyn ModuleCallbackContext,
+		_output: &mut ModuleOutputBuilder,
+		_packet: &mut Packet,
+		_relayer: &Signer,
+	) -> Result<(), Ics04Error> {
+		Err(unsupported("Sending packets from the host port"))
+	}
+}
+
+/// Controller module, should be routed all ports prefixed with `icacontroller-`
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct IcaController<T: Config>(PhantomData<T>);
+
+impl<T: Config> Default for IcaController<T> {
+	fn default() -> Self {
+		Self(PhantomData)
+	}
+}
+
+impl<T: Config + Send + Sync> IbcModule for IcaController<T>
+where
+	u32: From<<T as frame_system::Config>::BlockNumber>,
+	AccountId32: From<<T as frame_system::Config>::AccountId>,
+{
+	fn on_chan_open_init(
+		&mut self,
+		_ctx: &dyn ModuleCallbackContext,
+		_output: &mut ModuleOutputBuilder,
+		order: Order,
+		connection_hops: &[ConnectionId],
+		port_id: &PortId,
+		_channel_id: &ChannelId,
+		counterparty: &Counterparty,
+		version: &Version,
+		_relayer: &Signer,
+	) -> Result<(), Ics04Error> {
+		controller::on_chan_open_init(
+			&IcaContext::<T>::default(),
+			order,
+			connection_hops,
+			port_id,
+			counterparty,
+			version,
+		)
+		.map_err(to_ics04_error)
+	}
+
+	fn on_chan_open_try(
+		&mut self,
+		_ctx: &dyn ModuleCallbackContext,
+		_output: &mut ModuleOutputBuilder,
+		_order: Order,
+		_connection_hops: &[ConnectionId],
+		_port_id: &PortId,
+		_channel_id: &ChannelId,
+		_counterparty: &Counterparty,
+		_version: &Version,
+		_counterparty_version: &Version,
+		_relayer: &Signer,
+	) -> Result<Version, Ics04Error> {
+		Err(unsupported("Channel opening from a counterparty on the controller port"))
+	}
+
+	fn on_chan_open_ack(
+		&mut self,
+		_ctx: &dyn ModuleCallbackContext,
+		_output: &mut ModuleOutputBuilder,
+		port_id: &PortId,
+		channel_id: &ChannelId,
+		counterparty_version: &Version,
+		_relayer: &Signer,
+	) -> Result<(), Ics04Error> {
+		let (connection_id, _) = channel_connection::<T>(port_id, channel_id)?;
+		controller::on_chan_open_ack(
+			&mut IcaContext::<T>::default(),
+			&connection_id,
+			port_id,
+			channel_id,
+			counterparty_version,
+		)
+		.map_err(to_ics04_error)
+	}
+
+	fn on_chan_open_confirm(
+		&mut self,
+		_ctx: &dyn ModuleCallbackContext,
+		_output: &mut ModuleOutputBuilder,
+		_port_id: &PortId,
+		_channel_id: &ChannelId,
+		_relayer: &Signer,
+	) -> Result<(), Ics04Error> {
+		Err(unsupported("Channel opening from a counterparty on the controller port"))
+	}
+
+	fn on_chan_close_init(
+		&mut self,
+		_ctx: &dyn ModuleCallbackContext,
+		_output: &mut ModuleOutputBuilder,
+		_port_id: &PortId,
+		_channel_id: &ChannelId,
+		_relayer: &Signer,
+	) -> Result<(), Ics04Error> {
+		Err(unsupported("Closing interchain account channels"))
+	}
+
+	fn on_chan_close_confirm(
+		&mut self,
+		_ctx: &dyn ModuleCallbackContext,
+		_output: &mut ModuleOutputBuilder,
+		_port_id: &PortId,
+		_channel_id: &ChannelId,
+		_relayer: &Signer,
+	) -> Result<(), Ics04Error> {
+		Ok(())
+	}
+
+	fn on_recv_packet(
+		&self,
+		_ctx: &dyn ModuleCallbackContext,
+		_output: &mut ModuleOutputBuilder,
+		_packet: &mut Packet,
+		_relayer: &Signer,
+	) -> Result<OnRecvPacketAck, Ics04Error> {
+		Err(unsupported("Receiving packets on the controller port"))
+	}
+
+	fn on_acknowledgement_packet(
+		&mut self,
+		_ctx: &dyn ModuleCallbackContext,
+		_output: &mut ModuleOutputBuilder,
+		packet: &mut Packet,
+		acknowledgement: &Acknowledgement,
+		_relayer: &Signer,
+	) -> Result<(), Ics04Error> {
+		let success = serde_json::from_slice::<GenericAck>(acknowledgement.as_ref())
+			.map(|ack| ack.is_successful())
+			.unwrap_or(false);
+		Pallet::<T>::deposit_event(Event::<T>::ControllerTxAcknowledged {
+			port_id: packet.source_port.as_bytes().to_vec(),
+			channel_id: packet.source_channel.to_string().into_bytes(),
+			sequence: packet.sequence.into(),
+			success,
+		});
+		Ok(())
+	}
+
+	fn on_timeout_packet(
+		&mut self,
+		_ctx: &dyn ModuleCallbackContext,
+		_output: &mut ModuleOutputBuilder,
+		packet: &mut Packet,
+		_relayer: &Signer,
+	) -> Result<(), Ics04Error> {
+		// Timeouts close ordered channels, the owner has to register the account again to open a
+		// new channel, the host keeps the same account for it.
+		Pallet::<T>::deposit_event(Event::<T>::ControllerTxTimedOut {
+			port_id: packet.source_port.as_bytes().to_vec(),
+			channel_id: packet.source_channel.to_string().into_bytes(),
+			sequence: packet.sequence.into(),
+		});
+		Ok(())
+	}
+}
+
+pub struct WeightHandler<T: crate::Config>(PhantomData<T>);
+
+impl<T: crate::Config> Default for WeightHandler<T> {
+	fn default() -> Self {
+		Self(PhantomData)
+	}
+}
+
+impl<T: crate::Config> CallbackWeight for WeightHandler<T> {
+	fn on_chan_open_init(&self) -> Weight {
+		<T as crate::Config>::WeightInfo::on_chan_open_init()
+	}
+
+	fn on_chan_open_try(&self) -> Weight {
+		<T as crate::Config>::WeightInfo::on_chan_open_try()
+	}
+
+	fn on_chan_open_ack(&self, _port_id: &PortId, _channel_id: &ChannelId) -> Weight {
+		<T as crate::Config>::WeightInfo::on_chan_open_ack()
+	}
+
+	fn on_chan_open_confirm(&self, _port_id: &PortId, _channel_id: &ChannelId) -> Weight {
+		<T as crate::Config>::WeightInfo::on_chan_open_confirm()
+	}
+
+	fn on_chan_close_init(&self, _port_id: &PortId, _channel_id: &ChannelId) -> Weight {
+		<T as crate::Config>::WeightInfo::on_chan_close_init()
+	}
+
+	fn on_chan_close_confirm(&self, _port_id: &PortId, _channel_id: &ChannelId) -> Weight {
+		<T as crate::Config>::WeightInfo::on_chan_close_confirm()
+	}
+
+	fn on_recv_packet(&self, _packet: &Packet) -> Weight {
+		// The calls are only decoded when the packet is executed, charge the most they can weigh
+		<T as crate::Config>::WeightInfo::on_recv_packet()
+			.saturating_add(<T as crate::Config>::IcaMaxTxWeight::get())
+	}
+
+	fn on_acknowledgement_packet(
+		&self,
+		_packet: &Packet,
+		_acknowledgement: &Acknowledgement,
+	) -> Weight {
+		<T as crate::Config>::WeightInfo::on_acknowledgement_packet()
+	}
+
+	fn on_timeout_packet(&self, _packet: &Packet) -> Weight {
+		<T as crate::Config>::WeightInfo::on_timeout_packet()
+	}
+}
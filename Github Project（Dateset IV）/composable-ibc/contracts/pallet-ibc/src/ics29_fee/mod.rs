@@ -0,0 +1,998 @@
+//! ICS-29 fee middleware.
+//!
+//! Lets users escrow receive, acknowledgement and timeout fees for packets sent over fee enabled
+//! channels, the fees are paid out to the relayers that delivered the packet once the packet
+//! lifecycle completes. Fee support is negotiated during the channel handshake by wrapping the
+//! application version in a [`Metadata`] json object.
+use crate::ics23::packet_commitments::PacketCommitment;
+use alloc::{
+	format,
+	string::{String, ToString},
+	vec::Vec,
+};
+use core::{fmt::Debug, str::FromStr};
+use frame_support::traits::{
+	fungibles::Mutate, tokens::Preservation, Currency, ExistenceRequirement,
+};
+use ibc::{
+	applications::transfer::acknowledgement::Acknowledgement as Ics20Ack,
+	core::{
+		ics04_channel::{
+			channel::{Counterparty, Order},
+			error::Error as Ics04Error,
+			msgs::acknowledgement::Acknowledgement,
+			packet::Packet,
+			Version,
+		},
+		ics24_host::identifier::{ChannelId, ConnectionId, PortId},
+		ics26_routing::context::{
+			Module as IbcModule, ModuleCallbackContext, ModuleOutputBuilder, OnRecvPacketAck,
+		},
+	},
+	signer::Signer,
+};
+use serde::{Deserialize, Serialize};
+use sp_core::crypto::AccountId32;
+use sp_runtime::{
+	traits::{Get, IdentifyAccount, Saturating, Zero},
+	DispatchResult,
+};
+
+pub use pallet::*;
+
+/// Version of the fee middleware negotiated in the channel version metadata
+pub const FEE_VERSION: &str = "ics29-1";
+
+/// Channel version used by fee enabled channels, wraps the version of the underlying application
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Metadata {
+	/// Fee middleware version, must be [`FEE_VERSION`]
+	pub fee_version: String,
+	/// Version of the application the middleware wraps
+	pub app_version: String,
+}
+
+impl Metadata {
+	/// Returns the metadata if the version is a fee version metadata object
+	pub fn from_version(version: &Version) -> Option<Self> {
+		serde_json::from_str(&version.to_string()).ok()
+	}
+
+	/// Returns the fee enabled channel version wrapping `app_version`
+	pub fn version(app_version: &Version) -> Result<Version, Ics04Error> {
+		let metadata =
+			Self { fee_version: FEE_VERSION.to_string(), app_version: app_version.to_string() };
+		serde_json::to_string(&metadata).map(Version::new).map_err(|e| {
+			Ics04Error::implementation_specific(format!("Failed to encode fee metadata {e:?}"))
+		})
+	}
+
+	fn app_version(&self) -> Result<Version, Ics04Error> {
+		if self.fee_version != FEE_VERSION {
+			return Err(Ics04Error::implementation_specific(format!(
+				"Unsupported fee version {}, expected {FEE_VERSION}",
+				self.fee_version
+			)))
+		}
+		Ok(Version::new(self.app_version.clone()))
+	}
+}
+
+/// Acknowledgement written for packets received over fee enabled channels
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct IncentivizedAcknowledgement {
+	/// Base64 encoded acknowledgement of the underlying application
+	pub app_acknowledgement: String,
+	/// Address of the forward relayer on the packet source chain, it receives the recv fee
+	pub forward_relayer_address: String,
+	/// Whether the underlying application processed the packet successfully
+	pub underlying_app_success: bool,
+}
+
+impl IncentivizedAcknowledgement {
+	fn new(app_ack: &Acknowledgement, forward_relayer_address: String) -> Self {
+		// Acknowledgements that are not ics20 acknowledgements are treated as successful
+		let underlying_app_success = serde_json::from_slice::<Ics20Ack>(app_ack.as_ref())
+			.map(|ack| ack.is_successful())
+			.unwrap_or(true);
+		Self {
+			app_acknowledgement: base64::encode(app_ack.as_ref()),
+			forward_relayer_address,
+			underlying_app_success,
+		}
+	}
+
+	fn app_acknowledgement(&self) -> Result<Acknowledgement, Ics04Error> {
+		base64::decode(&self.app_acknowledgement)
+			.map(Acknowledgement::from_bytes)
+			.map_err(|e| {
+				Ics04Error::implementation_specific(format!(
+					"Failed to decode app acknowledgement {e:?}"
+				))
+			})
+	}
+}
+
+#[frame_support::pallet]
+pub mod pallet {
+	use crate::WeightInfo;
+	use alloc::string::String;
+	use frame_support::{pallet_prelude::*, PalletId};
+	use frame_system::pallet_prelude::*;
+	use ibc::core::{
+		ics04_channel::packet::Sequence,
+		ics24_host::identifier::{ChannelId, PortId},
+	};
+	use sp_runtime::traits::{AccountIdConversion, Saturating, Zero};
+	use sp_std::{str::FromStr, vec::Vec};
+
+	/// Fees escrowed for a single packet, paid to the relayers once the packet lifecycle completes.
+	#[derive(Clone, Encode, Decode, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+	pub struct PacketFee<AccountId, AssetId, Balance> {
+		/// Asset the fees are paid in
+		pub asset_id: AssetId,
+		/// Paid to the relayer that delivers the packet to the counterparty
+		pub recv_fee: Balance,
+		/// Paid to the relayer that delivers the acknowledgement back to this chain
+		pub ack_fee: Balance,
+		/// Paid to the relayer that delivers the timeout to this chain
+		pub timeout_fee: Balance,
+		/// Account refunded with the fees that are not paid out
+		pub refund_address: AccountId,
+	}
+
+	impl<AccountId, AssetId, Balance: Saturating + Copy> PacketFee<AccountId, AssetId, Balance> {
+		/// Total amount escrowed for this fee
+		pub fn total(&self) -> Balance {
+			self.recv_fee.saturating_add(self.ack_fee).saturating_add(self.timeout_fee)
+		}
+	}
+
+	pub type PacketFeeOf<T> = PacketFee<
+		<T as frame_system::Config>::AccountId,
+		<T as crate::Config>::AssetId,
+		<T as crate::Config>::Balance,
+	>;
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config + crate::Config {
+		/// The overarching event type.
+		type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
+		/// Pallet id of the account that holds escrowed packet fees
+		#[pallet::constant]
+		type PalletId: Get<PalletId>;
+		/// Maximum number of fees that can be escrowed for a single packet
+		#[pallet::constant]
+		type MaxPacketFees: Get<u32>;
+	}
+
+	#[pallet::pallet]
+	#[pallet::without_storage_info]
+	pub struct Pallet<T>(_);
+
+	#[pallet::storage]
+	#[allow(clippy::disallowed_types)]
+	/// Channels that negotiated the fee version during the channel handshake, keyed by (port_id,
+	/// channel_id)
+	pub type FeeEnabledChannels<T: Config> =
+		StorageDoubleMap<_, Blake2_128Concat, Vec<u8>, Blake2_128Concat, Vec<u8>, (), ValueQuery>;
+
+	#[pallet::storage]
+	#[allow(clippy::disallowed_types)]
+	/// Account that receives the ack and timeout fees earned by a relayer on a channel, keyed by
+	/// (channel_id, relayer)
+	pub type Payees<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		Vec<u8>,
+		Blake2_128Concat,
+		T::AccountId,
+		T::AccountId,
+		OptionQuery,
+	>;
+
+	#[pallet::storage]
+	#[allow(clippy::disallowed_types)]
+	/// Counterparty chain address of a relayer, returned in the acknowledgement of packets it
+	/// delivers so that it can be paid the recv fee on the source chain. Keyed by (channel_id,
+	/// relayer)
+	pub type CounterpartyPayees<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		Vec<u8>,
+		Blake2_128Concat,
+		T::AccountId,
+		Vec<u8>,
+		OptionQuery,
+	>;
+
+	#[pallet::storage]
+	#[allow(clippy::disallowed_types)]
+	/// Fees escrowed for in flight packets, keyed by ((port_id, channel_id), sequence)
+	pub type PacketFees<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		(Vec<u8>, Vec<u8>),
+		Blake2_128Concat,
+		u64,
+		BoundedVec<PacketFeeOf<T>, T::MaxPacketFees>,
+		ValueQuery,
+	>;
+
+	#[pallet::storage]
+	#[allow(clippy::disallowed_types)]
+	/// Forward relayer address of received packets whose acknowledgement is written
+	/// asynchronously, keyed by ((port_id, channel_id), sequence)
+	pub type AsyncAckForwardRelayers<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		(Vec<u8>, Vec<u8>),
+		Blake2_128Concat,
+		u64,
+		Vec<u8>,
+		OptionQuery,
+	>;
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub (super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// Fees were escrowed for a packet
+		IncentivizedPacket {
+			port_id: Vec<u8>,
+			channel_id: Vec<u8>,
+			sequence: u64,
+			asset_id: T::AssetId,
+			recv_fee: T::Balance,
+			ack_fee: T::Balance,
+			timeout_fee: T::Balance,
+		},
+		/// A relayer registered the account its fees should be paid to
+		PayeeRegistered { channel_id: Vec<u8>, relayer: T::AccountId, payee: T::AccountId },
+		/// A relayer registered its address on the counterparty chain
+		CounterpartyPayeeRegistered {
+			channel_id: Vec<u8>,
+			relayer: T::AccountId,
+			counterparty_payee: Vec<u8>,
+		},
+		/// A fee was paid to a relayer
+		FeePaid {
+			port_id: Vec<u8>,
+			channel_id: Vec<u8>,
+			sequence: u64,
+			asset_id: T::AssetId,
+			payee: T::AccountId,
+			amount: T::Balance,
+		},
+		/// Unused fees were refunded
+		FeeRefunded {
+			port_id: Vec<u8>,
+			channel_id: Vec<u8>,
+			sequence: u64,
+			asset_id: T::AssetId,
+			refund_address: T::AccountId,
+			amount: T::Balance,
+		},
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// Port or channel identifier is invalid
+		InvalidIdentifier,
+		/// Channel did not negotiate the fee version
+		ChannelNotFeeEnabled,
+		/// Packet was not sent or its lifecycle is already complete
+		PacketNotInFlight,
+		/// All fees are zero
+		InvalidFee,
+		/// Counterparty payee address is empty or not valid utf8
+		InvalidCounterpartyPayee,
+		/// Packet already has the maximum number of fees escrowed
+		TooManyPacketFees,
+	}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Register the account that receives the ack and timeout fees earned by the caller on
+		/// `channel_id`.
+		#[pallet::call_index(0)]
+		#[pallet::weight(<T as crate::Config>::WeightInfo::register_payee())]
+		pub fn register_payee(
+			origin: OriginFor<T>,
+			channel_id: Vec<u8>,
+			payee: T::AccountId,
+		) -> DispatchResult {
+			let relayer = ensure_signed(origin)?;
+			Self::channel_id(&channel_id)?;
+			Payees::<T>::insert(&channel_id, &relayer, &payee);
+			Self::deposit_event(Event::<T>::PayeeRegistered { channel_id, relayer, payee });
+			Ok(())
+		}
+
+		/// Register the address of the caller on the counterparty chain of `channel_id`, the
+		/// address is used to pay the caller the recv fee of packets it relays to this chain.
+		#[pallet::call_index(1)]
+		#[pallet::weight(<T as crate::Config>::WeightInfo::register_counterparty_payee())]
+		pub fn register_counterparty_payee(
+			origin: OriginFor<T>,
+			channel_id: Vec<u8>,
+			counterparty_payee: Vec<u8>,
+		) -> DispatchResult {
+			let relayer = ensure_signed(origin)?;
+			Self::channel_id(&channel_id)?;
+			let is_valid = sp_std::str::from_utf8(&counterparty_payee)
+				.map(|payee| !payee.trim().is_empty())
+				.unwrap_or(false);
+			ensure!(is_valid, Error::<T>::InvalidCounterpartyPayee);
+			CounterpartyPayees::<T>::insert(&channel_id, &relayer, &counterparty_payee);
+			Self::deposit_event(Event::<T>::CounterpartyPayeeRegistered {
+				channel_id,
+				relayer,
+				counterparty_payee,
+			});
+			Ok(())
+		}
+
+		/// Escrow fees for a packet that was already sent on a fee enabled channel. Fees can be
+		/// added multiple times for the same packet, fees that are not paid out are refunded to
+		/// the caller.
+		#[pallet::call_index(2)]
+		#[pallet::weight(<T as crate::Config>::WeightInfo::pay_packet_fee_async())]
+		#[frame_support::transactional]
+		pub fn pay_packet_fee_async(
+			origin: OriginFor<T>,
+			port_id: Vec<u8>,
+			channel_id: Vec<u8>,
+			sequence: u64,
+			asset_id: T::AssetId,
+			recv_fee: T::Balance,
+			ack_fee: T::Balance,
+			timeout_fee: T::Balance,
+		) -> DispatchResult {
+			let refund_address = ensure_signed(origin)?;
+			let port = PortId::from_str(
+				&String::from_utf8(port_id.clone()).map_err(|_| Error::<T>::InvalidIdentifier)?,
+			)
+			.map_err(|_| Error::<T>::InvalidIdentifier)?;
+			let channel = Self::channel_id(&channel_id)?;
+			ensure!(
+				FeeEnabledChannels::<T>::contains_key(&port_id, &channel_id),
+				Error::<T>::ChannelNotFeeEnabled
+			);
+			ensure!(
+				super::PacketCommitment::<T>::contains_key((
+					port,
+					channel,
+					Sequence::from(sequence)
+				)),
+				Error::<T>::PacketNotInFlight
+			);
+
+			let fee = PacketFee { asset_id, recv_fee, ack_fee, timeout_fee, refund_address };
+			ensure!(!fee.total().is_zero(), Error::<T>::InvalidFee);
+			PacketFees::<T>::try_mutate((port_id.clone(), channel_id.clone()), sequence, |fees| {
+				fees.try_push(fee.clone()).map_err(|_| Error::<T>::TooManyPacketFees)
+			})?;
+			super::transfer::<T>(
+				fee.asset_id.clone(),
+				&fee.refund_address,
+				&Self::account_id(),
+				fee.total(),
+			)?;
+			Self::deposit_event(Event::<T>::IncentivizedPacket {
+				port_id: port_id.clone(),
+				channel_id: channel_id.clone(),
+				sequence,
+				asset_id: fee.asset_id.clone(),
+				recv_fee,
+				ack_fee,
+				timeout_fee,
+			});
+			Ok(())
+		}
+	}
+
+	impl<T: Config> Pallet<T> {
+		/// Account holding the escrowed packet fees
+		pub fn account_id() -> T::AccountId {
+			T::PalletId::get().into_account_truncating()
+		}
+
+		fn channel_id(channel_id: &[u8]) -> Result<ChannelId, Error<T>> {
+			sp_std::str::from_utf8(channel_id)
+				.ok()
+				.and_then(|channel_id| ChannelId::from_str(channel_id).ok())
+				.ok_or(Error::<T>::InvalidIdentifier)
+		}
+	}
+}
+
+/// Transfer `amount` of `asset_id` between two accounts
+fn transfer<T: Config>(
+	asset_id: T::AssetId,
+	from: &T::AccountId,
+	to: &T::AccountId,
+	amount: T::Balance,
+) -> DispatchResult {
+	if amount.is_zero() {
+		return Ok(())
+	}
+	if asset_id == T::NativeAssetId::get() {
+		<T::NativeCurrency as Currency<T::AccountId>>::transfer(
+			from,
+			to,
+			amount,
+			ExistenceRequirement::AllowDeath,
+		)
+	} else {
+		<<T as crate::Config>::Fungibles as Mutate<T::AccountId>>::transfer(
+			asset_id,
+			from,
+			to,
+			amount,
+			Preservation::Expendable,
+		)
+		.map(|_| ())
+	}
+}
+
+/// This middleware implements ICS-29 relayer incentivization for the application it wraps.
+/// It should be the outermost layer of the application stack so that the acknowledgements it
+/// wraps are the ones committed on chain.
+/// USAGE:
+/// ```rust
+/// # #[cfg(any())] // This is just to make the doc test compile
+/// pub struct Router {
+///     ics20: crate::ics29_fee::Ics29Fee<
+///         Runtime,
+///         crate::ics20::memo::Memo<Runtime, crate::ics20::IbcModule<Runtime>>,
+///     >,
+/// }
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Ics29Fee<T: Config, S: IbcModule + Clone + Default + PartialEq + Eq + Debug> {
+	inner: S,
+	_phantom: core::marker::PhantomData<T>,
+}
+
+impl<T: Config + Send + Sync, S: IbcModule + Clone + Default + PartialEq + Eq + Debug> Default
+	for Ics29Fee<T, S>
+{
+	fn default() -> Self {
+		Self { inner: S::default(), _phantom: Default::default() }
+	}
+}
+
+impl<T: Config + Send + Sync, S: IbcModule + Clone + Default + PartialEq + Eq + Debug> IbcModule
+	for Ics29Fee<T, S>
+where
+	u32: From<<T as frame_system::Config>::BlockNumber>,
+	AccountId32: From<<T as frame_system::Config>::AccountId>,
+{
+	fn on_chan_open_init(
+		&mut self,
+		ctx: &dyn ModuleCallbackContext,
+		output: &mut ModuleOutputBuilder,
+		order: Order,
+		connection_hops: &[ConnectionId],
+		port_id: &PortId,
+		channel_id: &ChannelId,
+		counterparty: &Counterparty,
+		version: &Version,
+		relayer: &Signer,
+	) -> Result<(), Ics04Error> {
+		let metadata = match Metadata::from_version(version) {
+			Some(metadata) => metadata,
+			None =>
+				return self.inner.on_chan_open_init(
+					ctx,
+					output,
+					order,
+					connection_hops,
+					port_id,
+					channel_id,
+					counterparty,
+					version,
+					relayer,
+				),
+		};
+		self.inner.on_chan_open_init(
+			ctx,
+			output,
+			order,
+			connection_hops,
+			port_id,
+			channel_id,
+			counterparty,
+			&metadata.app_version()?,
+			relayer,
+		)?;
+		FeeEnabledChannels::<T>::insert(
+			port_id.as_bytes().to_vec(),
+			channel_id.to_string().as_bytes().to_vec(),
+			(),
+		);
+		Ok(())
+	}
+
+	fn on_chan_open_try(
+		&mut self,
+		ctx: &dyn ModuleCallbackContext,
+		output: &mut ModuleOutputBuilder,
+		order: Order,
+		connection_hops: &[ConnectionId],
+		port_id: &PortId,
+		channel_id: &ChannelId,
+		counterparty: &Counterparty,
+		version: &Version,
+		counterparty_version: &Version,
+		relayer: &Signer,
+	) -> Result<Version, Ics04Error> {
+		let metadata = match Metadata::from_version(counterparty_version) {
+			Some(metadata) => metadata,
+			None =>
+				return self.inner.on_chan_open_try(
+					ctx,
+					output,
+					order,
+					connection_hops,
+					port_id,
+					channel_id,
+					counterparty,
+					version,
+					counterparty_version,
+					relayer,
+				),
+		};
+		let version = match Metadata::from_version(version) {
+			Some(metadata) => metadata.app_version()?,
+			None => version.clone(),
+		};
+		let app_version = self.inner.on_chan_open_try(
+			ctx,
+			output,
+			order,
+			connection_hops,
+			port_id,
+			channel_id,
+			counterparty,
+			&version,
+			&metadata.app_version()?,
+			relayer,
+		)?;
+		FeeEnabledChannels::<T>::insert(
+			port_id.as_bytes().to_vec(),
+			channel_id.to_string().as_bytes().to_vec(),
+			(),
+		);
+		Metadata::version(&app_version)
+	}
+
+	fn on_chan_open_ack(
+		&mut self,
+		ctx: &dyn ModuleCallbackContext,
+		output: &mut ModuleOutputBuilder,
+		port_id: &PortId,
+		channel_id: &ChannelId,
+		counterparty_version: &Version,
+		relayer: &Signer,
+	) -> Result<(), Ics04Error> {
+		if !Self::is_fee_enabled(port_id, channel_id) {
+			return self.inner.on_chan_open_ack(
+				ctx,
+				output,
+				port_id,
+				channel_id,
+				counterparty_version,
+				relayer,
+			)
+		}
+		let metadata = Metadata::from_version(counterparty_version).ok_or_else(|| {
+			Ics04Error::implementation_specific(format!(
+				"Expected fee version metadata in counterparty version {counterparty_version}"
+			))
+		})?;
+		self.inner.on_chan_open_ack(
+			ctx,
+			output,
+			port_id,
+			channel_id,
+			&metadata.app_version()?,
+			relayer,
+		)
+	}
+
+	fn on_chan_open_confirm(
+		&mut self,
+		ctx: &dyn ModuleCallbackContext,
+		output: &mut ModuleOutputBuilder,
+		port_id: &PortId,
+		channel_id: &ChannelId,
+		relayer: &Signer,
+	) -> Result<(), Ics04Error> {
+		self.inner.on_chan_open_confirm(ctx, output, port_id, channel_id, relayer)
+	}
+
+	fn on_chan_close_init(
+		&mut self,
+		ctx: &dyn ModuleCallbackContext,
+		output: &mut ModuleOutputBuilder,
+		port_id: &PortId,
+		channel_id: &ChannelId,
+		relayer: &Signer,
+	) -> Result<(), Ics04Error> {
+		self.inner.on_chan_close_init(ctx, output, port_id, channel_id, relayer)?;
+		Self::refund_channel_fees(port_id, channel_id);
+		Ok(())
+	}
+
+	fn on_chan_close_confirm(
+		&mut self,
+		ctx: &dyn ModuleCallbackContext,
+		output: &mut ModuleOutputBuilder,
+		port_id: &PortId,
+		channel_id: &ChannelId,
+		relayer: &Signer,
+	) -> Result<(), Ics04Error> {
+		self.inner.on_chan_close_confirm(ctx, output, port_id, channel_id, relayer)?;
+		Self::refund_channel_fees(port_id, channel_id);
+		Ok(())
+	}
+
+	fn on_chan_upgrade_init(
+		&mut self,
+		ctx: &dyn ModuleCallbackContext,
+		output: &mut ModuleOutputBuilder,
+		port_id: &PortId,
+		channel_id: &ChannelId,
+		proposed_order: Order,
+		proposed_connection_hops: &[ConnectionId],
+		proposed_version: &Version,
+	) -> Result<Version, Ics04Error> {
+		let metadata = match Metadata::from_version(proposed_version) {
+			Some(metadata) => metadata,
+			None =>
+				return self.inner.on_chan_upgrade_init(
+					ctx,
+					output,
+					port_id,
+					channel_id,
+					proposed_order,
+					proposed_connection_hops,
+					proposed_version,
+				),
+		};
+		let app_version = self.inner.on_chan_upgrade_init(
+			ctx,
+			output,
+			port_id,
+			channel_id,
+			proposed_order,
+			proposed_connection_hops,
+			&metadata.app_version()?,
+		)?;
+		Metadata::version(&app_version)
+	}
+
+	fn on_chan_upgrade_try(
+		&mut self,
+		ctx: &dyn ModuleCallbackContext,
+		output: &mut ModuleOutputBuilder,
+		port_id: &PortId,
+		channel_id: &ChannelId,
+		proposed_order: Order,
+		proposed_connection_hops: &[ConnectionId],
+		counterparty_version: &Version,
+	) -> Result<Version, Ics04Error> {
+		let metadata = match Metadata::from_version(counterparty_version) {
+			Some(metadata) => metadata,
+			None =>
+				return self.inner.on_chan_upgrade_try(
+					ctx,
+					output,
+					port_id,
+					channel_id,
+					proposed_order,
+					proposed_connection_hops,
+					counterparty_version,
+				),
+		};
+		let app_version = self.inner.on_chan_upgrade_try(
+			ctx,
+			output,
+			port_id,
+			channel_id,
+			proposed_order,
+			proposed_connection_hops,
+			&metadata.app_version()?,
+		)?;
+		Metadata::version(&app_version)
+	}
+
+	fn on_chan_upgrade_ack(
+		&mut self,
+		ctx: &dyn ModuleCallbackContext,
+		output: &mut ModuleOutputBuilder,
+		port_id: &PortId,
+		channel_id: &ChannelId,
+		counterparty_version: &Version,
+	) -> Result<(), Ics04Error> {
+		let app_version = match Metadata::from_version(counterparty_version) {
+			Some(metadata) => metadata.app_version()?,
+			None => counterparty_version.clone(),
+		};
+		self.inner.on_chan_upgrade_ack(ctx, output, port_id, channel_id, &app_version)
+	}
+
+	fn on_chan_upgrade_open(
+		&mut self,
+		ctx: &dyn ModuleCallbackContext,
+		output: &mut ModuleOutputBuilder,
+		port_id: &PortId,
+		channel_id: &ChannelId,
+		order: Order,
+		connection_hops: &[ConnectionId],
+		version: &Version,
+	) -> Result<(), Ics04Error> {
+		// The upgrade may enable or disable fees on the channel
+		match Metadata::from_version(version) {
+			Some(metadata) => {
+				self.inner.on_chan_upgrade_open(
+					ctx,
+					output,
+					port_id,
+					channel_id,
+					order,
+					connection_hops,
+					&metadata.app_version()?,
+				)?;
+				FeeEnabledChannels::<T>::insert(
+					port_id.as_bytes().to_vec(),
+					channel_id.to_string().as_bytes().to_vec(),
+					(),
+				);
+			},
+			None => {
+				self.inner.on_chan_upgrade_open(
+					ctx,
+					output,
+					port_id,
+					channel_id,
+					order,
+					connection_hops,
+					version,
+				)?;
+				if Self::is_fee_enabled(port_id, channel_id) {
+					Self::refund_channel_fees(port_id, channel_id);
+				}
+			},
+		}
+		Ok(())
+	}
+
+	fn on_recv_packet(
+		&self,
+		ctx: &dyn ModuleCallbackContext,
+		output: &mut ModuleOutputBuilder,
+		packet: &mut Packet,
+		relayer: &Signer,
+	) -> Result<OnRecvPacketAck, Ics04Error> {
+		let ack = self.inner.on_recv_packet(ctx, output, packet, relayer)?;
+		if !Self::is_fee_enabled(&packet.destination_port, &packet.destination_channel) {
+			return Ok(ack)
+		}
+		// Relayers that did not register a counterparty payee are not paid the recv fee, an empty
+		// forward relayer address refunds it to the payer on the source chain
+		let forward_relayer_address = Self::local_account(relayer)
+			.and_then(|relayer| {
+				CounterpartyPayees::<T>::get(
+					packet.destination_channel.to_string().as_bytes().to_vec(),
+					relayer,
+				)
+			})
+			.and_then(|payee| String::from_utf8(payee).ok())
+			.unwrap_or_default();
+		match ack {
+			OnRecvPacketAck::Sync(ack) =>
+				Self::incentivized_acknowledgement(&ack, forward_relayer_address).map(Into::into),
+			OnRecvPacketAck::Async => {
+				// The acknowledgement is wrapped once the application writes it
+				AsyncAckForwardRelayers::<T>::insert(
+					(
+						packet.destination_port.as_bytes().to_vec(),
+						packet.destination_channel.to_string().into_bytes(),
+					),
+					u64::from(packet.sequence),
+					forward_relayer_address.into_bytes(),
+				);
+				Ok(OnRecvPacketAck::Async)
+			},
+		}
+	}
+
+	fn on_write_acknowledgement(
+		&self,
+		packet: &Packet,
+		ack: Acknowledgement,
+	) -> Result<Acknowledgement, Ics04Error> {
+		let ack = self.inner.on_write_acknowledgement(packet, ack)?;
+		let forward_relayer_address = AsyncAckForwardRelayers::<T>::take(
+			(
+				packet.destination_port.as_bytes().to_vec(),
+				packet.destination_channel.to_string().into_bytes(),
+			),
+			u64::from(packet.sequence),
+		);
+		match forward_relayer_address.and_then(|address| String::from_utf8(address).ok()) {
+			Some(address) => Self::incentivized_acknowledgement(&ack, address),
+			None => Ok(ack),
+		}
+	}
+
+	fn on_acknowledgement_packet(
+		&mut self,
+		ctx: &dyn ModuleCallbackContext,
+		output: &mut ModuleOutputBuilder,
+		packet: &mut Packet,
+		acknowledgement: &Acknowledgement,
+		relayer: &Signer,
+	) -> Result<(), Ics04Error> {
+		if !Self::is_fee_enabled(&packet.source_port, &packet.source_channel) {
+			return self.inner.on_acknowledgement_packet(
+				ctx,
+				output,
+				packet,
+				acknowledgement,
+				relayer,
+			)
+		}
+		let ack = serde_json::from_slice::<IncentivizedAcknowledgement>(acknowledgement.as_ref())
+			.map_err(|e| {
+			Ics04Error::implementation_specific(format!(
+				"Failed to decode incentivized acknowledgement {e:?}"
+			))
+		})?;
+		self.inner.on_acknowledgement_packet(
+			ctx,
+			output,
+			packet,
+			&ack.app_acknowledgement()?,
+			relayer,
+		)?;
+		let forward_relayer = Signer::from_str(&ack.forward_relayer_address)
+			.ok()
+			.and_then(|signer| Self::local_account(&signer));
+		let reverse_relayer = Self::local_account(relayer);
+		Self::distribute_fees(packet, |fee| {
+			[(forward_relayer.clone(), fee.recv_fee), (reverse_relayer.clone(), fee.ack_fee)]
+		});
+		Ok(())
+	}
+
+	fn on_timeout_packet(
+		&mut self,
+		ctx: &dyn ModuleCallbackContext,
+		output: &mut ModuleOutputBuilder,
+		packet: &mut Packet,
+		relayer: &Signer,
+	) -> Result<(), Ics04Error> {
+		self.inner.on_timeout_packet(ctx, output, packet, relayer)?;
+		if !Self::is_fee_enabled(&packet.source_port, &packet.source_channel) {
+			return Ok(())
+		}
+		let timeout_relayer = Self::local_account(relayer);
+		Self::distribute_fees(packet, |fee| {
+			[(timeout_relayer.clone(), fee.timeout_fee), (None, Zero::zero())]
+		});
+		Ok(())
+	}
+}
+
+impl<T: Config + Send + Sync, S: IbcModule + Clone + Default + PartialEq + Eq + Debug>
+	Ics29Fee<T, S>
+{
+	fn incentivized_acknowledgement(
+		app_ack: &Acknowledgement,
+		forward_relayer_address: String,
+	) -> Result<Acknowledgement, Ics04Error> {
+		let ack = IncentivizedAcknowledgement::new(app_ack, forward_relayer_address);
+		serde_json::to_vec(&ack).map(Acknowledgement::from_bytes).map_err(|e| {
+			Ics04Error::implementation_specific(format!(
+				"Failed to encode incentivized acknowledgement {e:?}"
+			))
+		})
+	}
+
+	fn is_fee_enabled(port_id: &PortId, channel_id: &ChannelId) -> bool {
+		FeeEnabledChannels::<T>::contains_key(
+			port_id.as_bytes().to_vec(),
+			channel_id.to_string().as_bytes().to_vec(),
+		)
+	}
+
+	fn local_account(signer: &Signer) -> Option<T::AccountId> {
+		<T as crate::Config>::AccountIdConversion::try_from(signer.clone())
+			.ok()
+			.map(|account| account.into_account())
+	}
+
+	/// Pays out the fees escrowed for `packet`, `payouts` returns the relayers to pay along with
+	/// their share of each fee. Relayers are paid through their registered payee, fees that are
+	/// not paid out are refunded.
+	fn distribute_fees<F>(packet: &Packet, payouts: F)
+	where
+		F: Fn(&PacketFeeOf<T>) -> [(Option<T::AccountId>, T::Balance); 2],
+	{
+		let port_id = packet.source_port.as_bytes().to_vec();
+		let channel_id = packet.source_channel.to_string().as_bytes().to_vec();
+		let sequence = u64::from(packet.sequence);
+		let fees = PacketFees::<T>::take((port_id.clone(), channel_id.clone()), sequence);
+		let escrow = Pallet::<T>::account_id();
+		for fee in fees {
+			let mut refund = fee.total();
+			for (relayer, amount) in payouts(&fee) {
+				let relayer = match relayer {
+					Some(relayer) if !amount.is_zero() => relayer,
+					_ => continue,
+				};
+				let payee = Payees::<T>::get(&channel_id, &relayer).unwrap_or(relayer);
+				match transfer::<T>(fee.asset_id.clone(), &escrow, &payee, amount) {
+					Ok(_) => {
+						refund = refund.saturating_sub(amount);
+						Pallet::<T>::deposit_event(Event::<T>::FeePaid {
+							port_id: port_id.clone(),
+							channel_id: channel_id.clone(),
+							sequence,
+							asset_id: fee.asset_id.clone(),
+							payee,
+							amount,
+						});
+					},
+					Err(e) => {
+						log::error!(target: "pallet_ibc", "Failed to pay packet fee: {:?}", e);
+					},
+				}
+			}
+			Self::refund(&port_id, &channel_id, sequence, &fee, refund);
+		}
+	}
+
+	fn refund_channel_fees(port_id: &PortId, channel_id: &ChannelId) {
+		let port_id = port_id.as_bytes().to_vec();
+		let channel_id = channel_id.to_string().as_bytes().to_vec();
+		for (sequence, fees) in PacketFees::<T>::drain_prefix((port_id.clone(), channel_id.clone()))
+		{
+			for fee in fees {
+				Self::refund(&port_id, &channel_id, sequence, &fee, fee.total());
+			}
+		}
+		FeeEnabledChannels::<T>::remove(&port_id, &channel_id);
+	}
+
+	fn refund(
+		port_id: &[u8],
+		channel_id: &[u8],
+		sequence: u64,
+		fee: &PacketFeeOf<T>,
+		amount: T::Balance,
+	) {
+		match transfer::<T>(
+			fee.asset_id.clone(),
+			&Pallet::<T>::account_id(),
+			&fee.refund_address,
+			amount,
+		) {
+			Ok(_) if !amount.is_zero() => Pallet::<T>::deposit_event(Event::<T>::FeeRefunded {
+				port_id: port_id.to_vec(),
+				channel_id: channel_id.to_vec(),
+				sequence,
+				asset_id: fee.asset_id.clone(),
+				refund_address: fee.refund_address.clone(),
+				amount,
+			}),
+			Ok(_) => {},
+			Err(e) => {
+				log::error!(target: "pallet_ibc", "Failed to refund packet fee: {:?}", e);
+			},
+		}
+	}
+}
@@ -3,22 +3,26 @@ use core::time::Duration;
 
 use crate::{
 	ics23::{
-		acknowledgements::Acknowledgements, channels::Channels, client_states::ClientStates,
-		connections::Connections, consensus_states::ConsensusStates,
-		next_seq_recv::NextSequenceRecv, packet_commitments::PacketCommitment,
-		receipts::PacketReceipt,
+		acknowledgements::Acknowledgements, channel_upgrades::ChannelUpgrades, channels::Channels,
+		client_states::ClientStates, connections::Connections, consensus_states::ConsensusStates,
+		next_seq_recv::NextSequenceRecv, packet_commitments::PacketCommitment, paginate,
+		receipts::PacketReceipt, upgrade_errors::UpgradeErrors,
 	},
 	light_clients::AnyClientState,
 	routing::Context,
-	Acks, ChannelsConnection, Config, ConnectionClient, DenomToAssetId, Error, EscrowAddresses,
-	IbcAssets, Pallet, PendingRecvPacketSeqs, PendingSendPacketSeqs, RecvPackets, SendPackets,
-	MODULE_ID,
+	Acks, ChannelIds, ChannelsConnection, Config, ConnectionClient, DenomToAssetId, Error,
+	EscrowAddressChannels, EscrowAddresses, EscrowedDenoms, ForwardAttempts, IbcAssets, Pallet,
+	PendingAcknowledgements, PendingRecvPacketSeqs, PendingSendPacketSeqs, RecvPackets,
+	SendPackets, TotalEscrowed, MODULE_ID,
 };
 use codec::{Decode, Encode};
 use frame_support::traits::{fungibles::Inspect, Currency};
 use ibc::{
 	applications::transfer::{
-		msgs::transfer::MsgTransfer, relay::send_transfer::send_transfer, PrefixedCoin,
+		is_sender_chain_source,
+		msgs::{multi_transfer::MsgMultiTransfer, transfer::MsgTransfer},
+		relay::send_transfer::send_multi_transfer,
+		PrefixedCoin, PrefixedDenom,
 	},
 	core::{
 		ics02_client::{
@@ -32,14 +36,19 @@ use ibc::{
 			msgs::{chan_close_init::MsgChannelCloseInit, chan_open_init::MsgChannelOpenInit},
 			packet::{Packet, Sequence},
 		},
+		ics05_port::context::PortReader,
 		ics24_host::{
 			identifier::*,
 			path::{
-				AcksPath, ChannelEndsPath, ClientConsensusStatePath, ClientStatePath,
-				CommitmentsPath, ConnectionsPath, ReceiptsPath, SeqRecvsPath,
+				AcksPath, ChannelEndsPath, ChannelUpgradeErrorPath, ChannelUpgradePath,
+				ClientConsensusStatePath, ClientStatePath, CommitmentsPath, ConnectionsPath,
+				ReceiptsPath, SeqRecvsPath,
 			},
 		},
-		ics26_routing::handler::MsgReceipt,
+		ics26_routing::{
+			context::{Ics26Context, Router},
+			handler::MsgReceipt,
+		},
 	},
 	handler::HandlerOutputBuilder,
 	signer::Signer,
@@ -50,21 +59,26 @@ use ibc::{
 use ibc_primitives::{
 	apply_prefix, channel_id_from_bytes, client_id_from_bytes, connection_id_from_bytes,
 	get_channel_escrow_address, port_id_from_bytes, runtime_interface, ConnectionHandshake,
-	Error as IbcHandlerError, HandlerMessage, IbcHandler, IdentifiedChannel, IdentifiedClientState,
-	IdentifiedConnection, PacketInfo, PacketState, QueryChannelResponse, QueryChannelsResponse,
-	QueryClientStateResponse, QueryConnectionResponse, QueryConnectionsResponse,
-	QueryConsensusStateResponse, QueryNextSequenceReceiveResponse,
+	Error as IbcHandlerError, EscrowedAmount, HandlerMessage, IbcHandler, IdentifiedChannel,
+	IdentifiedClientState, IdentifiedConnection, PacketInfo, PacketState, PageRequest,
+	PageResponse, QueryChannelResponse, QueryChannelUpgradeResponse, QueryChannelsResponse,
+	QueryClientStateResponse, QueryClientsResponse, QueryConnectionResponse,
+	QueryConnectionsResponse, QueryConsensusStateResponse, QueryEscrowInvariantResponse,
+	QueryForwardStatusResponse, QueryNextSequenceReceiveResponse,
 	QueryPacketAcknowledgementResponse, QueryPacketAcknowledgementsResponse,
 	QueryPacketCommitmentResponse, QueryPacketCommitmentsResponse, QueryPacketReceiptResponse,
-	Timeout,
+	QueryUpgradeErrorResponse, Timeout,
 };
 use scale_info::prelude::string::ToString;
 use sp_core::crypto::AccountId32;
 use sp_runtime::{
-	traits::{Get, IdentifyAccount},
+	traits::{Get, IdentifyAccount, UniqueSaturatedInto},
 	Either,
 };
-use sp_std::prelude::*;
+use sp_std::{
+	collections::{btree_map::BTreeMap, btree_set::BTreeSet},
+	prelude::*,
+};
 use tendermint_proto::Protobuf;
 
 const PACKET_CLEANUP_PER_CYCLE: u64 = 1001;
@@ -75,11 +89,14 @@ where
 	u32: From<<T as frame_system::Config>::BlockNumber>,
 	AccountId32: From<<T as frame_system::Config>::AccountId>,
 {
+	/// Executes the messages and returns the gas used by the wasm light clients.
 	pub(crate) fn execute_ibc_messages(
 		ctx: &mut Context<T>,
 		messages: Vec<ibc_proto::google::protobuf::Any>,
-	) {
-		let (events, logs) =
+	) -> u64 {
+		// wasm light clients are run by the contracts stored in this pallet
+		let mut wasm_contracts = crate::wasm::WasmContracts::<T>::default();
+		let (events, logs) = ics08_wasm::executor::using(&mut wasm_contracts, || {
 			messages.into_iter().fold((vec![], vec![]), |(mut events, mut logs), msg| {
 				match ibc::core::ics26_routing::handler::deliver(ctx, msg) {
 					Ok(MsgReceipt { events: temp_events, log: temp_logs }) => {
@@ -92,12 +109,14 @@ where
 					},
 				}
 				(events, logs)
-			});
+			})
+		});
 
 		log::trace!(target: "pallet_ibc", "logs: {:#?}", logs);
 		if !events.is_empty() {
 			Self::deposit_event(events.into())
 		};
+		wasm_contracts.gas_used()
 	}
 }
 
@@ -124,6 +143,38 @@ where
 		Ok(QueryChannelResponse { channel, trie_key: key, height: host_height::<T>() })
 	}
 
+	/// Get the upgrade proposed by a channel end
+	pub fn channel_upgrade(
+		channel_id: Vec<u8>,
+		port_id: Vec<u8>,
+	) -> Result<QueryChannelUpgradeResponse, Error<T>> {
+		let port_id = port_id_from_bytes(port_id).map_err(|_| Error::<T>::DecodingError)?;
+		let channel_id =
+			channel_id_from_bytes(channel_id).map_err(|_| Error::<T>::DecodingError)?;
+		let upgrade = ChannelUpgrades::<T>::get(port_id.clone(), channel_id)
+			.ok_or(Error::<T>::ChannelUpgradeNotFound)?;
+		let upgrade_path = format!("{}", ChannelUpgradePath(port_id, channel_id));
+		let key = apply_prefix(T::PalletPrefix::get(), vec![upgrade_path]);
+
+		Ok(QueryChannelUpgradeResponse { upgrade, trie_key: key, height: host_height::<T>() })
+	}
+
+	/// Get the error receipt of the last aborted upgrade of a channel end
+	pub fn channel_upgrade_error(
+		channel_id: Vec<u8>,
+		port_id: Vec<u8>,
+	) -> Result<QueryUpgradeErrorResponse, Error<T>> {
+		let port_id = port_id_from_bytes(port_id).map_err(|_| Error::<T>::DecodingError)?;
+		let channel_id =
+			channel_id_from_bytes(channel_id).map_err(|_| Error::<T>::DecodingError)?;
+		let error_receipt = UpgradeErrors::<T>::get(port_id.clone(), channel_id)
+			.ok_or(Error::<T>::UpgradeErrorNotFound)?;
+		let error_path = format!("{}", ChannelUpgradeErrorPath(port_id, channel_id));
+		let key = apply_prefix(T::PalletPrefix::get(), vec![error_path]);
+
+		Ok(QueryUpgradeErrorResponse { error_receipt, trie_key: key, height: host_height::<T>() })
+	}
+
 	/// Get a connection state
 	pub fn connection(connection_id: Vec<u8>) -> Result<QueryConnectionResponse, Error<T>> {
 		let connection_id =
@@ -157,6 +208,18 @@ where
 			.collect::<Vec<_>>()
 	}
 
+	/// Get a page of client states
+	pub fn clients_paginated(pagination: PageRequest) -> (QueryClientsResponse, PageResponse) {
+		let (clients, pagination) = paginate(pagination, |start_key| {
+			ClientStates::<T>::iter_from(start_key).map(|(key, (client_id, client_state))| {
+				let client_id = client_id.as_bytes().to_vec();
+				(key, IdentifiedClientState { client_id, client_state })
+			})
+		});
+
+		(QueryClientsResponse { clients, height: host_height::<T>() }, pagination)
+	}
+
 	/// Get a consensus state for client
 	pub fn consensus_state(
 		client_id: Vec<u8>,
@@ -243,25 +306,55 @@ where
 	/// Get all channel states
 	pub fn channels() -> Result<QueryChannelsResponse, Error<T>> {
 		let channels = Channels::<T>::iter()
-			.map(|(port_id, channel_id, channel_end)| {
-				Ok(IdentifiedChannel { channel_id, port_id, channel_end })
+			.map(|(port_id, channel_id, channel_end)| IdentifiedChannel {
+				channel_id,
+				port_id,
+				channel_end,
 			})
-			.collect::<Result<Vec<_>, Error<T>>>()?;
+			.collect::<Vec<_>>();
 
 		Ok(QueryChannelsResponse { channels, height: host_height::<T>() })
 	}
 
+	/// Get a page of channel states
+	pub fn channels_paginated(
+		pagination: PageRequest,
+	) -> Result<(QueryChannelsResponse, PageResponse), Error<T>> {
+		let (channels, pagination) = paginate(pagination, |start_key| {
+			Channels::<T>::iter_from(start_key).map(|(key, (port_id, channel_id, channel_end))| {
+				(key, IdentifiedChannel { channel_id, port_id, channel_end })
+			})
+		});
+
+		Ok((QueryChannelsResponse { channels, height: host_height::<T>() }, pagination))
+	}
+
 	/// Get all connection states
 	pub fn connections() -> Result<QueryConnectionsResponse, Error<T>> {
-		let connections = Connections::<T>::iter()
-			.map(|(connection_id, connection_end)| {
-				Ok(IdentifiedConnection { connection_id, connection_end })
+		let connections = Connections::<T>::iter_from(None)
+			.map(|(_, (connection_id, connection_end))| {
+				let connection_id = connection_id.as_bytes().to_vec();
+				IdentifiedConnection { connection_id, connection_end }
 			})
-			.collect::<Result<Vec<_>, Error<T>>>()?;
+			.collect::<Vec<_>>();
 
 		Ok(QueryConnectionsResponse { connections, height: host_height::<T>() })
 	}
 
+	/// Get a page of connection states
+	pub fn connections_paginated(
+		pagination: PageRequest,
+	) -> Result<(QueryConnectionsResponse, PageResponse), Error<T>> {
+		let (connections, pagination) = paginate(pagination, |start_key| {
+			Connections::<T>::iter_from(start_key).map(|(key, (connection_id, connection_end))| {
+				let connection_id = connection_id.as_bytes().to_vec();
+				(key, IdentifiedConnection { connection_id, connection_end })
+			})
+		});
+
+		Ok((QueryConnectionsResponse { connections, height: host_height::<T>() }, pagination))
+	}
+
 	/// Get all channels bound to this connection
 	pub fn connection_channels(connection_id: Vec<u8>) -> Result<QueryChannelsResponse, Error<T>> {
 		let identifiers = ChannelsConnection::<T>::get(connection_id);
@@ -294,23 +387,42 @@ where
 			.map_err(|_| Error::<T>::DecodingError)?;
 		let port_id =
 			port_id_from_bytes(port_id_bytes.clone()).map_err(|_| Error::<T>::DecodingError)?;
-		let commitments = PacketCommitment::<T>::iter()
-			.filter_map(|((p, c, s), commitment)| {
-				if p == port_id && c == channel_id {
+		let commitments = PacketCommitment::<T>::iter_channel_from(port_id, channel_id, None)
+			.map(|(_, (sequence, commitment))| PacketState {
+				port_id: port_id_bytes.clone(),
+				channel_id: channel_id_bytes.clone(),
+				sequence: sequence.into(),
+				data: commitment,
+			})
+			.collect::<Vec<_>>();
+
+		Ok(QueryPacketCommitmentsResponse { commitments, height: host_height::<T>() })
+	}
+
+	pub fn packet_commitments_paginated(
+		channel_id_bytes: Vec<u8>,
+		port_id_bytes: Vec<u8>,
+		pagination: PageRequest,
+	) -> Result<(QueryPacketCommitmentsResponse, PageResponse), Error<T>> {
+		let channel_id = channel_id_from_bytes(channel_id_bytes.clone())
+			.map_err(|_| Error::<T>::DecodingError)?;
+		let port_id =
+			port_id_from_bytes(port_id_bytes.clone()).map_err(|_| Error::<T>::DecodingError)?;
+		let (commitments, pagination) = paginate(pagination, |start_key| {
+			PacketCommitment::<T>::iter_channel_from(port_id, channel_id, start_key).map(
+				|(key, (sequence, commitment))| {
 					let packet_state = PacketState {
 						port_id: port_id_bytes.clone(),
 						channel_id: channel_id_bytes.clone(),
-						sequence: s.into(),
+						sequence: sequence.into(),
 						data: commitment,
 					};
-					Some(packet_state)
-				} else {
-					None
-				}
-			})
-			.collect::<Vec<_>>();
+					(key, packet_state)
+				},
+			)
+		});
 
-		Ok(QueryPacketCommitmentsResponse { commitments, height: host_height::<T>() })
+		Ok((QueryPacketCommitmentsResponse { commitments, height: host_height::<T>() }, pagination))
 	}
 
 	pub fn packet_acknowledgements(
@@ -321,22 +433,40 @@ where
 			.map_err(|_| Error::<T>::DecodingError)?;
 		let port_id =
 			port_id_from_bytes(port_id_bytes.clone()).map_err(|_| Error::<T>::DecodingError)?;
-		let acks = Acknowledgements::<T>::iter()
-			.filter_map(|((p, c, s), ack)| {
-				if p == port_id && c == channel_id {
+		let acks = Acknowledgements::<T>::iter_channel_from(port_id, channel_id, None)
+			.map(|(_, (sequence, ack))| PacketState {
+				port_id: port_id_bytes.clone(),
+				channel_id: channel_id_bytes.clone(),
+				sequence: sequence.into(),
+				data: ack,
+			})
+			.collect::<Vec<_>>();
+		Ok(QueryPacketAcknowledgementsResponse { acks, height: host_height::<T>() })
+	}
+
+	pub fn packet_acknowledgements_paginated(
+		channel_id_bytes: Vec<u8>,
+		port_id_bytes: Vec<u8>,
+		pagination: PageRequest,
+	) -> Result<(QueryPacketAcknowledgementsResponse, PageResponse), Error<T>> {
+		let channel_id = channel_id_from_bytes(channel_id_bytes.clone())
+			.map_err(|_| Error::<T>::DecodingError)?;
+		let port_id =
+			port_id_from_bytes(port_id_bytes.clone()).map_err(|_| Error::<T>::DecodingError)?;
+		let (acks, pagination) = paginate(pagination, |start_key| {
+			Acknowledgements::<T>::iter_channel_from(port_id, channel_id, start_key).map(
+				|(key, (sequence, ack))| {
 					let packet_state = PacketState {
 						port_id: port_id_bytes.clone(),
 						channel_id: channel_id_bytes.clone(),
-						sequence: s.into(),
+						sequence: sequence.into(),
 						data: ack,
 					};
-					Some(packet_state)
-				} else {
-					None
-				}
-			})
-			.collect::<Vec<_>>();
-		Ok(QueryPacketAcknowledgementsResponse { acks, height: host_height::<T>() })
+					(key, packet_state)
+				},
+			)
+		});
+		Ok((QueryPacketAcknowledgementsResponse { acks, height: host_height::<T>() }, pagination))
 	}
 
 	pub fn unreceived_packets(
@@ -585,7 +715,12 @@ where
 
 			// We first try to remove sequences that were skipped in a previous cycle
 			for seq in recv_seq_set.clone() {
-				if !Acknowledgements::<T>::contains_key((port_id.clone(), channel_id, seq.into())) {
+				// Packets whose acknowledgement is not written yet are kept
+				if !Acknowledgements::<T>::contains_key((port_id.clone(), channel_id, seq.into())) &&
+					!PendingAcknowledgements::<T>::contains_key(
+						(port_id_bytes.clone(), channel_id_bytes.clone()),
+						seq,
+					) {
 					let key = Pallet::<T>::recv_packet_key(
 						channel_id_bytes.clone(),
 						port_id_bytes.clone(),
@@ -609,7 +744,12 @@ where
 			let range = (last_removed_ack + 1)..
 				(last_removed_ack + PACKET_CLEANUP_PER_CYCLE).min(next_seq_recv.into());
 			for seq in range {
-				if !Acknowledgements::<T>::contains_key((port_id.clone(), channel_id, seq.into())) {
+				// Packets whose acknowledgement is not written yet are kept
+				if !Acknowledgements::<T>::contains_key((port_id.clone(), channel_id, seq.into())) &&
+					!PendingAcknowledgements::<T>::contains_key(
+						(port_id_bytes.clone(), channel_id_bytes.clone()),
+						seq,
+					) {
 					let key = Pallet::<T>::recv_packet_key(
 						channel_id_bytes.clone(),
 						port_id_bytes.clone(),
@@ -726,6 +866,173 @@ impl<T: Config> Pallet<T> {
 		let set = EscrowAddresses::<T>::get();
 		set.contains(&address)
 	}
+
+	/// Records the channel an escrow address holds tokens for, so that movements in and out of
+	/// it can be attributed in `TotalEscrowed`.
+	/// The mapping is kept after the channel is closed since refunds of in-flight packets are
+	/// still paid out of the escrow account.
+	pub(crate) fn register_channel_escrow_address(
+		port_id: &PortId,
+		channel_id: ChannelId,
+	) -> Result<(), IbcHandlerError> {
+		let escrow_address = get_channel_escrow_address(port_id, channel_id).map_err(|_| {
+			IbcHandlerError::ChannelOrPortError {
+				msg: Some("Failed to derive channel escrow address".to_string()),
+			}
+		})?;
+		let account_id = T::AccountIdConversion::try_from(escrow_address)
+			.map_err(|_| IbcHandlerError::ChannelOrPortError {
+				msg: Some("Failed to derive channel escrow address".to_string()),
+			})?
+			.into_account();
+		if !EscrowAddressChannels::<T>::contains_key(&account_id) {
+			EscrowAddressChannels::<T>::insert(
+				account_id,
+				(port_id.as_bytes().to_vec(), channel_id.to_string().into_bytes()),
+			);
+		}
+		Ok(())
+	}
+
+	/// Updates the escrowed totals when `amount` of `denom` moves from `from` to `to`.
+	/// Called for every ics20 `send_coins`, which covers escrowing on send, unescrowing on receive
+	/// and refunds on acknowledgement errors and timeouts.
+	/// Totals are kept per base denom, the ibc denoms making up each total are recorded in
+	/// `EscrowedDenoms` so that their balances can be summed up again.
+	pub(crate) fn track_escrowed_amount(
+		from: &<T as frame_system::Config>::AccountId,
+		to: &<T as frame_system::Config>::AccountId,
+		denom: &PrefixedDenom,
+		amount: u128,
+	) {
+		let base_denom = denom.base_denom().as_str().as_bytes().to_vec();
+		if let Some(port_channel) = EscrowAddressChannels::<T>::get(to) {
+			TotalEscrowed::<T>::mutate(port_channel.clone(), base_denom.clone(), |total| {
+				*total = total.saturating_add(amount)
+			});
+			EscrowedDenoms::<T>::mutate(port_channel, base_denom.clone(), |denoms| {
+				denoms.insert(denom.to_string().into_bytes());
+			});
+		}
+		if let Some(port_channel) = EscrowAddressChannels::<T>::get(from) {
+			TotalEscrowed::<T>::mutate(port_channel, base_denom, |total| {
+				*total = total.saturating_sub(amount)
+			});
+		}
+	}
+
+	/// Balance of `denom` held by `account`, zero if the denom is not a known asset.
+	fn escrowed_balance(account: &<T as frame_system::Config>::AccountId, denom: &str) -> u128 {
+		match T::IbcDenomToAssetIdConversion::from_denom_to_asset_id(denom) {
+			Ok(asset_id) if asset_id == T::NativeAssetId::get() =>
+				T::NativeCurrency::free_balance(account).unique_saturated_into(),
+			Ok(asset_id) => T::Fungibles::balance(asset_id, account).unique_saturated_into(),
+			Err(_) => 0,
+		}
+	}
+
+	/// Seeds `TotalEscrowed` with the balances the escrow accounts of the open transfer channels
+	/// already hold, so that escrow created before the tracking existed is checked as well.
+	/// Returns the number of storage reads and writes made.
+	pub(crate) fn seed_escrowed_amounts() -> (u64, u64) {
+		let mut denoms = BTreeSet::new();
+		let mut reads = 1u64;
+		let mut writes = 0u64;
+		if let Some(native) =
+			T::IbcDenomToAssetIdConversion::from_asset_id_to_denom(T::NativeAssetId::get())
+		{
+			denoms.insert(native);
+		}
+		let mut start_key = None;
+		loop {
+			let IbcAssets { denoms: page, next_id, .. } =
+				T::IbcDenomToAssetIdConversion::ibc_assets(start_key, 100);
+			reads += page.len() as u64 + 1;
+			denoms.extend(page.into_iter().filter_map(|denom| String::from_utf8(denom).ok()));
+			match next_id {
+				Some(id) => start_key = Some(Either::Left(id)),
+				None => break,
+			}
+		}
+
+		let port_id = PortId::transfer();
+		for channel_id in ChannelIds::<T>::get() {
+			let Ok(channel_id) = channel_id_from_bytes(channel_id) else { continue };
+			let Ok(escrow_address) = get_channel_escrow_address(&port_id, channel_id) else {
+				continue
+			};
+			let Ok(account_id) = T::AccountIdConversion::try_from(escrow_address) else { continue };
+			let account_id = account_id.into_account();
+			let port_channel = (port_id.as_bytes().to_vec(), channel_id.to_string().into_bytes());
+			let mut totals = BTreeMap::<Vec<u8>, (u128, BTreeSet<Vec<u8>>)>::new();
+			for denom in &denoms {
+				let Ok(prefixed_denom) = PrefixedDenom::from_str(denom) else { continue };
+				let balance = Self::escrowed_balance(&account_id, denom);
+				reads += 2;
+				if balance == 0 {
+					continue
+				}
+				let (total, escrowed) = totals
+					.entry(prefixed_denom.base_denom().as_str().as_bytes().to_vec())
+					.or_default();
+				*total = total.saturating_add(balance);
+				escrowed.insert(denom.as_bytes().to_vec());
+			}
+			if totals.is_empty() {
+				continue
+			}
+			EscrowAddressChannels::<T>::insert(account_id, port_channel.clone());
+			writes += 1;
+			for (base_denom, (total, escrowed)) in totals {
+				TotalEscrowed::<T>::insert(port_channel.clone(), base_denom.clone(), total);
+				EscrowedDenoms::<T>::insert(port_channel.clone(), base_denom, escrowed);
+				writes += 2;
+			}
+		}
+		(reads, writes)
+	}
+
+	/// Returns the attempts made to forward the tokens received in the packet with `seq` on
+	/// `port_id` and `channel_id`.
+	pub fn forward_status(
+		port_id: Vec<u8>,
+		channel_id: Vec<u8>,
+		seq: u64,
+	) -> QueryForwardStatusResponse
+	where
+		u32: From<<T as frame_system::Config>::BlockNumber>,
+	{
+		QueryForwardStatusResponse {
+			attempts: ForwardAttempts::<T>::get((port_id, channel_id), seq).into_inner(),
+			height: host_height::<T>(),
+		}
+	}
+
+	/// Returns the tracked escrowed amount and the actual escrow account balance for every
+	/// (port, channel, base denom) that has tokens escrowed.
+	pub fn escrow_invariant() -> QueryEscrowInvariantResponse
+	where
+		u32: From<<T as frame_system::Config>::BlockNumber>,
+	{
+		let escrows = TotalEscrowed::<T>::iter()
+			.filter_map(|((port_id, channel_id), denom, tracked)| {
+				let port = port_id_from_bytes(port_id.clone()).ok()?;
+				let channel = channel_id_from_bytes(channel_id.clone()).ok()?;
+				let escrow_address = get_channel_escrow_address(&port, channel).ok()?;
+				let account_id =
+					T::AccountIdConversion::try_from(escrow_address).ok()?.into_account();
+				let balance =
+					EscrowedDenoms::<T>::get((port_id.clone(), channel_id.clone()), &denom)
+						.into_iter()
+						.filter_map(|escrowed| String::from_utf8(escrowed).ok())
+						.fold(0u128, |balance, escrowed| {
+							balance.saturating_add(Self::escrowed_balance(&account_id, &escrowed))
+						});
+				Some(EscrowedAmount { port_id, channel_id, denom, tracked, balance })
+			})
+			.collect();
+		QueryEscrowInvariantResponse { escrows, height: host_height::<T>() }
+	}
 }
 
 impl<T: Config> Pallet<T> {
@@ -963,6 +1270,28 @@ where
 			let msg = Some(format!("Failed to {action} acknowledgement{err:?}"));
 			IbcHandlerError::AcknowledgementError { msg }
 		};
+		// Acknowledgements the receiving module returned as pending go through its middlewares
+		// before being written
+		let is_pending = PendingAcknowledgements::<T>::contains_key(
+			(
+				packet.destination_port.as_bytes().to_vec(),
+				packet.destination_channel.to_string().into_bytes(),
+			),
+			u64::from(packet.sequence),
+		);
+		let ack = if is_pending {
+			let module_id = ctx
+				.lookup_module_by_port(&packet.destination_port)
+				.map_err(|e| error("route", Ics04Error::ics05_port(e)))?;
+			ctx.router_mut()
+				.get_route_mut(&module_id)
+				.ok_or_else(|| error("route", Ics04Error::route_not_found()))?
+				.on_write_acknowledgement(&packet, ack.into())
+				.map_err(|e| error("process", e))?
+				.into_bytes()
+		} else {
+			ack
+		};
 		let result =
 			ibc::core::ics04_channel::handler::write_acknowledgement::process(&ctx, packet, ack)
 				.map_err(|e| error("validate", e))?;
@@ -1043,9 +1372,20 @@ where
 	}
 
 	pub(crate) fn send_transfer(msg: MsgTransfer<PrefixedCoin>) -> Result<(), IbcHandlerError> {
+		Self::send_multi_transfer(msg.into())
+	}
+
+	pub(crate) fn send_multi_transfer(
+		msg: MsgMultiTransfer<PrefixedCoin>,
+	) -> Result<(), IbcHandlerError> {
+		if msg.tokens.iter().any(|token| {
+			is_sender_chain_source(msg.source_port.clone(), msg.source_channel, &token.denom)
+		}) {
+			Pallet::<T>::register_channel_escrow_address(&msg.source_port, msg.source_channel)?;
+		}
 		let mut ctx = Context::<T>::default();
 		let mut handler_output = HandlerOutputBuilder::default();
-		send_transfer::<_, _>(&mut ctx, &mut handler_output, msg)
+		send_multi_transfer::<_, _>(&mut ctx, &mut handler_output, msg)
 			.map_err(|e| IbcHandlerError::SendTransferError { msg: Some(e.to_string()) })?;
 		let result = handler_output.with_result(());
 		Self::deposit_event(result.events.into());
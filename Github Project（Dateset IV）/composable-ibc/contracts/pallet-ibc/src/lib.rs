@@ -54,13 +54,17 @@ pub mod events;
 pub mod ics20;
 mod ics23;
 pub mod light_clients;
+pub mod migrations;
 mod port;
 pub mod routing;
+mod wasm;
 pub use client::HostConsensusProof;
 pub use ibc_primitives::Timeout;
 pub use light_client_common;
 
 pub const MODULE_ID: &str = "pallet_ibc";
+/// Maximum number of assets sent in a single multi-asset transfer
+pub const MAX_TRANSFER_ASSETS: usize = 8;
 
 #[derive(Clone, PartialEq, Eq, Encode, Decode, RuntimeDebug, TypeInfo)]
 pub struct Any {
@@ -111,6 +115,31 @@ pub struct TransferParams<AccountId> {
 	pub timeout: Timeout,
 }
 
+/// A transfer forwarded by an ics20 memo that has not been acknowledged yet.
+#[derive(
+	frame_support::RuntimeDebug, PartialEq, Eq, scale_info::TypeInfo, Encode, Decode, Clone,
+)]
+pub struct InFlightForward<AccountId, AssetId, MemoMessage> {
+	/// The received packet, its acknowledgement is held until the forward settles
+	pub packet: ibc_primitives::PacketInfo,
+	/// Receiver of the incoming transfer, it sends the forwarded transfer
+	pub sender: AccountId,
+	/// Receiver of the forwarded transfer on the next chain
+	pub receiver: Vec<u8>,
+	/// Channel the transfer is forwarded on
+	pub channel: u64,
+	/// Timeout offset in nanoseconds used for every attempt
+	pub timeout: u64,
+	/// Number of times the transfer is sent again if it times out
+	pub retries: u64,
+	pub asset_id: AssetId,
+	pub amount: u128,
+	/// Amount received in `packet` before the service fee was charged, a failed forward reverts
+	/// the fee along with the forwarded amount
+	pub received_amount: u128,
+	pub next_memo: Option<MemoMessage>,
+}
+
 #[derive(
 	frame_support::RuntimeDebug, PartialEq, Eq, scale_info::TypeInfo, Encode, Decode, Clone,
 )]
@@ -128,6 +157,9 @@ mod mock;
 mod tests;
 
 pub mod ics20_fee;
+pub mod ics20_hooks;
+pub mod ics27;
+pub mod ics29_fee;
 mod impls;
 pub mod weight;
 
@@ -168,8 +200,8 @@ pub mod pallet {
 	};
 	use ibc::{
 		applications::transfer::{
-			context::BankKeeper, is_sender_chain_source, msgs::transfer::MsgTransfer, Amount,
-			PrefixedCoin, PrefixedDenom,
+			context::BankKeeper, is_sender_chain_source, msgs::multi_transfer::MsgMultiTransfer,
+			packet::Forwarding, Amount, PrefixedCoin, PrefixedDenom,
 		},
 		bigint::U256,
 		core::{
@@ -308,10 +340,32 @@ pub mod pallet {
 		type FlatFeeAssetId: Get<Self::AssetId>;
 		//Asset amount that will be charged. for example 10 (USDT)
 		type FlatFeeAmount: Get<Self::Balance>;
+		/// Maximum size in bytes of the code of a wasm light client
+		#[pallet::constant]
+		type MaxWasmCodeSize: Get<u32>;
+		/// Gas available to a single call of a wasm light client
+		#[pallet::constant]
+		type WasmGasLimit: Get<u64>;
+		/// Maximum weight of the calls an interchain account hosted on this chain executes for a
+		/// single packet, it is charged for every packet received on the host port
+		#[pallet::constant]
+		type IcaMaxTxWeight: Get<Weight>;
+		/// Maximum number of attempts to forward the tokens of a received transfer, a memo forward
+		/// may ask for one retry less
+		#[pallet::constant]
+		type MaxForwardAttempts: Get<u32>;
+		/// Number of blocks the forward attempts of a received transfer can still be queried for
+		/// after the transfer was acknowledged
+		#[pallet::constant]
+		type ForwardStatusRetention: Get<Self::BlockNumber>;
 	}
 
+	/// The current storage version.
+	const STORAGE_VERSION: StorageVersion = StorageVersion::new(2);
+
 	#[pallet::pallet]
 	#[pallet::generate_store(pub (super) trait Store)]
+	#[pallet::storage_version(STORAGE_VERSION)]
 	#[pallet::without_storage_info]
 	pub struct Pallet<T>(_);
 
@@ -358,9 +412,31 @@ pub mod pallet {
 
 	#[pallet::storage]
 	#[allow(clippy::disallowed_types)]
-	/// storage map where key is transfer sequence number and value calculated fee for that sequence
-	/// number
-	pub type SequenceFee<T: Config> = StorageMap<_, Blake2_128Concat, u64, u128, ValueQuery>;
+	/// storage map where key is ((port_id, channel_id), sequence) of a sent transfer and value the
+	/// fee calculated for it
+	pub type SequenceFee<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		(Vec<u8>, Vec<u8>),
+		Blake2_128Concat,
+		u64,
+		u128,
+		ValueQuery,
+	>;
+
+	#[pallet::storage]
+	#[allow(clippy::disallowed_types)]
+	/// storage map where key is ((port_id, channel_id), sequence) of a sent multi-asset transfer
+	/// and value the fee calculated for each of its assets
+	pub type SequenceFees<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		(Vec<u8>, Vec<u8>),
+		Blake2_128Concat,
+		u64,
+		Vec<u128>,
+		ValueQuery,
+	>;
 
 	#[pallet::storage]
 	#[allow(clippy::disallowed_types)]
@@ -411,6 +487,44 @@ pub mod pallet {
 	pub type EscrowAddresses<T: Config> =
 		StorageValue<_, BTreeSet<<T as frame_system::Config>::AccountId>, ValueQuery>;
 
+	#[pallet::storage]
+	#[allow(clippy::disallowed_types)]
+	/// Escrow address => (port_id, channel_id) whose tokens it holds
+	pub type EscrowAddressChannels<T: Config> = StorageMap<
+		_,
+		Blake2_128Concat,
+		<T as frame_system::Config>::AccountId,
+		(Vec<u8>, Vec<u8>),
+		OptionQuery,
+	>;
+
+	#[pallet::storage]
+	#[allow(clippy::disallowed_types)]
+	/// (port_id, channel_id), base denom => Total amount of tokens escrowed for outstanding
+	/// vouchers on the counterparty chain
+	pub type TotalEscrowed<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		(Vec<u8>, Vec<u8>),
+		Blake2_128Concat,
+		Vec<u8>,
+		u128,
+		ValueQuery,
+	>;
+
+	#[pallet::storage]
+	#[allow(clippy::disallowed_types)]
+	/// (port_id, channel_id), base denom => Ibc denoms escrowed under that base denom
+	pub type EscrowedDenoms<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		(Vec<u8>, Vec<u8>),
+		Blake2_128Concat,
+		Vec<u8>,
+		BTreeSet<Vec<u8>>,
+		ValueQuery,
+	>;
+
 	#[pallet::storage]
 	#[allow(clippy::disallowed_types)]
 	/// Consensus heights
@@ -452,6 +566,68 @@ pub mod pallet {
 	pub type PendingRecvPacketSeqs<T: Config> =
 		StorageMap<_, Blake2_128Concat, (Vec<u8>, Vec<u8>), (BTreeSet<u64>, u64), ValueQuery>;
 
+	#[pallet::storage]
+	#[allow(clippy::disallowed_types)]
+	/// Received packets whose acknowledgement will be written asynchronously by the receiving
+	/// module, keyed by ((port_id, channel_id), sequence), the value is the block number at
+	/// which the packet was received.
+	pub type PendingAcknowledgements<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		(Vec<u8>, Vec<u8>),
+		Blake2_128Concat,
+		u64,
+		T::BlockNumber,
+		OptionQuery,
+	>;
+
+	#[pallet::storage]
+	#[allow(clippy::disallowed_types)]
+	/// Transfers forwarded by an ics20 memo that are waiting for an acknowledgement, keyed by
+	/// ((port_id, channel_id), sequence) of the forwarded packet.
+	pub type InFlightForwards<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		(Vec<u8>, Vec<u8>),
+		Blake2_128Concat,
+		u64,
+		InFlightForward<T::AccountId, T::AssetId, T::MemoMessage>,
+		OptionQuery,
+	>;
+
+	#[pallet::storage]
+	#[allow(clippy::disallowed_types)]
+	/// Attempts to forward the tokens of received transfers as requested by their memo, keyed by
+	/// ((port_id, channel_id), sequence) of the received packet. The attempts are pruned
+	/// `ForwardStatusRetention` blocks after the received packet is acknowledged.
+	pub type ForwardAttempts<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		(Vec<u8>, Vec<u8>),
+		Blake2_128Concat,
+		u64,
+		BoundedVec<ibc_primitives::ForwardAttempt, T::MaxForwardAttempts>,
+		ValueQuery,
+	>;
+
+	#[pallet::storage]
+	#[allow(clippy::disallowed_types)]
+	/// Keys of the `ForwardAttempts` of acknowledged transfers, by the block they are pruned in
+	pub type ExpiringForwardAttempts<T: Config> = StorageDoubleMap<
+		_,
+		Twox64Concat,
+		T::BlockNumber,
+		Blake2_128Concat,
+		((Vec<u8>, Vec<u8>), u64),
+		(),
+		OptionQuery,
+	>;
+
+	#[pallet::storage]
+	/// Code of the wasm light clients, keyed by its sha256 checksum
+	pub type WasmCodes<T: Config> =
+		StorageMap<_, Blake2_128Concat, Vec<u8>, BoundedVec<u8, T::MaxWasmCodeSize>, OptionQuery>;
+
 	#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
 	pub struct AssetConfig<AssetId> {
 		pub id: AssetId,
@@ -619,7 +795,7 @@ pub mod pallet {
 		ExecuteMemoIbcTokenTransferFailedWithReason {
 			from: T::AccountId,
 			memo: String,
-			reason: u8,
+			reason: crate::ics20::MemoFailureReason,
 		},
 		ExecuteMemoIbcTokenTransferFailed {
 			from: T::AccountId,
@@ -629,6 +805,23 @@ pub mod pallet {
 			channel: u64,
 			next_memo: Option<T::MemoMessage>,
 		},
+		/// A forwarded transfer timed out and was sent again
+		ExecuteMemoIbcTokenTransferRetried {
+			from: T::AccountId,
+			to: Vec<u8>,
+			channel: u64,
+			sequence: u64,
+			retries_left: u64,
+		},
+		/// A forwarded transfer failed, the received tokens are refunded to the sender on the
+		/// source chain through an error acknowledgement
+		ExecuteMemoIbcTokenTransferRefunded {
+			from: T::AccountId,
+			asset_id: T::AssetId,
+			amount: u128,
+			source_channel: Vec<u8>,
+			sequence: u64,
+		},
 		ExecuteMemoXcmSuccess {
 			from: T::AccountId,
 			to: T::AccountId,
@@ -726,6 +919,18 @@ pub mod pallet {
 		/// - The memo is in invalid format
 		/// - The memo contains unsupported middlewares
 		InvalidMemo,
+		/// More assets than allowed were given for a single transfer
+		TooManyTransferAssets,
+		/// No upgrade in progress for the channel
+		ChannelUpgradeNotFound,
+		/// No upgrade error receipt for the channel
+		UpgradeErrorNotFound,
+		/// The code isn't a valid wasm light client
+		InvalidWasmCode,
+		/// The code of the wasm light client is larger than `MaxWasmCodeSize`
+		WasmCodeTooLarge,
+		/// A wasm light client with the same checksum is already stored
+		WasmCodeExists,
 	}
 
 	#[pallet::hooks]
@@ -735,6 +940,15 @@ pub mod pallet {
 		T: Send + Sync,
 		AccountId32: From<<T as frame_system::Config>::AccountId>,
 	{
+		fn on_initialize(n: BlockNumberFor<T>) -> Weight {
+			let mut pruned = 0;
+			for ((port_channel, sequence), ()) in ExpiringForwardAttempts::<T>::drain_prefix(n) {
+				ForwardAttempts::<T>::remove(port_channel, sequence);
+				pruned += 1;
+			}
+			T::DbWeight::get().reads_writes(pruned + 1, pruned * 2)
+		}
+
 		fn on_idle(n: BlockNumberFor<T>, remaining_weight: Weight) -> Weight {
 			if n % T::CleanUpPacketsPeriod::get() != T::BlockNumber::zero() {
 				return remaining_weight
@@ -750,6 +964,29 @@ pub mod pallet {
 		}
 
 		fn offchain_worker(_n: BlockNumberFor<T>) {}
+
+		/// Every channel escrow account must hold at least the amount tracked as outstanding on
+		/// the counterparty chain, otherwise vouchers can no longer be redeemed.
+		#[cfg(feature = "try-runtime")]
+		fn try_state(_n: BlockNumberFor<T>) -> Result<(), sp_runtime::TryRuntimeError> {
+			for escrow in Pallet::<T>::escrow_invariant().escrows {
+				if escrow.balance < escrow.tracked {
+					log::error!(
+						target: "pallet_ibc",
+						"Escrow invariant violated for port {:?}, channel {:?}, denom {:?}: balance {} < tracked {}",
+						String::from_utf8_lossy(&escrow.port_id),
+						String::from_utf8_lossy(&escrow.channel_id),
+						String::from_utf8_lossy(&escrow.denom),
+						escrow.balance,
+						escrow.tracked
+					);
+					return Err(
+						"Escrowed balance is lower than the tracked outstanding amount".into()
+					)
+				}
+			}
+			Ok(())
+		}
 	}
 
 	// Dispatch able functions allows users to interact with the pallet and invoke state changes.
@@ -765,12 +1002,17 @@ pub mod pallet {
 		#[pallet::call_index(0)]
 		#[pallet::weight(crate::weight::deliver::< T > (messages))]
 		#[frame_support::transactional]
-		pub fn deliver(origin: OriginFor<T>, messages: Vec<Any>) -> DispatchResult {
+		pub fn deliver(origin: OriginFor<T>, messages: Vec<Any>) -> DispatchResultWithPostInfo {
 			use ibc::core::{
 				ics02_client::msgs::create_client, ics03_connection::msgs::conn_open_init,
 			};
 			let sender = T::RelayerOrigin::ensure_origin(origin)?;
 
+			// the wasm light clients are charged their gas limit, the gas they don't use is
+			// refunded after execution
+			let wasm_gas_limit = crate::weight::wasm_gas_limit::<T>(&messages);
+			let max_weight = (wasm_gas_limit > 0).then(|| crate::weight::deliver::<T>(&messages));
+
 			// reserve a fixed deposit for every client and connection created
 			// so people don't spam our chain with useless clients.
 			let mut ctx = routing::Context::<T>::new();
@@ -800,9 +1042,14 @@ pub mod pallet {
 					<T as frame_system::Config>::AccountId,
 				>>::reserve(&sender, reserve_amt)?;
 			}
-			Self::execute_ibc_messages(&mut ctx, messages);
+			let wasm_gas_used = Self::execute_ibc_messages(&mut ctx, messages);
 
-			Ok(())
+			let actual_weight = max_weight.map(|weight| {
+				weight
+					.saturating_sub(wasm::gas_weight(wasm_gas_limit))
+					.saturating_add(wasm::gas_weight(wasm_gas_used))
+			});
+			Ok(actual_weight.into())
 		}
 
 		#[pallet::call_index(1)]
@@ -816,243 +1063,24 @@ pub mod pallet {
 			memo: Option<T::MemoMessage>,
 		) -> DispatchResult {
 			let account_id_32 = T::TransferOrigin::ensure_origin(origin)?.into();
-			let denom = T::IbcDenomToAssetIdConversion::from_asset_id_to_denom(asset_id)
-				.ok_or(Error::<T>::InvalidAssetId)?;
-			let from = {
-				let mut hex_string = hex::encode(account_id_32.to_raw_vec());
-				hex_string.insert_str(0, "0x");
-				hex_string
-			};
-
-			let to = match params.to {
-				MultiAddress::Id(id) => {
-					// we convert id to hex string instead of ss58 because destination chain could
-					// have a different ss58 prefix from source chain
-					let account_id_32: AccountId32 = id.into();
-					let mut hex_string = hex::encode(account_id_32.to_raw_vec());
-					hex_string.insert_str(0, "0x");
-					hex_string
-				},
-				MultiAddress::Raw(bytes) =>
-					String::from_utf8(bytes).map_err(|_| Error::<T>::Utf8Error)?,
-			};
-			let denom =
-				PrefixedDenom::from_str(&denom).map_err(|_| Error::<T>::PrefixedDenomParse)?;
-			let ibc_amount =
-				Amount::from_str(&format!("{amount:?}")).map_err(|_| Error::<T>::InvalidAmount)?;
-			let mut coin = PrefixedCoin { denom, amount: ibc_amount };
-			let source_channel = ChannelId::new(params.source_channel);
-			let source_port = PortId::transfer();
-			let (latest_height, _) =
-				Pallet::<T>::latest_height_and_timestamp(&source_port, &source_channel)
-					.map_err(|_| Error::<T>::TimestampAndHeightNotFound)?;
-
-			let (timeout_height, timeout_timestamp) = match params.timeout {
-				Timeout::Offset { timestamp, height } => {
-					let latest_timestamp = T::TimeProvider::now();
-					let timestamp = timestamp
-						.map(|offset| {
-							Timestamp::from_nanoseconds(
-								(latest_timestamp + Duration::from_secs(offset)).as_nanos() as u64,
-							)
-						})
-						.transpose()
-						.map_err(|_| Error::<T>::InvalidTimestamp)?
-						.unwrap_or_default();
-					let height = height.map(|offset| latest_height.add(offset)).unwrap_or_default();
-					(height, timestamp)
-				},
-				Timeout::Absolute { timestamp, height } => {
-					let timestamp = timestamp
-						.map(Timestamp::from_nanoseconds)
-						.transpose()
-						.map_err(|_| Error::<T>::InvalidTimestamp)?
-						.unwrap_or_default();
-					let height = height
-						.map(|revision_height| {
-							Height::new(latest_height.revision_number, revision_height)
-						})
-						.unwrap_or_default();
-					(height, timestamp)
-				},
-			};
-
-			if timeout_height.is_zero() && timeout_timestamp.nanoseconds() == 0 {
-				return Err(Error::<T>::InvalidTimestamp.into())
-			}
-
-			let mut ctx = Context::<T>::default();
-			let channel_end = ctx
-				.channel_end(&(PortId::transfer(), source_channel))
-				.map_err(|_| Error::<T>::ChannelNotFound)?;
-
-			let destination_channel =
-				channel_end.counterparty().channel_id.ok_or(Error::<T>::ChannelNotFound)?;
-
-			let is_feeless_channel_ids = FeeLessChannelIds::<T>::contains_key((
-				source_channel.sequence(),
-				destination_channel.sequence(),
-			));
-
-			if !is_feeless_channel_ids {
-				let percent = ServiceChargeOut::<T>::get().unwrap_or(T::ServiceChargeOut::get());
-				// Now we proceed to send the service fee from the receiver's account to the pallet
-				// FeeAccount
-				let fee_account = T::FeeAccount::get();
-
-				let mut fee_coin = coin.clone();
-				let asset_id =
-					<T as crate::Config>::IbcDenomToAssetIdConversion::from_denom_to_asset_id(
-						&fee_coin.denom.to_string(),
-					);
-				let amt = coin.amount.as_u256().low_u128();
-				let mut is_flat_fee = false;
-				let mut fee = match asset_id {
-					Ok(a) => {
-						let fee_asset_id = T::FlatFeeAssetId::get();
-						let fee_asset_amount = T::FlatFeeAmount::get();
-						is_flat_fee = true;
-
-						T::FlatFeeConverter::get_flat_fee(a, fee_asset_id, fee_asset_amount)
-							.unwrap_or_else(|| {
-								// We have ensured that token amounts larger than the max value
-								// for a u128 are rejected in the ics20 on_recv_packet callback
-								// so we can multiply safely. Percent does Non-Overflowing
-								// multiplication so this is infallible
-								is_flat_fee = false;
-								percent * amt
-							})
-					},
-					Err(_) => percent * amt,
-				};
-
-				fee = fee.min(amt);
-				fee_coin.amount = U256::from(fee).into();
-
-				let signer_from = Signer::from_str(&from).map_err(|_| Error::<T>::Utf8Error)?;
-				let account_id_from = <T as Config>::AccountIdConversion::try_from(signer_from)
-					.map_err(|_| Error::<T>::OriginAddress)?;
-
-				ctx.send_coins(&account_id_from, &fee_account, &fee_coin).map_err(|e| {
-					log::debug!(target: "pallet_ibc", "[transfer]: error: {:?}", &e);
-					Error::<T>::FailedSendFeeToAccount
-				})?;
-
-				// We modify the packet data to remove the fee so any other middleware has access to
-				// the correct amount deposited in the receiver's account
-				coin.amount = (coin.amount.as_u256() - U256::from(fee)).into();
-				//found sequence that will used in Pallet::<T>::send_transfer function.
-				let sequence = ctx
-					.get_next_sequence_send(&(source_port.clone(), source_channel))
-					.map_err(|_| Error::<T>::ChannelNotFound)?;
-				//use this sequence as a key in storage map where sequence is key and fee is value
-				let sequence: u64 = sequence.into();
-				//we need this data in storage map because on_timeout_packet and
-				// on_acknowledgement_packet use this data to refund fee in case of falure or clean
-				// un in case of on_acknowledgement_packet success.
-				SequenceFee::<T>::insert(sequence, fee);
-				Self::deposit_event(Event::<T>::ChargingFeeOnTransferInitiated {
-					sequence,
-					from: from.clone().into(),
-					to: to.clone().into(),
-					amount: fee.into(),
-					is_flat_fee,
-					local_asset_id: T::IbcDenomToAssetIdConversion::from_denom_to_asset_id(
-						&coin.denom.to_string(),
-					)
-					.ok(),
-					ibc_denom: coin.denom.to_string().into_bytes(),
-					source_channel: source_channel.to_string().into_bytes(),
-					destination_channel: destination_channel.to_string().into_bytes(),
-				});
-			};
-
-			memo.as_ref()
-				.map(|memo| {
-					memo.validate().map_err(|e| {
-						log::debug!(target: "pallet_ibc", "[transfer]: memo validation error: {}", e);
-						Error::<T>::InvalidMemo
-					})
-				})
-				.transpose()?;
-
-			let msg = MsgTransfer {
-				source_port,
-				source_channel,
-				token: coin.clone(),
-				sender: Signer::from_str(&from).map_err(|_| Error::<T>::Utf8Error)?,
-				receiver: Signer::from_str(&to).map_err(|_| Error::<T>::Utf8Error)?,
-				timeout_height,
-				timeout_timestamp,
-				memo: memo.map(|memo| memo.to_string()).unwrap_or_default(),
-			};
-
-			let is_sender_source = is_sender_chain_source(
-				msg.source_port.clone(),
-				msg.source_channel,
-				&msg.token.denom,
-			);
-
-			if is_sender_source {
-				// Store escrow address
-				let escrow_address =
-					get_channel_escrow_address(&msg.source_port, msg.source_channel)
-						.map_err(|_| Error::<T>::ChannelEscrowAddress)?;
-				let account_id = T::AccountIdConversion::try_from(escrow_address)
-					.map_err(|_| Error::<T>::ChannelEscrowAddress)?
-					.into_account();
-				let _ = EscrowAddresses::<T>::try_mutate::<_, &'static str, _>(|addresses| {
-					if !addresses.contains(&account_id) {
-						addresses.insert(account_id);
-						Ok(())
-					} else {
-						Err("Address already exists")
-					}
-				});
-			}
-
-			Pallet::<T>::send_transfer(msg).map_err(|e| {
-				log::warn!(target: "pallet_ibc", "[transfer]: error: {:?}", &e);
-				use ibc_primitives::Error::*;
-				match e {
-					SendPacketError { .. } => Error::<T>::TransferSend,
-					SendTransferError { .. } => Error::<T>::TransferSend,
-
-					ReceivePacketError { .. } => Error::<T>::TransferProtocol,
-					WriteAcknowledgementError { .. } => Error::<T>::TransferProtocol,
-					AcknowledgementError { .. } => Error::<T>::TransferProtocol,
-					TimeoutError { .. } => Error::<T>::TransferProtocol,
-
-					TimestampOrHeightNotFound { .. } => Error::<T>::TransferInternals,
-					ChannelOrPortError { .. } => Error::<T>::TransferInternals,
-					ClientStateError { .. } => Error::<T>::TransferInternals,
-					ConnectionIdError { .. } => Error::<T>::TransferInternals,
-					ClientIdError { .. } => Error::<T>::TransferInternals,
-					BindPortError { .. } => Error::<T>::TransferInternals,
-					ChannelInitError { .. } => Error::<T>::TransferInternals,
-					ChannelCloseError { .. } => Error::<T>::TransferInternals,
-
-					DecodingError { .. } => Error::<T>::TransferSerde,
-					ErrorDecodingPrefix => Error::<T>::TransferSerde,
-
-					Other { .. } => Error::<T>::TransferOther,
-				}
-			})?;
+			Self::do_transfer(account_id_32, params, vec![(asset_id, amount)], memo)
+		}
 
-			Self::deposit_event(Event::<T>::TokenTransferInitiated {
-				from: from.as_bytes().to_vec(),
-				to: to.as_bytes().to_vec(),
-				amount,
-				local_asset_id: T::IbcDenomToAssetIdConversion::from_denom_to_asset_id(
-					&coin.denom.to_string(),
-				)
-				.ok(),
-				ibc_denom: coin.denom.to_string().into_bytes(),
-				is_sender_source,
-				source_channel: source_channel.to_string().into_bytes(),
-				destination_channel: destination_channel.to_string().into_bytes(),
-			});
-			Ok(())
+		/// Transfers several assets in a single packet. The source channel must have negotiated
+		/// the `ics20-2` version unless a single asset is transferred.
+		#[pallet::call_index(10)]
+		#[frame_support::transactional]
+		#[pallet::weight(<T as Config>::WeightInfo::transfer().saturating_mul(assets.len() as u64))]
+		pub fn transfer_multi(
+			origin: OriginFor<T>,
+			params: TransferParams<<T as frame_system::Config>::AccountId>,
+			assets: Vec<(T::AssetId, T::Balance)>,
+			memo: Option<T::MemoMessage>,
+		) -> DispatchResult {
+			let account_id_32 = T::TransferOrigin::ensure_origin(origin)?.into();
+			ensure!(!assets.is_empty(), Error::<T>::InvalidParams);
+			ensure!(assets.len() <= MAX_TRANSFER_ASSETS, Error::<T>::TooManyTransferAssets);
+			Self::do_transfer(account_id_32, params, assets, memo)
 		}
 
 		/// We write the consensus & client state under these predefined paths so that
@@ -1113,6 +1141,10 @@ pub mod pallet {
 					)
 				},
 				AnyClientState::Wasm(_) => return Err(Error::<T>::ClientFreezeFailed.into()),
+				AnyClientState::SoloMachine(mut sm) => {
+					sm.is_frozen = true;
+					AnyClientState::wrap(&sm)
+				},
 				#[cfg(test)]
 				AnyClientState::Mock(mut ms) => {
 					ms.frozen_height =
@@ -1264,6 +1296,347 @@ pub mod pallet {
 
 			Ok(())
 		}
+
+		/// Stores the code of a wasm light client pushed with `MsgPushNewWasmCode`. Clients of
+		/// the `08-wasm` type whose checksum matches the code are executed by it.
+		#[pallet::call_index(11)]
+		#[pallet::weight(<T as Config>::WeightInfo::push_wasm_code(message.value.len() as u32))]
+		#[frame_support::transactional]
+		pub fn push_wasm_code(origin: OriginFor<T>, message: Any) -> DispatchResult {
+			use ics08_wasm::msg::{MsgPushNewWasmCode, WASM_PUSH_WASM_CODE_TYPE_URL};
+			<T as Config>::AdminOrigin::ensure_origin(origin)?;
+
+			ensure!(
+				message.type_url == WASM_PUSH_WASM_CODE_TYPE_URL,
+				Error::<T>::InvalidMessageType
+			);
+			let msg = MsgPushNewWasmCode::decode_vec(&message.value)
+				.map_err(|_| Error::<T>::DecodingError)?;
+			let code: BoundedVec<u8, T::MaxWasmCodeSize> =
+				msg.code.try_into().map_err(|_| Error::<T>::WasmCodeTooLarge)?;
+			wasm::vm::validate(&code).map_err(|e| {
+				log::trace!(target: "pallet_ibc", "invalid wasm code: {}", e);
+				Error::<T>::InvalidWasmCode
+			})?;
+
+			let wasm_checksum = sp_io::hashing::sha2_256(&code).to_vec();
+			ensure!(!WasmCodes::<T>::contains_key(&wasm_checksum), Error::<T>::WasmCodeExists);
+			WasmCodes::<T>::insert(&wasm_checksum, code);
+
+			Self::deposit_event(Event::<T>::Events {
+				events: vec![Ok(events::IbcEvent::PushWasmCode { wasm_checksum })],
+			});
+
+			Ok(())
+		}
+
+		/// Delivers `MsgChannelUpgradeInit` and `MsgChannelUpgradeCancel` messages on behalf of
+		/// the upgrade authority. Only the authority can propose a channel upgrade, or cancel one
+		/// without proving that the counterparty aborted it.
+		#[pallet::call_index(12)]
+		#[pallet::weight(crate::weight::deliver::< T > (messages))]
+		#[frame_support::transactional]
+		pub fn deliver_channel_upgrade(origin: OriginFor<T>, messages: Vec<Any>) -> DispatchResult {
+			use ibc::core::ics04_channel::msgs::{chan_upgrade_cancel, chan_upgrade_init};
+			<T as Config>::AdminOrigin::ensure_origin(origin)?;
+
+			let messages = messages
+				.into_iter()
+				.map(|message| {
+					ensure!(
+						matches!(
+							message.type_url.as_str(),
+							chan_upgrade_init::TYPE_URL | chan_upgrade_cancel::TYPE_URL
+						),
+						Error::<T>::InvalidMessageType
+					);
+					Ok(ibc_proto::google::protobuf::Any {
+						type_url: message.type_url,
+						value: message.value,
+					})
+				})
+				.collect::<Result<Vec<_>, Error<T>>>()?;
+
+			let mut ctx = routing::Context::<T>::new().with_upgrade_authority();
+			Self::execute_ibc_messages(&mut ctx, messages);
+
+			Ok(())
+		}
+	}
+
+	impl<T: Config> Pallet<T>
+	where
+		T: Send + Sync,
+		AccountId32: From<<T as frame_system::Config>::AccountId>,
+		u32: From<<T as frame_system::Config>::BlockNumber>,
+	{
+		/// Sends `assets` in a single transfer packet, the service fee is charged on each of them
+		/// unless the channel is feeless.
+		fn do_transfer(
+			account_id_32: AccountId32,
+			params: TransferParams<<T as frame_system::Config>::AccountId>,
+			assets: Vec<(T::AssetId, T::Balance)>,
+			memo: Option<T::MemoMessage>,
+		) -> DispatchResult {
+			let mut coins = assets
+				.iter()
+				.map(|(asset_id, amount)| {
+					let denom =
+						T::IbcDenomToAssetIdConversion::from_asset_id_to_denom(asset_id.clone())
+							.ok_or(Error::<T>::InvalidAssetId)?;
+					let denom = PrefixedDenom::from_str(&denom)
+						.map_err(|_| Error::<T>::PrefixedDenomParse)?;
+					let amount = Amount::from_str(&format!("{amount:?}"))
+						.map_err(|_| Error::<T>::InvalidAmount)?;
+					Ok(PrefixedCoin { denom, amount })
+				})
+				.collect::<Result<Vec<_>, Error<T>>>()?;
+			let from = {
+				let mut hex_string = hex::encode(account_id_32.to_raw_vec());
+				hex_string.insert_str(0, "0x");
+				hex_string
+			};
+
+			let to = match params.to {
+				MultiAddress::Id(id) => {
+					// we convert id to hex string instead of ss58 because destination chain could
+					// have a different ss58 prefix from source chain
+					let account_id_32: AccountId32 = id.into();
+					let mut hex_string = hex::encode(account_id_32.to_raw_vec());
+					hex_string.insert_str(0, "0x");
+					hex_string
+				},
+				MultiAddress::Raw(bytes) =>
+					String::from_utf8(bytes).map_err(|_| Error::<T>::Utf8Error)?,
+			};
+			let source_channel = ChannelId::new(params.source_channel);
+			let source_port = PortId::transfer();
+			let (latest_height, _) =
+				Pallet::<T>::latest_height_and_timestamp(&source_port, &source_channel)
+					.map_err(|_| Error::<T>::TimestampAndHeightNotFound)?;
+
+			let (timeout_height, timeout_timestamp) = match params.timeout {
+				Timeout::Offset { timestamp, height } => {
+					let latest_timestamp = T::TimeProvider::now();
+					let timestamp = timestamp
+						.map(|offset| {
+							Timestamp::from_nanoseconds(
+								(latest_timestamp + Duration::from_secs(offset)).as_nanos() as u64,
+							)
+						})
+						.transpose()
+						.map_err(|_| Error::<T>::InvalidTimestamp)?
+						.unwrap_or_default();
+					let height = height.map(|offset| latest_height.add(offset)).unwrap_or_default();
+					(height, timestamp)
+				},
+				Timeout::Absolute { timestamp, height } => {
+					let timestamp = timestamp
+						.map(Timestamp::from_nanoseconds)
+						.transpose()
+						.map_err(|_| Error::<T>::InvalidTimestamp)?
+						.unwrap_or_default();
+					let height = height
+						.map(|revision_height| {
+							Height::new(latest_height.revision_number, revision_height)
+						})
+						.unwrap_or_default();
+					(height, timestamp)
+				},
+			};
+
+			if timeout_height.is_zero() && timeout_timestamp.nanoseconds() == 0 {
+				return Err(Error::<T>::InvalidTimestamp.into())
+			}
+
+			let mut ctx = Context::<T>::default();
+			let channel_end = ctx
+				.channel_end(&(PortId::transfer(), source_channel))
+				.map_err(|_| Error::<T>::ChannelNotFound)?;
+
+			let destination_channel =
+				channel_end.counterparty().channel_id.ok_or(Error::<T>::ChannelNotFound)?;
+
+			let is_feeless_channel_ids = FeeLessChannelIds::<T>::contains_key((
+				source_channel.sequence(),
+				destination_channel.sequence(),
+			));
+
+			if !is_feeless_channel_ids {
+				let percent = ServiceChargeOut::<T>::get().unwrap_or(T::ServiceChargeOut::get());
+				// Now we proceed to send the service fee from the receiver's account to the pallet
+				// FeeAccount
+				let fee_account = T::FeeAccount::get();
+				let signer_from = Signer::from_str(&from).map_err(|_| Error::<T>::Utf8Error)?;
+				let account_id_from = <T as Config>::AccountIdConversion::try_from(signer_from)
+					.map_err(|_| Error::<T>::OriginAddress)?;
+				//found sequence that will used in Pallet::<T>::send_multi_transfer function.
+				let sequence = ctx
+					.get_next_sequence_send(&(source_port.clone(), source_channel))
+					.map_err(|_| Error::<T>::ChannelNotFound)?;
+				let sequence: u64 = sequence.into();
+
+				let mut fees = Vec::with_capacity(coins.len());
+				for coin in coins.iter_mut() {
+					let mut fee_coin = coin.clone();
+					let asset_id =
+						<T as crate::Config>::IbcDenomToAssetIdConversion::from_denom_to_asset_id(
+							&fee_coin.denom.to_string(),
+						);
+					let amt = coin.amount.as_u256().low_u128();
+					let mut is_flat_fee = false;
+					let mut fee = match asset_id {
+						Ok(a) => {
+							let fee_asset_id = T::FlatFeeAssetId::get();
+							let fee_asset_amount = T::FlatFeeAmount::get();
+							is_flat_fee = true;
+
+							T::FlatFeeConverter::get_flat_fee(a, fee_asset_id, fee_asset_amount)
+								.unwrap_or_else(|| {
+									// We have ensured that token amounts larger than the max value
+									// for a u128 are rejected in the ics20 on_recv_packet callback
+									// so we can multiply safely. Percent does Non-Overflowing
+									// multiplication so this is infallible
+									is_flat_fee = false;
+									percent * amt
+								})
+						},
+						Err(_) => percent * amt,
+					};
+
+					fee = fee.min(amt);
+					fee_coin.amount = U256::from(fee).into();
+
+					ctx.send_coins(&account_id_from, &fee_account, &fee_coin).map_err(|e| {
+						log::debug!(target: "pallet_ibc", "[transfer]: error: {:?}", &e);
+						Error::<T>::FailedSendFeeToAccount
+					})?;
+
+					// We modify the packet data to remove the fee so any other middleware has
+					// access to the correct amount deposited in the receiver's account
+					coin.amount = (coin.amount.as_u256() - U256::from(fee)).into();
+					fees.push(fee);
+					Self::deposit_event(Event::<T>::ChargingFeeOnTransferInitiated {
+						sequence,
+						from: from.clone().into(),
+						to: to.clone().into(),
+						amount: fee.into(),
+						is_flat_fee,
+						local_asset_id: T::IbcDenomToAssetIdConversion::from_denom_to_asset_id(
+							&coin.denom.to_string(),
+						)
+						.ok(),
+						ibc_denom: coin.denom.to_string().into_bytes(),
+						source_channel: source_channel.to_string().into_bytes(),
+						destination_channel: destination_channel.to_string().into_bytes(),
+					});
+				}
+				//use the port, channel and sequence as a key in storage map where fee is value
+				//we need this data in storage map because on_timeout_packet and
+				// on_acknowledgement_packet use this data to refund fee in case of falure or clean
+				// un in case of on_acknowledgement_packet success.
+				let key =
+					(source_port.as_bytes().to_vec(), source_channel.to_string().into_bytes());
+				if fees.len() == 1 {
+					SequenceFee::<T>::insert(key, sequence, fees[0]);
+				} else {
+					SequenceFees::<T>::insert(key, sequence, fees);
+				}
+			};
+
+			memo.as_ref()
+				.map(|memo| {
+					memo.validate().map_err(|e| {
+						log::debug!(target: "pallet_ibc", "[transfer]: memo validation error: {}", e);
+						Error::<T>::InvalidMemo
+					})
+				})
+				.transpose()?;
+
+			let msg = MsgMultiTransfer {
+				source_port,
+				source_channel,
+				tokens: coins.clone(),
+				sender: Signer::from_str(&from).map_err(|_| Error::<T>::Utf8Error)?,
+				receiver: Signer::from_str(&to).map_err(|_| Error::<T>::Utf8Error)?,
+				timeout_height,
+				timeout_timestamp,
+				memo: memo.map(|memo| memo.to_string()).unwrap_or_default(),
+				forwarding: Forwarding::default(),
+			};
+
+			let is_sender_source = coins
+				.iter()
+				.map(|coin| {
+					is_sender_chain_source(msg.source_port.clone(), msg.source_channel, &coin.denom)
+				})
+				.collect::<Vec<_>>();
+
+			if is_sender_source.contains(&true) {
+				// Store escrow address
+				let escrow_address =
+					get_channel_escrow_address(&msg.source_port, msg.source_channel)
+						.map_err(|_| Error::<T>::ChannelEscrowAddress)?;
+				let account_id = T::AccountIdConversion::try_from(escrow_address)
+					.map_err(|_| Error::<T>::ChannelEscrowAddress)?
+					.into_account();
+				let _ = EscrowAddresses::<T>::try_mutate::<_, &'static str, _>(|addresses| {
+					if !addresses.contains(&account_id) {
+						addresses.insert(account_id);
+						Ok(())
+					} else {
+						Err("Address already exists")
+					}
+				});
+			}
+
+			Pallet::<T>::send_multi_transfer(msg).map_err(|e| {
+				log::warn!(target: "pallet_ibc", "[transfer]: error: {:?}", &e);
+				use ibc_primitives::Error::*;
+				match e {
+					SendPacketError { .. } => Error::<T>::TransferSend,
+					SendTransferError { .. } => Error::<T>::TransferSend,
+
+					ReceivePacketError { .. } => Error::<T>::TransferProtocol,
+					WriteAcknowledgementError { .. } => Error::<T>::TransferProtocol,
+					AcknowledgementError { .. } => Error::<T>::TransferProtocol,
+					TimeoutError { .. } => Error::<T>::TransferProtocol,
+
+					TimestampOrHeightNotFound { .. } => Error::<T>::TransferInternals,
+					ChannelOrPortError { .. } => Error::<T>::TransferInternals,
+					ClientStateError { .. } => Error::<T>::TransferInternals,
+					ConnectionIdError { .. } => Error::<T>::TransferInternals,
+					ClientIdError { .. } => Error::<T>::TransferInternals,
+					BindPortError { .. } => Error::<T>::TransferInternals,
+					ChannelInitError { .. } => Error::<T>::TransferInternals,
+					ChannelCloseError { .. } => Error::<T>::TransferInternals,
+
+					DecodingError { .. } => Error::<T>::TransferSerde,
+					ErrorDecodingPrefix => Error::<T>::TransferSerde,
+
+					Other { .. } => Error::<T>::TransferOther,
+				}
+			})?;
+
+			for (((_, amount), coin), is_sender_source) in
+				assets.into_iter().zip(coins).zip(is_sender_source)
+			{
+				Self::deposit_event(Event::<T>::TokenTransferInitiated {
+					from: from.as_bytes().to_vec(),
+					to: to.as_bytes().to_vec(),
+					amount,
+					local_asset_id: T::IbcDenomToAssetIdConversion::from_denom_to_asset_id(
+						&coin.denom.to_string(),
+					)
+					.ok(),
+					ibc_denom: coin.denom.to_string().into_bytes(),
+					is_sender_source,
+					source_channel: source_channel.to_string().into_bytes(),
+					destination_channel: destination_channel.to_string().into_bytes(),
+				});
+			}
+			Ok(())
+		}
 	}
 }
 
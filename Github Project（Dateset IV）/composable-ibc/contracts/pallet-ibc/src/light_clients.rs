@@ -12,6 +12,14 @@ use ibc::core::{
 use ibc_derive::{ClientDef, ClientMessage, ClientState, ConsensusState, Protobuf};
 use ibc_primitives::runtime_interface;
 use ibc_proto::google::protobuf::Any;
+use ics06_solomachine::{
+	client_message::{
+		SOLOMACHINE_CLIENT_MESSAGE_TYPE_URL, SOLOMACHINE_HEADER_TYPE_URL,
+		SOLOMACHINE_MISBEHAVIOUR_TYPE_URL,
+	},
+	client_state::SOLOMACHINE_CLIENT_STATE_TYPE_URL,
+	consensus_state::SOLOMACHINE_CONSENSUS_STATE_TYPE_URL,
+};
 use ics07_tendermint::{
 	client_message::{
 		TENDERMINT_CLIENT_MESSAGE_TYPE_URL, TENDERMINT_HEADER_TYPE_URL,
@@ -116,6 +124,32 @@ impl Verifier for HostFunctionsManager {
 
 impl ics07_tendermint::HostFunctionsProvider for HostFunctionsManager {}
 
+impl ics06_solomachine::HostFunctions for HostFunctionsManager {
+	fn ed25519_verify(signature: &[u8], msg: &[u8], pubkey: &[u8]) -> bool {
+		match (ed25519::Signature::from_slice(signature), ed25519::Public::from_slice(pubkey)) {
+			(Some(signature), Ok(pubkey)) =>
+				sp_io::crypto::ed25519_verify(&signature, msg, &pubkey),
+			_ => false,
+		}
+	}
+
+	fn secp256k1_verify(signature: &[u8], msg: &[u8], pubkey: &[u8]) -> bool {
+		let signature: [u8; 64] = match signature.try_into() {
+			Ok(signature) => signature,
+			Err(_) => return false,
+		};
+		let hash = sp_io::hashing::sha2_256(msg);
+		// cosmos signatures don't include the recovery id, so try both
+		(0u8..2).any(|recovery_id| {
+			let mut signature_with_id = [0u8; 65];
+			signature_with_id[..64].copy_from_slice(&signature);
+			signature_with_id[64] = recovery_id;
+			sp_io::crypto::secp256k1_ecdsa_recover_compressed(&signature_with_id, &hash)
+				.map_or(false, |recovered| recovered.as_slice() == pubkey)
+		})
+	}
+}
+
 pub struct GrandpaHeaderHashesStorageInstance;
 impl StorageInstance for GrandpaHeaderHashesStorageInstance {
 	fn pallet_prefix() -> &'static str {
@@ -210,6 +244,7 @@ pub enum AnyClient {
 	Beefy(ics11_beefy::client_def::BeefyClient<HostFunctionsManager>),
 	Tendermint(ics07_tendermint::client_def::TendermintClient<HostFunctionsManager>),
 	Wasm(ics08_wasm::client_def::WasmClient<AnyClient, AnyClientState, AnyConsensusState>),
+	SoloMachine(ics06_solomachine::client_def::SoloMachineClient<HostFunctionsManager>),
 	#[cfg(test)]
 	Mock(ibc::mock::client_def::MockClient),
 }
@@ -220,6 +255,7 @@ pub enum AnyUpgradeOptions {
 	Beefy(ics11_beefy::client_state::UpgradeOptions),
 	Tendermint(ics07_tendermint::client_state::UpgradeOptions),
 	Wasm(Box<Self>),
+	SoloMachine(ics06_solomachine::client_state::UpgradeOptions),
 	#[cfg(test)]
 	Mock(()),
 }
@@ -234,6 +270,8 @@ pub enum AnyClientState {
 	Tendermint(ics07_tendermint::client_state::ClientState<HostFunctionsManager>),
 	#[ibc(proto_url = "WASM_CLIENT_STATE_TYPE_URL")]
 	Wasm(ics08_wasm::client_state::ClientState<AnyClient, Self, AnyConsensusState>),
+	#[ibc(proto_url = "SOLOMACHINE_CLIENT_STATE_TYPE_URL")]
+	SoloMachine(ics06_solomachine::client_state::ClientState<HostFunctionsManager>),
 	#[cfg(test)]
 	#[ibc(proto_url = "MOCK_CLIENT_STATE_TYPE_URL")]
 	Mock(ibc::mock::client_state::MockClientState),
@@ -294,6 +332,8 @@ pub enum AnyConsensusState {
 	Tendermint(ics07_tendermint::consensus_state::ConsensusState),
 	#[ibc(proto_url = "WASM_CONSENSUS_STATE_TYPE_URL")]
 	Wasm(ics08_wasm::consensus_state::ConsensusState<Self>),
+	#[ibc(proto_url = "SOLOMACHINE_CONSENSUS_STATE_TYPE_URL")]
+	SoloMachine(ics06_solomachine::consensus_state::ConsensusState),
 	#[cfg(test)]
 	#[ibc(proto_url = "MOCK_CONSENSUS_STATE_TYPE_URL")]
 	Mock(ibc::mock::client_state::MockConsensusState),
@@ -319,6 +359,8 @@ pub enum AnyClientMessage {
 	Tendermint(ics07_tendermint::client_message::ClientMessage),
 	#[ibc(proto_url = "WASM_CLIENT_MESSAGE_TYPE_URL")]
 	Wasm(ics08_wasm::client_message::ClientMessage<Self>),
+	#[ibc(proto_url = "SOLOMACHINE_CLIENT_MESSAGE_TYPE_URL")]
+	SoloMachine(ics06_solomachine::client_message::ClientMessage),
 	#[cfg(test)]
 	#[ibc(proto_url = "MOCK_CLIENT_MESSAGE_TYPE_URL")]
 	Mock(ibc::mock::header::MockClientMessage),
@@ -380,6 +422,21 @@ impl TryFrom<Any> for AnyClientMessage {
 					ics07_tendermint::client_message::Misbehaviour::decode_vec(&value.value)
 						.map_err(ics02_client::error::Error::decode_raw_header)?,
 				))),
+			SOLOMACHINE_CLIENT_MESSAGE_TYPE_URL => Ok(Self::SoloMachine(
+				ics06_solomachine::client_message::ClientMessage::decode_vec(&value.value)
+					.map_err(ics02_client::error::Error::decode_raw_header)?,
+			)),
+			SOLOMACHINE_HEADER_TYPE_URL =>
+				Ok(Self::SoloMachine(ics06_solomachine::client_message::ClientMessage::Header(
+					ics06_solomachine::client_message::Header::decode_vec(&value.value)
+						.map_err(ics02_client::error::Error::decode_raw_header)?,
+				))),
+			SOLOMACHINE_MISBEHAVIOUR_TYPE_URL => Ok(Self::SoloMachine(
+				ics06_solomachine::client_message::ClientMessage::Misbehaviour(
+					ics06_solomachine::client_message::Misbehaviour::decode_vec(&value.value)
+						.map_err(ics02_client::error::Error::decode_raw_header)?,
+				),
+			)),
 			WASM_CLIENT_MESSAGE_TYPE_URL => Ok(Self::Wasm(
 				ics08_wasm::client_message::ClientMessage::decode_vec(&value.value)
 					.map_err(ics02_client::error::Error::decode_raw_header)?,
@@ -414,6 +471,16 @@ impl From<AnyClientMessage> for Any {
 				type_url: TENDERMINT_CLIENT_MESSAGE_TYPE_URL.to_string(),
 				value: msg.encode_vec().expect("encode_vec failed"),
 			},
+			AnyClientMessage::SoloMachine(msg) => match msg {
+				ics06_solomachine::client_message::ClientMessage::Header(h) => Any {
+					type_url: SOLOMACHINE_HEADER_TYPE_URL.to_string(),
+					value: h.encode_vec().expect("encode_vec failed"),
+				},
+				ics06_solomachine::client_message::ClientMessage::Misbehaviour(m) => Any {
+					type_url: SOLOMACHINE_MISBEHAVIOUR_TYPE_URL.to_string(),
+					value: m.encode_vec().expect("encode_vec failed"),
+				},
+			},
 
 			#[cfg(test)]
 			AnyClientMessage::Mock(_msg) => panic!("MockHeader can't be serialized"),
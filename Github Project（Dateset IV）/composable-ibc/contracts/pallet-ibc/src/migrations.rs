@@ -0,0 +1,106 @@
+//! Storage migrations of the ibc pallet.
+
+use super::*;
+use frame_support::traits::{Get, GetStorageVersion, OnRuntimeUpgrade, StorageVersion};
+
+/// Seeds the escrow tracking introduced in storage version 1 from the balances the channel
+/// escrow accounts already hold, otherwise `try_state` and `ibc_escrowInvariant` would only
+/// see tokens escrowed after the upgrade.
+pub struct SeedEscrowedAmounts<T>(PhantomData<T>);
+
+impl<T: Config> OnRuntimeUpgrade for SeedEscrowedAmounts<T> {
+	fn on_runtime_upgrade() -> Weight {
+		let on_chain_version = Pallet::<T>::on_chain_storage_version();
+		if on_chain_version >= 1 {
+			log::info!(target: "pallet_ibc", "Escrowed amounts already seeded, skipping migration");
+			return T::DbWeight::get().reads(1)
+		}
+		let (reads, writes) = Pallet::<T>::seed_escrowed_amounts();
+		StorageVersion::new(1).put::<Pallet<T>>();
+		log::info!(target: "pallet_ibc", "Seeded escrowed amounts of {} escrows", writes / 2);
+		T::DbWeight::get().reads_writes(reads + 1, writes + 1)
+	}
+
+	#[cfg(feature = "try-runtime")]
+	fn post_upgrade(_state: Vec<u8>) -> Result<(), sp_runtime::TryRuntimeError> {
+		frame_support::ensure!(
+			Pallet::<T>::on_chain_storage_version() >= 1,
+			"Storage version was not bumped"
+		);
+		Ok(())
+	}
+}
+
+/// Storage layout before version 2
+pub(crate) mod v1 {
+	use super::*;
+	use frame_support::{
+		pallet_prelude::{StorageMap, ValueQuery},
+		storage_alias, Blake2_128Concat,
+	};
+
+	/// Fee charged for a sent transfer, keyed by its sequence only
+	#[storage_alias]
+	pub(crate) type SequenceFee<T: Config> =
+		StorageMap<Pallet<T>, Blake2_128Concat, u64, u128, ValueQuery>;
+}
+
+/// Keys the fees of sent transfers in `SequenceFee` by the port and channel they were sent on
+/// in addition to their sequence, as of storage version 2.
+///
+/// A fee is moved to the only transfer channel that still has a commitment for its sequence, the
+/// transfer is then still pending and can be refunded. Fees of other sequences are dropped.
+pub struct MigrateSequenceFees<T>(PhantomData<T>);
+
+impl<T: Config> OnRuntimeUpgrade for MigrateSequenceFees<T> {
+	fn on_runtime_upgrade() -> Weight {
+		let on_chain_version = Pallet::<T>::on_chain_storage_version();
+		if on_chain_version != 1 {
+			log::info!(target: "pallet_ibc", "Sequence fees not at storage version 1, skipping migration");
+			return T::DbWeight::get().reads(1)
+		}
+		let port_id = ibc::core::ics24_host::identifier::PortId::transfer();
+		let channels = ChannelIds::<T>::get()
+			.into_iter()
+			.filter_map(|channel_id| ibc_primitives::channel_id_from_bytes(channel_id).ok())
+			.collect::<Vec<_>>();
+		// Collected first, the fees are stored under the same prefix with the new keys
+		let fees = v1::SequenceFee::<T>::drain().collect::<Vec<_>>();
+		let (mut reads, mut writes) = (2, fees.len() as u64 + 1);
+		let (mut moved, mut dropped) = (0, 0);
+		for (sequence, fee) in fees {
+			let mut pending = channels.iter().filter(|channel_id| {
+				crate::ics23::packet_commitments::PacketCommitment::<T>::contains_key((
+					port_id.clone(),
+					**channel_id,
+					sequence.into(),
+				))
+			});
+			reads += channels.len() as u64;
+			match (pending.next(), pending.next()) {
+				(Some(channel_id), None) => {
+					SequenceFee::<T>::insert(
+						(port_id.as_bytes().to_vec(), channel_id.to_string().into_bytes()),
+						sequence,
+						fee,
+					);
+					writes += 1;
+					moved += 1;
+				},
+				_ => dropped += 1,
+			}
+		}
+		StorageVersion::new(2).put::<Pallet<T>>();
+		log::info!(target: "pallet_ibc", "Migrated {moved} sequence fees, dropped {dropped}");
+		T::DbWeight::get().reads_writes(reads, writes)
+	}
+
+	#[cfg(feature = "try-runtime")]
+	fn post_upgrade(_state: Vec<u8>) -> Result<(), sp_runtime::TryRuntimeError> {
+		frame_support::ensure!(
+			Pallet::<T>::on_chain_storage_version() >= 2,
+			"Storage version was not bumped"
+		);
+		Ok(())
+	}
+}
@@ -14,6 +14,7 @@ use frame_support::{
 		},
 		AsEnsureOriginWithArg, ConstU64, Everything,
 	},
+	weights::Weight,
 };
 use frame_system as system;
 use frame_system::EnsureSigned;
@@ -212,10 +213,16 @@ parameter_types! {
 	pub const IbcTriePrefix : &'static [u8] = b"ibc/";
 	pub const ServiceCharge: Perbill = Perbill::from_percent(1);
 	pub const PalletId: frame_support::PalletId = frame_support::PalletId(*b"ics20fee");
+	pub const Ics29FeePalletId: frame_support::PalletId = frame_support::PalletId(*b"ics29fee");
+	pub const MaxPacketFees: u32 = 4;
 	pub const FlatFeeAssetId: AssetId = 130;
 	pub const FlatFeeAmount: AssetId = 10_000_000;
 	pub FeeAccount: <Test as Config>::AccountIdConversion = create_alice_key();
 	pub const CleanUpPacketsPeriod: u32 = 10;
+	pub const MaxWasmCodeSize: u32 = 3 * 1024 * 1024;
+	pub const WasmGasLimit: u64 = 50_000_000;
+	pub const MaxForwardAttempts: u32 = 4;
+	pub const ForwardStatusRetention: u32 = 10;
 }
 
 fn create_alice_key() -> <Test as Config>::AccountIdConversion {
@@ -285,7 +292,7 @@ impl Config for Test {
 	type IbcAccountId = Self::AccountId;
 	type TransferOrigin = EnsureSigned<Self::IbcAccountId>;
 	type RelayerOrigin = EnsureSigned<Self::AccountId>;
-	type HandleMemo = IbcMemoHandler<(), Test>;
+	type HandleMemo = IbcMemoHandler<(), Test, crate::ics20_hooks::RuntimeCallMemo<Test>>;
 	type MemoMessage = RawMemo;
 	type IsReceiveEnabled = sp_core::ConstBool<true>;
 	type IsSendEnabled = sp_core::ConstBool<true>;
@@ -295,6 +302,11 @@ impl Config for Test {
 	type FlatFeeConverter = FlatFeeConverterDummy<Test>;
 	type FlatFeeAssetId = FlatFeeAssetId;
 	type FlatFeeAmount = FlatFeeAmount;
+	type MaxWasmCodeSize = MaxWasmCodeSize;
+	type WasmGasLimit = WasmGasLimit;
+	type IcaMaxTxWeight = IcaMaxTxWeight;
+	type MaxForwardAttempts = MaxForwardAttempts;
+	type ForwardStatusRetention = ForwardStatusRetention;
 	type SubstrateMultihopXcmHandler = SubstrateMultihopXcmHandlerNone<Test>;
 }
 
@@ -320,6 +332,39 @@ impl crate::ics20_fee::Config for Test {
 	type PalletId = PalletId;
 }
 
+pub struct RemarkCallFilter;
+impl frame_support::traits::Contains<RuntimeCall> for RemarkCallFilter {
+	fn contains(call: &RuntimeCall) -> bool {
+		matches!(call, RuntimeCall::System(frame_system::Call::remark_with_event { .. }))
+	}
+}
+
+parameter_types! {
+	pub const MaxHooksCallWeight: Weight = Weight::from_parts(1_000_000_000, 64 * 1024);
+	pub const IcaMaxTxWeight: Weight = Weight::from_parts(1_000_000_000, 64 * 1024);
+}
+
+impl crate::ics20_hooks::Config for Test {
+	type RuntimeEvent = RuntimeEvent;
+	type RuntimeCall = RuntimeCall;
+	type CallFilter = RemarkCallFilter;
+	type MaxCallWeight = MaxHooksCallWeight;
+}
+
+impl crate::ics27::Config for Test {
+	type RuntimeEvent = RuntimeEvent;
+	type RuntimeCall = RuntimeCall;
+	type CallFilter = RemarkCallFilter;
+	type IsHostEnabled = frame_support::traits::ConstBool<true>;
+	type IsControllerEnabled = frame_support::traits::ConstBool<true>;
+}
+
+impl crate::ics29_fee::Config for Test {
+	type RuntimeEvent = RuntimeEvent;
+	type PalletId = Ics29FeePalletId;
+	type MaxPacketFees = MaxPacketFees;
+}
+
 #[derive(
 	Debug, codec::Encode, Clone, codec::Decode, PartialEq, Eq, scale_info::TypeInfo, Default,
 )]
@@ -420,13 +465,41 @@ where
 	}
 }
 
+pub const ASYNC_ACK_PORT_ID: &str = "asyncack";
+pub const ASYNC_ACK_MODULE_ID: &str = "asyncack";
+
+/// Module that writes the acknowledgement of received packets asynchronously
+#[derive(Clone, Debug, Eq, PartialEq, Default)]
+pub struct AsyncAckModule;
+
+impl ibc::core::ics26_routing::context::Module for AsyncAckModule {
+	fn on_recv_packet(
+		&self,
+		_ctx: &dyn ibc::core::ics26_routing::context::ModuleCallbackContext,
+		_output: &mut ibc::core::ics26_routing::context::ModuleOutputBuilder,
+		_packet: &mut ibc::core::ics04_channel::packet::Packet,
+		_relayer: &ibc::signer::Signer,
+	) -> Result<
+		ibc::core::ics26_routing::context::OnRecvPacketAck,
+		ibc::core::ics04_channel::error::Error,
+	> {
+		Ok(ibc::core::ics26_routing::context::OnRecvPacketAck::Async)
+	}
+}
+
 #[derive(Clone, Debug, Eq, PartialEq, Default)]
 pub struct Router {
 	ibc_ping: pallet_ibc_ping::IbcModule<Test>,
-	ics20: crate::ics20::memo::Memo<
+	ics20: crate::ics29_fee::Ics29Fee<
 		Test,
-		crate::ics20_fee::Ics20ServiceCharge<Test, crate::ics20::IbcModule<Test>>,
+		crate::ics20::memo::Memo<
+			Test,
+			crate::ics20_fee::Ics20ServiceCharge<Test, crate::ics20::IbcModule<Test>>,
+		>,
 	>,
+	ica_host: crate::ics27::IcaHost<Test>,
+	ica_controller: crate::ics27::IcaController<Test>,
+	async_ack: crate::ics29_fee::Ics29Fee<Test, AsyncAckModule>,
 }
 
 impl ModuleRouter for Router {
@@ -437,6 +510,10 @@ impl ModuleRouter for Router {
 		match module_id.as_ref() {
 			pallet_ibc_ping::MODULE_ID => Some(&mut self.ibc_ping),
 			ibc::applications::transfer::MODULE_ID_STR => Some(&mut self.ics20),
+			ibc::applications::interchain_accounts::HOST_MODULE_ID_STR => Some(&mut self.ica_host),
+			ibc::applications::interchain_accounts::CONTROLLER_MODULE_ID_STR =>
+				Some(&mut self.ica_controller),
+			ASYNC_ACK_MODULE_ID => Some(&mut self.async_ack),
 			&_ => None,
 		}
 	}
@@ -444,7 +521,11 @@ impl ModuleRouter for Router {
 	fn has_route(module_id: &ibc::core::ics26_routing::context::ModuleId) -> bool {
 		matches!(
 			module_id.as_ref(),
-			pallet_ibc_ping::MODULE_ID | ibc::applications::transfer::MODULE_ID_STR
+			pallet_ibc_ping::MODULE_ID |
+				ibc::applications::transfer::MODULE_ID_STR |
+				ibc::applications::interchain_accounts::HOST_MODULE_ID_STR |
+				ibc::applications::interchain_accounts::CONTROLLER_MODULE_ID_STR |
+				ASYNC_ACK_MODULE_ID
 		)
 	}
 
@@ -460,6 +541,18 @@ impl ModuleRouter for Router {
 					ibc::applications::transfer::MODULE_ID_STR,
 				)
 				.ok(),
+			ibc::applications::interchain_accounts::HOST_PORT_ID_STR =>
+				ibc::core::ics26_routing::context::ModuleId::from_str(
+					ibc::applications::interchain_accounts::HOST_MODULE_ID_STR,
+				)
+				.ok(),
+			ASYNC_ACK_PORT_ID =>
+				ibc::core::ics26_routing::context::ModuleId::from_str(ASYNC_ACK_MODULE_ID).ok(),
+			_ if ibc::applications::interchain_accounts::is_controller_port(port_id) =>
+				ibc::core::ics26_routing::context::ModuleId::from_str(
+					ibc::applications::interchain_accounts::CONTROLLER_MODULE_ID_STR,
+				)
+				.ok(),
 			_ => None,
 		}
 	}
@@ -480,6 +573,9 @@ frame_support::construct_runtime!(
 		PalletBalances: balances,
 		IbcPing: pallet_ibc_ping,
 		Ics20Fee: crate::ics20_fee,
+		Ics20Hooks: crate::ics20_hooks,
+		Ics27: crate::ics27,
+		Ics29Fee: crate::ics29_fee,
 		Ibc: pallet_ibc,
 		Aura: pallet_aura,
 		Membership: pallet_membership::<Instance2>,
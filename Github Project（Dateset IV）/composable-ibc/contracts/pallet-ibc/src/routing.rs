@@ -16,11 +16,13 @@ use sp_core::crypto::AccountId32;
 pub(crate) struct Context<T: Config> {
 	pub _pd: PhantomData<T>,
 	router: IbcRouter<T>,
+	/// Whether messages are delivered by `AdminOrigin`, which governs channel upgrades.
+	pub upgrade_authority: bool,
 }
 
 impl<T: Config + Send + Sync> Default for Context<T> {
 	fn default() -> Self {
-		Self { _pd: PhantomData, router: IbcRouter::default() }
+		Self { _pd: PhantomData, router: IbcRouter::default(), upgrade_authority: false }
 	}
 }
 
@@ -28,10 +30,17 @@ impl<T: Config + Send + Sync> Context<T> {
 	pub fn new() -> Self {
 		Self::default()
 	}
+
+	/// Returns a context delivering messages on behalf of the channel upgrade authority.
+	pub fn with_upgrade_authority(self) -> Self {
+		Self { upgrade_authority: true, ..self }
+	}
 }
 
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct IbcRouter<T: Config> {
+	/// Transfer module used when the runtime `Router` has no route for it, runtimes that
+	/// incentivize relaying route it through [`crate::ics29_fee::Ics29Fee`] instead.
 	ibc_transfer: ics20::IbcModule<T>,
 	sub_router: T::Router,
 }
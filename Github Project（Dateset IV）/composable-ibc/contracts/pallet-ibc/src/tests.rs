@@ -1,24 +1,30 @@
 use crate::{
+	ics20::MemoFailureReason,
 	ics20_fee::FlatFeeConverter,
+	ics23::{acknowledgements::Acknowledgements, packet_commitments::PacketCommitment},
+	ics29_fee::{FeeEnabledChannels, IncentivizedAcknowledgement, PacketFees},
 	light_clients::{AnyClientState, AnyConsensusState},
+	migrations::{MigrateSequenceFees, SeedEscrowedAmounts},
 	mock::*,
 	routing::Context,
-	Any, Config, ConsensusHeights, DenomToAssetId, Event, MultiAddress, Pallet,
-	PendingRecvPacketSeqs, PendingSendPacketSeqs, Timeout, TransferParams, MODULE_ID,
+	Acks, Any, ChannelIds, Config, ConsensusHeights, DenomToAssetId, Event, InFlightForwards,
+	MultiAddress, Pallet, PendingAcknowledgements, PendingRecvPacketSeqs, PendingSendPacketSeqs,
+	SequenceFee, Timeout, TotalEscrowed, TransferParams, WasmCodes, MODULE_ID,
 };
 use core::time::Duration;
 use frame_support::{
 	assert_noop, assert_ok,
 	traits::{
 		fungibles::{Inspect, Mutate},
-		Currency, Hooks, Len,
+		Currency, GetStorageVersion, Hooks, Len, OnRuntimeUpgrade, StorageVersion,
 	},
 	weights::Weight,
 };
 use ibc::{
 	applications::transfer::{
-		acknowledgement::Acknowledgement as Ics20Acknowledgement, packet::PacketData, Coin,
-		PrefixedDenom, VERSION,
+		acknowledgement::Acknowledgement as Ics20Acknowledgement,
+		packet::{PacketData, PacketDataV2},
+		Coin, PrefixedDenom, VERSION,
 	},
 	core::{
 		ics02_client::{
@@ -54,7 +60,10 @@ use ibc::{
 	signer::Signer,
 	tx_msg::Msg,
 };
-use ibc_primitives::{get_channel_escrow_address, HandlerMessage, IbcHandler};
+use ibc_primitives::{
+	get_channel_escrow_address, ForwardAttempt, ForwardDestination, ForwardStatus, HandlerMessage,
+	IbcHandler, PageRequest,
+};
 use sp_core::Pair;
 use sp_runtime::{
 	traits::{Bounded, IdentifyAccount},
@@ -271,6 +280,110 @@ fn send_transfer() {
 	})
 }
 
+#[test]
+fn send_multi_asset_transfer_and_refund_on_error_acknowledgement() {
+	let mut ext = new_test_ext();
+	ext.execute_with(|| {
+		let sender = AccountId32::new([0; 32]);
+		let balance = 100000 * MILLIS;
+		let amt = 1000 * MILLIS;
+		setup_client_and_consensus_state(PortId::transfer());
+		let native_asset_id = <<Test as Config>::IbcDenomToAssetIdConversion as DenomToAssetId<
+			Test,
+		>>::from_denom_to_asset_id("PICA")
+		.unwrap();
+		let asset_id =
+			<<Test as Config>::IbcDenomToAssetIdConversion as DenomToAssetId<Test>>::from_denom_to_asset_id(
+				"PICAFLATFEE",
+			)
+			.unwrap();
+		let _ = <<Test as Config>::NativeCurrency as Currency<
+			<Test as frame_system::Config>::AccountId,
+		>>::deposit_creating(&sender, balance);
+		<<Test as Config>::Fungibles as Mutate<<Test as frame_system::Config>::AccountId>>::mint_into(
+			asset_id, &sender, balance,
+		)
+		.unwrap();
+		Ibc::add_channels_to_feeless_channel_list(RuntimeOrigin::root(), 0, 1).unwrap();
+
+		let params = TransferParams {
+			to: MultiAddress::Raw(vec![42; 10]),
+			source_channel: 0,
+			timeout: Timeout::Offset { timestamp: None, height: Some(5) },
+		};
+		let assets = vec![(native_asset_id, amt), (asset_id, amt)];
+
+		// Several tokens can not be sent over an ics20-1 channel
+		assert_noop!(
+			Ibc::transfer_multi(
+				RuntimeOrigin::signed(sender.clone()),
+				params.clone(),
+				assets.clone(),
+				None
+			),
+			crate::Error::<Test>::TransferSend
+		);
+
+		let port_id = PortId::transfer();
+		let channel_id = ChannelId::new(0);
+		let mut ctx = Context::<Test>::default();
+		let mut channel_end = ctx.channel_end(&(port_id.clone(), channel_id)).unwrap();
+		channel_end.version = ChanVersion::ics20_v2();
+		ctx.store_channel((port_id.clone(), channel_id), &channel_end).unwrap();
+
+		assert_ok!(Ibc::transfer_multi(
+			RuntimeOrigin::signed(sender.clone()),
+			params,
+			assets,
+			None
+		));
+		assert_eq!(Balances::free_balance(&sender), balance - amt);
+		assert_eq!(<Assets as Inspect<AccountId>>::balance(asset_id, &sender), balance - amt);
+
+		let packet_info = Ibc::get_send_packet_info(
+			channel_id.to_string().into_bytes(),
+			port_id.as_bytes().to_vec(),
+			vec![1],
+		)
+		.unwrap()
+		.get(0)
+		.unwrap()
+		.clone();
+		let packet_data =
+			PacketDataV2::decode(&ChanVersion::ics20_v2(), &packet_info.data).unwrap();
+		let denoms = packet_data
+			.tokens
+			.iter()
+			.map(|token| token.denom.to_string())
+			.collect::<Vec<_>>();
+		assert_eq!(denoms, vec!["PICA".to_string(), "PICAFLATFEE".to_string()]);
+
+		let msg = MsgAcknowledgement {
+			packet: Packet::from(packet_info),
+			acknowledgement: Acknowledgement::from_bytes(
+				Ics20Acknowledgement::Error("transfer failed".to_string())
+					.to_string()
+					.into_bytes(),
+			),
+			proofs: Proofs::new(
+				vec![0u8; 32].try_into().unwrap(),
+				None,
+				None,
+				None,
+				Height::new(0, 1),
+			)
+			.unwrap(),
+			signer: Signer::from_str(MODULE_ID).unwrap(),
+		};
+		let msg = Any { type_url: msg.type_url(), value: msg.encode_vec().unwrap() };
+		Ibc::deliver(RuntimeOrigin::signed(sender.clone()), vec![msg]).unwrap();
+
+		// Every token of the packet is refunded
+		assert_eq!(Balances::free_balance(&sender), balance);
+		assert_eq!(<Assets as Inspect<AccountId>>::balance(asset_id, &sender), balance);
+	})
+}
+
 #[test]
 fn send_transfer_with_invalid_memo() {
 	let mut ext = new_test_ext();
@@ -400,6 +513,134 @@ fn send_transfer_no_fee_feeless_channels() {
 	})
 }
 
+#[test]
+fn send_transfer_tracks_escrowed_amount() {
+	let mut ext = new_test_ext();
+	let balance = 100000 * MILLIS;
+	ext.execute_with(|| {
+		let pair = sp_core::sr25519::Pair::from_seed(b"12345678901234567890123456789012");
+		let ss58_address =
+			ibc_primitives::runtime_interface::account_id_to_ss58(pair.public().0, 49);
+		setup_client_and_consensus_state(PortId::transfer());
+		let asset_id =
+			<<Test as Config>::IbcDenomToAssetIdConversion as DenomToAssetId<Test>>::from_denom_to_asset_id(
+				"PICA",
+			)
+			.unwrap();
+		let _ = <<Test as Config>::NativeCurrency as Currency<
+			<Test as frame_system::Config>::AccountId,
+		>>::deposit_creating(&AccountId32::new([0; 32]), balance);
+
+		let timeout = Timeout::Offset { timestamp: Some(1000), height: Some(5) };
+		Ibc::add_channels_to_feeless_channel_list(RuntimeOrigin::root(), 0, 1)
+			.expect("expect add channels to feeless list");
+
+		Ibc::transfer(
+			RuntimeOrigin::signed(AccountId32::new([0; 32])),
+			TransferParams {
+				to: MultiAddress::Raw(ss58_address.as_bytes().to_vec()),
+				source_channel: 0,
+				timeout,
+			},
+			asset_id,
+			balance,
+			None,
+		)
+		.unwrap();
+
+		let port_id = PortId::transfer().as_bytes().to_vec();
+		let channel_id = ChannelId::new(0).to_string().into_bytes();
+		assert_eq!(
+			TotalEscrowed::<Test>::get((port_id.clone(), channel_id.clone()), b"PICA".to_vec()),
+			balance
+		);
+
+		let escrows = Pallet::<Test>::escrow_invariant().escrows;
+		assert_eq!(escrows.len(), 1);
+		assert_eq!(escrows[0].port_id, port_id);
+		assert_eq!(escrows[0].channel_id, channel_id);
+		assert_eq!(escrows[0].tracked, balance);
+		assert!(escrows[0].balance >= escrows[0].tracked);
+	})
+}
+
+#[test]
+fn migration_seeds_escrowed_amounts_from_existing_balances() {
+	let mut ext = new_test_ext();
+	let balance = 100000 * MILLIS;
+	ext.execute_with(|| {
+		let channel_id = ChannelId::new(0);
+		let channel_escrow_address =
+			get_channel_escrow_address(&PortId::transfer(), channel_id).unwrap();
+		let channel_escrow_address =
+			<Test as Config>::AccountIdConversion::try_from(channel_escrow_address)
+				.map_err(|_| ())
+				.unwrap()
+				.into_account();
+		// Escrow created before the tracking existed
+		let _ = <<Test as Config>::NativeCurrency as Currency<
+			<Test as frame_system::Config>::AccountId,
+		>>::deposit_creating(&channel_escrow_address, balance);
+		ChannelIds::<Test>::put(vec![channel_id.to_string().into_bytes()]);
+		StorageVersion::new(0).put::<Pallet<Test>>();
+
+		SeedEscrowedAmounts::<Test>::on_runtime_upgrade();
+
+		let port_id = PortId::transfer().as_bytes().to_vec();
+		let channel_id = channel_id.to_string().into_bytes();
+		assert_eq!(
+			TotalEscrowed::<Test>::get((port_id.clone(), channel_id.clone()), b"PICA".to_vec()),
+			balance
+		);
+		assert_eq!(Pallet::<Test>::on_chain_storage_version(), 1);
+		let escrows = Pallet::<Test>::escrow_invariant().escrows;
+		assert_eq!(escrows.len(), 1);
+		assert_eq!(escrows[0].tracked, balance);
+		assert_eq!(escrows[0].balance, balance);
+
+		// Running the migration again does not count the escrow twice
+		SeedEscrowedAmounts::<Test>::on_runtime_upgrade();
+		assert_eq!(TotalEscrowed::<Test>::get((port_id, channel_id), b"PICA".to_vec()), balance);
+	})
+}
+
+#[test]
+fn migration_keys_sequence_fees_by_channel() {
+	new_test_ext().execute_with(|| {
+		let port_id = PortId::transfer();
+		ChannelIds::<Test>::put(vec![b"channel-0".to_vec(), b"channel-1".to_vec()]);
+		// Sequence 1 is only pending on channel-0, sequence 2 is pending on both channels and
+		// sequence 3 on none
+		PacketCommitment::<Test>::insert(
+			(port_id.clone(), ChannelId::new(0), 1u64.into()),
+			vec![1; 32].into(),
+		);
+		PacketCommitment::<Test>::insert(
+			(port_id.clone(), ChannelId::new(0), 2u64.into()),
+			vec![2; 32].into(),
+		);
+		PacketCommitment::<Test>::insert(
+			(port_id.clone(), ChannelId::new(1), 2u64.into()),
+			vec![2; 32].into(),
+		);
+		crate::migrations::v1::SequenceFee::<Test>::insert(1, 100);
+		crate::migrations::v1::SequenceFee::<Test>::insert(2, 200);
+		crate::migrations::v1::SequenceFee::<Test>::insert(3, 300);
+		StorageVersion::new(1).put::<Pallet<Test>>();
+
+		MigrateSequenceFees::<Test>::on_runtime_upgrade();
+
+		let key = (port_id.as_bytes().to_vec(), b"channel-0".to_vec());
+		assert_eq!(SequenceFee::<Test>::get(&key, 1), 100);
+		assert_eq!(SequenceFee::<Test>::iter().count(), 1);
+		assert_eq!(Pallet::<Test>::on_chain_storage_version(), 2);
+
+		// Running the migration again leaves the fees alone
+		MigrateSequenceFees::<Test>::on_runtime_upgrade();
+		assert_eq!(SequenceFee::<Test>::get(&key, 1), 100);
+	})
+}
+
 #[test]
 fn on_deliver_ics20_recv_packet() {
 	let mut ext = new_test_ext();
@@ -626,7 +867,9 @@ fn on_deliver_ics20_recv_packet_incorrect_memo() {
 							from,
 						} = ibc_event
 						{
-							return memo == &incorrect_memo && *reason == 0 && from == &reciever
+							return memo == &incorrect_memo &&
+								*reason == MemoFailureReason::ParseError &&
+								from == &reciever
 						}
 					}
 					false
@@ -818,13 +1061,10 @@ fn on_ack_transfer_with_custom_success_result() {
 }
 
 #[test]
-fn on_deliver_ics20_recv_packet_transfered_amount_less_then_flat_fee() {
+fn on_ack_fee_enabled_channel_pays_relayers() {
 	let mut ext = new_test_ext();
 	ext.execute_with(|| {
-		// Create  a new account
 		let pair = sp_core::sr25519::Pair::from_seed(b"12345678901234567890123456789012");
-		let ss58_address =
-			ibc_primitives::runtime_interface::account_id_to_ss58(pair.public().0, 49);
 		frame_system::Pallet::<Test>::set_block_number(1u32);
 		let asset_id =
 			<<Test as Config>::IbcDenomToAssetIdConversion as DenomToAssetId<Test>>::from_denom_to_asset_id(
@@ -834,64 +1074,79 @@ fn on_deliver_ics20_recv_packet_transfered_amount_less_then_flat_fee() {
 		setup_client_and_consensus_state(PortId::transfer());
 
 		let channel_id = ChannelId::new(0);
-		let balance = 100000 * MILLIS;
-
-		// We are simulating a transfer back to the source chain
-
-		let denom = "transfer/channel-1/PICAFLATFEE";
-		let channel_escrow_address =
-			get_channel_escrow_address(&PortId::transfer(), channel_id).unwrap();
-		let channel_escrow_address =
-			<Test as Config>::AccountIdConversion::try_from(channel_escrow_address)
-				.map_err(|_| ())
-				.unwrap();
-		let channel_escrow_address = channel_escrow_address.into_account();
+		let port_id = PortId::transfer().as_bytes().to_vec();
+		FeeEnabledChannels::<Test>::insert(
+			port_id.clone(),
+			channel_id.to_string().into_bytes(),
+			(),
+		);
 
-		// Endow escrow address with tokens
+		let acc = AccountId32::new(pair.public().0);
+		let forward_relayer = AccountId32::new([1; 32]);
+		let reverse_relayer = AccountId32::new([2; 32]);
+		let reverse_payee = AccountId32::new([3; 32]);
 		<<Test as Config>::Fungibles as Mutate<
 			<Test as frame_system::Config>::AccountId,
-		>>::mint_into(asset_id, &channel_escrow_address, balance)
+		>>::mint_into(asset_id, &acc, 100000 * MILLIS)
 		.unwrap();
 
-		let prefixed_denom = PrefixedDenom::from_str(denom).unwrap();
-		let usdt_fee_amount = 1000 * MILLIS;
-		let fee_asset_id = <Test as crate::Config>::FlatFeeAssetId::get();
-		let amt = <Test as crate::Config>::FlatFeeConverter::get_flat_fee(
+		assert_ok!(Ibc::transfer(
+			RuntimeOrigin::signed(acc.clone()),
+			TransferParams {
+				to: MultiAddress::Raw(vec![42; 10]),
+				source_channel: channel_id.sequence(),
+				timeout: Timeout::Offset { timestamp: None, height: Some(1) },
+			},
 			asset_id,
-			fee_asset_id,
-			usdt_fee_amount,
+			1000 * MILLIS,
+			None,
+		));
+
+		let (recv_fee, ack_fee, timeout_fee) = (10_000, 20_000, 30_000);
+		assert_ok!(Ics29Fee::register_payee(
+			RuntimeOrigin::signed(reverse_relayer.clone()),
+			channel_id.to_string().into_bytes(),
+			reverse_payee.clone(),
+		));
+		let balance_before_fee = <Assets as Inspect<AccountId>>::balance(asset_id, &acc);
+		assert_ok!(Ics29Fee::pay_packet_fee_async(
+			RuntimeOrigin::signed(acc.clone()),
+			port_id,
+			channel_id.to_string().into_bytes(),
+			1,
+			asset_id,
+			recv_fee,
+			ack_fee,
+			timeout_fee,
+		));
+		assert_eq!(
+			<Assets as Inspect<AccountId>>::balance(asset_id, &Ics29Fee::account_id()),
+			recv_fee + ack_fee + timeout_fee
+		);
+
+		let packet_info = Ibc::get_send_packet_info(
+			channel_id.to_string().into_bytes(),
+			PortId::transfer().as_bytes().to_vec(),
+			vec![1],
 		)
-		.unwrap_or_default();
-		println!("Transferred Amount {amt}");
-		let coin = Coin {
-			denom: prefixed_denom,
-			amount: ibc::applications::transfer::Amount::from_str(&format!("{amt:?}")).unwrap(),
-		};
-		let packet_data = PacketData {
-			token: coin,
-			sender: Signer::from_str("alice").unwrap(),
-			receiver: Signer::from_str(&ss58_address).unwrap(),
-			memo: "".to_string(),
-		};
+		.unwrap()
+		.get(0)
+		.unwrap()
+		.clone();
+		let packet = Packet::from(packet_info);
 
-		let time_now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_nanos();
-		let data = serde_json::to_vec(&packet_data).unwrap();
-		let packet = Packet {
-			sequence: 1u64.into(),
-			source_port: PortId::transfer(),
-			source_channel: ChannelId::new(1),
-			destination_port: PortId::transfer(),
-			destination_channel: ChannelId::new(0),
-			data,
-			timeout_height: Height::new(2000, 5),
-			timeout_timestamp: ibc::timestamp::Timestamp::from_nanoseconds(
-				time_now as u64 + 10000000,
-			)
-			.unwrap(),
+		let app_ack = Ics20Acknowledgement::success().to_string().into_bytes();
+		let ack = IncentivizedAcknowledgement {
+			app_acknowledgement: base64::encode(app_ack),
+			forward_relayer_address: format!(
+				"0x{}",
+				hex::encode(<[u8; 32]>::from(forward_relayer.clone()))
+			),
+			underlying_app_success: true,
 		};
-
-		let msg = MsgRecvPacket {
+		let msg = MsgAcknowledgement {
 			packet,
+			acknowledgement: Acknowledgement::from_bytes(serde_json::to_vec(&ack).unwrap()),
 			proofs: Proofs::new(
 				vec![0u8; 32].try_into().unwrap(),
 				None,
@@ -900,40 +1155,31 @@ fn on_deliver_ics20_recv_packet_transfered_amount_less_then_flat_fee() {
 				Height::new(0, 1),
 			)
 			.unwrap(),
-			signer: Signer::from_str(MODULE_ID).unwrap(),
+			signer: Signer::from_str(&format!(
+				"0x{}",
+				hex::encode(<[u8; 32]>::from(reverse_relayer.clone()))
+			))
+			.unwrap(),
 		};
-
 		let msg = Any { type_url: msg.type_url(), value: msg.encode_vec().unwrap() };
-
-		let account_data = Assets::balance(asset_id, AccountId32::new(pair.public().0));
-		// Assert account balance before transfer
-		assert_eq!(account_data, 0);
 		Ibc::deliver(RuntimeOrigin::signed(AccountId32::new([0; 32])), vec![msg]).unwrap();
 
-		let balance =
-			<Assets as Inspect<AccountId>>::balance(asset_id, &AccountId32::new(pair.public().0));
-		let pallet_balance = <Assets as Inspect<AccountId>>::balance(
-			asset_id,
-			&<Test as crate::Config>::FeeAccount::get().into_account(),
+		assert_eq!(<Assets as Inspect<AccountId>>::balance(asset_id, &forward_relayer), recv_fee);
+		assert_eq!(<Assets as Inspect<AccountId>>::balance(asset_id, &reverse_payee), ack_fee);
+		assert_eq!(<Assets as Inspect<AccountId>>::balance(asset_id, &reverse_relayer), 0);
+		// The timeout fee is refunded since the packet was acknowledged
+		assert_eq!(
+			<Assets as Inspect<AccountId>>::balance(asset_id, &acc),
+			balance_before_fee - recv_fee - ack_fee
 		);
-		let fee = <Test as crate::Config>::FlatFeeConverter::get_flat_fee(
-			asset_id,
-			fee_asset_id,
-			usdt_fee_amount,
-		)
-		.unwrap_or_default();
-		let actual_fee = fee.min(amt);
-		assert_eq!(balance, 0);
-		assert_eq!(balance, amt - actual_fee);
-		assert_eq!(pallet_balance, actual_fee);
+		assert_eq!(PacketFees::<Test>::iter().count(), 0);
 	})
 }
 
 #[test]
-fn on_deliver_ics20_recv_packet_should_not_double_spend() {
+fn on_recv_fee_enabled_transfer_acknowledges_forward_relayer() {
 	let mut ext = new_test_ext();
 	ext.execute_with(|| {
-		// Create  a new account
 		let pair = sp_core::sr25519::Pair::from_seed(b"12345678901234567890123456789012");
 		let ss58_address =
 			ibc_primitives::runtime_interface::account_id_to_ss58(pair.public().0, 49);
@@ -941,31 +1187,1146 @@ fn on_deliver_ics20_recv_packet_should_not_double_spend() {
 		setup_client_and_consensus_state(PortId::transfer());
 
 		let channel_id = ChannelId::new(0);
-		let balance = 100000 * MILLIS;
-
-		// We are simulating a transfer back to the source chain
+		let (port_bytes, channel_bytes) =
+			(PortId::transfer().as_bytes().to_vec(), channel_id.to_string().into_bytes());
+		FeeEnabledChannels::<Test>::insert(port_bytes.clone(), channel_bytes.clone(), ());
+
+		let relayer = AccountId32::new([1; 32]);
+		assert_ok!(Ics29Fee::register_counterparty_payee(
+			RuntimeOrigin::signed(relayer.clone()),
+			channel_bytes.clone(),
+			b"centauri1relayer".to_vec(),
+		));
 
-		let denom = "transfer/channel-1/PICA";
+		// Endow escrow address with the tokens transferred back to this chain
 		let channel_escrow_address =
 			get_channel_escrow_address(&PortId::transfer(), channel_id).unwrap();
 		let channel_escrow_address =
 			<Test as Config>::AccountIdConversion::try_from(channel_escrow_address)
 				.map_err(|_| ())
-				.unwrap();
-		let channel_escrow_address = channel_escrow_address.into_account();
-
-		// Endow escrow address with tokens
+				.unwrap()
+				.into_account();
 		let _ = <<Test as Config>::NativeCurrency as Currency<
 			<Test as frame_system::Config>::AccountId,
-		>>::deposit_creating(&channel_escrow_address, balance);
+		>>::deposit_creating(&channel_escrow_address, 100000 * MILLIS);
 
-		let prefixed_denom = PrefixedDenom::from_str(denom).unwrap();
-		let amt = MILLIS / 100;
-		println!("Transferred Amount {amt}");
-		let coin = Coin {
-			denom: prefixed_denom,
-			amount: ibc::applications::transfer::Amount::from_str(&format!("{amt:?}")).unwrap(),
-		};
+		let amt = 1000 * MILLIS;
+		let packet_data = PacketData {
+			token: Coin {
+				denom: PrefixedDenom::from_str("transfer/channel-1/PICA").unwrap(),
+				amount: ibc::applications::transfer::Amount::from_str(&format!("{amt:?}")).unwrap(),
+			},
+			sender: Signer::from_str("alice").unwrap(),
+			receiver: Signer::from_str(&ss58_address).unwrap(),
+			memo: "".to_string(),
+		};
+		let time_now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_nanos();
+		let packet = Packet {
+			sequence: 1u64.into(),
+			source_port: PortId::transfer(),
+			source_channel: ChannelId::new(1),
+			destination_port: PortId::transfer(),
+			destination_channel: channel_id,
+			data: serde_json::to_vec(&packet_data).unwrap(),
+			timeout_height: Height::new(2000, 5),
+			timeout_timestamp: ibc::timestamp::Timestamp::from_nanoseconds(
+				time_now as u64 + 10000000,
+			)
+			.unwrap(),
+		};
+		let msg = MsgRecvPacket {
+			packet,
+			proofs: Proofs::new(
+				vec![0u8; 32].try_into().unwrap(),
+				None,
+				None,
+				None,
+				Height::new(0, 1),
+			)
+			.unwrap(),
+			signer: Signer::from_str(&format!("0x{}", hex::encode(<[u8; 32]>::from(relayer))))
+				.unwrap(),
+		};
+		let msg = Any { type_url: msg.type_url(), value: msg.encode_vec().unwrap() };
+		Ibc::deliver(RuntimeOrigin::signed(AccountId32::new([0; 32])), vec![msg]).unwrap();
+
+		// The fee middleware wrapped the transfer acknowledgement so the relayer is paid the recv
+		// fee on the source chain
+		let ack = Acks::<Test>::get(Pallet::<Test>::ack_key(channel_bytes, port_bytes, 1)).unwrap();
+		let ack = serde_json::from_slice::<IncentivizedAcknowledgement>(&ack).unwrap();
+		assert_eq!(ack.forward_relayer_address, "centauri1relayer");
+		assert!(ack.underlying_app_success);
+		let app_ack = base64::decode(ack.app_acknowledgement).unwrap();
+		assert!(serde_json::from_slice::<Ics20Acknowledgement>(&app_ack)
+			.unwrap()
+			.is_successful());
+
+		let fee = <Test as crate::ics20_fee::Config>::ServiceChargeIn::get() * amt;
+		assert_eq!(
+			<<Test as Config>::NativeCurrency as Currency<
+				<Test as frame_system::Config>::AccountId,
+			>>::free_balance(&AccountId32::new(pair.public().0)),
+			amt - fee
+		);
+	})
+}
+
+#[test]
+fn pay_packet_fee_async_is_bounded_per_packet() {
+	let mut ext = new_test_ext();
+	ext.execute_with(|| {
+		let pair = sp_core::sr25519::Pair::from_seed(b"12345678901234567890123456789012");
+		frame_system::Pallet::<Test>::set_block_number(1u32);
+		let asset_id =
+			<<Test as Config>::IbcDenomToAssetIdConversion as DenomToAssetId<Test>>::from_denom_to_asset_id(
+				"PICAFLATFEE",
+			)
+			.unwrap();
+		setup_client_and_consensus_state(PortId::transfer());
+
+		let channel_id = ChannelId::new(0);
+		let port_id = PortId::transfer().as_bytes().to_vec();
+		FeeEnabledChannels::<Test>::insert(
+			port_id.clone(),
+			channel_id.to_string().into_bytes(),
+			(),
+		);
+
+		let acc = AccountId32::new(pair.public().0);
+		<<Test as Config>::Fungibles as Mutate<
+			<Test as frame_system::Config>::AccountId,
+		>>::mint_into(asset_id, &acc, 100000 * MILLIS)
+		.unwrap();
+
+		assert_ok!(Ibc::transfer(
+			RuntimeOrigin::signed(acc.clone()),
+			TransferParams {
+				to: MultiAddress::Raw(vec![42; 10]),
+				source_channel: channel_id.sequence(),
+				timeout: Timeout::Offset { timestamp: None, height: Some(1) },
+			},
+			asset_id,
+			1000 * MILLIS,
+			None,
+		));
+
+		let pay_fee = || {
+			Ics29Fee::pay_packet_fee_async(
+				RuntimeOrigin::signed(acc.clone()),
+				port_id.clone(),
+				channel_id.to_string().into_bytes(),
+				1,
+				asset_id,
+				10_000,
+				20_000,
+				30_000,
+			)
+		};
+		for _ in 0..MaxPacketFees::get() {
+			assert_ok!(pay_fee());
+		}
+		let balance_before_fee = <Assets as Inspect<AccountId>>::balance(asset_id, &acc);
+		assert_noop!(pay_fee(), crate::ics29_fee::Error::<Test>::TooManyPacketFees);
+		assert_eq!(<Assets as Inspect<AccountId>>::balance(asset_id, &acc), balance_before_fee);
+		assert_eq!(
+			PacketFees::<Test>::get((port_id, channel_id.to_string().into_bytes()), 1).len(),
+			MaxPacketFees::get() as usize
+		);
+	})
+}
+
+#[test]
+fn on_recv_ica_packet_dispatches_allowed_calls_from_interchain_account() {
+	use crate::ics27::{self, IcaContext, RUNTIME_CALL_TYPE_URL};
+	use codec::Encode;
+	use ibc::applications::interchain_accounts::{
+		context::InterchainAccountHostContext, controller_port_id, host_port_id,
+		packet::InterchainAccountPacketData,
+	};
+
+	let mut ext = new_test_ext();
+	ext.execute_with(|| {
+		frame_system::Pallet::<Test>::set_block_number(1u32);
+		setup_client_and_consensus_state(host_port_id());
+
+		// Host end of a channel opened by a controller on the counterparty chain
+		let mut ctx = Context::<Test>::default();
+		let connection_id = ConnectionId::new(0);
+		let channel_id = ChannelId::new(0);
+		let controller_port = controller_port_id("owner").unwrap();
+		let channel_end = ChannelEnd::new(
+			State::Open,
+			Order::Ordered,
+			ChanCounterParty::new(controller_port.clone(), Some(ChannelId::new(1))),
+			vec![connection_id.clone()],
+			ChanVersion::new(ibc::applications::interchain_accounts::VERSION.to_string()),
+		);
+		ctx.store_channel((host_port_id(), channel_id), &channel_end).unwrap();
+		let mut ica_ctx = IcaContext::<Test>::default();
+		ica_ctx.register_interchain_account(&connection_id, &controller_port).unwrap();
+		ica_ctx
+			.store_host_active_channel(&connection_id, &controller_port, &channel_id)
+			.unwrap();
+		let account = ics27::Pallet::<Test>::host_account(
+			connection_id.as_bytes(),
+			controller_port.as_bytes(),
+		);
+
+		let recv_packet = |sequence: u64, call: RuntimeCall| {
+			let data = InterchainAccountPacketData::execute_tx(
+				vec![Any { type_url: RUNTIME_CALL_TYPE_URL.to_string(), value: call.encode() }],
+				String::new(),
+			)
+			.to_bytes();
+			let time_now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_nanos();
+			let msg = MsgRecvPacket {
+				packet: Packet {
+					sequence: sequence.into(),
+					source_port: controller_port.clone(),
+					source_channel: ChannelId::new(1),
+					destination_port: host_port_id(),
+					destination_channel: channel_id,
+					data,
+					timeout_height: Height::new(2000, 5),
+					timeout_timestamp: ibc::timestamp::Timestamp::from_nanoseconds(
+						time_now as u64 + 10000000,
+					)
+					.unwrap(),
+				},
+				proofs: Proofs::new(
+					vec![0u8; 32].try_into().unwrap(),
+					None,
+					None,
+					None,
+					Height::new(0, 1),
+				)
+				.unwrap(),
+				signer: Signer::from_str(MODULE_ID).unwrap(),
+			};
+			let msg = Any { type_url: msg.type_url(), value: msg.encode_vec().unwrap() };
+			Ibc::deliver(RuntimeOrigin::signed(AccountId32::new([0; 32])), vec![msg]).unwrap();
+		};
+		let executed = |sequence: u64| {
+			System::events().into_iter().find_map(|record| match record.event {
+				RuntimeEvent::Ics27(ics27::Event::HostTxExecuted {
+					sequence: executed,
+					success,
+					..
+				}) if executed == sequence => Some(success),
+				_ => None,
+			})
+		};
+
+		recv_packet(
+			1,
+			RuntimeCall::System(frame_system::Call::remark_with_event { remark: vec![1] }),
+		);
+		assert_eq!(executed(1), Some(true));
+		assert!(System::events().into_iter().any(|record| matches!(
+			record.event,
+			RuntimeEvent::System(frame_system::Event::Remarked { ref sender, .. }) if *sender == account
+		)));
+
+		// Calls outside of the filter produce an error acknowledgement
+		recv_packet(2, RuntimeCall::System(frame_system::Call::remark { remark: vec![2] }));
+		assert_eq!(executed(2), Some(false));
+	})
+}
+
+#[test]
+fn ica_host_rejects_transactions_over_the_weight_limit() {
+	use crate::ics27::{IcaContext, RUNTIME_CALL_TYPE_URL};
+	use codec::Encode;
+	use frame_support::dispatch::GetDispatchInfo;
+	use ibc::applications::interchain_accounts::{
+		context::InterchainAccountHostContext, controller_port_id,
+	};
+
+	let mut ext = new_test_ext();
+	ext.execute_with(|| {
+		frame_system::Pallet::<Test>::set_block_number(1u32);
+		let connection_id = ConnectionId::new(0);
+		let controller_port = controller_port_id("owner").unwrap();
+		let mut ica_ctx = IcaContext::<Test>::default();
+		let address =
+			ica_ctx.register_interchain_account(&connection_id, &controller_port).unwrap();
+
+		let call = RuntimeCall::System(frame_system::Call::remark_with_event { remark: vec![1] });
+		let call_weight = call.get_dispatch_info().weight.ref_time();
+		let max_weight = <Test as Config>::IcaMaxTxWeight::get().ref_time();
+		let msg = Any { type_url: RUNTIME_CALL_TYPE_URL.to_string(), value: call.encode() };
+		let messages = vec![msg; (max_weight / call_weight + 1) as usize];
+
+		assert!(ica_ctx.execute_tx(&address, messages).is_err());
+		assert!(!System::events().into_iter().any(|record| matches!(
+			record.event,
+			RuntimeEvent::System(frame_system::Event::Remarked { .. })
+		)));
+	})
+}
+
+#[test]
+fn ica_controller_registers_account_and_sends_transactions() {
+	use crate::ics27::{self, ControllerActiveChannels, InterchainAccountMessage};
+	use ibc::applications::interchain_accounts::{
+		host_port_id, packet::InterchainAccountPacketData,
+	};
+
+	let mut ext = new_test_ext();
+	ext.execute_with(|| {
+		frame_system::Pallet::<Test>::set_block_number(1u32);
+		let owner = AccountId32::new([7; 32]);
+		let port_id = ics27::Pallet::<Test>::controller_port(&owner).unwrap();
+		setup_client_and_consensus_state(PortId::transfer());
+		let connection_id = ConnectionId::new(0);
+		let connection = connection_id.as_bytes().to_vec();
+
+		// Registering opens an ordered channel to the host port of the counterparty
+		assert_ok!(ics27::Pallet::<Test>::register_interchain_account(
+			RuntimeOrigin::signed(owner.clone()),
+			connection.clone(),
+		));
+		let channel_id = ChannelId::new(0);
+		let channel_end =
+			Context::<Test>::default().channel_end(&(port_id.clone(), channel_id)).unwrap();
+		assert_eq!(channel_end.state, State::Init);
+		assert_eq!(channel_end.ordering, Order::Ordered);
+		assert_eq!(channel_end.counterparty().port_id(), &host_port_id());
+
+		let messages = vec![InterchainAccountMessage {
+			type_url: b"/cosmos.bank.v1beta1.MsgSend".to_vec(),
+			value: vec![1, 2, 3],
+		}];
+		let timeout = Timeout::Offset { timestamp: Some(1000), height: Some(5) };
+		assert_noop!(
+			ics27::Pallet::<Test>::send_tx(
+				RuntimeOrigin::signed(owner.clone()),
+				connection.clone(),
+				messages.clone(),
+				vec![],
+				timeout.clone(),
+			),
+			ics27::Error::<Test>::NoActiveChannel
+		);
+
+		// Handshake completed by the counterparty
+		let mut ctx = Context::<Test>::default();
+		let mut open_channel = channel_end;
+		open_channel.set_state(State::Open);
+		open_channel.set_counterparty_channel_id(ChannelId::new(1));
+		ctx.store_channel((port_id.clone(), channel_id), &open_channel).unwrap();
+		ControllerActiveChannels::<Test>::insert(
+			&connection,
+			port_id.as_bytes(),
+			(channel_id.to_string().into_bytes(), b"host-account".to_vec()),
+		);
+		assert_eq!(
+			ics27::Pallet::<Test>::controller_account(&owner, connection.clone()),
+			Some(b"host-account".to_vec())
+		);
+
+		assert_ok!(ics27::Pallet::<Test>::send_tx(
+			RuntimeOrigin::signed(owner.clone()),
+			connection.clone(),
+			messages,
+			b"memo".to_vec(),
+			timeout,
+		));
+		assert!(System::events().into_iter().any(|record| matches!(
+			record.event,
+			RuntimeEvent::Ics27(ics27::Event::ControllerTxSent { ref connection_id, .. })
+				if *connection_id == connection
+		)));
+		let packet_info = Ibc::get_send_packet_info(
+			channel_id.to_string().into_bytes(),
+			port_id.as_bytes().to_vec(),
+			vec![1],
+		)
+		.unwrap()
+		.get(0)
+		.unwrap()
+		.clone();
+		let packet = Packet::from(packet_info);
+		let data = InterchainAccountPacketData::from_bytes(&packet.data).unwrap();
+		let sent = data.messages().unwrap();
+		assert_eq!(sent.len(), 1);
+		assert_eq!(sent[0].type_url, "/cosmos.bank.v1beta1.MsgSend");
+		assert_eq!(data.memo, "memo");
+
+		let msg = MsgAcknowledgement {
+			packet,
+			acknowledgement: Acknowledgement::from_bytes(
+				Ics20Acknowledgement::success().to_string().into_bytes(),
+			),
+			proofs: Proofs::new(
+				vec![0u8; 32].try_into().unwrap(),
+				None,
+				None,
+				None,
+				Height::new(0, 1),
+			)
+			.unwrap(),
+			signer: Signer::from_str(MODULE_ID).unwrap(),
+		};
+		let msg = Any { type_url: msg.type_url(), value: msg.encode_vec().unwrap() };
+		Ibc::deliver(RuntimeOrigin::signed(AccountId32::new([0; 32])), vec![msg]).unwrap();
+		assert!(System::events().into_iter().any(|record| matches!(
+			record.event,
+			RuntimeEvent::Ics27(ics27::Event::ControllerTxAcknowledged {
+				sequence: 1,
+				success: true,
+				..
+			})
+		)));
+	})
+}
+
+#[test]
+fn async_acknowledgement_is_written_once_module_finalizes_it() {
+	let mut ext = new_test_ext();
+	ext.execute_with(|| {
+		frame_system::Pallet::<Test>::set_block_number(1u32);
+		let port_id = PortId::from_str(ASYNC_ACK_PORT_ID).unwrap();
+		setup_client_and_consensus_state(port_id.clone());
+		let channel_id = ChannelId::new(0);
+		let (port_bytes, channel_bytes) =
+			(port_id.as_bytes().to_vec(), channel_id.to_string().into_bytes());
+		FeeEnabledChannels::<Test>::insert(port_bytes.clone(), channel_bytes.clone(), ());
+
+		let time_now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_nanos();
+		let packet = Packet {
+			sequence: 1u64.into(),
+			source_port: port_id.clone(),
+			source_channel: ChannelId::new(1),
+			destination_port: port_id.clone(),
+			destination_channel: channel_id,
+			data: vec![1],
+			timeout_height: Height::new(2000, 5),
+			timeout_timestamp: ibc::timestamp::Timestamp::from_nanoseconds(
+				time_now as u64 + 10000000,
+			)
+			.unwrap(),
+		};
+		let msg = MsgRecvPacket {
+			packet: packet.clone(),
+			proofs: Proofs::new(
+				vec![0u8; 32].try_into().unwrap(),
+				None,
+				None,
+				None,
+				Height::new(0, 1),
+			)
+			.unwrap(),
+			signer: Signer::from_str(MODULE_ID).unwrap(),
+		};
+		let msg = Any { type_url: msg.type_url(), value: msg.encode_vec().unwrap() };
+		Ibc::deliver(RuntimeOrigin::signed(AccountId32::new([0; 32])), vec![msg]).unwrap();
+
+		let key = (port_id.clone(), channel_id, packet.sequence);
+		assert!(!Acknowledgements::<Test>::contains_key(key.clone()));
+		assert!(PendingAcknowledgements::<Test>::contains_key(
+			(port_bytes.clone(), channel_bytes.clone()),
+			1
+		));
+
+		assert_ok!(Pallet::<Test>::handle_message(HandlerMessage::WriteAck {
+			packet,
+			ack: b"done".to_vec(),
+		}));
+		assert!(Acknowledgements::<Test>::contains_key(key));
+		assert!(!PendingAcknowledgements::<Test>::contains_key(
+			(port_bytes.clone(), channel_bytes.clone()),
+			1
+		));
+		// The fee middleware wrapped the acknowledgement written by the module
+		let ack = Acks::<Test>::get(Pallet::<Test>::ack_key(channel_bytes, port_bytes, 1)).unwrap();
+		let ack = serde_json::from_slice::<IncentivizedAcknowledgement>(&ack).unwrap();
+		assert_eq!(ack.app_acknowledgement, base64::encode(b"done"));
+		assert_eq!(ack.forward_relayer_address, MODULE_ID);
+	})
+}
+
+#[test]
+fn failed_memo_forward_refunds_sender_with_error_acknowledgement() {
+	let mut ext = new_test_ext();
+	ext.execute_with(|| {
+		let pair = sp_core::sr25519::Pair::from_seed(b"12345678901234567890123456789012");
+		let receiver = AccountId32::new(pair.public().0);
+		let ss58_address =
+			ibc_primitives::runtime_interface::account_id_to_ss58(pair.public().0, 49);
+		frame_system::Pallet::<Test>::set_block_number(1u32);
+		setup_client_and_consensus_state(PortId::transfer());
+
+		let channel_id = ChannelId::new(0);
+		let (port_bytes, channel_bytes) =
+			(PortId::transfer().as_bytes().to_vec(), channel_id.to_string().into_bytes());
+		let balance = 100000 * MILLIS;
+		let channel_escrow_address =
+			get_channel_escrow_address(&PortId::transfer(), channel_id).unwrap();
+		let channel_escrow_address =
+			<Test as Config>::AccountIdConversion::try_from(channel_escrow_address)
+				.map_err(|_| ())
+				.unwrap()
+				.into_account();
+		let _ = <<Test as Config>::NativeCurrency as Currency<
+			<Test as frame_system::Config>::AccountId,
+		>>::deposit_creating(&channel_escrow_address, balance);
+		// The receiver covers the service charge taken when the tokens are received
+		let _ = <<Test as Config>::NativeCurrency as Currency<
+			<Test as frame_system::Config>::AccountId,
+		>>::deposit_creating(&receiver, balance);
+
+		let amt = 1000 * MILLIS;
+		let memo = r#"{"forward":{"receiver":"bob","port":"transfer","channel":"channel-0","timeout":600000000000,"retries":1}}"#;
+		let packet_data = PacketData {
+			token: Coin {
+				denom: PrefixedDenom::from_str("transfer/channel-1/PICA").unwrap(),
+				amount: ibc::applications::transfer::Amount::from_str(&format!("{:?}", amt))
+					.unwrap(),
+			},
+			sender: Signer::from_str("alice").unwrap(),
+			receiver: Signer::from_str(&ss58_address).unwrap(),
+			memo: memo.to_string(),
+		};
+		let time_now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_nanos();
+		let packet = Packet {
+			sequence: 7u64.into(),
+			source_port: PortId::transfer(),
+			source_channel: ChannelId::new(1),
+			destination_port: PortId::transfer(),
+			destination_channel: channel_id,
+			data: serde_json::to_vec(&packet_data).unwrap(),
+			timeout_height: Height::new(2000, 5),
+			timeout_timestamp: ibc::timestamp::Timestamp::from_nanoseconds(
+				time_now as u64 + 10000000,
+			)
+			.unwrap(),
+		};
+		let msg = MsgRecvPacket {
+			packet: packet.clone(),
+			proofs: Proofs::new(
+				vec![0u8; 32].try_into().unwrap(),
+				None,
+				None,
+				None,
+				Height::new(0, 1),
+			)
+			.unwrap(),
+			signer: Signer::from_str(MODULE_ID).unwrap(),
+		};
+		let msg = Any { type_url: msg.type_url(), value: msg.encode_vec().unwrap() };
+		Ibc::deliver(RuntimeOrigin::signed(AccountId32::new([0; 32])), vec![msg]).unwrap();
+
+		// The acknowledgement is held until the forwarded transfer settles
+		let recv_key = (PortId::transfer(), channel_id, packet.sequence);
+		assert!(!Acknowledgements::<Test>::contains_key(recv_key.clone()));
+		assert!(InFlightForwards::<Test>::contains_key((port_bytes.clone(), channel_bytes.clone()), 1));
+		let attempts =
+			Pallet::<Test>::forward_status(port_bytes.clone(), channel_bytes.clone(), 7).attempts;
+		assert_eq!(
+			attempts,
+			vec![ForwardAttempt {
+				destination: ForwardDestination::Ibc {
+					port_id: port_bytes.clone(),
+					channel_id: channel_bytes.clone(),
+					sequence: 1,
+				},
+				status: ForwardStatus::Pending,
+			}]
+		);
+
+		let forwarded = Ibc::get_send_packet_info(
+			channel_bytes.clone(),
+			port_bytes.clone(),
+			vec![1],
+		)
+		.unwrap()
+		.get(0)
+		.unwrap()
+		.clone();
+		let msg = MsgAcknowledgement {
+			packet: Packet::from(forwarded),
+			acknowledgement: Acknowledgement::from_bytes(
+				Ics20Acknowledgement::Error("forward failed".to_string()).to_string().into_bytes(),
+			),
+			proofs: Proofs::new(
+				vec![0u8; 32].try_into().unwrap(),
+				None,
+				None,
+				None,
+				Height::new(0, 1),
+			)
+			.unwrap(),
+			signer: Signer::from_str(MODULE_ID).unwrap(),
+		};
+		let msg = Any { type_url: msg.type_url(), value: msg.encode_vec().unwrap() };
+		Ibc::deliver(RuntimeOrigin::signed(AccountId32::new([0; 32])), vec![msg]).unwrap();
+
+		assert!(Acknowledgements::<Test>::contains_key(recv_key));
+		assert!(!InFlightForwards::<Test>::contains_key((port_bytes.clone(), channel_bytes.clone()), 1));
+		let attempts =
+			Pallet::<Test>::forward_status(port_bytes.clone(), channel_bytes.clone(), 7).attempts;
+		assert_eq!(attempts.len(), 1);
+		assert_eq!(attempts[0].status, ForwardStatus::Refunded);
+		// The attempts are kept for a while after the received packet is acknowledged
+		let retention = <Test as Config>::ForwardStatusRetention::get();
+		Ibc::on_initialize(retention);
+		assert_eq!(
+			Pallet::<Test>::forward_status(port_bytes.clone(), channel_bytes.clone(), 7)
+				.attempts
+				.len(),
+			1
+		);
+		Ibc::on_initialize(1 + retention);
+		assert!(Pallet::<Test>::forward_status(port_bytes.clone(), channel_bytes.clone(), 7)
+			.attempts
+			.is_empty());
+		let ack = Acks::<Test>::get(Pallet::<Test>::ack_key(channel_bytes, port_bytes, 7)).unwrap();
+		let ack = serde_json::from_slice::<Ics20Acknowledgement>(&ack).unwrap();
+		assert!(!ack.is_successful());
+		// The tokens released on receive are back in escrow
+		let escrow_balance = <<Test as Config>::NativeCurrency as Currency<
+			<Test as frame_system::Config>::AccountId,
+		>>::free_balance(&channel_escrow_address);
+		assert_eq!(escrow_balance, balance);
+	})
+}
+
+#[test]
+fn memo_forward_retries_are_bounded() {
+	let mut ext = new_test_ext();
+	ext.execute_with(|| {
+		let pair = sp_core::sr25519::Pair::from_seed(b"12345678901234567890123456789012");
+		let ss58_address =
+			ibc_primitives::runtime_interface::account_id_to_ss58(pair.public().0, 49);
+		frame_system::Pallet::<Test>::set_block_number(1u32);
+		setup_client_and_consensus_state(PortId::transfer());
+
+		let channel_id = ChannelId::new(0);
+		let (port_bytes, channel_bytes) =
+			(PortId::transfer().as_bytes().to_vec(), channel_id.to_string().into_bytes());
+		let retries = <Test as Config>::MaxForwardAttempts::get();
+		let memo = format!(
+			r#"{{"forward":{{"receiver":"bob","port":"transfer","channel":"channel-0","timeout":600000000000,"retries":{retries}}}}}"#
+		);
+		let packet_data = PacketData {
+			token: Coin {
+				denom: PrefixedDenom::from_str("UNIT").unwrap(),
+				amount: ibc::applications::transfer::Amount::from_str(&format!(
+					"{:?}",
+					1000 * MILLIS
+				))
+				.unwrap(),
+			},
+			sender: Signer::from_str("alice").unwrap(),
+			receiver: Signer::from_str(&ss58_address).unwrap(),
+			memo: memo.clone(),
+		};
+		let time_now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_nanos();
+		let msg = MsgRecvPacket {
+			packet: Packet {
+				sequence: 1u64.into(),
+				source_port: PortId::transfer(),
+				source_channel: ChannelId::new(1),
+				destination_port: PortId::transfer(),
+				destination_channel: channel_id,
+				data: serde_json::to_vec(&packet_data).unwrap(),
+				timeout_height: Height::new(2000, 5),
+				timeout_timestamp: ibc::timestamp::Timestamp::from_nanoseconds(
+					time_now as u64 + 10000000,
+				)
+				.unwrap(),
+			},
+			proofs: Proofs::new(
+				vec![0u8; 32].try_into().unwrap(),
+				None,
+				None,
+				None,
+				Height::new(0, 1),
+			)
+			.unwrap(),
+			signer: Signer::from_str(MODULE_ID).unwrap(),
+		};
+		let msg = Any { type_url: msg.type_url(), value: msg.encode_vec().unwrap() };
+		Ibc::deliver(RuntimeOrigin::signed(AccountId32::new([0; 32])), vec![msg]).unwrap();
+
+		// The tokens stay with the receiver and nothing is forwarded
+		let ack = Acks::<Test>::get(Pallet::<Test>::ack_key(
+			channel_bytes.clone(),
+			port_bytes.clone(),
+			1,
+		))
+		.unwrap();
+		assert!(serde_json::from_slice::<Ics20Acknowledgement>(&ack).unwrap().is_successful());
+		assert!(!InFlightForwards::<Test>::contains_key((port_bytes.clone(), channel_bytes.clone()), 1));
+		assert!(Pallet::<Test>::forward_status(port_bytes, channel_bytes, 1).attempts.is_empty());
+		assert!(System::events().iter().any(|record| matches!(
+			&record.event,
+			RuntimeEvent::Ibc(Event::<Test>::ExecuteMemoIbcTokenTransferFailedWithReason {
+				memo: failed_memo,
+				reason: MemoFailureReason::TooManyRetries,
+				..
+			}) if failed_memo == &memo
+		)));
+	})
+}
+
+#[test]
+fn failed_memo_forward_reverts_minted_vouchers_and_service_fee() {
+	let mut ext = new_test_ext();
+	ext.execute_with(|| {
+		let pair = sp_core::sr25519::Pair::from_seed(b"12345678901234567890123456789012");
+		let receiver = AccountId32::new(pair.public().0);
+		let ss58_address =
+			ibc_primitives::runtime_interface::account_id_to_ss58(pair.public().0, 49);
+		frame_system::Pallet::<Test>::set_block_number(1u32);
+		setup_client_and_consensus_state(PortId::transfer());
+
+		let channel_id = ChannelId::new(0);
+		let (port_bytes, channel_bytes) =
+			(PortId::transfer().as_bytes().to_vec(), channel_id.to_string().into_bytes());
+		// Tokens native to the counterparty are minted as vouchers when received
+		let asset_id =
+			<<Test as Config>::IbcDenomToAssetIdConversion as DenomToAssetId<Test>>::from_denom_to_asset_id(
+				"transfer/channel-0/UNIT",
+			)
+			.unwrap();
+		let fee_account = <Test as crate::Config>::FeeAccount::get().into_account();
+
+		let amt = 1000 * MILLIS;
+		let memo = r#"{"forward":{"receiver":"bob","port":"transfer","channel":"channel-0","timeout":600000000000,"retries":0}}"#;
+		let packet_data = PacketData {
+			token: Coin {
+				denom: PrefixedDenom::from_str("UNIT").unwrap(),
+				amount: ibc::applications::transfer::Amount::from_str(&format!("{:?}", amt))
+					.unwrap(),
+			},
+			sender: Signer::from_str("alice").unwrap(),
+			receiver: Signer::from_str(&ss58_address).unwrap(),
+			memo: memo.to_string(),
+		};
+		let time_now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_nanos();
+		let packet = Packet {
+			sequence: 1u64.into(),
+			source_port: PortId::transfer(),
+			source_channel: ChannelId::new(1),
+			destination_port: PortId::transfer(),
+			destination_channel: channel_id,
+			data: serde_json::to_vec(&packet_data).unwrap(),
+			timeout_height: Height::new(2000, 5),
+			timeout_timestamp: ibc::timestamp::Timestamp::from_nanoseconds(
+				time_now as u64 + 10000000,
+			)
+			.unwrap(),
+		};
+		let msg = MsgRecvPacket {
+			packet: packet.clone(),
+			proofs: Proofs::new(
+				vec![0u8; 32].try_into().unwrap(),
+				None,
+				None,
+				None,
+				Height::new(0, 1),
+			)
+			.unwrap(),
+			signer: Signer::from_str(MODULE_ID).unwrap(),
+		};
+		let msg = Any { type_url: msg.type_url(), value: msg.encode_vec().unwrap() };
+		Ibc::deliver(RuntimeOrigin::signed(AccountId32::new([0; 32])), vec![msg]).unwrap();
+
+		// The service fee was charged on receipt and the rest forwarded
+		let fee = <Test as crate::ics20_fee::Config>::ServiceChargeIn::get() * amt;
+		let forward =
+			InFlightForwards::<Test>::get((port_bytes.clone(), channel_bytes.clone()), 1).unwrap();
+		assert_eq!(forward.amount, amt - fee);
+		assert_eq!(forward.received_amount, amt);
+		assert_eq!(<Assets as Inspect<AccountId>>::balance(asset_id, &fee_account), fee);
+
+		let forwarded =
+			Ibc::get_send_packet_info(channel_bytes.clone(), port_bytes.clone(), vec![1])
+				.unwrap()
+				.get(0)
+				.unwrap()
+				.clone();
+		let msg = MsgAcknowledgement {
+			packet: Packet::from(forwarded),
+			acknowledgement: Acknowledgement::from_bytes(
+				Ics20Acknowledgement::Error("forward failed".to_string()).to_string().into_bytes(),
+			),
+			proofs: Proofs::new(
+				vec![0u8; 32].try_into().unwrap(),
+				None,
+				None,
+				None,
+				Height::new(0, 1),
+			)
+			.unwrap(),
+			signer: Signer::from_str(MODULE_ID).unwrap(),
+		};
+		let msg = Any { type_url: msg.type_url(), value: msg.encode_vec().unwrap() };
+		Ibc::deliver(RuntimeOrigin::signed(AccountId32::new([0; 32])), vec![msg]).unwrap();
+
+		let ack = Acks::<Test>::get(Pallet::<Test>::ack_key(channel_bytes, port_bytes, 1)).unwrap();
+		let ack = serde_json::from_slice::<Ics20Acknowledgement>(&ack).unwrap();
+		assert!(!ack.is_successful());
+		// The source chain refunds the full amount, so every voucher minted for the transfer,
+		// including the service fee, is burnt
+		assert_eq!(<Assets as Inspect<AccountId>>::total_issuance(asset_id), 0);
+		assert_eq!(<Assets as Inspect<AccountId>>::balance(asset_id, &receiver), 0);
+		assert_eq!(<Assets as Inspect<AccountId>>::balance(asset_id, &fee_account), 0);
+	})
+}
+
+#[test]
+fn unsent_memo_forward_rejects_transfer() {
+	let mut ext = new_test_ext();
+	ext.execute_with(|| {
+		let pair = sp_core::sr25519::Pair::from_seed(b"12345678901234567890123456789012");
+		let receiver = AccountId32::new(pair.public().0);
+		let ss58_address =
+			ibc_primitives::runtime_interface::account_id_to_ss58(pair.public().0, 49);
+		frame_system::Pallet::<Test>::set_block_number(1u32);
+		setup_client_and_consensus_state(PortId::transfer());
+
+		let channel_id = ChannelId::new(0);
+		let (port_bytes, channel_bytes) =
+			(PortId::transfer().as_bytes().to_vec(), channel_id.to_string().into_bytes());
+		let asset_id =
+			<<Test as Config>::IbcDenomToAssetIdConversion as DenomToAssetId<Test>>::from_denom_to_asset_id(
+				"transfer/channel-0/UNIT",
+			)
+			.unwrap();
+
+		// There is no channel-7 to forward the tokens over
+		let memo = r#"{"forward":{"receiver":"bob","port":"transfer","channel":"channel-7","timeout":600000000000,"retries":0}}"#;
+		let packet_data = PacketData {
+			token: Coin {
+				denom: PrefixedDenom::from_str("UNIT").unwrap(),
+				amount: ibc::applications::transfer::Amount::from_str(&format!(
+					"{:?}",
+					1000 * MILLIS
+				))
+				.unwrap(),
+			},
+			sender: Signer::from_str("alice").unwrap(),
+			receiver: Signer::from_str(&ss58_address).unwrap(),
+			memo: memo.to_string(),
+		};
+		let time_now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_nanos();
+		let msg = MsgRecvPacket {
+			packet: Packet {
+				sequence: 1u64.into(),
+				source_port: PortId::transfer(),
+				source_channel: ChannelId::new(1),
+				destination_port: PortId::transfer(),
+				destination_channel: channel_id,
+				data: serde_json::to_vec(&packet_data).unwrap(),
+				timeout_height: Height::new(2000, 5),
+				timeout_timestamp: ibc::timestamp::Timestamp::from_nanoseconds(
+					time_now as u64 + 10000000,
+				)
+				.unwrap(),
+			},
+			proofs: Proofs::new(
+				vec![0u8; 32].try_into().unwrap(),
+				None,
+				None,
+				None,
+				Height::new(0, 1),
+			)
+			.unwrap(),
+			signer: Signer::from_str(MODULE_ID).unwrap(),
+		};
+		let msg = Any { type_url: msg.type_url(), value: msg.encode_vec().unwrap() };
+		Ibc::deliver(RuntimeOrigin::signed(AccountId32::new([0; 32])), vec![msg]).unwrap();
+
+		// The transfer is acknowledged with an error right away so the sender is refunded
+		let ack = Acks::<Test>::get(Pallet::<Test>::ack_key(
+			channel_bytes.clone(),
+			port_bytes.clone(),
+			1,
+		))
+		.unwrap();
+		assert!(!serde_json::from_slice::<Ics20Acknowledgement>(&ack).unwrap().is_successful());
+		assert!(Pallet::<Test>::forward_status(port_bytes, channel_bytes, 1).attempts.is_empty());
+		assert_eq!(<Assets as Inspect<AccountId>>::total_issuance(asset_id), 0);
+		assert_eq!(<Assets as Inspect<AccountId>>::balance(asset_id, &receiver), 0);
+		assert!(System::events().iter().any(|record| matches!(
+			&record.event,
+			RuntimeEvent::Ibc(Event::<Test>::ExecuteMemoIbcTokenTransferFailedWithReason {
+				reason: MemoFailureReason::IbcSendFailure,
+				..
+			})
+		)));
+	})
+}
+
+#[test]
+fn transfer_recv_weight_covers_memo_calls() {
+	new_test_ext().execute_with(|| {
+		let packet = Packet { destination_port: PortId::transfer(), ..Default::default() };
+		let weight = crate::weight::WeightRouter::<Test>::get_weight(PortId::transfer().as_str())
+			.unwrap()
+			.on_recv_packet(&packet);
+		assert!(weight.all_gte(MaxHooksCallWeight::get()));
+	})
+}
+
+#[test]
+fn memo_runtime_call_is_dispatched_from_hooks_account() {
+	let mut ext = new_test_ext();
+	ext.execute_with(|| {
+		frame_system::Pallet::<Test>::set_block_number(1u32);
+		setup_client_and_consensus_state(PortId::transfer());
+		let channel_id = ChannelId::new(0);
+		let (port_bytes, channel_bytes) =
+			(PortId::transfer().as_bytes().to_vec(), channel_id.to_string().into_bytes());
+		let balance = 100000 * MILLIS;
+		let channel_escrow_address =
+			get_channel_escrow_address(&PortId::transfer(), channel_id).unwrap();
+		let channel_escrow_address =
+			<Test as Config>::AccountIdConversion::try_from(channel_escrow_address)
+				.map_err(|_| ())
+				.unwrap()
+				.into_account();
+		let _ = <<Test as Config>::NativeCurrency as Currency<
+			<Test as frame_system::Config>::AccountId,
+		>>::deposit_creating(&channel_escrow_address, balance);
+
+		let hooks_account = Ics20Hooks::hooks_account(&channel_id, "alice");
+		let receiver =
+			ibc_primitives::runtime_interface::account_id_to_ss58(hooks_account.clone().into(), 49);
+		let recv = |sequence: u64, call: RuntimeCall| {
+			let packet_data = PacketData {
+				token: Coin {
+					denom: PrefixedDenom::from_str("transfer/channel-1/PICA").unwrap(),
+					amount: ibc::applications::transfer::Amount::from_str(&format!(
+						"{:?}",
+						1000 * MILLIS
+					))
+					.unwrap(),
+				},
+				sender: Signer::from_str("alice").unwrap(),
+				receiver: Signer::from_str(&receiver).unwrap(),
+				memo: format!(r#"{{"call":"0x{}"}}"#, hex::encode(codec::Encode::encode(&call))),
+			};
+			let time_now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_nanos();
+			let msg = MsgRecvPacket {
+				packet: Packet {
+					sequence: sequence.into(),
+					source_port: PortId::transfer(),
+					source_channel: ChannelId::new(1),
+					destination_port: PortId::transfer(),
+					destination_channel: channel_id,
+					data: serde_json::to_vec(&packet_data).unwrap(),
+					timeout_height: Height::new(2000, 5),
+					timeout_timestamp: ibc::timestamp::Timestamp::from_nanoseconds(
+						time_now as u64 + 10000000,
+					)
+					.unwrap(),
+				},
+				proofs: Proofs::new(
+					vec![0u8; 32].try_into().unwrap(),
+					None,
+					None,
+					None,
+					Height::new(0, 1),
+				)
+				.unwrap(),
+				signer: Signer::from_str(MODULE_ID).unwrap(),
+			};
+			let msg = Any { type_url: msg.type_url(), value: msg.encode_vec().unwrap() };
+			Ibc::deliver(RuntimeOrigin::signed(AccountId32::new([0; 32])), vec![msg]).unwrap();
+			let ack = Acks::<Test>::get(Pallet::<Test>::ack_key(
+				channel_bytes.clone(),
+				port_bytes.clone(),
+				sequence,
+			))
+			.unwrap();
+			serde_json::from_slice::<Ics20Acknowledgement>(&ack).unwrap()
+		};
+
+		let ack = recv(
+			1,
+			RuntimeCall::System(frame_system::Call::remark_with_event { remark: b"ibc".to_vec() }),
+		);
+		assert!(ack.is_successful());
+		assert!(System::events().iter().any(|record| matches!(
+			&record.event,
+			RuntimeEvent::System(frame_system::Event::Remarked { sender, .. }) if sender == &hooks_account
+		)));
+
+		// Calls rejected by the filter are refunded to the sender, along with the service fee
+		// charged on receipt
+		let free_balance = |account: &AccountId| {
+			<<Test as Config>::NativeCurrency as Currency<
+				<Test as frame_system::Config>::AccountId,
+			>>::free_balance(account)
+		};
+		let fee_account = <Test as crate::Config>::FeeAccount::get().into_account();
+		let (escrow_balance, fee_balance, hooks_balance) = (
+			free_balance(&channel_escrow_address),
+			free_balance(&fee_account),
+			free_balance(&hooks_account),
+		);
+		let ack =
+			recv(2, RuntimeCall::System(frame_system::Call::remark { remark: b"ibc".to_vec() }));
+		assert!(!ack.is_successful());
+		assert_eq!(free_balance(&channel_escrow_address), escrow_balance);
+		assert_eq!(free_balance(&fee_account), fee_balance);
+		assert_eq!(free_balance(&hooks_account), hooks_balance);
+	})
+}
+
+#[test]
+fn on_deliver_ics20_recv_packet_transfered_amount_less_then_flat_fee() {
+	let mut ext = new_test_ext();
+	ext.execute_with(|| {
+		// Create  a new account
+		let pair = sp_core::sr25519::Pair::from_seed(b"12345678901234567890123456789012");
+		let ss58_address =
+			ibc_primitives::runtime_interface::account_id_to_ss58(pair.public().0, 49);
+		frame_system::Pallet::<Test>::set_block_number(1u32);
+		let asset_id =
+			<<Test as Config>::IbcDenomToAssetIdConversion as DenomToAssetId<Test>>::from_denom_to_asset_id(
+				"PICAFLATFEE",
+			)
+			.unwrap();
+		setup_client_and_consensus_state(PortId::transfer());
+
+		let channel_id = ChannelId::new(0);
+		let balance = 100000 * MILLIS;
+
+		// We are simulating a transfer back to the source chain
+
+		let denom = "transfer/channel-1/PICAFLATFEE";
+		let channel_escrow_address =
+			get_channel_escrow_address(&PortId::transfer(), channel_id).unwrap();
+		let channel_escrow_address =
+			<Test as Config>::AccountIdConversion::try_from(channel_escrow_address)
+				.map_err(|_| ())
+				.unwrap();
+		let channel_escrow_address = channel_escrow_address.into_account();
+
+		// Endow escrow address with tokens
+		<<Test as Config>::Fungibles as Mutate<
+			<Test as frame_system::Config>::AccountId,
+		>>::mint_into(asset_id, &channel_escrow_address, balance)
+		.unwrap();
+
+		let prefixed_denom = PrefixedDenom::from_str(denom).unwrap();
+		let usdt_fee_amount = 1000 * MILLIS;
+		let fee_asset_id = <Test as crate::Config>::FlatFeeAssetId::get();
+		let amt = <Test as crate::Config>::FlatFeeConverter::get_flat_fee(
+			asset_id,
+			fee_asset_id,
+			usdt_fee_amount,
+		)
+		.unwrap_or_default();
+		println!("Transferred Amount {amt}");
+		let coin = Coin {
+			denom: prefixed_denom,
+			amount: ibc::applications::transfer::Amount::from_str(&format!("{amt:?}")).unwrap(),
+		};
+		let packet_data = PacketData {
+			token: coin,
+			sender: Signer::from_str("alice").unwrap(),
+			receiver: Signer::from_str(&ss58_address).unwrap(),
+			memo: "".to_string(),
+		};
+
+		let time_now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_nanos();
+		let data = serde_json::to_vec(&packet_data).unwrap();
+		let packet = Packet {
+			sequence: 1u64.into(),
+			source_port: PortId::transfer(),
+			source_channel: ChannelId::new(1),
+			destination_port: PortId::transfer(),
+			destination_channel: ChannelId::new(0),
+			data,
+			timeout_height: Height::new(2000, 5),
+			timeout_timestamp: ibc::timestamp::Timestamp::from_nanoseconds(
+				time_now as u64 + 10000000,
+			)
+			.unwrap(),
+		};
+
+		let msg = MsgRecvPacket {
+			packet,
+			proofs: Proofs::new(
+				vec![0u8; 32].try_into().unwrap(),
+				None,
+				None,
+				None,
+				Height::new(0, 1),
+			)
+			.unwrap(),
+			signer: Signer::from_str(MODULE_ID).unwrap(),
+		};
+
+		let msg = Any { type_url: msg.type_url(), value: msg.encode_vec().unwrap() };
+
+		let account_data = Assets::balance(asset_id, AccountId32::new(pair.public().0));
+		// Assert account balance before transfer
+		assert_eq!(account_data, 0);
+		Ibc::deliver(RuntimeOrigin::signed(AccountId32::new([0; 32])), vec![msg]).unwrap();
+
+		let balance =
+			<Assets as Inspect<AccountId>>::balance(asset_id, &AccountId32::new(pair.public().0));
+		let pallet_balance = <Assets as Inspect<AccountId>>::balance(
+			asset_id,
+			&<Test as crate::Config>::FeeAccount::get().into_account(),
+		);
+		let fee = <Test as crate::Config>::FlatFeeConverter::get_flat_fee(
+			asset_id,
+			fee_asset_id,
+			usdt_fee_amount,
+		)
+		.unwrap_or_default();
+		let actual_fee = fee.min(amt);
+		assert_eq!(balance, 0);
+		assert_eq!(balance, amt - actual_fee);
+		assert_eq!(pallet_balance, actual_fee);
+	})
+}
+
+#[test]
+fn on_deliver_ics20_recv_packet_should_not_double_spend() {
+	let mut ext = new_test_ext();
+	ext.execute_with(|| {
+		// Create  a new account
+		let pair = sp_core::sr25519::Pair::from_seed(b"12345678901234567890123456789012");
+		let ss58_address =
+			ibc_primitives::runtime_interface::account_id_to_ss58(pair.public().0, 49);
+		frame_system::Pallet::<Test>::set_block_number(1u32);
+		setup_client_and_consensus_state(PortId::transfer());
+
+		let channel_id = ChannelId::new(0);
+		let balance = 100000 * MILLIS;
+
+		// We are simulating a transfer back to the source chain
+
+		let denom = "transfer/channel-1/PICA";
+		let channel_escrow_address =
+			get_channel_escrow_address(&PortId::transfer(), channel_id).unwrap();
+		let channel_escrow_address =
+			<Test as Config>::AccountIdConversion::try_from(channel_escrow_address)
+				.map_err(|_| ())
+				.unwrap();
+		let channel_escrow_address = channel_escrow_address.into_account();
+
+		// Endow escrow address with tokens
+		let _ = <<Test as Config>::NativeCurrency as Currency<
+			<Test as frame_system::Config>::AccountId,
+		>>::deposit_creating(&channel_escrow_address, balance);
+
+		let prefixed_denom = PrefixedDenom::from_str(denom).unwrap();
+		let amt = MILLIS / 100;
+		println!("Transferred Amount {amt}");
+		let coin = Coin {
+			denom: prefixed_denom,
+			amount: ibc::applications::transfer::Amount::from_str(&format!("{amt:?}")).unwrap(),
+		};
 		let packet_data = PacketData {
 			token: coin,
 			sender: Signer::from_str("alice").unwrap(),
@@ -1099,6 +2460,56 @@ fn should_fetch_recv_packet_with_acknowledgement() {
 	})
 }
 
+#[test]
+fn should_paginate_packet_commitments() {
+	new_test_ext().execute_with(|| {
+		let port_id = PortId::transfer();
+		let channel_id = ChannelId::new(0);
+		for seq in 1..=5u64 {
+			PacketCommitment::<Test>::insert(
+				(port_id.clone(), channel_id, seq.into()),
+				vec![seq as u8; 32].into(),
+			);
+		}
+		// Commitments of other channels must not leak into the pages
+		PacketCommitment::<Test>::insert(
+			(port_id.clone(), ChannelId::new(1), 1u64.into()),
+			vec![0; 32].into(),
+		);
+
+		let query = |pagination| {
+			Pallet::<Test>::packet_commitments_paginated(
+				channel_id.to_string().into_bytes(),
+				port_id.as_bytes().to_vec(),
+				pagination,
+			)
+			.unwrap()
+		};
+
+		let (first, first_page) = query(PageRequest { key: None, limit: 2 });
+		assert_eq!(first.commitments.len(), 2);
+
+		let mut sequences = first.commitments.iter().map(|c| c.sequence).collect::<Vec<_>>();
+		let mut next_key = first_page.next_key;
+		while let Some(key) = next_key {
+			let (page, pagination) = query(PageRequest { key: Some(key), limit: 2 });
+			assert!(page.commitments.len() <= 2);
+			sequences.extend(page.commitments.iter().map(|c| c.sequence));
+			next_key = pagination.next_key;
+		}
+		sequences.sort();
+		assert_eq!(sequences, vec![1, 2, 3, 4, 5]);
+
+		// The unpaginated query still returns every commitment of the channel at once
+		let all = Pallet::<Test>::packet_commitments(
+			channel_id.to_string().into_bytes(),
+			port_id.as_bytes().to_vec(),
+		)
+		.unwrap();
+		assert_eq!(all.commitments.len(), 5);
+	})
+}
+
 #[test]
 fn should_cleanup_offchain_packets_correctly() {
 	let mut ext = new_test_ext();
@@ -1310,3 +2721,288 @@ fn test_next_and_previous_consensus_state_for_other_client_types() {
 		assert!(ctx.next_consensus_state(&client_id, Height::new(0, 400)).unwrap().is_some());
 	})
 }
+
+/// A wasm light client that writes `test => value` to its store on `sudo` and reports every
+/// client message as valid on `query`.
+const WASM_CLIENT: &str = r#"
+(module
+	(import "env" "db_write" (func $db_write (param i32 i32)))
+	(memory (export "memory") 1)
+	;; regions of the key, the value and the responses of sudo and query
+	(data (i32.const 0) "\00\01\00\00\04\00\00\00\04\00\00\00")
+	(data (i32.const 12) "\10\01\00\00\05\00\00\00\05\00\00\00")
+	(data (i32.const 24) "\20\01\00\00\3e\00\00\00\3e\00\00\00")
+	(data (i32.const 36) "\00\02\00\00\21\00\00\00\21\00\00\00")
+	(data (i32.const 256) "test")
+	(data (i32.const 272) "value")
+	(data (i32.const 288) "{\"ok\":{\"messages\":[],\"attributes\":[],\"events\":[],\"data\":null}}")
+	(data (i32.const 512) "{\"ok\":\"eyJpc192YWxpZCI6dHJ1ZX0=\"}")
+	(global $heap (mut i32) (i32.const 1024))
+	(func (export "allocate") (param $size i32) (result i32)
+		(local $region i32)
+		(local.set $region (global.get $heap))
+		(i32.store (local.get $region) (i32.add (local.get $region) (i32.const 12)))
+		(i32.store offset=4 (local.get $region) (local.get $size))
+		(i32.store offset=8 (local.get $region) (i32.const 0))
+		(global.set $heap (i32.add (i32.add (local.get $region) (i32.const 12)) (local.get $size)))
+		(local.get $region))
+	(func (export "deallocate") (param i32))
+	(func (export "interface_version_8"))
+	(func (export "sudo") (param i32 i32) (result i32)
+		(call $db_write (i32.const 0) (i32.const 12))
+		(i32.const 24))
+	(func (export "query") (param i32 i32) (result i32)
+		(i32.const 36)))
+"#;
+
+#[test]
+fn channel_upgrades_are_delivered_by_the_admin_origin() {
+	use ibc::core::ics04_channel::msgs::{chan_open_init, chan_upgrade_init};
+	new_test_ext().execute_with(|| {
+		let message = |type_url: &str| Any { type_url: type_url.to_string(), value: vec![] };
+
+		assert_noop!(
+			Ibc::deliver_channel_upgrade(
+				RuntimeOrigin::signed(AccountId32::new([0; 32])),
+				vec![message(chan_upgrade_init::TYPE_URL)]
+			),
+			sp_runtime::DispatchError::BadOrigin
+		);
+		assert_noop!(
+			Ibc::deliver_channel_upgrade(
+				RuntimeOrigin::root(),
+				vec![message(chan_upgrade_init::TYPE_URL), message(chan_open_init::TYPE_URL)]
+			),
+			crate::Error::<Test>::InvalidMessageType
+		);
+	})
+}
+
+fn push_wasm_code_message(code: Vec<u8>) -> Any {
+	use ics08_wasm::msg::{MsgPushNewWasmCode, WASM_PUSH_WASM_CODE_TYPE_URL};
+	let msg = MsgPushNewWasmCode { code, signer: Signer::from_str(MODULE_ID).unwrap() };
+	Any { type_url: WASM_PUSH_WASM_CODE_TYPE_URL.to_string(), value: msg.encode_vec().unwrap() }
+}
+
+#[test]
+fn should_push_valid_wasm_code_once() {
+	new_test_ext().execute_with(|| {
+		let code = wat::parse_str(WASM_CLIENT).unwrap();
+		let checksum = sp_io::hashing::sha2_256(&code).to_vec();
+
+		assert_noop!(
+			Ibc::push_wasm_code(
+				RuntimeOrigin::signed(AccountId32::new([0; 32])),
+				push_wasm_code_message(code.clone())
+			),
+			sp_runtime::DispatchError::BadOrigin
+		);
+		assert_noop!(
+			Ibc::push_wasm_code(
+				RuntimeOrigin::root(),
+				push_wasm_code_message(vec![0, 97, 115, 109])
+			),
+			crate::Error::<Test>::InvalidWasmCode
+		);
+
+		assert_ok!(Ibc::push_wasm_code(
+			RuntimeOrigin::root(),
+			push_wasm_code_message(code.clone())
+		));
+		assert_eq!(
+			WasmCodes::<Test>::get(&checksum).map(|code| code.into_inner()),
+			Some(code.clone())
+		);
+
+		assert_noop!(
+			Ibc::push_wasm_code(RuntimeOrigin::root(), push_wasm_code_message(code)),
+			crate::Error::<Test>::WasmCodeExists
+		);
+	})
+}
+
+#[test]
+fn should_reject_wasm_code_with_floats_or_too_much_memory() {
+	use crate::wasm::vm::MAX_MEMORY_PAGES;
+	new_test_ext().execute_with(|| {
+		let memory = r#"(memory (export "memory") 1)"#;
+		let codes = [
+			WASM_CLIENT.replace(
+				memory,
+				&format!(r#"(memory (export "memory") 1 {})"#, MAX_MEMORY_PAGES + 1),
+			),
+			WASM_CLIENT.replace(
+				memory,
+				&format!(r#"(memory (export "memory") {})"#, MAX_MEMORY_PAGES + 1),
+			),
+			WASM_CLIENT.replace(
+				"(func (export \"deallocate\") (param i32))",
+				"(func (export \"deallocate\") (param i32) (drop (f32.const 1)))",
+			),
+		];
+		for code in codes {
+			assert_noop!(
+				Ibc::push_wasm_code(
+					RuntimeOrigin::root(),
+					push_wasm_code_message(wat::parse_str(code).unwrap())
+				),
+				crate::Error::<Test>::InvalidWasmCode
+			);
+		}
+
+		let max_memory = format!(r#"(memory (export "memory") 1 {MAX_MEMORY_PAGES})"#);
+		assert_ok!(Ibc::push_wasm_code(
+			RuntimeOrigin::root(),
+			push_wasm_code_message(
+				wat::parse_str(WASM_CLIENT.replace(memory, &max_memory)).unwrap()
+			)
+		));
+	})
+}
+
+#[test]
+fn wasm_client_memory_cannot_grow_past_the_limit() {
+	use crate::wasm::{vm::MAX_MEMORY_PAGES, WasmContracts};
+	use ics08_wasm::executor::{SudoMsg, UpdateStateMsg, WasmExecutor};
+
+	new_test_ext().execute_with(|| {
+		// The contract traps when its memory can't grow by `pages`
+		let growing_client = |pages: u32| {
+			let sudo = format!(
+				"(if (i32.eq (memory.grow (i32.const {pages})) (i32.const -1)) (then unreachable))"
+			);
+			let code = wat::parse_str(
+				WASM_CLIENT.replace("(call $db_write (i32.const 0) (i32.const 12))", &sudo),
+			)
+			.unwrap();
+			let checksum = sp_io::hashing::sha2_256(&code).to_vec();
+			assert_ok!(Ibc::push_wasm_code(RuntimeOrigin::root(), push_wasm_code_message(code)));
+			checksum
+		};
+		let client_id = ClientId::from_str("08-wasm-0").unwrap();
+		let msg = SudoMsg::UpdateState(UpdateStateMsg { client_message: vec![1] });
+		let mut contracts = WasmContracts::<Test>::default();
+
+		// The memory of the contract starts with one page
+		let checksum = growing_client(MAX_MEMORY_PAGES - 1);
+		assert!(contracts.sudo(&checksum, &client_id, &msg).is_ok());
+		let checksum = growing_client(MAX_MEMORY_PAGES);
+		assert!(contracts.sudo(&checksum, &client_id, &msg).is_err());
+	})
+}
+
+#[test]
+fn wasm_client_runs_against_its_client_store() {
+	use crate::wasm::{vm::Storage, ClientStore, WasmContracts};
+	use ics08_wasm::executor::{
+		ContractResult, QueryMsg, QueryResponse, SudoMsg, UpdateStateMsg, VerifyClientMessageMsg,
+		WasmExecutor,
+	};
+
+	new_test_ext().execute_with(|| {
+		let code = wat::parse_str(WASM_CLIENT).unwrap();
+		let checksum = sp_io::hashing::sha2_256(&code).to_vec();
+		assert_ok!(Ibc::push_wasm_code(RuntimeOrigin::root(), push_wasm_code_message(code)));
+
+		let client_id = ClientId::from_str("08-wasm-0").unwrap();
+		let mut contracts = WasmContracts::<Test>::default();
+		assert!(contracts.has_code(&checksum));
+
+		let response = contracts.query(
+			&checksum,
+			&client_id,
+			&QueryMsg::VerifyClientMessage(VerifyClientMessageMsg { client_message: vec![1] }),
+		);
+		assert_eq!(response.unwrap(), QueryResponse { is_valid: true, found_misbehaviour: None });
+
+		let result = contracts.sudo(
+			&checksum,
+			&client_id,
+			&SudoMsg::UpdateState(UpdateStateMsg { client_message: vec![1] }),
+		);
+		assert_eq!(result.unwrap(), ContractResult::default());
+		assert_eq!(ClientStore::<Test>::new(&client_id).get(b"test"), Some(b"value".to_vec()));
+		// The store of the contract is the store of its client only
+		let other_client_id = ClientId::from_str("08-wasm-1").unwrap();
+		assert_eq!(ClientStore::<Test>::new(&other_client_id).get(b"test"), None);
+
+		// The contract is compiled once, later calls don't load its code
+		let gas_used = contracts.gas_used();
+		assert!(gas_used > 0);
+		WasmCodes::<Test>::remove(&checksum);
+		let response = contracts.query(
+			&checksum,
+			&client_id,
+			&QueryMsg::VerifyClientMessage(VerifyClientMessageMsg { client_message: vec![1] }),
+		);
+		assert!(response.is_ok());
+		assert!(contracts.gas_used() > gas_used);
+	})
+}
+
+/// A wasm light client that writes the value of the last entry of its store to `last` and the
+/// value of the first entry to `first` on `sudo`.
+const WASM_ITERATING_CLIENT: &str = r#"
+(module
+	(import "env" "db_write" (func $db_write (param i32 i32)))
+	(import "env" "db_scan" (func $db_scan (param i32 i32 i32) (result i32)))
+	(import "env" "db_next_value" (func $db_next_value (param i32) (result i32)))
+	(memory (export "memory") 1)
+	;; regions of the keys and of the response of sudo
+	(data (i32.const 0) "\00\01\00\00\04\00\00\00\04\00\00\00")
+	(data (i32.const 12) "\10\01\00\00\05\00\00\00\05\00\00\00")
+	(data (i32.const 24) "\20\01\00\00\3e\00\00\00\3e\00\00\00")
+	(data (i32.const 256) "last")
+	(data (i32.const 272) "first")
+	(data (i32.const 288) "{\"ok\":{\"messages\":[],\"attributes\":[],\"events\":[],\"data\":null}}")
+	(global $heap (mut i32) (i32.const 1024))
+	(func (export "allocate") (param $size i32) (result i32)
+		(local $region i32)
+		(local.set $region (global.get $heap))
+		(i32.store (local.get $region) (i32.add (local.get $region) (i32.const 12)))
+		(i32.store offset=4 (local.get $region) (local.get $size))
+		(i32.store offset=8 (local.get $region) (i32.const 0))
+		(global.set $heap (i32.add (i32.add (local.get $region) (i32.const 12)) (local.get $size)))
+		(local.get $region))
+	(func (export "deallocate") (param i32))
+	(func (export "interface_version_8"))
+	(func (export "sudo") (param i32 i32) (result i32)
+		(call $db_write
+			(i32.const 0)
+			(call $db_next_value (call $db_scan (i32.const 0) (i32.const 0) (i32.const 2))))
+		(call $db_write
+			(i32.const 12)
+			(call $db_next_value (call $db_scan (i32.const 0) (i32.const 0) (i32.const 1))))
+		(i32.const 24))
+	(func (export "query") (param i32 i32) (result i32)
+		(i32.const 24)))
+"#;
+
+#[test]
+fn wasm_client_iterates_over_its_client_store() {
+	use crate::wasm::{vm::Storage, ClientStore, WasmContracts};
+	use ics08_wasm::executor::{SudoMsg, UpdateStateMsg, WasmExecutor};
+
+	new_test_ext().execute_with(|| {
+		let code = wat::parse_str(WASM_ITERATING_CLIENT).unwrap();
+		let checksum = sp_io::hashing::sha2_256(&code).to_vec();
+		assert_ok!(Ibc::push_wasm_code(RuntimeOrigin::root(), push_wasm_code_message(code)));
+
+		let client_id = ClientId::from_str("08-wasm-0").unwrap();
+		let mut store = ClientStore::<Test>::new(&client_id);
+		store.set(b"a", b"1");
+		store.set(b"b", b"2");
+		// Entries of other clients are out of the range of the iterators
+		ClientStore::<Test>::new(&ClientId::from_str("08-wasm-1").unwrap()).set(b"c", b"3");
+
+		let mut contracts = WasmContracts::<Test>::default();
+		let result = contracts.sudo(
+			&checksum,
+			&client_id,
+			&SudoMsg::UpdateState(UpdateStateMsg { client_message: vec![1] }),
+		);
+		assert!(result.is_ok());
+		assert_eq!(store.get(b"last"), Some(b"2".to_vec()));
+		assert_eq!(store.get(b"first"), Some(b"1".to_vec()));
+	})
+}
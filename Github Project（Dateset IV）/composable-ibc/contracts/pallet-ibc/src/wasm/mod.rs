@@ -0,0 +1,226 @@
+//! Wasm light clients.
+//!
+//! The code of the light clients pushed with `MsgPushNewWasmCode` is stored in [`WasmCodes`] and
+//! executed by [`WasmContracts`] whenever a `08-wasm` client refers to it by checksum, so new
+//! light clients can be supported without a runtime upgrade. The store of a contract is the
+//! store of its client in the ibc child trie, `clientState` and `consensusStates/{height}` are
+//! the same keys the pallet reads the client and consensus states from. The gas used by the
+//! contracts is charged in the weight of the extrinsic that runs them.
+
+pub mod vm;
+
+use crate::{impls::host_height, Config, WasmCodes};
+use alloc::string::{String, ToString};
+use frame_support::{
+	storage::{child, child::ChildInfo, with_transaction_unchecked, TransactionOutcome},
+	traits::{Get, UnixTime},
+	weights::{constants::WEIGHT_REF_TIME_PER_NANOS, Weight},
+};
+use ibc::core::{ics02_client::error::Error, ics24_host::identifier::ClientId};
+use ics08_wasm::executor::{ContractResult, QueryMsg, QueryResponse, SudoMsg, WasmExecutor};
+use serde::{Deserialize, Serialize};
+use sp_std::{marker::PhantomData, prelude::*};
+
+/// Weight of a unit of gas, about one wasm instruction
+pub const WEIGHT_REF_TIME_PER_GAS: u64 = WEIGHT_REF_TIME_PER_NANOS;
+
+/// Returns the weight of `gas` used by the wasm light clients.
+pub fn gas_weight(gas: u64) -> Weight {
+	Weight::from_parts(gas.saturating_mul(WEIGHT_REF_TIME_PER_GAS), 0)
+}
+
+/// Runs the stored wasm light clients for the ics08 client.
+pub struct WasmContracts<T> {
+	vm: vm::Vm,
+	_phantom: PhantomData<T>,
+}
+
+impl<T> Default for WasmContracts<T> {
+	fn default() -> Self {
+		Self { vm: vm::Vm::default(), _phantom: PhantomData }
+	}
+}
+
+impl<T> WasmContracts<T> {
+	/// Returns the gas used by the contracts run so far.
+	pub fn gas_used(&self) -> u64 {
+		self.vm.gas_used()
+	}
+}
+
+impl<T: Config> WasmContracts<T>
+where
+	u32: From<<T as frame_system::Config>::BlockNumber>,
+{
+	fn code(checksum: &[u8]) -> Result<Vec<u8>, String> {
+		WasmCodes::<T>::get(checksum)
+			.map(|code| code.into_inner())
+			.ok_or_else(|| format!("wasm code {} not found", hex::encode(checksum)))
+	}
+
+	fn env(client_id: &ClientId) -> Result<Vec<u8>, Error> {
+		let para_id: u32 = parachain_info::Pallet::<T>::parachain_id().into();
+		let env = Env {
+			block: BlockInfo {
+				height: host_height::<T>(),
+				time: T::TimeProvider::now().as_nanos().to_string(),
+				chain_id: para_id.to_string(),
+			},
+			transaction: None,
+			contract: ContractInfo { address: client_id.to_string() },
+		};
+		serde_json::to_vec(&env).map_err(|e| Error::implementation_specific(e.to_string()))
+	}
+}
+
+impl<T: Config> WasmExecutor for WasmContracts<T>
+where
+	u32: From<<T as frame_system::Config>::BlockNumber>,
+{
+	fn has_code(&self, checksum: &[u8]) -> bool {
+		WasmCodes::<T>::contains_key(checksum)
+	}
+
+	fn sudo(
+		&mut self,
+		checksum: &[u8],
+		client_id: &ClientId,
+		msg: &SudoMsg,
+	) -> Result<ContractResult, Error> {
+		let env = Self::env(client_id)?;
+		let msg =
+			serde_json::to_vec(msg).map_err(|e| Error::implementation_specific(e.to_string()))?;
+		// The writes of a contract that fails must not be kept
+		with_transaction_unchecked(|| {
+			let result = self
+				.vm
+				.sudo(
+					checksum,
+					|| Self::code(checksum),
+					ClientStore::<T>::new(client_id),
+					&env,
+					&msg,
+					T::WasmGasLimit::get(),
+				)
+				.and_then(|response| match parse::<SudoResponse>(&response)? {
+					SudoResponse { data: Some(data) } => {
+						let data = base64::decode(data).map_err(|e| e.to_string())?;
+						serde_json::from_slice(&data).map_err(|e| e.to_string())
+					},
+					SudoResponse { data: None } => Ok(ContractResult::default()),
+				})
+				.map_err(Error::implementation_specific);
+			match result {
+				Ok(result) => TransactionOutcome::Commit(Ok(result)),
+				Err(e) => TransactionOutcome::Rollback(Err(e)),
+			}
+		})
+	}
+
+	fn query(
+		&mut self,
+		checksum: &[u8],
+		client_id: &ClientId,
+		msg: &QueryMsg,
+	) -> Result<QueryResponse, Error> {
+		let env = Self::env(client_id)?;
+		let msg =
+			serde_json::to_vec(msg).map_err(|e| Error::implementation_specific(e.to_string()))?;
+		self.vm
+			.query(
+				checksum,
+				|| Self::code(checksum),
+				ClientStore::<T>::new(client_id),
+				&env,
+				&msg,
+				T::WasmGasLimit::get(),
+			)
+			.and_then(|response| {
+				let data =
+					base64::decode(parse::<String>(&response)?).map_err(|e| e.to_string())?;
+				serde_json::from_slice(&data).map_err(|e| e.to_string())
+			})
+			.map_err(Error::implementation_specific)
+	}
+}
+
+/// Parses the `{"ok": ..}` or `{"error": ..}` response of an entry point of the contract.
+fn parse<'a, R: Deserialize<'a>>(response: &'a [u8]) -> Result<R, String> {
+	match serde_json::from_slice(response).map_err(|e| e.to_string())? {
+		ContractResponse::Ok(r) => Ok(r),
+		ContractResponse::Error(e) => Err(e),
+	}
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum ContractResponse<R> {
+	Ok(R),
+	Error(String),
+}
+
+/// `Response` of a `sudo` call, only its data is used.
+#[derive(Deserialize)]
+struct SudoResponse {
+	#[serde(default)]
+	data: Option<String>,
+}
+
+#[derive(Serialize)]
+struct Env {
+	block: BlockInfo,
+	transaction: Option<()>,
+	contract: ContractInfo,
+}
+
+#[derive(Serialize)]
+struct BlockInfo {
+	height: u64,
+	/// Nanoseconds since the unix epoch
+	time: String,
+	chain_id: String,
+}
+
+#[derive(Serialize)]
+struct ContractInfo {
+	/// The contract of a client is addressed by the client id
+	address: String,
+}
+
+/// Store of a wasm client, the keys of the contract are prefixed with `clients/{client_id}/`.
+pub struct ClientStore<T> {
+	prefix: Vec<u8>,
+	_phantom: PhantomData<T>,
+}
+
+impl<T: Config> ClientStore<T> {
+	pub fn new(client_id: &ClientId) -> Self {
+		let prefix = [T::PalletPrefix::get(), format!("clients/{client_id}/").as_bytes()].concat();
+		Self { prefix, _phantom: PhantomData }
+	}
+
+	fn key(&self, key: &[u8]) -> Vec<u8> {
+		[&self.prefix[..], key].concat()
+	}
+}
+
+impl<T: Config> vm::Storage for ClientStore<T> {
+	fn get(&self, key: &[u8]) -> Option<Vec<u8>> {
+		child::get(&ChildInfo::new_default(T::PalletPrefix::get()), &self.key(key))
+	}
+
+	fn set(&mut self, key: &[u8], value: &[u8]) {
+		child::put(&ChildInfo::new_default(T::PalletPrefix::get()), &self.key(key), &value)
+	}
+
+	fn remove(&mut self, key: &[u8]) {
+		child::kill(&ChildInfo::new_default(T::PalletPrefix::get()), &self.key(key))
+	}
+
+	fn next_key(&self, key: &[u8]) -> Option<Vec<u8>> {
+		let child_info = ChildInfo::new_default(T::PalletPrefix::get());
+		let next =
+			sp_io::default_child_storage::next_key(child_info.storage_key(), &self.key(key))?;
+		next.strip_prefix(&self.prefix[..]).map(|key| key.to_vec())
+	}
+}
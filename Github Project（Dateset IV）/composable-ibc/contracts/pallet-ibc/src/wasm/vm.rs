@@ -0,0 +1,794 @@
+//! A minimal CosmWasm virtual machine built on wasmi.
+//!
+//! It implements the subset of the CosmWasm contract ABI used by the 08-wasm light clients: the
+//! `sudo` and `query` entry points, the storage, address and crypto imports of the `env`
+//! module, and regions to move data across the memory of the contract. Execution is metered
+//! with wasmi fuel, host functions charge fuel for the work they do on behalf of the contract.
+//! A [`Vm`] compiles every contract once and accounts the gas used by all of its calls.
+
+use alloc::{
+	collections::BTreeMap,
+	string::{String, ToString},
+};
+use core::fmt::Display;
+use sp_core::ed25519;
+use sp_std::prelude::*;
+use wasmi::{
+	core::Trap, AsContext, AsContextMut, Caller, Config, Engine, Extern, ExternType, Linker,
+	Memory, Module, Store, StoreLimits, StoreLimitsBuilder,
+};
+
+/// Largest region the contract may pass to or receive from the host
+const MAX_REGION_LENGTH: u32 = 8 * 1024 * 1024;
+/// Size of a region in the memory of the contract: offset, capacity and length as u32
+const REGION_SIZE: usize = 12;
+/// Most pages of 64 KiB the memory of a contract may have
+pub const MAX_MEMORY_PAGES: u32 = 512;
+/// Size of a page of wasm memory
+const PAGE_SIZE: usize = 64 * 1024;
+
+/// Gas charged for every call of a host function
+const GAS_PER_HOST_CALL: u64 = 1_000;
+/// Gas charged for every byte read from or written to the store
+const GAS_PER_STORAGE_BYTE: u64 = 10;
+/// Gas charged for every key visited by an iterator
+const GAS_PER_ITERATION: u64 = 5_000;
+/// Gas charged for every signature verification or public key recovery
+const GAS_PER_SIGNATURE: u64 = 100_000;
+/// Gas charged for every byte of code compiled
+pub const GAS_PER_CODE_BYTE: u64 = 20;
+
+/// Most keys a descending iterator may visit, they are collected when it is opened
+const MAX_DESCENDING_ITERATION: usize = 10_000;
+
+/// Exports every contract must have
+const REQUIRED_EXPORTS: &[&str] =
+	&["memory", "allocate", "deallocate", "sudo", "query", "interface_version_8"];
+
+/// Imports of the `env` module provided to the contracts
+const SUPPORTED_IMPORTS: &[&str] = &[
+	"db_read",
+	"db_write",
+	"db_remove",
+	"db_scan",
+	"db_next",
+	"db_next_key",
+	"db_next_value",
+	"addr_validate",
+	"addr_canonicalize",
+	"addr_humanize",
+	"secp256k1_verify",
+	"secp256k1_recover_pubkey",
+	"ed25519_verify",
+	"ed25519_batch_verify",
+	"debug",
+	"abort",
+	"query_chain",
+];
+
+// Error codes of the crypto imports, as defined by the CosmWasm VM
+const INVALID_HASH_FORMAT: u32 = 3;
+const INVALID_SIGNATURE_FORMAT: u32 = 4;
+const INVALID_PUBKEY_FORMAT: u32 = 5;
+const INVALID_RECOVERY_PARAM: u32 = 6;
+const BATCH_ERROR: u32 = 7;
+const GENERIC_ERROR: u32 = 10;
+
+/// Key value store of a contract.
+pub trait Storage {
+	fn get(&self, key: &[u8]) -> Option<Vec<u8>>;
+
+	fn set(&mut self, key: &[u8], value: &[u8]);
+
+	fn remove(&mut self, key: &[u8]);
+
+	/// Returns the first key of the store that is strictly greater than `key`.
+	fn next_key(&self, key: &[u8]) -> Option<Vec<u8>>;
+}
+
+struct Host<S> {
+	storage: S,
+	/// Writes to the store are rejected while running a query
+	read_only: bool,
+	/// Iterators opened with `db_scan`
+	iterators: Vec<DbIterator>,
+	/// Bounds the memory the contract may grow to
+	limits: StoreLimits,
+}
+
+impl<S> Host<S> {
+	fn new(storage: S, read_only: bool) -> Self {
+		let limits = StoreLimitsBuilder::new()
+			.memory_size(MAX_MEMORY_PAGES as usize * PAGE_SIZE)
+			.build();
+		Self { storage, read_only, iterators: vec![], limits }
+	}
+}
+
+enum DbIterator {
+	/// Entries are read one at a time, `next` is the key of the next entry to return
+	Ascending { next: Option<Vec<u8>>, end: Option<Vec<u8>> },
+	/// Keys of the range in ascending order, they are returned from the last one
+	Descending(Vec<Vec<u8>>),
+}
+
+/// Runs the contracts of the wasm light clients.
+pub struct Vm {
+	engine: Engine,
+	/// Compiled contracts by checksum of their code
+	modules: BTreeMap<Vec<u8>, Module>,
+	/// Gas used by the calls so far, compilation included
+	gas_used: u64,
+}
+
+impl Default for Vm {
+	fn default() -> Self {
+		Self { engine: engine(), modules: BTreeMap::new(), gas_used: 0 }
+	}
+}
+
+/// Checks that `code` is a valid wasm module without floats that exports the entry points of a
+/// light client contract, only imports functions supported by the host and doesn't declare more
+/// than [`MAX_MEMORY_PAGES`] of memory.
+pub fn validate(code: &[u8]) -> Result<(), String> {
+	let module = Module::new(&engine(), code).map_err(to_string)?;
+	for export in module.exports() {
+		if let ExternType::Memory(memory) = export.ty() {
+			let pages = memory.maximum_pages().unwrap_or_else(|| memory.initial_pages());
+			if u32::from(pages) > MAX_MEMORY_PAGES {
+				return Err(format!("memory {} exceeds {MAX_MEMORY_PAGES} pages", export.name()))
+			}
+		}
+	}
+	for export in REQUIRED_EXPORTS {
+		if !module.exports().any(|e| e.name() == *export) {
+			return Err(format!("missing export {export}"))
+		}
+	}
+	for import in module.imports() {
+		if import.module() != "env" || !SUPPORTED_IMPORTS.contains(&import.name()) {
+			return Err(format!("unsupported import {}.{}", import.module(), import.name()))
+		}
+	}
+	Ok(())
+}
+
+impl Vm {
+	/// Returns the gas used by the calls so far, including the compilation of the contracts.
+	pub fn gas_used(&self) -> u64 {
+		self.gas_used
+	}
+
+	/// Calls the `sudo` entry point of the contract and returns the JSON encoded response. The
+	/// `code` of the contract is only loaded if it isn't compiled yet.
+	pub fn sudo<S: Storage + 'static>(
+		&mut self,
+		checksum: &[u8],
+		code: impl FnOnce() -> Result<Vec<u8>, String>,
+		storage: S,
+		env: &[u8],
+		msg: &[u8],
+		gas_limit: u64,
+	) -> Result<Vec<u8>, String> {
+		self.execute(checksum, code, Host::new(storage, false), "sudo", env, msg, gas_limit)
+	}
+
+	/// Calls the `query` entry point of the contract and returns the JSON encoded response. The
+	/// contract can't write to the store.
+	pub fn query<S: Storage + 'static>(
+		&mut self,
+		checksum: &[u8],
+		code: impl FnOnce() -> Result<Vec<u8>, String>,
+		storage: S,
+		env: &[u8],
+		msg: &[u8],
+		gas_limit: u64,
+	) -> Result<Vec<u8>, String> {
+		self.execute(checksum, code, Host::new(storage, true), "query", env, msg, gas_limit)
+	}
+
+	#[allow(clippy::too_many_arguments)]
+	fn execute<S: Storage + 'static>(
+		&mut self,
+		checksum: &[u8],
+		code: impl FnOnce() -> Result<Vec<u8>, String>,
+		host: Host<S>,
+		entry_point: &str,
+		env: &[u8],
+		msg: &[u8],
+		gas_limit: u64,
+	) -> Result<Vec<u8>, String> {
+		if !self.modules.contains_key(checksum) {
+			let code = code()?;
+			self.gas_used = self
+				.gas_used
+				.saturating_add(GAS_PER_CODE_BYTE.saturating_mul(code.len() as u64));
+			let module = Module::new(&self.engine, &code[..]).map_err(to_string)?;
+			self.modules.insert(checksum.to_vec(), module);
+		}
+		let module = &self.modules[checksum];
+		let mut store = Store::new(&self.engine, host);
+		store.limiter(|host| &mut host.limits);
+		store.add_fuel(gas_limit).map_err(to_string)?;
+		let result = call(&self.engine, module, &mut store, entry_point, env, msg);
+		// Failed calls are charged too, up to the gas limit when they run out of fuel
+		let gas_used = store.fuel_consumed().unwrap_or(gas_limit);
+		self.gas_used = self.gas_used.saturating_add(gas_used);
+		result
+	}
+}
+
+fn engine() -> Engine {
+	let mut config = Config::default();
+	// Float results may differ across machines, contracts must run deterministically
+	config.consume_fuel(true).floats(false);
+	Engine::new(&config)
+}
+
+fn call<S: Storage + 'static>(
+	engine: &Engine,
+	module: &Module,
+	store: &mut Store<Host<S>>,
+	entry_point: &str,
+	env: &[u8],
+	msg: &[u8],
+) -> Result<Vec<u8>, String> {
+	let instance = linker(engine)?
+		.instantiate(&mut *store, module)
+		.map_err(to_string)?
+		.start(&mut *store)
+		.map_err(to_string)?;
+	let memory = instance.get_memory(&*store, "memory").ok_or("missing memory export")?;
+	let allocate = instance.get_typed_func::<u32, u32>(&*store, "allocate").map_err(to_string)?;
+	let entry_point = instance
+		.get_typed_func::<(u32, u32), u32>(&*store, entry_point)
+		.map_err(to_string)?;
+
+	let mut copy = |data: &[u8]| -> Result<u32, String> {
+		let ptr = allocate.call(&mut *store, data.len() as u32).map_err(to_string)?;
+		write_region(&memory, &mut *store, ptr, data).map_err(to_string)?;
+		Ok(ptr)
+	};
+	let env_ptr = copy(env)?;
+	let msg_ptr = copy(msg)?;
+	let result_ptr = entry_point.call(&mut *store, (env_ptr, msg_ptr)).map_err(to_string)?;
+	read_region(&memory, &*store, result_ptr).map_err(to_string)
+}
+
+fn linker<S: Storage + 'static>(engine: &Engine) -> Result<Linker<Host<S>>, String> {
+	let mut linker = Linker::<Host<S>>::new(engine);
+	linker
+		.func_wrap(
+			"env",
+			"db_read",
+			|mut caller: Caller<'_, Host<S>>, key_ptr: u32| -> Result<u32, Trap> {
+				let key = read(&mut caller, key_ptr)?;
+				let value = caller.data().storage.get(&key);
+				match value {
+					Some(value) => {
+						charge(
+							&mut caller,
+							GAS_PER_STORAGE_BYTE * (key.len() + value.len()) as u64,
+						)?;
+						allocate(&mut caller, &value)
+					},
+					None => {
+						charge(&mut caller, GAS_PER_STORAGE_BYTE * key.len() as u64)?;
+						Ok(0)
+					},
+				}
+			},
+		)
+		.map_err(to_string)?
+		.func_wrap(
+			"env",
+			"db_write",
+			|mut caller: Caller<'_, Host<S>>, key_ptr: u32, value_ptr: u32| -> Result<(), Trap> {
+				ensure_writable(&caller)?;
+				let key = read(&mut caller, key_ptr)?;
+				let value = read(&mut caller, value_ptr)?;
+				charge(&mut caller, GAS_PER_STORAGE_BYTE * (key.len() + value.len()) as u64)?;
+				caller.data_mut().storage.set(&key, &value);
+				Ok(())
+			},
+		)
+		.map_err(to_string)?
+		.func_wrap(
+			"env",
+			"db_remove",
+			|mut caller: Caller<'_, Host<S>>, key_ptr: u32| -> Result<(), Trap> {
+				ensure_writable(&caller)?;
+				let key = read(&mut caller, key_ptr)?;
+				charge(&mut caller, GAS_PER_STORAGE_BYTE * key.len() as u64)?;
+				caller.data_mut().storage.remove(&key);
+				Ok(())
+			},
+		)
+		.map_err(to_string)?
+		.func_wrap(
+			"env",
+			"db_scan",
+			|mut caller: Caller<'_, Host<S>>,
+			 start_ptr: u32,
+			 end_ptr: u32,
+			 order: i32|
+			 -> Result<u32, Trap> {
+				let start = if start_ptr == 0 { None } else { Some(read(&mut caller, start_ptr)?) };
+				let end = if end_ptr == 0 { None } else { Some(read(&mut caller, end_ptr)?) };
+				let iterator = match order {
+					1 => {
+						charge(&mut caller, 0)?;
+						DbIterator::Ascending { next: first_key(&caller, start), end }
+					},
+					2 => DbIterator::Descending(scan(&mut caller, start, end)?),
+					_ => return Err(Trap::new("invalid iteration order")),
+				};
+				let iterators = &mut caller.data_mut().iterators;
+				iterators.push(iterator);
+				Ok(iterators.len() as u32)
+			},
+		)
+		.map_err(to_string)?
+		.func_wrap(
+			"env",
+			"db_next",
+			|mut caller: Caller<'_, Host<S>>, iterator_id: u32| -> Result<u32, Trap> {
+				charge(&mut caller, 0)?;
+				let (key, value) = next(&mut caller, iterator_id)?;
+				allocate(&mut caller, &encode_sections(&[&key, &value]))
+			},
+		)
+		.map_err(to_string)?
+		.func_wrap(
+			"env",
+			"db_next_key",
+			|mut caller: Caller<'_, Host<S>>, iterator_id: u32| -> Result<u32, Trap> {
+				charge(&mut caller, 0)?;
+				let (key, _) = next(&mut caller, iterator_id)?;
+				allocate(&mut caller, &key)
+			},
+		)
+		.map_err(to_string)?
+		.func_wrap(
+			"env",
+			"db_next_value",
+			|mut caller: Caller<'_, Host<S>>, iterator_id: u32| -> Result<u32, Trap> {
+				charge(&mut caller, 0)?;
+				let (_, value) = next(&mut caller, iterator_id)?;
+				allocate(&mut caller, &value)
+			},
+		)
+		.map_err(to_string)?
+		.func_wrap(
+			"env",
+			"addr_validate",
+			|mut caller: Caller<'_, Host<S>>, source_ptr: u32| -> Result<u32, Trap> {
+				let source = read(&mut caller, source_ptr)?;
+				charge(&mut caller, 0)?;
+				match validate_address(&source) {
+					Ok(_) => Ok(0),
+					Err(e) => allocate(&mut caller, e.as_bytes()),
+				}
+			},
+		)
+		.map_err(to_string)?
+		.func_wrap(
+			"env",
+			"addr_canonicalize",
+			|mut caller: Caller<'_, Host<S>>,
+			 source_ptr: u32,
+			 destination_ptr: u32|
+			 -> Result<u32, Trap> {
+				let source = read(&mut caller, source_ptr)?;
+				charge(&mut caller, 0)?;
+				// Addresses are used as they are, the canonical form is the utf8 string
+				match validate_address(&source) {
+					Ok(address) => {
+						write(&mut caller, destination_ptr, address.as_bytes())?;
+						Ok(0)
+					},
+					Err(e) => allocate(&mut caller, e.as_bytes()),
+				}
+			},
+		)
+		.map_err(to_string)?
+		.func_wrap(
+			"env",
+			"addr_humanize",
+			|mut caller: Caller<'_, Host<S>>,
+			 source_ptr: u32,
+			 destination_ptr: u32|
+			 -> Result<u32, Trap> {
+				let source = read(&mut caller, source_ptr)?;
+				charge(&mut caller, 0)?;
+				match validate_address(&source) {
+					Ok(address) => {
+						write(&mut caller, destination_ptr, address.as_bytes())?;
+						Ok(0)
+					},
+					Err(e) => allocate(&mut caller, e.as_bytes()),
+				}
+			},
+		)
+		.map_err(to_string)?
+		.func_wrap(
+			"env",
+			"secp256k1_verify",
+			|mut caller: Caller<'_, Host<S>>,
+			 hash_ptr: u32,
+			 signature_ptr: u32,
+			 pubkey_ptr: u32|
+			 -> Result<u32, Trap> {
+				let hash = read(&mut caller, hash_ptr)?;
+				let signature = read(&mut caller, signature_ptr)?;
+				let pubkey = read(&mut caller, pubkey_ptr)?;
+				charge(&mut caller, GAS_PER_SIGNATURE)?;
+				Ok(verification_result(secp256k1_verify(&hash, &signature, &pubkey)))
+			},
+		)
+		.map_err(to_string)?
+		.func_wrap(
+			"env",
+			"secp256k1_recover_pubkey",
+			|mut caller: Caller<'_, Host<S>>,
+			 hash_ptr: u32,
+			 signature_ptr: u32,
+			 recovery_param: u32|
+			 -> Result<u64, Trap> {
+				let hash = read(&mut caller, hash_ptr)?;
+				let signature = read(&mut caller, signature_ptr)?;
+				charge(&mut caller, GAS_PER_SIGNATURE)?;
+				// The region of the public key is returned in the low half, an error code in the
+				// high half
+				match secp256k1_recover_pubkey(&hash, &signature, recovery_param) {
+					Ok(pubkey) => allocate(&mut caller, &pubkey).map(u64::from),
+					Err(code) => Ok(u64::from(code) << 32),
+				}
+			},
+		)
+		.map_err(to_string)?
+		.func_wrap(
+			"env",
+			"ed25519_verify",
+			|mut caller: Caller<'_, Host<S>>,
+			 message_ptr: u32,
+			 signature_ptr: u32,
+			 pubkey_ptr: u32|
+			 -> Result<u32, Trap> {
+				let message = read(&mut caller, message_ptr)?;
+				let signature = read(&mut caller, signature_ptr)?;
+				let pubkey = read(&mut caller, pubkey_ptr)?;
+				charge(&mut caller, GAS_PER_SIGNATURE)?;
+				Ok(verification_result(ed25519_verify(&message, &signature, &pubkey)))
+			},
+		)
+		.map_err(to_string)?
+		.func_wrap(
+			"env",
+			"ed25519_batch_verify",
+			|mut caller: Caller<'_, Host<S>>,
+			 messages_ptr: u32,
+			 signatures_ptr: u32,
+			 pubkeys_ptr: u32|
+			 -> Result<u32, Trap> {
+				let messages = read(&mut caller, messages_ptr)?;
+				let signatures = read(&mut caller, signatures_ptr)?;
+				let pubkeys = read(&mut caller, pubkeys_ptr)?;
+				let (Some(messages), Some(signatures), Some(pubkeys)) = (
+					decode_sections(&messages),
+					decode_sections(&signatures),
+					decode_sections(&pubkeys),
+				) else {
+					return Ok(BATCH_ERROR)
+				};
+				charge(&mut caller, GAS_PER_SIGNATURE * signatures.len() as u64)?;
+				Ok(verification_result(ed25519_batch_verify(&messages, &signatures, &pubkeys)))
+			},
+		)
+		.map_err(to_string)?
+		.func_wrap(
+			"env",
+			"debug",
+			|mut caller: Caller<'_, Host<S>>, message_ptr: u32| -> Result<(), Trap> {
+				let message = read(&mut caller, message_ptr)?;
+				log::debug!(target: "pallet_ibc", "wasm client: {}", String::from_utf8_lossy(&message));
+				Ok(())
+			},
+		)
+		.map_err(to_string)?
+		.func_wrap(
+			"env",
+			"abort",
+			|mut caller: Caller<'_, Host<S>>, message_ptr: u32| -> Result<(), Trap> {
+				let message = read(&mut caller, message_ptr)?;
+				Err(Trap::new(format!("aborted: {}", String::from_utf8_lossy(&message))))
+			},
+		)
+		.map_err(to_string)?
+		.func_wrap(
+			"env",
+			"query_chain",
+			|mut caller: Caller<'_, Host<S>>, _request_ptr: u32| -> Result<u32, Trap> {
+				charge(&mut caller, 0)?;
+				// The light clients have no other module to query on the host
+				allocate(
+					&mut caller,
+					br#"{"error":{"unsupported_request":{"kind":"query_chain"}}}"#,
+				)
+			},
+		)
+		.map_err(to_string)?;
+	Ok(linker)
+}
+
+fn to_string(e: impl Display) -> String {
+	e.to_string()
+}
+
+fn trap(e: impl Display) -> Trap {
+	Trap::new(e.to_string())
+}
+
+/// Charges the cost of a host call plus `gas`.
+fn charge<S>(caller: &mut Caller<'_, Host<S>>, gas: u64) -> Result<(), Trap> {
+	caller.consume_fuel(GAS_PER_HOST_CALL.saturating_add(gas)).map_err(trap)?;
+	Ok(())
+}
+
+fn ensure_writable<S>(caller: &Caller<'_, Host<S>>) -> Result<(), Trap> {
+	if caller.data().read_only {
+		return Err(Trap::new("the store can't be written by a query"))
+	}
+	Ok(())
+}
+
+fn memory<S>(caller: &Caller<'_, Host<S>>) -> Result<Memory, Trap> {
+	caller
+		.get_export("memory")
+		.and_then(Extern::into_memory)
+		.ok_or_else(|| Trap::new("missing memory export"))
+}
+
+/// Returns the offset, capacity and length of the region at `ptr`.
+fn region(memory: &Memory, ctx: impl AsContext, ptr: u32) -> Result<(u32, u32, u32), Trap> {
+	let mut region = [0u8; REGION_SIZE];
+	memory.read(ctx, ptr as usize, &mut region).map_err(trap)?;
+	let field =
+		|i: usize| u32::from_le_bytes([region[i], region[i + 1], region[i + 2], region[i + 3]]);
+	let (offset, capacity, length) = (field(0), field(4), field(8));
+	if length > capacity || length > MAX_REGION_LENGTH {
+		return Err(Trap::new("invalid region"))
+	}
+	Ok((offset, capacity, length))
+}
+
+fn read_region(memory: &Memory, ctx: impl AsContext, ptr: u32) -> Result<Vec<u8>, Trap> {
+	let (offset, _, length) = region(memory, &ctx, ptr)?;
+	let mut data = vec![0u8; length as usize];
+	memory.read(&ctx, offset as usize, &mut data).map_err(trap)?;
+	Ok(data)
+}
+
+fn write_region(
+	memory: &Memory,
+	mut ctx: impl AsContextMut,
+	ptr: u32,
+	data: &[u8],
+) -> Result<(), Trap> {
+	let (offset, capacity, _) = region(memory, &ctx, ptr)?;
+	if data.len() > capacity as usize {
+		return Err(Trap::new("region is too small"))
+	}
+	memory.write(&mut ctx, offset as usize, data).map_err(trap)?;
+	memory
+		.write(&mut ctx, ptr as usize + 8, &(data.len() as u32).to_le_bytes())
+		.map_err(trap)
+}
+
+fn read<S>(caller: &mut Caller<'_, Host<S>>, ptr: u32) -> Result<Vec<u8>, Trap> {
+	let memory = memory(caller)?;
+	read_region(&memory, &*caller, ptr)
+}
+
+fn write<S>(caller: &mut Caller<'_, Host<S>>, ptr: u32, data: &[u8]) -> Result<(), Trap> {
+	let memory = memory(caller)?;
+	write_region(&memory, caller, ptr, data)
+}
+
+/// Copies `data` to a new region allocated by the contract and returns the region.
+fn allocate<S>(caller: &mut Caller<'_, Host<S>>, data: &[u8]) -> Result<u32, Trap> {
+	let allocate = caller
+		.get_export("allocate")
+		.and_then(Extern::into_func)
+		.ok_or_else(|| Trap::new("missing allocate export"))?
+		.typed::<u32, u32>(&*caller)
+		.map_err(trap)?;
+	let ptr = allocate.call(&mut *caller, data.len() as u32).map_err(trap)?;
+	write(caller, ptr, data)?;
+	Ok(ptr)
+}
+
+/// Returns the first key of the store that is greater than or equal to `start`.
+fn first_key<S: Storage>(caller: &Caller<'_, Host<S>>, start: Option<Vec<u8>>) -> Option<Vec<u8>> {
+	let storage = &caller.data().storage;
+	match start {
+		Some(start) if storage.get(&start).is_some() => Some(start),
+		Some(start) => storage.next_key(&start),
+		None => storage.next_key(&[]),
+	}
+}
+
+/// Collects the keys in `start..end`, in ascending order. The scan traps if the range has more
+/// than [`MAX_DESCENDING_ITERATION`] keys.
+fn scan<S: Storage>(
+	caller: &mut Caller<'_, Host<S>>,
+	start: Option<Vec<u8>>,
+	end: Option<Vec<u8>>,
+) -> Result<Vec<Vec<u8>>, Trap> {
+	let mut key = first_key(caller, start);
+	let mut keys = Vec::new();
+	while let Some(current) = key {
+		if end.as_ref().map_or(false, |end| &current >= end) {
+			break
+		}
+		if keys.len() == MAX_DESCENDING_ITERATION {
+			return Err(Trap::new("too many keys to iterate in descending order"))
+		}
+		charge(caller, GAS_PER_ITERATION)?;
+		key = caller.data().storage.next_key(&current);
+		keys.push(current);
+	}
+	Ok(keys)
+}
+
+/// Returns the next entry of the iterator, an empty key and value once it is exhausted. Entries
+/// removed since the iterator was opened are skipped.
+fn next<S: Storage>(
+	caller: &mut Caller<'_, Host<S>>,
+	iterator_id: u32,
+) -> Result<(Vec<u8>, Vec<u8>), Trap> {
+	let Host { storage, iterators, .. } = caller.data_mut();
+	let iterator = (iterator_id as usize)
+		.checked_sub(1)
+		.and_then(|i| iterators.get_mut(i))
+		.ok_or_else(|| Trap::new("unknown iterator"))?;
+	let entry = loop {
+		let key = match iterator {
+			DbIterator::Ascending { next, end } => {
+				let key = next.take().filter(|key| end.as_ref().map_or(true, |end| key < end));
+				if let Some(key) = &key {
+					*next = storage.next_key(key);
+				}
+				key
+			},
+			DbIterator::Descending(keys) => keys.pop(),
+		};
+		let Some(key) = key else { break None };
+		if let Some(value) = storage.get(&key) {
+			break Some((key, value))
+		}
+	};
+	match entry {
+		Some((key, value)) => {
+			charge(
+				caller,
+				GAS_PER_ITERATION + GAS_PER_STORAGE_BYTE * (key.len() + value.len()) as u64,
+			)?;
+			Ok((key, value))
+		},
+		None => Ok(Default::default()),
+	}
+}
+
+/// Encodes each section followed by its length as a big endian u32.
+fn encode_sections(sections: &[&[u8]]) -> Vec<u8> {
+	let mut out = Vec::new();
+	for section in sections {
+		out.extend_from_slice(section);
+		out.extend_from_slice(&(section.len() as u32).to_be_bytes());
+	}
+	out
+}
+
+fn decode_sections(mut data: &[u8]) -> Option<Vec<Vec<u8>>> {
+	let mut sections = Vec::new();
+	while !data.is_empty() {
+		let (rest, length) = data.split_at(data.len().checked_sub(4)?);
+		let length = u32::from_be_bytes(length.try_into().ok()?) as usize;
+		let (rest, section) = rest.split_at(rest.len().checked_sub(length)?);
+		sections.push(section.to_vec());
+		data = rest;
+	}
+	sections.reverse();
+	Some(sections)
+}
+
+fn validate_address(address: &[u8]) -> Result<&str, &'static str> {
+	match core::str::from_utf8(address) {
+		Ok(address) if !address.is_empty() => Ok(address),
+		Ok(_) => Err("empty address"),
+		Err(_) => Err("address is not utf8"),
+	}
+}
+
+/// Result of a signature verification as returned to the contract: 0 if the signature is valid,
+/// 1 if it isn't and an error code if the input is malformed.
+fn verification_result(result: Result<bool, u32>) -> u32 {
+	match result {
+		Ok(true) => 0,
+		Ok(false) => 1,
+		Err(code) => code,
+	}
+}
+
+fn secp256k1_verify(hash: &[u8], signature: &[u8], pubkey: &[u8]) -> Result<bool, u32> {
+	let hash: [u8; 32] = hash.try_into().map_err(|_| INVALID_HASH_FORMAT)?;
+	let signature: [u8; 64] = signature.try_into().map_err(|_| INVALID_SIGNATURE_FORMAT)?;
+	if !matches!(pubkey.len(), 33 | 65) {
+		return Err(INVALID_PUBKEY_FORMAT)
+	}
+	// The host only recovers public keys, so the signature is valid if the key recovered with
+	// either parity matches
+	Ok((0..2).any(|recovery_id| {
+		let mut recoverable = [0u8; 65];
+		recoverable[..64].copy_from_slice(&signature);
+		recoverable[64] = recovery_id;
+		match pubkey.len() {
+			33 => sp_io::crypto::secp256k1_ecdsa_recover_compressed(&recoverable, &hash)
+				.map_or(false, |recovered| recovered[..] == pubkey[..]),
+			_ => sp_io::crypto::secp256k1_ecdsa_recover(&recoverable, &hash)
+				.map_or(false, |recovered| pubkey[0] == 4 && recovered[..] == pubkey[1..]),
+		}
+	}))
+}
+
+fn secp256k1_recover_pubkey(
+	hash: &[u8],
+	signature: &[u8],
+	recovery_param: u32,
+) -> Result<Vec<u8>, u32> {
+	let hash: [u8; 32] = hash.try_into().map_err(|_| INVALID_HASH_FORMAT)?;
+	let signature: [u8; 64] = signature.try_into().map_err(|_| INVALID_SIGNATURE_FORMAT)?;
+	if recovery_param > 1 {
+		return Err(INVALID_RECOVERY_PARAM)
+	}
+	let mut recoverable = [0u8; 65];
+	recoverable[..64].copy_from_slice(&signature);
+	recoverable[64] = recovery_param as u8;
+	let pubkey =
+		sp_io::crypto::secp256k1_ecdsa_recover(&recoverable, &hash).map_err(|_| GENERIC_ERROR)?;
+	Ok([&[4u8][..], &pubkey[..]].concat())
+}
+
+fn ed25519_verify(message: &[u8], signature: &[u8], pubkey: &[u8]) -> Result<bool, u32> {
+	let signature: [u8; 64] = signature.try_into().map_err(|_| INVALID_SIGNATURE_FORMAT)?;
+	let pubkey: [u8; 32] = pubkey.try_into().map_err(|_| INVALID_PUBKEY_FORMAT)?;
+	Ok(sp_io::crypto::ed25519_verify(
+		&ed25519::Signature::from_raw(signature),
+		message,
+		&ed25519::Public::from_raw(pubkey),
+	))
+}
+
+/// Verifies the signatures against their message and public key. A single message or public key
+/// is used for all the signatures.
+fn ed25519_batch_verify(
+	messages: &[Vec<u8>],
+	signatures: &[Vec<u8>],
+	pubkeys: &[Vec<u8>],
+) -> Result<bool, u32> {
+	let n = signatures.len();
+	let messages_ok = messages.len() == n || messages.len() == 1;
+	let pubkeys_ok = pubkeys.len() == n || pubkeys.len() == 1;
+	if !messages_ok || !pubkeys_ok {
+		return Err(BATCH_ERROR)
+	}
+	for i in 0..n {
+		let message = &messages[if messages.len() == 1 { 0 } else { i }];
+		let pubkey = &pubkeys[if pubkeys.len() == 1 { 0 } else { i }];
+		if !ed25519_verify(message, &signatures[i], pubkey)? {
+			return Ok(false)
+		}
+	}
+	Ok(true)
+}
@@ -1,13 +1,17 @@
 use super::*;
-use crate::{light_clients::AnyClientMessage, routing::Context};
+use crate::{light_clients::AnyClientMessage, routing::Context, wasm};
 use core::marker::PhantomData;
-use frame_support::{pallet_prelude::Weight, weights::constants::WEIGHT_REF_TIME_PER_MILLIS};
+use frame_support::{
+	pallet_prelude::Weight,
+	traits::Get,
+	weights::constants::{WEIGHT_REF_TIME_PER_MILLIS, WEIGHT_REF_TIME_PER_NANOS},
+};
 use grandpa_client_primitives::justification::GrandpaJustification;
 use ibc::core::{
 	ics02_client::msgs::ClientMsg,
 	ics03_connection::{context::ConnectionReader, msgs::ConnectionMsg},
 	ics04_channel::msgs::{ChannelMsg, PacketMsg},
-	ics24_host::identifier::ClientId,
+	ics24_host::identifier::{ChannelId, ClientId, ConnectionId, PortId},
 	ics26_routing::msgs::Ics26Envelope,
 };
 use ibc_primitives::{client_id_from_bytes, CallbackWeight};
@@ -42,6 +46,19 @@ pub trait WeightInfo {
 	fn on_timeout_packet() -> Weight;
 	fn update_grandpa_client(i: u32, j: u32) -> Weight;
 	fn packet_cleanup(i: u32) -> Weight;
+	fn channel_upgrade_init() -> Weight;
+	fn channel_upgrade_try() -> Weight;
+	fn channel_upgrade_ack() -> Weight;
+	fn channel_upgrade_confirm() -> Weight;
+	fn channel_upgrade_open() -> Weight;
+	fn channel_upgrade_timeout() -> Weight;
+	fn channel_upgrade_cancel() -> Weight;
+	fn push_wasm_code(i: u32) -> Weight;
+	fn register_payee() -> Weight;
+	fn register_counterparty_payee() -> Weight;
+	fn pay_packet_fee_async() -> Weight;
+	fn register_interchain_account() -> Weight;
+	fn send_interchain_account_tx(i: u32) -> Weight;
 }
 
 impl WeightInfo for () {
@@ -152,6 +169,72 @@ impl WeightInfo for () {
 	fn packet_cleanup(_i: u32) -> Weight {
 		Weight::default()
 	}
+
+	// Channel upgrades are not benchmarked yet, their weights are estimated from the number of
+	// proofs each message verifies.
+	fn channel_upgrade_init() -> Weight {
+		Weight::from_parts(WEIGHT_REF_TIME_PER_MILLIS, 0)
+	}
+
+	fn channel_upgrade_try() -> Weight {
+		Weight::from_parts(3 * WEIGHT_REF_TIME_PER_MILLIS, 0)
+	}
+
+	fn channel_upgrade_ack() -> Weight {
+		Weight::from_parts(3 * WEIGHT_REF_TIME_PER_MILLIS, 0)
+	}
+
+	fn channel_upgrade_confirm() -> Weight {
+		Weight::from_parts(3 * WEIGHT_REF_TIME_PER_MILLIS, 0)
+	}
+
+	fn channel_upgrade_open() -> Weight {
+		Weight::from_parts(2 * WEIGHT_REF_TIME_PER_MILLIS, 0)
+	}
+
+	fn channel_upgrade_timeout() -> Weight {
+		Weight::from_parts(2 * WEIGHT_REF_TIME_PER_MILLIS, 0)
+	}
+
+	fn channel_upgrade_cancel() -> Weight {
+		Weight::from_parts(2 * WEIGHT_REF_TIME_PER_MILLIS, 0)
+	}
+
+	// Pushing wasm code is not benchmarked yet, its weight is estimated from the validation of
+	// the code and the size it takes in the proof.
+	fn push_wasm_code(i: u32) -> Weight {
+		Weight::from_parts(
+			WEIGHT_REF_TIME_PER_MILLIS + 50 * i as u64 * WEIGHT_REF_TIME_PER_NANOS,
+			i as u64,
+		)
+	}
+
+	// The fee calls are not benchmarked yet, their weights are estimated from the storage they
+	// access.
+	fn register_payee() -> Weight {
+		Weight::from_parts(WEIGHT_REF_TIME_PER_MILLIS / 10, 0)
+	}
+
+	fn register_counterparty_payee() -> Weight {
+		Weight::from_parts(WEIGHT_REF_TIME_PER_MILLIS / 10, 0)
+	}
+
+	fn pay_packet_fee_async() -> Weight {
+		Weight::from_parts(WEIGHT_REF_TIME_PER_MILLIS / 2, 0)
+	}
+
+	// The interchain account calls are not benchmarked yet, registering is estimated from the
+	// channel handshake it starts and sending from the size of the packet it commits to.
+	fn register_interchain_account() -> Weight {
+		Weight::from_parts(2 * WEIGHT_REF_TIME_PER_MILLIS, 0)
+	}
+
+	fn send_interchain_account_tx(i: u32) -> Weight {
+		Weight::from_parts(
+			WEIGHT_REF_TIME_PER_MILLIS + 10 * i as u64 * WEIGHT_REF_TIME_PER_NANOS,
+			i as u64,
+		)
+	}
 }
 
 pub struct WeightRouter<T: Config>(PhantomData<T>);
@@ -161,6 +244,15 @@ impl<T: Config> WeightRouter<T> {
 		match port_id {
 			#[allow(clippy::box_default)]
 			ibc::applications::transfer::PORT_ID_STR => Some(Box::new(ics20::WeightHandler::<T>::default())),
+			#[allow(clippy::box_default)]
+			ibc::applications::interchain_accounts::HOST_PORT_ID_STR =>
+				Some(Box::new(ics27::WeightHandler::<T>::default())),
+			#[allow(clippy::box_default)]
+			port_id
+				if port_id.starts_with(
+					ibc::applications::interchain_accounts::CONTROLLER_PORT_PREFIX,
+				) =>
+				Some(Box::new(ics27::WeightHandler::<T>::default())),
 			_ => None,
 		}
 	}
@@ -180,6 +272,207 @@ pub fn channel_client<T: Config>(channel_id: &[u8], port_id: &[u8]) -> Result<Cl
 	Err(Error::<T>::Other)
 }
 
+/// Number of calls of its contract a wasm client makes for a client message:
+/// `verify_client_message`, `check_for_misbehaviour` and the state update
+const WASM_CALLS_PER_CLIENT_MESSAGE: u64 = 3;
+
+fn is_wasm_client(client_id: &ClientId) -> bool {
+	client_id
+		.as_str()
+		.rsplit_once('-')
+		.map_or(false, |(client_type_str, ..)| client_type_str.contains("wasm"))
+}
+
+/// Returns the client that verifies `msg` and the number of calls of its contract it makes when
+/// it is a wasm client, one for every proof it verifies.
+fn client_calls<T: Config + Send + Sync>(msg: &Ics26Envelope<Context<T>>) -> Option<(ClientId, u64)>
+where
+	u32: From<<T as frame_system::Config>::BlockNumber>,
+{
+	let connection_client = |connection_id: &ConnectionId| {
+		let ctx = routing::Context::<T>::new();
+		ctx.connection_end(connection_id)
+			.ok()
+			.map(|connection_end| connection_end.client_id().clone())
+	};
+	let local_channel_client = |port_id: &PortId, channel_id: &ChannelId| {
+		channel_client::<T>(port_id.as_bytes(), channel_id.to_string().as_bytes()).ok()
+	};
+	match msg {
+		Ics26Envelope::Ics2Msg(msg) => match msg {
+			ClientMsg::CreateClient(_) => None,
+			ClientMsg::UpdateClient(msg) =>
+				Some((msg.client_id.clone(), WASM_CALLS_PER_CLIENT_MESSAGE)),
+			ClientMsg::Misbehaviour(msg) =>
+				Some((msg.client_id.clone(), WASM_CALLS_PER_CLIENT_MESSAGE)),
+			ClientMsg::UpgradeClient(msg) => Some((msg.client_id.clone(), 1)),
+		},
+		Ics26Envelope::Ics3Msg(msg) => match msg {
+			ConnectionMsg::ConnectionOpenInit(_) => None,
+			// the connection, client state and consensus state proofs
+			ConnectionMsg::ConnectionOpenTry(msg) => Some((msg.client_id.clone(), 3)),
+			ConnectionMsg::ConnectionOpenAck(msg) =>
+				connection_client(&msg.connection_id).map(|client_id| (client_id, 3)),
+			ConnectionMsg::ConnectionOpenConfirm(msg) =>
+				connection_client(&msg.connection_id).map(|client_id| (client_id, 1)),
+		},
+		Ics26Envelope::Ics4ChannelMsg(msg) => match msg {
+			ChannelMsg::ChannelOpenInit(_) |
+			ChannelMsg::ChannelCloseInit(_) |
+			ChannelMsg::ChannelUpgradeInit(_) => None,
+			ChannelMsg::ChannelOpenTry(msg) => msg
+				.channel
+				.connection_hops
+				.get(0)
+				.and_then(connection_client)
+				.map(|client_id| (client_id, 1)),
+			ChannelMsg::ChannelOpenAck(msg) =>
+				local_channel_client(&msg.port_id, &msg.channel_id).map(|client_id| (client_id, 1)),
+			ChannelMsg::ChannelOpenConfirm(msg) =>
+				local_channel_client(&msg.port_id, &msg.channel_id).map(|client_id| (client_id, 1)),
+			ChannelMsg::ChannelCloseConfirm(msg) =>
+				local_channel_client(&msg.port_id, &msg.channel_id).map(|client_id| (client_id, 1)),
+			// the channel and upgrade proofs
+			ChannelMsg::ChannelUpgradeTry(msg) =>
+				local_channel_client(&msg.port_id, &msg.channel_id).map(|client_id| (client_id, 2)),
+			ChannelMsg::ChannelUpgradeAck(msg) =>
+				local_channel_client(&msg.port_id, &msg.channel_id).map(|client_id| (client_id, 2)),
+			ChannelMsg::ChannelUpgradeConfirm(msg) =>
+				local_channel_client(&msg.port_id, &msg.channel_id).map(|client_id| (client_id, 2)),
+			ChannelMsg::ChannelUpgradeOpen(msg) =>
+				local_channel_client(&msg.port_id, &msg.channel_id).map(|client_id| (client_id, 1)),
+			ChannelMsg::ChannelUpgradeTimeout(msg) =>
+				local_channel_client(&msg.port_id, &msg.channel_id).map(|client_id| (client_id, 1)),
+			ChannelMsg::ChannelUpgradeCancel(msg) =>
+				local_channel_client(&msg.port_id, &msg.channel_id).map(|client_id| (client_id, 1)),
+		},
+		Ics26Envelope::Ics4PacketMsg(msg) => match msg {
+			PacketMsg::RecvPacket(msg) =>
+				local_channel_client(&msg.packet.destination_port, &msg.packet.destination_channel)
+					.map(|client_id| (client_id, 1)),
+			PacketMsg::AckPacket(msg) =>
+				local_channel_client(&msg.packet.source_port, &msg.packet.source_channel)
+					.map(|client_id| (client_id, 1)),
+			PacketMsg::ToPacket(msg) =>
+				local_channel_client(&msg.packet.source_port, &msg.packet.source_channel)
+					.map(|client_id| (client_id, 1)),
+			// the channel and the packet receipt proofs
+			PacketMsg::ToClosePacket(msg) =>
+				local_channel_client(&msg.packet.source_port, &msg.packet.source_channel)
+					.map(|client_id| (client_id, 2)),
+		},
+		// the proof of every packet is verified by its own call
+		Ics26Envelope::Ics4PacketBatchMsg(msg) => msg
+			.packets
+			.first()
+			.and_then(|packet| {
+				local_channel_client(&packet.destination_port, &packet.destination_channel)
+			})
+			.map(|client_id| (client_id, msg.packets.len() as u64)),
+	}
+}
+
+/// Gas a wasm client may use to verify `msg`, including the compilation of its contract. Zero if
+/// `msg` is not verified by a wasm client.
+fn wasm_message_gas<T: Config + Send + Sync>(msg: &Ics26Envelope<Context<T>>) -> u64
+where
+	u32: From<<T as frame_system::Config>::BlockNumber>,
+{
+	match client_calls::<T>(msg) {
+		Some((client_id, calls)) if is_wasm_client(&client_id) =>
+			calls.saturating_mul(T::WasmGasLimit::get()).saturating_add(
+				wasm::vm::GAS_PER_CODE_BYTE.saturating_mul(T::MaxWasmCodeSize::get().into()),
+			),
+		_ => 0,
+	}
+}
+
+/// Gas the wasm clients may use to verify `msgs`. It is part of the weight of [`deliver`], the
+/// gas they don't use is refunded once the messages are executed.
+pub(crate) fn wasm_gas_limit<T: Config + Send + Sync>(msgs: &[Any]) -> u64
+where
+	u32: From<<T as frame_system::Config>::BlockNumber>,
+{
+	msgs.iter()
+		.filter_map(|msg| {
+			let type_url = msg.type_url.clone();
+			let msg = ibc_proto::google::protobuf::Any { type_url, value: msg.value.clone() };
+			let msg: Option<Ics26Envelope<Context<T>>> = msg.try_into().ok();
+			msg
+		})
+		.fold(0u64, |acc, msg| acc.saturating_add(wasm_message_gas::<T>(&msg)))
+}
+
+/// Weight of verifying a `client_message` submitted for the client `client_id`, either in an
+/// update or as misbehaviour. Returns `Weight::MAX` if the message does not match the client type.
+fn client_message_weight<T: Config>(
+	client_id: &ClientId,
+	client_message: AnyClientMessage,
+) -> Weight {
+	let client_type =
+		client_id.as_str().rsplit_once('-').map(|(client_type_str, ..)| client_type_str);
+	match client_type {
+		Some(ty) if ty.contains("tendermint") => match client_message {
+			AnyClientMessage::Tendermint(client_message) => match client_message {
+				ics07_tendermint::client_message::ClientMessage::Header(header) =>
+					<T as Config>::WeightInfo::update_tendermint_client(
+						header.signed_header.commit.signatures.len() as u32,
+					),
+				ics07_tendermint::client_message::ClientMessage::Misbehaviour(misbehaviour) =>
+					<T as Config>::WeightInfo::update_tendermint_client(
+						misbehaviour.header1.signed_header.commit.signatures.len() as u32,
+					)
+					.saturating_add(<T as Config>::WeightInfo::update_tendermint_client(
+						misbehaviour.header2.signed_header.commit.signatures.len() as u32,
+					)),
+			},
+			_ => Weight::MAX,
+		},
+		Some(ty) if ty.contains("grandpa") => match client_message {
+			AnyClientMessage::Grandpa(client_message) => match client_message {
+				ClientMessage::Header(header) => {
+					let justification = GrandpaJustification::<RelayChainHeader>::decode(
+						&mut &*header.finality_proof.justification,
+					)
+					.expect("Justification should be valid");
+					<T as Config>::WeightInfo::update_grandpa_client(
+						justification.commit.precommits.len() as u32,
+						header.finality_proof.unknown_headers.len() as u32,
+					)
+				},
+				ClientMessage::Misbehaviour(misbehaviour) => {
+					let justification_a = GrandpaJustification::<RelayChainHeader>::decode(
+						&mut &*misbehaviour.first_finality_proof.justification,
+					)
+					.expect("Justification should be valid");
+
+					let justification_b = GrandpaJustification::<RelayChainHeader>::decode(
+						&mut &*misbehaviour.second_finality_proof.justification,
+					)
+					.expect("Justification should be valid");
+
+					<T as Config>::WeightInfo::update_grandpa_client(
+						justification_a.commit.precommits.len() as u32,
+						misbehaviour.first_finality_proof.unknown_headers.len() as u32,
+					)
+					.saturating_add(<T as Config>::WeightInfo::update_grandpa_client(
+						justification_b.commit.precommits.len() as u32,
+						misbehaviour.second_finality_proof.unknown_headers.len() as u32,
+					))
+				},
+			},
+			_ => Weight::MAX,
+		},
+		// the gas of the contract is added by `deliver`, as for every message a wasm client
+		// verifies
+		Some(ty) if ty.contains("wasm") => match client_message {
+			AnyClientMessage::Wasm(_) => Weight::default(),
+			_ => Weight::MAX,
+		},
+		_ => Weight::default(),
+	}
+}
+
 pub(crate) fn deliver<T: Config + Send + Sync>(msgs: &[Any]) -> Weight
 where
 	u32: From<<T as frame_system::Config>::BlockNumber>,
@@ -192,72 +485,18 @@ where
 			msg
 		})
 		.fold(Weight::default(), |acc, msg| {
+			// Add the gas of the wasm client verifying the message
+			let wasm_gas = wasm::gas_weight(wasm_message_gas::<T>(&msg));
 			// Add benchmarked weight for that message type
 			// Add benchmarked weight for module callback
 			let temp = match msg {
 				Ics26Envelope::Ics2Msg(msgs) => match msgs {
 					ClientMsg::CreateClient(_) => <T as Config>::WeightInfo::create_client(),
-					ClientMsg::UpdateClient(msg) => {
-						let client_type = msg
-							.client_id
-							.as_str()
-							.rsplit_once('-')
-							.map(|(client_type_str, ..)| client_type_str);
-						match client_type {
-							Some(ty) if ty.contains("tendermint") => match msg.client_message {
-								AnyClientMessage::Tendermint(client_message) => match client_message {
-									ics07_tendermint::client_message::ClientMessage::Header(header) => {
-										<T as Config>::WeightInfo::update_tendermint_client(header.signed_header.commit.signatures.len() as u32)
-									}
-									ics07_tendermint::client_message::ClientMessage::Misbehaviour(misbehaviour) => {
-										<T as Config>::WeightInfo::update_tendermint_client(misbehaviour.header1.signed_header.commit.signatures.len() as u32).
-											saturating_add(<T as Config>::WeightInfo::update_tendermint_client(misbehaviour.header2.signed_header.commit.signatures.len() as u32))
-									}
-
-								}
-								_ => return Weight::MAX,
-							}
-							Some(ty) if ty.contains("grandpa") => match msg.client_message {
-								AnyClientMessage::Grandpa(client_message) => match client_message {
-									ClientMessage::Header(header) => {
-										let justification =
-											GrandpaJustification::<RelayChainHeader>::decode(
-												&mut &*header.finality_proof.justification,
-											)
-											.expect("Justification should be valid");
-										<T as Config>::WeightInfo::update_grandpa_client(
-											justification.commit.precommits.len() as u32,
-											header.finality_proof.unknown_headers.len() as u32,
-										)
-									},
-									ClientMessage::Misbehaviour(misbehaviour) => {
-										let justification_a =
-											GrandpaJustification::<RelayChainHeader>::decode(
-												&mut &*misbehaviour.first_finality_proof.justification,
-											)
-												.expect("Justification should be valid");
-
-										let justification_b =
-											GrandpaJustification::<RelayChainHeader>::decode(
-												&mut &*misbehaviour.second_finality_proof.justification,
-											)
-												.expect("Justification should be valid");
-
-										<T as Config>::WeightInfo::update_grandpa_client(
-											justification_a.commit.precommits.len() as u32,
-											misbehaviour.first_finality_proof.unknown_headers.len() as u32,
-										).saturating_add(<T as Config>::WeightInfo::update_grandpa_client(
-											justification_b.commit.precommits.len() as u32,
-											misbehaviour.second_finality_proof.unknown_headers.len() as u32,
-										))
-									},
-								},
-								_ => return Weight::MAX,
-							},
-							_ => Weight::default(),
-						}
-					},
+					ClientMsg::UpdateClient(msg) =>
+						client_message_weight::<T>(&msg.client_id, msg.client_message),
 					ClientMsg::UpgradeClient(_) => Weight::default(),
+					ClientMsg::Misbehaviour(msg) =>
+						client_message_weight::<T>(&msg.client_id, msg.misbehaviour),
 				},
 				Ics26Envelope::Ics3Msg(msgs) => match msgs {
 					ConnectionMsg::ConnectionOpenInit(_) =>
@@ -453,6 +692,20 @@ where
 						};
 						cb_weight.saturating_add(lc_verification_weight)
 					},
+					ChannelMsg::ChannelUpgradeInit(_) =>
+						<T as Config>::WeightInfo::channel_upgrade_init(),
+					ChannelMsg::ChannelUpgradeTry(_) =>
+						<T as Config>::WeightInfo::channel_upgrade_try(),
+					ChannelMsg::ChannelUpgradeAck(_) =>
+						<T as Config>::WeightInfo::channel_upgrade_ack(),
+					ChannelMsg::ChannelUpgradeConfirm(_) =>
+						<T as Config>::WeightInfo::channel_upgrade_confirm(),
+					ChannelMsg::ChannelUpgradeOpen(_) =>
+						<T as Config>::WeightInfo::channel_upgrade_open(),
+					ChannelMsg::ChannelUpgradeTimeout(_) =>
+						<T as Config>::WeightInfo::channel_upgrade_timeout(),
+					ChannelMsg::ChannelUpgradeCancel(_) =>
+						<T as Config>::WeightInfo::channel_upgrade_cancel(),
 				},
 				Ics26Envelope::Ics4PacketMsg(msgs) => match msgs {
 					PacketMsg::RecvPacket(packet_msg) => {
@@ -568,8 +821,44 @@ where
 						cb_weight.saturating_add(lc_verification_weight)
 					},
 				},
+				Ics26Envelope::Ics4PacketBatchMsg(batch_msg) => {
+					// Every packet is handled by its module, but the proof is verified only once.
+					let cb_weight =
+						batch_msg.packets.iter().fold(Weight::default(), |acc, packet| {
+							let cb =
+								WeightRouter::<T>::get_weight(packet.destination_port.as_str())
+									.unwrap_or_else(|| Box::new(()));
+							acc.saturating_add(cb.on_recv_packet(packet))
+						});
+					let lc_verification_weight = match batch_msg.packets.first().map(|packet| {
+						channel_client::<T>(
+							packet.destination_port.as_bytes(),
+							packet.destination_channel.to_string().as_bytes(),
+						)
+					}) {
+						Some(Ok(client_id)) => {
+							let client_type = client_id
+								.as_str()
+								.rsplit_once('-')
+								.map(|(client_type_str, ..)| client_type_str);
+							match client_type {
+								Some(ty) if ty.contains("tendermint") =>
+									<T as Config>::WeightInfo::recv_packet_tendermint(
+										batch_msg
+											.packets
+											.iter()
+											.map(|packet| packet.data.len() as u32)
+											.fold(0u32, u32::saturating_add),
+									),
+								_ => Weight::default(),
+							}
+						},
+						_ => Weight::default(),
+					};
+					cb_weight.saturating_add(lc_verification_weight)
+				},
 				_ => Weight::default(),
 			};
-			acc.saturating_add(temp)
+			acc.saturating_add(temp).saturating_add(wasm_gas)
 		})
 }
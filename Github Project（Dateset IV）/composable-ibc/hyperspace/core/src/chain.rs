@@ -53,7 +53,7 @@ use ibc_proto::{
 		channel::v1::{
 			QueryChannelResponse, QueryChannelsResponse, QueryNextSequenceReceiveResponse,
 			QueryPacketAcknowledgementResponse, QueryPacketCommitmentResponse,
-			QueryPacketReceiptResponse,
+			QueryPacketReceiptResponse, QueryUpgradeErrorResponse, QueryUpgradeResponse,
 		},
 		client::v1::{QueryClientStateResponse, QueryConsensusStateResponse},
 		connection::v1::{IdentifiedConnection, QueryConnectionResponse},
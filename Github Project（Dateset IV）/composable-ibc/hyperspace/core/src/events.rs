@@ -28,14 +28,20 @@ use ibc::{
 		},
 		ics04_channel::{
 			channel::{ChannelEnd, Counterparty as ChannelCounterparty, State},
+			events::{UpgradeConfirm, UpgradeOpen},
 			msgs::{
 				acknowledgement::MsgAcknowledgement, chan_close_confirm::MsgChannelCloseConfirm,
 				chan_open_ack::MsgChannelOpenAck, chan_open_confirm::MsgChannelOpenConfirm,
-				chan_open_try::MsgChannelOpenTry, recv_packet::MsgRecvPacket,
+				chan_open_try::MsgChannelOpenTry, chan_upgrade_ack::MsgChannelUpgradeAck,
+				chan_upgrade_cancel::MsgChannelUpgradeCancel,
+				chan_upgrade_confirm::MsgChannelUpgradeConfirm,
+				chan_upgrade_open::MsgChannelUpgradeOpen, chan_upgrade_try::MsgChannelUpgradeTry,
+				recv_packet::MsgRecvPacket,
 			},
+			upgrade::{ErrorReceipt, Upgrade},
 		},
 		ics23_commitment::commitment::{CommitmentPrefix, CommitmentProofBytes},
-		ics24_host::identifier::ConnectionId,
+		ics24_host::identifier::{ChannelId, ConnectionId, PortId},
 	},
 	events::{IbcEvent, IbcEventType},
 	proofs::{ConsensusProof, Proofs},
@@ -439,6 +445,219 @@ pub async fn parse_events(
 				let msg = Any { value, type_url: msg.type_url() };
 				messages.push(msg)
 			},
+			IbcEvent::UpgradeInitChannel(upgrade_init) => {
+				let (channel_end, upgrade, proofs) = query_channel_upgrade_with_proofs(
+					source,
+					upgrade_init.height(),
+					upgrade_init.port_id().clone(),
+					*upgrade_init.channel_id(),
+				)
+				.await?;
+				let counterparty = channel_end.counterparty();
+
+				// The sink upgrades to the other end of the connection proposed by the source
+				let connection_id =
+					upgrade.fields.connection_hops.get(0).cloned().ok_or_else(|| {
+						Error::Custom("Channel upgrade missing connection id".to_string())
+					})?;
+				let connection_response = source
+					.query_connection_end(upgrade_init.height(), connection_id.clone())
+					.await?;
+				let connection_end =
+					ConnectionEnd::try_from(connection_response.connection.ok_or_else(|| {
+						Error::Custom(format!("ConnectionEnd not found for {connection_id:?}"))
+					})?)?;
+				let counterparty_connection_id =
+					connection_end.counterparty().connection_id().cloned().ok_or_else(|| {
+						Error::Custom(format!(
+							"Connection counterparty not found for {connection_id:?}"
+						))
+					})?;
+
+				let msg = MsgChannelUpgradeTry {
+					port_id: counterparty.port_id.clone(),
+					channel_id: counterparty.channel_id.expect("Expect channel id to be set"),
+					proposed_upgrade_connection_hops: vec![counterparty_connection_id],
+					counterparty_upgrade_fields: upgrade.fields,
+					counterparty_upgrade_sequence: channel_end.upgrade_sequence,
+					proofs,
+					signer: sink.account_id(),
+				};
+
+				let value = msg.encode_vec()?;
+				let msg = Any { value, type_url: msg.type_url() };
+				messages.push(msg)
+			},
+			IbcEvent::UpgradeTryChannel(upgrade_try) => {
+				let (channel_end, upgrade, proofs) = query_channel_upgrade_with_proofs(
+					source,
+					upgrade_try.height(),
+					upgrade_try.port_id().clone(),
+					*upgrade_try.channel_id(),
+				)
+				.await?;
+				let counterparty = channel_end.counterparty();
+
+				let msg = MsgChannelUpgradeAck {
+					port_id: counterparty.port_id.clone(),
+					channel_id: counterparty.channel_id.expect("Expect channel id to be set"),
+					counterparty_upgrade: upgrade,
+					proofs,
+					signer: sink.account_id(),
+				};
+
+				let value = msg.encode_vec()?;
+				let msg = Any { value, type_url: msg.type_url() };
+				messages.push(msg)
+			},
+			IbcEvent::UpgradeAckChannel(upgrade_ack) => {
+				let (channel_end, upgrade, proofs) = query_channel_upgrade_with_proofs(
+					source,
+					upgrade_ack.height(),
+					upgrade_ack.port_id().clone(),
+					*upgrade_ack.channel_id(),
+				)
+				.await?;
+				let counterparty = channel_end.counterparty();
+
+				let msg = MsgChannelUpgradeConfirm {
+					port_id: counterparty.port_id.clone(),
+					channel_id: counterparty.channel_id.expect("Expect channel id to be set"),
+					counterparty_channel_state: channel_end.state,
+					counterparty_upgrade: upgrade,
+					proofs,
+					signer: sink.account_id(),
+				};
+
+				let value = msg.encode_vec()?;
+				let msg = Any { value, type_url: msg.type_url() };
+				messages.push(msg)
+			},
+			// Once the source has flushed its packets, or opened the upgraded channel, the sink
+			// can open its end if it is done flushing as well.
+			IbcEvent::UpgradeConfirmChannel(UpgradeConfirm(attributes)) |
+			IbcEvent::UpgradeOpenChannel(UpgradeOpen(attributes)) => {
+				let channel_response = source
+					.query_channel_end(
+						attributes.height,
+						attributes.channel_id,
+						attributes.port_id.clone(),
+					)
+					.await?;
+				let channel_end =
+					ChannelEnd::try_from(channel_response.channel.ok_or_else(|| {
+						Error::Custom(format!(
+							"[get_messages_for_events - chan_upgrade_open] ChannelEnd not found for {:?}/{:?}",
+							attributes.channel_id, attributes.port_id
+						))
+					})?)?;
+				if !matches!(channel_end.state, State::FlushComplete | State::Open) {
+					continue
+				}
+				let counterparty = channel_end.counterparty();
+				let counterparty_channel_id =
+					counterparty.channel_id.expect("Expect channel id to be set");
+
+				let (sink_height, _) = sink.latest_height_and_timestamp().await?;
+				let sink_channel_response = sink
+					.query_channel_end(
+						sink_height,
+						counterparty_channel_id,
+						counterparty.port_id.clone(),
+					)
+					.await?;
+				let sink_channel_state = sink_channel_response.channel.map(|channel| channel.state);
+				if sink_channel_state != Some(State::FlushComplete as i32) {
+					log::debug!(
+						target: "hyperspace",
+						"Skipping channel upgrade open, counterparty channel is not done flushing"
+					);
+					continue
+				}
+
+				let channel_proof = CommitmentProofBytes::try_from(channel_response.proof)?;
+				let proof_height =
+					channel_response.proof_height.expect("Proof height should be present");
+				let proof_height =
+					Height::new(proof_height.revision_number, proof_height.revision_height);
+
+				let msg = MsgChannelUpgradeOpen {
+					port_id: counterparty.port_id.clone(),
+					channel_id: counterparty_channel_id,
+					counterparty_channel_state: channel_end.state,
+					counterparty_upgrade_sequence: channel_end.upgrade_sequence,
+					proofs: Proofs::new(channel_proof, None, None, None, proof_height)?,
+					signer: sink.account_id(),
+				};
+
+				let value = msg.encode_vec()?;
+				let msg = Any { value, type_url: msg.type_url() };
+				messages.push(msg)
+			},
+			IbcEvent::UpgradeErrorChannel(upgrade_error) => {
+				let port_id = upgrade_error.port_id().clone();
+				let channel_id = *upgrade_error.channel_id();
+				let channel_response = source
+					.query_channel_end(upgrade_error.height(), channel_id, port_id.clone())
+					.await?;
+				let channel_end =
+					ChannelEnd::try_from(channel_response.channel.ok_or_else(|| {
+						Error::Custom(format!(
+							"[get_messages_for_events - chan_upgrade_error] ChannelEnd not found for {:?}/{:?}",
+							channel_id, port_id
+						))
+					})?)?;
+				let counterparty = channel_end.counterparty();
+				let counterparty_channel_id =
+					counterparty.channel_id.expect("Expect channel id to be set");
+
+				// Only cancel the upgrade if the sink still has one in progress
+				let (sink_height, _) = sink.latest_height_and_timestamp().await?;
+				let sink_upgrade = sink
+					.query_channel_upgrade(
+						sink_height,
+						counterparty_channel_id,
+						counterparty.port_id.clone(),
+					)
+					.await
+					.ok()
+					.and_then(|response| response.upgrade)
+					.and_then(|upgrade| upgrade.fields);
+				if sink_upgrade.is_none() {
+					continue
+				}
+
+				let error_response = source
+					.query_channel_upgrade_error(
+						upgrade_error.height(),
+						channel_id,
+						port_id.clone(),
+					)
+					.await?;
+				let error_receipt =
+					ErrorReceipt::try_from(error_response.error_receipt.ok_or_else(|| {
+						Error::Custom(format!(
+							"Upgrade error receipt not found for {channel_id:?}/{port_id:?}"
+						))
+					})?)?;
+				let error_proof = CommitmentProofBytes::try_from(error_response.proof)?;
+				let proof_height =
+					error_response.proof_height.expect("Proof height should be present");
+				let proof_height =
+					Height::new(proof_height.revision_number, proof_height.revision_height);
+
+				let msg = MsgChannelUpgradeCancel {
+					port_id: counterparty.port_id.clone(),
+					channel_id: counterparty_channel_id,
+					error_receipt,
+					proofs: Some(Proofs::new(error_proof, None, None, None, proof_height)?),
+					signer: sink.account_id(),
+				};
+
+				let value = msg.encode_vec()?;
+				let msg = Any { value, type_url: msg.type_url() };
+				messages.push(msg)
+			},
 			IbcEvent::SendPacket(send_packet) => {
 				#[cfg(feature = "testing")]
 				if !packet_relay_status() {
@@ -582,6 +801,31 @@ pub async fn parse_events(
 	Ok(messages)
 }
 
+/// Fetch a channel end along with its proposed upgrade, and the proofs of both at the given height.
+async fn query_channel_upgrade_with_proofs(
+	source: &impl Chain,
+	at: Height,
+	port_id: PortId,
+	channel_id: ChannelId,
+) -> Result<(ChannelEnd, Upgrade, Proofs), anyhow::Error> {
+	let channel_response = source.query_channel_end(at, channel_id, port_id.clone()).await?;
+	let channel_end = ChannelEnd::try_from(channel_response.channel.ok_or_else(|| {
+		Error::Custom(format!("ChannelEnd not found for {channel_id:?}/{port_id:?}"))
+	})?)?;
+	let upgrade_response = source.query_channel_upgrade(at, channel_id, port_id.clone()).await?;
+	let upgrade = Upgrade::try_from(upgrade_response.upgrade.ok_or_else(|| {
+		Error::Custom(format!("Channel upgrade not found for {channel_id:?}/{port_id:?}"))
+	})?)?;
+
+	let channel_proof = CommitmentProofBytes::try_from(channel_response.proof)?;
+	let upgrade_proof = CommitmentProofBytes::try_from(upgrade_response.proof)?;
+	let proof_height = channel_response.proof_height.expect("Proof height should be present");
+	let proof_height = Height::new(proof_height.revision_number, proof_height.revision_height);
+	let proofs = Proofs::new(channel_proof, None, None, Some(upgrade_proof), proof_height)?;
+
+	Ok((channel_end, upgrade, proofs))
+}
+
 /// Fetch the consensus state proof for the sink chain.
 async fn query_host_consensus_state_proof(
 	sink: &impl Chain,
@@ -188,6 +188,43 @@ macro_rules! chains {
 				}
 			}
 
+			async fn query_channel_upgrade(
+				&self,
+				at: Height,
+				channel_id: ChannelId,
+				port_id: PortId,
+			) -> Result<QueryUpgradeResponse, Self::Error> {
+				match self {
+					$(
+						$(#[$($meta)*])*
+						Self::$name(chain) => chain
+							.query_channel_upgrade(at, channel_id, port_id)
+							.await
+							.map_err(AnyError::$name),
+					)*
+					AnyChain::Wasm(c) => c.inner.query_channel_upgrade(at, channel_id, port_id).await,
+				}
+			}
+
+			async fn query_channel_upgrade_error(
+				&self,
+				at: Height,
+				channel_id: ChannelId,
+				port_id: PortId,
+			) -> Result<QueryUpgradeErrorResponse, Self::Error> {
+				match self {
+					$(
+						$(#[$($meta)*])*
+						Self::$name(chain) => chain
+							.query_channel_upgrade_error(at, channel_id, port_id)
+							.await
+							.map_err(AnyError::$name),
+					)*
+					AnyChain::Wasm(c) =>
+						c.inner.query_channel_upgrade_error(at, channel_id, port_id).await,
+				}
+			}
+
 			async fn query_proof(&self, at: Height, keys: Vec<Vec<u8>>) -> Result<Vec<u8>, Self::Error> {
 				match self {
 					$(
@@ -17,6 +17,7 @@ use crate::send_packet_relay::packet_relay_status;
 use rand::Rng;
 use sp_runtime::Either::{Left, Right};
 use std::{
+	collections::BTreeMap,
 	sync::{
 		atomic::{AtomicUsize, Ordering},
 		Arc,
@@ -26,15 +27,15 @@ use std::{
 use tokio::{task::JoinSet, time::sleep};
 
 use crate::packets::utils::{
-	construct_ack_message, construct_recv_message, construct_timeout_message,
-	get_timeout_proof_height, verify_delay_passed, VerifyDelayOn,
+	construct_ack_message, construct_recv_message, construct_recv_packets_message,
+	construct_timeout_message, get_timeout_proof_height, verify_delay_passed, VerifyDelayOn,
 };
 use ibc::{
 	applications::transfer::packet::PacketData,
 	core::{
 		ics02_client::client_state::ClientState as ClientStateT,
 		ics03_connection::connection::ConnectionEnd,
-		ics04_channel::channel::{ChannelEnd, State},
+		ics04_channel::channel::{ChannelEnd, Order, State},
 	},
 	Height,
 };
@@ -206,11 +207,13 @@ pub async fn query_ready_and_timed_out_packets(
 		let sink = Arc::new(sink.clone());
 		let timeout_packets_count = Arc::new(AtomicUsize::new(0));
 		let send_packets_count = Arc::new(AtomicUsize::new(0));
+		let batch_recv_packets = sink.common_state().batch_recv_packets;
 		for send_packets in send_packets.chunks(PROCESS_PACKETS_BATCH_SIZE) {
 			for send_packet in send_packets.iter().cloned() {
 				let source_connection_end = source_connection_end.clone();
 				let sink_channel_end = sink_channel_end.clone();
 				let source_connection_end = source_connection_end.clone();
+				let sink_port_id = sink_port_id.clone();
 				let source = source.clone();
 				let sink = sink.clone();
 				let duration = Duration::from_millis(
@@ -228,13 +231,39 @@ pub async fn query_ready_and_timed_out_packets(
 						Error::Custom(format!("Packet height not found for packet {packet:?}"))
 					})?;
 
-					if packet.timed_out(&sink_timestamp, sink_height) {
+					// On an open `ORDERED_ALLOW_TIMEOUT` channel the sink has to skip a timed out packet
+					// first, which writes the timeout receipt that the source verifies.
+					let needs_timeout_receipt = sink_channel_end.ordering ==
+						Order::OrderedAllowTimeout &&
+						sink_channel_end.state == State::Open;
+					let skipped_on_sink = u64::from(packet.sequence) <
+						next_sequence_recv.next_sequence_receive;
+
+					if packet.timed_out(&sink_timestamp, sink_height) &&
+						(!needs_timeout_receipt || skipped_on_sink)
+					{
 						timeout_packets_count.fetch_add(1, Ordering::SeqCst);
 						// so we know this packet has timed out on the sink, we need to find the maximum
 						// consensus state height at which we can generate a non-membership proof of the
 						// packet for the sink's client on the source.
-						let proof_height =
-							if let Some(proof_height) = get_timeout_proof_height(
+						let proof_height = if needs_timeout_receipt {
+							// the timeout receipt can only be proven once the sink's client on the source
+							// has seen the packet being skipped
+							let next_sequence_recv_on_source = sink
+								.query_next_sequence_recv(
+									latest_sink_height_on_source,
+									&sink_port_id,
+									&sink_channel_id,
+								)
+								.await?;
+							if u64::from(packet.sequence) >=
+								next_sequence_recv_on_source.next_sequence_receive
+							{
+								log::trace!(target: "hyperspace", "Skipping packet as the timeout receipt is not yet provable: {:?}", packet);
+								return Ok(None)
+							}
+							latest_sink_height_on_source
+						} else if let Some(proof_height) = get_timeout_proof_height(
 								&**source,
 								&**sink,
 								source_height,
@@ -281,6 +310,8 @@ pub async fn query_ready_and_timed_out_packets(
 						)
 							.await?;
 						return Ok(Some(Left(msg)))
+					} else if needs_timeout_receipt && packet.timed_out(&sink_timestamp, sink_height) {
+						log::debug!(target: "hyperspace", "Delivering timed out packet so that the sink writes a timeout receipt: {:?}", packet);
 					} else {
 						log::trace!(target: "hyperspace", "The packet has not timed out yet: {:?}", packet);
 					}
@@ -363,20 +394,36 @@ pub async fn query_ready_and_timed_out_packets(
 						return Ok(None)
 					}
 
+					if batch_recv_packets {
+						return Ok(Some(Right(Right((packet, proof_height)))))
+					}
+
 					let msg = construct_recv_message(&**source, &**sink, packet, proof_height).await?;
-					Ok(Some(Right(msg)))
+					Ok(Some(Right(Left(msg))))
 				});
 			}
 		}
 
+		let mut batched_packets = BTreeMap::<Height, Vec<_>>::new();
 		while let Some(result) = recv_packets_join_set.join_next().await {
 			let Some(either) = result?? else { continue };
 			match either {
 				Left(msg) => timeout_messages.push(msg),
-				Right(msg) => messages.push(msg),
+				Right(Left(msg)) => messages.push(msg),
+				Right(Right((packet, proof_height))) =>
+					batched_packets.entry(proof_height).or_default().push(packet),
 			}
 		}
 
+		// Packets proven at the same height share a single multi-key proof.
+		for (proof_height, mut packets) in batched_packets {
+			packets.sort_by_key(|packet| packet.sequence);
+			log::debug!(target: "hyperspace", "Batching {} packets at proof height {proof_height}", packets.len());
+			let msg =
+				construct_recv_packets_message(&*source, &*sink, packets, proof_height).await?;
+			messages.push(msg);
+		}
+
 		let timeouts_count = timeout_packets_count.load(Ordering::SeqCst);
 		log::debug!(target: "hyperspace", "Found {timeouts_count} packets that have timed out");
 		source
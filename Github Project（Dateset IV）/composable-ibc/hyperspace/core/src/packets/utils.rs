@@ -21,7 +21,8 @@ use ibc::{
 			context::calculate_block_delay,
 			msgs::{
 				acknowledgement::MsgAcknowledgement, recv_packet::MsgRecvPacket,
-				timeout::MsgTimeout, timeout_on_close::MsgTimeoutOnClose,
+				recv_packets::MsgRecvPackets, timeout::MsgTimeout,
+				timeout_on_close::MsgTimeoutOnClose,
 			},
 			packet::{Packet, TimeoutVariant},
 		},
@@ -236,10 +237,12 @@ pub async fn construct_timeout_message(
 	next_sequence_recv: u64,
 	proof_height: Height,
 ) -> Result<Any, anyhow::Error> {
-	let path_type = if sink_channel_end.ordering == Order::Ordered {
-		KeyPathType::SeqRecv
-	} else {
-		KeyPathType::ReceiptPath
+	// An open `ORDERED_ALLOW_TIMEOUT` channel proves the timeout receipt written by the sink
+	let path_type = match sink_channel_end.ordering {
+		Order::Unordered => KeyPathType::ReceiptPath,
+		Order::OrderedAllowTimeout if sink_channel_end.state != State::Closed =>
+			KeyPathType::ReceiptPath,
+		Order::Ordered | Order::OrderedAllowTimeout => KeyPathType::SeqRecv,
 	};
 	let key = get_key_path(path_type, &packet).into_bytes();
 
@@ -299,6 +302,31 @@ pub async fn construct_recv_message(
 	Ok(msg)
 }
 
+/// Constructs a single `MsgRecvPackets` for packets sent on the same channel, with one multi-key
+/// proof of all their commitments at `proof_height`.
+pub async fn construct_recv_packets_message(
+	source: &impl Chain,
+	sink: &impl Chain,
+	packets: Vec<Packet>,
+	proof_height: Height,
+) -> Result<Any, anyhow::Error> {
+	let keys = packets
+		.iter()
+		.map(|packet| get_key_path(KeyPathType::CommitmentPath, packet).into_bytes())
+		.collect();
+	let proof = source.query_proof(proof_height, keys).await?;
+	let commitment_proof = CommitmentProofBytes::try_from(proof)?;
+	let actual_proof_height = source.get_proof_height(proof_height).await;
+	let msg = MsgRecvPackets {
+		packets,
+		proofs: Proofs::new(commitment_proof, None, None, None, actual_proof_height)?,
+		signer: sink.account_id(),
+	};
+	let value = msg.encode_vec()?;
+	let msg = Any { value, type_url: msg.type_url() };
+	Ok(msg)
+}
+
 pub async fn construct_ack_message(
 	source: &impl Chain,
 	sink: &impl Chain,
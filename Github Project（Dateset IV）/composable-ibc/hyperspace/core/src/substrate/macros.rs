@@ -490,6 +490,25 @@ macro_rules! define_ibc_event_wrapper {
 					MetadataIbcEvent::PushWasmCode{ wasm_checksum } => RawIbcEvent::PushWasmCode {
 						wasm_checksum
 					},
+					MetadataIbcEvent::ChannelUpgrade {
+						kind,
+						revision_height,
+						revision_number,
+						port_id,
+						channel_id,
+						counterparty_port_id,
+						counterparty_channel_id,
+						upgrade_sequence,
+					} => RawIbcEvent::ChannelUpgrade {
+						kind,
+						revision_height,
+						revision_number,
+						port_id,
+						channel_id,
+						counterparty_port_id,
+						counterparty_channel_id,
+						upgrade_sequence,
+					},
 					$($additional)*
 				}
 			}
@@ -307,6 +307,7 @@ where
 				misbehaviour_client_msg_queue: Arc::new(AsyncMutex::new(vec![])),
 				max_packets_to_process: config.common.max_packets_to_process as usize,
 				skip_tokens_list: config.skip_tokens_list.unwrap_or_default(),
+				batch_recv_packets: false,
 			},
 			join_handles: Arc::new(TokioMutex::new(vec![ws_driver_jh])),
 		})
@@ -22,8 +22,9 @@ use ibc::{
 		ics24_host::{
 			identifier::{ChainId, ChannelId, ClientId, ConnectionId, PortId},
 			path::{
-				AcksPath, ChannelEndsPath, ClientConsensusStatePath, ClientStatePath,
-				CommitmentsPath, ConnectionsPath, Path, ReceiptsPath, SeqRecvsPath, SeqSendsPath,
+				AcksPath, ChannelEndsPath, ChannelUpgradeErrorPath, ChannelUpgradePath,
+				ClientConsensusStatePath, ClientStatePath, CommitmentsPath, ConnectionsPath, Path,
+				ReceiptsPath, SeqRecvsPath, SeqSendsPath,
 			},
 		},
 	},
@@ -40,11 +41,13 @@ use ibc_proto::{
 	google::protobuf::Any,
 	ibc::core::{
 		channel::v1::{
-			Channel, QueryChannelResponse, QueryChannelsRequest, QueryChannelsResponse,
-			QueryConnectionChannelsRequest, QueryNextSequenceReceiveResponse,
-			QueryPacketAcknowledgementResponse, QueryPacketAcknowledgementsRequest,
-			QueryPacketCommitmentResponse, QueryPacketCommitmentsRequest,
-			QueryPacketReceiptResponse, QueryUnreceivedAcksRequest, QueryUnreceivedPacketsRequest,
+			Channel, ErrorReceipt, QueryChannelResponse, QueryChannelsRequest,
+			QueryChannelsResponse, QueryConnectionChannelsRequest,
+			QueryNextSequenceReceiveResponse, QueryPacketAcknowledgementResponse,
+			QueryPacketAcknowledgementsRequest, QueryPacketCommitmentResponse,
+			QueryPacketCommitmentsRequest, QueryPacketReceiptResponse, QueryUnreceivedAcksRequest,
+			QueryUnreceivedPacketsRequest, QueryUpgradeErrorResponse, QueryUpgradeResponse,
+			Upgrade as RawUpgrade,
 		},
 		client::v1::{
 			QueryClientStateResponse, QueryClientStatesRequest, QueryConsensusStateResponse,
@@ -366,6 +369,42 @@ where
 		})
 	}
 
+	async fn query_channel_upgrade(
+		&self,
+		at: Height,
+		channel_id: ChannelId,
+		port_id: PortId,
+	) -> Result<QueryUpgradeResponse, Self::Error> {
+		let path_bytes = Path::ChannelUpgrades(ChannelUpgradePath(port_id, channel_id))
+			.to_string()
+			.into_bytes();
+		let (q, proof) = self.query_path(path_bytes, at, true).await?;
+		let upgrade = RawUpgrade::decode(&*q.value)?;
+		Ok(QueryUpgradeResponse {
+			upgrade: Some(upgrade),
+			proof,
+			proof_height: increment_proof_height(Some(at.into())),
+		})
+	}
+
+	async fn query_channel_upgrade_error(
+		&self,
+		at: Height,
+		channel_id: ChannelId,
+		port_id: PortId,
+	) -> Result<QueryUpgradeErrorResponse, Self::Error> {
+		let path_bytes = Path::ChannelUpgradeErrors(ChannelUpgradeErrorPath(port_id, channel_id))
+			.to_string()
+			.into_bytes();
+		let (q, proof) = self.query_path(path_bytes, at, true).await?;
+		let error_receipt = ErrorReceipt::decode(&*q.value)?;
+		Ok(QueryUpgradeErrorResponse {
+			error_receipt: Some(error_receipt),
+			proof,
+			proof_height: increment_proof_height(Some(at.into())),
+		})
+	}
+
 	async fn query_proof(&self, at: Height, keys: Vec<Vec<u8>>) -> Result<Vec<u8>, Self::Error> {
 		let (_, proof) = self.query_path(keys[0].clone(), at, true).await?;
 		Ok(proof)
@@ -704,8 +743,8 @@ where
 					match ev {
 						Ok(IbcEvent::SendPacket(p))
 							if seqs.contains(&p.packet.sequence.0) &&
-								p.packet.source_port == port_id && p.packet.source_channel ==
-								channel_id =>
+								p.packet.source_port == port_id &&
+								p.packet.source_channel == channel_id =>
 						{
 							let seq = p.packet.sequence.0;
 							let mut info = PacketInfo::try_from(IbcPacketInfo::from(p.packet))
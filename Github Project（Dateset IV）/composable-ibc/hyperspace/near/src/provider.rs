@@ -35,7 +35,7 @@ use ibc_proto::ibc::core::{
 	channel::v1::{
 		Packet as RawPacket, QueryChannelResponse, QueryNextSequenceReceiveResponse,
 		QueryPacketAcknowledgementResponse, QueryPacketCommitmentResponse,
-		QueryPacketReceiptResponse,
+		QueryPacketReceiptResponse, QueryUpgradeErrorResponse, QueryUpgradeResponse,
 	},
 	client::v1::{QueryClientStateResponse, QueryConsensusStateResponse},
 	connection::v1::QueryConnectionResponse,
@@ -178,6 +178,24 @@ impl IbcProvider for Client {
 		self.send_query(query).await
 	}
 
+	async fn query_channel_upgrade(
+		&self,
+		_at: Height,
+		_channel_id: ChannelId,
+		_port_id: PortId,
+	) -> Result<QueryUpgradeResponse, Self::Error> {
+		todo!()
+	}
+
+	async fn query_channel_upgrade_error(
+		&self,
+		_at: Height,
+		_channel_id: ChannelId,
+		_port_id: PortId,
+	) -> Result<QueryUpgradeErrorResponse, Self::Error> {
+		todo!()
+	}
+
 	async fn query_proof(&self, at: Height, keys: Vec<Vec<u8>>) -> Result<Vec<u8>, Self::Error> {
 		let args = (keys,);
 		let query = self.make_contract_query_at(at.revision_height, "query_proof", &args)?;
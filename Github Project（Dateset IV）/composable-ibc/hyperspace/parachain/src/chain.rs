@@ -15,6 +15,8 @@
 use super::{error::Error, signer::ExtrinsicSigner, ParachainClient};
 use crate::{parachain::UncheckedExtrinsic, provider::TransactionId, FinalityProtocol};
 use anyhow::anyhow;
+use beefy_light_client_primitives::{ClientState as BeefyPrimitivesClientState, NodesUtils};
+use beefy_prover::relay_chain_queries::fetch_beefy_justification;
 use codec::{Decode, Encode};
 use finality_grandpa::BlockNumberOps;
 use finality_grandpa_rpc::GrandpaApiClient;
@@ -34,10 +36,13 @@ use ibc::{
 };
 use ibc_proto::google::protobuf::Any;
 use ics10_grandpa::client_message::{ClientMessage, Misbehaviour, RelayChainHeader};
+use ics11_beefy::client_message::{
+	BeefyHeader, ClientMessage as BeefyClientMessage, ParachainHeadersWithProof,
+};
 use itertools::Itertools;
 use jsonrpsee_ws_client::WsClientBuilder;
 use light_client_common::config::{EventRecordT, RuntimeCall, RuntimeTransactions};
-use pallet_ibc::light_clients::AnyClientMessage;
+use pallet_ibc::light_clients::{AnyClientMessage, AnyClientState};
 use primitives::{
 	mock::LocalClientTypes, Chain, CommonClientState, IbcProvider, MisbehaviourHandler,
 };
@@ -393,6 +398,7 @@ where
 	<T as subxt::Config>::Signature: From<MultiSignature> + Send + Sync,
 	<<T as subxt::Config>::Header as Header>::Number:
 		BlockNumberOps + From<u32> + Display + Ord + sp_runtime::traits::Zero + One,
+	<T as subxt::Config>::Header: Decode,
 	T::Hash: From<sp_core::H256> + From<[u8; 32]>,
 	BTreeMap<sp_core::H256, ParachainHeaderProofs>:
 		From<BTreeMap<<T as subxt::Config>::Hash, ParachainHeaderProofs>>,
@@ -512,6 +518,102 @@ where
 						.await?;
 				}
 			},
+			AnyClientMessage::Beefy(BeefyClientMessage::Header(header)) => {
+				let Some(headers_with_proof) = header.headers_with_proof else { return Ok(()) };
+
+				// compare the parachain headers of the update with the canonical ones
+				let mut forged_headers = vec![];
+				let mut lowest_relay_parent = u32::MAX;
+				for para_header in headers_with_proof.headers {
+					let number = para_header.parachain_header.number;
+					let canonical_hash =
+						self.para_client.rpc().block_hash(Some(number.into())).await?.ok_or_else(
+							|| anyhow!("No hash found for parachain block: {number}"),
+						)?;
+					let header_hash =
+						sp_runtime::traits::Header::hash(&para_header.parachain_header);
+					if header_hash != H256::from(canonical_hash) {
+						log::warn!(
+							"Found misbehaviour on client {}: parachain header #{number} {:?} != {:?}",
+							self.client_id
+								.lock()
+								.unwrap()
+								.as_ref()
+								.map(|x| x.as_str().to_owned())
+								.unwrap_or_else(|| "{unknown}".to_owned()),
+							header_hash,
+							canonical_hash
+						);
+						forged_headers.push(number.into());
+						lowest_relay_parent = lowest_relay_parent
+							.min(para_header.partial_mmr_leaf.parent_number_and_hash.0);
+					}
+				}
+				if forged_headers.is_empty() {
+					return Ok(())
+				}
+
+				let latest_height = counterparty.latest_height_and_timestamp().await?.0;
+				let response =
+					counterparty.query_client_state(latest_height, self.client_id()).await?;
+				let client_state = response
+					.client_state
+					.and_then(|client_state| {
+						AnyClientState::decode_recursive(client_state, |c| {
+							matches!(c, AnyClientState::Beefy(_))
+						})
+					})
+					.ok_or_else(|| anyhow!("Could not decode the client state"))?;
+				let AnyClientState::Beefy(client_state) = client_state else { unreachable!() };
+
+				// The canonical headers are proven against the latest commitment finalized by
+				// BEEFY, the light client freezes once it finds their consensus states differ
+				// from the forged ones it already stored.
+				let (signed_commitment, _) = fetch_beefy_justification(&self.relay_client)
+					.await
+					.map_err(|e| anyhow!("Failed to fetch the beefy justification: {:?}", e))?;
+				let beefy_client_state = BeefyPrimitivesClientState {
+					// the canonical headers are searched from the relay chain block the forged
+					// ones claim to be included in
+					latest_beefy_height: lowest_relay_parent,
+					mmr_root_hash: client_state.mmr_root_hash,
+					current_authorities: client_state.authority.clone(),
+					next_authorities: client_state.next_authority_set.clone(),
+				};
+				let (headers, batch_proof) = self
+					.query_beefy_finalized_parachain_headers_with_proof(
+						signed_commitment.commitment.block_number,
+						&beefy_client_state,
+						forged_headers,
+					)
+					.await?;
+				let mmr_update = self.query_beefy_mmr_update_proof(signed_commitment).await?;
+
+				let misbehaviour = BeefyClientMessage::Header(BeefyHeader {
+					headers_with_proof: Some(ParachainHeadersWithProof {
+						headers,
+						mmr_size: NodesUtils::new(batch_proof.leaf_count).size(),
+						leaf_indices: batch_proof.leaf_indices,
+						mmr_proofs: batch_proof
+							.items
+							.into_iter()
+							.map(|item| item.encode())
+							.collect(),
+						leaf_count: batch_proof.leaf_count,
+					}),
+					mmr_update_proof: Some(mmr_update),
+				});
+
+				counterparty
+					.submit(vec![MsgUpdateAnyClient::<LocalClientTypes>::new(
+						self.client_id(),
+						AnyClientMessage::Beefy(misbehaviour),
+						counterparty.account_id(),
+					)
+					.to_any()])
+					.map_err(|e| anyhow!("Failed to submit misbehaviour report: {:?}", e))
+					.await?;
+			},
 			_ => {},
 		}
 		Ok(())
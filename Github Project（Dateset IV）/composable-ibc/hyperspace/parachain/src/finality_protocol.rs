@@ -22,7 +22,7 @@ use finality_grandpa::BlockNumberOps;
 use finality_grandpa_rpc::GrandpaApiClient;
 use grandpa_light_client_primitives::{
 	justification::find_scheduled_change, FinalityProof, ParachainHeaderProofs,
-	ParachainHeadersWithFinalityProof,
+	ParachainHeadersWithFinalityProof, SolochainHeadersWithFinalityProof,
 };
 use ibc::{
 	core::ics02_client::{client_state::ClientState as _, msgs::update_client::MsgUpdateAnyClient},
@@ -32,7 +32,10 @@ use ibc::{
 };
 use ibc_proto::google::protobuf::Any;
 use ibc_rpc::{BlockNumberOrHash, IbcApiClient};
-use ics10_grandpa::client_message::{ClientMessage, Header as GrandpaHeader};
+use ics10_grandpa::{
+	client_message::{ClientMessage, Header as GrandpaHeader},
+	client_state::ChainType,
+};
 use ics11_beefy::client_message::{
 	BeefyHeader, ClientMessage as BeefyClientMessage, ParachainHeadersWithProof,
 };
@@ -204,12 +207,14 @@ where
 		)
 		.await?;
 
-	log::info!(
-		"Fetching events from {} for blocks {:?}..{:?}",
-		source.name(),
-		headers[0].number(),
-		headers.last().unwrap().number()
-	);
+	if let (Some(first), Some(last)) = (headers.first(), headers.last()) {
+		log::info!(
+			"Fetching events from {} for blocks {:?}..{:?}",
+			source.name(),
+			first.number(),
+			last.number()
+		);
+	}
 
 	// Get finalized parachain block numbers, but only those higher than the latest para
 	// height recorded in the on-chain client state, because in some cases a parachain
@@ -341,6 +346,16 @@ where
 
 	let mmr_update = source.query_beefy_mmr_update_proof(signed_commitment).await?;
 
+	// the latest parachain height known to the light client once the update is applied
+	let latest_para_height = headers_with_proof
+		.iter()
+		.flat_map(|headers_with_proof| headers_with_proof.headers.iter())
+		.map(|header| header.parachain_header.number as u64)
+		.chain([client_state.latest_height().revision_height])
+		.max()
+		.unwrap_or_default();
+	let height = Height::new(source.para_id as u64, latest_para_height);
+
 	let update_header = {
 		let msg = MsgUpdateAnyClient::<LocalClientTypes> {
 			client_id: source.client_id(),
@@ -354,8 +369,7 @@ where
 		Any { value, type_url: msg.type_url() }
 	};
 
-	// FIXME: use height from the beefy header
-	Ok(vec![(update_header, Height::new(0, 0), events, update_type)])
+	Ok(vec![(update_header, height, events, update_type)])
 }
 
 async fn find_next_justification<T>(
@@ -540,12 +554,14 @@ where
 	let justification = justification;
 
 	// fetch the latest finalized parachain header
-	let finalized_para_header = prover
-		.query_latest_finalized_parachain_header(justification.commit.target_number)
-		.await?;
+	let finalized_para_height = query_latest_finalized_header_number(
+		&prover,
+		source.chain_type,
+		justification.commit.target_number,
+	)
+	.await?;
 
 	// notice the inclusive range
-	let finalized_para_height = u32::from(finalized_para_header.number());
 	let finalized_blocks =
 		((client_state.latest_para_height + 1)..=finalized_para_height).collect::<Vec<_>>();
 
@@ -641,22 +657,25 @@ where
 
 	// We ensure we advance the finalized latest parachain height
 	if client_state.latest_para_height < finalized_para_height {
-		headers_with_events.insert(finalized_para_header.number());
+		headers_with_events
+			.insert(<<T as subxt::Config>::Header as Header>::Number::from(finalized_para_height));
 	}
 
-	let ParachainHeadersWithFinalityProof { finality_proof, parachain_headers, .. } = prover
-		.query_finalized_parachain_headers_with_proof::<T::Header>(
-			client_state.latest_relay_height,
-			justification.commit.target_number,
-			Some(justification.encode()),
-			headers_with_events.into_iter().collect(),
-		)
-		.await?;
+	let grandpa_header = query_grandpa_header(
+		&prover,
+		source.chain_type,
+		client_state.latest_relay_height,
+		justification.commit.target_number,
+		Some(justification.encode()),
+		headers_with_events.into_iter().collect(),
+		Height::new(source.para_id as u64, finalized_para_height as u64),
+	)
+	.await?;
 
 	let target = source
 		.relay_client
 		.rpc()
-		.header(Some(finality_proof.block.into()))
+		.header(Some(grandpa_header.finality_proof.block.into()))
 		.await?
 		.ok_or_else(|| {
 			Error::from("Could not find relay chain header for justification target".to_string())
@@ -674,12 +693,6 @@ where
 			false => UpdateType::Optional,
 		};
 
-	let grandpa_header = GrandpaHeader {
-		finality_proof: codec::Decode::decode(&mut &*finality_proof.encode())
-			.expect("Same struct from different crates,decode should not fail"),
-		parachain_headers: parachain_headers.into(),
-		height: Height::new(source.para_id as u64, finalized_para_height as u64),
-	};
 	let height = grandpa_header.height();
 	let update_header = {
 		let msg = MsgUpdateAnyClient::<LocalClientTypes> {
@@ -693,3 +706,90 @@ where
 
 	Ok(vec![(update_header, height, events, update_type)])
 }
+
+/// Returns the number of the latest header of the chain tracked by the GRANDPA client that is
+/// finalized at the given GRANDPA finalized height. For standalone chains this is the finalized
+/// height itself.
+pub async fn query_latest_finalized_header_number<T>(
+	prover: &GrandpaProver<T>,
+	chain_type: ChainType,
+	latest_finalized_height: u32,
+) -> Result<u32, anyhow::Error>
+where
+	T: light_client_common::config::Config + Send + Sync,
+	<<T as subxt::Config>::Header as Header>::Number: Ord + sp_runtime::traits::Zero,
+	u32: From<<<T as subxt::Config>::Header as Header>::Number>,
+	sp_core::H256: From<T::Hash>,
+	<T as subxt::Config>::Header: Decode,
+{
+	match chain_type {
+		ChainType::Parachain => {
+			let finalized_para_header =
+				prover.query_latest_finalized_parachain_header(latest_finalized_height).await?;
+			Ok(u32::from(finalized_para_header.number()))
+		},
+		ChainType::Solochain => Ok(latest_finalized_height),
+	}
+}
+
+/// Constructs the GRANDPA header proving the finality of the given headers of the chain tracked by
+/// the client, finalized by `latest_finalized_height`.
+pub async fn query_grandpa_header<T>(
+	prover: &GrandpaProver<T>,
+	chain_type: ChainType,
+	previous_finalized_height: u32,
+	latest_finalized_height: u32,
+	latest_justification: Option<Vec<u8>>,
+	header_numbers: Vec<<<T as subxt::Config>::Header as Header>::Number>,
+	height: Height,
+) -> Result<GrandpaHeader, anyhow::Error>
+where
+	T: light_client_common::config::Config + Send + Sync,
+	u32: From<<<T as subxt::Config>::Header as Header>::Number>,
+	<<T as subxt::Config>::Header as Header>::Number:
+		BlockNumberOps + Ord + sp_runtime::traits::Zero + One + Send + Sync,
+	<T as subxt::Config>::Header: Decode + Send + Sync,
+	sp_core::H256: From<T::Hash>,
+	BTreeMap<H256, ParachainHeaderProofs>:
+		From<BTreeMap<<T as subxt::Config>::Hash, ParachainHeaderProofs>>,
+{
+	let grandpa_header = match chain_type {
+		ChainType::Parachain => {
+			let ParachainHeadersWithFinalityProof { finality_proof, parachain_headers, .. } =
+				prover
+					.query_finalized_parachain_headers_with_proof::<T::Header>(
+						previous_finalized_height,
+						latest_finalized_height,
+						latest_justification,
+						header_numbers,
+					)
+					.await?;
+			GrandpaHeader {
+				finality_proof: codec::Decode::decode(&mut &*finality_proof.encode())
+					.expect("Same struct from different crates,decode should not fail"),
+				parachain_headers: parachain_headers.into(),
+				solochain_headers: Default::default(),
+				height,
+			}
+		},
+		ChainType::Solochain => {
+			let SolochainHeadersWithFinalityProof { finality_proof, solochain_headers } = prover
+				.query_finalized_solochain_headers_with_proof::<T::Header>(
+					previous_finalized_height,
+					latest_finalized_height,
+					latest_justification,
+					header_numbers,
+				)
+				.await?;
+			GrandpaHeader {
+				finality_proof: codec::Decode::decode(&mut &*finality_proof.encode())
+					.expect("Same struct from different crates,decode should not fail"),
+				parachain_headers: Default::default(),
+				solochain_headers,
+				height,
+			}
+		},
+	};
+
+	Ok(grandpa_header)
+}
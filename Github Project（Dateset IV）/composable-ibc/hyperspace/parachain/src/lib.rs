@@ -44,7 +44,7 @@ use crate::{
 };
 use beefy_light_client_primitives::{ClientState, MmrUpdateProof};
 use beefy_prover::Prover;
-use codec::Decode;
+use codec::{Decode, Encode};
 use grandpa_light_client_primitives::ParachainHeaderProofs;
 use grandpa_prover::GrandpaProver;
 use ibc::{
@@ -52,7 +52,7 @@ use ibc::{
 	timestamp::Timestamp,
 };
 use ics10_grandpa::{
-	client_state::ClientState as GrandpaClientState,
+	client_state::{ChainType, ClientState as GrandpaClientState},
 	consensus_state::ConsensusState as GrandpaConsensusState,
 };
 use ics11_beefy::{
@@ -60,7 +60,10 @@ use ics11_beefy::{
 	consensus_state::ConsensusState as BeefyConsensusState,
 };
 use jsonrpsee_ws_client::WsClientBuilder;
-use light_client_common::config::{AsInner, RuntimeStorage};
+use light_client_common::{
+	config::{AsInner, RuntimeStorage},
+	RelayChain, TimestampProofType,
+};
 use pallet_ibc::light_clients::{AnyClientState, AnyConsensusState, HostFunctionsManager};
 use pallet_mmr_primitives::Proof;
 use primitives::{CommonClientState, KeyProvider};
@@ -121,6 +124,12 @@ pub struct ParachainClient<T: light_client_common::config::Config> {
 	pub max_extrinsic_weight: u64,
 	/// Finality protocol to use, eg Beefy, Grandpa
 	pub finality_protocol: FinalityProtocol,
+	/// Relay chain the parachain is secured by
+	pub relay_chain: RelayChain,
+	/// Kind of chain, standalone chains are only supported by the GRANDPA finality protocol
+	pub chain_type: ChainType,
+	/// How the timestamps of the headers are proven to the light clients of this chain
+	pub timestamp_proof_type: TimestampProofType,
 	/// Common relayer data
 	pub common_state: CommonClientState,
 }
@@ -187,6 +196,21 @@ pub struct ParachainClientConfig {
 	/// All the client states and headers will be wrapped in WASM ones using the WASM code ID.
 	#[serde(default)]
 	pub wasm_checksum: Option<String>,
+	/// Receive packets in batches proven by a single multi-key proof.
+	#[serde(default)]
+	pub batch_recv_packets: bool,
+	/// Relay chain the parachain is secured by, written into the client states created for it.
+	#[serde(default)]
+	pub relay_chain: RelayChain,
+	/// Kind of chain. For standalone chains, both rpc urls point to the chain itself and the
+	/// `para_id` is only used as the revision number of the client heights.
+	#[serde(default)]
+	pub chain_type: ChainType,
+	/// How the timestamps of the headers are proven to the light clients created for this chain.
+	/// Storage proofs of `pallet_timestamp::Now` don't depend on the extrinsic format of the
+	/// runtime.
+	#[serde(default)]
+	pub timestamp_proof_type: TimestampProofType,
 }
 
 impl<T> ParachainClient<T>
@@ -261,12 +285,16 @@ where
 			ss58_version: Ss58AddressFormat::from(config.ss58_version),
 			channel_whitelist: Arc::new(Mutex::new(config.channel_whitelist.into_iter().collect())),
 			finality_protocol: config.finality_protocol,
+			relay_chain: config.relay_chain,
+			chain_type: config.chain_type,
+			timestamp_proof_type: config.timestamp_proof_type,
 			common_state: CommonClientState {
 				skip_optional_client_updates: true,
 				maybe_has_undelivered_packets: Arc::new(Mutex::new(Default::default())),
 				rpc_call_delay: DEFAULT_RPC_CALL_DELAY,
 				initial_rpc_call_delay: DEFAULT_RPC_CALL_DELAY,
 				misbehaviour_client_msg_queue: Arc::new(AsyncMutex::new(vec![])),
+				batch_recv_packets: config.batch_recv_packets,
 				..Default::default()
 			},
 		})
@@ -299,6 +327,17 @@ where
 			para_ws_client,
 			para_id: self.para_id,
 			rpc_call_delay: self.common_state.rpc_call_delay,
+			timestamp_proof_type: self.timestamp_proof_type,
+		}
+	}
+
+	/// Returns a beefy proving client.
+	pub fn beefy_prover(&self) -> Prover<T> {
+		Prover {
+			relay_client: self.relay_client.clone(),
+			para_client: self.para_client.clone(),
+			para_id: self.para_id,
+			timestamp_proof_type: self.timestamp_proof_type,
 		}
 	}
 
@@ -314,11 +353,7 @@ where
 		<<T as subxt::Config>::Header as Header>::Number: From<u32>,
 		<T as subxt::Config>::Header: Decode,
 	{
-		let client_wrapper = Prover {
-			relay_client: self.relay_client.clone(),
-			para_client: self.para_client.clone(),
-			para_id: self.para_id,
-		};
+		let client_wrapper = self.beefy_prover();
 
 		let headers = client_wrapper
 			.query_finalized_parachain_headers_at(
@@ -345,11 +380,7 @@ where
 		<<T as subxt::Config>::Header as Header>::Number: Ord + sp_runtime::traits::Zero,
 		<T as subxt::Config>::Header: Decode,
 	{
-		let client_wrapper = Prover {
-			relay_client: self.relay_client.clone(),
-			para_client: self.para_client.clone(),
-			para_id: self.para_id,
-		};
+		let client_wrapper = self.beefy_prover();
 
 		let (parachain_headers, batch_proof) = client_wrapper
 			.query_finalized_parachain_headers_with_proof(
@@ -373,6 +404,7 @@ where
 					heads_total_count: para_header.heads_total_count,
 					extrinsic_proof: para_header.extrinsic_proof,
 					timestamp_extrinsic: para_header.timestamp_extrinsic,
+					timestamp_storage_proof: para_header.timestamp_storage_proof,
 				})
 			})
 			.collect::<Result<Vec<_>, codec::Error>>()?;
@@ -388,11 +420,7 @@ where
 			beefy_primitives::crypto::Signature,
 		>,
 	) -> Result<MmrUpdateProof, Error> {
-		let prover = Prover {
-			relay_client: self.relay_client.clone(),
-			para_client: self.para_client.clone(),
-			para_id: self.para_id,
-		};
+		let prover = self.beefy_prover();
 
 		let mmr_update =
 			prover.fetch_mmr_update_proof_for(signed_commitment).await.map_err(|e| {
@@ -495,6 +523,7 @@ where
 			relay_client: self.relay_client.clone(),
 			para_client: self.para_client.clone(),
 			para_id: self.para_id,
+			timestamp_proof_type: self.timestamp_proof_type,
 		};
 		loop {
 			let beefy_state = client_wrapper.construct_beefy_client_state().await.map_err(|e| {
@@ -523,7 +552,7 @@ where
 			let block_number = decoded_para_head.number;
 			let client_state = BeefyClientState::<HostFunctionsManager> {
 				chain_id: ChainId::new("relay-chain".to_string(), 0),
-				relay_chain: Default::default(),
+				relay_chain: self.relay_chain.clone(),
 				mmr_root_hash: beefy_state.mmr_root_hash,
 				latest_beefy_height: beefy_state.latest_beefy_height,
 				frozen_height: None,
@@ -531,6 +560,7 @@ where
 				para_id: self.para_id,
 				authority: beefy_state.current_authorities,
 				next_authority_set: beefy_state.next_authorities,
+				timestamp_proof_type: self.timestamp_proof_type,
 				_phantom: Default::default(),
 			};
 			// we can't use the genesis block to construct the initial state.
@@ -584,31 +614,52 @@ where
 			para_ws_client,
 			para_id: self.para_id,
 			rpc_call_delay: self.common_state.rpc_call_delay,
+			timestamp_proof_type: self.timestamp_proof_type,
 		};
 		let api = self.relay_client.storage();
 		let para_client_api = self.para_client.storage();
 		loop {
-			let light_client_state = prover
-				.initialize_client_state()
-				.await
-				.map_err(|e| Error::from(format!("Error constructing client state: {e}")))?;
-
-			let heads_addr = T::Storage::paras_heads(self.para_id);
-			let head_data = <T::Storage as RuntimeStorage>::HeadData::from_inner(
-				api.at(light_client_state.latest_relay_hash.into())
-					.fetch(&heads_addr)
+			let light_client_state = match self.chain_type {
+				ChainType::Parachain => prover.initialize_client_state().await,
+				ChainType::Solochain => prover.initialize_solochain_client_state().await,
+			}
+			.map_err(|e| Error::from(format!("Error constructing client state: {e}")))?;
+
+			let head_data = match self.chain_type {
+				ChainType::Parachain => {
+					let heads_addr = T::Storage::paras_heads(self.para_id);
+					<T::Storage as RuntimeStorage>::HeadData::from_inner(
+						api.at(light_client_state.latest_relay_hash.into())
+							.fetch(&heads_addr)
+							.await?
+							.ok_or_else(|| {
+								Error::Custom(format!(
+									"Couldn't find header for ParaId({}) at relay block {:?}",
+									self.para_id, light_client_state.latest_relay_hash
+								))
+							})?,
+					)
+					.as_ref()
+					.to_vec()
+				},
+				// the finalized header is the header of the chain itself
+				ChainType::Solochain => self
+					.relay_client
+					.rpc()
+					.header(Some(light_client_state.latest_relay_hash.into()))
 					.await?
 					.ok_or_else(|| {
 						Error::Custom(format!(
-							"Couldn't find header for ParaId({}) at relay block {:?}",
-							self.para_id, light_client_state.latest_relay_hash
+							"Couldn't find header at block {:?}",
+							light_client_state.latest_relay_hash
 						))
-					})?,
-			);
+					})?
+					.encode(),
+			};
 			let decoded_para_head = sp_runtime::generic::Header::<
 				u32,
 				sp_runtime::traits::BlakeTwo256,
-			>::decode(&mut &*head_data.as_ref())?;
+			>::decode(&mut &*head_data)?;
 			let block_number = decoded_para_head.number;
 			// we can't use the genesis block to construct the initial state.
 			if block_number == 0 {
@@ -617,7 +668,7 @@ where
 
 			let mut client_state = GrandpaClientState::<HostFunctionsManager>::default();
 
-			client_state.relay_chain = Default::default();
+			client_state.relay_chain = self.relay_chain.clone();
 			client_state.current_authorities = light_client_state.current_authorities;
 			client_state.current_set_id = light_client_state.current_set_id;
 			client_state.latest_relay_hash = light_client_state.latest_relay_hash.into();
@@ -625,6 +676,8 @@ where
 			client_state.latest_para_height = block_number;
 			client_state.para_id = self.para_id;
 			client_state.latest_relay_height = light_client_state.latest_relay_height;
+			client_state.chain_type = self.chain_type;
+			client_state.timestamp_proof_type = self.timestamp_proof_type;
 
 			let subxt_block_number: subxt::rpc::types::BlockNumber = block_number.into();
 			let block_hash =
@@ -1,11 +1,11 @@
-use codec::{Decode, Encode};
+use codec::Decode;
 use std::{
 	collections::{BTreeMap, BTreeSet, HashMap},
 	fmt::Display,
 };
 
 use finality_grandpa::BlockNumberOps;
-use grandpa_light_client_primitives::{ParachainHeaderProofs, ParachainHeadersWithFinalityProof};
+use grandpa_light_client_primitives::ParachainHeaderProofs;
 use ibc_proto::google::protobuf::Any;
 use sp_core::H256;
 use sp_runtime::{
@@ -25,7 +25,8 @@ use ibc::{
 	core::ics24_host::identifier::ClientId, events::IbcEvent, signer::Signer, tx_msg::Msg, Height,
 };
 use ibc_rpc::{BlockNumberOrHash, IbcApiClient};
-use ics10_grandpa::client_message::{ClientMessage, Header as GrandpaHeader};
+use ics10_grandpa::{client_message::ClientMessage, client_state::ChainType};
+use ics11_beefy::client_message::{BeefyHeader, ClientMessage as BeefyClientMessage};
 use pallet_ibc::light_clients::{AnyClientMessage, AnyClientState};
 
 use primitives::{
@@ -33,7 +34,9 @@ use primitives::{
 };
 
 use super::{error::Error, ParachainClient};
-use crate::finality_protocol::FinalityProtocol;
+use crate::finality_protocol::{
+	query_grandpa_header, query_latest_finalized_header_number, FinalityProtocol,
+};
 
 const MAX_HEADERS_PER_ITERATION: usize = 100;
 
@@ -97,7 +100,26 @@ where
 				// finalized height then the light client is still in sync
 				Ok(session_changes == 0)
 			},
-			FinalityProtocol::Beefy => unimplemented!(),
+			FinalityProtocol::Beefy => {
+				let AnyClientState::Beefy(client_state) =
+					AnyClientState::decode_recursive(any_client_state, |c| {
+						matches!(c, AnyClientState::Beefy(_))
+					})
+					.ok_or_else(|| Error::Custom(format!("Could not decode client state")))?
+				else {
+					unreachable!()
+				};
+				let (.., latest_set_id) =
+					self.beefy_prover().latest_beefy_finalized_set_id().await.map_err(|e| {
+						Error::from(format!(
+							"[latest_beefy_finalized_set_id] Failed due to {:?}",
+							e
+						))
+					})?;
+				// The light client can verify commitments signed by either its current or next
+				// authority set, any further authority set rotation has to be relayed first
+				Ok(latest_set_id <= client_state.next_authority_set.id)
+			},
 		}
 	}
 
@@ -140,8 +162,26 @@ where
 					.await?;
 				(messages, events)
 			},
-			// Current implementation of Beefy needs to be revised
-			FinalityProtocol::Beefy => unimplemented!(),
+			FinalityProtocol::Beefy => {
+				let AnyClientState::Beefy(client_state) =
+					AnyClientState::decode_recursive(any_client_state, |c| {
+						matches!(c, AnyClientState::Beefy(_))
+					})
+					.ok_or_else(|| Error::Custom(format!("Could not decode client state")))?
+				else {
+					unreachable!()
+				};
+				let messages = self
+					.query_missed_beefy_updates(
+						client_state.authority.id,
+						client_state.latest_beefy_height,
+						self.client_id(),
+						counterparty.account_id(),
+						MAX_HEADERS_PER_ITERATION,
+					)
+					.await?;
+				(messages, vec![])
+			},
 		};
 
 		Ok((messages, events))
@@ -165,6 +205,48 @@ where
 	<T as subxt::Config>::AccountId: Send + Sync,
 	<T as subxt::Config>::Address: Send + Sync,
 {
+	/// Returns the client update messages for the mandatory blocks of every BEEFY authority set
+	/// enacted after the authority set with the given id. Each message only carries the mmr update,
+	/// rotating the authority set of the light client, new parachain headers are relayed by the
+	/// regular finality notifications.
+	pub async fn query_missed_beefy_updates(
+		&self,
+		validator_set_id: u64,
+		latest_beefy_height: u32,
+		client_id: ClientId,
+		signer: Signer,
+		limit: usize,
+	) -> Result<Vec<Any>, anyhow::Error> {
+		let prover = self.beefy_prover();
+		let signed_commitments = prover
+			.query_mandatory_commitments(validator_set_id, latest_beefy_height)
+			.await
+			.map_err(|e| {
+				Error::from(format!("[query_mandatory_commitments] Failed due to {:?}", e))
+			})?;
+
+		let mut messages = vec![];
+		for signed_commitment in signed_commitments.into_iter().take(limit) {
+			log::debug!(
+				target: "hyperspace",
+				"Getting message for the mandatory block #{} of authority set {}",
+				signed_commitment.commitment.block_number, signed_commitment.commitment.validator_set_id
+			);
+			let mmr_update = self.query_beefy_mmr_update_proof(signed_commitment).await?;
+			let msg = MsgUpdateAnyClient::<LocalClientTypes> {
+				client_id: client_id.clone(),
+				client_message: AnyClientMessage::Beefy(BeefyClientMessage::Header(BeefyHeader {
+					headers_with_proof: None,
+					mmr_update_proof: Some(mmr_update),
+				})),
+				signer: signer.clone(),
+			};
+			let value = msg.encode_vec()?;
+			messages.push(Any { value, type_url: msg.type_url() });
+		}
+		Ok(messages)
+	}
+
 	/// Returns a tuple of the client update messages in the exclusive range
 	/// `previous_finalized_height..latest_finalized_height`, relay chain block of the last message
 	/// in the list and latest parachain block finalized by the last message in the list
@@ -224,6 +306,7 @@ where
 				signer.clone(),
 				&self.name,
 				self.para_id,
+				self.chain_type,
 			)
 			.await?;
 			messages.push(msg);
@@ -249,6 +332,7 @@ async fn get_message<T: light_client_common::config::Config + Send + Sync>(
 	signer: Signer,
 	name: &str,
 	para_id: u32,
+	chain_type: ChainType,
 ) -> Result<(Any, Vec<IbcEvent>, u32, u32), anyhow::Error>
 where
 	u32: From<<<T as subxt::Config>::Header as HeaderT>::Number>
@@ -261,9 +345,8 @@ where
 		From<BTreeMap<<T as subxt::Config>::Hash, ParachainHeaderProofs>>,
 {
 	// fetch the latest finalized parachain header
-	let finalized_para_header =
-		prover.query_latest_finalized_parachain_header(latest_finalized_height).await?;
-	let finalized_para_height = u32::from(finalized_para_header.number());
+	let finalized_para_height =
+		query_latest_finalized_header_number(prover, chain_type, latest_finalized_height).await?;
 	let latest_finalized_para_height = finalized_para_height;
 	let finalized_blocks =
 		((previous_finalized_para_height + 1)..=latest_finalized_para_height).collect::<Vec<_>>();
@@ -310,7 +393,8 @@ where
 
 	// We ensure we advance the finalized latest parachain height
 	if previous_finalized_para_height < finalized_para_height {
-		headers_with_events.insert(finalized_para_header.number());
+		headers_with_events
+			.insert(<<T as subxt::Config>::Header as Header>::Number::from(finalized_para_height));
 	}
 
 	let events: Vec<IbcEvent> = events
@@ -330,21 +414,16 @@ where
 			)
 		})
 		.collect();
-	let ParachainHeadersWithFinalityProof { finality_proof, parachain_headers, .. } = prover
-		.query_finalized_parachain_headers_with_proof::<T::Header>(
-			previous_finalized_height,
-			latest_finalized_height,
-			None,
-			headers_with_events.into_iter().collect(),
-		)
-		.await?;
-
-	let grandpa_header = GrandpaHeader {
-		finality_proof: codec::Decode::decode(&mut &*finality_proof.encode())
-			.expect("Same struct from different crates,decode should not fail"),
-		parachain_headers: parachain_headers.into(),
-		height: Height::new(para_id as u64, finalized_para_height as u64),
-	};
+	let grandpa_header = query_grandpa_header(
+		prover,
+		chain_type,
+		previous_finalized_height,
+		latest_finalized_height,
+		None,
+		headers_with_events.into_iter().collect(),
+		Height::new(para_id as u64, finalized_para_height as u64),
+	)
+	.await?;
 
 	let msg = MsgUpdateAnyClient::<LocalClientTypes> {
 		client_id,
@@ -31,16 +31,16 @@ use ibc::{
 	Height,
 };
 use ibc_proto::{
+	cosmos::base::query::v1beta1::{PageRequest, PageResponse},
 	google::protobuf::Any,
 	ibc::core::{
 		channel::v1::{
 			IdentifiedChannel, QueryChannelResponse, QueryChannelsResponse,
 			QueryNextSequenceReceiveResponse, QueryPacketAcknowledgementResponse,
-			QueryPacketCommitmentResponse, QueryPacketReceiptResponse,
-		},
-		client::v1::{
-			IdentifiedClientState, QueryClientStateResponse, QueryConsensusStateResponse,
+			QueryPacketCommitmentResponse, QueryPacketReceiptResponse, QueryUpgradeErrorResponse,
+			QueryUpgradeResponse,
 		},
+		client::v1::{QueryClientStateResponse, QueryConsensusStateResponse},
 		connection::v1::{IdentifiedConnection, QueryConnectionResponse},
 	},
 };
@@ -247,6 +247,40 @@ where
 		Ok(response)
 	}
 
+	async fn query_channel_upgrade(
+		&self,
+		at: Height,
+		channel_id: ChannelId,
+		port_id: PortId,
+	) -> Result<QueryUpgradeResponse, Self::Error> {
+		let response = IbcApiClient::<u32, H256, <T as light_client_common::config::Config>::AssetId>::query_channel_upgrade(
+			&*self.para_ws_client,
+			at.revision_height as u32,
+			channel_id.to_string(),
+			port_id.to_string(),
+		)
+		.await
+		.map_err(|e| Error::from(format!("Rpc Error {:?}", e)))?;
+		Ok(response)
+	}
+
+	async fn query_channel_upgrade_error(
+		&self,
+		at: Height,
+		channel_id: ChannelId,
+		port_id: PortId,
+	) -> Result<QueryUpgradeErrorResponse, Self::Error> {
+		let response = IbcApiClient::<u32, H256, <T as light_client_common::config::Config>::AssetId>::query_channel_upgrade_error(
+			&*self.para_ws_client,
+			at.revision_height as u32,
+			channel_id.to_string(),
+			port_id.to_string(),
+		)
+		.await
+		.map_err(|e| Error::from(format!("Rpc Error {:?}", e)))?;
+		Ok(response)
+	}
+
 	/// Query the proof of the given keys at the given height.
 	///
 	/// Note: all the keys will be prefixed with the connection prefix.
@@ -374,16 +408,25 @@ where
 		channel_id: ChannelId,
 		port_id: PortId,
 	) -> Result<Vec<u64>, Self::Error> {
-		let res =
-			IbcApiClient::<u32, H256, <T as light_client_common::config::Config>::AssetId>::query_packet_commitments(
+		let mut sequences = vec![];
+		let mut key = vec![];
+		loop {
+			let res = IbcApiClient::<u32, H256, <T as light_client_common::config::Config>::AssetId>::query_packet_commitments(
 				&*self.para_ws_client,
 				at.revision_height as u32,
 				channel_id.to_string(),
 				port_id.to_string(),
+				Some(PageRequest { key, ..Default::default() }),
 			)
 			.await
 			.map_err(|e| Error::from(format!("Rpc Error {:?}", e)))?;
-		Ok(res.commitments.into_iter().map(|packet_state| packet_state.sequence).collect())
+			sequences.extend(res.commitments.into_iter().map(|packet_state| packet_state.sequence));
+			match res.pagination {
+				Some(PageResponse { next_key, .. }) if !next_key.is_empty() => key = next_key,
+				_ => break,
+			}
+		}
+		Ok(sequences)
 	}
 
 	async fn query_packet_acknowledgements(
@@ -392,19 +435,26 @@ where
 		channel_id: ChannelId,
 		port_id: PortId,
 	) -> Result<Vec<u64>, Self::Error> {
-		let res = IbcApiClient::<u32, H256, <T as light_client_common::config::Config>::AssetId>::query_packet_acknowledgements(
-			&*self.para_ws_client,
-			at.revision_height as u32,
-			channel_id.to_string(),
-			port_id.to_string(),
-		)
-		.await
-		.map_err(|e| Error::from(format!("Rpc Error {:?}", e)))?;
-		Ok(res
-			.acknowledgements
-			.into_iter()
-			.map(|packet_state| packet_state.sequence)
-			.collect())
+		let mut sequences = vec![];
+		let mut key = vec![];
+		loop {
+			let res = IbcApiClient::<u32, H256, <T as light_client_common::config::Config>::AssetId>::query_packet_acknowledgements(
+				&*self.para_ws_client,
+				at.revision_height as u32,
+				channel_id.to_string(),
+				port_id.to_string(),
+				Some(PageRequest { key, ..Default::default() }),
+			)
+			.await
+			.map_err(|e| Error::from(format!("Rpc Error {:?}", e)))?;
+			sequences
+				.extend(res.acknowledgements.into_iter().map(|packet_state| packet_state.sequence));
+			match res.pagination {
+				Some(PageResponse { next_key, .. }) if !next_key.is_empty() => key = next_key,
+				_ => break,
+			}
+		}
+		Ok(sequences)
 	}
 
 	async fn query_unreceived_packets(
@@ -480,15 +530,23 @@ where
 		port_id: PortId,
 		seqs: Vec<u64>,
 	) -> Result<Vec<PacketInfo>, Self::Error> {
-		let response =
-			IbcApiClient::<u32, H256, <T as light_client_common::config::Config>::AssetId>::query_send_packets(
+		// The rpc rejects requests for more than a page of sequences
+		let mut response = vec![];
+		for seqs in seqs.chunks(ibc_primitives::MAX_PAGE_LIMIT as usize) {
+			let packets = IbcApiClient::<
+				u32,
+				H256,
+				<T as light_client_common::config::Config>::AssetId,
+			>::query_send_packets(
 				&*self.para_ws_client,
 				channel_id.to_string(),
 				port_id.to_string(),
-				seqs,
+				seqs.to_vec(),
 			)
 			.await
 			.map_err(|e| Error::from(format!("Rpc Error {:?}", e)))?;
+			response.extend(packets);
+		}
 
 		Ok(response)
 	}
@@ -499,15 +557,23 @@ where
 		port_id: PortId,
 		seqs: Vec<u64>,
 	) -> Result<Vec<PacketInfo>, Self::Error> {
-		let response =
-			IbcApiClient::<u32, H256, <T as light_client_common::config::Config>::AssetId>::query_recv_packets(
+		// The rpc rejects requests for more than a page of sequences
+		let mut response = vec![];
+		for seqs in seqs.chunks(ibc_primitives::MAX_PAGE_LIMIT as usize) {
+			let packets = IbcApiClient::<
+				u32,
+				H256,
+				<T as light_client_common::config::Config>::AssetId,
+			>::query_recv_packets(
 				&*self.para_ws_client,
 				channel_id.to_string(),
 				port_id.to_string(),
-				seqs,
+				seqs.to_vec(),
 			)
 			.await
 			.map_err(|e| Error::from(format!("Rpc Error {:?}", e)))?;
+			response.extend(packets);
+		}
 		Ok(response)
 	}
 
@@ -643,40 +709,59 @@ where
 	}
 
 	async fn query_clients(&self) -> Result<Vec<ClientId>, Self::Error> {
-		let response: Vec<IdentifiedClientState> = IbcApiClient::<
-			u32,
-			H256,
-			<T as light_client_common::config::Config>::AssetId,
-		>::query_clients(&*self.para_ws_client)
-		.await
-		.map_err(|e| Error::from(format!("Rpc Error {:?}", e)))?;
-		response
-			.into_iter()
-			.map(|client| {
-				ClientId::from_str(&client.client_id)
-					.map_err(|_| Error::Custom("Invalid client id ".to_string()))
-			})
-			.collect()
+		let mut clients = vec![];
+		let mut key = vec![];
+		loop {
+			let response = IbcApiClient::<
+				u32,
+				H256,
+				<T as light_client_common::config::Config>::AssetId,
+			>::query_clients(
+				&*self.para_ws_client, Some(PageRequest { key, ..Default::default() })
+			)
+			.await
+			.map_err(|e| Error::from(format!("Rpc Error {:?}", e)))?;
+			for client in response.client_states {
+				clients.push(
+					ClientId::from_str(&client.client_id)
+						.map_err(|_| Error::Custom("Invalid client id ".to_string()))?,
+				);
+			}
+			match response.pagination {
+				Some(PageResponse { next_key, .. }) if !next_key.is_empty() => key = next_key,
+				_ => break,
+			}
+		}
+		Ok(clients)
 	}
 
 	async fn query_channels(&self) -> Result<Vec<(ChannelId, PortId)>, Self::Error> {
-		let response = IbcApiClient::<u32, H256, <T as light_client_common::config::Config>::AssetId>::query_channels(
-			&*self.para_ws_client,
-		)
-		.await
-		.map_err(|e| Error::from(format!("Rpc Error {:?}", e)))?;
-		response
-			.channels
-			.into_iter()
-			.map(|identified_chan| {
-				Ok((
+		let mut channels = vec![];
+		let mut key = vec![];
+		loop {
+			let response = IbcApiClient::<
+				u32,
+				H256,
+				<T as light_client_common::config::Config>::AssetId,
+			>::query_channels(
+				&*self.para_ws_client, Some(PageRequest { key, ..Default::default() })
+			)
+			.await
+			.map_err(|e| Error::from(format!("Rpc Error {:?}", e)))?;
+			channels.extend(response.channels.into_iter().map(|identified_chan| {
+				(
 					ChannelId::from_str(&identified_chan.channel_id)
 						.expect("Failed to convert invalid string to channel id"),
 					PortId::from_str(&identified_chan.port_id)
 						.expect("Failed to convert invalid string to port id"),
-				))
-			})
-			.collect::<Result<Vec<_>, _>>()
+				)
+			}));
+			match response.pagination {
+				Some(PageResponse { next_key, .. }) if !next_key.is_empty() => key = next_key,
+				_ => break,
+			}
+		}
+		Ok(channels)
 	}
 
 	async fn query_connection_using_client(
@@ -19,7 +19,7 @@ use futures::{Stream, StreamExt};
 use grandpa_light_client_primitives::ParachainHeaderProofs;
 use ibc::{
 	applications::transfer::{msgs::transfer::MsgTransfer, PrefixedCoin},
-	core::ics24_host::identifier::{ChannelId, ClientId},
+	core::ics24_host::identifier::{ChannelId, ClientId, ConnectionId},
 };
 use ibc_proto::google::protobuf::Any;
 use ibc_rpc::IbcApiClient;
@@ -37,8 +37,11 @@ use sp_runtime::{
 	MultiSignature, MultiSigner,
 };
 use std::{collections::BTreeMap, fmt::Display, pin::Pin, str::FromStr};
-use subxt::config::{
-	extrinsic_params::BaseExtrinsicParamsBuilder, ExtrinsicParams, Header as HeaderT, Header,
+use subxt::{
+	config::{
+		extrinsic_params::BaseExtrinsicParamsBuilder, ExtrinsicParams, Header as HeaderT, Header,
+	},
+	dynamic::Value,
 };
 
 impl<T: light_client_common::config::Config + Send + Sync> ParachainClient<T>
@@ -124,6 +127,74 @@ where
 
 		Ok(())
 	}
+
+	/// Registers an interchain account of the signer on the chain at the other end of
+	/// `connection_id`.
+	pub async fn register_interchain_account(
+		&self,
+		connection_id: ConnectionId,
+	) -> Result<(), Error> {
+		let call = subxt::dynamic::tx(
+			"Ics27",
+			"register_interchain_account",
+			vec![Value::from_bytes(connection_id.as_bytes())],
+		);
+		self.submit_call(call).await?;
+		Ok(())
+	}
+
+	/// Sends `messages` to be executed by the interchain account of the signer on the chain at
+	/// the other end of `connection_id`.
+	pub async fn send_interchain_account_tx(
+		&self,
+		connection_id: ConnectionId,
+		messages: Vec<Any>,
+		timeout: Timeout,
+	) -> Result<(), Error> {
+		let messages = messages.into_iter().map(|msg| {
+			Value::named_composite([
+				("type_url", Value::from_bytes(msg.type_url.as_bytes())),
+				("value", Value::from_bytes(msg.value)),
+			])
+		});
+		let option = |value: Option<u64>| match value {
+			Some(value) => Value::unnamed_variant("Some", [Value::u128(value.into())]),
+			None => Value::unnamed_variant("None", []),
+		};
+		let timeout = match timeout {
+			Timeout::Offset { timestamp, height } => Value::named_variant(
+				"Offset",
+				[("timestamp", option(timestamp)), ("height", option(height))],
+			),
+			Timeout::Absolute { timestamp, height } => Value::named_variant(
+				"Absolute",
+				[("timestamp", option(timestamp)), ("height", option(height))],
+			),
+		};
+		let call = subxt::dynamic::tx(
+			"Ics27",
+			"send_tx",
+			vec![
+				Value::from_bytes(connection_id.as_bytes()),
+				Value::unnamed_composite(messages),
+				Value::from_bytes([]),
+				timeout,
+			],
+		);
+		self.submit_call(call).await?;
+		Ok(())
+	}
+
+	/// Returns the scale encoded runtime call `pallet::call` of this chain, as dispatched by the
+	/// interchain accounts it hosts.
+	pub fn runtime_call_data(
+		&self,
+		pallet: &str,
+		call: &str,
+		fields: Vec<Value>,
+	) -> Result<Vec<u8>, Error> {
+		Ok(self.para_client.tx().call_data(&subxt::dynamic::tx(pallet, call, fields))?)
+	}
 }
 
 #[async_trait::async_trait]
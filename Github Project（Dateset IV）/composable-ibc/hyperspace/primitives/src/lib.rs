@@ -21,7 +21,7 @@ use ibc_proto::{
 		channel::v1::{
 			QueryChannelResponse, QueryNextSequenceReceiveResponse,
 			QueryPacketAcknowledgementResponse, QueryPacketCommitmentResponse,
-			QueryPacketReceiptResponse,
+			QueryPacketReceiptResponse, QueryUpgradeErrorResponse, QueryUpgradeResponse,
 		},
 		client::v1::{QueryClientStateResponse, QueryConsensusStateResponse},
 		connection::v1::QueryConnectionResponse,
@@ -133,6 +133,9 @@ pub struct CommonClientState {
 	pub misbehaviour_client_msg_queue: Arc<AsyncMutex<Vec<AnyClientMessage>>>,
 	pub max_packets_to_process: usize,
 	pub skip_tokens_list: Vec<String>,
+	/// Deliver packets received at the same proof height in a single `MsgRecvPackets`, proven by
+	/// one multi-key proof. Only supported by pallet-ibc sinks.
+	pub batch_recv_packets: bool,
 }
 
 impl Default for CommonClientState {
@@ -146,6 +149,7 @@ impl Default for CommonClientState {
 			misbehaviour_client_msg_queue: Arc::new(Default::default()),
 			max_packets_to_process: 100,
 			skip_tokens_list: Default::default(),
+			batch_recv_packets: false,
 		}
 	}
 }
@@ -255,6 +259,22 @@ pub trait IbcProvider {
 		port_id: PortId,
 	) -> Result<QueryChannelResponse, Self::Error>;
 
+	/// Query the upgrade proposed by a channel end with proof
+	async fn query_channel_upgrade(
+		&self,
+		at: Height,
+		channel_id: ChannelId,
+		port_id: PortId,
+	) -> Result<QueryUpgradeResponse, Self::Error>;
+
+	/// Query the error receipt of the last aborted upgrade of a channel end with proof
+	async fn query_channel_upgrade_error(
+		&self,
+		at: Height,
+		channel_id: ChannelId,
+		port_id: PortId,
+	) -> Result<QueryUpgradeErrorResponse, Self::Error>;
+
 	/// Query proof for provided key path
 	async fn query_proof(&self, at: Height, keys: Vec<Vec<u8>>) -> Result<Vec<u8>, Self::Error>;
 
@@ -612,20 +632,51 @@ pub async fn query_undelivered_sequences(
 		.ok_or_else(|| Error::Custom("Expected counterparty channel id".to_string()))?;
 	let counterparty_port_id = channel_end.counterparty().port_id.clone();
 
-	let undelivered_sequences = if channel_end.ordering == Order::Unordered {
-		sink.query_unreceived_packets(
-			sink_height,
-			counterparty_channel_id,
-			counterparty_port_id.clone(),
-			seqs,
-		)
-		.await?
-	} else {
-		let next_seq_recv = sink
-			.query_next_sequence_recv(sink_height, &counterparty_port_id, &counterparty_channel_id)
-			.await?
-			.next_sequence_receive;
-		seqs.into_iter().filter(|seq| *seq > next_seq_recv).collect()
+	let undelivered_sequences = match channel_end.ordering {
+		Order::Unordered =>
+			sink.query_unreceived_packets(
+				sink_height,
+				counterparty_channel_id,
+				counterparty_port_id.clone(),
+				seqs,
+			)
+			.await?,
+		Order::Ordered => {
+			let next_seq_recv = sink
+				.query_next_sequence_recv(
+					sink_height,
+					&counterparty_port_id,
+					&counterparty_channel_id,
+				)
+				.await?
+				.next_sequence_receive;
+			seqs.into_iter().filter(|seq| *seq > next_seq_recv).collect()
+		},
+		Order::OrderedAllowTimeout => {
+			let next_seq_recv = sink
+				.query_next_sequence_recv(
+					sink_height,
+					&counterparty_port_id,
+					&counterparty_channel_id,
+				)
+				.await?
+				.next_sequence_receive;
+			let (mut pending, passed): (Vec<_>, Vec<_>) =
+				seqs.into_iter().partition(|seq| *seq >= next_seq_recv);
+			// Ordered channels only write receipts for skipped packets, which still have to be
+			// timed out on the source.
+			let without_receipt = sink
+				.query_unreceived_packets(
+					sink_height,
+					counterparty_channel_id,
+					counterparty_port_id.clone(),
+					passed.clone(),
+				)
+				.await?;
+			pending.extend(passed.into_iter().filter(|seq| !without_receipt.contains(seq)));
+			pending.sort_unstable();
+			pending
+		},
 	};
 
 	Ok(undelivered_sequences)
@@ -893,7 +944,9 @@ pub fn filter_events_by_ids(
 	use ibc::core::{
 		ics02_client::events::Attributes as ClientAttributes,
 		ics03_connection::events::Attributes as ConnectionAttributes,
-		ics04_channel::events::Attributes as ChannelAttributes,
+		ics04_channel::events::{
+			Attributes as ChannelAttributes, UpgradeAttributes as ChannelUpgradeAttributes,
+		},
 	};
 	let channel_ids = channel_and_port_ids
 		.iter()
@@ -928,6 +981,15 @@ pub fn filter_events_by_ids(
 				.unwrap_or(false)
 	};
 
+	let filter_upgrade_attributes = |attributes: &ChannelUpgradeAttributes| {
+		channel_ids.contains(&&attributes.channel_id) ||
+			attributes
+				.counterparty_channel_id
+				.as_ref()
+				.map(|id| channel_ids.contains(&id))
+				.unwrap_or(false)
+	};
+
 	let v = match ev {
 		IbcEvent::SendPacket(e) => filter_packet(&e.packet),
 		IbcEvent::WriteAcknowledgement(e) => filter_packet(&e.packet),
@@ -955,6 +1017,15 @@ pub fn filter_events_by_ids(
 			filter_channel_attributes(&ChannelAttributes::from(e.clone())),
 		IbcEvent::CloseConfirmChannel(e) =>
 			filter_channel_attributes(&ChannelAttributes::from(e.clone())),
+		IbcEvent::UpgradeInitChannel(_) |
+		IbcEvent::UpgradeTryChannel(_) |
+		IbcEvent::UpgradeAckChannel(_) |
+		IbcEvent::UpgradeConfirmChannel(_) |
+		IbcEvent::UpgradeOpenChannel(_) |
+		IbcEvent::UpgradeTimeoutChannel(_) |
+		IbcEvent::UpgradeCancelChannel(_) |
+		IbcEvent::UpgradeErrorChannel(_) =>
+			ev.upgrade_attributes().map(filter_upgrade_attributes).unwrap_or(false),
 		IbcEvent::PushWasmCode(_) => true,
 		IbcEvent::NewBlock(_) |
 		IbcEvent::AppModule(_) |
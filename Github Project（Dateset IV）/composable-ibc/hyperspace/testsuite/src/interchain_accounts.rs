@@ -0,0 +1,135 @@
+// Copyright 2022 ComposableFi
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::{timeout_future, StreamExt};
+use futures::future;
+use hyperspace_core::substrate::DefaultConfig;
+use hyperspace_parachain::ParachainClient;
+use hyperspace_primitives::{Chain, IbcProvider};
+use ibc::{
+	applications::{
+		interchain_accounts::host_port_id, transfer::acknowledgement::Acknowledgement as GenericAck,
+	},
+	core::ics24_host::identifier::ConnectionId,
+	events::IbcEvent,
+};
+use ibc_proto::google::protobuf::Any;
+use pallet_ibc::{ics27::RUNTIME_CALL_TYPE_URL, Timeout};
+use subxt::dynamic::Value;
+
+/// Registers an interchain account of chain A's signer on chain B over `connection_id`, then has
+/// it dispatch a `System::remark_with_event` on chain B and asserts the host acknowledged the
+/// transaction as successful.
+pub async fn ibc_messaging_interchain_account(
+	chain_a: &mut ParachainClient<DefaultConfig>,
+	chain_b: &mut ParachainClient<DefaultConfig>,
+	connection_id: ConnectionId,
+) {
+	let client_a_clone = chain_a.clone();
+	let client_b_clone = chain_b.clone();
+	// Start relayer loop
+	let handle = tokio::task::spawn(async move {
+		hyperspace_core::relay(client_a_clone, client_b_clone, None, None, None)
+			.await
+			.unwrap()
+	});
+
+	// Channel handshakes are relayed regardless of the whitelist
+	chain_a.register_interchain_account(connection_id.clone()).await.unwrap();
+	let future = chain_b
+		.ibc_events()
+		.await
+		.skip_while(|ev| {
+			future::ready(
+				!matches!(ev, IbcEvent::OpenConfirmChannel(open) if open.port_id == host_port_id()),
+			)
+		})
+		.take(1)
+		.collect::<Vec<_>>();
+	let events = timeout_future(
+		future,
+		20 * 60,
+		format!("Didn't see interchain account channel open on {}", chain_b.name()),
+	)
+	.await;
+	let (channel_a, controller_port_id, channel_b) = match &events[0] {
+		IbcEvent::OpenConfirmChannel(open) => (
+			open.counterparty_channel_id.unwrap(),
+			open.counterparty_port_id.clone(),
+			open.channel_id.unwrap(),
+		),
+		_ => unreachable!(),
+	};
+	log::info!(target: "hyperspace", "============ Interchain account channel open: ChannelId({channel_a}) ============");
+
+	chain_a.add_channel_to_whitelist((channel_a, controller_port_id));
+	chain_b.add_channel_to_whitelist((channel_b, host_port_id()));
+
+	let remark = chain_b
+		.runtime_call_data(
+			"System",
+			"remark_with_event",
+			vec![Value::from_bytes(b"hello from an interchain account")],
+		)
+		.unwrap();
+	chain_a
+		.send_interchain_account_tx(
+			connection_id,
+			vec![Any { type_url: RUNTIME_CALL_TYPE_URL.to_string(), value: remark }],
+			Timeout::Offset { timestamp: Some(60 * 60), height: Some(100) },
+		)
+		.await
+		.unwrap();
+
+	let future = chain_b
+		.ibc_events()
+		.await
+		.skip_while(|ev| {
+			future::ready(!matches!(
+				ev,
+				IbcEvent::WriteAcknowledgement(write)
+					if write.packet.destination_port == host_port_id()
+			))
+		})
+		.take(1)
+		.collect::<Vec<_>>();
+	let events = timeout_future(
+		future,
+		20 * 60,
+		format!("Didn't see interchain account acknowledgement on {}", chain_b.name()),
+	)
+	.await;
+	match &events[0] {
+		IbcEvent::WriteAcknowledgement(write) => {
+			let ack = json::from_slice::<GenericAck>(&write.ack).unwrap();
+			assert!(ack.is_successful(), "interchain account transaction failed: {ack}");
+		},
+		_ => unreachable!(),
+	}
+
+	let future = chain_a
+		.ibc_events()
+		.await
+		.skip_while(|ev| future::ready(!matches!(ev, IbcEvent::AcknowledgePacket(_))))
+		.take(1)
+		.collect::<Vec<_>>();
+	timeout_future(
+		future,
+		20 * 60,
+		format!("Didn't see interchain account acknowledgement on {}", chain_a.name()),
+	)
+	.await;
+	log::info!(target: "hyperspace", "🚀🚀 Interchain account transaction executed on {}", chain_b.name());
+	handle.abort()
+}
@@ -189,6 +189,7 @@ where
 				state_proof: state_proof.clone(),
 				extrinsic: timestamp_extrinsic.clone(),
 				extrinsic_proof: extrinsic_proof.clone(),
+				timestamp_storage_proof: vec![],
 			},
 		);
 	}
@@ -196,6 +197,7 @@ where
 	let grandpa_header = GrandpaHeader {
 		finality_proof,
 		parachain_headers,
+		solochain_headers: Default::default(),
 		height: Height::new(client_state.para_id as u64, parachain_header.number as u64),
 	};
 	let client_message = AnyClientMessage::Grandpa(ClientMessage::Header(grandpa_header));
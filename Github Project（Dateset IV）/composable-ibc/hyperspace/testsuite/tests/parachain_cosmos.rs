@@ -30,6 +30,8 @@ use hyperspace_testsuite::{
 	setup_connection_and_channel,
 };
 use ibc::core::ics24_host::identifier::PortId;
+use ics10_grandpa::client_state::ChainType;
+use light_client_common::{RelayChain, TimestampProofType};
 use sp_core::hashing::sha2_256;
 
 #[derive(Debug, Clone)]
@@ -87,6 +89,10 @@ async fn setup_clients() -> (AnyChain, AnyChain) {
 		private_key: "//Alice".to_string(),
 		key_type: "sr25519".to_string(),
 		wasm_checksum: None,
+		batch_recv_packets: false,
+		relay_chain: RelayChain::Rococo,
+		chain_type: ChainType::Parachain,
+		timestamp_proof_type: TimestampProofType::Extrinsic,
 	};
 
 	let mut config_b = CosmosClientConfig {
@@ -23,8 +23,11 @@ use hyperspace_testsuite::{
 	ibc_messaging_packet_height_timeout_with_connection_delay,
 	ibc_messaging_packet_timeout_on_channel_close,
 	ibc_messaging_packet_timestamp_timeout_with_connection_delay,
-	ibc_messaging_with_connection_delay, misbehaviour::ibc_messaging_submit_misbehaviour,
+	ibc_messaging_with_connection_delay, interchain_accounts::ibc_messaging_interchain_account,
+	misbehaviour::ibc_messaging_submit_misbehaviour,
 };
+use ics10_grandpa::client_state::ChainType;
+use light_client_common::{RelayChain, TimestampProofType};
 use std::time::Duration;
 
 #[derive(Debug, Clone)]
@@ -36,12 +39,18 @@ pub struct Args {
 	pub para_id_b: u32,
 	pub connection_prefix_a: String,
 	pub connection_prefix_b: String,
+	pub finality_protocol: FinalityProtocol,
 }
 
 impl Default for Args {
 	fn default() -> Self {
 		let relay = std::env::var("RELAY_HOST").unwrap_or_else(|_| "127.0.0.1".to_string());
 		let para = std::env::var("PARA_HOST").unwrap_or_else(|_| "127.0.0.1".to_string());
+		// relay with BEEFY instead of GRANDPA by setting `FINALITY_PROTOCOL=beefy`
+		let finality_protocol = match std::env::var("FINALITY_PROTOCOL") {
+			Ok(protocol) if protocol.eq_ignore_ascii_case("beefy") => FinalityProtocol::Beefy,
+			_ => FinalityProtocol::Grandpa,
+		};
 
 		Args {
 			chain_a: format!("ws://{para}:9988"),
@@ -51,6 +60,7 @@ impl Default for Args {
 			para_id_b: 2000,
 			connection_prefix_a: "ibc/".to_string(),
 			connection_prefix_b: "ibc/".to_string(),
+			finality_protocol,
 		}
 	}
 }
@@ -70,10 +80,14 @@ async fn setup_clients() -> (ParachainClient<DefaultConfig>, ParachainClient<Def
 		commitment_prefix: args.connection_prefix_b.as_bytes().to_vec().into(),
 		ss58_version: 42,
 		channel_whitelist: vec![],
-		finality_protocol: FinalityProtocol::Grandpa,
+		finality_protocol: args.finality_protocol.clone(),
 		private_key: "//Alice".to_string(),
 		key_type: "sr25519".to_string(),
 		wasm_checksum: None,
+		batch_recv_packets: false,
+		relay_chain: RelayChain::Rococo,
+		chain_type: ChainType::Parachain,
+		timestamp_proof_type: TimestampProofType::Extrinsic,
 	};
 	let config_b = ParachainClientConfig {
 		name: "9188".to_string(),
@@ -86,9 +100,13 @@ async fn setup_clients() -> (ParachainClient<DefaultConfig>, ParachainClient<Def
 		private_key: "//Alice".to_string(),
 		ss58_version: 42,
 		channel_whitelist: vec![],
-		finality_protocol: FinalityProtocol::Grandpa,
+		finality_protocol: args.finality_protocol.clone(),
 		key_type: "sr25519".to_string(),
 		wasm_checksum: None,
+		batch_recv_packets: false,
+		relay_chain: RelayChain::Rococo,
+		chain_type: ChainType::Parachain,
+		timestamp_proof_type: TimestampProofType::Extrinsic,
 	};
 
 	let mut chain_a = ParachainClient::<DefaultConfig>::new(config_a).await.unwrap();
@@ -143,7 +161,7 @@ async fn parachain_to_parachain_ibc_messaging_full_integration_test() {
 	handle.abort();
 
 	// Set connections and channel whitelist
-	chain_a.set_connection_id(connection_id_a);
+	chain_a.set_connection_id(connection_id_a.clone());
 	chain_b.set_connection_id(connection_id_b);
 
 	chain_a.set_channel_whitelist(vec![(channel_a, PortId::transfer())].into_iter().collect());
@@ -204,10 +222,17 @@ async fn parachain_to_parachain_ibc_messaging_full_integration_test() {
 		res.unwrap();
 	}
 
+	// interchain account on chain B controlled from chain A
+	ibc_messaging_interchain_account(&mut chain_a, &mut chain_b, connection_id_a).await;
+	log::info!(target: "hyperspace", "🚀🚀 finished interchain account transaction");
+
 	// Test sync abilities, run this before misbehaviour test
 	client_synchronization_test(&mut chain_a, &mut chain_b).await;
 
-	// misbehaviour
-	ibc_messaging_submit_misbehaviour(&mut chain_a, &mut chain_b).await;
-	log::info!(target: "hyperspace", "🚀🚀 Waiting for misbehaviour to be submitted");
+	// misbehaviour, the testsuite can only forge GRANDPA finality proofs, so BEEFY misbehaviour
+	// reporting is not exercised here
+	if matches!(chain_a.finality_protocol, FinalityProtocol::Grandpa) {
+		ibc_messaging_submit_misbehaviour(&mut chain_a, &mut chain_b).await;
+		log::info!(target: "hyperspace", "🚀🚀 Waiting for misbehaviour to be submitted");
+	}
 }
@@ -456,6 +456,112 @@ impl State {
 		}
 	}
 
+	fn impl_fn_verify_channel_upgrade(&self) -> proc_macro2::TokenStream {
+		let crate_ = &self.crate_ident;
+		let trait_ = &self.current_impl_trait;
+		let error = &self.current_impl_error;
+		let client_state_trait = &self.client_state_trait;
+		let cases = self.clients.iter().map(|client| {
+			let variant_ident = &client.variant_ident;
+			let attrs = &client.attrs;
+			quote! {
+				#(#attrs)*
+				Self::#variant_ident(client) => {
+					let client_type = #client_state_trait::client_type(client_state).to_owned();
+					let client_state = #crate_::downcast!(client_state => Self::ClientState::#variant_ident)
+						.ok_or_else(|| #error::client_args_type_mismatch(client_type))?;
+
+					#trait_::verify_channel_upgrade(
+						client,
+						ctx,
+						client_id,
+						client_state,
+						height,
+						prefix,
+						proof,
+						root,
+						port_id,
+						channel_id,
+						expected_upgrade,
+					)
+				}
+			}
+		});
+
+		quote! {
+			fn verify_channel_upgrade<Ctx: #crate_::core::ics26_routing::context::ReaderContext>(
+				&self,
+				ctx: &Ctx,
+				client_id: &#crate_::core::ics24_host::identifier::ClientId,
+				client_state: &Self::ClientState,
+				height: #crate_::core::ics02_client::height::Height,
+				prefix: &#crate_::core::ics23_commitment::commitment::CommitmentPrefix,
+				proof: &#crate_::core::ics23_commitment::commitment::CommitmentProofBytes,
+				root: &#crate_::core::ics23_commitment::commitment::CommitmentRoot,
+				port_id: &#crate_::core::ics24_host::identifier::PortId,
+				channel_id: &#crate_::core::ics24_host::identifier::ChannelId,
+				expected_upgrade: &#crate_::core::ics04_channel::upgrade::Upgrade,
+			) -> ::core::result::Result<(), #error> {
+				match self {
+					#(#cases)*
+				}
+			}
+		}
+	}
+
+	fn impl_fn_verify_channel_upgrade_error(&self) -> proc_macro2::TokenStream {
+		let crate_ = &self.crate_ident;
+		let trait_ = &self.current_impl_trait;
+		let error = &self.current_impl_error;
+		let client_state_trait = &self.client_state_trait;
+		let cases = self.clients.iter().map(|client| {
+			let variant_ident = &client.variant_ident;
+			let attrs = &client.attrs;
+			quote! {
+				#(#attrs)*
+				Self::#variant_ident(client) => {
+					let client_type = #client_state_trait::client_type(client_state).to_owned();
+					let client_state = #crate_::downcast!(client_state => Self::ClientState::#variant_ident)
+						.ok_or_else(|| #error::client_args_type_mismatch(client_type))?;
+
+					#trait_::verify_channel_upgrade_error(
+						client,
+						ctx,
+						client_id,
+						client_state,
+						height,
+						prefix,
+						proof,
+						root,
+						port_id,
+						channel_id,
+						expected_error_receipt,
+					)
+				}
+			}
+		});
+
+		quote! {
+			fn verify_channel_upgrade_error<Ctx: #crate_::core::ics26_routing::context::ReaderContext>(
+				&self,
+				ctx: &Ctx,
+				client_id: &#crate_::core::ics24_host::identifier::ClientId,
+				client_state: &Self::ClientState,
+				height: #crate_::core::ics02_client::height::Height,
+				prefix: &#crate_::core::ics23_commitment::commitment::CommitmentPrefix,
+				proof: &#crate_::core::ics23_commitment::commitment::CommitmentProofBytes,
+				root: &#crate_::core::ics23_commitment::commitment::CommitmentRoot,
+				port_id: &#crate_::core::ics24_host::identifier::PortId,
+				channel_id: &#crate_::core::ics24_host::identifier::ChannelId,
+				expected_error_receipt: &#crate_::core::ics04_channel::upgrade::ErrorReceipt,
+			) -> ::core::result::Result<(), #error> {
+				match self {
+					#(#cases)*
+				}
+			}
+		}
+	}
+
 	fn impl_fn_verify_client_full_state(&self) -> proc_macro2::TokenStream {
 		let crate_ = &self.crate_ident;
 		let trait_ = &self.current_impl_trait;
@@ -676,6 +782,116 @@ impl State {
 		}
 	}
 
+	fn impl_fn_verify_packet_receipt(&self) -> proc_macro2::TokenStream {
+		let crate_ = &self.crate_ident;
+		let trait_ = &self.current_impl_trait;
+		let error = &self.current_impl_error;
+		let client_state_trait = &self.client_state_trait;
+		let cases = self.clients.iter().map(|client| {
+			let variant_ident = &client.variant_ident;
+			let attrs = &client.attrs;
+			let _client_state_path = &client.client_state_path;
+			quote! {
+				#(#attrs)*
+				Self::#variant_ident(client) => {
+					let client_type = #client_state_trait::client_type(client_state).to_owned();
+					let client_state = #crate_::downcast!(
+						client_state => Self::ClientState::#variant_ident
+					)
+					.ok_or_else(|| #error::client_args_type_mismatch(client_type))?;
+
+					#trait_::verify_packet_receipt(
+						client,
+						ctx,
+						client_id,
+						client_state,
+						height,
+						connection_end,
+						proof,
+						root,
+						port_id,
+						channel_id,
+						sequence,
+						receipt,
+					)
+				}
+			}
+		});
+
+		quote! {
+			fn verify_packet_receipt<Ctx: #crate_::core::ics26_routing::context::ReaderContext>(
+				&self,
+				ctx: &Ctx,
+				client_id: &#crate_::core::ics24_host::identifier::ClientId,
+				client_state: &Self::ClientState,
+				height: #crate_::core::ics02_client::height::Height,
+				connection_end: &#crate_::core::ics03_connection::connection::ConnectionEnd,
+				proof: &#crate_::core::ics23_commitment::commitment::CommitmentProofBytes,
+				root: &#crate_::core::ics23_commitment::commitment::CommitmentRoot,
+				port_id: &#crate_::core::ics24_host::identifier::PortId,
+				channel_id: &#crate_::core::ics24_host::identifier::ChannelId,
+				sequence: #crate_::core::ics04_channel::packet::Sequence,
+				receipt: #crate_::core::ics04_channel::packet::Receipt,
+			) -> ::core::result::Result<(), #error> {
+				match self {
+					#(#cases)*
+				}
+			}
+		}
+	}
+
+	fn impl_fn_verify_batch_membership(&self) -> proc_macro2::TokenStream {
+		let crate_ = &self.crate_ident;
+		let trait_ = &self.current_impl_trait;
+		let error = &self.current_impl_error;
+		let client_state_trait = &self.client_state_trait;
+		let cases = self.clients.iter().map(|client| {
+			let variant_ident = &client.variant_ident;
+			let attrs = &client.attrs;
+			let _client_state_path = &client.client_state_path;
+			quote! {
+				#(#attrs)*
+				Self::#variant_ident(client) => {
+					let client_type = #client_state_trait::client_type(client_state).to_owned();
+					let client_state = #crate_::downcast!(
+						client_state => Self::ClientState::#variant_ident
+					)
+					.ok_or_else(|| #error::client_args_type_mismatch(client_type))?;
+
+					#trait_::verify_batch_membership(
+						client,
+						ctx,
+						client_id,
+						client_state,
+						height,
+						connection_end,
+						proof,
+						root,
+						items,
+					)
+				}
+			}
+		});
+
+		quote! {
+			fn verify_batch_membership<Ctx: #crate_::core::ics26_routing::context::ReaderContext>(
+				&self,
+				ctx: &Ctx,
+				client_id: &#crate_::core::ics24_host::identifier::ClientId,
+				client_state: &Self::ClientState,
+				height: #crate_::core::ics02_client::height::Height,
+				connection_end: &#crate_::core::ics03_connection::connection::ConnectionEnd,
+				proof: &#crate_::core::ics23_commitment::commitment::CommitmentProofBytes,
+				root: &#crate_::core::ics23_commitment::commitment::CommitmentRoot,
+				items: ::alloc::vec::Vec<(#crate_::core::ics24_host::Path, ::alloc::vec::Vec<u8>)>,
+			) -> ::core::result::Result<(), #error> {
+				match self {
+					#(#cases)*
+				}
+			}
+		}
+	}
+
 	fn impl_fn_verify_packet_receipt_absence(&self) -> proc_macro2::TokenStream {
 		let crate_ = &self.crate_ident;
 		let trait_ = &self.current_impl_trait;
@@ -732,6 +948,54 @@ impl State {
 		}
 	}
 
+	fn impl_fn_update_state_on_proof_verified(&self) -> proc_macro2::TokenStream {
+		let crate_ = &self.crate_ident;
+		let trait_ = &self.current_impl_trait;
+		let error = &self.current_impl_error;
+		let any_client_state = &self.any_data.client_state_ident;
+		let gen_params = &self.generics.params;
+		let client_state_trait = &self.client_state_trait;
+		let cases = self.clients.iter().map(|client| {
+			let variant_ident = &client.variant_ident;
+			let attrs = &client.attrs;
+			quote! {
+				#(#attrs)*
+				Self::#variant_ident(client) => {
+					let client_type = #client_state_trait::client_type(&client_state).to_owned();
+					let client_state = #crate_::downcast!(
+						client_state => #any_client_state::<#gen_params>::#variant_ident
+					)
+					.ok_or_else(|| #error::client_args_type_mismatch(client_type))?;
+
+					let updated =
+						#trait_::update_state_on_proof_verified(client, ctx, client_state, proof)?;
+					Ok(updated.map(|(new_state, new_consensus)| {
+						(Self::ClientState::#variant_ident(new_state), new_consensus)
+					}))
+				}
+			}
+		});
+
+		quote! {
+			fn update_state_on_proof_verified<Ctx: #crate_::core::ics26_routing::context::ReaderContext>(
+				&self,
+				ctx: &Ctx,
+				client_state: Self::ClientState,
+				proof: &#crate_::core::ics23_commitment::commitment::CommitmentProofBytes,
+			) -> ::core::result::Result<
+				::core::option::Option<(
+					Self::ClientState,
+					#crate_::core::ics02_client::client_def::ConsensusUpdateResult<Ctx>,
+				)>,
+				#error,
+			> {
+				match self {
+					#(#cases)*
+				}
+			}
+		}
+	}
+
 	pub fn impl_client_def(&mut self) -> proc_macro2::TokenStream {
 		let crate_ = &self.crate_ident;
 		self.current_impl_trait =
@@ -755,11 +1019,16 @@ impl State {
 		let fn_verify_client_consensus_state = self.impl_fn_verify_client_consensus_state();
 		let fn_verify_connection_state = self.impl_fn_verify_connection_state();
 		let fn_verify_channel_state = self.impl_fn_verify_channel_state();
+		let fn_verify_channel_upgrade = self.impl_fn_verify_channel_upgrade();
+		let fn_verify_channel_upgrade_error = self.impl_fn_verify_channel_upgrade_error();
 		let fn_verify_client_full_state = self.impl_fn_verify_client_full_state();
 		let fn_verify_packet_data = self.impl_fn_verify_packet_data();
 		let fn_verify_packet_acknowledgement = self.impl_fn_verify_packet_acknowledgement();
 		let fn_verify_next_sequence_recv = self.impl_fn_verify_next_sequence_recv();
+		let fn_verify_packet_receipt = self.impl_fn_verify_packet_receipt();
+		let fn_verify_batch_membership = self.impl_fn_verify_batch_membership();
 		let fn_verify_packet_receipt_absence = self.impl_fn_verify_packet_receipt_absence();
+		let fn_update_state_on_proof_verified = self.impl_fn_update_state_on_proof_verified();
 
 		quote! {
 			impl #impl_generics #client_def_trait for #this #ty_generics #where_clause {
@@ -776,11 +1045,16 @@ impl State {
 				#fn_verify_client_consensus_state
 				#fn_verify_connection_state
 				#fn_verify_channel_state
+				#fn_verify_channel_upgrade
+				#fn_verify_channel_upgrade_error
 				#fn_verify_client_full_state
 				#fn_verify_packet_data
 				#fn_verify_packet_acknowledgement
 				#fn_verify_next_sequence_recv
+				#fn_verify_packet_receipt
+				#fn_verify_batch_membership
 				#fn_verify_packet_receipt_absence
+				#fn_update_state_on_proof_verified
 			}
 		}
 	}
@@ -0,0 +1,86 @@
+// Copyright 2022 ComposableFi
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use ibc_proto::google::protobuf::Any;
+
+use super::error::Error;
+use crate::{
+	core::ics24_host::identifier::{ChannelId, ConnectionId, PortId},
+	prelude::*,
+};
+
+/// State needed by the host side of interchain accounts
+pub trait InterchainAccountHostContext {
+	/// Returns false if the host does not accept new channels or packets
+	fn is_host_enabled(&self) -> bool;
+
+	/// Returns the channel currently used by the controller port on the connection, only if the
+	/// channel is still open
+	fn host_active_channel(
+		&self,
+		connection_id: &ConnectionId,
+		controller_port_id: &PortId,
+	) -> Option<ChannelId>;
+
+	/// Records the channel used by the controller port on the connection
+	fn store_host_active_channel(
+		&mut self,
+		connection_id: &ConnectionId,
+		controller_port_id: &PortId,
+		channel_id: &ChannelId,
+	) -> Result<(), Error>;
+
+	/// Returns the address of the interchain account owned by the controller port
+	fn interchain_account(
+		&self,
+		connection_id: &ConnectionId,
+		controller_port_id: &PortId,
+	) -> Option<String>;
+
+	/// Derives and registers a new interchain account owned by the controller port, returns its
+	/// address
+	fn register_interchain_account(
+		&mut self,
+		connection_id: &ConnectionId,
+		controller_port_id: &PortId,
+	) -> Result<String, Error>;
+
+	/// Executes `messages` atomically on behalf of the interchain account, returns a response
+	/// for every message
+	fn execute_tx(&mut self, address: &str, messages: Vec<Any>) -> Result<Vec<Any>, Error>;
+}
+
+/// State needed by the controller side of interchain accounts
+pub trait InterchainAccountControllerContext {
+	/// Returns false if the controller does not accept new channels or packets
+	fn is_controller_enabled(&self) -> bool;
+
+	/// Returns the channel currently used by the controller port on the connection, only if the
+	/// channel is still open
+	fn controller_active_channel(
+		&self,
+		connection_id: &ConnectionId,
+		port_id: &PortId,
+	) -> Option<ChannelId>;
+
+	/// Records the channel used by the controller port on the connection along with the address
+	/// of the interchain account registered by the host
+	fn store_controller_active_channel(
+		&mut self,
+		connection_id: &ConnectionId,
+		port_id: &PortId,
+		channel_id: &ChannelId,
+		address: String,
+	) -> Result<(), Error>;
+}
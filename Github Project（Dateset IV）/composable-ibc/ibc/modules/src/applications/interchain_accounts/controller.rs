@@ -0,0 +1,104 @@
+// Copyright 2022 ComposableFi
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Channel handshake callbacks and packet construction of the interchain accounts controller.
+use ibc_proto::google::protobuf::Any;
+
+use super::{
+	context::InterchainAccountControllerContext, error::Error, host::single_hop,
+	is_controller_port, metadata::Metadata, packet::InterchainAccountPacketData, HOST_PORT_ID_STR,
+};
+use crate::{
+	core::{
+		ics04_channel::{
+			channel::{Counterparty, Order},
+			Version,
+		},
+		ics24_host::identifier::{ChannelId, ConnectionId, PortId},
+	},
+	prelude::*,
+};
+
+/// Validates a channel opened by an interchain account owner
+pub fn on_chan_open_init(
+	ctx: &impl InterchainAccountControllerContext,
+	order: Order,
+	connection_hops: &[ConnectionId],
+	port_id: &PortId,
+	counterparty: &Counterparty,
+	version: &Version,
+) -> Result<(), Error> {
+	if !ctx.is_controller_enabled() {
+		return Err(Error::controller_disabled())
+	}
+	if order != Order::Ordered {
+		return Err(Error::channel_not_ordered(order))
+	}
+	if !is_controller_port(port_id) {
+		return Err(Error::invalid_controller_port(port_id.clone()))
+	}
+	if counterparty.port_id().as_str() != HOST_PORT_ID_STR {
+		return Err(Error::invalid_host_port(counterparty.port_id().clone()))
+	}
+	let connection_id = single_hop(connection_hops)?;
+	Metadata::try_from(version)?.validate(Some(connection_id), None)?;
+
+	if let Some(channel_id) = ctx.controller_active_channel(connection_id, port_id) {
+		return Err(Error::active_channel_exists(port_id.clone(), channel_id))
+	}
+	Ok(())
+}
+
+/// Records the interchain account address returned by the host
+pub fn on_chan_open_ack(
+	ctx: &mut impl InterchainAccountControllerContext,
+	connection_id: &ConnectionId,
+	port_id: &PortId,
+	channel_id: &ChannelId,
+	counterparty_version: &Version,
+) -> Result<(), Error> {
+	if !is_controller_port(port_id) {
+		return Err(Error::invalid_controller_port(port_id.clone()))
+	}
+	let metadata = Metadata::try_from(counterparty_version)?;
+	metadata.validate(Some(connection_id), None)?;
+	if metadata.address.is_empty() {
+		return Err(Error::empty_address())
+	}
+	if let Some(channel_id) = ctx.controller_active_channel(connection_id, port_id) {
+		return Err(Error::active_channel_exists(port_id.clone(), channel_id))
+	}
+	ctx.store_controller_active_channel(connection_id, port_id, channel_id, metadata.address)
+}
+
+/// Returns the active channel of the owner port and the packet data executing `messages` on the
+/// host chain.
+pub fn send_tx(
+	ctx: &impl InterchainAccountControllerContext,
+	connection_id: &ConnectionId,
+	port_id: &PortId,
+	messages: Vec<Any>,
+	memo: String,
+) -> Result<(ChannelId, Vec<u8>), Error> {
+	if !ctx.is_controller_enabled() {
+		return Err(Error::controller_disabled())
+	}
+	if messages.is_empty() {
+		return Err(Error::empty_tx())
+	}
+	let channel_id = ctx
+		.controller_active_channel(connection_id, port_id)
+		.ok_or_else(|| Error::no_active_channel(port_id.clone(), connection_id.clone()))?;
+	Ok((channel_id, InterchainAccountPacketData::execute_tx(messages, memo).to_bytes()))
+}
@@ -0,0 +1,123 @@
+// Copyright 2022 ComposableFi
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use flex_error::{define_error, DisplayOnly};
+
+use crate::{
+	core::{
+		ics04_channel::channel::Order,
+		ics24_host::{
+			error::ValidationError,
+			identifier::{ChannelId, ConnectionId, PortId},
+		},
+	},
+	prelude::*,
+};
+
+define_error! {
+	#[derive(Debug, PartialEq, Eq)]
+	Error {
+		EmptyOwner
+			| _ | { "interchain account owner is empty" },
+
+		InvalidPortId
+			{ context: String }
+			[ ValidationError ]
+			| _ | { "invalid port identifier" },
+
+		InvalidHostPort
+			{ port_id: PortId }
+			| e | { format_args!("expected host port '{0}', got '{1}'", super::HOST_PORT_ID_STR, e.port_id) },
+
+		InvalidControllerPort
+			{ port_id: PortId }
+			| e | { format_args!("expected controller port prefixed with '{0}', got '{1}'", super::CONTROLLER_PORT_PREFIX, e.port_id) },
+
+		ChannelNotOrdered
+			{ order: Order }
+			| e | { format_args!("expected '{0}' channel, got '{1}'", Order::Ordered, e.order) },
+
+		InvalidConnectionHops
+			{ len: usize }
+			| e | { format_args!("expected a single connection hop, got {0}", e.len) },
+
+		MetadataDeserialization
+			{ reason: String }
+			| e | { format_args!("failed to deserialize channel version metadata: {0}", e.reason) },
+
+		InvalidVersion
+			{ version: String }
+			| e | { format_args!("expected version '{0}', got '{1}'", super::VERSION, e.version) },
+
+		UnsupportedEncoding
+			{ encoding: String }
+			| e | { format_args!("unsupported encoding '{0}'", e.encoding) },
+
+		UnsupportedTxType
+			{ tx_type: String }
+			| e | { format_args!("unsupported transaction type '{0}'", e.tx_type) },
+
+		ConnectionMismatch
+			{ expected: ConnectionId, actual: String }
+			| e | { format_args!("expected connection '{0}', got '{1}'", e.expected, e.actual) },
+
+		ActiveChannelExists
+			{ port_id: PortId, channel_id: ChannelId }
+			| e | { format_args!("an active channel {0} already exists for port {1}", e.channel_id, e.port_id) },
+
+		NoActiveChannel
+			{ port_id: PortId, connection_id: ConnectionId }
+			| e | { format_args!("no active channel for port {0} on connection {1}", e.port_id, e.connection_id) },
+
+		EmptyAddress
+			| _ | { "interchain account address is empty" },
+
+		AccountNotFound
+			{ port_id: PortId, connection_id: ConnectionId }
+			| e | { format_args!("no interchain account registered for port {0} on connection {1}", e.port_id, e.connection_id) },
+
+		HostDisabled
+			| _ | { "interchain accounts host is disabled" },
+
+		ControllerDisabled
+			| _ | { "interchain accounts controller is disabled" },
+
+		PacketDataDeserialization
+			{ reason: String }
+			| e | { format_args!("failed to deserialize packet data: {0}", e.reason) },
+
+		UnsupportedPacketType
+			{ packet_type: String }
+			| e | { format_args!("unsupported packet type '{0}'", e.packet_type) },
+
+		CosmosTxDeserialization
+			[ DisplayOnly<prost::DecodeError> ]
+			| _ | { "failed to decode cosmos tx" },
+
+		EmptyTx
+			| _ | { "transaction contains no messages" },
+
+		MessageNotAllowed
+			{ type_url: String }
+			| e | { format_args!("message type '{0}' is not allowed on this host", e.type_url) },
+
+		ExecutionFailed
+			{ reason: String }
+			| e | { format_args!("failed to execute transaction: {0}", e.reason) },
+
+		Implementation
+			{ reason: String }
+			| e | { format_args!("{0}", e.reason) },
+	}
+}
@@ -0,0 +1,123 @@
+// Copyright 2022 ComposableFi
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Channel handshake and packet callbacks of the interchain accounts host.
+use ibc_proto::google::protobuf::Any;
+
+use super::{
+	context::InterchainAccountHostContext,
+	error::Error,
+	is_controller_port,
+	metadata::Metadata,
+	packet::{tx_msg_data, InterchainAccountPacketData},
+	HOST_PORT_ID_STR,
+};
+use crate::{
+	applications::transfer::acknowledgement::Acknowledgement,
+	core::{
+		ics04_channel::{
+			channel::{Counterparty, Order},
+			packet::Packet,
+			Version,
+		},
+		ics24_host::identifier::{ChannelId, ConnectionId, PortId},
+	},
+	prelude::*,
+};
+
+pub(crate) fn single_hop(connection_hops: &[ConnectionId]) -> Result<&ConnectionId, Error> {
+	match connection_hops {
+		[connection_id] => Ok(connection_id),
+		_ => Err(Error::invalid_connection_hops(connection_hops.len())),
+	}
+}
+
+/// Validates the controller proposed metadata and registers the interchain account, returns the
+/// channel version with the account address filled in.
+pub fn on_chan_open_try(
+	ctx: &mut impl InterchainAccountHostContext,
+	order: Order,
+	connection_hops: &[ConnectionId],
+	port_id: &PortId,
+	counterparty: &Counterparty,
+	counterparty_version: &Version,
+) -> Result<Version, Error> {
+	if !ctx.is_host_enabled() {
+		return Err(Error::host_disabled())
+	}
+	if order != Order::Ordered {
+		return Err(Error::channel_not_ordered(order))
+	}
+	if port_id.as_str() != HOST_PORT_ID_STR {
+		return Err(Error::invalid_host_port(port_id.clone()))
+	}
+	let controller_port_id = counterparty.port_id();
+	if !is_controller_port(controller_port_id) {
+		return Err(Error::invalid_controller_port(controller_port_id.clone()))
+	}
+	let connection_id = single_hop(connection_hops)?;
+
+	let mut metadata = Metadata::try_from(counterparty_version)?;
+	metadata.validate(None, Some(connection_id))?;
+
+	if let Some(channel_id) = ctx.host_active_channel(connection_id, controller_port_id) {
+		return Err(Error::active_channel_exists(controller_port_id.clone(), channel_id))
+	}
+
+	// Accounts are reused when a controller reopens a channel after the previous one was closed
+	metadata.address = match ctx.interchain_account(connection_id, controller_port_id) {
+		Some(address) => address,
+		None => ctx.register_interchain_account(connection_id, controller_port_id)?,
+	};
+
+	Ok(metadata.into())
+}
+
+/// Marks the channel as the active channel of the controller port
+pub fn on_chan_open_confirm(
+	ctx: &mut impl InterchainAccountHostContext,
+	connection_id: &ConnectionId,
+	controller_port_id: &PortId,
+	channel_id: &ChannelId,
+) -> Result<(), Error> {
+	ctx.store_host_active_channel(connection_id, controller_port_id, channel_id)
+}
+
+/// Executes the transaction carried by the packet on behalf of the interchain account owned by
+/// the packet source port.
+pub fn on_recv_packet(
+	ctx: &mut impl InterchainAccountHostContext,
+	connection_id: &ConnectionId,
+	packet: &Packet,
+) -> Acknowledgement {
+	match execute_tx(ctx, connection_id, packet) {
+		Ok(msg_responses) => Acknowledgement::Result(base64::encode(tx_msg_data(msg_responses))),
+		Err(e) => Acknowledgement::Error(e.to_string()),
+	}
+}
+
+fn execute_tx(
+	ctx: &mut impl InterchainAccountHostContext,
+	connection_id: &ConnectionId,
+	packet: &Packet,
+) -> Result<Vec<Any>, Error> {
+	if !ctx.is_host_enabled() {
+		return Err(Error::host_disabled())
+	}
+	let messages = InterchainAccountPacketData::from_bytes(&packet.data)?.messages()?;
+	let address = ctx.interchain_account(connection_id, &packet.source_port).ok_or_else(|| {
+		Error::account_not_found(packet.source_port.clone(), connection_id.clone())
+	})?;
+	ctx.execute_tx(&address, messages)
+}
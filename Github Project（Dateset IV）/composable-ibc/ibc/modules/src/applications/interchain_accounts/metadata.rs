@@ -0,0 +1,105 @@
+// Copyright 2022 ComposableFi
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use serde::{Deserialize, Serialize};
+
+use super::{error::Error, ENCODING_PROTOBUF, TX_TYPE_SDK_MULTI_MSG, VERSION};
+use crate::{
+	core::{ics04_channel::Version, ics24_host::identifier::ConnectionId},
+	prelude::*,
+};
+
+/// Protocol specific data encoded in the version of ICS27 channels
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Metadata {
+	/// ICS27 protocol version
+	pub version: String,
+	/// Connection identifier on the controller chain
+	pub controller_connection_id: String,
+	/// Connection identifier on the host chain
+	pub host_connection_id: String,
+	/// Interchain account address, filled in by the host in `OnChanOpenTry`
+	#[serde(default)]
+	pub address: String,
+	/// Encoding of the host transactions
+	pub encoding: String,
+	/// Type of transactions the interchain account can execute
+	pub tx_type: String,
+}
+
+impl Metadata {
+	/// Metadata proposed by a controller when registering an interchain account
+	pub fn new(controller_connection_id: &ConnectionId, host_connection_id: &ConnectionId) -> Self {
+		Self {
+			version: VERSION.to_string(),
+			controller_connection_id: controller_connection_id.to_string(),
+			host_connection_id: host_connection_id.to_string(),
+			address: String::new(),
+			encoding: ENCODING_PROTOBUF.to_string(),
+			tx_type: TX_TYPE_SDK_MULTI_MSG.to_string(),
+		}
+	}
+
+	/// Checks that the version, encoding and transaction type are supported and that the
+	/// connection identifiers match the ones the channel is built on.
+	pub fn validate(
+		&self,
+		controller_connection_id: Option<&ConnectionId>,
+		host_connection_id: Option<&ConnectionId>,
+	) -> Result<(), Error> {
+		if self.version != VERSION {
+			return Err(Error::invalid_version(self.version.clone()))
+		}
+		if self.encoding != ENCODING_PROTOBUF {
+			return Err(Error::unsupported_encoding(self.encoding.clone()))
+		}
+		if self.tx_type != TX_TYPE_SDK_MULTI_MSG {
+			return Err(Error::unsupported_tx_type(self.tx_type.clone()))
+		}
+		if let Some(connection_id) = controller_connection_id {
+			if self.controller_connection_id != connection_id.as_str() {
+				return Err(Error::connection_mismatch(
+					connection_id.clone(),
+					self.controller_connection_id.clone(),
+				))
+			}
+		}
+		if let Some(connection_id) = host_connection_id {
+			if self.host_connection_id != connection_id.as_str() {
+				return Err(Error::connection_mismatch(
+					connection_id.clone(),
+					self.host_connection_id.clone(),
+				))
+			}
+		}
+		Ok(())
+	}
+}
+
+impl TryFrom<&Version> for Metadata {
+	type Error = Error;
+
+	fn try_from(version: &Version) -> Result<Self, Self::Error> {
+		serde_json::from_str(&version.to_string())
+			.map_err(|e| Error::metadata_deserialization(e.to_string()))
+	}
+}
+
+impl From<Metadata> for Version {
+	fn from(metadata: Metadata) -> Self {
+		Version::new(
+			serde_json::to_string(&metadata).expect("Metadata serialization is infallible; qed"),
+		)
+	}
+}
@@ -0,0 +1,283 @@
+// Copyright 2022 ComposableFi
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! ICS 27: Interchain Accounts allows a controller chain to register accounts on a host chain
+//! and to execute transactions on the host chain on behalf of those accounts over an ordered
+//! channel.
+pub mod context;
+pub mod controller;
+pub mod error;
+pub mod host;
+pub mod metadata;
+pub mod packet;
+
+use crate::{core::ics24_host::identifier::PortId, prelude::*};
+use core::str::FromStr;
+use error::Error;
+
+/// Module identifier for the ICS27 host application.
+pub const HOST_MODULE_ID_STR: &str = "icahost";
+
+/// Module identifier for the ICS27 controller application.
+pub const CONTROLLER_MODULE_ID_STR: &str = "icacontroller";
+
+/// The port identifier the ICS27 host application binds with.
+pub const HOST_PORT_ID_STR: &str = "icahost";
+
+/// Prefix of the port identifiers bound by the ICS27 controller application, the prefix is
+/// followed by the owner of the interchain account.
+pub const CONTROLLER_PORT_PREFIX: &str = "icacontroller-";
+
+/// ICS27 application current version.
+pub const VERSION: &str = "ics27-1";
+
+/// Protobuf encoding of the host transactions.
+pub const ENCODING_PROTOBUF: &str = "proto3";
+
+/// Transaction type supported by the host, a list of messages executed atomically.
+pub const TX_TYPE_SDK_MULTI_MSG: &str = "sdk_multi_msg";
+
+/// Returns the controller port identifier of an interchain account owner
+pub fn controller_port_id(owner: &str) -> Result<PortId, Error> {
+	if owner.trim().is_empty() {
+		return Err(Error::empty_owner())
+	}
+	PortId::from_str(&format!("{CONTROLLER_PORT_PREFIX}{owner}"))
+		.map_err(|e| Error::invalid_port_id(owner.to_string(), e))
+}
+
+/// Returns true if the port is a controller port
+pub fn is_controller_port(port_id: &PortId) -> bool {
+	port_id.as_str().starts_with(CONTROLLER_PORT_PREFIX)
+}
+
+/// Returns the host port identifier
+pub fn host_port_id() -> PortId {
+	PortId::from_str(HOST_PORT_ID_STR).expect("HOST_PORT_ID_STR is static and valid; qed")
+}
+
+#[cfg(test)]
+mod tests {
+	use super::{
+		context::{InterchainAccountControllerContext, InterchainAccountHostContext},
+		metadata::Metadata,
+		*,
+	};
+	use crate::{
+		applications::transfer::acknowledgement::Acknowledgement,
+		core::{
+			ics04_channel::{
+				channel::{Counterparty, Order},
+				packet::Packet,
+				Version,
+			},
+			ics24_host::identifier::{ChannelId, ConnectionId},
+		},
+	};
+	use alloc::collections::BTreeMap;
+	use ibc_proto::google::protobuf::Any;
+
+	const BANK_SEND: &str = "/cosmos.bank.v1beta1.MsgSend";
+
+	/// Host that behaves like a cosmos chain running the ibc-go host module with only bank sends
+	/// allowed.
+	#[derive(Default)]
+	struct MockCosmosHost {
+		channels: BTreeMap<(ConnectionId, PortId), ChannelId>,
+		accounts: BTreeMap<(ConnectionId, PortId), String>,
+		executed: Vec<(String, Any)>,
+	}
+
+	impl InterchainAccountHostContext for MockCosmosHost {
+		fn is_host_enabled(&self) -> bool {
+			true
+		}
+
+		fn host_active_channel(&self, conn: &ConnectionId, port: &PortId) -> Option<ChannelId> {
+			self.channels.get(&(conn.clone(), port.clone())).cloned()
+		}
+
+		fn store_host_active_channel(
+			&mut self,
+			conn: &ConnectionId,
+			port: &PortId,
+			channel_id: &ChannelId,
+		) -> Result<(), Error> {
+			self.channels.insert((conn.clone(), port.clone()), *channel_id);
+			Ok(())
+		}
+
+		fn interchain_account(&self, conn: &ConnectionId, port: &PortId) -> Option<String> {
+			self.accounts.get(&(conn.clone(), port.clone())).cloned()
+		}
+
+		fn register_interchain_account(
+			&mut self,
+			conn: &ConnectionId,
+			port: &PortId,
+		) -> Result<String, Error> {
+			let address = format!("cosmos1{}", self.accounts.len());
+			self.accounts.insert((conn.clone(), port.clone()), address.clone());
+			Ok(address)
+		}
+
+		fn execute_tx(&mut self, address: &str, messages: Vec<Any>) -> Result<Vec<Any>, Error> {
+			if let Some(msg) = messages.iter().find(|msg| msg.type_url != BANK_SEND) {
+				return Err(Error::message_not_allowed(msg.type_url.clone()))
+			}
+			let responses = messages
+				.iter()
+				.map(|msg| Any { type_url: format!("{}Response", msg.type_url), value: vec![] })
+				.collect();
+			self.executed.extend(messages.into_iter().map(|msg| (address.to_string(), msg)));
+			Ok(responses)
+		}
+	}
+
+	#[derive(Default)]
+	struct MockController {
+		channels: BTreeMap<(ConnectionId, PortId), (ChannelId, String)>,
+	}
+
+	impl InterchainAccountControllerContext for MockController {
+		fn is_controller_enabled(&self) -> bool {
+			true
+		}
+
+		fn controller_active_channel(
+			&self,
+			conn: &ConnectionId,
+			port: &PortId,
+		) -> Option<ChannelId> {
+			self.channels
+				.get(&(conn.clone(), port.clone()))
+				.map(|(channel_id, _)| *channel_id)
+		}
+
+		fn store_controller_active_channel(
+			&mut self,
+			conn: &ConnectionId,
+			port: &PortId,
+			channel_id: &ChannelId,
+			address: String,
+		) -> Result<(), Error> {
+			self.channels.insert((conn.clone(), port.clone()), (*channel_id, address));
+			Ok(())
+		}
+	}
+
+	#[test]
+	fn controller_registers_account_and_executes_tx_on_cosmos_host() {
+		let mut controller = MockController::default();
+		let mut host = MockCosmosHost::default();
+		let controller_conn = ConnectionId::new(0);
+		let host_conn = ConnectionId::new(1);
+		let controller_port = controller_port_id("owner").unwrap();
+		let (controller_channel, host_channel) = (ChannelId::new(0), ChannelId::new(3));
+
+		let version = Version::from(Metadata::new(&controller_conn, &host_conn));
+		controller::on_chan_open_init(
+			&controller,
+			Order::Ordered,
+			&[controller_conn.clone()],
+			&controller_port,
+			&Counterparty::new(host_port_id(), None),
+			&version,
+		)
+		.unwrap();
+
+		let host_version = host::on_chan_open_try(
+			&mut host,
+			Order::Ordered,
+			&[host_conn.clone()],
+			&host_port_id(),
+			&Counterparty::new(controller_port.clone(), Some(controller_channel)),
+			&version,
+		)
+		.unwrap();
+		assert_eq!(Metadata::try_from(&host_version).unwrap().address, "cosmos10");
+
+		controller::on_chan_open_ack(
+			&mut controller,
+			&controller_conn,
+			&controller_port,
+			&controller_channel,
+			&host_version,
+		)
+		.unwrap();
+		host::on_chan_open_confirm(&mut host, &host_conn, &controller_port, &host_channel).unwrap();
+
+		// A second channel can't be opened while the first one is active
+		assert!(host::on_chan_open_try(
+			&mut host,
+			Order::Ordered,
+			&[host_conn.clone()],
+			&host_port_id(),
+			&Counterparty::new(controller_port.clone(), Some(ChannelId::new(1))),
+			&version,
+		)
+		.is_err());
+
+		let msg = Any { type_url: BANK_SEND.to_string(), value: vec![1, 2, 3] };
+		let (channel_id, data) = controller::send_tx(
+			&controller,
+			&controller_conn,
+			&controller_port,
+			vec![msg.clone()],
+			String::new(),
+		)
+		.unwrap();
+		assert_eq!(channel_id, controller_channel);
+
+		let packet = Packet {
+			source_port: controller_port.clone(),
+			source_channel: controller_channel,
+			destination_port: host_port_id(),
+			destination_channel: host_channel,
+			data,
+			..Default::default()
+		};
+		let ack = host::on_recv_packet(&mut host, &host_conn, &packet);
+		assert!(ack.is_successful());
+		assert_eq!(host.executed, vec![("cosmos10".to_string(), msg)]);
+
+		// Messages the host does not allow produce an error acknowledgement
+		let (_, data) = controller::send_tx(
+			&controller,
+			&controller_conn,
+			&controller_port,
+			vec![Any { type_url: "/cosmos.staking.v1beta1.MsgDelegate".into(), value: vec![] }],
+			String::new(),
+		)
+		.unwrap();
+		let ack = host::on_recv_packet(&mut host, &host_conn, &Packet { data, ..packet });
+		assert!(matches!(ack, Acknowledgement::Error(_)));
+		assert_eq!(host.executed.len(), 1);
+	}
+
+	#[test]
+	fn channels_must_be_ordered() {
+		let controller = MockController::default();
+		let conn = ConnectionId::new(0);
+		assert!(controller::on_chan_open_init(
+			&controller,
+			Order::Unordered,
+			&[conn.clone()],
+			&controller_port_id("owner").unwrap(),
+			&Counterparty::new(host_port_id(), None),
+			&Version::from(Metadata::new(&conn, &conn)),
+		)
+		.is_err());
+	}
+}
@@ -0,0 +1,123 @@
+// Copyright 2022 ComposableFi
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use ibc_proto::{
+	cosmos::base::abci::v1beta1::TxMsgData,
+	google::protobuf::Any,
+	ibc::applications::interchain_accounts::v1::{CosmosTx, Type},
+};
+use prost::Message;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use super::error::Error;
+use crate::prelude::*;
+
+/// Packet data sent by controllers, encoded the way cosmos chains encode it: the packet type as
+/// its protobuf enum name and the transaction as base64.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct InterchainAccountPacketData {
+	#[serde(
+		rename = "type",
+		serialize_with = "serialize_type",
+		deserialize_with = "deserialize_type"
+	)]
+	pub packet_type: Type,
+	#[serde(serialize_with = "serialize_base64", deserialize_with = "deserialize_base64")]
+	pub data: Vec<u8>,
+	#[serde(default)]
+	pub memo: String,
+}
+
+impl InterchainAccountPacketData {
+	/// Packet data executing `messages` on the host chain
+	pub fn execute_tx(messages: Vec<Any>, memo: String) -> Self {
+		Self { packet_type: Type::ExecuteTx, data: CosmosTx { messages }.encode_to_vec(), memo }
+	}
+
+	/// Decode packet data from raw packet bytes
+	pub fn from_bytes(bytes: &[u8]) -> Result<Self, Error> {
+		serde_json::from_slice(bytes).map_err(|e| Error::packet_data_deserialization(e.to_string()))
+	}
+
+	/// Encode the packet data into raw packet bytes
+	pub fn to_bytes(&self) -> Vec<u8> {
+		serde_json::to_vec(self).expect("Packet data serialization is infallible; qed")
+	}
+
+	/// Returns the messages of an `ExecuteTx` packet
+	pub fn messages(&self) -> Result<Vec<Any>, Error> {
+		if self.packet_type != Type::ExecuteTx {
+			return Err(Error::unsupported_packet_type(self.packet_type.as_str_name().to_string()))
+		}
+		let tx =
+			CosmosTx::decode(self.data.as_slice()).map_err(Error::cosmos_tx_deserialization)?;
+		if tx.messages.is_empty() {
+			return Err(Error::empty_tx())
+		}
+		Ok(tx.messages)
+	}
+}
+
+/// Result of a successfully executed transaction, written base64 encoded in the acknowledgement
+pub fn tx_msg_data(msg_responses: Vec<Any>) -> Vec<u8> {
+	#[allow(deprecated)]
+	TxMsgData { data: vec![], msg_responses }.encode_to_vec()
+}
+
+fn serialize_type<S: Serializer>(packet_type: &Type, serializer: S) -> Result<S::Ok, S::Error> {
+	serializer.serialize_str(packet_type.as_str_name())
+}
+
+fn deserialize_type<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Type, D::Error> {
+	let name = String::deserialize(deserializer)?;
+	Type::from_str_name(&name)
+		.ok_or_else(|| serde::de::Error::custom(format!("unknown packet type {name}")))
+}
+
+fn serialize_base64<S: Serializer>(data: &[u8], serializer: S) -> Result<S::Ok, S::Error> {
+	serializer.serialize_str(&base64::encode(data))
+}
+
+fn deserialize_base64<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vec<u8>, D::Error> {
+	let data = String::deserialize(deserializer)?;
+	base64::decode(data).map_err(serde::de::Error::custom)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn packet_data_roundtrip_with_cosmos_encoding() {
+		// Packet data as produced by an ibc-go controller
+		let messages =
+			vec![Any { type_url: "/cosmos.bank.v1beta1.MsgSend".into(), value: vec![1] }];
+		let data = base64::encode(CosmosTx { messages: messages.clone() }.encode_to_vec());
+		let raw = format!(r#"{{"type":"TYPE_EXECUTE_TX","data":"{data}","memo":"hello"}}"#);
+
+		let packet_data = InterchainAccountPacketData::from_bytes(raw.as_bytes()).unwrap();
+		assert_eq!(packet_data.memo, "hello");
+		assert_eq!(packet_data.messages().unwrap(), messages);
+		assert_eq!(
+			InterchainAccountPacketData::from_bytes(&packet_data.to_bytes()).unwrap(),
+			packet_data
+		);
+	}
+
+	#[test]
+	fn packet_data_rejects_empty_tx() {
+		let packet_data = InterchainAccountPacketData::execute_tx(vec![], String::new());
+		assert!(packet_data.messages().is_err());
+	}
+}
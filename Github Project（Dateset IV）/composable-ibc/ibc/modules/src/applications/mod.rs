@@ -14,4 +14,5 @@
 
 //! Various packet encoding semantics which underpin the various types of transactions.
 
+pub mod interchain_accounts;
 pub mod transfer;
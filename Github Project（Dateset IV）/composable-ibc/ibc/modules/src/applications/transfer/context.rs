@@ -19,7 +19,7 @@ use crate::{
 	applications::transfer::{
 		acknowledgement::Acknowledgement,
 		events::{AckEvent, AckStatusEvent, RecvEvent, TimeoutEvent},
-		packet::PacketData,
+		packet::PacketDataV2,
 		relay::{
 			on_ack_packet::process_ack_packet, on_recv_packet::process_recv_packet,
 			on_timeout_packet::process_timeout_packet,
@@ -27,6 +27,8 @@ use crate::{
 		PrefixedCoin, PrefixedDenom, VERSION,
 	},
 	core::{
+		ics02_client::{client_state::ClientState, context::ClientReader},
+		ics03_connection::context::ConnectionReader,
 		ics04_channel::{
 			channel::{Counterparty, Order},
 			context::{ChannelKeeper, ChannelReader},
@@ -157,21 +159,82 @@ fn validate_transfer_channel_params(
 		return Err(Ics20Error::invalid_port(port_id.clone(), bound_port))
 	}
 
-	if version != &Version::ics20() {
+	if !is_supported_version(version) {
 		return Err(Ics20Error::invalid_version(version.clone()))
 	}
 
 	Ok(())
 }
 
+/// Transfer channels escrow the tokens sent to the counterparty chain and trace the denoms received
+/// from it, so an upgrade may only move a channel to a connection with the same counterparty chain.
+fn validate_upgrade_connection_hops(
+	ctx: &impl Ics20Context,
+	port_id: &PortId,
+	channel_id: &ChannelId,
+	proposed_connection_hops: &[ConnectionId],
+) -> Result<(), Ics20Error> {
+	if proposed_connection_hops.len() != 1 {
+		return Err(Ics20Error::invalid_upgrade_connection_hops(format!(
+			"expected a single hop, got {}",
+			proposed_connection_hops.len()
+		)))
+	}
+
+	let channel_end = ctx
+		.channel_end(&(port_id.clone(), *channel_id))
+		.map_err(Ics20Error::ics04_channel)?;
+	let counterparty_chain = |connection_id: &ConnectionId| {
+		let connection_end = ConnectionReader::connection_end(ctx, connection_id)
+			.map_err(|e| Ics20Error::invalid_upgrade_connection_hops(e.to_string()))?;
+		ClientReader::client_state(ctx, connection_end.client_id())
+			.map(|client_state| client_state.chain_id())
+			.map_err(|e| Ics20Error::invalid_upgrade_connection_hops(e.to_string()))
+	};
+
+	let current_chain = match channel_end.connection_hops().first() {
+		Some(connection_id) => counterparty_chain(connection_id)?,
+		None =>
+			return Err(Ics20Error::invalid_upgrade_connection_hops(
+				"the channel has no connection hops".to_string(),
+			)),
+	};
+	let proposed_chain = counterparty_chain(&proposed_connection_hops[0])?;
+	if current_chain != proposed_chain {
+		return Err(Ics20Error::invalid_upgrade_connection_hops(format!(
+			"the counterparty chain changes from {current_chain} to {proposed_chain}"
+		)))
+	}
+
+	Ok(())
+}
+
+fn is_supported_version(version: &Version) -> bool {
+	version == &Version::ics20() || version == &Version::ics20_v2()
+}
+
 fn validate_counterparty_version(counterparty_version: &Version) -> Result<(), Ics20Error> {
-	if counterparty_version == &Version::ics20() {
+	if is_supported_version(counterparty_version) {
 		Ok(())
 	} else {
 		Err(Ics20Error::invalid_counterparty_version(counterparty_version.clone()))
 	}
 }
 
+/// Decodes the data of a packet sent or received on the given channel according to the version
+/// negotiated for it.
+pub fn decode_packet_data(
+	ctx: &impl ChannelReader,
+	port_id: &PortId,
+	channel_id: &ChannelId,
+	data: &[u8],
+) -> Result<PacketDataV2, Ics20Error> {
+	let channel_end = ctx
+		.channel_end(&(port_id.clone(), *channel_id))
+		.map_err(Ics20Error::ics04_channel)?;
+	PacketDataV2::decode(channel_end.version(), data)
+}
+
 #[allow(clippy::too_many_arguments)]
 pub fn on_chan_open_init(
 	ctx: &mut impl Ics20Context,
@@ -200,7 +263,8 @@ pub fn on_chan_open_try(
 ) -> Result<Version, Ics20Error> {
 	validate_transfer_channel_params(ctx, order, port_id, channel_id, version)?;
 	validate_counterparty_version(counterparty_version)?;
-	Ok(Version::ics20())
+	// Both versions are supported so the version proposed by the counterparty is accepted
+	Ok(counterparty_version.clone())
 }
 
 pub fn on_chan_open_ack(
@@ -241,15 +305,72 @@ pub fn on_chan_close_confirm(
 	Ok(())
 }
 
+/// Transfer channels can be upgraded, typically to move between the supported ICS-20 versions, as
+/// long as the upgraded channel still satisfies the transfer channel parameters.
+#[allow(clippy::too_many_arguments)]
+pub fn on_chan_upgrade_init(
+	ctx: &mut impl Ics20Context,
+	_output: &mut ModuleOutputBuilder,
+	port_id: &PortId,
+	channel_id: &ChannelId,
+	proposed_order: Order,
+	proposed_connection_hops: &[ConnectionId],
+	proposed_version: &Version,
+) -> Result<Version, Ics20Error> {
+	validate_transfer_channel_params(ctx, proposed_order, port_id, channel_id, proposed_version)?;
+	validate_upgrade_connection_hops(&*ctx, port_id, channel_id, proposed_connection_hops)?;
+	Ok(proposed_version.clone())
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn on_chan_upgrade_try(
+	ctx: &mut impl Ics20Context,
+	_output: &mut ModuleOutputBuilder,
+	port_id: &PortId,
+	channel_id: &ChannelId,
+	proposed_order: Order,
+	proposed_connection_hops: &[ConnectionId],
+	counterparty_version: &Version,
+) -> Result<Version, Ics20Error> {
+	validate_counterparty_version(counterparty_version)?;
+	validate_transfer_channel_params(
+		ctx,
+		proposed_order,
+		port_id,
+		channel_id,
+		counterparty_version,
+	)?;
+	validate_upgrade_connection_hops(&*ctx, port_id, channel_id, proposed_connection_hops)?;
+	// The version proposed by the counterparty is supported so it is accepted
+	Ok(counterparty_version.clone())
+}
+
+pub fn on_chan_upgrade_ack(
+	_ctx: &mut impl Ics20Context,
+	_output: &mut ModuleOutputBuilder,
+	_port_id: &PortId,
+	_channel_id: &ChannelId,
+	counterparty_version: &Version,
+) -> Result<(), Ics20Error> {
+	validate_counterparty_version(counterparty_version)
+}
+
 pub fn on_recv_packet<Ctx: 'static + Ics20Context>(
 	ctx: &mut Ctx,
 	output: &mut ModuleOutputBuilder,
 	packet: &Packet,
 	_relayer: &Signer,
 ) -> Acknowledgement {
-	let data = match serde_json::from_slice::<PacketData>(&packet.data) {
+	let data = match decode_packet_data(
+		ctx,
+		&packet.destination_port,
+		&packet.destination_channel,
+		&packet.data,
+	)
+	.and_then(|data| data.validate().map(|_| data))
+	{
 		Ok(data) => data,
-		Err(_) => return Acknowledgement::from_error(Ics20Error::packet_data_deserialization()),
+		Err(e) => return Acknowledgement::from_error(e),
 	};
 
 	log::debug!(target: "ics::transfer", "dispatch {:?}",&data);
@@ -258,13 +379,15 @@ pub fn on_recv_packet<Ctx: 'static + Ics20Context>(
 		Err(e) => Acknowledgement::from_error(e),
 	};
 
-	let recv_event = RecvEvent {
-		receiver: data.receiver,
-		denom: data.token.denom,
-		amount: data.token.amount,
-		success: ack.is_successful(),
-	};
-	output.emit(recv_event.into());
+	for token in data.tokens {
+		let recv_event = RecvEvent {
+			receiver: data.receiver.clone(),
+			denom: token.denom,
+			amount: token.amount,
+			success: ack.is_successful(),
+		};
+		output.emit(recv_event.into());
+	}
 
 	ack
 }
@@ -276,21 +399,22 @@ pub fn on_acknowledgement_packet(
 	acknowledgement: &GenericAcknowledgement,
 	_relayer: &Signer,
 ) -> Result<(), Ics20Error> {
-	let data = serde_json::from_slice::<PacketData>(&packet.data)
-		.map_err(|_| Ics20Error::packet_data_deserialization())?;
+	let data = decode_packet_data(ctx, &packet.source_port, &packet.source_channel, &packet.data)?;
 
 	let acknowledgement = serde_json::from_slice::<Acknowledgement>(acknowledgement.as_ref())
 		.map_err(|_| Ics20Error::ack_deserialization())?;
 
 	process_ack_packet(ctx, packet, &data, &acknowledgement)?;
 
-	let ack_event = AckEvent {
-		receiver: data.receiver,
-		denom: data.token.denom,
-		amount: data.token.amount,
-		acknowledgement: acknowledgement.clone(),
-	};
-	output.emit(ack_event.into());
+	for token in data.tokens {
+		let ack_event = AckEvent {
+			receiver: data.receiver.clone(),
+			denom: token.denom,
+			amount: token.amount,
+			acknowledgement: acknowledgement.clone(),
+		};
+		output.emit(ack_event.into());
+	}
 	output.emit(AckStatusEvent { acknowledgement }.into());
 
 	Ok(())
@@ -302,17 +426,18 @@ pub fn on_timeout_packet(
 	packet: &Packet,
 	_relayer: &Signer,
 ) -> Result<(), Ics20Error> {
-	let data = serde_json::from_slice::<PacketData>(&packet.data)
-		.map_err(|_| Ics20Error::packet_data_deserialization())?;
+	let data = decode_packet_data(ctx, &packet.source_port, &packet.source_channel, &packet.data)?;
 
 	process_timeout_packet(ctx, packet, &data)?;
 
-	let timeout_event = TimeoutEvent {
-		refund_receiver: data.sender,
-		refund_denom: data.token.denom,
-		refund_amount: data.token.amount,
-	};
-	output.emit(timeout_event.into());
+	for token in data.tokens {
+		let timeout_event = TimeoutEvent {
+			refund_receiver: data.sender.clone(),
+			refund_denom: token.denom,
+			refund_amount: token.amount,
+		};
+		output.emit(timeout_event.into());
+	}
 
 	Ok(())
 }
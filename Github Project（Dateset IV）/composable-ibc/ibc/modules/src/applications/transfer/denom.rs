@@ -16,6 +16,7 @@ use core::{fmt, str::FromStr};
 
 use derive_more::{Display, From, Into};
 use ibc_proto::{
+	composable::ibc::applications::transfer::v1::{Denom as RawDenom, Hop},
 	cosmos::base::v1beta1::Coin as RawCoin,
 	ibc::applications::transfer::v1::DenomTrace as RawDenomTrace,
 };
@@ -284,6 +285,40 @@ impl From<PrefixedDenom> for RawDenomTrace {
 	}
 }
 
+/// The trace of a `Denom` lists the hops in the order of the path, the most recent one first.
+impl TryFrom<RawDenom> for PrefixedDenom {
+	type Error = Error;
+
+	fn try_from(value: RawDenom) -> Result<Self, Self::Error> {
+		let base_denom = BaseDenom::from_str(&value.base)?;
+		let mut trace = vec![];
+		for (pos, hop) in value.trace.into_iter().rev().enumerate() {
+			let port_id =
+				PortId::from_str(&hop.port_id).map_err(|e| Error::invalid_trace_port_id(pos, e))?;
+			let channel_id = ChannelId::from_str(&hop.channel_id)
+				.map_err(|e| Error::invalid_trace_channel_id(pos, e))?;
+			trace.push(TracePrefix { port_id, channel_id });
+		}
+		Ok(Self { trace_path: trace.into(), base_denom })
+	}
+}
+
+impl From<PrefixedDenom> for RawDenom {
+	fn from(value: PrefixedDenom) -> Self {
+		let trace = value
+			.trace_path
+			.0
+			.into_iter()
+			.rev()
+			.map(|prefix| Hop {
+				port_id: prefix.port_id.to_string(),
+				channel_id: prefix.channel_id.to_string(),
+			})
+			.collect();
+		Self { base: value.base_denom.to_string(), trace }
+	}
+}
+
 impl From<BaseDenom> for PrefixedDenom {
 	fn from(denom: BaseDenom) -> Self {
 		Self { trace_path: Default::default(), base_denom: denom }
@@ -119,11 +119,33 @@ define_error! {
 
 		InvalidVersion
 			{ version: Version }
-			| e | { format_args!("expected version '{0}', got '{1}'", Version::ics20(), e.version) },
+			| e | { format_args!("expected version '{0}' or '{1}', got '{2}'", Version::ics20(), Version::ics20_v2(), e.version) },
 
 		InvalidCounterpartyVersion
 			{ version: Version }
-			| e | { format_args!("expected counterparty version '{0}', got '{1}'", Version::ics20(), e.version) },
+			| e | { format_args!("expected counterparty version '{0}' or '{1}', got '{2}'", Version::ics20(), Version::ics20_v2(), e.version) },
+
+		EmptyTokens
+			| _ | { "packet data must contain at least one token" },
+
+		UnsupportedByVersion
+			{ version: Version, feature: String }
+			| e | { format_args!("{0} is not supported on channels with version '{1}'", e.feature, e.version) },
+
+		TooManyForwardingHops
+			{ hops: usize, max: usize }
+			| e | { format_args!("forwarding path has {0} hops, at most {1} are allowed", e.hops, e.max) },
+
+		InvalidUpgradeConnectionHops
+			{ reason: String }
+			| e | { format_args!("invalid connection hops for the channel upgrade: {0}", e.reason) },
+
+		InvalidForwarding
+			{ reason: String }
+			| e | { format_args!("invalid forwarding path: {0}", e.reason) },
+
+		ForwardingNotSupported
+			| _ | { "forwarding received tokens is not supported" },
 
 		CantCloseChannel
 			| _ | { "channel cannot be closed" },
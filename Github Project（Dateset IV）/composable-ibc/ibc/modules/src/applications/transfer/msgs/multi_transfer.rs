@@ -0,0 +1,75 @@
+// Copyright 2022 ComposableFi
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Message used to transfer several tokens in a single packet over `ics20-2` channels.
+
+use crate::prelude::*;
+
+use ibc_proto::cosmos::base::v1beta1::Coin;
+
+use super::transfer::MsgTransfer;
+use crate::{
+	applications::transfer::packet::Forwarding,
+	core::{
+		ics02_client::height::Height,
+		ics24_host::identifier::{ChannelId, PortId},
+	},
+	signer::Signer,
+	timestamp::Timestamp,
+};
+
+/// Message used to build an ICS20 token transfer packet carrying multiple tokens.
+///
+/// Channels negotiated with the `ics20-1` version only accept a single token without forwarding,
+/// in which case the packet is encoded exactly like the one built from a [`MsgTransfer`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct MsgMultiTransfer<C = Coin> {
+	/// the port on which the packet will be sent
+	pub source_port: PortId,
+	/// the channel by which the packet will be sent
+	pub source_channel: ChannelId,
+	/// the tokens to be transferred
+	pub tokens: Vec<C>,
+	/// the sender address
+	pub sender: Signer,
+	/// the recipient address on the destination chain
+	pub receiver: Signer,
+	/// Timeout height relative to the current block height.
+	/// The timeout is disabled when set to 0.
+	pub timeout_height: Height,
+	/// Timeout timestamp relative to the current block timestamp.
+	/// The timeout is disabled when set to 0.
+	pub timeout_timestamp: Timestamp,
+	/// Memo field
+	pub memo: String,
+	/// Path the tokens are forwarded on once received. Forwarding is not supported on receipt by
+	/// this implementation, the counterparty must support it.
+	pub forwarding: Forwarding,
+}
+
+impl<C> From<MsgTransfer<C>> for MsgMultiTransfer<C> {
+	fn from(msg: MsgTransfer<C>) -> Self {
+		Self {
+			source_port: msg.source_port,
+			source_channel: msg.source_channel,
+			tokens: vec![msg.token],
+			sender: msg.sender,
+			receiver: msg.receiver,
+			timeout_height: msg.timeout_height,
+			timeout_timestamp: msg.timeout_timestamp,
+			memo: msg.memo,
+			forwarding: Forwarding::default(),
+		}
+	}
+}
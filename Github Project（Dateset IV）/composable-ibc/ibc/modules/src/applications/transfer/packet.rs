@@ -12,14 +12,34 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use alloc::string::{String, ToString};
+use alloc::{
+	string::{String, ToString},
+	vec,
+	vec::Vec,
+};
 use core::{convert::TryFrom, str::FromStr};
 
-use ibc_proto::ibc::applications::transfer::v2::FungibleTokenPacketData as RawPacketData;
+use ibc_proto::{
+	composable::ibc::applications::transfer::{
+		v1::{Hop as RawHop, Token as RawToken},
+		v2::{ForwardingPacketData as RawForwarding, FungibleTokenPacketDataV2 as RawPacketDataV2},
+	},
+	ibc::applications::transfer::v2::FungibleTokenPacketData as RawPacketData,
+};
+use prost::Message;
 use serde::{Deserialize, Serialize};
 
-use super::{error::Error, Amount, PrefixedCoin, PrefixedDenom};
-use crate::signer::Signer;
+use super::{error::Error, Amount, PrefixedCoin, PrefixedDenom, VERSION_V2};
+use crate::{
+	core::{
+		ics04_channel::Version,
+		ics24_host::identifier::{ChannelId, PortId},
+	},
+	signer::Signer,
+};
+
+/// Maximum number of hops a transfer can be forwarded through.
+pub const MAXIMUM_NUMBER_OF_FORWARDING_HOPS: usize = 8;
 
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub struct PacketData {
@@ -58,3 +78,315 @@ impl From<PacketData> for RawPacketData {
 		}
 	}
 }
+
+/// Metadata of a middleware such as ICS-29 that wraps the version of the application
+#[derive(Deserialize)]
+struct MiddlewareMetadata {
+	app_version: String,
+}
+
+/// Returns true if `ics20-2` was negotiated for a channel with the given version. The version may
+/// be wrapped in the metadata of a middleware such as ICS-29, in which case the application
+/// version of the metadata is checked. Any other version is treated as `ics20-1`.
+pub fn is_ics20_v2(version: &Version) -> bool {
+	let version = version.to_string();
+	if version == VERSION_V2 {
+		return true
+	}
+	serde_json::from_str::<MiddlewareMetadata>(&version)
+		.map_or(false, |metadata| is_ics20_v2(&Version::new(metadata.app_version)))
+}
+
+/// A channel end a transfer is forwarded through.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Hop {
+	pub port_id: PortId,
+	pub channel_id: ChannelId,
+}
+
+/// The path a transfer is forwarded on once received, along with the memo to deliver to the final
+/// destination.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Forwarding {
+	#[serde(default)]
+	pub hops: Vec<Hop>,
+	#[serde(default)]
+	pub destination_memo: String,
+}
+
+impl Forwarding {
+	pub fn is_empty(&self) -> bool {
+		self.hops.is_empty() && self.destination_memo.is_empty()
+	}
+
+	pub fn validate(&self) -> Result<(), Error> {
+		if self.hops.len() > MAXIMUM_NUMBER_OF_FORWARDING_HOPS {
+			return Err(Error::too_many_forwarding_hops(
+				self.hops.len(),
+				MAXIMUM_NUMBER_OF_FORWARDING_HOPS,
+			))
+		}
+		if self.hops.is_empty() && !self.destination_memo.is_empty() {
+			return Err(Error::invalid_forwarding(
+				"destination memo cannot be set without hops".to_string(),
+			))
+		}
+		Ok(())
+	}
+}
+
+/// Packet data of transfers over `ics20-2` channels, which may carry several tokens and a
+/// forwarding path. It is encoded as the `FungibleTokenPacketDataV2` protobuf message on the wire,
+/// like ibc-go does.
+///
+/// Packets sent over `ics20-1` channels are converted into this representation with a single
+/// token so that the transfer logic only deals with one packet data type.
+///
+/// Forwarding the received tokens along `forwarding` is out of scope, such packets are rejected
+/// on receipt with [`Error::forwarding_not_supported`]. Tokens can still be forwarded with a memo
+/// by the `pallet-ibc` memo middleware.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct PacketDataV2 {
+	pub tokens: Vec<PrefixedCoin>,
+	pub sender: Signer,
+	pub receiver: Signer,
+	#[serde(default)]
+	pub memo: String,
+	#[serde(default)]
+	pub forwarding: Forwarding,
+}
+
+impl PacketDataV2 {
+	/// Decodes the packet data of a transfer sent over a channel with the given version.
+	pub fn decode(version: &Version, data: &[u8]) -> Result<Self, Error> {
+		if is_ics20_v2(version) {
+			RawPacketDataV2::decode(data)
+				.map_err(|_| Error::packet_data_deserialization())?
+				.try_into()
+		} else {
+			serde_json::from_slice::<PacketData>(data)
+				.map(Into::into)
+				.map_err(|_| Error::packet_data_deserialization())
+		}
+	}
+
+	/// Encodes the packet data for a channel with the given version, `ics20-1` channels only
+	/// accept a single token without forwarding.
+	pub fn encode(self, version: &Version) -> Result<Vec<u8>, Error> {
+		if is_ics20_v2(version) {
+			Ok(RawPacketDataV2::from(self).encode_to_vec())
+		} else {
+			let data = PacketData::try_from(self)?;
+			Ok(serde_json::to_vec(&data).expect("PacketData's infallible Serialize impl failed"))
+		}
+	}
+
+	pub fn validate(&self) -> Result<(), Error> {
+		if self.tokens.is_empty() {
+			return Err(Error::empty_tokens())
+		}
+		self.forwarding.validate()?;
+		if !self.forwarding.hops.is_empty() && !self.memo.is_empty() {
+			return Err(Error::invalid_forwarding(
+				"memo must be empty when forwarding, use the destination memo instead".to_string(),
+			))
+		}
+		Ok(())
+	}
+}
+
+impl TryFrom<RawPacketDataV2> for PacketDataV2 {
+	type Error = Error;
+
+	fn try_from(raw_pkt_data: RawPacketDataV2) -> Result<Self, Self::Error> {
+		let tokens = raw_pkt_data
+			.tokens
+			.into_iter()
+			.map(|token| {
+				let denom = token.denom.ok_or_else(Error::packet_data_deserialization)?;
+				Ok(PrefixedCoin {
+					denom: PrefixedDenom::try_from(denom)?,
+					amount: Amount::from_str(&token.amount)?,
+				})
+			})
+			.collect::<Result<_, Error>>()?;
+		let forwarding = match raw_pkt_data.forwarding {
+			Some(forwarding) => Forwarding {
+				hops: forwarding
+					.hops
+					.into_iter()
+					.map(|hop| {
+						Ok(Hop {
+							port_id: hop
+								.port_id
+								.parse()
+								.map_err(|e| Error::invalid_port_id(hop.port_id.clone(), e))?,
+							channel_id: hop.channel_id.parse().map_err(|e| {
+								Error::invalid_channel_id(hop.channel_id.clone(), e)
+							})?,
+						})
+					})
+					.collect::<Result<_, Error>>()?,
+				destination_memo: forwarding.destination_memo,
+			},
+			None => Forwarding::default(),
+		};
+		Ok(Self {
+			tokens,
+			sender: raw_pkt_data.sender.parse().map_err(Error::signer)?,
+			receiver: raw_pkt_data.receiver.parse().map_err(Error::signer)?,
+			memo: raw_pkt_data.memo,
+			forwarding,
+		})
+	}
+}
+
+impl From<PacketDataV2> for RawPacketDataV2 {
+	fn from(pkt_data: PacketDataV2) -> Self {
+		let forwarding = (!pkt_data.forwarding.is_empty()).then(|| RawForwarding {
+			destination_memo: pkt_data.forwarding.destination_memo,
+			hops: pkt_data
+				.forwarding
+				.hops
+				.into_iter()
+				.map(|hop| RawHop {
+					port_id: hop.port_id.to_string(),
+					channel_id: hop.channel_id.to_string(),
+				})
+				.collect(),
+		});
+		Self {
+			tokens: pkt_data
+				.tokens
+				.into_iter()
+				.map(|token| RawToken {
+					denom: Some(token.denom.into()),
+					amount: token.amount.to_string(),
+				})
+				.collect(),
+			sender: pkt_data.sender.to_string(),
+			receiver: pkt_data.receiver.to_string(),
+			memo: pkt_data.memo,
+			forwarding,
+		}
+	}
+}
+
+impl From<PacketData> for PacketDataV2 {
+	fn from(data: PacketData) -> Self {
+		Self {
+			tokens: vec![data.token],
+			sender: data.sender,
+			receiver: data.receiver,
+			memo: data.memo,
+			forwarding: Forwarding::default(),
+		}
+	}
+}
+
+impl TryFrom<PacketDataV2> for PacketData {
+	type Error = Error;
+
+	fn try_from(mut data: PacketDataV2) -> Result<Self, Self::Error> {
+		let version = Version::ics20();
+		if data.tokens.len() != 1 {
+			return Err(Error::unsupported_by_version(version, "multiple tokens".to_string()))
+		}
+		if !data.forwarding.is_empty() {
+			return Err(Error::unsupported_by_version(version, "forwarding".to_string()))
+		}
+		Ok(Self {
+			token: data.tokens.remove(0),
+			sender: data.sender,
+			receiver: data.receiver,
+			memo: data.memo,
+		})
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn packet_data(tokens: &[&str]) -> PacketDataV2 {
+		PacketDataV2 {
+			tokens: tokens
+				.iter()
+				.map(|denom| PrefixedCoin {
+					denom: PrefixedDenom::from_str(denom).unwrap(),
+					amount: Amount::from_str("100").unwrap(),
+				})
+				.collect(),
+			sender: Signer::from_str("sender").unwrap(),
+			receiver: Signer::from_str("receiver").unwrap(),
+			memo: "".to_string(),
+			forwarding: Forwarding::default(),
+		}
+	}
+
+	#[test]
+	fn packet_data_is_encoded_according_to_channel_version() {
+		let single = packet_data(&["transfer/channel-0/UNIT"]);
+		let encoded = single.clone().encode(&Version::ics20()).unwrap();
+		assert_eq!(
+			core::str::from_utf8(&encoded).unwrap(),
+			r#"{"denom":"transfer/channel-0/UNIT","amount":"100","sender":"sender","receiver":"receiver","memo":""}"#
+		);
+		assert_eq!(PacketDataV2::decode(&Version::ics20(), &encoded).unwrap(), single);
+
+		let multi = packet_data(&["transfer/channel-0/UNIT", "PICA"]);
+		assert!(multi.clone().encode(&Version::ics20()).is_err());
+		let encoded = multi.clone().encode(&Version::ics20_v2()).unwrap();
+		assert_eq!(PacketDataV2::decode(&Version::ics20_v2(), &encoded).unwrap(), multi);
+
+		let fee_version =
+			Version::new(r#"{"fee_version":"ics29-1","app_version":"ics20-2"}"#.to_string());
+		assert!(is_ics20_v2(&fee_version));
+		assert_eq!(PacketDataV2::decode(&fee_version, &encoded).unwrap(), multi);
+		assert!(!is_ics20_v2(&Version::new(
+			r#"{"fee_version":"ics20-2","app_version":"ics20-1"}"#.to_string()
+		)));
+		assert!(!is_ics20_v2(&Version::new("ics20-20".to_string())));
+	}
+
+	#[test]
+	fn v2_packet_data_is_encoded_as_protobuf() {
+		let mut data = packet_data(&["transfer/channel-0/transfer/channel-1/UNIT"]);
+		data.forwarding.hops =
+			vec![Hop { port_id: PortId::transfer(), channel_id: ChannelId::new(2) }];
+		let encoded = data.clone().encode(&Version::ics20_v2()).unwrap();
+
+		let raw = RawPacketDataV2::decode(encoded.as_slice()).unwrap();
+		let denom = raw.tokens[0].denom.clone().unwrap();
+		assert_eq!(denom.base, "UNIT");
+		assert_eq!(
+			denom.trace,
+			vec![
+				RawHop { port_id: "transfer".to_string(), channel_id: "channel-0".to_string() },
+				RawHop { port_id: "transfer".to_string(), channel_id: "channel-1".to_string() },
+			]
+		);
+		assert_eq!(raw.forwarding.unwrap().hops[0].channel_id, "channel-2");
+		assert_eq!(PacketDataV2::decode(&Version::ics20_v2(), &encoded).unwrap(), data);
+	}
+
+	#[test]
+	fn forwarding_is_validated() {
+		let mut data = packet_data(&["PICA"]);
+		data.forwarding.destination_memo = "memo".to_string();
+		assert!(data.validate().is_err());
+
+		let hop = Hop { port_id: PortId::transfer(), channel_id: ChannelId::new(1) };
+		data.forwarding.hops = vec![hop.clone()];
+		assert!(data.validate().is_ok());
+
+		data.memo = "memo".to_string();
+		assert!(data.validate().is_err());
+
+		data.memo = "".to_string();
+		data.forwarding.hops = vec![hop; MAXIMUM_NUMBER_OF_FORWARDING_HOPS + 1];
+		assert!(data.validate().is_err());
+
+		assert!(packet_data(&[]).validate().is_err());
+	}
+}
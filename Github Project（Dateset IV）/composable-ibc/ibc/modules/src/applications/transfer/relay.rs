@@ -16,7 +16,7 @@
 use crate::{
 	applications::transfer::{
 		context::Ics20Context, error::Error as Ics20Error, is_sender_chain_source,
-		packet::PacketData,
+		packet::PacketDataV2,
 	},
 	core::ics04_channel::packet::Packet,
 	prelude::*,
@@ -30,7 +30,7 @@ pub mod send_transfer;
 fn refund_packet_token(
 	ctx: &mut impl Ics20Context,
 	packet: &Packet,
-	data: &PacketData,
+	data: &PacketDataV2,
 ) -> Result<(), Ics20Error> {
 	let sender = data
 		.sender
@@ -38,16 +38,18 @@ fn refund_packet_token(
 		.try_into()
 		.map_err(|_| Ics20Error::parse_account_failure())?;
 
-	if is_sender_chain_source(packet.source_port.clone(), packet.source_channel, &data.token.denom)
-	{
-		// unescrow tokens back to sender
-		let escrow_address =
-			ctx.get_channel_escrow_address(&packet.source_port, packet.source_channel)?;
+	for token in &data.tokens {
+		if is_sender_chain_source(packet.source_port.clone(), packet.source_channel, &token.denom) {
+			// unescrow tokens back to sender
+			let escrow_address =
+				ctx.get_channel_escrow_address(&packet.source_port, packet.source_channel)?;
 
-		ctx.send_coins(&escrow_address, &sender, &data.token)
-	}
-	// mint vouchers back to sender
-	else {
-		ctx.mint_coins(&sender, &data.token)
+			ctx.send_coins(&escrow_address, &sender, token)?;
+		}
+		// mint vouchers back to sender
+		else {
+			ctx.mint_coins(&sender, token)?;
+		}
 	}
+	Ok(())
 }
@@ -15,60 +15,69 @@
 use crate::{
 	applications::transfer::{
 		context::Ics20Context, error::Error as Ics20Error, events::DenomTraceEvent,
-		is_receiver_chain_source, packet::PacketData, TracePrefix,
+		is_receiver_chain_source, packet::PacketDataV2, TracePrefix,
 	},
 	core::{ics04_channel::packet::Packet, ics26_routing::context::ModuleOutputBuilder},
 	prelude::*,
 };
 
+/// Credits every token of a received packet to its receiver.
+/// If this method returns an error, the runtime is expected to rollback all state modifications to
+/// the `Ctx` caused by tokens credited before the failing one.
 pub fn process_recv_packet<Ctx: 'static + Ics20Context>(
 	ctx: &mut Ctx,
 	output: &mut ModuleOutputBuilder,
 	packet: &Packet,
-	data: PacketData,
+	data: PacketDataV2,
 ) -> Result<(), Ics20Error> {
 	if !ctx.is_receive_enabled() {
 		return Err(Ics20Error::receive_disabled())
 	}
 
+	// Multi-hop forwarding is out of scope, the sender is refunded by the error acknowledgement
+	if !data.forwarding.hops.is_empty() {
+		return Err(Ics20Error::forwarding_not_supported())
+	}
+
 	let receiver_account = data
 		.receiver
 		.clone()
 		.try_into()
 		.map_err(|_| Ics20Error::parse_account_failure())?;
 
-	if is_receiver_chain_source(
-		packet.source_port.clone(),
-		packet.source_channel,
-		&data.token.denom,
-	) {
-		// sender chain is not the source, unescrow tokens
-		let prefix = TracePrefix::new(packet.source_port.clone(), packet.source_channel);
-		let coin = {
-			let mut c = data.token;
-			c.denom.remove_trace_prefix(&prefix);
-			c
-		};
+	for token in data.tokens {
+		if is_receiver_chain_source(packet.source_port.clone(), packet.source_channel, &token.denom)
+		{
+			// sender chain is not the source, unescrow tokens
+			let prefix = TracePrefix::new(packet.source_port.clone(), packet.source_channel);
+			let coin = {
+				let mut c = token;
+				c.denom.remove_trace_prefix(&prefix);
+				c
+			};
 
-		let escrow_address =
-			ctx.get_channel_escrow_address(&packet.destination_port, packet.destination_channel)?;
+			let escrow_address = ctx
+				.get_channel_escrow_address(&packet.destination_port, packet.destination_channel)?;
 
-		ctx.send_coins(&escrow_address, &receiver_account, &coin)
-	} else {
-		// sender chain is the source, mint vouchers
-		let prefix = TracePrefix::new(packet.destination_port.clone(), packet.destination_channel);
-		let coin = {
-			let mut c = data.token;
-			c.denom.add_trace_prefix(prefix);
-			c
-		};
+			ctx.send_coins(&escrow_address, &receiver_account, &coin)?;
+		} else {
+			// sender chain is the source, mint vouchers
+			let prefix =
+				TracePrefix::new(packet.destination_port.clone(), packet.destination_channel);
+			let coin = {
+				let mut c = token;
+				c.denom.add_trace_prefix(prefix);
+				c
+			};
 
-		let denom_trace_event = DenomTraceEvent {
-			trace_hash: ctx.denom_hash_string(&coin.denom),
-			denom: coin.denom.clone(),
-		};
-		output.emit(denom_trace_event.into());
+			let denom_trace_event = DenomTraceEvent {
+				trace_hash: ctx.denom_hash_string(&coin.denom),
+				denom: coin.denom.clone(),
+			};
+			output.emit(denom_trace_event.into());
 
-		ctx.mint_coins(&receiver_account, &coin)
+			ctx.mint_coins(&receiver_account, &coin)?;
+		}
 	}
+	Ok(())
 }
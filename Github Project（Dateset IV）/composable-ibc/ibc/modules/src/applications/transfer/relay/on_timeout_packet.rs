@@ -14,7 +14,7 @@
 
 use crate::{
 	applications::transfer::{
-		context::Ics20Context, error::Error as Ics20Error, packet::PacketData,
+		context::Ics20Context, error::Error as Ics20Error, packet::PacketDataV2,
 		relay::refund_packet_token,
 	},
 	core::ics04_channel::packet::Packet,
@@ -23,7 +23,7 @@ use crate::{
 pub fn process_timeout_packet(
 	ctx: &mut impl Ics20Context,
 	packet: &Packet,
-	data: &PacketData,
+	data: &PacketDataV2,
 ) -> Result<(), Ics20Error> {
 	refund_packet_token(ctx, packet, data)
 }
@@ -14,8 +14,13 @@
 
 use crate::{
 	applications::transfer::{
-		context::Ics20Context, error::Error, events::TransferEvent, is_sender_chain_source,
-		msgs::transfer::MsgTransfer, packet::PacketData, Coin, PrefixedCoin,
+		context::Ics20Context,
+		error::Error,
+		events::TransferEvent,
+		is_sender_chain_source,
+		msgs::{multi_transfer::MsgMultiTransfer, transfer::MsgTransfer},
+		packet::PacketDataV2,
+		PrefixedCoin,
 	},
 	core::ics04_channel::{handler::send_packet::send_packet, packet::Packet},
 	handler::{HandlerOutput, HandlerOutputBuilder},
@@ -30,6 +35,22 @@ pub fn send_transfer<Ctx, C>(
 	output: &mut HandlerOutputBuilder<()>,
 	msg: MsgTransfer<C>,
 ) -> Result<(), Error>
+where
+	Ctx: Ics20Context,
+	C: TryInto<PrefixedCoin>,
+{
+	send_multi_transfer(ctx, output, msg.into())
+}
+
+/// Sends all the tokens of `msg` in a single packet, encoded according to the version of the
+/// source channel.
+/// If this method returns an error, the runtime is expected to rollback all state modifications to
+/// the `Ctx` caused by all messages from the transaction that this `msg` is a part of.
+pub fn send_multi_transfer<Ctx, C>(
+	ctx: &mut Ctx,
+	output: &mut HandlerOutputBuilder<()>,
+	msg: MsgMultiTransfer<C>,
+) -> Result<(), Error>
 where
 	Ctx: Ics20Context,
 	C: TryInto<PrefixedCoin>,
@@ -52,30 +73,35 @@ where
 		.get_next_sequence_send(&(msg.source_port.clone(), msg.source_channel))
 		.map_err(Error::ics04_channel)?;
 
-	let token = msg.token.try_into().map_err(|_| Error::invalid_token())?;
-	let denom = token.denom.clone();
-	let coin = Coin { denom: denom.clone(), amount: token.amount };
+	let tokens = msg
+		.tokens
+		.into_iter()
+		.map(|token| token.try_into().map_err(|_| Error::invalid_token()))
+		.collect::<Result<Vec<PrefixedCoin>, _>>()?;
+
+	let data = PacketDataV2 {
+		tokens,
+		sender: msg.sender.clone(),
+		receiver: msg.receiver.clone(),
+		memo: msg.memo,
+		forwarding: msg.forwarding,
+	};
+	data.validate()?;
+	let tokens = data.tokens.clone();
+	let data = data.encode(source_channel_end.version())?;
 
 	let sender = msg.sender.clone().try_into().map_err(|_| Error::parse_account_failure())?;
 
-	if is_sender_chain_source(msg.source_port.clone(), msg.source_channel, &denom) {
-		let escrow_address =
-			ctx.get_channel_escrow_address(&msg.source_port, msg.source_channel)?;
-		ctx.send_coins(&sender, &escrow_address, &coin)?;
-	} else {
-		ctx.burn_coins(&sender, &coin)?;
+	for coin in &tokens {
+		if is_sender_chain_source(msg.source_port.clone(), msg.source_channel, &coin.denom) {
+			let escrow_address =
+				ctx.get_channel_escrow_address(&msg.source_port, msg.source_channel)?;
+			ctx.send_coins(&sender, &escrow_address, coin)?;
+		} else {
+			ctx.burn_coins(&sender, coin)?;
+		}
 	}
 
-	let data = {
-		let data = PacketData {
-			token: coin,
-			sender: msg.sender.clone(),
-			receiver: msg.receiver.clone(),
-			memo: msg.memo,
-		};
-		serde_json::to_vec(&data).expect("PacketData's infallible Serialize impl failed")
-	};
-
 	let packet = Packet {
 		sequence,
 		source_port: msg.source_port,
@@ -96,7 +122,9 @@ where
 
 	output.log(format!(
 		"IBC fungible token transfer: {} --({})--> {}",
-		msg.sender, token, msg.receiver
+		msg.sender,
+		tokens.iter().map(ToString::to_string).collect::<Vec<_>>().join(","),
+		msg.receiver
 	));
 
 	let transfer_event = TransferEvent { sender: msg.sender, receiver: msg.receiver };
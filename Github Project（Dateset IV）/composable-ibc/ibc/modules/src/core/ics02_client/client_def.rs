@@ -16,15 +16,24 @@ use crate::core::ics02_client::{client_consensus::ConsensusState, client_state::
 
 use crate::{
 	core::{
-		ics02_client::{client_message::ClientMessage, context::ClientTypes, error::Error},
+		ics02_client::{
+			client_message::ClientMessage,
+			context::{ClientReader, ClientTypes},
+			error::Error,
+			handler::update_client::Result as UpdateResult,
+		},
 		ics03_connection::connection::ConnectionEnd,
 		ics04_channel::{
 			channel::ChannelEnd,
 			commitment::{AcknowledgementCommitment, PacketCommitment},
-			packet::Sequence,
+			packet::{Receipt, Sequence},
+			upgrade::{ErrorReceipt, Upgrade},
 		},
 		ics23_commitment::commitment::{CommitmentPrefix, CommitmentProofBytes, CommitmentRoot},
-		ics24_host::identifier::{ChannelId, ClientId, ConnectionId, PortId},
+		ics24_host::{
+			identifier::{ChannelId, ClientId, ConnectionId, PortId},
+			Path,
+		},
 		ics26_routing::context::ReaderContext,
 	},
 	prelude::*,
@@ -161,6 +170,39 @@ pub trait ClientDef: Clone {
 		expected_channel_end: &ChannelEnd,
 	) -> Result<(), Error>;
 
+	/// Verify a `proof` that the upgrade proposed by a channel end matches `expected_upgrade`.
+	#[allow(clippy::too_many_arguments)]
+	fn verify_channel_upgrade<Ctx: ReaderContext>(
+		&self,
+		ctx: &Ctx,
+		client_id: &ClientId,
+		client_state: &Self::ClientState,
+		height: Height,
+		prefix: &CommitmentPrefix,
+		proof: &CommitmentProofBytes,
+		root: &CommitmentRoot,
+		port_id: &PortId,
+		channel_id: &ChannelId,
+		expected_upgrade: &Upgrade,
+	) -> Result<(), Error>;
+
+	/// Verify a `proof` that the error receipt of an aborted channel upgrade matches
+	/// `expected_error_receipt`.
+	#[allow(clippy::too_many_arguments)]
+	fn verify_channel_upgrade_error<Ctx: ReaderContext>(
+		&self,
+		ctx: &Ctx,
+		client_id: &ClientId,
+		client_state: &Self::ClientState,
+		height: Height,
+		prefix: &CommitmentPrefix,
+		proof: &CommitmentProofBytes,
+		root: &CommitmentRoot,
+		port_id: &PortId,
+		channel_id: &ChannelId,
+		expected_error_receipt: &ErrorReceipt,
+	) -> Result<(), Error>;
+
 	/// Verify the client state for this chain that it is stored on the counterparty chain.
 	#[allow(clippy::too_many_arguments)]
 	fn verify_client_full_state<Ctx: ReaderContext>(
@@ -225,6 +267,38 @@ pub trait ClientDef: Clone {
 		sequence: Sequence,
 	) -> Result<(), Error>;
 
+	/// Verify a `proof` that the given `receipt` was written for a packet.
+	#[allow(clippy::too_many_arguments)]
+	fn verify_packet_receipt<Ctx: ReaderContext>(
+		&self,
+		ctx: &Ctx,
+		client_id: &ClientId,
+		client_state: &Self::ClientState,
+		height: Height,
+		connection_end: &ConnectionEnd,
+		proof: &CommitmentProofBytes,
+		root: &CommitmentRoot,
+		port_id: &PortId,
+		channel_id: &ChannelId,
+		sequence: Sequence,
+		receipt: Receipt,
+	) -> Result<(), Error>;
+
+	/// Verify a single multi-key `proof` that every `(path, value)` pair in `items` is stored on
+	/// the counterparty chain. Proof nodes shared between the paths are only checked once.
+	#[allow(clippy::too_many_arguments)]
+	fn verify_batch_membership<Ctx: ReaderContext>(
+		&self,
+		ctx: &Ctx,
+		client_id: &ClientId,
+		client_state: &Self::ClientState,
+		height: Height,
+		connection_end: &ConnectionEnd,
+		proof: &CommitmentProofBytes,
+		root: &CommitmentRoot,
+		items: Vec<(Path, Vec<u8>)>,
+	) -> Result<(), Error>;
+
 	/// Verify a `proof` that a packet has not been received.
 	#[allow(clippy::too_many_arguments)]
 	fn verify_packet_receipt_absence<Ctx: ReaderContext>(
@@ -240,4 +314,56 @@ pub trait ClientDef: Clone {
 		channel_id: &ChannelId,
 		sequence: Sequence,
 	) -> Result<(), Error>;
+
+	/// Returns the client and consensus state to store once a `proof` was verified with
+	/// `client_state`, for clients whose proofs can only be used once. Solo machines sign every
+	/// proof over their current sequence, which is advanced along with the timestamp of the proof.
+	/// Other clients are left unchanged.
+	fn update_state_on_proof_verified<Ctx: ReaderContext>(
+		&self,
+		_ctx: &Ctx,
+		_client_state: Self::ClientState,
+		_proof: &CommitmentProofBytes,
+	) -> Result<Option<(Self::ClientState, ConsensusUpdateResult<Ctx>)>, Error> {
+		Ok(None)
+	}
+}
+
+/// Returns the state of the client `client_id` to verify the next proof of a message with, the
+/// one `client_update` advanced it to after the previous proofs of the message if any.
+pub fn client_state_for_proof<Ctx: ReaderContext>(
+	ctx: &Ctx,
+	client_id: &ClientId,
+	client_update: &Option<UpdateResult<Ctx>>,
+) -> Result<Ctx::AnyClientState, Error> {
+	match client_update {
+		Some(update) if &update.client_id == client_id => Ok(update.client_state.clone()),
+		_ => ctx.client_state(client_id),
+	}
+}
+
+/// Records in `client_update` the state of the client `client_id` after a `proof` was verified
+/// with `client_state`, see [`ClientDef::update_state_on_proof_verified`]. Handlers return it
+/// with their result, so that it is only stored along with the rest of the message.
+pub fn record_state_on_proof_verified<Ctx: ReaderContext>(
+	ctx: &Ctx,
+	client_id: &ClientId,
+	client_state: Ctx::AnyClientState,
+	proof: &CommitmentProofBytes,
+	client_update: &mut Option<UpdateResult<Ctx>>,
+) -> Result<(), Error> {
+	let updated =
+		client_state
+			.client_def()
+			.update_state_on_proof_verified(ctx, client_state, proof)?;
+	if let Some((client_state, consensus_state)) = updated {
+		*client_update = Some(UpdateResult {
+			client_id: client_id.clone(),
+			client_state,
+			consensus_state: Some(consensus_state),
+			processed_time: ctx.host_timestamp(),
+			processed_height: ctx.host_height(),
+		});
+	}
+	Ok(())
 }
@@ -24,7 +24,7 @@ use crate::{
 			client_message::ClientMessage,
 			client_state::{ClientState, ClientType},
 			error::{Error, ErrorDetail},
-			handler::ClientResult::{self, Create, Update, Upgrade},
+			handler::ClientResult::{self, Create, Misbehaviour, Update, Upgrade},
 		},
 		ics24_host::identifier::ClientId,
 	},
@@ -218,6 +218,10 @@ where
 				}
 				Ok(())
 			},
+			Misbehaviour(res) => {
+				self.store_client_state(res.client_id, res.client_state)?;
+				Ok(())
+			},
 		}
 	}
 
@@ -75,6 +75,14 @@ define_error! {
 			{ reason: String }
 			| e | { format_args!("header verification failed with reason: {}", e.reason) },
 
+		MisbehaviourHandlingFailure
+			{ reason: String }
+			| e | { format_args!("misbehaviour handling failed with reason: {}", e.reason) },
+
+		MisbehaviourNotFound
+			{ client_id: ClientId }
+			| e | { format_args!("submitted evidence is not misbehaviour of client {0}", e.client_id) },
+
 		InvalidTrustThreshold
 			{ numerator: u64, denominator: u64 }
 			| e | { format_args!("failed to build trust threshold from fraction: {}/{}", e.numerator, e.denominator) },
@@ -152,6 +160,10 @@ define_error! {
 			[ ValidationError ]
 			| _ | { "invalid client id in the update client message" },
 
+		InvalidMsgSubmitMisbehaviourClientId
+			[ ValidationError ]
+			| _ | { "invalid client id in the submit misbehaviour message" },
+
 		Decode
 			[ TraceError<prost::DecodeError> ]
 			| _ | { "decode error" },
@@ -24,6 +24,7 @@ use crate::{
 use core::fmt::Debug;
 
 pub mod create_client;
+pub mod misbehaviour;
 pub mod update_client;
 pub mod upgrade_client;
 
@@ -32,6 +33,7 @@ pub enum ClientResult<C: ClientTypes> {
 	Create(create_client::Result<C>),
 	Update(update_client::Result<C>),
 	Upgrade(upgrade_client::Result<C>),
+	Misbehaviour(misbehaviour::Result<C>),
 }
 
 /// General entry point for processing any message related to ICS2 (client functions) protocols.
@@ -46,5 +48,6 @@ where
 		ClientMsg::CreateClient(msg) => create_client::process::<_>(ctx, msg),
 		ClientMsg::UpdateClient(msg) => update_client::process::<_>(ctx, msg),
 		ClientMsg::UpgradeClient(msg) => upgrade_client::process::<_>(ctx, msg),
+		ClientMsg::Misbehaviour(msg) => misbehaviour::process::<_>(ctx, msg),
 	}
 }
@@ -0,0 +1,172 @@
+// Copyright 2022 ComposableFi
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Protocol logic specific to processing ICS2 messages of type `MsgSubmitAnyMisbehaviour`.
+use core::fmt::Debug;
+
+use crate::{
+	core::{
+		ics02_client::{
+			client_def::ClientDef, client_state::ClientState, context::ClientTypes, error::Error,
+			events::Attributes, handler::ClientResult,
+			msgs::misbehaviour::MsgSubmitAnyMisbehaviour,
+		},
+		ics24_host::identifier::ClientId,
+		ics26_routing::context::ReaderContext,
+	},
+	events::IbcEvent,
+	handler::{HandlerOutput, HandlerResult},
+	prelude::*,
+};
+
+/// The result following the successful processing of a `MsgSubmitAnyMisbehaviour` message.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Result<C: ClientTypes> {
+	pub client_id: ClientId,
+	pub client_state: C::AnyClientState,
+}
+
+pub fn process<Ctx>(
+	ctx: &Ctx,
+	msg: MsgSubmitAnyMisbehaviour<Ctx>,
+) -> HandlerResult<ClientResult<Ctx>, Error>
+where
+	Ctx: ReaderContext,
+{
+	let mut output = HandlerOutput::builder();
+
+	let MsgSubmitAnyMisbehaviour { client_id, misbehaviour, signer: _ } = msg;
+
+	// Read client type from the host chain store. The client should already exist.
+	let client_type = ctx.client_type(&client_id)?;
+
+	// Read client state from the host chain store.
+	let client_state = ctx.client_state(&client_id)?;
+
+	if client_state.is_frozen(ctx, &client_id) {
+		return Err(Error::client_frozen(client_id))
+	}
+
+	let client_def = client_state.client_def();
+
+	client_def
+		.verify_client_message::<Ctx>(
+			ctx,
+			client_id.clone(),
+			client_state.clone(),
+			misbehaviour.clone(),
+		)
+		.map_err(|e| Error::misbehaviour_handling_failure(e.to_string()))?;
+
+	let found_misbehaviour = client_def
+		.check_for_misbehaviour(ctx, client_id.clone(), client_state.clone(), misbehaviour.clone())
+		.map_err(|e| Error::misbehaviour_handling_failure(e.to_string()))?;
+
+	if !found_misbehaviour {
+		return Err(Error::misbehaviour_not_found(client_id))
+	}
+
+	let event_attributes = Attributes {
+		client_id: client_id.clone(),
+		height: ctx.host_height(),
+		client_type,
+		consensus_height: client_state.latest_height(),
+	};
+
+	let client_state = client_def
+		.update_state_on_misbehaviour(client_state, misbehaviour)
+		.map_err(|e| Error::misbehaviour_handling_failure(e.to_string()))?;
+
+	let result = ClientResult::Misbehaviour(Result { client_id, client_state });
+
+	output.emit(IbcEvent::ClientMisbehaviour(event_attributes.into()));
+
+	Ok(output.with_result(result))
+}
+
+#[cfg(test)]
+mod tests {
+	use core::str::FromStr;
+	use test_log::test;
+
+	use crate::{
+		core::{
+			ics02_client::{
+				client_state::ClientState,
+				context::ClientKeeper,
+				error::{Error, ErrorDetail},
+				handler::{dispatch, ClientResult},
+				msgs::{misbehaviour::MsgSubmitAnyMisbehaviour, ClientMsg},
+			},
+			ics24_host::identifier::ClientId,
+		},
+		events::IbcEvent,
+		handler::HandlerOutput,
+		mock::{
+			context::{MockClientTypes, MockContext},
+			header::MockHeader,
+			misbehaviour::MockMisbehaviour,
+		},
+		prelude::*,
+		test_utils::get_dummy_account_id,
+		timestamp::Timestamp,
+		Height,
+	};
+
+	#[test]
+	fn test_submit_misbehaviour_freezes_client() {
+		let client_id = ClientId::from_str("mockclient1").unwrap();
+		let signer = get_dummy_account_id();
+
+		let mut ctx =
+			MockContext::<MockClientTypes>::default().with_client(&client_id, Height::new(0, 42));
+		ctx.store_update_time(client_id.clone(), Height::new(0, 42), Timestamp::now())
+			.unwrap();
+
+		let msg = MsgSubmitAnyMisbehaviour {
+			client_id: client_id.clone(),
+			misbehaviour: MockMisbehaviour {
+				client_id: client_id.clone(),
+				header1: MockHeader::new(Height::new(0, 46)),
+				header2: MockHeader::new(Height::new(0, 46)),
+			}
+			.into(),
+			signer,
+		};
+
+		let output = dispatch(&ctx, ClientMsg::Misbehaviour(msg)).unwrap();
+		let HandlerOutput { result, events, .. } = output;
+		assert!(
+			matches!(&events[..], [IbcEvent::ClientMisbehaviour(e)] if e.client_id() == &client_id)
+		);
+		let result = match result {
+			ClientResult::Misbehaviour(result) => result,
+			_ => panic!("misbehaviour handler result has incorrect type"),
+		};
+		assert_eq!(result.client_id, client_id);
+		assert!(result.client_state.frozen_height().is_some());
+
+		// a header is not evidence of misbehaviour
+		let msg = MsgSubmitAnyMisbehaviour {
+			client_id: client_id.clone(),
+			misbehaviour: MockHeader::new(Height::new(0, 46)).into(),
+			signer: get_dummy_account_id(),
+		};
+		match dispatch(&ctx, ClientMsg::Misbehaviour(msg)) {
+			Err(Error(ErrorDetail::MisbehaviourNotFound(e), _)) =>
+				assert_eq!(e.client_id, client_id),
+			output => panic!("expected MisbehaviourNotFound error, instead got {:?}", output),
+		}
+	}
+}
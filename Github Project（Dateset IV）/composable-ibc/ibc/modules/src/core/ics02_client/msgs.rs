@@ -21,12 +21,13 @@
 use crate::core::ics02_client::{
 	context::ClientTypes,
 	msgs::{
-		create_client::MsgCreateAnyClient, update_client::MsgUpdateAnyClient,
-		upgrade_client::MsgUpgradeAnyClient,
+		create_client::MsgCreateAnyClient, misbehaviour::MsgSubmitAnyMisbehaviour,
+		update_client::MsgUpdateAnyClient, upgrade_client::MsgUpgradeAnyClient,
 	},
 };
 
 pub mod create_client;
+pub mod misbehaviour;
 pub mod update_client;
 pub mod upgrade_client;
 
@@ -36,4 +37,5 @@ pub enum ClientMsg<C: ClientTypes> {
 	CreateClient(MsgCreateAnyClient<C>),
 	UpdateClient(MsgUpdateAnyClient<C>),
 	UpgradeClient(MsgUpgradeAnyClient<C>),
+	Misbehaviour(MsgSubmitAnyMisbehaviour<C>),
 }
@@ -0,0 +1,121 @@
+// Copyright 2022 ComposableFi
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Definition of domain type message `MsgSubmitAnyMisbehaviour`.
+
+use crate::prelude::*;
+use core::fmt::Display;
+
+use ibc_proto::google::protobuf::Any;
+use tendermint_proto::Protobuf;
+
+use crate::core::ics02_client::context::ClientTypes;
+use ibc_proto::ibc::core::client::v1::MsgSubmitMisbehaviour as RawMsgSubmitMisbehaviour;
+
+use crate::core::ics02_client::error::Error;
+
+use crate::{
+	core::ics24_host::{error::ValidationError, identifier::ClientId},
+	signer::Signer,
+	tx_msg::Msg,
+};
+
+pub const TYPE_URL: &str = "/ibc.core.client.v1.MsgSubmitMisbehaviour";
+
+/// A type of message that submits evidence of misbehaviour of the counterparty of an on-chain
+/// (IBC) client, freezing the client.
+#[derive(Clone, Debug, PartialEq)]
+pub struct MsgSubmitAnyMisbehaviour<C: ClientTypes> {
+	/// client unique identifier
+	pub client_id: ClientId,
+	/// misbehaviour used for freezing the light client
+	pub misbehaviour: C::AnyClientMessage,
+	/// signer address
+	pub signer: Signer,
+}
+
+impl<C> MsgSubmitAnyMisbehaviour<C>
+where
+	C: ClientTypes,
+{
+	pub fn new(client_id: ClientId, misbehaviour: C::AnyClientMessage, signer: Signer) -> Self {
+		MsgSubmitAnyMisbehaviour { client_id, misbehaviour, signer }
+	}
+}
+
+impl<C> Msg for MsgSubmitAnyMisbehaviour<C>
+where
+	C: ClientTypes + Clone,
+	C::AnyClientMessage: Clone,
+	Any: From<C::AnyClientMessage>,
+{
+	type ValidationError = ValidationError;
+	type Raw = RawMsgSubmitMisbehaviour;
+
+	fn route(&self) -> String {
+		crate::keys::ROUTER_KEY.to_string()
+	}
+
+	fn type_url(&self) -> String {
+		TYPE_URL.to_string()
+	}
+}
+
+impl<C> Protobuf<RawMsgSubmitMisbehaviour> for MsgSubmitAnyMisbehaviour<C>
+where
+	C: ClientTypes + Clone,
+	C::AnyClientMessage: Clone,
+	Any: From<C::AnyClientMessage>,
+	MsgSubmitAnyMisbehaviour<C>: TryFrom<RawMsgSubmitMisbehaviour>,
+	<MsgSubmitAnyMisbehaviour<C> as TryFrom<RawMsgSubmitMisbehaviour>>::Error: Display,
+{
+}
+
+impl<C> TryFrom<RawMsgSubmitMisbehaviour> for MsgSubmitAnyMisbehaviour<C>
+where
+	C: ClientTypes,
+	C::AnyClientMessage: TryFrom<Any>,
+	Error: From<<C::AnyClientMessage as TryFrom<Any>>::Error>,
+{
+	type Error = Error;
+
+	#[allow(deprecated)]
+	fn try_from(raw: RawMsgSubmitMisbehaviour) -> Result<Self, Self::Error> {
+		let raw_misbehaviour = raw.misbehaviour.ok_or_else(Error::missing_raw_misbehaviour)?;
+
+		Ok(MsgSubmitAnyMisbehaviour {
+			client_id: raw
+				.client_id
+				.parse()
+				.map_err(Error::invalid_msg_submit_misbehaviour_client_id)?,
+			misbehaviour: C::AnyClientMessage::try_from(raw_misbehaviour)?,
+			signer: raw.signer.parse().map_err(Error::signer)?,
+		})
+	}
+}
+
+impl<C> From<MsgSubmitAnyMisbehaviour<C>> for RawMsgSubmitMisbehaviour
+where
+	C: ClientTypes,
+	Any: From<C::AnyClientMessage>,
+{
+	#[allow(deprecated)]
+	fn from(ics_msg: MsgSubmitAnyMisbehaviour<C>) -> Self {
+		RawMsgSubmitMisbehaviour {
+			client_id: ics_msg.client_id.to_string(),
+			misbehaviour: Some(ics_msg.misbehaviour.into()),
+			signer: ics_msg.signer.to_string(),
+		}
+	}
+}
@@ -18,6 +18,7 @@
 
 use crate::{
 	core::{
+		ics02_client::{context::ClientKeeper, handler::ClientResult},
 		ics03_connection::{
 			connection::ConnectionEnd,
 			error::Error,
@@ -72,7 +73,15 @@ pub trait ConnectionReader {
 /// A context supplying all the necessary write-only dependencies (i.e., storage writing facility)
 /// for processing any `ConnectionMsg`.
 pub trait ConnectionKeeper {
-	fn store_connection_result(&mut self, result: ConnectionResult) -> Result<(), Error> {
+	fn store_connection_result(&mut self, result: ConnectionResult<Self>) -> Result<(), Error>
+	where
+		Self: ClientKeeper + Sized,
+	{
+		if let Some(client_update) = result.client_update {
+			self.store_client_result(ClientResult::Update(client_update))
+				.map_err(Error::ics02_client)?;
+		}
+
 		self.store_connection(result.connection_id.clone(), &result.connection_end)?;
 
 		// If we generated an identifier, increase the counter & associate this new identifier
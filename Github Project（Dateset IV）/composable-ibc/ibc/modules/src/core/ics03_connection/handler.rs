@@ -16,6 +16,7 @@
 
 use crate::{
 	core::{
+		ics02_client::{context::ClientTypes, handler::update_client::Result as UpdateResult},
 		ics03_connection::{connection::ConnectionEnd, error::Error, msgs::ConnectionMsg},
 		ics24_host::identifier::ConnectionId,
 		ics26_routing::context::ReaderContext,
@@ -43,7 +44,7 @@ pub enum ConnectionIdState {
 }
 
 #[derive(Clone, Debug)]
-pub struct ConnectionResult {
+pub struct ConnectionResult<C: ClientTypes> {
 	/// The identifier for the connection which the handler processed. Typically this represents
 	/// the newly-generated connection id (e.g., when processing `MsgConnectionOpenInit`) or
 	/// an existing connection id (e.g., for `MsgConnectionOpenAck`).
@@ -54,6 +55,10 @@ pub struct ConnectionResult {
 
 	/// The connection end, which the handler produced as a result of processing the message.
 	pub connection_end: ConnectionEnd,
+
+	/// The state of the client advanced by the proofs verified to process the message, stored
+	/// along with the connection end.
+	pub client_update: Option<UpdateResult<C>>,
 }
 
 /// General entry point for processing any type of message related to the ICS3 connection open
@@ -61,7 +66,7 @@ pub struct ConnectionResult {
 pub fn dispatch<Ctx: ReaderContext>(
 	ctx: &Ctx,
 	msg: ConnectionMsg<Ctx>,
-) -> Result<HandlerOutput<ConnectionResult>, Error> {
+) -> Result<HandlerOutput<ConnectionResult<Ctx>>, Error> {
 	match msg {
 		ConnectionMsg::ConnectionOpenInit(msg) => conn_open_init::process(ctx, msg),
 		ConnectionMsg::ConnectionOpenTry(msg) => conn_open_try::process::<_>(ctx, *msg),
@@ -39,8 +39,9 @@ use crate::{
 pub(crate) fn process<Ctx: ReaderContext>(
 	ctx: &Ctx,
 	msg: MsgConnectionOpenAck<Ctx>,
-) -> HandlerResult<ConnectionResult, Error> {
+) -> HandlerResult<ConnectionResult<Ctx>, Error> {
 	let mut output = HandlerOutput::builder();
+	let mut client_update = None;
 
 	// Check the client's (consensus state) proof height if it consensus proof is provided
 	if msg.proofs.consensus_proof().is_some() {
@@ -111,6 +112,7 @@ pub(crate) fn process<Ctx: ReaderContext>(
 		&expected_conn,
 		msg.proofs.height(),
 		msg.proofs.object_proof(),
+		&mut client_update,
 	)?;
 
 	verify_client_proof::<Ctx>(
@@ -120,6 +122,7 @@ pub(crate) fn process<Ctx: ReaderContext>(
 		&client_state,
 		msg.proofs.height(),
 		client_proof,
+		&mut client_update,
 	)?;
 
 	verify_consensus_proof::<Ctx>(
@@ -128,6 +131,7 @@ pub(crate) fn process<Ctx: ReaderContext>(
 		&conn_end,
 		&consensus_proof,
 		msg.host_consensus_state_proof,
+		&mut client_update,
 	)?;
 
 	output.log("success: connection verification passed");
@@ -144,6 +148,7 @@ pub(crate) fn process<Ctx: ReaderContext>(
 		connection_id: msg.connection_id,
 		connection_id_state: ConnectionIdState::Reused,
 		connection_end: conn_end,
+		client_update,
 	};
 
 	output.emit(IbcEvent::OpenAckConnection(event_attributes.into()));
@@ -309,7 +314,7 @@ mod tests {
 					assert!(!proto_output.events.is_empty()); // Some events must exist.
 
 					// The object in the output is a ConnectionEnd, should have OPEN state.
-					let res: ConnectionResult = proto_output.result;
+					let res: ConnectionResult<_> = proto_output.result;
 					assert_eq!(res.connection_end.state().clone(), State::Open);
 
 					// assert that counterparty connection id is correct
@@ -33,8 +33,9 @@ use crate::{
 pub(crate) fn process<Ctx: ReaderContext>(
 	ctx: &Ctx,
 	msg: MsgConnectionOpenConfirm,
-) -> HandlerResult<ConnectionResult, Error> {
+) -> HandlerResult<ConnectionResult<Ctx>, Error> {
 	let mut output = HandlerOutput::builder();
+	let mut client_update = None;
 
 	// Validate the connection end.
 	let mut conn_end = ctx.connection_end(&msg.connection_id)?;
@@ -66,6 +67,7 @@ pub(crate) fn process<Ctx: ReaderContext>(
 		&expected_conn,
 		msg.proofs.height(),
 		msg.proofs.object_proof(),
+		&mut client_update,
 	)?;
 
 	output.log("success: connection verification passed");
@@ -85,6 +87,7 @@ pub(crate) fn process<Ctx: ReaderContext>(
 		connection_id: msg.connection_id,
 		connection_id_state: ConnectionIdState::Reused,
 		connection_end: conn_end,
+		client_update,
 	};
 
 	output.emit(IbcEvent::OpenConfirmConnection(event_attributes.into()));
@@ -197,7 +200,7 @@ mod tests {
 					assert!(!proto_output.events.is_empty()); // Some events must exist.
 
 					// The object in the output is a ConnectionEnd, should have OPEN state.
-					let res: ConnectionResult = proto_output.result;
+					let res: ConnectionResult<_> = proto_output.result;
 					assert_eq!(res.connection_end.state().clone(), State::Open);
 
 					for e in proto_output.events.iter() {
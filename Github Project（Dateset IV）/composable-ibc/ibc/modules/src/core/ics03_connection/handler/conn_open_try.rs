@@ -40,8 +40,9 @@ use crate::{
 pub(crate) fn process<Ctx: ReaderContext>(
 	ctx: &Ctx,
 	msg: MsgConnectionOpenTry<Ctx>,
-) -> HandlerResult<ConnectionResult, Error> {
+) -> HandlerResult<ConnectionResult<Ctx>, Error> {
 	let mut output = HandlerOutput::builder();
+	let mut client_update = None;
 
 	let minimum_delay_period = ctx.minimum_delay_period();
 	if msg.delay_period < minimum_delay_period {
@@ -105,6 +106,7 @@ pub(crate) fn process<Ctx: ReaderContext>(
 		&expected_conn,
 		msg.proofs.height(),
 		msg.proofs.object_proof(),
+		&mut client_update,
 	)?;
 
 	verify_client_proof::<_>(
@@ -114,6 +116,7 @@ pub(crate) fn process<Ctx: ReaderContext>(
 		&client_state,
 		msg.proofs.height(),
 		client_proof,
+		&mut client_update,
 	)?;
 
 	verify_consensus_proof::<_>(
@@ -122,6 +125,7 @@ pub(crate) fn process<Ctx: ReaderContext>(
 		&new_connection_end,
 		&consensus_proof,
 		msg.host_consensus_state_proof,
+		&mut client_update,
 	)?;
 
 	// Transition the connection end to the new state & pick a version.
@@ -148,6 +152,7 @@ pub(crate) fn process<Ctx: ReaderContext>(
 		connection_id: conn_id,
 		connection_id_state: ConnectionIdState::Generated,
 		connection_end: new_connection_end,
+		client_update,
 	};
 
 	output.emit(IbcEvent::OpenTryConnection(event_attributes.into()));
@@ -278,7 +283,7 @@ mod tests {
 					assert!(!proto_output.events.is_empty()); // Some events must exist.
 
 					// The object in the output is a ConnectionEnd, should have TryOpen state.
-					let res: ConnectionResult = proto_output.result;
+					let res: ConnectionResult<_> = proto_output.result;
 					assert_eq!(res.connection_end.state().clone(), State::TryOpen);
 
 					for e in proto_output.events.iter() {
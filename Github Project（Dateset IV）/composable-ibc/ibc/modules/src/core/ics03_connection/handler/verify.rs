@@ -15,7 +15,10 @@
 //! ICS3 verification functions, common across all four handlers of ICS3.
 
 use crate::core::ics02_client::{
-	client_consensus::ConsensusState, client_def::ClientDef, client_state::ClientState,
+	client_consensus::ConsensusState,
+	client_def::{client_state_for_proof, record_state_on_proof_verified, ClientDef},
+	client_state::ClientState,
+	handler::update_client::Result as UpdateResult,
 };
 
 use crate::{
@@ -39,10 +42,12 @@ pub fn verify_connection_proof<Ctx: ReaderContext>(
 	expected_conn: &ConnectionEnd,
 	proof_height: Height,
 	proof: &CommitmentProofBytes,
+	client_update: &mut Option<UpdateResult<Ctx>>,
 ) -> Result<(), Error> {
 	// Fetch the client state (IBC client on the local/host chain).
 	let client_id = connection_end.client_id();
-	let client_state = ctx.client_state(client_id).map_err(Error::ics02_client)?;
+	let client_state =
+		client_state_for_proof(ctx, client_id, client_update).map_err(Error::ics02_client)?;
 
 	// The client must not be frozen.
 	if client_state.is_frozen(ctx, client_id) {
@@ -76,7 +81,10 @@ pub fn verify_connection_proof<Ctx: ReaderContext>(
 			connection_id,
 			expected_conn,
 		)
-		.map_err(Error::verify_connection_state)
+		.map_err(Error::verify_connection_state)?;
+
+	record_state_on_proof_verified(ctx, client_id, client_state, proof, client_update)
+		.map_err(Error::ics02_client)
 }
 
 /// Verifies the client `proof` from a connection handshake message, typically from a
@@ -93,10 +101,12 @@ pub fn verify_client_proof<Ctx: ReaderContext>(
 	expected_client_state: &Ctx::AnyClientState,
 	proof_height: Height,
 	proof: &CommitmentProofBytes,
+	client_update: &mut Option<UpdateResult<Ctx>>,
 ) -> Result<(), Error> {
 	// Fetch the local client state (IBC client running on the host chain).
 	let client_id = connection_end.client_id();
-	let client_state = ctx.client_state(client_id).map_err(Error::ics02_client)?;
+	let client_state =
+		client_state_for_proof(ctx, client_id, client_update).map_err(Error::ics02_client)?;
 
 	if client_state.is_frozen(ctx, client_id) {
 		return Err(Error::frozen_client(client_id.clone()))
@@ -119,7 +129,10 @@ pub fn verify_client_proof<Ctx: ReaderContext>(
 			connection_end.counterparty().client_id(),
 			&expected_client_state,
 		)
-		.map_err(|e| Error::client_state_verification_failure(client_id.clone(), e))
+		.map_err(|e| Error::client_state_verification_failure(client_id.clone(), e))?;
+
+	record_state_on_proof_verified(ctx, client_id, client_state, proof, client_update)
+		.map_err(Error::ics02_client)
 }
 
 pub fn verify_consensus_proof<Ctx: ReaderContext>(
@@ -128,10 +141,12 @@ pub fn verify_consensus_proof<Ctx: ReaderContext>(
 	connection_end: &ConnectionEnd,
 	proof: &ConsensusProof,
 	host_consensus_state_proof: Vec<u8>,
+	client_update: &mut Option<UpdateResult<Ctx>>,
 ) -> Result<(), Error> {
 	// Fetch the client state (IBC client on the local chain).
 	let client_id = connection_end.client_id();
-	let client_state = ctx.client_state(client_id).map_err(Error::ics02_client)?;
+	let client_state =
+		client_state_for_proof(ctx, client_id, client_update).map_err(Error::ics02_client)?;
 
 	if client_state.is_frozen(ctx, client_id) {
 		return Err(Error::frozen_client(client_id.clone()))
@@ -168,7 +183,8 @@ pub fn verify_consensus_proof<Ctx: ReaderContext>(
 		)
 		.map_err(|e| Error::consensus_state_verification_failure(proof.height(), e))?;
 
-	Ok(())
+	record_state_on_proof_verified(ctx, client_id, client_state, proof.proof(), client_update)
+		.map_err(Error::ics02_client)
 }
 
 /// Checks that `claimed_height` is within normal bounds, i.e., fresh enough so that the chain has
@@ -68,6 +68,7 @@ impl Default for Version {
 			features: vec![
 				Order::Ordered.as_str().to_owned(),
 				Order::Unordered.as_str().to_owned(),
+				Order::OrderedAllowTimeout.as_str().to_owned(),
 			],
 		}
 	}
@@ -58,6 +58,7 @@ impl TryFrom<RawIdentifiedChannel> for IdentifiedChannelEnd {
 			counterparty: value.counterparty,
 			connection_hops: value.connection_hops,
 			version: value.version,
+			upgrade_sequence: value.upgrade_sequence,
 		};
 
 		Ok(IdentifiedChannelEnd {
@@ -83,6 +84,7 @@ impl From<IdentifiedChannelEnd> for RawIdentifiedChannel {
 			version: value.channel_end.version.to_string(),
 			port_id: value.port_id.to_string(),
 			channel_id: value.channel_id.to_string(),
+			upgrade_sequence: value.channel_end.upgrade_sequence,
 		}
 	}
 }
@@ -94,6 +96,8 @@ pub struct ChannelEnd {
 	pub remote: Counterparty,
 	pub connection_hops: Vec<ConnectionId>,
 	pub version: Version,
+	/// Incremented every time an upgrade of the channel is initialised
+	pub upgrade_sequence: u64,
 }
 
 impl Default for ChannelEnd {
@@ -104,6 +108,7 @@ impl Default for ChannelEnd {
 			remote: Counterparty::default(),
 			connection_hops: Vec::new(),
 			version: Version::default(),
+			upgrade_sequence: 0,
 		}
 	}
 }
@@ -131,7 +136,8 @@ impl TryFrom<RawChannel> for ChannelEnd {
 
 		let version = value.version.into();
 
-		Ok(ChannelEnd::new(chan_state, chan_ordering, remote, connection_hops, version))
+		Ok(ChannelEnd::new(chan_state, chan_ordering, remote, connection_hops, version)
+			.with_upgrade_sequence(value.upgrade_sequence))
 	}
 }
 
@@ -143,6 +149,7 @@ impl From<ChannelEnd> for RawChannel {
 			counterparty: Some(value.counterparty().clone().into()),
 			connection_hops: value.connection_hops.iter().map(|v| v.as_str().to_string()).collect(),
 			version: value.version.to_string(),
+			upgrade_sequence: value.upgrade_sequence,
 		}
 	}
 }
@@ -156,7 +163,11 @@ impl ChannelEnd {
 		connection_hops: Vec<ConnectionId>,
 		version: Version,
 	) -> Self {
-		Self { state, ordering, remote, connection_hops, version }
+		Self { state, ordering, remote, connection_hops, version, upgrade_sequence: 0 }
+	}
+
+	pub fn with_upgrade_sequence(self, upgrade_sequence: u64) -> Self {
+		Self { upgrade_sequence, ..self }
 	}
 
 	/// Updates the ChannelEnd to assume a new State 's'.
@@ -177,6 +188,11 @@ impl ChannelEnd {
 		self.state_matches(&State::Open)
 	}
 
+	/// Returns `true` if this `ChannelEnd` is flushing in-flight packets as part of an upgrade.
+	pub fn is_flushing(&self) -> bool {
+		matches!(self.state, State::Flushing | State::FlushComplete)
+	}
+
 	pub fn state(&self) -> &State {
 		&self.state
 	}
@@ -280,6 +296,9 @@ impl From<Counterparty> for RawCounterparty {
 pub enum Order {
 	Unordered = 1,
 	Ordered = 2,
+	/// Packets are delivered in order, but a packet that timed out on the receiving end is
+	/// skipped instead of closing the channel.
+	OrderedAllowTimeout = 3,
 }
 
 impl Default for Order {
@@ -300,14 +319,21 @@ impl Order {
 		match self {
 			Self::Unordered => "ORDER_UNORDERED",
 			Self::Ordered => "ORDER_ORDERED",
+			Self::OrderedAllowTimeout => "ORDER_ORDERED_ALLOW_TIMEOUT",
 		}
 	}
 
+	/// Returns true if packets on a channel with this ordering must be received in sequence.
+	pub fn is_ordered(&self) -> bool {
+		matches!(self, Self::Ordered | Self::OrderedAllowTimeout)
+	}
+
 	// Parses the Order out from a i32.
 	pub fn from_i32(nr: i32) -> Result<Self, Error> {
 		match nr {
 			1 => Ok(Self::Unordered),
 			2 => Ok(Self::Ordered),
+			3 => Ok(Self::OrderedAllowTimeout),
 			_ => Err(Error::unknown_order_type(nr.to_string())),
 		}
 	}
@@ -320,6 +346,7 @@ impl FromStr for Order {
 		match s.to_lowercase().trim_start_matches("order_") {
 			"unordered" => Ok(Self::Unordered),
 			"ordered" => Ok(Self::Ordered),
+			"ordered_allow_timeout" => Ok(Self::OrderedAllowTimeout),
 			_ => Err(Error::unknown_order_type(s.to_string())),
 		}
 	}
@@ -331,6 +358,8 @@ pub enum State {
 	TryOpen = 2,
 	Open = 3,
 	Closed = 4,
+	Flushing = 5,
+	FlushComplete = 6,
 }
 
 impl State {
@@ -341,6 +370,8 @@ impl State {
 			Self::TryOpen => "TRYOPEN",
 			Self::Open => "OPEN",
 			Self::Closed => "CLOSED",
+			Self::Flushing => "FLUSHING",
+			Self::FlushComplete => "FLUSHCOMPLETE",
 		}
 	}
 
@@ -351,6 +382,8 @@ impl State {
 			2 => Ok(Self::TryOpen),
 			3 => Ok(Self::Open),
 			4 => Ok(Self::Closed),
+			5 => Ok(Self::Flushing),
+			6 => Ok(Self::FlushComplete),
 			_ => Err(Error::unknown_state(s)),
 		}
 	}
@@ -421,6 +454,7 @@ pub mod test_util {
 			counterparty: Some(get_dummy_raw_counterparty()),
 			connection_hops: vec![ConnectionId::default().to_string()],
 			version: "ics20".to_string(), // The version is not validated.
+			upgrade_sequence: 0,
 		}
 	}
 }
@@ -528,6 +562,11 @@ mod tests {
 		let tests: Vec<Test> = vec![
 			Test { ordering: "UNORDERED", want_res: Order::Unordered, want_err: false },
 			Test { ordering: "ORDERED", want_res: Order::Ordered, want_err: false },
+			Test {
+				ordering: "ORDER_ORDERED_ALLOW_TIMEOUT",
+				want_res: Order::OrderedAllowTimeout,
+				want_err: false,
+			},
 		]
 		.into_iter()
 		.collect();
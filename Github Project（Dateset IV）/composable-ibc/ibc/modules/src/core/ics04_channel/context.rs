@@ -19,13 +19,15 @@ use num_traits::float::FloatCore;
 
 use crate::{
 	core::{
+		ics02_client::{context::ClientKeeper, handler::ClientResult},
 		ics04_channel::{
-			channel::ChannelEnd,
+			channel::{ChannelEnd, State},
 			commitment::{AcknowledgementCommitment, PacketCommitment},
 			error::Error,
 			handler::{recv_packet::RecvPacketResult, ChannelIdState, ChannelResult},
 			msgs::acknowledgement::Acknowledgement,
 			packet::Receipt,
+			upgrade::{ErrorReceipt, Upgrade},
 		},
 		ics24_host::identifier::{ChannelId, ClientId, ConnectionId, PortId},
 	},
@@ -109,6 +111,39 @@ pub trait ChannelReader {
 	/// Returns the maximum expected time per block
 	fn max_expected_time_per_block(&self) -> Duration;
 
+	/// Returns the upgrade proposed by the given channel end, if an upgrade is in progress.
+	fn channel_upgrade(&self, port_channel_id: &(PortId, ChannelId)) -> Result<Upgrade, Error>;
+
+	/// Returns the upgrade proposed by the counterparty of the given channel end, which is only
+	/// known once the channel end has started flushing.
+	fn counterparty_channel_upgrade(
+		&self,
+		port_channel_id: &(PortId, ChannelId),
+	) -> Result<Upgrade, Error>;
+
+	/// Returns the error receipt of the last aborted upgrade of the given channel end.
+	fn upgrade_error_receipt(
+		&self,
+		port_channel_id: &(PortId, ChannelId),
+	) -> Result<ErrorReceipt, Error>;
+
+	/// Returns `true` if packets sent on the given channel end have not been acknowledged or
+	/// timed out yet, i.e. packet commitments still exist for the channel end.
+	fn has_inflight_packets(&self, port_channel_id: &(PortId, ChannelId)) -> Result<bool, Error>;
+
+	/// Time given to the counterparty to finish flushing its in-flight packets once a channel end
+	/// starts flushing, after which the upgrade can be timed out.
+	fn channel_upgrade_timeout(&self) -> Duration {
+		Duration::from_secs(600)
+	}
+
+	/// Returns `true` if the messages are delivered by the governance authority of the host, the
+	/// only one allowed to initiate channel upgrades and to cancel them without a proof of the
+	/// counterparty error receipt.
+	fn is_upgrade_authority(&self) -> bool {
+		false
+	}
+
 	/// Calculates the block delay period using the connection's delay period and the maximum
 	/// expected time per block.
 	fn block_delay(&self, delay_period_time: Duration) -> u64 {
@@ -119,10 +154,44 @@ pub trait ChannelReader {
 /// A context supplying all the necessary write-only dependencies (i.e., storage writing facility)
 /// for processing any `ChannelMsg`.
 pub trait ChannelKeeper: ChannelReader {
-	fn store_channel_result(&mut self, result: ChannelResult) -> Result<(), Error> {
+	fn store_channel_result(&mut self, result: ChannelResult<Self>) -> Result<(), Error>
+	where
+		Self: ClientKeeper + Sized,
+	{
+		if let Some(client_update) = result.client_update {
+			self.store_client_result(ClientResult::Update(client_update))
+				.map_err(Error::ics02_client)?;
+		}
+
 		// The handler processed this channel & some modifications occurred, store the new end.
 		self.store_channel((result.port_id.clone(), result.channel_id), &result.channel_end)?;
 
+		// The upgrade handshake handlers also report the new upgrade state of the channel end.
+		if let Some(upgrade_result) = result.upgrade {
+			let port_channel_id = (result.port_id.clone(), result.channel_id);
+			match upgrade_result.upgrade {
+				Some(upgrade) => self.store_channel_upgrade(port_channel_id.clone(), upgrade)?,
+				None => self.delete_channel_upgrade(port_channel_id.clone())?,
+			}
+			match upgrade_result.counterparty_upgrade {
+				Some(upgrade) =>
+					self.store_counterparty_channel_upgrade(port_channel_id.clone(), upgrade)?,
+				None => self.delete_counterparty_channel_upgrade(port_channel_id.clone())?,
+			}
+			if let Some(error_receipt) = upgrade_result.error_receipt {
+				self.store_upgrade_error_receipt(port_channel_id.clone(), error_receipt)?;
+			}
+			if let Some(seq) = upgrade_result.next_sequence_recv {
+				self.store_next_sequence_recv(port_channel_id.clone(), seq)?;
+			}
+			if let Some(seq) = upgrade_result.next_sequence_ack {
+				self.store_next_sequence_ack(port_channel_id.clone(), seq)?;
+			}
+			if let Some(connection_id) = upgrade_result.connection_id {
+				self.store_connection_channels(connection_id, &port_channel_id)?;
+			}
+		}
+
 		// The channel identifier was freshly brewed.
 		// Increase counter & initialize seq. nrs.
 		if matches!(result.channel_id_state, ChannelIdState::Generated) {
@@ -143,7 +212,15 @@ pub trait ChannelKeeper: ChannelReader {
 		Ok(())
 	}
 
-	fn store_packet_result(&mut self, general_result: PacketResult) -> Result<(), Error> {
+	fn store_packet_result(&mut self, mut general_result: PacketResult<Self>) -> Result<(), Error>
+	where
+		Self: ClientKeeper + Sized,
+	{
+		if let Some(client_update) = general_result.take_client_update() {
+			self.store_client_result(ClientResult::Update(client_update))
+				.map_err(Error::ics02_client)?;
+		}
+
 		match general_result {
 			PacketResult::Send(res) => {
 				self.store_next_sequence_send(
@@ -159,15 +236,31 @@ pub trait ChannelKeeper: ChannelReader {
 				self.store_send_packet((res.port_id.clone(), res.channel_id, res.seq), res.packet)?;
 			},
 			PacketResult::Recv(res) => match res {
-				RecvPacketResult::Ordered { port_id, channel_id, next_seq_recv, packet } => {
+				RecvPacketResult::Ordered {
+					port_id, channel_id, next_seq_recv, packet, ..
+				} => {
 					self.store_next_sequence_recv((port_id.clone(), channel_id), next_seq_recv)?;
 					self.store_recv_packet((port_id, channel_id, packet.sequence), packet)?
 				},
-				RecvPacketResult::Unordered { port_id, channel_id, sequence, receipt, packet } => {
+				RecvPacketResult::Unordered {
+					port_id,
+					channel_id,
+					sequence,
+					receipt,
+					packet,
+					..
+				} => {
 					self.store_packet_receipt((port_id.clone(), channel_id, sequence), receipt)?;
 					self.store_recv_packet((port_id, channel_id, packet.sequence), packet)?
 				},
 
+				RecvPacketResult::Timeout {
+					port_id, channel_id, sequence, next_seq_recv, ..
+				} => {
+					self.store_next_sequence_recv((port_id.clone(), channel_id), next_seq_recv)?;
+					self.store_packet_receipt((port_id, channel_id, sequence), Receipt::Timeout)?
+				},
+
 				RecvPacketResult::NoOp => unreachable!(),
 			},
 			PacketResult::WriteAck(res) => {
@@ -180,6 +273,7 @@ pub trait ChannelKeeper: ChannelReader {
 					(res.port_id.clone(), res.channel_id, res.seq),
 					res.ack.into(),
 				)?;
+				self.delete_pending_acknowledgement((res.port_id, res.channel_id, res.seq))?;
 			},
 			PacketResult::Ack(res) => {
 				if let Some(s) = res.seq_number {
@@ -189,18 +283,52 @@ pub trait ChannelKeeper: ChannelReader {
 
 				// Delete packet commitment since packet has been aknowledged
 				self.delete_packet_commitment((res.port_id.clone(), res.channel_id, res.seq))?;
+				self.complete_flush((res.port_id, res.channel_id))?;
 			},
 			PacketResult::Timeout(res) => {
 				if let Some(c) = res.channel {
 					//Ordered Channel
-					self.store_channel((res.port_id.clone(), res.channel_id), &c)?;
+					let port_channel_id = (res.port_id.clone(), res.channel_id);
+					// Closing the channel end aborts the upgrade it was flushing packets for.
+					let channel_end = self.channel_end(&port_channel_id)?;
+					if channel_end.state_matches(&State::Flushing) {
+						self.delete_channel_upgrade(port_channel_id.clone())?;
+						self.delete_counterparty_channel_upgrade(port_channel_id.clone())?;
+						self.store_upgrade_error_receipt(
+							port_channel_id.clone(),
+							ErrorReceipt::new(
+								channel_end.upgrade_sequence,
+								"packet timed out on ordered channel".into(),
+							),
+						)?;
+					}
+					self.store_channel(port_channel_id, &c)?;
+				}
+				if let Some(s) = res.seq_number {
+					//Ordered Allow Timeout Channel
+					self.store_next_sequence_ack((res.port_id.clone(), res.channel_id), s)?;
 				}
 				self.delete_packet_commitment((res.port_id.clone(), res.channel_id, res.seq))?;
+				self.complete_flush((res.port_id, res.channel_id))?;
 			},
 		}
 		Ok(())
 	}
 
+	/// Moves a flushing channel end to `FLUSHCOMPLETE` once the counterparty upgrade is known and
+	/// the last in-flight packet has been acknowledged or timed out.
+	fn complete_flush(&mut self, port_channel_id: (PortId, ChannelId)) -> Result<(), Error> {
+		let mut channel_end = self.channel_end(&port_channel_id)?;
+		if !channel_end.state_matches(&State::Flushing) ||
+			self.counterparty_channel_upgrade(&port_channel_id).is_err() ||
+			self.has_inflight_packets(&port_channel_id)?
+		{
+			return Ok(())
+		}
+		channel_end.set_state(State::FlushComplete);
+		self.store_channel(port_channel_id, &channel_end)
+	}
+
 	fn store_packet_commitment(
 		&mut self,
 		key: (PortId, ChannelId, Sequence),
@@ -247,6 +375,19 @@ pub trait ChannelKeeper: ChannelReader {
 		key: (PortId, ChannelId, Sequence),
 	) -> Result<(), Error>;
 
+	/// Records a received packet whose acknowledgement will be written asynchronously by the
+	/// module that received it
+	fn store_pending_acknowledgement(
+		&mut self,
+		key: (PortId, ChannelId, Sequence),
+	) -> Result<(), Error>;
+
+	/// Called once the acknowledgement of a received packet is written
+	fn delete_pending_acknowledgement(
+		&mut self,
+		key: (PortId, ChannelId, Sequence),
+	) -> Result<(), Error>;
+
 	fn store_connection_channels(
 		&mut self,
 		conn_id: ConnectionId,
@@ -278,6 +419,39 @@ pub trait ChannelKeeper: ChannelReader {
 		seq: Sequence,
 	) -> Result<(), Error>;
 
+	/// Stores the upgrade proposed by the given channel end.
+	fn store_channel_upgrade(
+		&mut self,
+		port_channel_id: (PortId, ChannelId),
+		upgrade: Upgrade,
+	) -> Result<(), Error>;
+
+	/// Deletes the upgrade proposed by the given channel end, this must succeed even if no upgrade
+	/// is stored.
+	fn delete_channel_upgrade(&mut self, port_channel_id: (PortId, ChannelId))
+		-> Result<(), Error>;
+
+	/// Stores the upgrade proposed by the counterparty of the given channel end.
+	fn store_counterparty_channel_upgrade(
+		&mut self,
+		port_channel_id: (PortId, ChannelId),
+		upgrade: Upgrade,
+	) -> Result<(), Error>;
+
+	/// Deletes the upgrade proposed by the counterparty of the given channel end, this must succeed
+	/// even if no upgrade is stored.
+	fn delete_counterparty_channel_upgrade(
+		&mut self,
+		port_channel_id: (PortId, ChannelId),
+	) -> Result<(), Error>;
+
+	/// Stores the error receipt of an aborted upgrade, replacing any previous receipt.
+	fn store_upgrade_error_receipt(
+		&mut self,
+		port_channel_id: (PortId, ChannelId),
+		error_receipt: ErrorReceipt,
+	) -> Result<(), Error>;
+
 	/// Called upon channel identifier creation (Init or Try message processing).
 	/// Increases the counter which keeps track of how many channels have been created.
 	/// Should never fail.
@@ -27,7 +27,7 @@ use crate::{
 	prelude::*,
 	proofs::ProofError,
 	signer::SignerError,
-	timestamp::Timestamp,
+	timestamp::{Timestamp, TimestampOverflowError},
 	Height,
 };
 
@@ -180,6 +180,26 @@ define_error! {
 					e.sequence)
 			},
 
+		PacketBatchVerificationFailed
+			[ client_error::Error ]
+			| _ | { "Verification fails for the batch of packets" },
+
+		MixedPacketBatch
+			{ port_id: PortId, channel_id: ChannelId }
+			| e | {
+				format_args!(
+					"all packets of a batch must be received on port {0} and channel {1}",
+					e.port_id, e.channel_id)
+			},
+
+		DuplicatePacketInBatch
+			{ sequence: Sequence }
+			| e | {
+				format_args!(
+					"the packet with the sequence number {0} appears more than once in the batch",
+					e.sequence)
+			},
+
 		VerifyChannelFailed
 			[ client_error::Error ]
 			| _ | {
@@ -396,6 +416,76 @@ define_error! {
 
 		NotEnoughtFundsForFee
 			| _ | { "not enough funds for fee" },
+
+		MissingUpgrade
+			{ port_id: PortId, channel_id: ChannelId }
+			| e | {
+				format_args!(
+					"no upgrade in progress for the channel end ({0}, {1})",
+					e.port_id, e.channel_id)
+			},
+
+		MissingUpgradeFields
+			| _ | { "missing upgrade fields" },
+
+		MissingUpgradeProof
+			| _ | { "invalid proof: missing upgrade proof" },
+
+		MissingErrorReceipt
+			| _ | { "missing upgrade error receipt" },
+
+		InvalidUpgradeTimeout
+			| _ | { "upgrade timeout height and upgrade timeout timestamp cannot both be 0" },
+
+		UnauthorizedUpgrade
+			| _ | { "channel upgrades can only be initiated or cancelled without a proof by the upgrade authority" },
+
+		UpgradeFieldsUnchanged
+			| _ | { "the proposed upgrade fields are identical to the current channel fields" },
+
+		IncompatibleUpgrade
+			{ reason: String }
+			| e | { format_args!("incompatible counterparty upgrade: {}", e.reason) },
+
+		InvalidUpgradeSequence
+			{ expected: u64, actual: u64 }
+			| e | {
+				format_args!(
+					"invalid upgrade sequence: expected {0}; actual {1}",
+					e.expected, e.actual)
+			},
+
+		InvalidErrorReceiptSequence
+			{ sequence: u64, upgrade_sequence: u64 }
+			| e | {
+				format_args!(
+					"error receipt sequence {0} is lower than the channel upgrade sequence {1}",
+					e.sequence, e.upgrade_sequence)
+			},
+
+		UpgradeTimeoutNotReached
+			| _ | { "the counterparty upgrade timeout has not been reached" },
+
+		UpgradeAlreadyCompleted
+			| _ | { "the counterparty has already completed the upgrade" },
+
+		VerifyUpgradeFailed
+			[ client_error::Error ]
+			| _ | {
+				"Error verifying channel upgrade"
+			},
+
+		UpgradeTimeoutOverflow
+			[ TimestampOverflowError ]
+			| _ | { "the upgrade timeout timestamp overflows" },
+
+		UpgradeNotSupported
+			{ port_id: PortId }
+			| e | {
+				format_args!(
+					"the module bound to port {0} does not support channel upgrades",
+					e.port_id)
+			},
 	}
 }
 
@@ -37,6 +37,7 @@ pub const CHANNEL_ID_ATTRIBUTE_KEY: &str = "channel_id";
 pub const PORT_ID_ATTRIBUTE_KEY: &str = "port_id";
 pub const COUNTERPARTY_CHANNEL_ID_ATTRIBUTE_KEY: &str = "counterparty_channel_id";
 pub const COUNTERPARTY_PORT_ID_ATTRIBUTE_KEY: &str = "counterparty_port_id";
+pub const UPGRADE_SEQUENCE_ATTRIBUTE_KEY: &str = "upgrade_sequence";
 
 /// Packet event attribute keys
 pub const PKT_SEQ_ATTRIBUTE_KEY: &str = "packet_sequence";
@@ -81,6 +82,18 @@ pub fn try_from_tx(event: &tendermint::abci::Event) -> Option<IbcEvent> {
 			.map(|res| res.ok().map(IbcEvent::CloseConfirmChannel))
 			.ok()
 			.flatten(),
+		Ok(
+			event_type @ (IbcEventType::UpgradeInitChannel |
+			IbcEventType::UpgradeTryChannel |
+			IbcEventType::UpgradeAckChannel |
+			IbcEventType::UpgradeConfirmChannel |
+			IbcEventType::UpgradeOpenChannel |
+			IbcEventType::UpgradeTimeoutChannel |
+			IbcEventType::UpgradeCancelChannel |
+			IbcEventType::UpgradeErrorChannel),
+		) => extract_upgrade_attributes_from_tx(event)
+			.ok()
+			.and_then(|attrs| upgrade_event(event_type, attrs)),
 		Ok(IbcEventType::SendPacket) => {
 			extract_packet_and_write_ack_from_tx(event)
 				.map(|(packet, write_ack)| {
@@ -750,6 +763,213 @@ macro_rules! impl_try_from_raw_obj_for_event {
 
 impl_try_from_raw_obj_for_event!(OpenInit, OpenTry, OpenAck, OpenConfirm, CloseInit, CloseConfirm);
 
+/// Attributes shared by all the channel upgrade handshake events.
+#[derive(Debug, Default, Deserialize, Serialize, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct UpgradeAttributes {
+	pub height: Height,
+	pub port_id: PortId,
+	pub channel_id: ChannelId,
+	pub counterparty_port_id: PortId,
+	pub counterparty_channel_id: Option<ChannelId>,
+	pub upgrade_sequence: u64,
+}
+
+/// Convert upgrade attributes to Tendermint ABCI tags
+impl From<UpgradeAttributes> for Vec<EventAttribute> {
+	fn from(a: UpgradeAttributes) -> Self {
+		let mut attributes = vec![
+			EventAttribute {
+				key: HEIGHT_ATTRIBUTE_KEY.to_string(),
+				value: a.height.to_string(),
+				index: false,
+			},
+			EventAttribute {
+				key: PORT_ID_ATTRIBUTE_KEY.to_string(),
+				value: a.port_id.to_string(),
+				index: false,
+			},
+			EventAttribute {
+				key: CHANNEL_ID_ATTRIBUTE_KEY.to_string(),
+				value: a.channel_id.to_string(),
+				index: false,
+			},
+			EventAttribute {
+				key: COUNTERPARTY_PORT_ID_ATTRIBUTE_KEY.to_string(),
+				value: a.counterparty_port_id.to_string(),
+				index: false,
+			},
+		];
+		if let Some(channel_id) = a.counterparty_channel_id {
+			attributes.push(EventAttribute {
+				key: COUNTERPARTY_CHANNEL_ID_ATTRIBUTE_KEY.to_string(),
+				value: channel_id.to_string(),
+				index: false,
+			});
+		}
+		attributes.push(EventAttribute {
+			key: UPGRADE_SEQUENCE_ATTRIBUTE_KEY.to_string(),
+			value: a.upgrade_sequence.to_string(),
+			index: false,
+		});
+		attributes
+	}
+}
+
+/// Builds the channel upgrade event of the given type, returns `None` if `event_type` is not a
+/// channel upgrade event.
+pub fn upgrade_event(event_type: IbcEventType, attrs: UpgradeAttributes) -> Option<IbcEvent> {
+	let event = match event_type {
+		IbcEventType::UpgradeInitChannel => IbcEvent::UpgradeInitChannel(UpgradeInit(attrs)),
+		IbcEventType::UpgradeTryChannel => IbcEvent::UpgradeTryChannel(UpgradeTry(attrs)),
+		IbcEventType::UpgradeAckChannel => IbcEvent::UpgradeAckChannel(UpgradeAck(attrs)),
+		IbcEventType::UpgradeConfirmChannel =>
+			IbcEvent::UpgradeConfirmChannel(UpgradeConfirm(attrs)),
+		IbcEventType::UpgradeOpenChannel => IbcEvent::UpgradeOpenChannel(UpgradeOpen(attrs)),
+		IbcEventType::UpgradeTimeoutChannel =>
+			IbcEvent::UpgradeTimeoutChannel(UpgradeTimeout(attrs)),
+		IbcEventType::UpgradeCancelChannel => IbcEvent::UpgradeCancelChannel(UpgradeCancel(attrs)),
+		IbcEventType::UpgradeErrorChannel => IbcEvent::UpgradeErrorChannel(UpgradeError(attrs)),
+		_ => return None,
+	};
+	Some(event)
+}
+
+fn extract_upgrade_attributes_from_tx(
+	event: &tendermint::abci::Event,
+) -> Result<UpgradeAttributes, Error> {
+	let mut attr = UpgradeAttributes::default();
+
+	for tag in &event.attributes {
+		let key = tag.key.as_str();
+		let value = tag.value.as_str();
+		match key {
+			PORT_ID_ATTRIBUTE_KEY => attr.port_id = value.parse().map_err(Error::identifier)?,
+			CHANNEL_ID_ATTRIBUTE_KEY => {
+				attr.channel_id = value.parse().map_err(Error::identifier)?;
+			},
+			COUNTERPARTY_PORT_ID_ATTRIBUTE_KEY => {
+				attr.counterparty_port_id = value.parse().map_err(Error::identifier)?;
+			},
+			COUNTERPARTY_CHANNEL_ID_ATTRIBUTE_KEY => {
+				attr.counterparty_channel_id = value.parse().ok();
+			},
+			UPGRADE_SEQUENCE_ATTRIBUTE_KEY => {
+				attr.upgrade_sequence = value.parse().map_err(|_| {
+					Error::implementation_specific("parse upgrade_sequence error".to_string())
+				})?;
+			},
+			_ => {},
+		}
+	}
+
+	Ok(attr)
+}
+
+/// Emitted when a channel upgrade is proposed (`ChanUpgradeInit`).
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq, Eq)]
+pub struct UpgradeInit(pub UpgradeAttributes);
+
+/// Emitted when the counterparty accepts a proposed upgrade and starts flushing
+/// (`ChanUpgradeTry`).
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq, Eq)]
+pub struct UpgradeTry(pub UpgradeAttributes);
+
+/// Emitted when the initiating end accepts the counterparty upgrade (`ChanUpgradeAck`).
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq, Eq)]
+pub struct UpgradeAck(pub UpgradeAttributes);
+
+/// Emitted when the counterparty confirms the upgrade (`ChanUpgradeConfirm`).
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq, Eq)]
+pub struct UpgradeConfirm(pub UpgradeAttributes);
+
+/// Emitted once the upgraded channel is open again.
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq, Eq)]
+pub struct UpgradeOpen(pub UpgradeAttributes);
+
+/// Emitted when an upgrade is aborted because the counterparty timed out.
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq, Eq)]
+pub struct UpgradeTimeout(pub UpgradeAttributes);
+
+/// Emitted when an upgrade is cancelled because of a counterparty error receipt.
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq, Eq)]
+pub struct UpgradeCancel(pub UpgradeAttributes);
+
+/// Emitted whenever an upgrade is aborted and an error receipt is written, so that the
+/// counterparty can be cancelled as well.
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq, Eq)]
+pub struct UpgradeError(pub UpgradeAttributes);
+
+macro_rules! impl_upgrade_event {
+    ($($event:ident => $variant:ident),+) => {
+        $(impl $event {
+            pub fn attributes(&self) -> &UpgradeAttributes {
+                &self.0
+            }
+            pub fn port_id(&self) -> &PortId {
+                &self.0.port_id
+            }
+            pub fn channel_id(&self) -> &ChannelId {
+                &self.0.channel_id
+            }
+            pub fn upgrade_sequence(&self) -> u64 {
+                self.0.upgrade_sequence
+            }
+            pub fn height(&self) -> Height {
+                self.0.height
+            }
+            pub fn set_height(&mut self, height: Height) {
+                self.0.height = height;
+            }
+        }
+
+        impl From<UpgradeAttributes> for $event {
+            fn from(attrs: UpgradeAttributes) -> Self {
+                Self(attrs)
+            }
+        }
+
+        impl From<$event> for UpgradeAttributes {
+            fn from(ev: $event) -> Self {
+                ev.0
+            }
+        }
+
+        impl From<$event> for IbcEvent {
+            fn from(v: $event) -> Self {
+                IbcEvent::$variant(v)
+            }
+        }
+
+        impl EventType for $event {
+            fn event_type() -> IbcEventType {
+                IbcEventType::$variant
+            }
+        }
+
+        impl From<$event> for AbciEvent {
+            fn from(v: $event) -> Self {
+                let attributes = Vec::<EventAttribute>::from(v.0);
+                let kind = <$event>::event_type().as_str().to_string();
+                AbciEvent {
+                    kind,
+                    attributes,
+                }
+            }
+        })+
+    };
+}
+
+impl_upgrade_event!(
+	UpgradeInit => UpgradeInitChannel,
+	UpgradeTry => UpgradeTryChannel,
+	UpgradeAck => UpgradeAckChannel,
+	UpgradeConfirm => UpgradeConfirmChannel,
+	UpgradeOpen => UpgradeOpenChannel,
+	UpgradeTimeout => UpgradeTimeoutChannel,
+	UpgradeCancel => UpgradeCancelChannel,
+	UpgradeError => UpgradeErrorChannel
+);
+
 #[derive(Deserialize, Serialize, Clone, PartialEq, Eq)]
 pub struct SendPacket {
 	pub height: Height,
@@ -1163,6 +1383,58 @@ mod tests {
 		}
 	}
 
+	#[test]
+	fn upgrade_event_to_abci_event() {
+		let attributes = UpgradeAttributes {
+			height: Height::default(),
+			port_id: "test_port".parse().unwrap(),
+			channel_id: "channel-0".parse().unwrap(),
+			counterparty_port_id: "counterparty_test_port".parse().unwrap(),
+			counterparty_channel_id: Some("channel-1".parse().unwrap()),
+			upgrade_sequence: 3,
+		};
+		let abci_events = vec![
+			(
+				AbciEvent::from(UpgradeInit::from(attributes.clone())),
+				IbcEventType::UpgradeInitChannel,
+			),
+			(
+				AbciEvent::from(UpgradeTry::from(attributes.clone())),
+				IbcEventType::UpgradeTryChannel,
+			),
+			(
+				AbciEvent::from(UpgradeAck::from(attributes.clone())),
+				IbcEventType::UpgradeAckChannel,
+			),
+			(
+				AbciEvent::from(UpgradeConfirm::from(attributes.clone())),
+				IbcEventType::UpgradeConfirmChannel,
+			),
+			(
+				AbciEvent::from(UpgradeOpen::from(attributes.clone())),
+				IbcEventType::UpgradeOpenChannel,
+			),
+			(
+				AbciEvent::from(UpgradeTimeout::from(attributes.clone())),
+				IbcEventType::UpgradeTimeoutChannel,
+			),
+			(
+				AbciEvent::from(UpgradeCancel::from(attributes.clone())),
+				IbcEventType::UpgradeCancelChannel,
+			),
+			(
+				AbciEvent::from(UpgradeError::from(attributes.clone())),
+				IbcEventType::UpgradeErrorChannel,
+			),
+		];
+
+		for (abci_event, event_type) in abci_events {
+			let event = try_from_tx(&abci_event).expect("converted event was wrong");
+			assert_eq!(event.event_type(), event_type);
+			assert_eq!(event.upgrade_attributes(), Some(&attributes));
+		}
+	}
+
 	#[test]
 	fn packet_event_to_abci_event() {
 		let packet = Packet {
@@ -16,22 +16,25 @@
 
 use crate::{
 	core::{
+		ics02_client::{context::ClientTypes, handler::update_client::Result as UpdateResult},
 		ics04_channel::{
-			channel::ChannelEnd,
+			channel::{ChannelEnd, State},
 			error::Error,
-			events::WriteAcknowledgement,
-			msgs::{ChannelMsg, PacketMsg},
-			packet::PacketResult,
+			events::{UpgradeAttributes, WriteAcknowledgement},
+			msgs::{recv_packet::MsgRecvPacket, ChannelMsg, PacketMsg},
+			packet::{PacketResult, Sequence},
+			upgrade::{ErrorReceipt, Upgrade, UpgradeTimeout},
 		},
-		ics24_host::identifier::{ChannelId, PortId},
+		ics24_host::identifier::{ChannelId, ConnectionId, PortId},
 		ics26_routing::context::{
-			Ics26Context, ModuleId, ModuleOutputBuilder, ReaderContext, Router,
+			Ics26Context, ModuleId, ModuleOutputBuilder, OnRecvPacketAck, ReaderContext, Router,
 		},
 	},
 	events::IbcEvent,
 	handler::{HandlerOutput, HandlerOutputBuilder},
+	Height,
 };
-use alloc::string::ToString;
+use alloc::string::{String, ToString};
 use core::fmt::Debug;
 
 pub mod acknowledgement;
@@ -41,7 +44,15 @@ pub mod chan_open_ack;
 pub mod chan_open_confirm;
 pub mod chan_open_init;
 pub mod chan_open_try;
+pub mod chan_upgrade_ack;
+pub mod chan_upgrade_cancel;
+pub mod chan_upgrade_confirm;
+pub mod chan_upgrade_init;
+pub mod chan_upgrade_open;
+pub mod chan_upgrade_timeout;
+pub mod chan_upgrade_try;
 pub mod recv_packet;
+pub mod recv_packets;
 pub mod send_packet;
 pub mod timeout;
 pub mod timeout_on_close;
@@ -60,11 +71,105 @@ pub enum ChannelIdState {
 }
 
 #[derive(Clone, Debug)]
-pub struct ChannelResult {
+pub struct ChannelResult<C: ClientTypes> {
 	pub port_id: PortId,
 	pub channel_id: ChannelId,
 	pub channel_id_state: ChannelIdState,
 	pub channel_end: ChannelEnd,
+	/// Set by the upgrade handshake handlers, `None` leaves the upgrade state untouched.
+	pub upgrade: Option<ChannelUpgradeResult>,
+	/// The state of the client advanced by the proofs verified to process the message, stored
+	/// along with the channel end.
+	pub client_update: Option<UpdateResult<C>>,
+}
+
+/// The upgrade state of a channel end following an upgrade handshake message.
+#[derive(Clone, Debug, Default)]
+pub struct ChannelUpgradeResult {
+	/// The upgrade proposed by the channel end, `None` deletes it.
+	pub upgrade: Option<Upgrade>,
+	/// The upgrade proposed by the counterparty, `None` deletes it.
+	pub counterparty_upgrade: Option<Upgrade>,
+	/// Written when the upgrade is aborted.
+	pub error_receipt: Option<ErrorReceipt>,
+	/// Set when the upgrade changes the ordering of the channel end, packets are then received
+	/// from the first sequence the counterparty sent after its upgrade.
+	pub next_sequence_recv: Option<Sequence>,
+	/// Set when the upgrade changes the ordering of the channel end, packets are then
+	/// acknowledged from the first sequence sent after the upgrade.
+	pub next_sequence_ack: Option<Sequence>,
+	/// Set when the upgrade moves the channel end to another connection.
+	pub connection_id: Option<ConnectionId>,
+}
+
+impl ChannelUpgradeResult {
+	/// Aborts the upgrade in progress: the channel end goes back to `OPEN` with its current
+	/// parameters, both upgrades are deleted and an error receipt is written for the counterparty.
+	pub(crate) fn abort(channel_end: &mut ChannelEnd, message: String) -> Self {
+		channel_end.set_state(State::Open);
+		Self {
+			upgrade: None,
+			counterparty_upgrade: None,
+			error_receipt: Some(ErrorReceipt::new(channel_end.upgrade_sequence, message)),
+			..Default::default()
+		}
+	}
+
+	/// Applies the upgrade once both channel ends are done flushing: the channel end goes back to
+	/// `OPEN` with the upgraded parameters and both upgrades are deleted.
+	pub(crate) fn open(
+		channel_end: &mut ChannelEnd,
+		upgrade: Upgrade,
+		counterparty_upgrade: &Upgrade,
+	) -> Self {
+		let mut result = Self::default();
+		if channel_end.ordering != upgrade.fields.ordering {
+			result.next_sequence_recv = Some(counterparty_upgrade.next_sequence_send);
+			result.next_sequence_ack = Some(upgrade.next_sequence_send);
+		}
+		if channel_end.connection_hops != upgrade.fields.connection_hops {
+			result.connection_id = upgrade.fields.connection_hops.first().cloned();
+		}
+		channel_end.ordering = upgrade.fields.ordering;
+		channel_end.connection_hops = upgrade.fields.connection_hops;
+		channel_end.version = upgrade.fields.version;
+		channel_end.set_state(State::Open);
+		result
+	}
+}
+
+/// Moves the channel end to `FLUSHING`, recording in the upgrade the deadline for the
+/// counterparty to complete its side of the handshake and the first sequence sent after the
+/// upgrade.
+pub(crate) fn start_flushing<Ctx: ReaderContext>(
+	ctx: &Ctx,
+	port_channel_id: &(PortId, ChannelId),
+	channel_end: &mut ChannelEnd,
+	upgrade: &mut Upgrade,
+) -> Result<(), Error> {
+	let timeout_timestamp = (ctx.host_timestamp() + ctx.channel_upgrade_timeout())
+		.map_err(Error::upgrade_timeout_overflow)?;
+	upgrade.timeout = Some(UpgradeTimeout::new(Height::zero(), timeout_timestamp)?);
+	upgrade.next_sequence_send = ctx.get_next_sequence_send(port_channel_id)?;
+	channel_end.set_state(State::Flushing);
+	Ok(())
+}
+
+/// Attributes of the upgrade events emitted for the given channel end.
+pub(crate) fn upgrade_event_attributes<Ctx: ReaderContext>(
+	ctx: &Ctx,
+	port_id: &PortId,
+	channel_id: &ChannelId,
+	channel_end: &ChannelEnd,
+) -> UpgradeAttributes {
+	UpgradeAttributes {
+		height: ctx.host_height(),
+		port_id: port_id.clone(),
+		channel_id: *channel_id,
+		counterparty_port_id: channel_end.counterparty().port_id.clone(),
+		counterparty_channel_id: channel_end.counterparty().channel_id,
+		upgrade_sequence: channel_end.upgrade_sequence,
+	}
 }
 
 pub fn channel_validate<Ctx>(ctx: &Ctx, msg: &ChannelMsg) -> Result<ModuleId, Error>
@@ -84,7 +189,7 @@ where
 pub fn channel_dispatch<Ctx>(
 	ctx: &Ctx,
 	msg: &ChannelMsg,
-) -> Result<(HandlerOutputBuilder<()>, ChannelResult), Error>
+) -> Result<(HandlerOutputBuilder<()>, ChannelResult<Ctx>), Error>
 where
 	Ctx: ReaderContext,
 {
@@ -96,6 +201,13 @@ where
 		ChannelMsg::ChannelOpenConfirm(msg) => chan_open_confirm::process::<_>(ctx, msg),
 		ChannelMsg::ChannelCloseInit(msg) => chan_close_init::process(ctx, msg),
 		ChannelMsg::ChannelCloseConfirm(msg) => chan_close_confirm::process::<_>(ctx, msg),
+		ChannelMsg::ChannelUpgradeInit(msg) => chan_upgrade_init::process(ctx, msg),
+		ChannelMsg::ChannelUpgradeTry(msg) => chan_upgrade_try::process::<_>(ctx, msg),
+		ChannelMsg::ChannelUpgradeAck(msg) => chan_upgrade_ack::process::<_>(ctx, msg),
+		ChannelMsg::ChannelUpgradeConfirm(msg) => chan_upgrade_confirm::process::<_>(ctx, msg),
+		ChannelMsg::ChannelUpgradeOpen(msg) => chan_upgrade_open::process::<_>(ctx, msg),
+		ChannelMsg::ChannelUpgradeTimeout(msg) => chan_upgrade_timeout::process::<_>(ctx, msg),
+		ChannelMsg::ChannelUpgradeCancel(msg) => chan_upgrade_cancel::process::<_>(ctx, msg),
 	}?;
 	let HandlerOutput { result, log, events } = output;
 	let builder = HandlerOutput::builder().with_log(log).with_events(events);
@@ -106,9 +218,9 @@ pub fn channel_callback<Ctx>(
 	ctx: &mut Ctx,
 	module_id: &ModuleId,
 	msg: &ChannelMsg,
-	mut result: ChannelResult,
+	mut result: ChannelResult<Ctx>,
 	module_output: &mut ModuleOutputBuilder,
-) -> Result<ChannelResult, Error>
+) -> Result<ChannelResult<Ctx>, Error>
 where
 	Ctx: Ics26Context,
 {
@@ -172,10 +284,76 @@ where
 			&result.channel_id,
 			&msg.signer,
 		)?,
+		ChannelMsg::ChannelUpgradeInit(msg) => {
+			if let Some(upgrade) = proposed_upgrade(&mut result) {
+				upgrade.fields.version = cb.on_chan_upgrade_init(
+					&ctx_clone,
+					module_output,
+					&msg.port_id,
+					&msg.channel_id,
+					upgrade.fields.ordering,
+					&upgrade.fields.connection_hops,
+					&upgrade.fields.version,
+				)?;
+			}
+		},
+		// Nothing to negotiate if the upgrade was aborted.
+		ChannelMsg::ChannelUpgradeTry(msg) =>
+			if let Some(upgrade) = proposed_upgrade(&mut result) {
+				upgrade.fields.version = cb.on_chan_upgrade_try(
+					&ctx_clone,
+					module_output,
+					&msg.port_id,
+					&msg.channel_id,
+					upgrade.fields.ordering,
+					&upgrade.fields.connection_hops,
+					&msg.counterparty_upgrade_fields.version,
+				)?;
+			},
+		ChannelMsg::ChannelUpgradeAck(msg) =>
+			if proposed_upgrade(&mut result).is_some() {
+				cb.on_chan_upgrade_ack(
+					&ctx_clone,
+					module_output,
+					&msg.port_id,
+					&msg.channel_id,
+					&msg.counterparty_upgrade.fields.version,
+				)?
+			},
+		// The upgrade is complete if the channel end is open again without having been aborted.
+		ChannelMsg::ChannelUpgradeConfirm(msg) =>
+			if result.channel_end.is_open() &&
+				result.upgrade.as_ref().map_or(false, |upgrade| upgrade.error_receipt.is_none())
+			{
+				cb.on_chan_upgrade_open(
+					&ctx_clone,
+					module_output,
+					&msg.port_id,
+					&msg.channel_id,
+					result.channel_end.ordering,
+					&result.channel_end.connection_hops,
+					&result.channel_end.version,
+				)?
+			},
+		ChannelMsg::ChannelUpgradeOpen(msg) => cb.on_chan_upgrade_open(
+			&ctx_clone,
+			module_output,
+			&msg.port_id,
+			&msg.channel_id,
+			result.channel_end.ordering,
+			&result.channel_end.connection_hops,
+			&result.channel_end.version,
+		)?,
+		ChannelMsg::ChannelUpgradeTimeout(_) | ChannelMsg::ChannelUpgradeCancel(_) => {},
 	}
 	Ok(result)
 }
 
+/// The upgrade proposed by the channel end in the result of an upgrade handshake message.
+fn proposed_upgrade<C: ClientTypes>(result: &mut ChannelResult<C>) -> Option<&mut Upgrade> {
+	result.upgrade.as_mut().and_then(|upgrade| upgrade.upgrade.as_mut())
+}
+
 pub fn get_module_for_packet_msg<Ctx>(ctx: &Ctx, msg: &PacketMsg) -> Result<ModuleId, Error>
 where
 	Ctx: Ics26Context,
@@ -203,7 +381,7 @@ where
 pub fn packet_dispatch<Ctx>(
 	ctx: &Ctx,
 	msg: &PacketMsg,
-) -> Result<(HandlerOutputBuilder<()>, PacketResult), Error>
+) -> Result<(HandlerOutputBuilder<()>, PacketResult<Ctx>), Error>
 where
 	Ctx: ReaderContext,
 {
@@ -218,6 +396,20 @@ where
 	Ok((builder, result))
 }
 
+/// Dispatcher for a packet of a `MsgRecvPackets` batch, whose commitment was already proven by
+/// [`recv_packets::process`].
+pub fn batched_recv_packet_dispatch<Ctx>(
+	ctx: &Ctx,
+	msg: &MsgRecvPacket,
+) -> Result<(HandlerOutputBuilder<()>, PacketResult<Ctx>), Error>
+where
+	Ctx: ReaderContext,
+{
+	let HandlerOutput { result, log, events } = recv_packet::process_verified::<_>(ctx, msg)?;
+	let builder = HandlerOutput::builder().with_log(log).with_events(events);
+	Ok((builder, result))
+}
+
 pub fn packet_callback<Ctx>(
 	ctx: &mut Ctx,
 	module_id: &ModuleId,
@@ -237,17 +429,22 @@ where
 			let ack = cb
 				.on_recv_packet(&ctx_clone, module_output, &mut packet, &msg.signer)
 				.map_err(|e| Error::app_module(e.to_string()))?;
+			let key = (
+				msg.packet.destination_port.clone(),
+				msg.packet.destination_channel.clone(),
+				msg.packet.sequence,
+			);
+			let ack = match ack {
+				OnRecvPacketAck::Sync(ack) => ack,
+				// The module writes the acknowledgement once it is done processing the packet
+				OnRecvPacketAck::Async => return ctx.store_pending_acknowledgement(key),
+			};
 			if ack.as_ref().is_empty() {
 				return Err(Error::invalid_acknowledgement())
 			}
 			// NOTE: IBC app modules or middlewares might have written the acknowledgement
 			// synchronously on the OnRecvPacket callback so we only write the acknowledgement if it
 			// does not exist
-			let key = (
-				msg.packet.destination_port.clone(),
-				msg.packet.destination_channel.clone(),
-				msg.packet.sequence,
-			);
 			if let Err(_) = ctx.get_packet_acknowledgement(&key) {
 				let ack_commitment = ctx.ack_commitment(ack.clone());
 				ctx.store_raw_acknowledgement(key.clone(), ack.clone())?;
@@ -14,9 +14,10 @@
 
 use crate::{
 	core::{
+		ics02_client::{context::ClientTypes, handler::update_client::Result as UpdateResult},
 		ics03_connection::connection::State as ConnectionState,
 		ics04_channel::{
-			channel::{Counterparty, Order, State},
+			channel::{Counterparty, State},
 			error::Error,
 			events::AcknowledgePacket,
 			handler::verify::verify_packet_acknowledgement_proofs,
@@ -33,25 +34,29 @@ use crate::{
 use core::fmt::Debug;
 
 #[derive(Clone, Debug)]
-pub struct AckPacketResult {
+pub struct AckPacketResult<C: ClientTypes> {
 	pub port_id: PortId,
 	pub channel_id: ChannelId,
 	pub seq: Sequence,
 	pub seq_number: Option<Sequence>,
+	/// The state of the client advanced by the proof of the acknowledgement.
+	pub client_update: Option<UpdateResult<C>>,
 }
 
 pub fn process<Ctx: ReaderContext>(
 	ctx: &Ctx,
 	msg: &MsgAcknowledgement,
-) -> HandlerResult<PacketResult, Error> {
+) -> HandlerResult<PacketResult<Ctx>, Error> {
 	let mut output = HandlerOutput::builder();
+	let mut client_update = None;
 
 	let packet = &msg.packet;
 
 	let source_channel_end =
 		ctx.channel_end(&(packet.source_port.clone(), packet.source_channel))?;
 
-	if !source_channel_end.state_matches(&State::Open) {
+	// In-flight packets are still acknowledged and timed out while the channel is flushing.
+	if !source_channel_end.state_matches(&State::Open) && !source_channel_end.is_flushing() {
 		return Err(Error::channel_closed(packet.source_channel))
 	}
 
@@ -97,9 +102,10 @@ pub fn process<Ctx: ReaderContext>(
 		msg.acknowledgement.clone(),
 		&connection_end,
 		&msg.proofs,
+		&mut client_update,
 	)?;
 
-	let result = if source_channel_end.order_matches(&Order::Ordered) {
+	let result = if source_channel_end.ordering().is_ordered() {
 		let next_seq_ack =
 			ctx.get_next_sequence_ack(&(packet.source_port.clone(), packet.source_channel))?;
 
@@ -112,6 +118,7 @@ pub fn process<Ctx: ReaderContext>(
 			channel_id: packet.source_channel,
 			seq: packet.sequence,
 			seq_number: Some(next_seq_ack.increment()),
+			client_update,
 		})
 	} else {
 		PacketResult::Ack(AckPacketResult {
@@ -119,6 +126,7 @@ pub fn process<Ctx: ReaderContext>(
 			channel_id: packet.source_channel,
 			seq: packet.sequence,
 			seq_number: None,
+			client_update,
 		})
 	};
 
@@ -34,8 +34,9 @@ use crate::{
 pub(crate) fn process<Ctx: ReaderContext>(
 	ctx: &Ctx,
 	msg: &MsgChannelCloseConfirm,
-) -> HandlerResult<ChannelResult, Error> {
+) -> HandlerResult<ChannelResult<Ctx>, Error> {
 	let mut output = HandlerOutput::builder();
+	let mut client_update = None;
 
 	// Retrieve the old channel end and validate it against the message.
 	let mut channel_end = ctx.channel_end(&(msg.port_id.clone(), msg.channel_id))?;
@@ -85,6 +86,7 @@ pub(crate) fn process<Ctx: ReaderContext>(
 		&conn,
 		&expected_channel_end,
 		&msg.proofs.object_proof(),
+		&mut client_update,
 	)?;
 
 	output.log("success: channel close confirm ");
@@ -106,6 +108,8 @@ pub(crate) fn process<Ctx: ReaderContext>(
 		channel_id: msg.channel_id,
 		channel_id_state: ChannelIdState::Reused,
 		channel_end,
+		upgrade: None,
+		client_update,
 	};
 
 	output.emit(IbcEvent::CloseConfirmChannel(
@@ -130,7 +134,10 @@ mod tests {
 
 	use crate::{
 		core::{
-			ics02_client::context::ClientReader,
+			ics02_client::{
+				client_def::ConsensusUpdateResult, client_state::ClientState,
+				context::ClientReader, handler::update_client::Result as UpdateResult,
+			},
 			ics03_connection::{
 				connection::{
 					ConnectionEnd, Counterparty as ConnectionCounterparty, State as ConnectionState,
@@ -140,12 +147,16 @@ mod tests {
 			},
 			ics04_channel::{
 				channel::{ChannelEnd, Counterparty, Order, State as ChannelState},
+				context::{ChannelKeeper, ChannelReader},
 				handler::channel_dispatch,
 				Version,
 			},
 			ics24_host::identifier::{ClientId, ConnectionId},
 		},
-		mock::client_state::MockClientState,
+		mock::{
+			client_state::{MockClientState, MockConsensusState},
+			header::MockHeader,
+		},
 	};
 
 	use crate::{
@@ -206,4 +217,73 @@ mod tests {
 			assert_eq!(event.height(), context.host_height());
 		}
 	}
+
+	#[test]
+	fn chan_close_confirm_stores_client_update_with_result() {
+		let client_id = ClientId::new(&MockClientState::client_type(), 24).unwrap();
+		let conn_id = ConnectionId::new(2);
+		let default_context = MockContext::<MockClientTypes>::default();
+		let client_consensus_state_height = default_context.host_height();
+
+		let conn_end = ConnectionEnd::new(
+			ConnectionState::Open,
+			client_id.clone(),
+			ConnectionCounterparty::try_from(get_dummy_raw_counterparty()).unwrap(),
+			get_compatible_versions(),
+			ZERO_DURATION,
+		);
+
+		let msg_chan_close_confirm = MsgChannelCloseConfirm::try_from(
+			get_dummy_raw_msg_chan_close_confirm(client_consensus_state_height.revision_height),
+		)
+		.unwrap();
+
+		let chan_end = ChannelEnd::new(
+			ChannelState::Open,
+			Order::default(),
+			Counterparty::new(
+				msg_chan_close_confirm.port_id.clone(),
+				Some(msg_chan_close_confirm.channel_id),
+			),
+			vec![conn_id.clone()],
+			Version::default(),
+		);
+
+		let port_channel_id =
+			(msg_chan_close_confirm.port_id.clone(), msg_chan_close_confirm.channel_id);
+		let mut context = default_context
+			.with_client(&client_id, client_consensus_state_height)
+			.with_connection(conn_id, conn_end)
+			.with_channel(port_channel_id.0.clone(), port_channel_id.1, chan_end);
+
+		let (_, mut result) =
+			channel_dispatch(&context, &ChannelMsg::ChannelCloseConfirm(msg_chan_close_confirm))
+				.unwrap();
+		// Mock clients are not advanced by the proofs they verify.
+		assert!(result.client_update.is_none());
+
+		// A client advanced by the proof is only stored along with the channel end.
+		let height = client_consensus_state_height.increment();
+		result.client_update = Some(UpdateResult {
+			client_id: client_id.clone(),
+			client_state: MockClientState::new(MockHeader::new(height).into()).into(),
+			consensus_state: Some(ConsensusUpdateResult::Single(
+				MockConsensusState::new(MockHeader::new(height)).into(),
+			)),
+			processed_time: context.host_timestamp(),
+			processed_height: context.host_height(),
+		});
+		assert_eq!(
+			context.client_state(&client_id).unwrap().latest_height(),
+			client_consensus_state_height
+		);
+
+		context.store_channel_result(result).unwrap();
+		assert_eq!(context.client_state(&client_id).unwrap().latest_height(), height);
+		assert!(context.consensus_state(&client_id, height).is_ok());
+		assert!(context
+			.channel_end(&port_channel_id)
+			.unwrap()
+			.state_matches(&ChannelState::Closed));
+	}
 }
@@ -33,7 +33,7 @@ use crate::{
 pub(crate) fn process<Ctx: ReaderContext>(
 	ctx: &Ctx,
 	msg: &MsgChannelCloseInit,
-) -> HandlerResult<ChannelResult, Error> {
+) -> HandlerResult<ChannelResult<Ctx>, Error> {
 	let mut output = HandlerOutput::builder();
 
 	// Unwrap the old channel end and validate it against the message.
@@ -76,6 +76,8 @@ pub(crate) fn process<Ctx: ReaderContext>(
 		channel_id: msg.channel_id,
 		channel_id_state: ChannelIdState::Reused,
 		channel_end,
+		upgrade: None,
+		client_update: None,
 	};
 
 	output.emit(IbcEvent::CloseInitChannel(
@@ -34,8 +34,9 @@ use crate::{
 pub(crate) fn process<Ctx: ReaderContext>(
 	ctx: &Ctx,
 	msg: &MsgChannelOpenAck,
-) -> HandlerResult<ChannelResult, Error> {
+) -> HandlerResult<ChannelResult<Ctx>, Error> {
 	let mut output = HandlerOutput::builder();
+	let mut client_update = None;
 
 	// Unwrap the old channel end and validate it against the message.
 	let mut channel_end = ctx.channel_end(&(msg.port_id.clone(), msg.channel_id))?;
@@ -90,6 +91,7 @@ pub(crate) fn process<Ctx: ReaderContext>(
 		&conn,
 		&expected_channel_end,
 		&msg.proofs.object_proof(),
+		&mut client_update,
 	)?;
 
 	output.log("success: channel open ack ");
@@ -112,6 +114,8 @@ pub(crate) fn process<Ctx: ReaderContext>(
 		channel_id: msg.channel_id,
 		channel_id_state: ChannelIdState::Reused,
 		channel_end,
+		upgrade: None,
+		client_update,
 	};
 
 	output.emit(IbcEvent::OpenAckChannel(
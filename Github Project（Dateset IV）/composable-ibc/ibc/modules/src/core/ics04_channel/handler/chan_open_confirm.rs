@@ -34,11 +34,12 @@ use crate::{
 pub(crate) fn process<Ctx>(
 	ctx: &Ctx,
 	msg: &MsgChannelOpenConfirm,
-) -> HandlerResult<ChannelResult, Error>
+) -> HandlerResult<ChannelResult<Ctx>, Error>
 where
 	Ctx: ReaderContext,
 {
 	let mut output = HandlerOutput::builder();
+	let mut client_update = None;
 
 	// Unwrap the old channel end and validate it against the message.
 	let mut channel_end = ctx.channel_end(&(msg.port_id.clone(), msg.channel_id))?;
@@ -88,6 +89,7 @@ where
 		&conn,
 		&expected_channel_end,
 		&msg.proofs.object_proof(),
+		&mut client_update,
 	)
 	.map_err(Error::chan_open_confirm_proof_verification)?;
 
@@ -110,6 +112,8 @@ where
 		channel_id: msg.channel_id,
 		channel_id_state: ChannelIdState::Reused,
 		channel_end,
+		upgrade: None,
+		client_update,
 	};
 
 	output.emit(IbcEvent::OpenConfirmChannel(
@@ -34,7 +34,7 @@ use crate::{
 pub(crate) fn process<Ctx: ReaderContext>(
 	ctx: &Ctx,
 	msg: &MsgChannelOpenInit,
-) -> HandlerResult<ChannelResult, Error> {
+) -> HandlerResult<ChannelResult<Ctx>, Error> {
 	let mut output = HandlerOutput::builder();
 
 	if msg.channel.connection_hops().len() != 1 {
@@ -86,6 +86,8 @@ pub(crate) fn process<Ctx: ReaderContext>(
 		channel_id: chan_id,
 		channel_end: new_channel_end,
 		channel_id_state: ChannelIdState::Generated,
+		upgrade: None,
+		client_update: None,
 	};
 
 	output.emit(IbcEvent::OpenInitChannel(
@@ -35,11 +35,12 @@ use crate::{
 pub(crate) fn process<Ctx>(
 	ctx: &Ctx,
 	msg: &MsgChannelOpenTry,
-) -> HandlerResult<ChannelResult, Error>
+) -> HandlerResult<ChannelResult<Ctx>, Error>
 where
 	Ctx: ReaderContext,
 {
 	let mut output = HandlerOutput::builder();
+	let mut client_update = None;
 
 	// Unwrap the old channel end (if any) and validate it against the message.
 	let (mut new_channel_end, channel_id) = {
@@ -111,6 +112,7 @@ where
 		&conn,
 		&expected_channel_end,
 		&msg.proofs.object_proof(),
+		&mut client_update,
 	)?;
 
 	output.log("success: channel open try ");
@@ -132,6 +134,8 @@ where
 		channel_id_state: ChannelIdState::Generated,
 		channel_id,
 		channel_end: new_channel_end,
+		upgrade: None,
+		client_update,
 	};
 
 	output.emit(IbcEvent::OpenTryChannel(
@@ -0,0 +1,315 @@
+// Copyright 2022 ComposableFi
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Protocol logic specific to ICS4 messages of type `MsgChannelUpgradeAck`.
+
+use crate::{
+	core::{
+		ics03_connection::connection::State as ConnectionState,
+		ics04_channel::{
+			channel::{ChannelEnd, Counterparty, State},
+			error::Error,
+			events::{UpgradeAck, UpgradeError},
+			handler::{
+				start_flushing, upgrade_event_attributes,
+				verify::{verify_channel_proofs, verify_channel_upgrade_proofs},
+				ChannelIdState, ChannelResult, ChannelUpgradeResult,
+			},
+			msgs::chan_upgrade_ack::MsgChannelUpgradeAck,
+			upgrade::Upgrade,
+		},
+		ics26_routing::context::ReaderContext,
+	},
+	events::IbcEvent,
+	handler::{HandlerOutput, HandlerResult},
+	prelude::*,
+};
+
+pub(crate) fn process<Ctx: ReaderContext>(
+	ctx: &Ctx,
+	msg: &MsgChannelUpgradeAck,
+) -> HandlerResult<ChannelResult<Ctx>, Error> {
+	let mut output = HandlerOutput::builder();
+	let mut client_update = None;
+
+	let port_channel_id = (msg.port_id.clone(), msg.channel_id);
+	let mut channel_end = ctx.channel_end(&port_channel_id)?;
+
+	if !channel_end.state_matches(&State::Open) && !channel_end.state_matches(&State::Flushing) {
+		return Err(Error::invalid_channel_state(msg.channel_id, channel_end.state))
+	}
+
+	let mut upgrade = ctx
+		.channel_upgrade(&port_channel_id)
+		.map_err(|_| Error::missing_upgrade(msg.port_id.clone(), msg.channel_id))?;
+
+	if channel_end.connection_hops().len() != 1 {
+		return Err(Error::invalid_connection_hops_length(1, channel_end.connection_hops().len()))
+	}
+
+	let conn = ctx
+		.connection_end(&channel_end.connection_hops()[0])
+		.map_err(Error::ics03_connection)?;
+
+	if !conn.state_matches(&ConnectionState::Open) {
+		return Err(Error::connection_not_open(channel_end.connection_hops()[0].clone()))
+	}
+
+	// Proof verification in two steps:
+	// 1. Setup: build the Channel as we expect to find it on the other party.
+	let ccid = conn.counterparty().connection_id().ok_or_else(|| {
+		Error::undefined_connection_counterparty(channel_end.connection_hops()[0].clone())
+	})?;
+
+	let expected_channel_end = ChannelEnd::new(
+		State::Flushing,
+		*channel_end.ordering(),
+		Counterparty::new(msg.port_id.clone(), Some(msg.channel_id)),
+		vec![ccid.clone()],
+		channel_end.version().clone(),
+	)
+	.with_upgrade_sequence(channel_end.upgrade_sequence);
+
+	// 2. Verify proofs
+	verify_channel_proofs::<Ctx>(
+		ctx,
+		msg.proofs.height(),
+		&channel_end,
+		&conn,
+		&expected_channel_end,
+		&msg.proofs.object_proof(),
+		&mut client_update,
+	)?;
+
+	let upgrade_proof =
+		msg.proofs.other_proof().as_ref().ok_or_else(Error::missing_upgrade_proof)?;
+	verify_channel_upgrade_proofs::<Ctx>(
+		ctx,
+		msg.proofs.height(),
+		&channel_end,
+		&conn,
+		&msg.counterparty_upgrade,
+		upgrade_proof,
+		&mut client_update,
+	)?;
+
+	if channel_end.state_matches(&State::Open) {
+		start_flushing(ctx, &port_channel_id, &mut channel_end, &mut upgrade)?;
+	}
+
+	// Once the proofs are verified, an upgrade that can no longer complete is aborted rather than
+	// rejected, so that the counterparty can cancel it as well.
+	if let Err(e) = check_counterparty_upgrade(ctx, &upgrade, msg) {
+		let upgrade_result = ChannelUpgradeResult::abort(&mut channel_end, e.to_string());
+		output.log("success: channel upgrade ack aborted");
+
+		let event_attributes =
+			upgrade_event_attributes(ctx, &msg.port_id, &msg.channel_id, &channel_end);
+		output.emit(IbcEvent::UpgradeErrorChannel(UpgradeError(event_attributes)));
+
+		return Ok(output.with_result(ChannelResult {
+			port_id: msg.port_id.clone(),
+			channel_id: msg.channel_id,
+			channel_id_state: ChannelIdState::Reused,
+			channel_end,
+			upgrade: Some(upgrade_result),
+			client_update,
+		}))
+	}
+
+	// The version negotiated by the counterparty is validated by the module callback.
+	upgrade.fields.version = msg.counterparty_upgrade.fields.version.clone();
+
+	if !ctx.has_inflight_packets(&port_channel_id)? {
+		channel_end.set_state(State::FlushComplete);
+	}
+
+	output.log("success: channel upgrade ack");
+
+	let event_attributes =
+		upgrade_event_attributes(ctx, &msg.port_id, &msg.channel_id, &channel_end);
+
+	let result = ChannelResult {
+		port_id: msg.port_id.clone(),
+		channel_id: msg.channel_id,
+		channel_id_state: ChannelIdState::Reused,
+		channel_end,
+		upgrade: Some(ChannelUpgradeResult {
+			upgrade: Some(upgrade),
+			counterparty_upgrade: Some(msg.counterparty_upgrade.clone()),
+			error_receipt: None,
+			..Default::default()
+		}),
+		client_update,
+	};
+
+	output.emit(IbcEvent::UpgradeAckChannel(UpgradeAck(event_attributes)));
+
+	Ok(output.with_result(result))
+}
+
+/// Checks that the counterparty upgrade has not timed out and agrees with the proposed upgrade.
+fn check_counterparty_upgrade<Ctx: ReaderContext>(
+	ctx: &Ctx,
+	upgrade: &Upgrade,
+	msg: &MsgChannelUpgradeAck,
+) -> Result<(), Error> {
+	let counterparty_upgrade = &msg.counterparty_upgrade;
+
+	let timeout = counterparty_upgrade
+		.timeout
+		.as_ref()
+		.ok_or_else(Error::invalid_upgrade_timeout)?;
+	if timeout.has_passed(ctx.host_height(), &ctx.host_timestamp()) {
+		return Err(Error::incompatible_upgrade("counterparty upgrade timed out".to_string()))
+	}
+
+	if upgrade.fields.ordering != counterparty_upgrade.fields.ordering {
+		return Err(Error::incompatible_upgrade(format!(
+			"proposed ordering {} does not match the counterparty ordering {}",
+			upgrade.fields.ordering, counterparty_upgrade.fields.ordering
+		)))
+	}
+
+	// The counterparty must be upgrading to the other end of the proposed connection.
+	let proposed_conn = ctx
+		.connection_end(&upgrade.fields.connection_hops[0])
+		.map_err(Error::ics03_connection)?;
+	if proposed_conn.counterparty().connection_id() !=
+		counterparty_upgrade.fields.connection_hops.first()
+	{
+		return Err(Error::incompatible_upgrade(
+			"counterparty connection hops do not match the proposed connection".to_string(),
+		))
+	}
+
+	Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+	use test_log::test;
+
+	use crate::{
+		core::{
+			ics02_client::context::ClientReader,
+			ics03_connection::{
+				connection::{
+					ConnectionEnd, Counterparty as ConnectionCounterparty, State as ConnectionState,
+				},
+				msgs::test_util::get_dummy_raw_counterparty,
+				version::get_compatible_versions,
+			},
+			ics04_channel::{
+				channel::{ChannelEnd, Counterparty, Order, State},
+				handler::channel_dispatch,
+				msgs::{
+					chan_upgrade_ack::{
+						test_util::get_dummy_raw_msg_chan_upgrade_ack, MsgChannelUpgradeAck,
+					},
+					ChannelMsg,
+				},
+				packet::Sequence,
+				upgrade::{Upgrade, UpgradeTimeout},
+				Version,
+			},
+			ics24_host::identifier::{ChannelId, ClientId, ConnectionId, PortId},
+		},
+		events::IbcEvent,
+		mock::{
+			client_state::MockClientState,
+			context::{MockClientTypes, MockContext},
+		},
+		prelude::*,
+		timestamp::{Timestamp, ZERO_DURATION},
+		Height,
+	};
+
+	#[test]
+	fn chan_upgrade_ack_msg_processing() {
+		let port_id = PortId::default();
+		let channel_id = ChannelId::default();
+		let client_id = ClientId::new(&MockClientState::client_type(), 24).unwrap();
+		let conn_id = ConnectionId::default();
+		let context = MockContext::<MockClientTypes>::default();
+		let proof_height = context.host_height().revision_height;
+
+		let msg = MsgChannelUpgradeAck::try_from(get_dummy_raw_msg_chan_upgrade_ack(proof_height))
+			.unwrap();
+
+		let conn_end = ConnectionEnd::new(
+			ConnectionState::Open,
+			client_id.clone(),
+			ConnectionCounterparty::try_from(get_dummy_raw_counterparty()).unwrap(),
+			get_compatible_versions(),
+			ZERO_DURATION,
+		);
+		let channel_end = ChannelEnd::new(
+			State::Open,
+			Order::Unordered,
+			Counterparty::new(port_id.clone(), Some(channel_id)),
+			vec![conn_id.clone()],
+			Version::new("ics20-1".to_string()),
+		)
+		.with_upgrade_sequence(1);
+		let context = context
+			.with_client(&client_id, Height::new(0, proof_height))
+			.with_connection(conn_id, conn_end)
+			.with_send_sequence(port_id.clone(), channel_id, Sequence::from(3))
+			.with_channel(port_id.clone(), channel_id, channel_end.clone());
+
+		// There must be an upgrade proposed on this end.
+		assert!(channel_dispatch(&context, &ChannelMsg::ChannelUpgradeAck(msg.clone())).is_err());
+		let context = context.with_channel_upgrade(
+			port_id.clone(),
+			channel_id,
+			Upgrade::new(msg.counterparty_upgrade.fields.clone()),
+		);
+
+		// Without in-flight packets the channel end is done flushing right away.
+		let (output, result) =
+			channel_dispatch(&context, &ChannelMsg::ChannelUpgradeAck(msg.clone()))
+				.expect("upgrade ack should succeed");
+		assert!(result.channel_end.state_matches(&State::FlushComplete));
+		let upgrade_result = result.upgrade.unwrap();
+		let upgrade = upgrade_result.upgrade.unwrap();
+		assert_eq!(upgrade.next_sequence_send, Sequence::from(3));
+		assert!(upgrade.timeout.is_some());
+		assert_eq!(upgrade_result.counterparty_upgrade, Some(msg.counterparty_upgrade.clone()));
+		assert!(upgrade_result.error_receipt.is_none());
+		let events = output.with_result(()).events;
+		assert!(matches!(events.as_slice(), [IbcEvent::UpgradeAckChannel(_)]));
+
+		// A counterparty upgrade that timed out is aborted with an error receipt.
+		let mut timed_out = msg.clone();
+		timed_out.counterparty_upgrade.timeout =
+			Some(UpgradeTimeout::new(Height::new(0, 1), Timestamp::none()).unwrap());
+		let (output, result) =
+			channel_dispatch(&context, &ChannelMsg::ChannelUpgradeAck(timed_out))
+				.expect("timed out upgrade ack should be aborted");
+		assert!(result.channel_end.state_matches(&State::Open));
+		let upgrade_result = result.upgrade.unwrap();
+		assert!(upgrade_result.upgrade.is_none());
+		assert!(upgrade_result.counterparty_upgrade.is_none());
+		assert_eq!(upgrade_result.error_receipt.unwrap().sequence, 1);
+		let events = output.with_result(()).events;
+		assert!(matches!(events.as_slice(), [IbcEvent::UpgradeErrorChannel(_)]));
+
+		// A closed channel end cannot be upgraded.
+		let mut closed = channel_end;
+		closed.set_state(State::Closed);
+		let context = context.with_channel(port_id, channel_id, closed);
+		assert!(channel_dispatch(&context, &ChannelMsg::ChannelUpgradeAck(msg)).is_err());
+	}
+}
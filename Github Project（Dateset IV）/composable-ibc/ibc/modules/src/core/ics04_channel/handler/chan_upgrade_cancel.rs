@@ -0,0 +1,220 @@
+// Copyright 2022 ComposableFi
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Protocol logic specific to ICS4 messages of type `MsgChannelUpgradeCancel`.
+
+use crate::{
+	core::{
+		ics03_connection::connection::State as ConnectionState,
+		ics04_channel::{
+			error::Error,
+			events::{UpgradeCancel, UpgradeError},
+			handler::{
+				upgrade_event_attributes, verify::verify_channel_upgrade_error_proofs,
+				ChannelIdState, ChannelResult, ChannelUpgradeResult,
+			},
+			msgs::chan_upgrade_cancel::MsgChannelUpgradeCancel,
+		},
+		ics26_routing::context::ReaderContext,
+	},
+	events::IbcEvent,
+	handler::{HandlerOutput, HandlerResult},
+	prelude::*,
+};
+
+pub(crate) fn process<Ctx: ReaderContext>(
+	ctx: &Ctx,
+	msg: &MsgChannelUpgradeCancel,
+) -> HandlerResult<ChannelResult<Ctx>, Error> {
+	let mut output = HandlerOutput::builder();
+	let mut client_update = None;
+
+	let port_channel_id = (msg.port_id.clone(), msg.channel_id);
+	let mut channel_end = ctx.channel_end(&port_channel_id)?;
+
+	// There must be an upgrade in progress to cancel.
+	ctx.channel_upgrade(&port_channel_id)
+		.map_err(|_| Error::missing_upgrade(msg.port_id.clone(), msg.channel_id))?;
+
+	// The error receipt must refer to the current upgrade or a later one.
+	if msg.error_receipt.sequence < channel_end.upgrade_sequence {
+		return Err(Error::invalid_error_receipt_sequence(
+			msg.error_receipt.sequence,
+			channel_end.upgrade_sequence,
+		))
+	}
+
+	if channel_end.connection_hops().len() != 1 {
+		return Err(Error::invalid_connection_hops_length(1, channel_end.connection_hops().len()))
+	}
+
+	let conn = ctx
+		.connection_end(&channel_end.connection_hops()[0])
+		.map_err(Error::ics03_connection)?;
+
+	if !conn.state_matches(&ConnectionState::Open) {
+		return Err(Error::connection_not_open(channel_end.connection_hops()[0].clone()))
+	}
+
+	// The upgrade authority can cancel an upgrade the counterparty has not aborted.
+	match &msg.proofs {
+		Some(proofs) => verify_channel_upgrade_error_proofs::<Ctx>(
+			ctx,
+			proofs.height(),
+			&channel_end,
+			&conn,
+			&msg.error_receipt,
+			proofs.object_proof(),
+			&mut client_update,
+		)?,
+		None if ctx.is_upgrade_authority() => {},
+		None => return Err(Error::unauthorized_upgrade()),
+	}
+
+	output.log("success: channel upgrade cancel");
+
+	// Catch up with the counterparty so that a stale upgrade cannot be resumed.
+	channel_end.upgrade_sequence = msg.error_receipt.sequence;
+	let upgrade_result =
+		ChannelUpgradeResult::abort(&mut channel_end, msg.error_receipt.message.clone());
+
+	let event_attributes =
+		upgrade_event_attributes(ctx, &msg.port_id, &msg.channel_id, &channel_end);
+	output.emit(IbcEvent::UpgradeCancelChannel(UpgradeCancel(event_attributes.clone())));
+	output.emit(IbcEvent::UpgradeErrorChannel(UpgradeError(event_attributes)));
+
+	let result = ChannelResult {
+		port_id: msg.port_id.clone(),
+		channel_id: msg.channel_id,
+		channel_id_state: ChannelIdState::Reused,
+		channel_end,
+		upgrade: Some(upgrade_result),
+		client_update,
+	};
+
+	Ok(output.with_result(result))
+}
+
+#[cfg(test)]
+mod tests {
+	use test_log::test;
+
+	use crate::{
+		core::{
+			ics02_client::context::ClientReader,
+			ics03_connection::{
+				connection::{
+					ConnectionEnd, Counterparty as ConnectionCounterparty, State as ConnectionState,
+				},
+				msgs::test_util::get_dummy_raw_counterparty,
+				version::get_compatible_versions,
+			},
+			ics04_channel::{
+				channel::{ChannelEnd, Counterparty, Order, State},
+				handler::channel_dispatch,
+				msgs::{
+					chan_upgrade_cancel::{
+						test_util::get_dummy_raw_msg_chan_upgrade_cancel, MsgChannelUpgradeCancel,
+					},
+					ChannelMsg,
+				},
+				upgrade::{test_util::get_dummy_raw_upgrade_fields, Upgrade, UpgradeFields},
+				Version,
+			},
+			ics24_host::identifier::{ChannelId, ClientId, ConnectionId, PortId},
+		},
+		events::IbcEvent,
+		mock::{
+			client_state::MockClientState,
+			context::{MockClientTypes, MockContext},
+		},
+		prelude::*,
+		timestamp::ZERO_DURATION,
+		Height,
+	};
+
+	#[test]
+	fn chan_upgrade_cancel_msg_processing() {
+		let port_id = PortId::default();
+		let channel_id = ChannelId::default();
+		let client_id = ClientId::new(&MockClientState::client_type(), 24).unwrap();
+		let conn_id = ConnectionId::default();
+		let context = MockContext::<MockClientTypes>::default();
+		let proof_height = context.host_height().revision_height;
+
+		let msg = MsgChannelUpgradeCancel::try_from(get_dummy_raw_msg_chan_upgrade_cancel(
+			proof_height,
+			2,
+		))
+		.unwrap();
+
+		let conn_end = ConnectionEnd::new(
+			ConnectionState::Open,
+			client_id.clone(),
+			ConnectionCounterparty::try_from(get_dummy_raw_counterparty()).unwrap(),
+			get_compatible_versions(),
+			ZERO_DURATION,
+		);
+		let channel_end = ChannelEnd::new(
+			State::Open,
+			Order::Unordered,
+			Counterparty::new(port_id.clone(), Some(channel_id)),
+			vec![conn_id.clone()],
+			Version::new("ics20-1".to_string()),
+		)
+		.with_upgrade_sequence(1);
+		let context = context
+			.with_client(&client_id, Height::new(0, proof_height))
+			.with_connection(conn_id, conn_end)
+			.with_channel(port_id.clone(), channel_id, channel_end);
+
+		// There must be an upgrade in progress to cancel.
+		assert!(channel_dispatch(&context, &ChannelMsg::ChannelUpgradeCancel(msg.clone())).is_err());
+		let context = context.with_channel_upgrade(
+			port_id,
+			channel_id,
+			Upgrade::new(UpgradeFields::try_from(get_dummy_raw_upgrade_fields()).unwrap()),
+		);
+
+		// The counterparty error receipt aborts the upgrade and the channel end catches up with
+		// its upgrade sequence.
+		let (output, result) =
+			channel_dispatch(&context, &ChannelMsg::ChannelUpgradeCancel(msg.clone()))
+				.expect("upgrade cancel should succeed");
+		assert!(result.channel_end.state_matches(&State::Open));
+		assert_eq!(result.channel_end.upgrade_sequence, 2);
+		let upgrade_result = result.upgrade.unwrap();
+		assert!(upgrade_result.upgrade.is_none());
+		let error_receipt = upgrade_result.error_receipt.unwrap();
+		assert_eq!(error_receipt.sequence, 2);
+		assert_eq!(error_receipt.message, msg.error_receipt.message);
+		let events = output.with_result(()).events;
+		assert!(matches!(
+			events.as_slice(),
+			[IbcEvent::UpgradeCancelChannel(_), IbcEvent::UpgradeErrorChannel(_)]
+		));
+
+		// An error receipt of an earlier upgrade is rejected.
+		let mut stale = msg.clone();
+		stale.error_receipt.sequence = 0;
+		assert!(channel_dispatch(&context, &ChannelMsg::ChannelUpgradeCancel(stale)).is_err());
+
+		// Only the upgrade authority can cancel without the counterparty error receipt.
+		let unproven = MsgChannelUpgradeCancel { proofs: None, ..msg };
+		assert!(channel_dispatch(&context, &ChannelMsg::ChannelUpgradeCancel(unproven.clone()))
+			.is_err());
+		let context = context.with_upgrade_authority();
+		assert!(channel_dispatch(&context, &ChannelMsg::ChannelUpgradeCancel(unproven)).is_ok());
+	}
+}
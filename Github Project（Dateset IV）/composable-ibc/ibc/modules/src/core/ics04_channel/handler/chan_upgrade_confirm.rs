@@ -0,0 +1,300 @@
+// Copyright 2022 ComposableFi
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Protocol logic specific to ICS4 messages of type `MsgChannelUpgradeConfirm`.
+
+use crate::{
+	core::{
+		ics03_connection::connection::State as ConnectionState,
+		ics04_channel::{
+			channel::{ChannelEnd, Counterparty, State},
+			error::Error,
+			events::{UpgradeConfirm, UpgradeError, UpgradeOpen},
+			handler::{
+				upgrade_event_attributes,
+				verify::{verify_channel_proofs, verify_channel_upgrade_proofs},
+				ChannelIdState, ChannelResult, ChannelUpgradeResult,
+			},
+			msgs::chan_upgrade_confirm::MsgChannelUpgradeConfirm,
+		},
+		ics26_routing::context::ReaderContext,
+	},
+	events::IbcEvent,
+	handler::{HandlerOutput, HandlerResult},
+	prelude::*,
+};
+
+pub(crate) fn process<Ctx: ReaderContext>(
+	ctx: &Ctx,
+	msg: &MsgChannelUpgradeConfirm,
+) -> HandlerResult<ChannelResult<Ctx>, Error> {
+	let mut output = HandlerOutput::builder();
+	let mut client_update = None;
+
+	let port_channel_id = (msg.port_id.clone(), msg.channel_id);
+	let mut channel_end = ctx.channel_end(&port_channel_id)?;
+
+	if !channel_end.state_matches(&State::Flushing) {
+		return Err(Error::invalid_channel_state(msg.channel_id, channel_end.state))
+	}
+
+	let upgrade = ctx
+		.channel_upgrade(&port_channel_id)
+		.map_err(|_| Error::missing_upgrade(msg.port_id.clone(), msg.channel_id))?;
+
+	if channel_end.connection_hops().len() != 1 {
+		return Err(Error::invalid_connection_hops_length(1, channel_end.connection_hops().len()))
+	}
+
+	let conn = ctx
+		.connection_end(&channel_end.connection_hops()[0])
+		.map_err(Error::ics03_connection)?;
+
+	if !conn.state_matches(&ConnectionState::Open) {
+		return Err(Error::connection_not_open(channel_end.connection_hops()[0].clone()))
+	}
+
+	// Proof verification in two steps:
+	// 1. Setup: build the Channel as we expect to find it on the other party.
+	let ccid = conn.counterparty().connection_id().ok_or_else(|| {
+		Error::undefined_connection_counterparty(channel_end.connection_hops()[0].clone())
+	})?;
+
+	let expected_channel_end = ChannelEnd::new(
+		msg.counterparty_channel_state,
+		*channel_end.ordering(),
+		Counterparty::new(msg.port_id.clone(), Some(msg.channel_id)),
+		vec![ccid.clone()],
+		channel_end.version().clone(),
+	)
+	.with_upgrade_sequence(channel_end.upgrade_sequence);
+
+	// 2. Verify proofs
+	verify_channel_proofs::<Ctx>(
+		ctx,
+		msg.proofs.height(),
+		&channel_end,
+		&conn,
+		&expected_channel_end,
+		&msg.proofs.object_proof(),
+		&mut client_update,
+	)?;
+
+	let upgrade_proof =
+		msg.proofs.other_proof().as_ref().ok_or_else(Error::missing_upgrade_proof)?;
+	verify_channel_upgrade_proofs::<Ctx>(
+		ctx,
+		msg.proofs.height(),
+		&channel_end,
+		&conn,
+		&msg.counterparty_upgrade,
+		upgrade_proof,
+		&mut client_update,
+	)?;
+
+	let timed_out = msg
+		.counterparty_upgrade
+		.timeout
+		.as_ref()
+		.map(|timeout| timeout.has_passed(ctx.host_height(), &ctx.host_timestamp()))
+		.unwrap_or(true);
+	if timed_out {
+		let upgrade_result =
+			ChannelUpgradeResult::abort(&mut channel_end, "counterparty upgrade timed out".into());
+		output.log("success: channel upgrade confirm aborted");
+
+		let event_attributes =
+			upgrade_event_attributes(ctx, &msg.port_id, &msg.channel_id, &channel_end);
+		output.emit(IbcEvent::UpgradeErrorChannel(UpgradeError(event_attributes)));
+
+		return Ok(output.with_result(ChannelResult {
+			port_id: msg.port_id.clone(),
+			channel_id: msg.channel_id,
+			channel_id_state: ChannelIdState::Reused,
+			channel_end,
+			upgrade: Some(upgrade_result),
+			client_update,
+		}))
+	}
+
+	if !ctx.has_inflight_packets(&port_channel_id)? {
+		channel_end.set_state(State::FlushComplete);
+	}
+
+	output.log("success: channel upgrade confirm");
+
+	let event_attributes =
+		upgrade_event_attributes(ctx, &msg.port_id, &msg.channel_id, &channel_end);
+	output.emit(IbcEvent::UpgradeConfirmChannel(UpgradeConfirm(event_attributes)));
+
+	// Both ends are done flushing, the upgrade can be applied right away.
+	let upgrade_result = if channel_end.state_matches(&State::FlushComplete) &&
+		msg.counterparty_channel_state == State::FlushComplete
+	{
+		let upgrade_result =
+			ChannelUpgradeResult::open(&mut channel_end, upgrade, &msg.counterparty_upgrade);
+
+		let event_attributes =
+			upgrade_event_attributes(ctx, &msg.port_id, &msg.channel_id, &channel_end);
+		output.emit(IbcEvent::UpgradeOpenChannel(UpgradeOpen(event_attributes)));
+
+		upgrade_result
+	} else {
+		ChannelUpgradeResult {
+			upgrade: Some(upgrade),
+			counterparty_upgrade: Some(msg.counterparty_upgrade.clone()),
+			..Default::default()
+		}
+	};
+
+	let result = ChannelResult {
+		port_id: msg.port_id.clone(),
+		channel_id: msg.channel_id,
+		channel_id_state: ChannelIdState::Reused,
+		channel_end,
+		upgrade: Some(upgrade_result),
+		client_update,
+	};
+
+	Ok(output.with_result(result))
+}
+
+#[cfg(test)]
+mod tests {
+	use test_log::test;
+
+	use crate::{
+		core::{
+			ics02_client::context::ClientReader,
+			ics03_connection::{
+				connection::{
+					ConnectionEnd, Counterparty as ConnectionCounterparty, State as ConnectionState,
+				},
+				msgs::test_util::get_dummy_raw_counterparty,
+				version::get_compatible_versions,
+			},
+			ics04_channel::{
+				channel::{ChannelEnd, Counterparty, Order, State},
+				handler::channel_dispatch,
+				msgs::{
+					chan_upgrade_confirm::{
+						test_util::get_dummy_raw_msg_chan_upgrade_confirm, MsgChannelUpgradeConfirm,
+					},
+					ChannelMsg,
+				},
+				packet::Sequence,
+				upgrade::{Upgrade, UpgradeTimeout},
+				Version,
+			},
+			ics24_host::identifier::{ChannelId, ClientId, ConnectionId, PortId},
+		},
+		events::IbcEvent,
+		mock::{
+			client_state::MockClientState,
+			context::{MockClientTypes, MockContext},
+		},
+		prelude::*,
+		timestamp::{Timestamp, ZERO_DURATION},
+		Height,
+	};
+
+	#[test]
+	fn chan_upgrade_confirm_msg_processing() {
+		let port_id = PortId::default();
+		let channel_id = ChannelId::default();
+		let client_id = ClientId::new(&MockClientState::client_type(), 24).unwrap();
+		let conn_id = ConnectionId::default();
+		let context = MockContext::<MockClientTypes>::default();
+		let proof_height = context.host_height().revision_height;
+
+		let mut msg = MsgChannelUpgradeConfirm::try_from(get_dummy_raw_msg_chan_upgrade_confirm(
+			proof_height,
+		))
+		.unwrap();
+		msg.counterparty_upgrade.next_sequence_send = Sequence::from(7);
+		let upgrade =
+			Upgrade { next_sequence_send: Sequence::from(4), ..msg.counterparty_upgrade.clone() };
+
+		let conn_end = ConnectionEnd::new(
+			ConnectionState::Open,
+			client_id.clone(),
+			ConnectionCounterparty::try_from(get_dummy_raw_counterparty()).unwrap(),
+			get_compatible_versions(),
+			ZERO_DURATION,
+		);
+		let mut channel_end = ChannelEnd::new(
+			State::Flushing,
+			Order::Unordered,
+			Counterparty::new(port_id.clone(), Some(channel_id)),
+			vec![conn_id.clone()],
+			Version::new("ics20-1".to_string()),
+		)
+		.with_upgrade_sequence(1);
+		let context = context
+			.with_client(&client_id, Height::new(0, proof_height))
+			.with_connection(conn_id, conn_end)
+			.with_channel(port_id.clone(), channel_id, channel_end.clone())
+			.with_channel_upgrade(port_id.clone(), channel_id, upgrade.clone());
+
+		// Both ends are done flushing, the upgrade is applied and the ordering change restarts the
+		// packet sequences from the first ones sent after the upgrade.
+		let (output, result) =
+			channel_dispatch(&context, &ChannelMsg::ChannelUpgradeConfirm(msg.clone()))
+				.expect("upgrade confirm should succeed");
+		assert!(result.channel_end.state_matches(&State::Open));
+		assert_eq!(result.channel_end.ordering, Order::Ordered);
+		assert_eq!(result.channel_end.version, upgrade.fields.version);
+		let upgrade_result = result.upgrade.unwrap();
+		assert!(upgrade_result.upgrade.is_none());
+		assert!(upgrade_result.counterparty_upgrade.is_none());
+		assert_eq!(upgrade_result.next_sequence_recv, Some(Sequence::from(7)));
+		assert_eq!(upgrade_result.next_sequence_ack, Some(Sequence::from(4)));
+		assert!(upgrade_result.connection_id.is_none());
+		let events = output.with_result(()).events;
+		assert!(matches!(
+			events.as_slice(),
+			[IbcEvent::UpgradeConfirmChannel(_), IbcEvent::UpgradeOpenChannel(_)]
+		));
+
+		// The counterparty is still flushing, the upgrades are kept until it opens.
+		let flushing =
+			MsgChannelUpgradeConfirm { counterparty_channel_state: State::Flushing, ..msg.clone() };
+		let (_, result) = channel_dispatch(&context, &ChannelMsg::ChannelUpgradeConfirm(flushing))
+			.expect("upgrade confirm should succeed");
+		assert!(result.channel_end.state_matches(&State::FlushComplete));
+		let upgrade_result = result.upgrade.unwrap();
+		assert_eq!(upgrade_result.upgrade, Some(upgrade));
+		assert_eq!(upgrade_result.counterparty_upgrade, Some(msg.counterparty_upgrade.clone()));
+
+		// A counterparty upgrade that timed out is aborted with an error receipt.
+		let mut timed_out = msg.clone();
+		timed_out.counterparty_upgrade.timeout =
+			Some(UpgradeTimeout::new(Height::new(0, 1), Timestamp::none()).unwrap());
+		let (output, result) =
+			channel_dispatch(&context, &ChannelMsg::ChannelUpgradeConfirm(timed_out))
+				.expect("timed out upgrade confirm should be aborted");
+		assert!(result.channel_end.state_matches(&State::Open));
+		assert_eq!(result.channel_end.ordering, Order::Unordered);
+		let upgrade_result = result.upgrade.unwrap();
+		assert!(upgrade_result.upgrade.is_none());
+		assert_eq!(upgrade_result.error_receipt.unwrap().sequence, 1);
+		let events = output.with_result(()).events;
+		assert!(matches!(events.as_slice(), [IbcEvent::UpgradeErrorChannel(_)]));
+
+		// Only a flushing channel end can confirm an upgrade.
+		channel_end.set_state(State::Open);
+		let context = context.with_channel(port_id, channel_id, channel_end);
+		assert!(channel_dispatch(&context, &ChannelMsg::ChannelUpgradeConfirm(msg)).is_err());
+	}
+}
@@ -0,0 +1,194 @@
+// Copyright 2022 ComposableFi
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Protocol logic specific to ICS4 messages of type `MsgChannelUpgradeInit`.
+
+use crate::{
+	core::{
+		ics03_connection::connection::State as ConnectionState,
+		ics04_channel::{
+			channel::State,
+			error::Error,
+			events::UpgradeInit,
+			handler::{
+				upgrade_event_attributes, ChannelIdState, ChannelResult, ChannelUpgradeResult,
+			},
+			msgs::chan_upgrade_init::MsgChannelUpgradeInit,
+			upgrade::{ErrorReceipt, Upgrade},
+		},
+		ics26_routing::context::ReaderContext,
+	},
+	events::IbcEvent,
+	handler::{HandlerOutput, HandlerResult},
+	prelude::*,
+};
+
+pub(crate) fn process<Ctx: ReaderContext>(
+	ctx: &Ctx,
+	msg: &MsgChannelUpgradeInit,
+) -> HandlerResult<ChannelResult<Ctx>, Error> {
+	let mut output = HandlerOutput::builder();
+
+	// Upgrades are proposed by governance rather than by any relayer.
+	if !ctx.is_upgrade_authority() {
+		return Err(Error::unauthorized_upgrade())
+	}
+
+	let port_channel_id = (msg.port_id.clone(), msg.channel_id);
+	let mut channel_end = ctx.channel_end(&port_channel_id)?;
+
+	// Only an open channel can be upgraded.
+	if !channel_end.state_matches(&State::Open) {
+		return Err(Error::invalid_channel_state(msg.channel_id, channel_end.state))
+	}
+
+	if msg.fields.matches_channel(&channel_end) {
+		return Err(Error::upgrade_fields_unchanged())
+	}
+
+	// The connection the channel is upgraded to must be open.
+	let conn = ctx
+		.connection_end(&msg.fields.connection_hops[0])
+		.map_err(Error::ics03_connection)?;
+	if !conn.state_matches(&ConnectionState::Open) {
+		return Err(Error::connection_not_open(msg.fields.connection_hops[0].clone()))
+	}
+
+	// A new proposal supersedes the one in progress, which the counterparty has to cancel.
+	let error_receipt = ctx
+		.channel_upgrade(&port_channel_id)
+		.ok()
+		.map(|_| ErrorReceipt::new(channel_end.upgrade_sequence, "upgrade superseded".to_string()));
+
+	channel_end.upgrade_sequence += 1;
+
+	output.log("success: channel upgrade init");
+
+	let event_attributes =
+		upgrade_event_attributes(ctx, &msg.port_id, &msg.channel_id, &channel_end);
+
+	let result = ChannelResult {
+		port_id: msg.port_id.clone(),
+		channel_id: msg.channel_id,
+		channel_id_state: ChannelIdState::Reused,
+		channel_end,
+		upgrade: Some(ChannelUpgradeResult {
+			upgrade: Some(Upgrade::new(msg.fields.clone())),
+			counterparty_upgrade: None,
+			error_receipt,
+			..Default::default()
+		}),
+		client_update: None,
+	};
+
+	output.emit(IbcEvent::UpgradeInitChannel(UpgradeInit(event_attributes)));
+
+	Ok(output.with_result(result))
+}
+
+#[cfg(test)]
+mod tests {
+	use core::str::FromStr;
+
+	use test_log::test;
+
+	use crate::{
+		core::{
+			ics03_connection::{
+				connection::{ConnectionEnd, State as ConnectionState},
+				msgs::conn_open_init::{
+					test_util::get_dummy_raw_msg_conn_open_init, MsgConnectionOpenInit,
+				},
+				version::get_compatible_versions,
+			},
+			ics04_channel::{
+				channel::{ChannelEnd, Counterparty, Order, State},
+				handler::channel_dispatch,
+				msgs::{
+					chan_upgrade_init::{
+						test_util::get_dummy_raw_msg_chan_upgrade_init, MsgChannelUpgradeInit,
+					},
+					ChannelMsg,
+				},
+				upgrade::UpgradeFields,
+				Version,
+			},
+			ics24_host::identifier::{ChannelId, ConnectionId, PortId},
+		},
+		events::IbcEvent,
+		mock::context::{MockClientTypes, MockContext},
+		prelude::*,
+	};
+
+	#[test]
+	fn chan_upgrade_init_msg_processing() {
+		let msg = MsgChannelUpgradeInit::try_from(get_dummy_raw_msg_chan_upgrade_init()).unwrap();
+		let port_id = PortId::default();
+		let channel_id = ChannelId::default();
+		let conn_id = ConnectionId::new(2);
+
+		let msg_conn_init =
+			MsgConnectionOpenInit::try_from(get_dummy_raw_msg_conn_open_init()).unwrap();
+		let conn_end = ConnectionEnd::new(
+			ConnectionState::Open,
+			msg_conn_init.client_id.clone(),
+			msg_conn_init.counterparty.clone(),
+			get_compatible_versions(),
+			msg_conn_init.delay_period,
+		);
+
+		let channel_end = ChannelEnd::new(
+			State::Open,
+			Order::Unordered,
+			Counterparty::new(PortId::default(), Some(ChannelId::from_str("channel-1").unwrap())),
+			vec![conn_id.clone()],
+			Version::new("ics20-1".to_string()),
+		);
+
+		let context = MockContext::<MockClientTypes>::default()
+			.with_connection(conn_id.clone(), conn_end.clone())
+			.with_connection(msg.fields.connection_hops[0].clone(), conn_end)
+			.with_channel(port_id.clone(), channel_id, channel_end.clone());
+
+		// Only the upgrade authority can propose an upgrade.
+		assert!(channel_dispatch(&context, &ChannelMsg::ChannelUpgradeInit(msg.clone())).is_err());
+		let context = context.with_upgrade_authority();
+
+		let res = channel_dispatch(&context, &ChannelMsg::ChannelUpgradeInit(msg.clone()));
+		let (output, result) = res.expect("upgrade init should succeed");
+		assert_eq!(result.channel_end.upgrade_sequence, 1);
+		assert!(result.channel_end.state_matches(&State::Open));
+		let upgrade = result.upgrade.and_then(|upgrade| upgrade.upgrade).unwrap();
+		assert_eq!(upgrade.fields, msg.fields);
+		let events = output.with_result(()).events;
+		assert!(matches!(events.as_slice(), [IbcEvent::UpgradeInitChannel(_)]));
+
+		// Proposing the current channel parameters is rejected.
+		let unchanged = MsgChannelUpgradeInit {
+			fields: UpgradeFields::new(
+				Order::Unordered,
+				vec![conn_id],
+				Version::new("ics20-1".to_string()),
+			),
+			..msg.clone()
+		};
+		assert!(channel_dispatch(&context, &ChannelMsg::ChannelUpgradeInit(unchanged)).is_err());
+
+		// A closed channel cannot be upgraded.
+		let mut closed = channel_end;
+		closed.set_state(State::Closed);
+		let context = context.with_channel(port_id, channel_id, closed);
+		assert!(channel_dispatch(&context, &ChannelMsg::ChannelUpgradeInit(msg)).is_err());
+	}
+}
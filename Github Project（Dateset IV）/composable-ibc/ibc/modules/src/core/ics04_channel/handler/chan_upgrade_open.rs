@@ -0,0 +1,277 @@
+// Copyright 2022 ComposableFi
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Protocol logic specific to ICS4 messages of type `MsgChannelUpgradeOpen`.
+
+use crate::{
+	core::{
+		ics03_connection::connection::State as ConnectionState,
+		ics04_channel::{
+			channel::{ChannelEnd, Counterparty, State},
+			error::Error,
+			events::UpgradeOpen,
+			handler::{
+				upgrade_event_attributes, verify::verify_channel_proofs, ChannelIdState,
+				ChannelResult, ChannelUpgradeResult,
+			},
+			msgs::chan_upgrade_open::MsgChannelUpgradeOpen,
+		},
+		ics26_routing::context::ReaderContext,
+	},
+	events::IbcEvent,
+	handler::{HandlerOutput, HandlerResult},
+	prelude::*,
+};
+
+pub(crate) fn process<Ctx: ReaderContext>(
+	ctx: &Ctx,
+	msg: &MsgChannelUpgradeOpen,
+) -> HandlerResult<ChannelResult<Ctx>, Error> {
+	let mut output = HandlerOutput::builder();
+	let mut client_update = None;
+
+	let port_channel_id = (msg.port_id.clone(), msg.channel_id);
+	let mut channel_end = ctx.channel_end(&port_channel_id)?;
+
+	if !channel_end.state_matches(&State::FlushComplete) {
+		return Err(Error::invalid_channel_state(msg.channel_id, channel_end.state))
+	}
+
+	let upgrade = ctx
+		.channel_upgrade(&port_channel_id)
+		.map_err(|_| Error::missing_upgrade(msg.port_id.clone(), msg.channel_id))?;
+
+	if channel_end.connection_hops().len() != 1 {
+		return Err(Error::invalid_connection_hops_length(1, channel_end.connection_hops().len()))
+	}
+
+	let conn = ctx
+		.connection_end(&channel_end.connection_hops()[0])
+		.map_err(Error::ics03_connection)?;
+
+	if !conn.state_matches(&ConnectionState::Open) {
+		return Err(Error::connection_not_open(channel_end.connection_hops()[0].clone()))
+	}
+
+	// Proof verification in two steps:
+	// 1. Setup: build the Channel as we expect to find it on the other party.
+	let expected_counterparty = Counterparty::new(msg.port_id.clone(), Some(msg.channel_id));
+	let expected_channel_end = match msg.counterparty_channel_state {
+		// The counterparty has already opened the upgraded channel.
+		State::Open => {
+			if msg.counterparty_upgrade_sequence < channel_end.upgrade_sequence {
+				return Err(Error::invalid_upgrade_sequence(
+					channel_end.upgrade_sequence,
+					msg.counterparty_upgrade_sequence,
+				))
+			}
+			let upgrade_conn = ctx
+				.connection_end(&upgrade.fields.connection_hops[0])
+				.map_err(Error::ics03_connection)?;
+			let ccid = upgrade_conn.counterparty().connection_id().ok_or_else(|| {
+				Error::undefined_connection_counterparty(upgrade.fields.connection_hops[0].clone())
+			})?;
+			ChannelEnd::new(
+				State::Open,
+				upgrade.fields.ordering,
+				expected_counterparty,
+				vec![ccid.clone()],
+				upgrade.fields.version.clone(),
+			)
+		},
+		_ => {
+			let ccid = conn.counterparty().connection_id().ok_or_else(|| {
+				Error::undefined_connection_counterparty(channel_end.connection_hops()[0].clone())
+			})?;
+			ChannelEnd::new(
+				State::FlushComplete,
+				*channel_end.ordering(),
+				expected_counterparty,
+				vec![ccid.clone()],
+				channel_end.version().clone(),
+			)
+		},
+	}
+	.with_upgrade_sequence(msg.counterparty_upgrade_sequence);
+
+	// 2. Verify proofs
+	verify_channel_proofs::<Ctx>(
+		ctx,
+		msg.proofs.height(),
+		&channel_end,
+		&conn,
+		&expected_channel_end,
+		&msg.proofs.object_proof(),
+		&mut client_update,
+	)?;
+
+	output.log("success: channel upgrade open");
+
+	let counterparty_upgrade = ctx
+		.counterparty_channel_upgrade(&port_channel_id)
+		.map_err(|_| Error::missing_upgrade(msg.port_id.clone(), msg.channel_id))?;
+	let upgrade_result =
+		ChannelUpgradeResult::open(&mut channel_end, upgrade, &counterparty_upgrade);
+
+	let event_attributes =
+		upgrade_event_attributes(ctx, &msg.port_id, &msg.channel_id, &channel_end);
+
+	let result = ChannelResult {
+		port_id: msg.port_id.clone(),
+		channel_id: msg.channel_id,
+		channel_id_state: ChannelIdState::Reused,
+		channel_end,
+		upgrade: Some(upgrade_result),
+		client_update,
+	};
+
+	output.emit(IbcEvent::UpgradeOpenChannel(UpgradeOpen(event_attributes)));
+
+	Ok(output.with_result(result))
+}
+
+#[cfg(test)]
+mod tests {
+	use test_log::test;
+
+	use crate::{
+		core::{
+			ics02_client::context::ClientReader,
+			ics03_connection::{
+				connection::{
+					ConnectionEnd, Counterparty as ConnectionCounterparty, State as ConnectionState,
+				},
+				msgs::test_util::get_dummy_raw_counterparty,
+				version::get_compatible_versions,
+			},
+			ics04_channel::{
+				channel::{ChannelEnd, Counterparty, Order, State},
+				context::{ChannelKeeper, ChannelReader},
+				handler::channel_dispatch,
+				msgs::{
+					chan_upgrade_open::{
+						test_util::get_dummy_raw_msg_chan_upgrade_open, MsgChannelUpgradeOpen,
+					},
+					ChannelMsg,
+				},
+				packet::Sequence,
+				upgrade::{test_util::get_dummy_raw_upgrade, Upgrade},
+				Version,
+			},
+			ics24_host::identifier::{ChannelId, ClientId, ConnectionId, PortId},
+		},
+		events::IbcEvent,
+		mock::{
+			client_state::MockClientState,
+			context::{MockClientTypes, MockContext},
+		},
+		prelude::*,
+		timestamp::ZERO_DURATION,
+		Height,
+	};
+
+	#[test]
+	fn chan_upgrade_open_msg_processing() {
+		let port_id = PortId::default();
+		let channel_id = ChannelId::default();
+		let port_channel_id = (port_id.clone(), channel_id);
+		let client_id = ClientId::new(&MockClientState::client_type(), 24).unwrap();
+		let conn_id = ConnectionId::default();
+		let context = MockContext::<MockClientTypes>::default();
+		let proof_height = context.host_height().revision_height;
+
+		let msg =
+			MsgChannelUpgradeOpen::try_from(get_dummy_raw_msg_chan_upgrade_open(proof_height))
+				.unwrap();
+		let upgrade = Upgrade {
+			next_sequence_send: Sequence::from(4),
+			..Upgrade::try_from(get_dummy_raw_upgrade()).unwrap()
+		};
+		let counterparty_upgrade =
+			Upgrade { next_sequence_send: Sequence::from(7), ..upgrade.clone() };
+
+		let conn_end = ConnectionEnd::new(
+			ConnectionState::Open,
+			client_id.clone(),
+			ConnectionCounterparty::try_from(get_dummy_raw_counterparty()).unwrap(),
+			get_compatible_versions(),
+			ZERO_DURATION,
+		);
+		let mut channel_end = ChannelEnd::new(
+			State::FlushComplete,
+			Order::Unordered,
+			Counterparty::new(port_id.clone(), Some(channel_id)),
+			vec![conn_id.clone()],
+			Version::new("ics20-1".to_string()),
+		)
+		.with_upgrade_sequence(1);
+		let context = context
+			.with_client(&client_id, Height::new(0, proof_height))
+			.with_connection(conn_id.clone(), conn_end.clone())
+			.with_connection(ConnectionId::new(1), conn_end)
+			.with_send_sequence(port_id.clone(), channel_id, Sequence::from(4))
+			.with_recv_sequence(port_id.clone(), channel_id, Sequence::from(2))
+			.with_ack_sequence(port_id.clone(), channel_id, Sequence::from(2))
+			.with_channel(port_id.clone(), channel_id, channel_end.clone())
+			.with_channel_upgrade(port_id.clone(), channel_id, upgrade.clone());
+
+		// The counterparty upgrade must be known before the upgrade can be applied.
+		assert!(channel_dispatch(&context, &ChannelMsg::ChannelUpgradeOpen(msg.clone())).is_err());
+		let context = context.with_counterparty_channel_upgrade(
+			port_id.clone(),
+			channel_id,
+			counterparty_upgrade,
+		);
+
+		// The upgrade is applied and the ordering change restarts the packet sequences from the
+		// first ones sent after the upgrade.
+		let (output, result) =
+			channel_dispatch(&context, &ChannelMsg::ChannelUpgradeOpen(msg.clone()))
+				.expect("upgrade open should succeed");
+		assert!(result.channel_end.state_matches(&State::Open));
+		assert_eq!(result.channel_end.ordering, Order::Ordered);
+		assert_eq!(result.channel_end.version, upgrade.fields.version);
+		let events = output.with_result(()).events;
+		assert!(matches!(events.as_slice(), [IbcEvent::UpgradeOpenChannel(_)]));
+
+		let mut ctx = context.clone();
+		ctx.store_channel_result(result).unwrap();
+		assert_eq!(ctx.get_next_sequence_recv(&port_channel_id).unwrap(), Sequence::from(7));
+		assert_eq!(ctx.get_next_sequence_ack(&port_channel_id).unwrap(), Sequence::from(4));
+		assert_eq!(ctx.get_next_sequence_send(&port_channel_id).unwrap(), Sequence::from(4));
+		assert!(ctx.channel_upgrade(&port_channel_id).is_err());
+		assert!(ctx.counterparty_channel_upgrade(&port_channel_id).is_err());
+
+		// Moving the channel end to another connection associates it to that connection.
+		let mut moved = upgrade.clone();
+		moved.fields.connection_hops = vec![ConnectionId::new(1)];
+		moved.fields.ordering = Order::Unordered;
+		let mut ctx = context.clone().with_channel_upgrade(port_id.clone(), channel_id, moved);
+		let (_, result) = channel_dispatch(&ctx, &ChannelMsg::ChannelUpgradeOpen(msg.clone()))
+			.expect("upgrade open should succeed");
+		assert_eq!(result.channel_end.connection_hops, vec![ConnectionId::new(1)]);
+		ctx.store_channel_result(result).unwrap();
+		assert_eq!(
+			ctx.connection_channels(&ConnectionId::new(1)).unwrap(),
+			vec![port_channel_id.clone()]
+		);
+		// The ordering did not change, the packet sequences are kept.
+		assert_eq!(ctx.get_next_sequence_recv(&port_channel_id).unwrap(), Sequence::from(2));
+
+		// Only a channel end that is done flushing can be opened.
+		channel_end.set_state(State::Flushing);
+		let context = context.with_channel(port_id, channel_id, channel_end);
+		assert!(channel_dispatch(&context, &ChannelMsg::ChannelUpgradeOpen(msg)).is_err());
+	}
+}
@@ -0,0 +1,234 @@
+// Copyright 2022 ComposableFi
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Protocol logic specific to ICS4 messages of type `MsgChannelUpgradeTimeout`.
+
+use crate::{
+	core::{
+		ics03_connection::connection::State as ConnectionState,
+		ics04_channel::{
+			channel::State,
+			error::Error,
+			events::{UpgradeError, UpgradeTimeout},
+			handler::{
+				upgrade_event_attributes, verify::verify_channel_proofs, ChannelIdState,
+				ChannelResult, ChannelUpgradeResult,
+			},
+			msgs::chan_upgrade_timeout::MsgChannelUpgradeTimeout,
+		},
+		ics26_routing::context::ReaderContext,
+	},
+	events::IbcEvent,
+	handler::{HandlerOutput, HandlerResult},
+	prelude::*,
+};
+
+pub(crate) fn process<Ctx: ReaderContext>(
+	ctx: &Ctx,
+	msg: &MsgChannelUpgradeTimeout,
+) -> HandlerResult<ChannelResult<Ctx>, Error> {
+	let mut output = HandlerOutput::builder();
+	let mut client_update = None;
+
+	let port_channel_id = (msg.port_id.clone(), msg.channel_id);
+	let mut channel_end = ctx.channel_end(&port_channel_id)?;
+
+	if !channel_end.is_flushing() {
+		return Err(Error::invalid_channel_state(msg.channel_id, channel_end.state))
+	}
+
+	let upgrade = ctx
+		.channel_upgrade(&port_channel_id)
+		.map_err(|_| Error::missing_upgrade(msg.port_id.clone(), msg.channel_id))?;
+
+	if channel_end.connection_hops().len() != 1 {
+		return Err(Error::invalid_connection_hops_length(1, channel_end.connection_hops().len()))
+	}
+
+	let conn = ctx
+		.connection_end(&channel_end.connection_hops()[0])
+		.map_err(Error::ics03_connection)?;
+
+	if !conn.state_matches(&ConnectionState::Open) {
+		return Err(Error::connection_not_open(channel_end.connection_hops()[0].clone()))
+	}
+
+	// The counterparty must have failed to complete the handshake before the upgrade timeout,
+	// which is checked against its own clock at the proof height.
+	let proof_height = msg.proofs.height();
+	let consensus_state = ctx
+		.consensus_state(conn.client_id(), proof_height)
+		.map_err(|_| Error::error_invalid_consensus_state())?;
+	let timeout = upgrade.timeout.as_ref().ok_or_else(Error::invalid_upgrade_timeout)?;
+	if !timeout.has_passed(proof_height, &consensus_state.timestamp()) {
+		return Err(Error::upgrade_timeout_not_reached())
+	}
+
+	let counterparty_channel = &msg.counterparty_channel;
+	if counterparty_channel.state_matches(&State::FlushComplete) ||
+		(counterparty_channel.state_matches(&State::Open) &&
+			counterparty_channel.upgrade_sequence == channel_end.upgrade_sequence)
+	{
+		return Err(Error::upgrade_already_completed())
+	}
+
+	verify_channel_proofs::<Ctx>(
+		ctx,
+		proof_height,
+		&channel_end,
+		&conn,
+		counterparty_channel,
+		&msg.proofs.object_proof(),
+		&mut client_update,
+	)?;
+
+	output.log("success: channel upgrade timeout");
+
+	let upgrade_result =
+		ChannelUpgradeResult::abort(&mut channel_end, "upgrade timed out".to_string());
+
+	let event_attributes =
+		upgrade_event_attributes(ctx, &msg.port_id, &msg.channel_id, &channel_end);
+	output.emit(IbcEvent::UpgradeTimeoutChannel(UpgradeTimeout(event_attributes.clone())));
+	output.emit(IbcEvent::UpgradeErrorChannel(UpgradeError(event_attributes)));
+
+	let result = ChannelResult {
+		port_id: msg.port_id.clone(),
+		channel_id: msg.channel_id,
+		channel_id_state: ChannelIdState::Reused,
+		channel_end,
+		upgrade: Some(upgrade_result),
+		client_update,
+	};
+
+	Ok(output.with_result(result))
+}
+
+#[cfg(test)]
+mod tests {
+	use test_log::test;
+
+	use crate::{
+		core::{
+			ics02_client::context::ClientReader,
+			ics03_connection::{
+				connection::{
+					ConnectionEnd, Counterparty as ConnectionCounterparty, State as ConnectionState,
+				},
+				msgs::test_util::get_dummy_raw_counterparty,
+				version::get_compatible_versions,
+			},
+			ics04_channel::{
+				channel::{ChannelEnd, Counterparty, Order, State},
+				handler::channel_dispatch,
+				msgs::{
+					chan_upgrade_timeout::{
+						test_util::get_dummy_raw_msg_chan_upgrade_timeout, MsgChannelUpgradeTimeout,
+					},
+					ChannelMsg,
+				},
+				upgrade::{test_util::get_dummy_raw_upgrade, Upgrade, UpgradeTimeout},
+				Version,
+			},
+			ics24_host::identifier::{ChannelId, ClientId, ConnectionId, PortId},
+		},
+		events::IbcEvent,
+		mock::{
+			client_state::MockClientState,
+			context::{MockClientTypes, MockContext},
+		},
+		prelude::*,
+		timestamp::{Timestamp, ZERO_DURATION},
+		Height,
+	};
+
+	#[test]
+	fn chan_upgrade_timeout_msg_processing() {
+		let port_id = PortId::default();
+		let channel_id = ChannelId::default();
+		let client_id = ClientId::new(&MockClientState::client_type(), 24).unwrap();
+		let conn_id = ConnectionId::default();
+		let context = MockContext::<MockClientTypes>::default();
+		let proof_height = context.host_height().revision_height;
+
+		let msg = MsgChannelUpgradeTimeout::try_from(get_dummy_raw_msg_chan_upgrade_timeout(
+			proof_height,
+		))
+		.unwrap();
+		let upgrade = Upgrade {
+			timeout: Some(UpgradeTimeout::new(Height::new(0, 1), Timestamp::none()).unwrap()),
+			..Upgrade::try_from(get_dummy_raw_upgrade()).unwrap()
+		};
+
+		let conn_end = ConnectionEnd::new(
+			ConnectionState::Open,
+			client_id.clone(),
+			ConnectionCounterparty::try_from(get_dummy_raw_counterparty()).unwrap(),
+			get_compatible_versions(),
+			ZERO_DURATION,
+		);
+		let mut channel_end = ChannelEnd::new(
+			State::Flushing,
+			Order::Unordered,
+			Counterparty::new(port_id.clone(), Some(channel_id)),
+			vec![conn_id.clone()],
+			Version::new("ics20-1".to_string()),
+		)
+		.with_upgrade_sequence(1);
+		let context = context
+			.with_client(&client_id, Height::new(0, proof_height))
+			.with_connection(conn_id, conn_end)
+			.with_channel(port_id.clone(), channel_id, channel_end.clone())
+			.with_channel_upgrade(port_id.clone(), channel_id, upgrade.clone());
+
+		// The counterparty did not complete the handshake in time, the upgrade is aborted with an
+		// error receipt.
+		let (output, result) =
+			channel_dispatch(&context, &ChannelMsg::ChannelUpgradeTimeout(msg.clone()))
+				.expect("upgrade timeout should succeed");
+		assert!(result.channel_end.state_matches(&State::Open));
+		assert_eq!(result.channel_end.ordering, Order::Unordered);
+		let upgrade_result = result.upgrade.unwrap();
+		assert!(upgrade_result.upgrade.is_none());
+		assert!(upgrade_result.counterparty_upgrade.is_none());
+		assert_eq!(upgrade_result.error_receipt.unwrap().sequence, 1);
+		let events = output.with_result(()).events;
+		assert!(matches!(
+			events.as_slice(),
+			[IbcEvent::UpgradeTimeoutChannel(_), IbcEvent::UpgradeErrorChannel(_)]
+		));
+
+		// The upgrade cannot be timed out before its timeout.
+		let pending = Upgrade {
+			timeout: Some(
+				UpgradeTimeout::new(Height::new(0, proof_height + 1), Timestamp::none()).unwrap(),
+			),
+			..upgrade
+		};
+		let pending = context.clone().with_channel_upgrade(port_id.clone(), channel_id, pending);
+		assert!(
+			channel_dispatch(&pending, &ChannelMsg::ChannelUpgradeTimeout(msg.clone())).is_err()
+		);
+
+		// Nor once the counterparty is done flushing.
+		let mut completed = msg.clone();
+		completed.counterparty_channel.set_state(State::FlushComplete);
+		assert!(channel_dispatch(&context, &ChannelMsg::ChannelUpgradeTimeout(completed)).is_err());
+
+		// Only a flushing channel end can time out an upgrade.
+		channel_end.set_state(State::Open);
+		let context = context.with_channel(port_id, channel_id, channel_end);
+		assert!(channel_dispatch(&context, &ChannelMsg::ChannelUpgradeTimeout(msg)).is_err());
+	}
+}
@@ -0,0 +1,300 @@
+// Copyright 2022 ComposableFi
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Protocol logic specific to ICS4 messages of type `MsgChannelUpgradeTry`.
+
+use crate::{
+	core::{
+		ics03_connection::connection::State as ConnectionState,
+		ics04_channel::{
+			channel::{ChannelEnd, Counterparty, State},
+			error::Error,
+			events::{UpgradeError, UpgradeTry},
+			handler::{
+				start_flushing, upgrade_event_attributes,
+				verify::{verify_channel_proofs, verify_channel_upgrade_proofs},
+				ChannelIdState, ChannelResult, ChannelUpgradeResult,
+			},
+			msgs::chan_upgrade_try::MsgChannelUpgradeTry,
+			upgrade::Upgrade,
+		},
+		ics26_routing::context::ReaderContext,
+	},
+	events::IbcEvent,
+	handler::{HandlerOutput, HandlerResult},
+	prelude::*,
+};
+
+pub(crate) fn process<Ctx: ReaderContext>(
+	ctx: &Ctx,
+	msg: &MsgChannelUpgradeTry,
+) -> HandlerResult<ChannelResult<Ctx>, Error> {
+	let mut output = HandlerOutput::builder();
+	let mut client_update = None;
+
+	let port_channel_id = (msg.port_id.clone(), msg.channel_id);
+	let mut channel_end = ctx.channel_end(&port_channel_id)?;
+
+	if !channel_end.state_matches(&State::Open) {
+		return Err(Error::invalid_channel_state(msg.channel_id, channel_end.state))
+	}
+
+	if channel_end.connection_hops().len() != 1 {
+		return Err(Error::invalid_connection_hops_length(1, channel_end.connection_hops().len()))
+	}
+
+	let conn = ctx
+		.connection_end(&channel_end.connection_hops()[0])
+		.map_err(Error::ics03_connection)?;
+
+	if !conn.state_matches(&ConnectionState::Open) {
+		return Err(Error::connection_not_open(channel_end.connection_hops()[0].clone()))
+	}
+
+	// Reuse the upgrade proposed on this end if both ends initialised an upgrade, otherwise
+	// propose the counterparty upgrade on the given connection.
+	let mut upgrade = match ctx.channel_upgrade(&port_channel_id) {
+		Ok(upgrade) => {
+			if upgrade.fields.connection_hops != msg.proposed_upgrade_connection_hops {
+				return Err(Error::incompatible_upgrade(
+					"proposed connection hops do not match the upgrade in progress".to_string(),
+				))
+			}
+			upgrade
+		},
+		Err(_) => {
+			let mut fields = msg.counterparty_upgrade_fields.clone();
+			fields.connection_hops = msg.proposed_upgrade_connection_hops.clone();
+			if fields.matches_channel(&channel_end) {
+				return Err(Error::upgrade_fields_unchanged())
+			}
+			Upgrade::new(fields)
+		},
+	};
+
+	let proposed_conn = ctx
+		.connection_end(&upgrade.fields.connection_hops[0])
+		.map_err(Error::ics03_connection)?;
+	if !proposed_conn.state_matches(&ConnectionState::Open) {
+		return Err(Error::connection_not_open(upgrade.fields.connection_hops[0].clone()))
+	}
+
+	// Proof verification in two steps:
+	// 1. Setup: build the Channel as we expect to find it on the other party.
+	let ccid = conn.counterparty().connection_id().ok_or_else(|| {
+		Error::undefined_connection_counterparty(channel_end.connection_hops()[0].clone())
+	})?;
+
+	let expected_channel_end = ChannelEnd::new(
+		State::Open,
+		*channel_end.ordering(),
+		Counterparty::new(msg.port_id.clone(), Some(msg.channel_id)),
+		vec![ccid.clone()],
+		channel_end.version().clone(),
+	)
+	.with_upgrade_sequence(msg.counterparty_upgrade_sequence);
+
+	// The counterparty upgrade has not started flushing yet.
+	let expected_upgrade = Upgrade::new(msg.counterparty_upgrade_fields.clone());
+
+	// 2. Verify proofs
+	verify_channel_proofs::<Ctx>(
+		ctx,
+		msg.proofs.height(),
+		&channel_end,
+		&conn,
+		&expected_channel_end,
+		&msg.proofs.object_proof(),
+		&mut client_update,
+	)?;
+
+	let upgrade_proof =
+		msg.proofs.other_proof().as_ref().ok_or_else(Error::missing_upgrade_proof)?;
+	verify_channel_upgrade_proofs::<Ctx>(
+		ctx,
+		msg.proofs.height(),
+		&channel_end,
+		&conn,
+		&expected_upgrade,
+		upgrade_proof,
+		&mut client_update,
+	)?;
+
+	// The upgrade sequence of the counterparty is only allowed to catch up with ours, a stale
+	// proposal is aborted so that the counterparty cancels it.
+	if msg.counterparty_upgrade_sequence < channel_end.upgrade_sequence {
+		let message = format!(
+			"counterparty upgrade sequence {} is lower than the channel upgrade sequence {}",
+			msg.counterparty_upgrade_sequence, channel_end.upgrade_sequence
+		);
+		let upgrade_result = ChannelUpgradeResult::abort(&mut channel_end, message);
+		output.log("success: channel upgrade try aborted");
+
+		let event_attributes =
+			upgrade_event_attributes(ctx, &msg.port_id, &msg.channel_id, &channel_end);
+		output.emit(IbcEvent::UpgradeErrorChannel(UpgradeError(event_attributes)));
+
+		return Ok(output.with_result(ChannelResult {
+			port_id: msg.port_id.clone(),
+			channel_id: msg.channel_id,
+			channel_id_state: ChannelIdState::Reused,
+			channel_end,
+			upgrade: Some(upgrade_result),
+			client_update,
+		}))
+	}
+	channel_end.upgrade_sequence = msg.counterparty_upgrade_sequence;
+
+	if upgrade.fields.ordering != msg.counterparty_upgrade_fields.ordering {
+		return Err(Error::incompatible_upgrade(format!(
+			"proposed ordering {} does not match the counterparty ordering {}",
+			upgrade.fields.ordering, msg.counterparty_upgrade_fields.ordering
+		)))
+	}
+
+	start_flushing(ctx, &port_channel_id, &mut channel_end, &mut upgrade)?;
+
+	output.log("success: channel upgrade try");
+
+	let event_attributes =
+		upgrade_event_attributes(ctx, &msg.port_id, &msg.channel_id, &channel_end);
+
+	let result = ChannelResult {
+		port_id: msg.port_id.clone(),
+		channel_id: msg.channel_id,
+		channel_id_state: ChannelIdState::Reused,
+		channel_end,
+		upgrade: Some(ChannelUpgradeResult {
+			upgrade: Some(upgrade),
+			counterparty_upgrade: None,
+			error_receipt: None,
+			..Default::default()
+		}),
+		client_update,
+	};
+
+	output.emit(IbcEvent::UpgradeTryChannel(UpgradeTry(event_attributes)));
+
+	Ok(output.with_result(result))
+}
+
+#[cfg(test)]
+mod tests {
+	use test_log::test;
+
+	use crate::{
+		core::{
+			ics02_client::context::ClientReader,
+			ics03_connection::{
+				connection::{
+					ConnectionEnd, Counterparty as ConnectionCounterparty, State as ConnectionState,
+				},
+				msgs::test_util::get_dummy_raw_counterparty,
+				version::get_compatible_versions,
+			},
+			ics04_channel::{
+				channel::{ChannelEnd, Counterparty, Order, State},
+				handler::channel_dispatch,
+				msgs::{
+					chan_upgrade_try::{
+						test_util::get_dummy_raw_msg_chan_upgrade_try, MsgChannelUpgradeTry,
+					},
+					ChannelMsg,
+				},
+				packet::Sequence,
+				Version,
+			},
+			ics24_host::identifier::{ChannelId, ClientId, ConnectionId, PortId},
+		},
+		events::IbcEvent,
+		mock::{
+			client_state::MockClientState,
+			context::{MockClientTypes, MockContext},
+		},
+		prelude::*,
+		timestamp::ZERO_DURATION,
+		Height,
+	};
+
+	#[test]
+	fn chan_upgrade_try_msg_processing() {
+		let port_id = PortId::default();
+		let channel_id = ChannelId::default();
+		let client_id = ClientId::new(&MockClientState::client_type(), 24).unwrap();
+		let conn_id = ConnectionId::default();
+		let context = MockContext::<MockClientTypes>::default();
+		let proof_height = context.host_height().revision_height;
+
+		let conn_end = ConnectionEnd::new(
+			ConnectionState::Open,
+			client_id.clone(),
+			ConnectionCounterparty::try_from(get_dummy_raw_counterparty()).unwrap(),
+			get_compatible_versions(),
+			ZERO_DURATION,
+		);
+		let channel_end = ChannelEnd::new(
+			State::Open,
+			Order::Unordered,
+			Counterparty::new(port_id.clone(), Some(channel_id)),
+			vec![conn_id.clone()],
+			Version::new("ics20-1".to_string()),
+		);
+		let context = context
+			.with_client(&client_id, Height::new(0, proof_height))
+			.with_connection(conn_id, conn_end)
+			.with_send_sequence(port_id.clone(), channel_id, Sequence::from(3))
+			.with_channel(port_id.clone(), channel_id, channel_end.clone());
+
+		let msg =
+			MsgChannelUpgradeTry::try_from(get_dummy_raw_msg_chan_upgrade_try(proof_height, 1))
+				.unwrap();
+
+		// The counterparty proposal is accepted and the channel end starts flushing.
+		let (output, result) =
+			channel_dispatch(&context, &ChannelMsg::ChannelUpgradeTry(msg.clone()))
+				.expect("upgrade try should succeed");
+		assert!(result.channel_end.state_matches(&State::Flushing));
+		assert_eq!(result.channel_end.upgrade_sequence, 1);
+		let upgrade_result = result.upgrade.unwrap();
+		let upgrade = upgrade_result.upgrade.unwrap();
+		assert_eq!(upgrade.fields, msg.counterparty_upgrade_fields);
+		assert_eq!(upgrade.next_sequence_send, Sequence::from(3));
+		assert!(upgrade.timeout.is_some());
+		assert!(upgrade_result.error_receipt.is_none());
+		let events = output.with_result(()).events;
+		assert!(matches!(events.as_slice(), [IbcEvent::UpgradeTryChannel(_)]));
+
+		// A proposal older than the channel upgrade sequence is aborted with an error receipt.
+		let stale = context.clone().with_channel(
+			port_id.clone(),
+			channel_id,
+			channel_end.clone().with_upgrade_sequence(2),
+		);
+		let (output, result) =
+			channel_dispatch(&stale, &ChannelMsg::ChannelUpgradeTry(msg.clone()))
+				.expect("stale upgrade try should be aborted");
+		assert!(result.channel_end.state_matches(&State::Open));
+		let upgrade_result = result.upgrade.unwrap();
+		assert!(upgrade_result.upgrade.is_none());
+		assert_eq!(upgrade_result.error_receipt.unwrap().sequence, 2);
+		let events = output.with_result(()).events;
+		assert!(matches!(events.as_slice(), [IbcEvent::UpgradeErrorChannel(_)]));
+
+		// Only an open channel end can accept an upgrade.
+		let mut flushing = channel_end;
+		flushing.set_state(State::Flushing);
+		let context = context.with_channel(port_id, channel_id, flushing);
+		assert!(channel_dispatch(&context, &ChannelMsg::ChannelUpgradeTry(msg)).is_err());
+	}
+}
@@ -14,9 +14,10 @@
 
 use crate::{
 	core::{
-		ics03_connection::connection::State as ConnectionState,
+		ics02_client::{context::ClientTypes, handler::update_client::Result as UpdateResult},
+		ics03_connection::connection::{ConnectionEnd, State as ConnectionState},
 		ics04_channel::{
-			channel::{Counterparty, Order, State},
+			channel::{ChannelEnd, Counterparty, Order, State},
 			error::Error,
 			events::ReceivePacket,
 			handler::verify::verify_packet_recv_proofs,
@@ -34,8 +35,10 @@ use crate::{
 };
 use core::fmt::Debug;
 
+/// The `client_update` of a result is the state of the client advanced by the proof of the packet,
+/// which an already received packet leaves untouched like the rest of the store.
 #[derive(Clone, Debug)]
-pub enum RecvPacketResult {
+pub enum RecvPacketResult<C: ClientTypes> {
 	NoOp,
 	Unordered {
 		port_id: PortId,
@@ -43,27 +46,77 @@ pub enum RecvPacketResult {
 		sequence: Sequence,
 		receipt: Receipt,
 		packet: Packet,
+		client_update: Option<UpdateResult<C>>,
 	},
 	Ordered {
 		port_id: PortId,
 		channel_id: ChannelId,
 		next_seq_recv: Sequence,
 		packet: Packet,
+		client_update: Option<UpdateResult<C>>,
+	},
+	/// A timed out packet skipped on an `ORDERED_ALLOW_TIMEOUT` channel.
+	Timeout {
+		port_id: PortId,
+		channel_id: ChannelId,
+		sequence: Sequence,
+		next_seq_recv: Sequence,
+		client_update: Option<UpdateResult<C>>,
 	},
 }
 
 pub fn process<Ctx: ReaderContext>(
 	ctx: &Ctx,
 	msg: &MsgRecvPacket,
-) -> HandlerResult<PacketResult, Error> {
-	let mut output = HandlerOutput::builder();
-
+) -> HandlerResult<PacketResult<Ctx>, Error> {
 	let packet = &msg.packet;
 
+	let (dest_channel_end, connection_end, timed_out) = validate(ctx, packet)?;
+
+	let mut client_update = None;
+	verify_packet_recv_proofs::<Ctx>(
+		ctx,
+		msg.proofs.height(),
+		packet,
+		&connection_end,
+		&msg.proofs,
+		&mut client_update,
+	)?;
+
+	receive(ctx, packet, &dest_channel_end, timed_out, client_update)
+}
+
+/// Processes a packet whose commitment was already proven by the multi-key proof of a
+/// `MsgRecvPackets` batch, see [`super::recv_packets::process`].
+pub(crate) fn process_verified<Ctx: ReaderContext>(
+	ctx: &Ctx,
+	msg: &MsgRecvPacket,
+) -> HandlerResult<PacketResult<Ctx>, Error> {
+	let (dest_channel_end, _, timed_out) = validate(ctx, &msg.packet)?;
+
+	receive(ctx, &msg.packet, &dest_channel_end, timed_out, None)
+}
+
+/// Checks that `packet` can be received on its destination channel. Returns the channel and
+/// connection ends, and whether the packet timed out on an `ORDERED_ALLOW_TIMEOUT` channel.
+pub(crate) fn validate<Ctx: ReaderContext>(
+	ctx: &Ctx,
+	packet: &Packet,
+) -> Result<(ChannelEnd, ConnectionEnd, bool), Error> {
 	let dest_channel_end =
 		ctx.channel_end(&(packet.destination_port.clone(), packet.destination_channel))?;
 
-	if !dest_channel_end.state_matches(&State::Open) {
+	// While the channel is upgrading, only the packets sent by the counterparty before it started
+	// flushing are still received.
+	let can_receive = dest_channel_end.state_matches(&State::Open) ||
+		(dest_channel_end.is_flushing() &&
+			ctx.counterparty_channel_upgrade(&(
+				packet.destination_port.clone(),
+				packet.destination_channel,
+			))
+			.map(|upgrade| packet.sequence < upgrade.next_sequence_send)
+			.unwrap_or(false));
+	if !can_receive {
 		return Err(Error::invalid_channel_state(packet.source_channel, dest_channel_end.state))
 	}
 
@@ -85,24 +138,35 @@ pub fn process<Ctx: ReaderContext>(
 	}
 
 	let latest_height = ctx.host_height();
-	if (!packet.timeout_height.is_zero()) && (packet.timeout_height <= latest_height) {
+	let height_timed_out =
+		(!packet.timeout_height.is_zero()) && (packet.timeout_height <= latest_height);
+	let latest_timestamp = ctx.host_timestamp();
+	let timestamp_timed_out =
+		matches!(latest_timestamp.check_expiry(&packet.timeout_timestamp), Expiry::Expired);
+
+	// Timed out packets are only processed on channels that skip them instead of closing.
+	let allow_timeout = dest_channel_end.order_matches(&Order::OrderedAllowTimeout);
+	if height_timed_out && !allow_timeout {
 		return Err(Error::low_packet_height(latest_height, packet.timeout_height))
 	}
 
-	let latest_timestamp = ctx.host_timestamp();
-	if let Expiry::Expired = latest_timestamp.check_expiry(&packet.timeout_timestamp) {
+	if timestamp_timed_out && !allow_timeout {
 		return Err(Error::low_packet_timestamp())
 	}
 
-	verify_packet_recv_proofs::<Ctx>(
-		ctx,
-		msg.proofs.height(),
-		packet,
-		&connection_end,
-		&msg.proofs,
-	)?;
+	Ok((dest_channel_end, connection_end, height_timed_out || timestamp_timed_out))
+}
+
+fn receive<Ctx: ReaderContext>(
+	ctx: &Ctx,
+	packet: &Packet,
+	dest_channel_end: &ChannelEnd,
+	timed_out: bool,
+	client_update: Option<UpdateResult<Ctx>>,
+) -> HandlerResult<PacketResult<Ctx>, Error> {
+	let mut output = HandlerOutput::builder();
 
-	let result = if dest_channel_end.order_matches(&Order::Ordered) {
+	let result = if dest_channel_end.ordering().is_ordered() {
 		let next_seq_recv = ctx.get_next_sequence_recv(&(
 			packet.destination_port.clone(),
 			packet.destination_channel,
@@ -111,18 +175,31 @@ pub fn process<Ctx: ReaderContext>(
 		if packet.sequence < next_seq_recv {
 			output.emit(IbcEvent::ReceivePacket(ReceivePacket {
 				height: Height::zero(),
-				packet: msg.packet.clone(),
+				packet: packet.clone(),
 			}));
 			return Ok(output.with_result(PacketResult::Recv(RecvPacketResult::NoOp)))
 		} else if packet.sequence != next_seq_recv {
 			return Err(Error::invalid_packet_sequence(packet.sequence, next_seq_recv))
 		}
 
+		if timed_out {
+			// Skip the packet and write a timeout receipt, which the sender proves to time it out.
+			output.log("success: packet timed out");
+			return Ok(output.with_result(PacketResult::Recv(RecvPacketResult::Timeout {
+				port_id: packet.destination_port.clone(),
+				channel_id: packet.destination_channel,
+				sequence: packet.sequence,
+				next_seq_recv: next_seq_recv.increment(),
+				client_update,
+			})))
+		}
+
 		PacketResult::Recv(RecvPacketResult::Ordered {
 			port_id: packet.destination_port.clone(),
 			channel_id: packet.destination_channel,
 			next_seq_recv: next_seq_recv.increment(),
 			packet: packet.clone(),
+			client_update,
 		})
 	} else {
 		let packet_rec = ctx.get_packet_receipt(&(
@@ -135,7 +212,7 @@ pub fn process<Ctx: ReaderContext>(
 			Ok(_receipt) => {
 				output.emit(IbcEvent::ReceivePacket(ReceivePacket {
 					height: Height::zero(),
-					packet: msg.packet.clone(),
+					packet: packet.clone(),
 				}));
 				return Ok(output.with_result(PacketResult::Recv(RecvPacketResult::NoOp)))
 			},
@@ -147,6 +224,7 @@ pub fn process<Ctx: ReaderContext>(
 					sequence: packet.sequence,
 					receipt: Receipt::Ok,
 					packet: packet.clone(),
+					client_update,
 				})
 			},
 			Err(e) => return Err(Error::implementation_specific(e.to_string())),
@@ -157,7 +235,7 @@ pub fn process<Ctx: ReaderContext>(
 
 	output.emit(IbcEvent::ReceivePacket(ReceivePacket {
 		height: ctx.host_height(),
-		packet: msg.packet.clone(),
+		packet: packet.clone(),
 	}));
 
 	Ok(output.with_result(result))
@@ -180,9 +258,9 @@ mod tests {
 			},
 			ics04_channel::{
 				channel::{ChannelEnd, Counterparty, Order, State},
-				handler::recv_packet::process,
+				handler::recv_packet::{process, RecvPacketResult},
 				msgs::recv_packet::{test_util::get_dummy_raw_msg_recv_packet, MsgRecvPacket},
-				packet::Packet,
+				packet::{Packet, PacketResult},
 				Version,
 			},
 			ics24_host::identifier::{ChannelId, ClientId, ConnectionId, PortId},
@@ -329,4 +407,78 @@ mod tests {
 			}
 		}
 	}
+
+	#[test]
+	fn recv_timed_out_packet_on_ordered_allow_timeout_channel() {
+		let context = MockContext::default();
+		let host_height = context.latest_height().increment();
+		let client_height = host_height.increment();
+
+		let msg =
+			MsgRecvPacket::try_from(get_dummy_raw_msg_recv_packet(client_height.revision_height))
+				.unwrap();
+		let packet = Packet {
+			sequence: 1.into(),
+			timeout_height: client_height,
+			timeout_timestamp: Timestamp::from_nanoseconds(1).unwrap(),
+			..msg.packet.clone()
+		};
+		let msg = MsgRecvPacket::new(packet.clone(), msg.proofs, get_dummy_account_id());
+
+		let dest_channel_end = ChannelEnd::new(
+			State::Open,
+			Order::OrderedAllowTimeout,
+			Counterparty::new(packet.source_port.clone(), Some(packet.source_channel)),
+			vec![ConnectionId::default()],
+			Version::ics20(),
+		);
+
+		let connection_end = ConnectionEnd::new(
+			ConnectionState::Open,
+			ClientId::default(),
+			ConnectionCounterparty::new(
+				ClientId::default(),
+				Some(ConnectionId::default()),
+				Default::default(),
+			),
+			get_compatible_versions(),
+			ZERO_DURATION,
+		);
+
+		let ctx = context
+			.with_client(&ClientId::default(), client_height)
+			.with_connection(ConnectionId::default(), connection_end)
+			.with_channel(
+				packet.destination_port.clone(),
+				packet.destination_channel,
+				dest_channel_end.clone(),
+			)
+			.with_height(host_height)
+			.with_recv_sequence(
+				packet.destination_port.clone(),
+				packet.destination_channel,
+				packet.sequence,
+			);
+
+		// The timed out packet is skipped instead of being rejected.
+		let output = process(&ctx, &msg).unwrap();
+		assert!(output.events.is_empty());
+		match output.result {
+			PacketResult::Recv(RecvPacketResult::Timeout { sequence, next_seq_recv, .. }) => {
+				assert_eq!(sequence, packet.sequence);
+				assert_eq!(next_seq_recv, packet.sequence.increment());
+			},
+			result => panic!("unexpected result: {result:?}"),
+		}
+
+		// The same packet is rejected on a plain ordered channel.
+		let mut ordered_channel_end = dest_channel_end;
+		ordered_channel_end.ordering = Order::Ordered;
+		let ctx = ctx.with_channel(
+			packet.destination_port.clone(),
+			packet.destination_channel,
+			ordered_channel_end,
+		);
+		assert!(process(&ctx, &msg).is_err());
+	}
 }
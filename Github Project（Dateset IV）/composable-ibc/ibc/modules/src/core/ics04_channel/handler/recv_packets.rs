@@ -0,0 +1,140 @@
+// Copyright 2022 ComposableFi
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::{
+	core::{
+		ics04_channel::{
+			error::Error,
+			handler::{recv_packet, verify::verify_packet_recv_batch_proofs},
+			msgs::recv_packets::MsgRecvPackets,
+		},
+		ics26_routing::context::ReaderContext,
+	},
+	handler::{HandlerOutput, HandlerResult},
+	prelude::*,
+};
+
+/// Checks that every packet of the batch can be received and verifies the single multi-key proof
+/// of their commitments. Each packet is then processed on its own with
+/// [`recv_packet::process_verified`], so that the state written for one packet is visible to the
+/// next.
+pub fn process<Ctx: ReaderContext>(ctx: &Ctx, msg: &MsgRecvPackets) -> HandlerResult<(), Error> {
+	let mut output = HandlerOutput::builder();
+
+	// All packets are received on the same channel, hence over the same connection.
+	let mut connection_end = None;
+	for packet in msg.packets.iter() {
+		let (_, packet_connection_end, _) = recv_packet::validate(ctx, packet)?;
+		connection_end.get_or_insert(packet_connection_end);
+	}
+	let connection_end = connection_end.ok_or_else(Error::missing_packet)?;
+
+	verify_packet_recv_batch_proofs::<Ctx>(
+		ctx,
+		msg.proofs.height(),
+		&msg.packets,
+		&connection_end,
+		&msg.proofs,
+	)?;
+
+	output.log(format!("success: verified batch of {} packets", msg.packets.len()));
+
+	Ok(output.with_result(()))
+}
+
+#[cfg(test)]
+mod tests {
+	use crate::prelude::*;
+
+	use test_log::test;
+
+	use crate::{
+		core::{
+			ics02_client::context::ClientReader,
+			ics03_connection::{
+				connection::{
+					ConnectionEnd, Counterparty as ConnectionCounterparty, State as ConnectionState,
+				},
+				version::get_compatible_versions,
+			},
+			ics04_channel::{
+				channel::{ChannelEnd, Counterparty, Order, State},
+				handler::recv_packets::process,
+				msgs::recv_packets::{test_util::get_dummy_raw_msg_recv_packets, MsgRecvPackets},
+				Version,
+			},
+			ics24_host::identifier::{ClientId, ConnectionId},
+		},
+		mock::context::MockContext,
+		timestamp::ZERO_DURATION,
+	};
+
+	#[test]
+	fn recv_packets_processing() {
+		let context = MockContext::default();
+
+		let host_height = context.latest_height().increment();
+
+		let client_height = host_height.increment();
+
+		let msg = MsgRecvPackets::try_from(get_dummy_raw_msg_recv_packets(
+			client_height.revision_height,
+			3,
+		))
+		.unwrap();
+
+		let packet = msg.packets[0].clone();
+
+		let dest_channel_end = ChannelEnd::new(
+			State::Open,
+			Order::default(),
+			Counterparty::new(packet.source_port.clone(), Some(packet.source_channel)),
+			vec![ConnectionId::default()],
+			Version::ics20(),
+		);
+
+		let connection_end = ConnectionEnd::new(
+			ConnectionState::Open,
+			ClientId::default(),
+			ConnectionCounterparty::new(
+				ClientId::default(),
+				Some(ConnectionId::default()),
+				Default::default(),
+			),
+			get_compatible_versions(),
+			ZERO_DURATION,
+		);
+
+		let ready_context = context
+			.clone()
+			.with_client(&ClientId::default(), client_height)
+			.with_connection(ConnectionId::default(), connection_end.clone())
+			.with_channel(
+				packet.destination_port.clone(),
+				packet.destination_channel,
+				dest_channel_end,
+			)
+			.with_height(host_height);
+
+		assert!(process(&ready_context, &msg).is_ok(), "batch verification should pass");
+
+		// Without the channel, none of the packets can be received.
+		let no_channel_context = context
+			.with_client(&ClientId::default(), client_height)
+			.with_connection(ConnectionId::default(), connection_end)
+			.with_height(host_height);
+
+		assert!(process(&no_channel_context, &msg).is_err(), "batch verification should fail");
+	}
+}
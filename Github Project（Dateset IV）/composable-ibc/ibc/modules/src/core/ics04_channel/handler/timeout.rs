@@ -14,14 +14,19 @@
 
 use crate::{
 	core::{
-		ics02_client::client_consensus::ConsensusState,
+		ics02_client::{
+			client_consensus::ConsensusState, context::ClientTypes,
+			handler::update_client::Result as UpdateResult,
+		},
 		ics04_channel::{
 			channel::{ChannelEnd, Counterparty, Order, State},
 			error::Error,
 			events::TimeoutPacket,
-			handler::verify::{verify_next_sequence_recv, verify_packet_receipt_absence},
+			handler::verify::{
+				verify_next_sequence_recv, verify_packet_receipt, verify_packet_receipt_absence,
+			},
 			msgs::timeout::MsgTimeout,
-			packet::{PacketResult, Sequence},
+			packet::{PacketResult, Receipt, Sequence},
 		},
 		ics24_host::identifier::{ChannelId, PortId},
 		ics26_routing::context::ReaderContext,
@@ -33,25 +38,31 @@ use crate::{
 use core::fmt::Debug;
 
 #[derive(Clone, Debug)]
-pub struct TimeoutPacketResult {
+pub struct TimeoutPacketResult<C: ClientTypes> {
 	pub port_id: PortId,
 	pub channel_id: ChannelId,
 	pub seq: Sequence,
 	pub channel: Option<ChannelEnd>,
+	/// The next sequence to acknowledge on an `ORDERED_ALLOW_TIMEOUT` channel.
+	pub seq_number: Option<Sequence>,
+	/// The state of the client advanced by the proofs of the timeout.
+	pub client_update: Option<UpdateResult<C>>,
 }
 
-pub fn process<Ctx>(ctx: &Ctx, msg: &MsgTimeout) -> HandlerResult<PacketResult, Error>
+pub fn process<Ctx>(ctx: &Ctx, msg: &MsgTimeout) -> HandlerResult<PacketResult<Ctx>, Error>
 where
 	Ctx: ReaderContext,
 {
 	let mut output = HandlerOutput::builder();
+	let mut client_update = None;
 
 	let packet = &msg.packet;
 
 	let mut source_channel_end =
 		ctx.channel_end(&(packet.source_port.clone(), packet.source_channel))?;
 
-	if !source_channel_end.state_matches(&State::Open) {
+	// In-flight packets are still acknowledged and timed out while the channel is flushing.
+	if !source_channel_end.state_matches(&State::Open) && !source_channel_end.is_flushing() {
 		return Err(Error::channel_closed(packet.source_channel))
 	}
 
@@ -102,7 +113,35 @@ where
 		return Err(Error::incorrect_packet_commitment(packet.sequence))
 	}
 
-	let result = if source_channel_end.order_matches(&Order::Ordered) {
+	let result = if source_channel_end.order_matches(&Order::OrderedAllowTimeout) {
+		// The counterparty skipped the packet and wrote a timeout receipt for it, so the channel
+		// stays open and the packet is acknowledged in sequence.
+		let next_seq_ack =
+			ctx.get_next_sequence_ack(&(packet.source_port.clone(), packet.source_channel))?;
+
+		if packet.sequence != next_seq_ack {
+			return Err(Error::invalid_packet_sequence(packet.sequence, next_seq_ack))
+		}
+
+		verify_packet_receipt::<Ctx>(
+			ctx,
+			msg.proofs.height(),
+			&connection_end,
+			packet.clone(),
+			Receipt::Timeout,
+			&msg.proofs,
+			&mut client_update,
+		)?;
+
+		PacketResult::Timeout(TimeoutPacketResult {
+			port_id: packet.source_port.clone(),
+			channel_id: packet.source_channel,
+			seq: packet.sequence,
+			channel: None,
+			seq_number: Some(next_seq_ack.increment()),
+			client_update,
+		})
+	} else if source_channel_end.order_matches(&Order::Ordered) {
 		if packet.sequence < msg.next_sequence_recv {
 			return Err(Error::invalid_packet_sequence(packet.sequence, msg.next_sequence_recv))
 		}
@@ -113,6 +152,7 @@ where
 			packet.clone(),
 			msg.next_sequence_recv,
 			&msg.proofs,
+			&mut client_update,
 		)?;
 
 		source_channel_end.state = State::Closed;
@@ -121,6 +161,8 @@ where
 			channel_id: packet.source_channel,
 			seq: packet.sequence,
 			channel: Some(source_channel_end),
+			seq_number: None,
+			client_update,
 		})
 	} else {
 		verify_packet_receipt_absence::<Ctx>(
@@ -129,6 +171,7 @@ where
 			&connection_end,
 			packet.clone(),
 			&msg.proofs,
+			&mut client_update,
 		)?;
 
 		PacketResult::Timeout(TimeoutPacketResult {
@@ -136,6 +179,8 @@ where
 			channel_id: packet.source_channel,
 			seq: packet.sequence,
 			channel: None,
+			seq_number: None,
+			client_update,
 		})
 	};
 
@@ -164,9 +209,11 @@ mod tests {
 			},
 			ics04_channel::{
 				channel::{ChannelEnd, Counterparty, Order, State},
-				context::ChannelReader,
-				handler::timeout::process,
+				context::{ChannelKeeper, ChannelReader},
+				handler::timeout::{process, TimeoutPacketResult},
 				msgs::timeout::{test_util::get_dummy_raw_msg_timeout, MsgTimeout},
+				packet::PacketResult,
+				upgrade::{test_util::get_dummy_raw_upgrade, Upgrade},
 				Version,
 			},
 			ics24_host::identifier::{ChannelId, ClientId, ConnectionId, PortId},
@@ -217,6 +264,9 @@ mod tests {
 		let mut source_ordered_channel_end = source_channel_end.clone();
 		source_ordered_channel_end.ordering = Order::Ordered;
 
+		let mut source_allow_timeout_channel_end = source_channel_end.clone();
+		source_allow_timeout_channel_end.ordering = Order::OrderedAllowTimeout;
+
 		let connection_end = ConnectionEnd::new(
 			ConnectionState::Open,
 			ClientId::default(),
@@ -280,6 +330,30 @@ mod tests {
                 msg: msg_ok.clone(),
                 want_pass: true,
             },
+            Test {
+                name: "Good parameters Ordered Allow Timeout Channel".to_string(),
+                ctx: context.clone()
+                    .with_client(&ClientId::default(), client_height)
+                    .with_connection(ConnectionId::default(), connection_end.clone())
+                    .with_channel(
+                        packet.source_port.clone(),
+                        packet.source_channel,
+                        source_allow_timeout_channel_end,
+                    )
+                    .with_packet_commitment(
+                        msg_ok.packet.source_port.clone(),
+                        msg_ok.packet.source_channel,
+                        msg_ok.packet.sequence,
+                        data.clone(),
+                    )
+                    .with_ack_sequence(
+                         msg_ok.packet.source_port.clone(),
+                         msg_ok.packet.source_channel,
+                         msg_ok.packet.sequence,
+                     ),
+                msg: msg_ok.clone(),
+                want_pass: true,
+            },
             Test {
                 name: "Good parameters Ordered Channel".to_string(),
                 ctx: context
@@ -341,4 +415,42 @@ mod tests {
 			}
 		}
 	}
+
+	#[test]
+	fn timeout_on_flushing_ordered_channel_aborts_upgrade() {
+		let port_id = PortId::default();
+		let channel_id = ChannelId::default();
+		let port_channel_id = (port_id.clone(), channel_id);
+		let channel_end = ChannelEnd::new(
+			State::Flushing,
+			Order::Ordered,
+			Counterparty::new(port_id.clone(), Some(channel_id)),
+			vec![ConnectionId::default()],
+			Version::default(),
+		)
+		.with_upgrade_sequence(1);
+		let upgrade = Upgrade::try_from(get_dummy_raw_upgrade()).unwrap();
+		let mut ctx = MockContext::<MockClientTypes>::default()
+			.with_channel(port_id.clone(), channel_id, channel_end.clone())
+			.with_channel_upgrade(port_id.clone(), channel_id, upgrade.clone())
+			.with_counterparty_channel_upgrade(port_id.clone(), channel_id, upgrade);
+
+		// Timing out a packet closes the ordered channel end, which aborts its upgrade.
+		let mut closed = channel_end;
+		closed.set_state(State::Closed);
+		ctx.store_packet_result(PacketResult::Timeout(TimeoutPacketResult {
+			port_id,
+			channel_id,
+			seq: 1.into(),
+			channel: Some(closed),
+			seq_number: None,
+			client_update: None,
+		}))
+		.unwrap();
+
+		assert!(ctx.channel_end(&port_channel_id).unwrap().state_matches(&State::Closed));
+		assert!(ctx.channel_upgrade(&port_channel_id).is_err());
+		assert!(ctx.counterparty_channel_upgrade(&port_channel_id).is_err());
+		assert_eq!(ctx.upgrade_error_receipt(&port_channel_id).unwrap().sequence, 1);
+	}
 }
@@ -15,7 +15,7 @@
 use crate::{
 	core::{
 		ics04_channel::{
-			channel::{ChannelEnd, Counterparty, Order, State},
+			channel::{ChannelEnd, Counterparty, State},
 			error::Error,
 			events::TimeoutOnClosePacket,
 			handler::{
@@ -37,8 +37,9 @@ use crate::{
 pub fn process<Ctx: ReaderContext>(
 	ctx: &Ctx,
 	msg: &MsgTimeoutOnClose,
-) -> HandlerResult<PacketResult, Error> {
+) -> HandlerResult<PacketResult<Ctx>, Error> {
 	let mut output = HandlerOutput::builder();
+	let mut client_update = None;
 
 	let packet = &msg.packet;
 
@@ -100,9 +101,10 @@ pub fn process<Ctx: ReaderContext>(
 			.other_proof()
 			.as_ref()
 			.ok_or_else(|| Error::missing_channel_proof())?,
+		&mut client_update,
 	)?;
 
-	let result = if source_channel_end.order_matches(&Order::Ordered) {
+	let result = if source_channel_end.ordering().is_ordered() {
 		if packet.sequence < msg.next_sequence_recv {
 			return Err(Error::invalid_packet_sequence(packet.sequence, msg.next_sequence_recv))
 		}
@@ -113,6 +115,7 @@ pub fn process<Ctx: ReaderContext>(
 			packet.clone(),
 			msg.next_sequence_recv,
 			&msg.proofs,
+			&mut client_update,
 		)?;
 
 		PacketResult::Timeout(TimeoutPacketResult {
@@ -120,6 +123,8 @@ pub fn process<Ctx: ReaderContext>(
 			channel_id: packet.source_channel,
 			seq: packet.sequence,
 			channel: Some(source_channel_end),
+			seq_number: None,
+			client_update,
 		})
 	} else {
 		verify_packet_receipt_absence::<Ctx>(
@@ -128,6 +133,7 @@ pub fn process<Ctx: ReaderContext>(
 			&connection_end,
 			packet.clone(),
 			&msg.proofs,
+			&mut client_update,
 		)?;
 
 		PacketResult::Timeout(TimeoutPacketResult {
@@ -135,6 +141,8 @@ pub fn process<Ctx: ReaderContext>(
 			channel_id: packet.source_channel,
 			seq: packet.sequence,
 			channel: None,
+			seq_number: None,
+			client_update,
 		})
 	};
 
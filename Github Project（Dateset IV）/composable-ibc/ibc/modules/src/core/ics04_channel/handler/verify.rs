@@ -15,16 +15,21 @@
 use crate::{
 	core::{
 		ics02_client::{
-			client_consensus::ConsensusState, client_def::ClientDef, client_state::ClientState,
+			client_consensus::ConsensusState,
+			client_def::{client_state_for_proof, record_state_on_proof_verified, ClientDef},
+			client_state::ClientState,
+			handler::update_client::Result as UpdateResult,
 		},
 		ics03_connection::connection::ConnectionEnd,
 		ics04_channel::{
 			channel::ChannelEnd,
 			error::Error,
 			msgs::acknowledgement::Acknowledgement,
-			packet::{Packet, Sequence},
+			packet::{Packet, Receipt, Sequence},
+			upgrade::{ErrorReceipt, Upgrade},
 		},
 		ics23_commitment::commitment::CommitmentProofBytes,
+		ics24_host::{path::CommitmentsPath, Path},
 		ics26_routing::context::ReaderContext,
 	},
 	prelude::*,
@@ -40,6 +45,7 @@ pub fn verify_channel_proofs<Ctx>(
 	connection_end: &ConnectionEnd,
 	expected_chan: &ChannelEnd,
 	proof: &CommitmentProofBytes,
+	client_update: &mut Option<UpdateResult<Ctx>>,
 ) -> Result<(), Error>
 where
 	Ctx: ReaderContext,
@@ -47,7 +53,8 @@ where
 	// This is the client which will perform proof verification.
 	let client_id = connection_end.client_id().clone();
 
-	let client_state = ctx.client_state(&client_id).map_err(Error::ics02_client)?;
+	let client_state =
+		client_state_for_proof(ctx, &client_id, client_update).map_err(Error::ics02_client)?;
 
 	// The client must not be frozen.
 	if client_state.is_frozen(ctx, &client_id) {
@@ -78,7 +85,114 @@ where
 				.ok_or_else(|| Error::missing_channel_id())?,
 			expected_chan,
 		)
-		.map_err(Error::verify_channel_failed)
+		.map_err(Error::verify_channel_failed)?;
+
+	record_state_on_proof_verified(ctx, &client_id, client_state, proof, client_update)
+		.map_err(Error::ics02_client)
+}
+
+/// Entry point for verifying the proof of the upgrade proposed by the counterparty of a channel
+/// end, bundled in the channel upgrade handshake messages.
+pub fn verify_channel_upgrade_proofs<Ctx>(
+	ctx: &Ctx,
+	height: Height,
+	channel_end: &ChannelEnd,
+	connection_end: &ConnectionEnd,
+	expected_upgrade: &Upgrade,
+	proof: &CommitmentProofBytes,
+	client_update: &mut Option<UpdateResult<Ctx>>,
+) -> Result<(), Error>
+where
+	Ctx: ReaderContext,
+{
+	let client_id = connection_end.client_id().clone();
+
+	let client_state =
+		client_state_for_proof(ctx, &client_id, client_update).map_err(Error::ics02_client)?;
+
+	// The client must not be frozen.
+	if client_state.is_frozen(ctx, &client_id) {
+		return Err(Error::frozen_client(client_id))
+	}
+
+	let consensus_state = ctx
+		.consensus_state(&client_id, height)
+		.map_err(|_| Error::error_invalid_consensus_state())?;
+
+	let client_def = client_state.client_def();
+
+	client_def
+		.verify_channel_upgrade(
+			ctx,
+			&client_id,
+			&client_state,
+			height,
+			connection_end.counterparty().prefix(),
+			proof,
+			consensus_state.root(),
+			channel_end.counterparty().port_id(),
+			channel_end
+				.counterparty()
+				.channel_id()
+				.ok_or_else(|| Error::missing_channel_id())?,
+			expected_upgrade,
+		)
+		.map_err(Error::verify_upgrade_failed)?;
+
+	record_state_on_proof_verified(ctx, &client_id, client_state, proof, client_update)
+		.map_err(Error::ics02_client)
+}
+
+/// Entry point for verifying the proof of the error receipt written by the counterparty of a
+/// channel end when it aborted an upgrade.
+pub fn verify_channel_upgrade_error_proofs<Ctx>(
+	ctx: &Ctx,
+	height: Height,
+	channel_end: &ChannelEnd,
+	connection_end: &ConnectionEnd,
+	expected_error_receipt: &ErrorReceipt,
+	proof: &CommitmentProofBytes,
+	client_update: &mut Option<UpdateResult<Ctx>>,
+) -> Result<(), Error>
+where
+	Ctx: ReaderContext,
+{
+	let client_id = connection_end.client_id().clone();
+
+	let client_state =
+		client_state_for_proof(ctx, &client_id, client_update).map_err(Error::ics02_client)?;
+
+	// The client must not be frozen.
+	if client_state.is_frozen(ctx, &client_id) {
+		return Err(Error::frozen_client(client_id))
+	}
+
+	let consensus_state = ctx
+		.consensus_state(&client_id, height)
+		.map_err(|_| Error::error_invalid_consensus_state())?;
+
+	let client_def = client_state.client_def();
+
+	client_def
+		.verify_channel_upgrade_error(
+			ctx,
+			&client_id,
+			&client_state,
+			height,
+			connection_end.counterparty().prefix(),
+			proof,
+			consensus_state.root(),
+			channel_end.counterparty().port_id(),
+			channel_end
+				.counterparty()
+				.channel_id()
+				.ok_or_else(|| Error::missing_channel_id())?,
+			expected_error_receipt,
+		)
+		.map_err(Error::verify_upgrade_failed)?;
+
+	record_state_on_proof_verified(ctx, &client_id, client_state, proof, client_update)
+		.map_err(Error::ics02_client)
 }
 
 /// Entry point for verifying all proofs bundled in a ICS4 packet recv. message.
@@ -88,9 +202,11 @@ pub fn verify_packet_recv_proofs<Ctx: ReaderContext>(
 	packet: &Packet,
 	connection_end: &ConnectionEnd,
 	proofs: &Proofs,
+	client_update: &mut Option<UpdateResult<Ctx>>,
 ) -> Result<(), Error> {
 	let client_id = connection_end.client_id();
-	let client_state = ctx.client_state(client_id).map_err(Error::ics02_client)?;
+	let client_state =
+		client_state_for_proof(ctx, client_id, client_update).map_err(Error::ics02_client)?;
 
 	// The client must not be frozen.
 	if client_state.is_frozen(ctx, client_id) {
@@ -123,6 +239,70 @@ pub fn verify_packet_recv_proofs<Ctx: ReaderContext>(
 		)
 		.map_err(|e| Error::packet_verification_failed(packet.sequence, e))?;
 
+	record_state_on_proof_verified(
+		ctx,
+		client_id,
+		client_state,
+		proofs.object_proof(),
+		client_update,
+	)
+	.map_err(Error::ics02_client)
+}
+
+/// Entry point for verifying the single multi-key proof bundled in an ICS4 batched packet recv
+/// message, which covers the commitments of all `packets`.
+pub fn verify_packet_recv_batch_proofs<Ctx: ReaderContext>(
+	ctx: &Ctx,
+	height: Height,
+	packets: &[Packet],
+	connection_end: &ConnectionEnd,
+	proofs: &Proofs,
+) -> Result<(), Error> {
+	let client_id = connection_end.client_id();
+	let client_state = ctx.client_state(client_id).map_err(Error::ics02_client)?;
+
+	// The client must not be frozen.
+	if client_state.is_frozen(ctx, client_id) {
+		return Err(Error::frozen_client(client_id.clone()))
+	}
+
+	let consensus_state = ctx
+		.consensus_state(client_id, proofs.height())
+		.map_err(|_| Error::error_invalid_consensus_state())?;
+
+	let client_def = client_state.client_def();
+
+	let items = packets
+		.iter()
+		.map(|packet| {
+			let path = CommitmentsPath {
+				port_id: packet.source_port.clone(),
+				channel_id: packet.source_channel,
+				sequence: packet.sequence,
+			};
+			let commitment = ctx.packet_commitment(
+				packet.data.clone(),
+				packet.timeout_height,
+				packet.timeout_timestamp,
+			);
+			(Path::from(path), commitment.into_vec())
+		})
+		.collect();
+
+	// Verify the proof for all the packets against the chain store at once.
+	client_def
+		.verify_batch_membership(
+			ctx,
+			client_id,
+			&client_state,
+			height,
+			connection_end,
+			proofs.object_proof(),
+			consensus_state.root(),
+			items,
+		)
+		.map_err(Error::packet_batch_verification_failed)?;
+
 	Ok(())
 }
 
@@ -134,9 +314,11 @@ pub fn verify_packet_acknowledgement_proofs<Ctx: ReaderContext>(
 	acknowledgement: Acknowledgement,
 	connection_end: &ConnectionEnd,
 	proofs: &Proofs,
+	client_update: &mut Option<UpdateResult<Ctx>>,
 ) -> Result<(), Error> {
 	let client_id = connection_end.client_id();
-	let client_state = ctx.client_state(client_id).map_err(Error::ics02_client)?;
+	let client_state =
+		client_state_for_proof(ctx, client_id, client_update).map_err(Error::ics02_client)?;
 
 	// The client must not be frozen.
 	if client_state.is_frozen(ctx, client_id) {
@@ -168,7 +350,14 @@ pub fn verify_packet_acknowledgement_proofs<Ctx: ReaderContext>(
 		)
 		.map_err(|e| Error::packet_verification_failed(packet.sequence, e))?;
 
-	Ok(())
+	record_state_on_proof_verified(
+		ctx,
+		client_id,
+		client_state,
+		proofs.object_proof(),
+		client_update,
+	)
+	.map_err(Error::ics02_client)
 }
 
 /// Entry point for verifying all timeout proofs.
@@ -179,12 +368,14 @@ pub fn verify_next_sequence_recv<Ctx>(
 	packet: Packet,
 	seq: Sequence,
 	proofs: &Proofs,
+	client_update: &mut Option<UpdateResult<Ctx>>,
 ) -> Result<(), Error>
 where
 	Ctx: ReaderContext,
 {
 	let client_id = connection_end.client_id();
-	let client_state = ctx.client_state(client_id).map_err(Error::ics02_client)?;
+	let client_state =
+		client_state_for_proof(ctx, client_id, client_update).map_err(Error::ics02_client)?;
 
 	// The client must not be frozen.
 	if client_state.is_frozen(ctx, client_id) {
@@ -213,7 +404,14 @@ where
 		)
 		.map_err(|e| Error::packet_verification_failed(seq, e))?;
 
-	Ok(())
+	record_state_on_proof_verified(
+		ctx,
+		client_id,
+		client_state,
+		proofs.object_proof(),
+		client_update,
+	)
+	.map_err(Error::ics02_client)
 }
 
 pub fn verify_packet_receipt_absence<Ctx>(
@@ -222,12 +420,14 @@ pub fn verify_packet_receipt_absence<Ctx>(
 	connection_end: &ConnectionEnd,
 	packet: Packet,
 	proofs: &Proofs,
+	client_update: &mut Option<UpdateResult<Ctx>>,
 ) -> Result<(), Error>
 where
 	Ctx: ReaderContext,
 {
 	let client_id = connection_end.client_id();
-	let client_state = ctx.client_state(client_id).map_err(Error::ics02_client)?;
+	let client_state =
+		client_state_for_proof(ctx, client_id, client_update).map_err(Error::ics02_client)?;
 
 	// The client must not be frozen.
 	if client_state.is_frozen(ctx, client_id) {
@@ -256,5 +456,67 @@ where
 		)
 		.map_err(|e| Error::packet_verification_failed(packet.sequence, e))?;
 
-	Ok(())
+	record_state_on_proof_verified(
+		ctx,
+		client_id,
+		client_state,
+		proofs.object_proof(),
+		client_update,
+	)
+	.map_err(Error::ics02_client)
+}
+
+/// Entry point for verifying the receipt written for a packet on the counterparty chain.
+pub fn verify_packet_receipt<Ctx>(
+	ctx: &Ctx,
+	height: Height,
+	connection_end: &ConnectionEnd,
+	packet: Packet,
+	receipt: Receipt,
+	proofs: &Proofs,
+	client_update: &mut Option<UpdateResult<Ctx>>,
+) -> Result<(), Error>
+where
+	Ctx: ReaderContext,
+{
+	let client_id = connection_end.client_id();
+	let client_state =
+		client_state_for_proof(ctx, client_id, client_update).map_err(Error::ics02_client)?;
+
+	// The client must not be frozen.
+	if client_state.is_frozen(ctx, client_id) {
+		return Err(Error::frozen_client(client_id.clone()))
+	}
+
+	let consensus_state = ctx
+		.consensus_state(client_id, proofs.height())
+		.map_err(|_| Error::error_invalid_consensus_state())?;
+
+	let client_def = client_state.client_def();
+
+	// Verify the proof for the packet against the chain store.
+	client_def
+		.verify_packet_receipt(
+			ctx,
+			client_id,
+			&client_state,
+			height,
+			connection_end,
+			proofs.object_proof(),
+			consensus_state.root(),
+			&packet.destination_port,
+			&packet.destination_channel,
+			packet.sequence,
+			receipt,
+		)
+		.map_err(|e| Error::packet_verification_failed(packet.sequence, e))?;
+
+	record_state_on_proof_verified(
+		ctx,
+		client_id,
+		client_state,
+		proofs.object_proof(),
+		client_update,
+	)
+	.map_err(Error::ics02_client)
 }
@@ -40,14 +40,14 @@ pub fn process<Ctx: ReaderContext>(
 	ctx: &Ctx,
 	packet: Packet,
 	ack: Vec<u8>,
-) -> HandlerResult<PacketResult, Error> {
+) -> HandlerResult<PacketResult<Ctx>, Error> {
 	let mut output = HandlerOutput::builder();
 
 	let dest_channel_end =
 		ctx.channel_end(&(packet.destination_port.clone(), packet.destination_channel))?;
 
-	if !dest_channel_end.state_matches(&State::Open) {
-		return Err(Error::invalid_channel_state(packet.source_channel, dest_channel_end.state))
+	if !dest_channel_end.state_matches(&State::Open) && !dest_channel_end.is_flushing() {
+		return Err(Error::invalid_channel_state(packet.destination_channel, dest_channel_end.state))
 	}
 
 	// NOTE: IBC app modules might have written the acknowledgement synchronously on
@@ -69,8 +69,8 @@ pub fn process<Ctx: ReaderContext>(
 	}
 
 	let result = PacketResult::WriteAck(WriteAckPacketResult {
-		port_id: packet.source_port.clone(),
-		channel_id: packet.source_channel,
+		port_id: packet.destination_port.clone(),
+		channel_id: packet.destination_channel,
 		seq: packet.sequence,
 		ack: ack.clone(),
 	});
@@ -103,14 +103,25 @@ mod tests {
 			},
 			ics04_channel::{
 				channel::{ChannelEnd, Counterparty, Order, State},
-				handler::write_acknowledgement::process,
+				context::{ChannelKeeper, ChannelReader},
+				error::Error,
+				handler::{packet_callback, write_acknowledgement::process},
+				msgs::{
+					recv_packet::{test_util::get_dummy_raw_msg_recv_packet, MsgRecvPacket},
+					PacketMsg,
+				},
 				packet::{test_utils::get_dummy_raw_packet, Packet},
 				Version,
 			},
 			ics24_host::identifier::{ChannelId, ClientId, ConnectionId, PortId},
+			ics26_routing::context::{
+				Module, ModuleCallbackContext, ModuleId, ModuleOutputBuilder, OnRecvPacketAck,
+				RouterBuilder,
+			},
 		},
 		events::IbcEvent,
-		mock::context::{MockClientTypes, MockContext},
+		mock::context::{MockClientTypes, MockContext, MockRouterBuilder},
+		signer::Signer,
 		timestamp::ZERO_DURATION,
 	};
 
@@ -225,4 +236,53 @@ mod tests {
 			}
 		}
 	}
+
+	#[test]
+	fn async_ack_is_written_once_processed() {
+		#[derive(Debug, Default)]
+		struct AsyncModule;
+
+		impl Module for AsyncModule {
+			fn on_recv_packet(
+				&self,
+				_ctx: &dyn ModuleCallbackContext,
+				_output: &mut ModuleOutputBuilder,
+				_packet: &mut Packet,
+				_relayer: &Signer,
+			) -> Result<OnRecvPacketAck, Error> {
+				Ok(OnRecvPacketAck::Async)
+			}
+		}
+
+		let module_id: ModuleId = "asyncmodule".parse().unwrap();
+		let router = MockRouterBuilder::default()
+			.add_route(module_id.clone(), AsyncModule)
+			.unwrap()
+			.build();
+		let msg = MsgRecvPacket::try_from(get_dummy_raw_msg_recv_packet(35)).unwrap();
+		let packet = msg.packet.clone();
+		let dest_channel_end = ChannelEnd::new(
+			State::Open,
+			Order::default(),
+			Counterparty::new(packet.source_port.clone(), Some(packet.source_channel)),
+			vec![ConnectionId::default()],
+			Version::ics20(),
+		);
+		let mut ctx = MockContext::<MockClientTypes>::default().with_router(router).with_channel(
+			packet.destination_port.clone(),
+			packet.destination_channel,
+			dest_channel_end,
+		);
+		let key = (packet.destination_port.clone(), packet.destination_channel, packet.sequence);
+
+		let mut output = ModuleOutputBuilder::new();
+		packet_callback(&mut ctx, &module_id, &PacketMsg::RecvPacket(msg), &mut output).unwrap();
+		assert!(ctx.get_packet_acknowledgement(&key).is_err());
+		assert!(ctx.ibc_store_share().lock().unwrap().pending_acknowledgements.contains(&key));
+
+		let result = process(&ctx, packet, vec![1]).unwrap();
+		ctx.store_packet_result(result.result).unwrap();
+		assert!(ctx.get_packet_acknowledgement(&key).is_ok());
+		assert!(ctx.ibc_store_share().lock().unwrap().pending_acknowledgements.is_empty());
+	}
 }
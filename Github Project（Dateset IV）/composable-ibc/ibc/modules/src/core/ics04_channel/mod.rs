@@ -23,6 +23,7 @@ pub mod events;
 pub mod handler;
 pub mod msgs;
 pub mod packet;
+pub mod upgrade;
 
 pub mod commitment;
 mod version;
@@ -12,8 +12,8 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-//! Message definitions for all ICS4 domain types: channel open & close handshake datagrams, as well
-//! as packets.
+//! Message definitions for all ICS4 domain types: channel open, close & upgrade handshake
+//! datagrams, as well as packets.
 
 use crate::core::{
 	ics04_channel::{
@@ -22,8 +22,12 @@ use crate::core::{
 			acknowledgement::MsgAcknowledgement, chan_close_confirm::MsgChannelCloseConfirm,
 			chan_close_init::MsgChannelCloseInit, chan_open_ack::MsgChannelOpenAck,
 			chan_open_confirm::MsgChannelOpenConfirm, chan_open_init::MsgChannelOpenInit,
-			chan_open_try::MsgChannelOpenTry, recv_packet::MsgRecvPacket, timeout::MsgTimeout,
-			timeout_on_close::MsgTimeoutOnClose,
+			chan_open_try::MsgChannelOpenTry, chan_upgrade_ack::MsgChannelUpgradeAck,
+			chan_upgrade_cancel::MsgChannelUpgradeCancel,
+			chan_upgrade_confirm::MsgChannelUpgradeConfirm,
+			chan_upgrade_init::MsgChannelUpgradeInit, chan_upgrade_open::MsgChannelUpgradeOpen,
+			chan_upgrade_timeout::MsgChannelUpgradeTimeout, chan_upgrade_try::MsgChannelUpgradeTry,
+			recv_packet::MsgRecvPacket, timeout::MsgTimeout, timeout_on_close::MsgTimeoutOnClose,
 		},
 	},
 	ics26_routing::context::{Ics26Context, ModuleId},
@@ -39,9 +43,19 @@ pub mod chan_open_try;
 pub mod chan_close_confirm;
 pub mod chan_close_init;
 
+// Upgrade handshake messages.
+pub mod chan_upgrade_ack;
+pub mod chan_upgrade_cancel;
+pub mod chan_upgrade_confirm;
+pub mod chan_upgrade_init;
+pub mod chan_upgrade_open;
+pub mod chan_upgrade_timeout;
+pub mod chan_upgrade_try;
+
 // Packet specific messages.
 pub mod acknowledgement;
 pub mod recv_packet;
+pub mod recv_packets;
 pub mod timeout;
 pub mod timeout_on_close;
 
@@ -54,6 +68,13 @@ pub enum ChannelMsg {
 	ChannelOpenConfirm(MsgChannelOpenConfirm),
 	ChannelCloseInit(MsgChannelCloseInit),
 	ChannelCloseConfirm(MsgChannelCloseConfirm),
+	ChannelUpgradeInit(MsgChannelUpgradeInit),
+	ChannelUpgradeTry(MsgChannelUpgradeTry),
+	ChannelUpgradeAck(MsgChannelUpgradeAck),
+	ChannelUpgradeConfirm(MsgChannelUpgradeConfirm),
+	ChannelUpgradeOpen(MsgChannelUpgradeOpen),
+	ChannelUpgradeTimeout(MsgChannelUpgradeTimeout),
+	ChannelUpgradeCancel(MsgChannelUpgradeCancel),
 }
 
 impl ChannelMsg {
@@ -71,6 +92,20 @@ impl ChannelMsg {
 				ctx.lookup_module_by_port(&msg.port_id).map_err(Error::ics05_port)?,
 			ChannelMsg::ChannelCloseConfirm(msg) =>
 				ctx.lookup_module_by_port(&msg.port_id).map_err(Error::ics05_port)?,
+			ChannelMsg::ChannelUpgradeInit(msg) =>
+				ctx.lookup_module_by_port(&msg.port_id).map_err(Error::ics05_port)?,
+			ChannelMsg::ChannelUpgradeTry(msg) =>
+				ctx.lookup_module_by_port(&msg.port_id).map_err(Error::ics05_port)?,
+			ChannelMsg::ChannelUpgradeAck(msg) =>
+				ctx.lookup_module_by_port(&msg.port_id).map_err(Error::ics05_port)?,
+			ChannelMsg::ChannelUpgradeConfirm(msg) =>
+				ctx.lookup_module_by_port(&msg.port_id).map_err(Error::ics05_port)?,
+			ChannelMsg::ChannelUpgradeOpen(msg) =>
+				ctx.lookup_module_by_port(&msg.port_id).map_err(Error::ics05_port)?,
+			ChannelMsg::ChannelUpgradeTimeout(msg) =>
+				ctx.lookup_module_by_port(&msg.port_id).map_err(Error::ics05_port)?,
+			ChannelMsg::ChannelUpgradeCancel(msg) =>
+				ctx.lookup_module_by_port(&msg.port_id).map_err(Error::ics05_port)?,
 		};
 		Ok(module_id)
 	}
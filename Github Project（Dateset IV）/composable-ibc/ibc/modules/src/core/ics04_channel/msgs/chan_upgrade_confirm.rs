@@ -0,0 +1,196 @@
+// Copyright 2022 ComposableFi
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::{
+	core::{
+		ics04_channel::{channel::State, error::Error, upgrade::Upgrade},
+		ics24_host::identifier::{ChannelId, PortId},
+	},
+	prelude::*,
+	proofs::Proofs,
+	signer::Signer,
+	tx_msg::Msg,
+};
+
+use ibc_proto::ibc::core::channel::v1::MsgChannelUpgradeConfirm as RawMsgChannelUpgradeConfirm;
+use tendermint_proto::Protobuf;
+
+pub const TYPE_URL: &str = "/ibc.core.channel.v1.MsgChannelUpgradeConfirm";
+
+///
+/// Message definition for the fourth step in the channel upgrade handshake (`ChanUpgradeConfirm`
+/// datagram).
+#[derive(Clone, Debug, PartialEq)]
+pub struct MsgChannelUpgradeConfirm {
+	pub port_id: PortId,
+	pub channel_id: ChannelId,
+	pub counterparty_channel_state: State,
+	pub counterparty_upgrade: Upgrade,
+	/// The object proof is the proof of the counterparty channel end, the other proof is the proof
+	/// of the counterparty upgrade.
+	pub proofs: Proofs,
+	pub signer: Signer,
+}
+
+impl MsgChannelUpgradeConfirm {
+	pub fn new(
+		port_id: PortId,
+		channel_id: ChannelId,
+		counterparty_channel_state: State,
+		counterparty_upgrade: Upgrade,
+		proofs: Proofs,
+		signer: Signer,
+	) -> Self {
+		Self {
+			port_id,
+			channel_id,
+			counterparty_channel_state,
+			counterparty_upgrade,
+			proofs,
+			signer,
+		}
+	}
+}
+
+impl Msg for MsgChannelUpgradeConfirm {
+	type ValidationError = Error;
+	type Raw = RawMsgChannelUpgradeConfirm;
+
+	fn route(&self) -> String {
+		crate::keys::ROUTER_KEY.to_string()
+	}
+
+	fn type_url(&self) -> String {
+		TYPE_URL.to_string()
+	}
+}
+
+impl Protobuf<RawMsgChannelUpgradeConfirm> for MsgChannelUpgradeConfirm {}
+
+impl TryFrom<RawMsgChannelUpgradeConfirm> for MsgChannelUpgradeConfirm {
+	type Error = Error;
+
+	fn try_from(raw_msg: RawMsgChannelUpgradeConfirm) -> Result<Self, Self::Error> {
+		if raw_msg.proof_upgrade.is_empty() {
+			return Err(Error::missing_upgrade_proof())
+		}
+		let proofs = Proofs::new(
+			raw_msg.proof_channel.try_into().map_err(Error::invalid_proof)?,
+			None,
+			None,
+			Some(raw_msg.proof_upgrade.try_into().map_err(Error::invalid_proof)?),
+			raw_msg.proof_height.ok_or_else(Error::missing_height)?.into(),
+		)
+		.map_err(Error::invalid_proof)?;
+
+		let counterparty_channel_state = State::from_i32(raw_msg.counterparty_channel_state)?;
+		if !matches!(counterparty_channel_state, State::Flushing | State::FlushComplete) {
+			return Err(Error::invalid_channel_state(
+				raw_msg.channel_id.parse().map_err(Error::identifier)?,
+				counterparty_channel_state,
+			))
+		}
+
+		let counterparty_upgrade: Upgrade = raw_msg
+			.counterparty_upgrade
+			.ok_or_else(Error::missing_upgrade_fields)?
+			.try_into()?;
+		counterparty_upgrade.fields.validate_basic()?;
+
+		Ok(MsgChannelUpgradeConfirm {
+			port_id: raw_msg.port_id.parse().map_err(Error::identifier)?,
+			channel_id: raw_msg.channel_id.parse().map_err(Error::identifier)?,
+			counterparty_channel_state,
+			counterparty_upgrade,
+			proofs,
+			signer: raw_msg.signer.parse().map_err(Error::signer)?,
+		})
+	}
+}
+
+impl From<MsgChannelUpgradeConfirm> for RawMsgChannelUpgradeConfirm {
+	fn from(domain_msg: MsgChannelUpgradeConfirm) -> Self {
+		RawMsgChannelUpgradeConfirm {
+			port_id: domain_msg.port_id.to_string(),
+			channel_id: domain_msg.channel_id.to_string(),
+			counterparty_channel_state: domain_msg.counterparty_channel_state as i32,
+			counterparty_upgrade: Some(domain_msg.counterparty_upgrade.into()),
+			proof_channel: domain_msg.proofs.object_proof().clone().into(),
+			proof_upgrade: domain_msg
+				.proofs
+				.other_proof()
+				.clone()
+				.map_or_else(Vec::new, |v| v.into()),
+			proof_height: Some(domain_msg.proofs.height().into()),
+			signer: domain_msg.signer.to_string(),
+		}
+	}
+}
+
+#[cfg(test)]
+pub mod test_util {
+	use crate::prelude::*;
+	use ibc_proto::ibc::core::{
+		channel::v1::MsgChannelUpgradeConfirm as RawMsgChannelUpgradeConfirm,
+		client::v1::Height as RawHeight,
+	};
+
+	use crate::{
+		core::{
+			ics04_channel::upgrade::test_util::get_dummy_raw_upgrade,
+			ics24_host::identifier::{ChannelId, PortId},
+		},
+		test_utils::{get_dummy_bech32_account, get_dummy_proof},
+	};
+
+	/// Returns a dummy `RawMsgChannelUpgradeConfirm`, for testing only!
+	pub fn get_dummy_raw_msg_chan_upgrade_confirm(
+		proof_height: u64,
+	) -> RawMsgChannelUpgradeConfirm {
+		RawMsgChannelUpgradeConfirm {
+			port_id: PortId::default().to_string(),
+			channel_id: ChannelId::default().to_string(),
+			counterparty_channel_state: 6,
+			counterparty_upgrade: Some(get_dummy_raw_upgrade()),
+			proof_channel: get_dummy_proof(),
+			proof_upgrade: get_dummy_proof(),
+			proof_height: Some(RawHeight { revision_number: 0, revision_height: proof_height }),
+			signer: get_dummy_bech32_account(),
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use ibc_proto::ibc::core::channel::v1::MsgChannelUpgradeConfirm as RawMsgChannelUpgradeConfirm;
+	use test_log::test;
+
+	use crate::core::ics04_channel::msgs::chan_upgrade_confirm::{
+		test_util::get_dummy_raw_msg_chan_upgrade_confirm, MsgChannelUpgradeConfirm,
+	};
+
+	#[test]
+	fn to_and_from() {
+		let raw = get_dummy_raw_msg_chan_upgrade_confirm(10);
+		let msg = MsgChannelUpgradeConfirm::try_from(raw.clone()).unwrap();
+		let raw_back = RawMsgChannelUpgradeConfirm::from(msg.clone());
+		let msg_back = MsgChannelUpgradeConfirm::try_from(raw_back.clone()).unwrap();
+		assert_eq!(raw, raw_back);
+		assert_eq!(msg, msg_back);
+
+		// the counterparty must be flushing
+		let raw = RawMsgChannelUpgradeConfirm { counterparty_channel_state: 3, ..raw };
+		assert!(MsgChannelUpgradeConfirm::try_from(raw).is_err());
+	}
+}
@@ -0,0 +1,255 @@
+// Copyright 2022 ComposableFi
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::{
+	core::{
+		ics04_channel::{error::Error, upgrade::UpgradeFields},
+		ics24_host::identifier::{ChannelId, ConnectionId, PortId},
+	},
+	prelude::*,
+	proofs::Proofs,
+	signer::Signer,
+	tx_msg::Msg,
+};
+
+use core::str::FromStr;
+use ibc_proto::ibc::core::channel::v1::MsgChannelUpgradeTry as RawMsgChannelUpgradeTry;
+use tendermint_proto::Protobuf;
+
+pub const TYPE_URL: &str = "/ibc.core.channel.v1.MsgChannelUpgradeTry";
+
+///
+/// Message definition for the second step in the channel upgrade handshake (`ChanUpgradeTry`
+/// datagram).
+#[derive(Clone, Debug, PartialEq)]
+pub struct MsgChannelUpgradeTry {
+	pub port_id: PortId,
+	pub channel_id: ChannelId,
+	pub proposed_upgrade_connection_hops: Vec<ConnectionId>,
+	pub counterparty_upgrade_fields: UpgradeFields,
+	pub counterparty_upgrade_sequence: u64,
+	/// The object proof is the proof of the counterparty channel end, the other proof is the proof
+	/// of the counterparty upgrade.
+	pub proofs: Proofs,
+	pub signer: Signer,
+}
+
+impl MsgChannelUpgradeTry {
+	pub fn new(
+		port_id: PortId,
+		channel_id: ChannelId,
+		proposed_upgrade_connection_hops: Vec<ConnectionId>,
+		counterparty_upgrade_fields: UpgradeFields,
+		counterparty_upgrade_sequence: u64,
+		proofs: Proofs,
+		signer: Signer,
+	) -> Self {
+		Self {
+			port_id,
+			channel_id,
+			proposed_upgrade_connection_hops,
+			counterparty_upgrade_fields,
+			counterparty_upgrade_sequence,
+			proofs,
+			signer,
+		}
+	}
+}
+
+impl Msg for MsgChannelUpgradeTry {
+	type ValidationError = Error;
+	type Raw = RawMsgChannelUpgradeTry;
+
+	fn route(&self) -> String {
+		crate::keys::ROUTER_KEY.to_string()
+	}
+
+	fn type_url(&self) -> String {
+		TYPE_URL.to_string()
+	}
+}
+
+impl Protobuf<RawMsgChannelUpgradeTry> for MsgChannelUpgradeTry {}
+
+impl TryFrom<RawMsgChannelUpgradeTry> for MsgChannelUpgradeTry {
+	type Error = Error;
+
+	fn try_from(raw_msg: RawMsgChannelUpgradeTry) -> Result<Self, Self::Error> {
+		if raw_msg.proof_upgrade.is_empty() {
+			return Err(Error::missing_upgrade_proof())
+		}
+		let proofs = Proofs::new(
+			raw_msg.proof_channel.try_into().map_err(Error::invalid_proof)?,
+			None,
+			None,
+			Some(raw_msg.proof_upgrade.try_into().map_err(Error::invalid_proof)?),
+			raw_msg.proof_height.ok_or_else(Error::missing_height)?.into(),
+		)
+		.map_err(Error::invalid_proof)?;
+
+		let proposed_upgrade_connection_hops = raw_msg
+			.proposed_upgrade_connection_hops
+			.into_iter()
+			.map(|conn_id| ConnectionId::from_str(conn_id.as_str()))
+			.collect::<Result<Vec<_>, _>>()
+			.map_err(Error::identifier)?;
+		if proposed_upgrade_connection_hops.len() != 1 {
+			return Err(Error::invalid_connection_hops_length(
+				1,
+				proposed_upgrade_connection_hops.len(),
+			))
+		}
+
+		let counterparty_upgrade_fields: UpgradeFields = raw_msg
+			.counterparty_upgrade_fields
+			.ok_or_else(Error::missing_upgrade_fields)?
+			.try_into()?;
+		counterparty_upgrade_fields.validate_basic()?;
+
+		Ok(MsgChannelUpgradeTry {
+			port_id: raw_msg.port_id.parse().map_err(Error::identifier)?,
+			channel_id: raw_msg.channel_id.parse().map_err(Error::identifier)?,
+			proposed_upgrade_connection_hops,
+			counterparty_upgrade_fields,
+			counterparty_upgrade_sequence: raw_msg.counterparty_upgrade_sequence,
+			proofs,
+			signer: raw_msg.signer.parse().map_err(Error::signer)?,
+		})
+	}
+}
+
+impl From<MsgChannelUpgradeTry> for RawMsgChannelUpgradeTry {
+	fn from(domain_msg: MsgChannelUpgradeTry) -> Self {
+		RawMsgChannelUpgradeTry {
+			port_id: domain_msg.port_id.to_string(),
+			channel_id: domain_msg.channel_id.to_string(),
+			proposed_upgrade_connection_hops: domain_msg
+				.proposed_upgrade_connection_hops
+				.iter()
+				.map(|v| v.as_str().to_string())
+				.collect(),
+			counterparty_upgrade_fields: Some(domain_msg.counterparty_upgrade_fields.into()),
+			counterparty_upgrade_sequence: domain_msg.counterparty_upgrade_sequence,
+			proof_channel: domain_msg.proofs.object_proof().clone().into(),
+			proof_upgrade: domain_msg
+				.proofs
+				.other_proof()
+				.clone()
+				.map_or_else(Vec::new, |v| v.into()),
+			proof_height: Some(domain_msg.proofs.height().into()),
+			signer: domain_msg.signer.to_string(),
+		}
+	}
+}
+
+#[cfg(test)]
+pub mod test_util {
+	use crate::prelude::*;
+	use ibc_proto::ibc::core::{
+		channel::v1::MsgChannelUpgradeTry as RawMsgChannelUpgradeTry,
+		client::v1::Height as RawHeight,
+	};
+
+	use crate::{
+		core::{
+			ics04_channel::upgrade::test_util::get_dummy_raw_upgrade_fields,
+			ics24_host::identifier::{ChannelId, ConnectionId, PortId},
+		},
+		test_utils::{get_dummy_bech32_account, get_dummy_proof},
+	};
+
+	/// Returns a dummy `RawMsgChannelUpgradeTry`, for testing only!
+	pub fn get_dummy_raw_msg_chan_upgrade_try(
+		proof_height: u64,
+		counterparty_upgrade_sequence: u64,
+	) -> RawMsgChannelUpgradeTry {
+		RawMsgChannelUpgradeTry {
+			port_id: PortId::default().to_string(),
+			channel_id: ChannelId::default().to_string(),
+			proposed_upgrade_connection_hops: vec![ConnectionId::default().to_string()],
+			counterparty_upgrade_fields: Some(get_dummy_raw_upgrade_fields()),
+			counterparty_upgrade_sequence,
+			proof_channel: get_dummy_proof(),
+			proof_upgrade: get_dummy_proof(),
+			proof_height: Some(RawHeight { revision_number: 0, revision_height: proof_height }),
+			signer: get_dummy_bech32_account(),
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use crate::prelude::*;
+	use ibc_proto::ibc::core::channel::v1::MsgChannelUpgradeTry as RawMsgChannelUpgradeTry;
+	use test_log::test;
+
+	use crate::core::ics04_channel::msgs::chan_upgrade_try::{
+		test_util::get_dummy_raw_msg_chan_upgrade_try, MsgChannelUpgradeTry,
+	};
+
+	#[test]
+	fn parse_channel_upgrade_try_msg() {
+		let default_raw_msg = get_dummy_raw_msg_chan_upgrade_try(10, 1);
+
+		let tests = vec![
+			("Good parameters", default_raw_msg.clone(), true),
+			(
+				"Missing upgrade proof",
+				RawMsgChannelUpgradeTry { proof_upgrade: Vec::new(), ..default_raw_msg.clone() },
+				false,
+			),
+			(
+				"Missing channel proof",
+				RawMsgChannelUpgradeTry { proof_channel: Vec::new(), ..default_raw_msg.clone() },
+				false,
+			),
+			(
+				"No proposed connection hops",
+				RawMsgChannelUpgradeTry {
+					proposed_upgrade_connection_hops: Vec::new(),
+					..default_raw_msg.clone()
+				},
+				false,
+			),
+			(
+				"Missing counterparty upgrade fields",
+				RawMsgChannelUpgradeTry { counterparty_upgrade_fields: None, ..default_raw_msg },
+				false,
+			),
+		];
+
+		for (name, raw, want_pass) in tests {
+			let res_msg = MsgChannelUpgradeTry::try_from(raw.clone());
+
+			assert_eq!(
+				want_pass,
+				res_msg.is_ok(),
+				"MsgChannelUpgradeTry::try_from failed for test {}, \nraw msg {:?} with error {:?}",
+				name,
+				raw,
+				res_msg.err(),
+			);
+		}
+	}
+
+	#[test]
+	fn to_and_from() {
+		let raw = get_dummy_raw_msg_chan_upgrade_try(10, 1);
+		let msg = MsgChannelUpgradeTry::try_from(raw.clone()).unwrap();
+		let raw_back = RawMsgChannelUpgradeTry::from(msg.clone());
+		let msg_back = MsgChannelUpgradeTry::try_from(raw_back.clone()).unwrap();
+		assert_eq!(raw, raw_back);
+		assert_eq!(msg, msg_back);
+	}
+}
@@ -0,0 +1,252 @@
+// Copyright 2022 ComposableFi
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::prelude::*;
+
+use alloc::collections::BTreeSet;
+use tendermint_proto::Protobuf;
+
+use ibc_proto::composable::ibc::core::channel::v1::MsgRecvPackets as RawMsgRecvPackets;
+
+use crate::{
+	core::ics04_channel::{
+		error::Error,
+		msgs::recv_packet::MsgRecvPacket,
+		packet::{Packet, Sequence},
+	},
+	proofs::Proofs,
+	signer::Signer,
+	tx_msg::Msg,
+};
+
+pub const TYPE_URL: &str = "/composable.ibc.core.channel.v1.MsgRecvPackets";
+
+///
+/// Message definition for the batched "packet receiving" datagram. All packets are received on
+/// the same channel and `proofs` holds a single multi-key proof of all their commitments.
+#[derive(Clone, Debug, PartialEq)]
+pub struct MsgRecvPackets {
+	pub packets: Vec<Packet>,
+	pub proofs: Proofs,
+	pub signer: Signer,
+}
+
+impl MsgRecvPackets {
+	pub fn new(packets: Vec<Packet>, proofs: Proofs, signer: Signer) -> MsgRecvPackets {
+		Self { packets, proofs, signer }
+	}
+
+	/// Splits the batch into one [`MsgRecvPacket`] per packet, each carrying the shared proof.
+	pub fn recv_packet_msgs(&self) -> Vec<MsgRecvPacket> {
+		self.packets
+			.iter()
+			.map(|packet| {
+				MsgRecvPacket::new(packet.clone(), self.proofs.clone(), self.signer.clone())
+			})
+			.collect()
+	}
+}
+
+impl Msg for MsgRecvPackets {
+	type ValidationError = Error;
+	type Raw = RawMsgRecvPackets;
+
+	fn route(&self) -> String {
+		crate::keys::ROUTER_KEY.to_string()
+	}
+
+	fn type_url(&self) -> String {
+		TYPE_URL.to_string()
+	}
+}
+
+impl Protobuf<RawMsgRecvPackets> for MsgRecvPackets {}
+
+impl TryFrom<RawMsgRecvPackets> for MsgRecvPackets {
+	type Error = Error;
+
+	fn try_from(raw_msg: RawMsgRecvPackets) -> Result<Self, Self::Error> {
+		let proofs = Proofs::new(
+			raw_msg.proof_commitment.try_into().map_err(Error::invalid_proof)?,
+			None,
+			None,
+			None,
+			raw_msg.proof_height.ok_or_else(Error::missing_height)?.into(),
+		)
+		.map_err(Error::invalid_proof)?;
+
+		let packets = raw_msg
+			.packets
+			.into_iter()
+			.map(Packet::try_from)
+			.collect::<Result<Vec<_>, _>>()?;
+
+		let first = packets.first().ok_or_else(Error::missing_packet)?;
+		let mut sequences = BTreeSet::<Sequence>::new();
+		for packet in packets.iter() {
+			if packet.destination_port != first.destination_port ||
+				packet.destination_channel != first.destination_channel
+			{
+				return Err(Error::mixed_packet_batch(
+					first.destination_port.clone(),
+					first.destination_channel,
+				))
+			}
+			if !sequences.insert(packet.sequence) {
+				return Err(Error::duplicate_packet_in_batch(packet.sequence))
+			}
+		}
+
+		Ok(MsgRecvPackets {
+			packets,
+			proofs,
+			signer: raw_msg.signer.parse().map_err(Error::signer)?,
+		})
+	}
+}
+
+impl From<MsgRecvPackets> for RawMsgRecvPackets {
+	fn from(domain_msg: MsgRecvPackets) -> Self {
+		RawMsgRecvPackets {
+			packets: domain_msg.packets.into_iter().map(Into::into).collect(),
+			proof_commitment: domain_msg.proofs.object_proof().clone().into(),
+			proof_height: Some(domain_msg.proofs.height().into()),
+			signer: domain_msg.signer.to_string(),
+		}
+	}
+}
+
+#[cfg(test)]
+pub mod test_util {
+	use crate::prelude::*;
+
+	use ibc_proto::{
+		composable::ibc::core::channel::v1::MsgRecvPackets as RawMsgRecvPackets,
+		ibc::core::client::v1::Height as RawHeight,
+	};
+
+	use crate::{
+		core::ics04_channel::packet::test_utils::get_dummy_raw_packet,
+		test_utils::{get_dummy_bech32_account, get_dummy_proof},
+		timestamp::Timestamp,
+	};
+	use core::{ops::Add, time::Duration};
+
+	/// Returns a dummy `RawMsgRecvPackets` with `count` packets of consecutive sequences, for
+	/// testing only! The `height` parametrizes both the proof height as well as the timeout height.
+	pub fn get_dummy_raw_msg_recv_packets(height: u64, count: u64) -> RawMsgRecvPackets {
+		let timestamp = Timestamp::now().add(Duration::from_secs(9));
+		let packets = (1..=count)
+			.map(|sequence| {
+				let mut packet = get_dummy_raw_packet(height, timestamp.unwrap().nanoseconds());
+				packet.sequence = sequence;
+				packet
+			})
+			.collect();
+		RawMsgRecvPackets {
+			packets,
+			proof_commitment: get_dummy_proof(),
+			proof_height: Some(RawHeight { revision_number: 0, revision_height: height }),
+			signer: get_dummy_bech32_account(),
+		}
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use crate::prelude::*;
+
+	use test_log::test;
+
+	use ibc_proto::composable::ibc::core::channel::v1::MsgRecvPackets as RawMsgRecvPackets;
+
+	use crate::core::ics04_channel::{
+		error::Error,
+		msgs::recv_packets::{test_util::get_dummy_raw_msg_recv_packets, MsgRecvPackets},
+	};
+
+	#[test]
+	fn msg_recv_packets_try_from_raw() {
+		struct Test {
+			name: String,
+			raw: RawMsgRecvPackets,
+			want_pass: bool,
+		}
+
+		let height = 20;
+		let default_raw_msg = get_dummy_raw_msg_recv_packets(height, 3);
+
+		let mut duplicate_sequence = default_raw_msg.clone();
+		duplicate_sequence.packets[1].sequence = 1;
+
+		let mut mixed_channels = default_raw_msg.clone();
+		mixed_channels.packets[2].destination_channel = "channel-1".to_string();
+
+		let tests: Vec<Test> = vec![
+			Test {
+				name: "Good parameters".to_string(),
+				raw: default_raw_msg.clone(),
+				want_pass: true,
+			},
+			Test {
+				name: "No packets".to_string(),
+				raw: RawMsgRecvPackets { packets: Vec::new(), ..default_raw_msg.clone() },
+				want_pass: false,
+			},
+			Test {
+				name: "Missing proof".to_string(),
+				raw: RawMsgRecvPackets { proof_commitment: Vec::new(), ..default_raw_msg.clone() },
+				want_pass: false,
+			},
+			Test {
+				name: "Missing proof height".to_string(),
+				raw: RawMsgRecvPackets { proof_height: None, ..default_raw_msg },
+				want_pass: false,
+			},
+			Test {
+				name: "Duplicate sequence".to_string(),
+				raw: duplicate_sequence,
+				want_pass: false,
+			},
+			Test {
+				name: "Packets received on different channels".to_string(),
+				raw: mixed_channels,
+				want_pass: false,
+			},
+		];
+
+		for test in tests {
+			let res_msg: Result<MsgRecvPackets, Error> = test.raw.clone().try_into();
+
+			assert_eq!(
+				res_msg.is_ok(),
+				test.want_pass,
+				"MsgRecvPackets::try_from failed for test {} \nraw message: {:?} with error: {:?}",
+				test.name,
+				test.raw,
+				res_msg.err()
+			);
+		}
+	}
+
+	#[test]
+	fn to_and_from() {
+		let raw = get_dummy_raw_msg_recv_packets(15, 2);
+		let msg = MsgRecvPackets::try_from(raw.clone()).unwrap();
+		let raw_back = RawMsgRecvPackets::from(msg.clone());
+		let msg_back = MsgRecvPackets::try_from(raw_back.clone()).unwrap();
+		assert_eq!(raw, raw_back);
+		assert_eq!(msg, msg_back);
+	}
+}
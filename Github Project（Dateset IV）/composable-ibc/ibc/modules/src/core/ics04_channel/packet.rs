@@ -22,6 +22,7 @@ use ibc_proto::ibc::core::channel::v1::Packet as RawPacket;
 
 use crate::{
 	core::{
+		ics02_client::{context::ClientTypes, handler::update_client::Result as UpdateResult},
 		ics04_channel::error::Error,
 		ics24_host::identifier::{ChannelId, PortId},
 	},
@@ -46,17 +47,49 @@ pub enum PacketMsgType {
 }
 
 #[derive(Clone, Debug)]
-pub enum PacketResult {
+pub enum PacketResult<C: ClientTypes> {
 	Send(SendPacketResult),
-	Recv(RecvPacketResult),
+	Recv(RecvPacketResult<C>),
 	WriteAck(WriteAckPacketResult),
-	Ack(AckPacketResult),
-	Timeout(TimeoutPacketResult),
+	Ack(AckPacketResult<C>),
+	Timeout(TimeoutPacketResult<C>),
+}
+
+impl<C: ClientTypes> PacketResult<C> {
+	/// Takes the state of the client advanced by the proofs of the packet message out of the
+	/// result.
+	pub fn take_client_update(&mut self) -> Option<UpdateResult<C>> {
+		match self {
+			PacketResult::Recv(
+				RecvPacketResult::Unordered { client_update, .. } |
+				RecvPacketResult::Ordered { client_update, .. } |
+				RecvPacketResult::Timeout { client_update, .. },
+			) => client_update.take(),
+			PacketResult::Ack(res) => res.client_update.take(),
+			PacketResult::Timeout(res) => res.client_update.take(),
+			PacketResult::Send(_) |
+			PacketResult::WriteAck(_) |
+			PacketResult::Recv(RecvPacketResult::NoOp) => None,
+		}
+	}
 }
 
 #[derive(Clone, Debug)]
 pub enum Receipt {
 	Ok,
+	/// Written on `ORDERED_ALLOW_TIMEOUT` channels in place of receiving a packet that has
+	/// already timed out, so that the sender can prove the timeout.
+	Timeout,
+}
+
+impl Receipt {
+	/// The value stored under the packet receipt path.
+	pub fn as_bytes(&self) -> &'static [u8] {
+		match self {
+			Receipt::Ok => b"Ok",
+			Receipt::Timeout => b"Timeout",
+		}
+	}
 }
 
 impl core::fmt::Display for PacketMsgType {
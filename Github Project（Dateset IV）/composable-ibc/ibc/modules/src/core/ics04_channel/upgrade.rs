@@ -0,0 +1,246 @@
+// Copyright 2022 ComposableFi
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Data types describing a channel upgrade, as negotiated by the `ChanUpgrade*` handshake.
+
+use crate::prelude::*;
+
+use core::str::FromStr;
+
+use tendermint_proto::Protobuf;
+
+use ibc_proto::ibc::core::channel::v1::{
+	ErrorReceipt as RawErrorReceipt, Timeout as RawTimeout, Upgrade as RawUpgrade,
+	UpgradeFields as RawUpgradeFields,
+};
+
+use crate::{
+	core::{
+		ics02_client::height::Height,
+		ics04_channel::{
+			channel::{ChannelEnd, Order},
+			error::Error,
+			packet::Sequence,
+			Version,
+		},
+		ics24_host::identifier::ConnectionId,
+	},
+	timestamp::{Expiry::Expired, Timestamp},
+};
+
+/// The channel parameters that may be modified by an upgrade.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct UpgradeFields {
+	pub ordering: Order,
+	pub connection_hops: Vec<ConnectionId>,
+	pub version: Version,
+}
+
+impl UpgradeFields {
+	pub fn new(ordering: Order, connection_hops: Vec<ConnectionId>, version: Version) -> Self {
+		Self { ordering, connection_hops, version }
+	}
+
+	/// Returns `true` if these fields are identical to the ones the channel end currently uses.
+	pub fn matches_channel(&self, channel_end: &ChannelEnd) -> bool {
+		channel_end.order_matches(&self.ordering) &&
+			channel_end.connection_hops_matches(&self.connection_hops) &&
+			channel_end.version_matches(&self.version)
+	}
+
+	pub fn validate_basic(&self) -> Result<(), Error> {
+		if self.connection_hops.len() != 1 {
+			return Err(Error::invalid_connection_hops_length(1, self.connection_hops.len()))
+		}
+		if self.version.to_string().trim().is_empty() {
+			return Err(Error::incompatible_upgrade("upgrade version cannot be empty".to_string()))
+		}
+		Ok(())
+	}
+}
+
+impl Protobuf<RawUpgradeFields> for UpgradeFields {}
+
+impl TryFrom<RawUpgradeFields> for UpgradeFields {
+	type Error = Error;
+
+	fn try_from(value: RawUpgradeFields) -> Result<Self, Self::Error> {
+		let ordering = Order::from_i32(value.ordering)?;
+		let connection_hops = value
+			.connection_hops
+			.into_iter()
+			.map(|conn_id| ConnectionId::from_str(conn_id.as_str()))
+			.collect::<Result<Vec<_>, _>>()
+			.map_err(Error::identifier)?;
+
+		Ok(UpgradeFields::new(ordering, connection_hops, value.version.into()))
+	}
+}
+
+impl From<UpgradeFields> for RawUpgradeFields {
+	fn from(value: UpgradeFields) -> Self {
+		RawUpgradeFields {
+			ordering: value.ordering as i32,
+			connection_hops: value.connection_hops.iter().map(|v| v.as_str().to_string()).collect(),
+			version: value.version.to_string(),
+		}
+	}
+}
+
+/// The point after which a counterparty may time out an upgrade that is still flushing.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct UpgradeTimeout {
+	pub height: Height,
+	pub timestamp: Timestamp,
+}
+
+impl UpgradeTimeout {
+	pub fn new(height: Height, timestamp: Timestamp) -> Result<Self, Error> {
+		if height == Height::zero() && timestamp == Timestamp::none() {
+			return Err(Error::invalid_upgrade_timeout())
+		}
+		Ok(Self { height, timestamp })
+	}
+
+	/// Returns `true` if the timeout has elapsed for a chain at `height` and `timestamp`.
+	pub fn has_passed(&self, height: Height, timestamp: &Timestamp) -> bool {
+		(self.height != Height::zero() && self.height <= height) ||
+			(self.timestamp != Timestamp::none() &&
+				timestamp.check_expiry(&self.timestamp) == Expired)
+	}
+}
+
+impl Protobuf<RawTimeout> for UpgradeTimeout {}
+
+impl TryFrom<RawTimeout> for UpgradeTimeout {
+	type Error = Error;
+
+	fn try_from(value: RawTimeout) -> Result<Self, Self::Error> {
+		let height = value.height.map(Height::from).unwrap_or_else(Height::zero);
+		let timestamp = Timestamp::from_nanoseconds(value.timestamp)
+			.map_err(Error::invalid_packet_timestamp)?;
+		UpgradeTimeout::new(height, timestamp)
+	}
+}
+
+impl From<UpgradeTimeout> for RawTimeout {
+	fn from(value: UpgradeTimeout) -> Self {
+		RawTimeout { height: Some(value.height.into()), timestamp: value.timestamp.nanoseconds() }
+	}
+}
+
+/// An upgrade proposed for a channel end.
+///
+/// The upgrade written by `ChanUpgradeInit` only carries the proposed fields, the timeout and
+/// the next send sequence are recorded once the channel end starts flushing.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Upgrade {
+	pub fields: UpgradeFields,
+	pub timeout: Option<UpgradeTimeout>,
+	pub next_sequence_send: Sequence,
+}
+
+impl Upgrade {
+	pub fn new(fields: UpgradeFields) -> Self {
+		Self { fields, timeout: None, next_sequence_send: Sequence::default() }
+	}
+}
+
+impl Protobuf<RawUpgrade> for Upgrade {}
+
+impl TryFrom<RawUpgrade> for Upgrade {
+	type Error = Error;
+
+	fn try_from(value: RawUpgrade) -> Result<Self, Self::Error> {
+		let fields = value.fields.ok_or_else(Error::missing_upgrade_fields)?.try_into()?;
+		let timeout = match value.timeout {
+			Some(timeout) if timeout.height.is_some() || timeout.timestamp != 0 =>
+				Some(timeout.try_into()?),
+			_ => None,
+		};
+
+		Ok(Upgrade { fields, timeout, next_sequence_send: value.next_sequence_send.into() })
+	}
+}
+
+impl From<Upgrade> for RawUpgrade {
+	fn from(value: Upgrade) -> Self {
+		RawUpgrade {
+			fields: Some(value.fields.into()),
+			timeout: value.timeout.map(Into::into),
+			next_sequence_send: value.next_sequence_send.into(),
+		}
+	}
+}
+
+/// Written when an upgrade is aborted, so that the counterparty can cancel its own upgrade.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ErrorReceipt {
+	pub sequence: u64,
+	pub message: String,
+}
+
+impl ErrorReceipt {
+	pub fn new(sequence: u64, message: String) -> Self {
+		Self { sequence, message }
+	}
+}
+
+impl Protobuf<RawErrorReceipt> for ErrorReceipt {}
+
+impl TryFrom<RawErrorReceipt> for ErrorReceipt {
+	type Error = Error;
+
+	fn try_from(value: RawErrorReceipt) -> Result<Self, Self::Error> {
+		Ok(ErrorReceipt::new(value.sequence, value.message))
+	}
+}
+
+impl From<ErrorReceipt> for RawErrorReceipt {
+	fn from(value: ErrorReceipt) -> Self {
+		RawErrorReceipt { sequence: value.sequence, message: value.message }
+	}
+}
+
+#[cfg(test)]
+pub mod test_util {
+	use crate::{core::ics24_host::identifier::ConnectionId, prelude::*};
+	use ibc_proto::ibc::core::{
+		channel::v1::{
+			Timeout as RawTimeout, Upgrade as RawUpgrade, UpgradeFields as RawUpgradeFields,
+		},
+		client::v1::Height as RawHeight,
+	};
+
+	/// Returns dummy `RawUpgradeFields`, for testing only!
+	pub fn get_dummy_raw_upgrade_fields() -> RawUpgradeFields {
+		RawUpgradeFields {
+			ordering: 2,
+			connection_hops: vec![ConnectionId::default().to_string()],
+			version: "ics20-2".to_string(),
+		}
+	}
+
+	/// Returns a dummy `RawUpgrade` that has started flushing, for testing only!
+	pub fn get_dummy_raw_upgrade() -> RawUpgrade {
+		RawUpgrade {
+			fields: Some(get_dummy_raw_upgrade_fields()),
+			timeout: Some(RawTimeout {
+				height: Some(RawHeight { revision_number: 0, revision_height: 0 }),
+				timestamp: u64::MAX,
+			}),
+			next_sequence_send: 1,
+		}
+	}
+}
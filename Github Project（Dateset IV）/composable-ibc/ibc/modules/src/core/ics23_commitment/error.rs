@@ -12,6 +12,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use crate::prelude::*;
 use flex_error::{define_error, TraceError};
 use prost::DecodeError;
 
@@ -38,6 +39,13 @@ define_error! {
 		EmptyVerifiedValue
 			|_| { "empty verified value" },
 
+		EmptyBatch
+			|_| { "empty batch of keys to verify" },
+
+		DuplicateBatchKey
+			{ key: String }
+			|e| { format_args!("duplicate key {} in batch", e.key) },
+
 		NumberOfSpecsMismatch
 			|_| { "mismatch between the number of proofs with that of specs" },
 
@@ -20,11 +20,13 @@ use crate::{
 	},
 	prelude::*,
 };
+use alloc::collections::BTreeMap;
 use core::marker::PhantomData;
 use ibc_proto::ibc::core::commitment::v1::{MerklePath, MerkleProof as RawMerkleProof, MerkleRoot};
 use ics23::{
-	calculate_existence_root, commitment_proof::Proof, verify_membership, verify_non_membership,
-	CommitmentProof, HostFunctionsProvider, NonExistenceProof,
+	batch_entry, calculate_existence_root, commitment_proof::Proof, decompress,
+	verify_batch_membership, verify_membership, verify_non_membership, CommitmentProof,
+	HostFunctionsProvider, NonExistenceProof,
 };
 
 pub fn apply_prefix(prefix: &CommitmentPrefix, mut path: Vec<String>) -> MerklePath {
@@ -178,6 +180,56 @@ impl<H: HostFunctionsProvider> MerkleProof<H> {
 			_ => Err(Error::invalid_merkle_proof()),
 		}
 	}
+
+	/// Verifies that every `(key, value)` pair in `items` is a member of the lowest subtree with a
+	/// single, possibly compressed, batch proof, and that this subtree is committed under `root` at
+	/// the given `prefix`.
+	pub fn verify_batch_membership(
+		&self,
+		specs: &ProofSpecs,
+		root: MerkleRoot,
+		prefix: &CommitmentPrefix,
+		items: Vec<(String, Vec<u8>)>,
+	) -> Result<(), Error> {
+		// validate arguments
+		if self.proofs.is_empty() {
+			return Err(Error::empty_merkle_proof())
+		}
+		if root.hash.is_empty() {
+			return Err(Error::empty_merkle_root())
+		}
+		let num = self.proofs.len();
+		let ics23_specs = Vec::<ics23::ProofSpec>::from(specs.clone());
+		if ics23_specs.len() != num {
+			return Err(Error::number_of_specs_mismatch())
+		}
+		let (first_key, _) = items.first().ok_or_else(Error::empty_batch)?;
+		// every key shares the same path above the lowest subtree
+		let keys = apply_prefix(prefix, vec![first_key.clone()]);
+		if keys.key_path.len() != num {
+			return Err(Error::number_of_keys_mismatch())
+		}
+
+		let mut batch = BTreeMap::new();
+		for (key, value) in items.iter() {
+			if value.is_empty() {
+				return Err(Error::empty_verified_value())
+			}
+			if batch.insert(key.as_bytes(), value.as_slice()).is_some() {
+				return Err(Error::duplicate_batch_key(key.clone()))
+			}
+		}
+
+		// verify the presence of all keys in lowest subtree
+		let proof = self.proofs.get(0).ok_or_else(Error::invalid_merkle_proof)?;
+		let spec = ics23_specs.get(0).ok_or_else(Error::invalid_merkle_proof)?;
+		let subroot = calculate_batch_root::<H>(proof)?;
+		if !verify_batch_membership::<H>(proof, spec, &subroot, batch) {
+			return Err(Error::verification_failure())
+		}
+		// verify membership proofs starting from index 1 with value = subroot
+		self.verify_membership(specs, root, keys, subroot, 1)
+	}
 }
 
 // TODO move to ics23
@@ -193,6 +245,26 @@ fn calculate_non_existence_root<H: HostFunctionsProvider>(
 	}
 }
 
+// TODO move to ics23
+fn calculate_batch_root<H: HostFunctionsProvider>(
+	proof: &CommitmentProof,
+) -> Result<Vec<u8>, Error> {
+	let proof = decompress(proof).map_err(|_| Error::invalid_merkle_proof())?;
+	let existence_proof = match &proof.proof {
+		Some(Proof::Exist(existence_proof)) => existence_proof,
+		Some(Proof::Batch(batch_proof)) => batch_proof
+			.entries
+			.iter()
+			.find_map(|entry| match &entry.proof {
+				Some(batch_entry::Proof::Exist(existence_proof)) => Some(existence_proof),
+				_ => None,
+			})
+			.ok_or_else(Error::invalid_merkle_proof)?,
+		_ => return Err(Error::invalid_merkle_proof()),
+	};
+	calculate_existence_root::<H>(existence_proof).map_err(|_| Error::invalid_merkle_proof())
+}
+
 // Merkle Proof serialization notes:
 // "Proof" id currently defined in a number of forms and included in a number of places
 // - TmProof: in tendermint-rs/src/merkle/proof.rs:Proof
@@ -42,6 +42,16 @@ const UPGRADED_CLIENT_STATE: &str = "upgradedClient";
 /// - The key identifying the upgraded consensus state
 const UPGRADED_CLIENT_CONSENSUS_STATE: &str = "upgradedConsState";
 
+/// Channel upgrade keys
+/// - The key prefixing all channel upgrade paths
+const CHANNEL_UPGRADES: &str = "channelUpgrades";
+/// - The key identifying the upgrade proposed by a channel end
+const UPGRADES: &str = "upgrades";
+/// - The key identifying the upgrade proposed by the counterparty of a channel end
+const COUNTERPARTY_UPGRADE: &str = "counterpartyUpgrade";
+/// - The key identifying the error receipt of an aborted upgrade
+const UPGRADE_ERROR: &str = "upgradeError";
+
 /// The Path enum abstracts out the different sub-paths.
 #[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, From, Display)]
 pub enum Path {
@@ -58,6 +68,9 @@ pub enum Path {
 	Commitments(CommitmentsPath),
 	Acks(AcksPath),
 	Receipts(ReceiptsPath),
+	ChannelUpgrades(ChannelUpgradePath),
+	CounterpartyChannelUpgrades(CounterpartyChannelUpgradePath),
+	ChannelUpgradeErrors(ChannelUpgradeErrorPath),
 	Upgrade(ClientUpgradePath),
 	Outside(OutsidePath),
 }
@@ -141,6 +154,18 @@ pub struct ReceiptsPath {
 	pub sequence: Sequence,
 }
 
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Display)]
+#[display(fmt = "{}/{}/ports/{}/channels/{}", CHANNEL_UPGRADES, UPGRADES, _0, _1)]
+pub struct ChannelUpgradePath(pub PortId, pub ChannelId);
+
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Display)]
+#[display(fmt = "{}/{}/ports/{}/channels/{}", CHANNEL_UPGRADES, COUNTERPARTY_UPGRADE, _0, _1)]
+pub struct CounterpartyChannelUpgradePath(pub PortId, pub ChannelId);
+
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Display)]
+#[display(fmt = "{}/{}/ports/{}/channels/{}", CHANNEL_UPGRADES, UPGRADE_ERROR, _0, _1)]
+pub struct ChannelUpgradeErrorPath(pub PortId, pub ChannelId);
+
 /// Paths that are specific for client upgrades.
 #[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Display)]
 pub enum ClientUpgradePath {
@@ -194,6 +219,7 @@ impl FromStr for Path {
 			.or_else(|| parse_commitments(&components))
 			.or_else(|| parse_acks(&components))
 			.or_else(|| parse_receipts(&components))
+			.or_else(|| parse_channel_upgrades(&components))
 			.or_else(|| parse_upgrades(&components))
 			.or_else(|| parse_outside_paths(&components))
 			.ok_or_else(|| PathError::parse_failure(s.to_string()))
@@ -507,6 +533,37 @@ fn parse_receipts(components: &[&str]) -> Option<Path> {
 	Some(ReceiptsPath { port_id, channel_id, sequence }.into())
 }
 
+fn parse_channel_upgrades(components: &[&str]) -> Option<Path> {
+	if components.len() != 6 {
+		return None
+	}
+
+	let first = match components.first() {
+		Some(f) => *f,
+		None => return None,
+	};
+
+	if first != CHANNEL_UPGRADES {
+		return None
+	}
+
+	let port = parse_ports(&components[2..=3]);
+	let channel = parse_channels(&components[4..=5]);
+
+	let port_id =
+		if let Some(Path::Ports(PortsPath(port_id))) = port { port_id } else { return None };
+
+	let channel_id =
+		if let Some(SubPath::Channels(channel_id)) = channel { channel_id } else { return None };
+
+	match components[1] {
+		UPGRADES => Some(ChannelUpgradePath(port_id, channel_id).into()),
+		COUNTERPARTY_UPGRADE => Some(CounterpartyChannelUpgradePath(port_id, channel_id).into()),
+		UPGRADE_ERROR => Some(ChannelUpgradeErrorPath(port_id, channel_id).into()),
+		_ => None,
+	}
+}
+
 fn parse_upgrades(components: &[&str]) -> Option<Path> {
 	if components.len() != 3 {
 		return None
@@ -876,6 +933,33 @@ mod tests {
 		);
 	}
 
+	#[test]
+	fn channel_upgrade_paths_parse() {
+		for (path, expected) in [
+			(
+				"channelUpgrades/upgrades/ports/defaultPort/channels/channel-0",
+				Path::ChannelUpgrades(ChannelUpgradePath(PortId::default(), ChannelId::default())),
+			),
+			(
+				"channelUpgrades/counterpartyUpgrade/ports/defaultPort/channels/channel-0",
+				Path::CounterpartyChannelUpgrades(CounterpartyChannelUpgradePath(
+					PortId::default(),
+					ChannelId::default(),
+				)),
+			),
+			(
+				"channelUpgrades/upgradeError/ports/defaultPort/channels/channel-0",
+				Path::ChannelUpgradeErrors(ChannelUpgradeErrorPath(
+					PortId::default(),
+					ChannelId::default(),
+				)),
+			),
+		] {
+			assert_eq!(Path::from_str(path).unwrap(), expected);
+			assert_eq!(expected.to_string(), path);
+		}
+	}
+
 	#[test]
 	fn test_parse_upgrades_fn() {
 		let path = "upgradedIBCState/0/upgradedClient";
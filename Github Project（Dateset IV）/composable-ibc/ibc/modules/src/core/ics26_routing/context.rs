@@ -103,6 +103,22 @@ pub trait Acknowledgement: AsRef<[u8]> {}
 
 pub type ModuleOutputBuilder = HandlerOutputBuilder<(), IbcEvent>;
 
+/// Acknowledgement returned by a module when it receives a packet
+#[derive(Clone, Debug, PartialEq)]
+pub enum OnRecvPacketAck {
+	/// The acknowledgement is written as soon as the packet is received
+	Sync(GenericAcknowledgement),
+	/// The packet is still being processed, the module writes the acknowledgement later using
+	/// the `write_acknowledgement` handler
+	Async,
+}
+
+impl From<GenericAcknowledgement> for OnRecvPacketAck {
+	fn from(ack: GenericAcknowledgement) -> Self {
+		Self::Sync(ack)
+	}
+}
+
 pub trait Module: Send + Sync + AsAnyMut {
 	#[allow(clippy::too_many_arguments)]
 	fn on_chan_open_init(
@@ -180,15 +196,84 @@ pub trait Module: Send + Sync + AsAnyMut {
 		Ok(())
 	}
 
-	/// Modules can choose to write acknowledgement to storage in this callback
+	/// Called when this chain proposes an upgrade of the channel, returns the version the upgraded
+	/// channel should use. Upgrades are rejected unless the module opts into them.
+	#[allow(clippy::too_many_arguments)]
+	fn on_chan_upgrade_init(
+		&mut self,
+		_ctx: &dyn ModuleCallbackContext,
+		_output: &mut ModuleOutputBuilder,
+		port_id: &PortId,
+		_channel_id: &ChannelId,
+		_proposed_order: Order,
+		_proposed_connection_hops: &[ConnectionId],
+		_proposed_version: &Version,
+	) -> Result<Version, Error> {
+		Err(Error::upgrade_not_supported(port_id.clone()))
+	}
+
+	/// Called when the counterparty proposes an upgrade of the channel, returns the version the
+	/// upgraded channel should use. Upgrades are rejected unless the module opts into them.
+	#[allow(clippy::too_many_arguments)]
+	fn on_chan_upgrade_try(
+		&mut self,
+		_ctx: &dyn ModuleCallbackContext,
+		_output: &mut ModuleOutputBuilder,
+		port_id: &PortId,
+		_channel_id: &ChannelId,
+		_proposed_order: Order,
+		_proposed_connection_hops: &[ConnectionId],
+		_counterparty_version: &Version,
+	) -> Result<Version, Error> {
+		Err(Error::upgrade_not_supported(port_id.clone()))
+	}
+
+	fn on_chan_upgrade_ack(
+		&mut self,
+		_ctx: &dyn ModuleCallbackContext,
+		_output: &mut ModuleOutputBuilder,
+		_port_id: &PortId,
+		_channel_id: &ChannelId,
+		_counterparty_version: &Version,
+	) -> Result<(), Error> {
+		Ok(())
+	}
+
+	/// Called once the upgraded channel is open, with the fields it now uses.
+	#[allow(clippy::too_many_arguments)]
+	fn on_chan_upgrade_open(
+		&mut self,
+		_ctx: &dyn ModuleCallbackContext,
+		_output: &mut ModuleOutputBuilder,
+		_port_id: &PortId,
+		_channel_id: &ChannelId,
+		_order: Order,
+		_connection_hops: &[ConnectionId],
+		_version: &Version,
+	) -> Result<(), Error> {
+		Ok(())
+	}
+
+	/// Modules can choose to write acknowledgement to storage in this callback, or return
+	/// [`OnRecvPacketAck::Async`] to write it once they are done processing the packet
 	fn on_recv_packet(
 		&self,
 		_ctx: &dyn ModuleCallbackContext,
 		_output: &mut ModuleOutputBuilder,
 		_packet: &mut Packet,
 		_relayer: &Signer,
+	) -> Result<OnRecvPacketAck, Error> {
+		Ok(OnRecvPacketAck::Sync(GenericAcknowledgement::from_bytes(Default::default())))
+	}
+
+	/// Called with an acknowledgement the module returned as [`OnRecvPacketAck::Async`] before it
+	/// is written, middlewares can use it to modify the acknowledgement of the module they wrap
+	fn on_write_acknowledgement(
+		&self,
+		_packet: &Packet,
+		ack: GenericAcknowledgement,
 	) -> Result<GenericAcknowledgement, Error> {
-		Ok(GenericAcknowledgement::from_bytes(Default::default()))
+		Ok(ack)
 	}
 
 	fn on_acknowledgement_packet(
@@ -21,21 +21,26 @@ use crate::{
 		ics03_connection::handler::dispatch as ics3_msg_dispatcher,
 		ics04_channel::{
 			handler::{
+				batched_recv_packet_dispatch as ics4_batched_recv_packet_dispatcher,
 				channel_callback as ics4_callback, channel_dispatch as ics4_msg_dispatcher,
 				channel_validate as ics4_validate, get_module_for_packet_msg,
 				packet_callback as ics4_packet_callback,
 				packet_dispatch as ics4_packet_msg_dispatcher, recv_packet::RecvPacketResult,
+				recv_packets::process as ics4_recv_packets_verifier,
 			},
+			msgs::PacketMsg,
 			packet::PacketResult,
 		},
 		ics26_routing::{
-			context::{Ics26Context, ModuleOutputBuilder, ReaderContext},
+			context::{Ics26Context, ModuleId, ModuleOutputBuilder, ReaderContext},
 			error::Error,
-			msgs::Ics26Envelope::{self, Ics2Msg, Ics3Msg, Ics4ChannelMsg, Ics4PacketMsg},
+			msgs::Ics26Envelope::{
+				self, Ics2Msg, Ics3Msg, Ics4ChannelMsg, Ics4PacketBatchMsg, Ics4PacketMsg,
+			},
 		},
 	},
 	events::IbcEvent,
-	handler::HandlerOutput,
+	handler::{HandlerOutput, HandlerOutputBuilder},
 	prelude::*,
 };
 use core::fmt::Debug;
@@ -133,20 +138,32 @@ where
 
 		Ics4PacketMsg(msg) => {
 			let module_id = get_module_for_packet_msg(ctx, &msg).map_err(Error::ics04_channel)?;
-			let (mut handler_builder, packet_result) =
+			let (handler_builder, packet_result) =
 				ics4_packet_msg_dispatcher::<_>(ctx, &msg).map_err(Error::ics04_channel)?;
 
-			if matches!(packet_result, PacketResult::Recv(RecvPacketResult::NoOp)) {
-				return Ok(handler_builder.with_result(()))
-			}
-
-			let mut module_output = ModuleOutputBuilder::new();
-			let cb_result = ics4_packet_callback(ctx, &module_id, &msg, &mut module_output);
-			handler_builder.merge(module_output);
-			cb_result.map_err(Error::ics04_channel)?;
+			apply_packet_result(ctx, &module_id, &msg, handler_builder, packet_result)?
+		},
 
-			// Apply any results to the host chain store.
-			ctx.store_packet_result(packet_result).map_err(Error::ics04_channel)?;
+		Ics4PacketBatchMsg(msg) => {
+			// The batch proof is verified once, then every packet is received one after the other
+			// exactly like a single `MsgRecvPacket`.
+			let batch_output =
+				ics4_recv_packets_verifier::<_>(ctx, &msg).map_err(Error::ics04_channel)?;
+			let mut handler_builder = HandlerOutput::builder();
+			handler_builder.merge_output(batch_output);
+
+			for msg in msg.recv_packet_msgs() {
+				let (packet_builder, packet_result) =
+					ics4_batched_recv_packet_dispatcher::<_>(ctx, &msg)
+						.map_err(Error::ics04_channel)?;
+				let msg = PacketMsg::RecvPacket(msg);
+				let module_id =
+					get_module_for_packet_msg(ctx, &msg).map_err(Error::ics04_channel)?;
+
+				let packet_output =
+					apply_packet_result(ctx, &module_id, &msg, packet_builder, packet_result)?;
+				handler_builder.merge_output(packet_output);
+			}
 
 			handler_builder.with_result(())
 		},
@@ -155,6 +172,39 @@ where
 	Ok(output)
 }
 
+/// Delivers a processed packet message to its module and applies the result to the host chain
+/// store.
+fn apply_packet_result<Ctx>(
+	ctx: &mut Ctx,
+	module_id: &ModuleId,
+	msg: &PacketMsg,
+	mut handler_builder: HandlerOutputBuilder<()>,
+	packet_result: PacketResult<Ctx>,
+) -> Result<HandlerOutput<()>, Error>
+where
+	Ctx: Ics26Context + ClientKeeper,
+{
+	if matches!(packet_result, PacketResult::Recv(RecvPacketResult::NoOp)) {
+		return Ok(handler_builder.with_result(()))
+	}
+
+	// A timed out packet is never delivered to the module, only its receipt is stored.
+	if matches!(packet_result, PacketResult::Recv(RecvPacketResult::Timeout { .. })) {
+		ctx.store_packet_result(packet_result).map_err(Error::ics04_channel)?;
+		return Ok(handler_builder.with_result(()))
+	}
+
+	let mut module_output = ModuleOutputBuilder::new();
+	let cb_result = ics4_packet_callback(ctx, module_id, msg, &mut module_output);
+	handler_builder.merge(module_output);
+	cb_result.map_err(Error::ics04_channel)?;
+
+	// Apply any results to the host chain store.
+	ctx.store_packet_result(packet_result).map_err(Error::ics04_channel)?;
+
+	Ok(handler_builder.with_result(()))
+}
+
 #[cfg(test)]
 mod tests {
 	use crate::prelude::*;
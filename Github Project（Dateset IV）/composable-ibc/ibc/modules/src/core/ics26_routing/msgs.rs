@@ -19,9 +19,9 @@ use ibc_proto::google::protobuf::Any;
 
 use crate::core::{
 	ics02_client::msgs::{
-		create_client, create_client::MsgCreateAnyClient, update_client,
-		update_client::MsgUpdateAnyClient, upgrade_client, upgrade_client::MsgUpgradeAnyClient,
-		ClientMsg,
+		create_client, create_client::MsgCreateAnyClient, misbehaviour,
+		misbehaviour::MsgSubmitAnyMisbehaviour, update_client, update_client::MsgUpdateAnyClient,
+		upgrade_client, upgrade_client::MsgUpgradeAnyClient, ClientMsg,
 	},
 	ics03_connection::msgs::{
 		conn_open_ack, conn_open_ack::MsgConnectionOpenAck, conn_open_confirm, conn_open_init,
@@ -29,7 +29,9 @@ use crate::core::{
 	},
 	ics04_channel::msgs::{
 		acknowledgement, chan_close_confirm, chan_close_init, chan_open_ack, chan_open_confirm,
-		chan_open_init, chan_open_try, recv_packet, timeout, timeout_on_close, ChannelMsg,
+		chan_open_init, chan_open_try, chan_upgrade_ack, chan_upgrade_cancel, chan_upgrade_confirm,
+		chan_upgrade_init, chan_upgrade_open, chan_upgrade_timeout, chan_upgrade_try, recv_packet,
+		recv_packets, recv_packets::MsgRecvPackets, timeout, timeout_on_close, ChannelMsg,
 		PacketMsg,
 	},
 	ics26_routing::error::Error,
@@ -37,7 +39,7 @@ use crate::core::{
 
 use crate::core::ics02_client::context::ClientTypes;
 use ibc_proto::ibc::core::{
-	client::v1::{MsgCreateClient, MsgUpdateClient, MsgUpgradeClient},
+	client::v1::{MsgCreateClient, MsgSubmitMisbehaviour, MsgUpdateClient, MsgUpgradeClient},
 	connection,
 };
 use tendermint_proto::Protobuf;
@@ -52,6 +54,7 @@ where
 	Ics3Msg(ConnectionMsg<C>),
 	Ics4ChannelMsg(ChannelMsg),
 	Ics4PacketMsg(PacketMsg),
+	Ics4PacketBatchMsg(MsgRecvPackets),
 }
 
 impl<C> TryFrom<Any> for Ics26Envelope<C>
@@ -69,6 +72,9 @@ where
 	MsgUpgradeAnyClient<C>: TryFrom<MsgUpgradeClient>,
 	<MsgUpgradeAnyClient<C> as TryFrom<MsgUpgradeClient>>::Error: Display,
 	MsgUpgradeAnyClient<C>: Protobuf<MsgUpgradeClient>,
+	MsgSubmitAnyMisbehaviour<C>: TryFrom<MsgSubmitMisbehaviour>,
+	<MsgSubmitAnyMisbehaviour<C> as TryFrom<MsgSubmitMisbehaviour>>::Error: Display,
+	MsgSubmitAnyMisbehaviour<C>: Protobuf<MsgSubmitMisbehaviour>,
 	MsgConnectionOpenTry<C>: TryFrom<connection::v1::MsgConnectionOpenTry>,
 	<MsgConnectionOpenTry<C> as TryFrom<connection::v1::MsgConnectionOpenTry>>::Error: Display,
 	MsgConnectionOpenTry<C>: Protobuf<connection::v1::MsgConnectionOpenTry>,
@@ -99,6 +105,11 @@ where
 						.map_err(Error::malformed_message_bytes)?;
 				Ok(Ics26Envelope::Ics2Msg(ClientMsg::UpgradeClient(domain_msg)))
 			},
+			misbehaviour::TYPE_URL => {
+				let domain_msg = MsgSubmitAnyMisbehaviour::<C>::decode_vec(&any_msg.value)
+					.map_err(Error::malformed_message_bytes)?;
+				Ok(Ics26Envelope::Ics2Msg(ClientMsg::Misbehaviour(domain_msg)))
+			},
 
 			// ICS03
 			conn_open_init::TYPE_URL => {
@@ -156,12 +167,58 @@ where
 						.map_err(Error::malformed_message_bytes)?;
 				Ok(Ics26Envelope::Ics4ChannelMsg(ChannelMsg::ChannelCloseConfirm(domain_msg)))
 			},
+			// ICS04 channel upgrade messages
+			chan_upgrade_init::TYPE_URL => {
+				let domain_msg =
+					chan_upgrade_init::MsgChannelUpgradeInit::decode_vec(&any_msg.value)
+						.map_err(Error::malformed_message_bytes)?;
+				Ok(Ics26Envelope::Ics4ChannelMsg(ChannelMsg::ChannelUpgradeInit(domain_msg)))
+			},
+			chan_upgrade_try::TYPE_URL => {
+				let domain_msg = chan_upgrade_try::MsgChannelUpgradeTry::decode_vec(&any_msg.value)
+					.map_err(Error::malformed_message_bytes)?;
+				Ok(Ics26Envelope::Ics4ChannelMsg(ChannelMsg::ChannelUpgradeTry(domain_msg)))
+			},
+			chan_upgrade_ack::TYPE_URL => {
+				let domain_msg = chan_upgrade_ack::MsgChannelUpgradeAck::decode_vec(&any_msg.value)
+					.map_err(Error::malformed_message_bytes)?;
+				Ok(Ics26Envelope::Ics4ChannelMsg(ChannelMsg::ChannelUpgradeAck(domain_msg)))
+			},
+			chan_upgrade_confirm::TYPE_URL => {
+				let domain_msg =
+					chan_upgrade_confirm::MsgChannelUpgradeConfirm::decode_vec(&any_msg.value)
+						.map_err(Error::malformed_message_bytes)?;
+				Ok(Ics26Envelope::Ics4ChannelMsg(ChannelMsg::ChannelUpgradeConfirm(domain_msg)))
+			},
+			chan_upgrade_open::TYPE_URL => {
+				let domain_msg =
+					chan_upgrade_open::MsgChannelUpgradeOpen::decode_vec(&any_msg.value)
+						.map_err(Error::malformed_message_bytes)?;
+				Ok(Ics26Envelope::Ics4ChannelMsg(ChannelMsg::ChannelUpgradeOpen(domain_msg)))
+			},
+			chan_upgrade_timeout::TYPE_URL => {
+				let domain_msg =
+					chan_upgrade_timeout::MsgChannelUpgradeTimeout::decode_vec(&any_msg.value)
+						.map_err(Error::malformed_message_bytes)?;
+				Ok(Ics26Envelope::Ics4ChannelMsg(ChannelMsg::ChannelUpgradeTimeout(domain_msg)))
+			},
+			chan_upgrade_cancel::TYPE_URL => {
+				let domain_msg =
+					chan_upgrade_cancel::MsgChannelUpgradeCancel::decode_vec(&any_msg.value)
+						.map_err(Error::malformed_message_bytes)?;
+				Ok(Ics26Envelope::Ics4ChannelMsg(ChannelMsg::ChannelUpgradeCancel(domain_msg)))
+			},
 			// ICS04 packet messages
 			recv_packet::TYPE_URL => {
 				let domain_msg = recv_packet::MsgRecvPacket::decode_vec(&any_msg.value)
 					.map_err(Error::malformed_message_bytes)?;
 				Ok(Ics26Envelope::Ics4PacketMsg(PacketMsg::RecvPacket(domain_msg)))
 			},
+			recv_packets::TYPE_URL => {
+				let domain_msg = MsgRecvPackets::decode_vec(&any_msg.value)
+					.map_err(Error::malformed_message_bytes)?;
+				Ok(Ics26Envelope::Ics4PacketBatchMsg(domain_msg))
+			},
 			acknowledgement::TYPE_URL => {
 				let domain_msg = acknowledgement::MsgAcknowledgement::decode_vec(&any_msg.value)
 					.map_err(Error::malformed_message_bytes)?;
@@ -24,7 +24,8 @@ use crate::{
 		ics04_channel::{
 			channel::ChannelEnd,
 			commitment::{AcknowledgementCommitment, PacketCommitment},
-			packet::Sequence,
+			packet::{Receipt, Sequence},
+			upgrade::{ErrorReceipt, Upgrade},
 		},
 		ics23_commitment::{
 			commitment::{CommitmentPrefix, CommitmentProofBytes, CommitmentRoot},
@@ -143,6 +144,38 @@ impl ClientDef for MockClient {
 		Ok(())
 	}
 
+	fn verify_channel_upgrade<Ctx: ReaderContext>(
+		&self,
+		_ctx: &Ctx,
+		_client_id: &ClientId,
+		_client_state: &Self::ClientState,
+		_height: Height,
+		_prefix: &CommitmentPrefix,
+		_proof: &CommitmentProofBytes,
+		_root: &CommitmentRoot,
+		_port_id: &PortId,
+		_channel_id: &ChannelId,
+		_expected_upgrade: &Upgrade,
+	) -> Result<(), Error> {
+		Ok(())
+	}
+
+	fn verify_channel_upgrade_error<Ctx: ReaderContext>(
+		&self,
+		_ctx: &Ctx,
+		_client_id: &ClientId,
+		_client_state: &Self::ClientState,
+		_height: Height,
+		_prefix: &CommitmentPrefix,
+		_proof: &CommitmentProofBytes,
+		_root: &CommitmentRoot,
+		_port_id: &PortId,
+		_channel_id: &ChannelId,
+		_expected_error_receipt: &ErrorReceipt,
+	) -> Result<(), Error> {
+		Ok(())
+	}
+
 	fn verify_client_full_state<Ctx: ReaderContext>(
 		&self,
 		_ctx: &Ctx,
@@ -207,6 +240,37 @@ impl ClientDef for MockClient {
 		Ok(())
 	}
 
+	fn verify_packet_receipt<Ctx: ReaderContext>(
+		&self,
+		_ctx: &Ctx,
+		_client_id: &ClientId,
+		_client_state: &Self::ClientState,
+		_height: Height,
+		_connection_end: &ConnectionEnd,
+		_proof: &CommitmentProofBytes,
+		_root: &CommitmentRoot,
+		_port_id: &PortId,
+		_channel_id: &ChannelId,
+		_sequence: Sequence,
+		_receipt: Receipt,
+	) -> Result<(), Error> {
+		Ok(())
+	}
+
+	fn verify_batch_membership<Ctx: ReaderContext>(
+		&self,
+		_ctx: &Ctx,
+		_client_id: &ClientId,
+		_client_state: &Self::ClientState,
+		_height: Height,
+		_connection_end: &ConnectionEnd,
+		_proof: &CommitmentProofBytes,
+		_root: &CommitmentRoot,
+		_items: Vec<(Path, Vec<u8>)>,
+	) -> Result<(), Error> {
+		Ok(())
+	}
+
 	fn verify_packet_receipt_absence<Ctx: ReaderContext>(
 		&self,
 		_ctx: &Ctx,
@@ -253,9 +317,10 @@ impl ClientDef for MockClient {
 
 	fn update_state_on_misbehaviour(
 		&self,
-		client_state: Self::ClientState,
+		mut client_state: Self::ClientState,
 		_client_msg: Self::ClientMessage,
 	) -> Result<Self::ClientState, Error> {
+		client_state.frozen_height = Some(client_state.latest_height());
 		Ok(client_state)
 	}
 
@@ -264,9 +329,9 @@ impl ClientDef for MockClient {
 		_ctx: &Ctx,
 		_client_id: ClientId,
 		_client_state: Self::ClientState,
-		_client_msg: Self::ClientMessage,
+		client_msg: Self::ClientMessage,
 	) -> Result<bool, Error> {
-		Ok(false)
+		Ok(matches!(client_msg, MockClientMessage::Misbehaviour(_)))
 	}
 
 	fn check_substitute_and_update_state<Ctx: ReaderContext>(
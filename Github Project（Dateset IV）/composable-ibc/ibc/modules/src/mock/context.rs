@@ -15,7 +15,10 @@
 //! Implementation of a global context mock. Used in testing handlers of all IBC modules.
 use crate::prelude::*;
 
-use alloc::{collections::btree_map::BTreeMap, sync::Arc};
+use alloc::{
+	collections::{btree_map::BTreeMap, btree_set::BTreeSet},
+	sync::Arc,
+};
 use core::{
 	cmp::min,
 	fmt::{Debug, Formatter},
@@ -49,6 +52,7 @@ use crate::{
 			error::Error as Ics04Error,
 			msgs::acknowledgement::Acknowledgement,
 			packet::{Receipt, Sequence},
+			upgrade::{ErrorReceipt, Upgrade},
 		},
 		ics05_port::{
 			context::PortReader,
@@ -106,6 +110,9 @@ pub struct MockContext<C: HostBlockType = MockClientTypes> {
 	/// ICS26 router impl
 	pub router: MockRouter,
 
+	/// Whether messages are delivered by the channel upgrade authority.
+	pub upgrade_authority: bool,
+
 	pub _phantom: PhantomData<C>,
 }
 
@@ -153,6 +160,7 @@ impl<C: HostBlockType> Clone for MockContext<C> {
 			block_time: self.block_time,
 			ibc_store,
 			router: self.router.clone(),
+			upgrade_authority: self.upgrade_authority,
 			_phantom: Default::default(),
 		}
 	}
@@ -217,6 +225,7 @@ where
 			block_time,
 			ibc_store: Arc::new(Mutex::new(MockIbcStore::<C>::default())),
 			router: Default::default(),
+			upgrade_authority: false,
 			_phantom: Default::default(),
 		}
 	}
@@ -314,10 +323,45 @@ where
 		self
 	}
 
+	/// Associates the upgrade proposed by a channel end to this context.
+	pub fn with_channel_upgrade(
+		self,
+		port_id: PortId,
+		chan_id: ChannelId,
+		upgrade: Upgrade,
+	) -> Self {
+		self.ibc_store
+			.lock()
+			.unwrap()
+			.channel_upgrades
+			.insert((port_id, chan_id), upgrade);
+		self
+	}
+
+	/// Associates the upgrade proposed by the counterparty of a channel end to this context.
+	pub fn with_counterparty_channel_upgrade(
+		self,
+		port_id: PortId,
+		chan_id: ChannelId,
+		upgrade: Upgrade,
+	) -> Self {
+		self.ibc_store
+			.lock()
+			.unwrap()
+			.counterparty_channel_upgrades
+			.insert((port_id, chan_id), upgrade);
+		self
+	}
+
 	pub fn with_router(self, router: MockRouter) -> Self {
 		Self { router, ..self }
 	}
 
+	/// Delivers messages on behalf of the channel upgrade authority.
+	pub fn with_upgrade_authority(self) -> Self {
+		Self { upgrade_authority: true, ..self }
+	}
+
 	/// Accessor for a block of the local (host) chain from this context.
 	/// Returns `None` if the block at the requested height does not exist.
 	pub fn host_block(&self, target_height: Height) -> Option<&C::HostBlock> {
@@ -534,6 +578,9 @@ pub struct MockIbcStore<C: HostBlockType> {
 
 	pub packet_acknowledgement: BTreeMap<(PortId, ChannelId, Sequence), AcknowledgementCommitment>,
 
+	/// Received packets whose acknowledgement will be written asynchronously
+	pub pending_acknowledgements: BTreeSet<(PortId, ChannelId, Sequence)>,
+
 	/// Maps ports to the the module that owns it
 	pub port_to_module: BTreeMap<PortId, ModuleId>,
 
@@ -542,6 +589,15 @@ pub struct MockIbcStore<C: HostBlockType> {
 
 	// Used by unordered channel
 	pub packet_receipt: BTreeMap<(PortId, ChannelId, Sequence), Receipt>,
+
+	/// Upgrades proposed by the channel ends in the store.
+	pub channel_upgrades: BTreeMap<(PortId, ChannelId), Upgrade>,
+
+	/// Upgrades proposed by the counterparties of the channel ends in the store.
+	pub counterparty_channel_upgrades: BTreeMap<(PortId, ChannelId), Upgrade>,
+
+	/// Error receipts of the last aborted upgrade of each channel end.
+	pub upgrade_error_receipts: BTreeMap<(PortId, ChannelId), ErrorReceipt>,
 }
 
 #[derive(Default)]
@@ -749,6 +805,57 @@ impl<C: HostBlockType> ChannelReader for MockContext<C> {
 	fn max_expected_time_per_block(&self) -> Duration {
 		self.block_time
 	}
+
+	fn channel_upgrade(
+		&self,
+		port_channel_id: &(PortId, ChannelId),
+	) -> Result<Upgrade, Ics04Error> {
+		match self.ibc_store.lock().unwrap().channel_upgrades.get(port_channel_id) {
+			Some(upgrade) => Ok(upgrade.clone()),
+			None => Err(Ics04Error::missing_upgrade(port_channel_id.0.clone(), port_channel_id.1)),
+		}
+	}
+
+	fn counterparty_channel_upgrade(
+		&self,
+		port_channel_id: &(PortId, ChannelId),
+	) -> Result<Upgrade, Ics04Error> {
+		match self
+			.ibc_store
+			.lock()
+			.unwrap()
+			.counterparty_channel_upgrades
+			.get(port_channel_id)
+		{
+			Some(upgrade) => Ok(upgrade.clone()),
+			None => Err(Ics04Error::missing_upgrade(port_channel_id.0.clone(), port_channel_id.1)),
+		}
+	}
+
+	fn upgrade_error_receipt(
+		&self,
+		port_channel_id: &(PortId, ChannelId),
+	) -> Result<ErrorReceipt, Ics04Error> {
+		match self.ibc_store.lock().unwrap().upgrade_error_receipts.get(port_channel_id) {
+			Some(receipt) => Ok(receipt.clone()),
+			None => Err(Ics04Error::missing_error_receipt()),
+		}
+	}
+
+	fn has_inflight_packets(
+		&self,
+		port_channel_id: &(PortId, ChannelId),
+	) -> Result<bool, Ics04Error> {
+		Ok(self.ibc_store.lock().unwrap().packet_commitment.keys().any(
+			|(port_id, channel_id, _)| {
+				(port_id, channel_id) == (&port_channel_id.0, &port_channel_id.1)
+			},
+		))
+	}
+
+	fn is_upgrade_authority(&self) -> bool {
+		self.upgrade_authority
+	}
 }
 
 impl<C: HostBlockType> ChannelKeeper for MockContext<C> {
@@ -782,6 +889,22 @@ impl<C: HostBlockType> ChannelKeeper for MockContext<C> {
 		Ok(())
 	}
 
+	fn store_pending_acknowledgement(
+		&mut self,
+		key: (PortId, ChannelId, Sequence),
+	) -> Result<(), Ics04Error> {
+		self.ibc_store.lock().unwrap().pending_acknowledgements.insert(key);
+		Ok(())
+	}
+
+	fn delete_pending_acknowledgement(
+		&mut self,
+		key: (PortId, ChannelId, Sequence),
+	) -> Result<(), Ics04Error> {
+		self.ibc_store.lock().unwrap().pending_acknowledgements.remove(&key);
+		Ok(())
+	}
+
 	fn store_connection_channels(
 		&mut self,
 		cid: ConnectionId,
@@ -837,6 +960,61 @@ impl<C: HostBlockType> ChannelKeeper for MockContext<C> {
 		Ok(())
 	}
 
+	fn store_channel_upgrade(
+		&mut self,
+		port_channel_id: (PortId, ChannelId),
+		upgrade: Upgrade,
+	) -> Result<(), Ics04Error> {
+		self.ibc_store.lock().unwrap().channel_upgrades.insert(port_channel_id, upgrade);
+		Ok(())
+	}
+
+	fn delete_channel_upgrade(
+		&mut self,
+		port_channel_id: (PortId, ChannelId),
+	) -> Result<(), Ics04Error> {
+		self.ibc_store.lock().unwrap().channel_upgrades.remove(&port_channel_id);
+		Ok(())
+	}
+
+	fn store_counterparty_channel_upgrade(
+		&mut self,
+		port_channel_id: (PortId, ChannelId),
+		upgrade: Upgrade,
+	) -> Result<(), Ics04Error> {
+		self.ibc_store
+			.lock()
+			.unwrap()
+			.counterparty_channel_upgrades
+			.insert(port_channel_id, upgrade);
+		Ok(())
+	}
+
+	fn delete_counterparty_channel_upgrade(
+		&mut self,
+		port_channel_id: (PortId, ChannelId),
+	) -> Result<(), Ics04Error> {
+		self.ibc_store
+			.lock()
+			.unwrap()
+			.counterparty_channel_upgrades
+			.remove(&port_channel_id);
+		Ok(())
+	}
+
+	fn store_upgrade_error_receipt(
+		&mut self,
+		port_channel_id: (PortId, ChannelId),
+		error_receipt: ErrorReceipt,
+	) -> Result<(), Ics04Error> {
+		self.ibc_store
+			.lock()
+			.unwrap()
+			.upgrade_error_receipts
+			.insert(port_channel_id, error_receipt);
+		Ok(())
+	}
+
 	fn increase_channel_counter(&mut self) {
 		self.ibc_store.lock().unwrap().channel_ids_counter += 1;
 	}
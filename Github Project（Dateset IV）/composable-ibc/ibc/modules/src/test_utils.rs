@@ -38,6 +38,7 @@ use crate::{
 			context::{ChannelKeeper, ChannelReader},
 			error::Error,
 			packet::{Receipt, Sequence},
+			upgrade::{ErrorReceipt, Upgrade},
 			Version,
 		},
 		ics05_port::{context::PortReader, error::Error as PortError},
@@ -307,6 +308,20 @@ impl<C: HostBlockType> ChannelKeeper for DummyTransferModule<C> {
 		unimplemented!()
 	}
 
+	fn store_pending_acknowledgement(
+		&mut self,
+		_key: (PortId, ChannelId, Sequence),
+	) -> Result<(), Error> {
+		unimplemented!()
+	}
+
+	fn delete_pending_acknowledgement(
+		&mut self,
+		_key: (PortId, ChannelId, Sequence),
+	) -> Result<(), Error> {
+		unimplemented!()
+	}
+
 	fn store_connection_channels(
 		&mut self,
 		_conn_id: ConnectionId,
@@ -352,6 +367,44 @@ impl<C: HostBlockType> ChannelKeeper for DummyTransferModule<C> {
 		unimplemented!()
 	}
 
+	fn store_channel_upgrade(
+		&mut self,
+		_port_channel_id: (PortId, ChannelId),
+		_upgrade: Upgrade,
+	) -> Result<(), Error> {
+		unimplemented!()
+	}
+
+	fn delete_channel_upgrade(
+		&mut self,
+		_port_channel_id: (PortId, ChannelId),
+	) -> Result<(), Error> {
+		unimplemented!()
+	}
+
+	fn store_counterparty_channel_upgrade(
+		&mut self,
+		_port_channel_id: (PortId, ChannelId),
+		_upgrade: Upgrade,
+	) -> Result<(), Error> {
+		unimplemented!()
+	}
+
+	fn delete_counterparty_channel_upgrade(
+		&mut self,
+		_port_channel_id: (PortId, ChannelId),
+	) -> Result<(), Error> {
+		unimplemented!()
+	}
+
+	fn store_upgrade_error_receipt(
+		&mut self,
+		_port_channel_id: (PortId, ChannelId),
+		_error_receipt: ErrorReceipt,
+	) -> Result<(), Error> {
+		unimplemented!()
+	}
+
 	fn store_send_packet(
 		&mut self,
 		_key: (PortId, ChannelId, Sequence),
@@ -607,6 +660,28 @@ impl<C: HostBlockType> ChannelReader for DummyTransferModule<C> {
 	fn max_expected_time_per_block(&self) -> Duration {
 		unimplemented!()
 	}
+
+	fn channel_upgrade(&self, _port_channel_id: &(PortId, ChannelId)) -> Result<Upgrade, Error> {
+		unimplemented!()
+	}
+
+	fn counterparty_channel_upgrade(
+		&self,
+		_port_channel_id: &(PortId, ChannelId),
+	) -> Result<Upgrade, Error> {
+		unimplemented!()
+	}
+
+	fn upgrade_error_receipt(
+		&self,
+		_port_channel_id: &(PortId, ChannelId),
+	) -> Result<ErrorReceipt, Error> {
+		unimplemented!()
+	}
+
+	fn has_inflight_packets(&self, _port_channel_id: &(PortId, ChannelId)) -> Result<bool, Error> {
+		unimplemented!()
+	}
 }
 
 impl<C: HostBlockType> ClientTypes for DummyTransferModule<C> {
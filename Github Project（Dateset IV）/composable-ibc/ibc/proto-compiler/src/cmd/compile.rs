@@ -242,6 +242,7 @@ impl CompileCmd {
 		// Paths
 		let proto_paths = vec![
 			format!("{root}/../proto/definitions/mock"),
+			format!("{root}/../proto/definitions/composable"),
 			format!("{}/proto/cosmos/auth", sdk_dir.display()),
 			format!("{}/proto/cosmos/gov", sdk_dir.display()),
 			format!("{}/proto/cosmos/tx", sdk_dir.display()),
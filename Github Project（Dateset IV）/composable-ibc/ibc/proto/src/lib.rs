@@ -222,6 +222,28 @@ pub mod ibc {
 	}
 }
 
+pub mod composable {
+	pub mod ibc {
+		pub mod applications {
+			pub mod transfer {
+				pub mod v1 {
+					include_proto!("composable.ibc.applications.transfer.v1.rs");
+				}
+				pub mod v2 {
+					include_proto!("composable.ibc.applications.transfer.v2.rs");
+				}
+			}
+		}
+		pub mod core {
+			pub mod channel {
+				pub mod v1 {
+					include_proto!("composable.ibc.core.channel.v1.rs");
+				}
+			}
+		}
+	}
+}
+
 pub mod base64 {
 	use alloc::{string::String, vec::Vec};
 
@@ -0,0 +1,32 @@
+/// Token defines a struct which represents a token to be transferred.
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct Token {
+    /// the token denomination
+    #[prost(message, optional, tag = "1")]
+    pub denom: ::core::option::Option<Denom>,
+    /// the token amount to be transferred
+    #[prost(string, tag = "2")]
+    pub amount: ::prost::alloc::string::String,
+}
+/// Denom holds the base denom of a Token and a trace of the chains it was sent through.
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct Denom {
+    /// the base token denomination
+    #[prost(string, tag = "1")]
+    pub base: ::prost::alloc::string::String,
+    /// the trace of the token
+    #[prost(message, repeated, tag = "3")]
+    pub trace: ::prost::alloc::vec::Vec<Hop>,
+}
+/// Hop defines a port ID, channel ID pair specifying where tokens must be forwarded
+/// next in a multihop transfer, or the trace of an existing token.
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct Hop {
+    #[prost(string, tag = "1")]
+    pub port_id: ::prost::alloc::string::String,
+    #[prost(string, tag = "2")]
+    pub channel_id: ::prost::alloc::string::String,
+}
@@ -0,0 +1,18 @@
+/// MsgRecvPackets receives a batch of incoming IBC packets sent over the same
+/// channel, with a single multi-key proof of all their commitments.
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct MsgRecvPackets {
+    #[prost(message, repeated, tag = "1")]
+    pub packets: ::prost::alloc::vec::Vec<
+        super::super::super::super::super::ibc::core::channel::v1::Packet,
+    >,
+    #[prost(bytes = "vec", tag = "2")]
+    pub proof_commitment: ::prost::alloc::vec::Vec<u8>,
+    #[prost(message, optional, tag = "3")]
+    pub proof_height: ::core::option::Option<
+        super::super::super::super::super::ibc::core::client::v1::Height,
+    >,
+    #[prost(string, tag = "4")]
+    pub signer: ::prost::alloc::string::String,
+}
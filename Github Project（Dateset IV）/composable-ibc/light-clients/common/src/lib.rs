@@ -21,7 +21,12 @@
 extern crate alloc;
 extern crate core;
 
-use alloc::{string::ToString, vec, vec::Vec};
+use alloc::{
+	boxed::Box,
+	string::{String, ToString},
+	vec,
+	vec::Vec,
+};
 use anyhow::anyhow;
 use codec::Compact;
 use core::{
@@ -88,6 +93,41 @@ where
 	Ok(())
 }
 
+/// Multi-key membership proof verification via child trie host function. Every trie node in
+/// `proof` is shared by all the `items` it covers and only needs to be sent and checked once.
+pub fn verify_batch_membership<H>(
+	prefix: &CommitmentPrefix,
+	proof: &CommitmentProofBytes,
+	root: &CommitmentRoot,
+	items: Vec<(Path, Vec<u8>)>,
+) -> Result<(), anyhow::Error>
+where
+	H: hash_db::Hasher<Out = H256> + Debug + 'static,
+{
+	if root.as_bytes().len() != 32 {
+		return Err(anyhow!("invalid commitment root length: {}", root.as_bytes().len()))
+	}
+	if items.is_empty() {
+		return Err(anyhow!("empty batch of paths to verify"))
+	}
+	let items = items
+		.into_iter()
+		.map(|(path, value)| {
+			let mut key = prefix.as_bytes().to_vec();
+			key.extend(path.to_string().as_bytes());
+			(key, Some(value))
+		})
+		.collect::<Vec<_>>();
+	let trie_proof: Vec<Vec<u8>> = codec::Decode::decode(&mut &*proof.as_bytes())
+		.map_err(|err| anyhow!("Failed to decode batch proof nodes: {err:#?}"))?;
+	let proof = StorageProof::new(trie_proof);
+	let root = H256::from_slice(root.as_bytes());
+	let child_info = ChildInfo::new_default(prefix.as_bytes());
+	state_machine::read_child_proof_check::<H, _>(root, proof, child_info, items)
+		.map_err(|err| anyhow!("Failed to verify batch proof, error: {err:#?}"))?;
+	Ok(())
+}
+
 /// Non-membership proof verification via child trie host function
 pub fn verify_non_membership<H, P>(
 	prefix: &CommitmentPrefix,
@@ -116,11 +156,61 @@ where
 	Ok(())
 }
 
-#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize, Serialize)]
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
 pub enum RelayChain {
-	Polkadot = 0,
-	Kusama = 1,
-	Rococo = 2,
+	Polkadot,
+	Kusama,
+	Rococo,
+	/// Any other relay chain, e.g. Westend, Paseo or a private network
+	Custom(CustomRelayChain),
+}
+
+/// Parameters of a relay chain that the light clients have no built-in knowledge of.
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(try_from = "UncheckedCustomRelayChain")]
+pub struct CustomRelayChain {
+	/// Name of the relay chain, used as the chain id of the clients
+	pub name: String,
+	/// Unbonding period of the relay chain validators
+	pub unbonding_period: Duration,
+	/// Period during which a header can be trusted, must be shorter than `unbonding_period`
+	pub trusting_period: Duration,
+}
+
+impl CustomRelayChain {
+	pub fn new(
+		name: String,
+		unbonding_period: Duration,
+		trusting_period: Duration,
+	) -> Result<Self, anyhow::Error> {
+		if name.is_empty() {
+			return Err(anyhow!("Custom relay chain name cannot be empty"))
+		}
+		if trusting_period.is_zero() || trusting_period >= unbonding_period {
+			return Err(anyhow!(
+				"Trusting period {trusting_period:?} of relay chain {name} must be non-zero and \
+				 shorter than its unbonding period {unbonding_period:?}"
+			))
+		}
+		Ok(Self { name, unbonding_period, trusting_period })
+	}
+}
+
+/// Deserialized parameters of a [`CustomRelayChain`], which are only accepted once validated by
+/// [`CustomRelayChain::new`].
+#[derive(Deserialize)]
+struct UncheckedCustomRelayChain {
+	name: String,
+	unbonding_period: Duration,
+	trusting_period: Duration,
+}
+
+impl TryFrom<UncheckedCustomRelayChain> for CustomRelayChain {
+	type Error = anyhow::Error;
+
+	fn try_from(unchecked: UncheckedCustomRelayChain) -> Result<Self, Self::Error> {
+		Self::new(unchecked.name, unchecked.unbonding_period, unchecked.trusting_period)
+	}
 }
 
 impl Default for RelayChain {
@@ -142,12 +232,16 @@ const KUSAMA_UNBONDING_PERIOD: u64 = 7;
 const DAY: u64 = 24 * 60 * 60;
 
 impl RelayChain {
+	/// Protobuf enum value of [`RelayChain::Custom`], whose parameters are encoded separately.
+	pub const CUSTOM: i32 = 3;
+
 	/// Yields the Order as a string
-	pub fn as_str(&self) -> &'static str {
+	pub fn as_str(&self) -> &str {
 		match self {
 			Self::Polkadot => "Polkadot",
 			Self::Kusama => "Kusama",
 			Self::Rococo => "Rococo",
+			Self::Custom(custom) => &custom.name,
 		}
 	}
 
@@ -157,21 +251,43 @@ impl RelayChain {
 			0 => Ok(Self::Polkadot),
 			1 => Ok(Self::Kusama),
 			2 => Ok(Self::Rococo),
+			Self::CUSTOM => Err(anyhow!("Missing parameters of custom relay chain")),
 			id => Err(anyhow!("Unknown relay chain {id}")),
 		}
 	}
 
+	/// Protobuf enum value of the relay chain
+	pub fn to_i32(&self) -> i32 {
+		match self {
+			Self::Polkadot => 0,
+			Self::Kusama => 1,
+			Self::Rococo => 2,
+			Self::Custom(_) => Self::CUSTOM,
+		}
+	}
+
+	/// Parameters of the relay chain, if it is a custom one
+	pub fn custom(&self) -> Option<&CustomRelayChain> {
+		match self {
+			Self::Custom(custom) => Some(custom),
+			_ => None,
+		}
+	}
+
 	pub fn unbonding_period(&self) -> Duration {
 		match self {
 			Self::Polkadot => Duration::from_secs(POLKADOT_UNBONDING_PERIOD * DAY),
 			Self::Kusama | Self::Rococo => Duration::from_secs(KUSAMA_UNBONDING_PERIOD * DAY),
+			Self::Custom(custom) => custom.unbonding_period,
 		}
 	}
 
 	pub fn trusting_period(&self) -> Duration {
-		let unbonding_period = self.unbonding_period();
-		// Trusting period is 1/3 of unbonding period
-		unbonding_period.checked_div(3).unwrap()
+		match self {
+			Self::Custom(custom) => custom.trusting_period,
+			// Trusting period is 1/3 of unbonding period
+			_ => self.unbonding_period().checked_div(3).unwrap(),
+		}
 	}
 }
 
@@ -199,6 +315,68 @@ pub fn decode_timestamp_extrinsic(ext: &Vec<u8>) -> Result<u64, anyhow::Error> {
 	Ok(timestamp.into())
 }
 
+/// How the timestamp of a substrate header is proven to a light client.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Deserialize, Serialize)]
+pub enum TimestampProofType {
+	/// Trie proof of the timestamp inherent, which must be the first extrinsic of the header,
+	/// against the extrinsics root of the header.
+	#[default]
+	Extrinsic,
+	/// Storage proof of `pallet_timestamp::Now` against the state root of the header. Doesn't
+	/// depend on the order or format of the extrinsics of the runtime.
+	Storage,
+}
+
+impl TimestampProofType {
+	// Parses the proof type out from a i32.
+	pub fn from_i32(nr: i32) -> Result<Self, anyhow::Error> {
+		match nr {
+			0 => Ok(Self::Extrinsic),
+			1 => Ok(Self::Storage),
+			id => Err(anyhow!("Unknown timestamp proof type {id}")),
+		}
+	}
+
+	/// Protobuf enum value of the proof type
+	pub fn to_i32(&self) -> i32 {
+		match self {
+			Self::Extrinsic => 0,
+			Self::Storage => 1,
+		}
+	}
+}
+
+/// Returns the storage key of `pallet_timestamp::Now`.
+pub fn timestamp_storage_key() -> Vec<u8> {
+	let mut key = sp_core::hashing::twox_128(b"Timestamp").to_vec();
+	key.extend_from_slice(&sp_core::hashing::twox_128(b"Now"));
+	key
+}
+
+/// Verifies the storage proof of `pallet_timestamp::Now` against the given state root and returns
+/// the proven timestamp in milliseconds.
+pub fn verify_timestamp_storage_proof<H>(
+	state_root: &H256,
+	proof: Vec<Vec<u8>>,
+) -> Result<u64, anyhow::Error>
+where
+	H: hash_db::Hasher<Out = H256> + Debug + 'static,
+{
+	let key = timestamp_storage_key();
+	let value = state_machine::read_raw_proof_check::<H, _>(
+		state_root,
+		StorageProof::new(proof),
+		vec![key.as_slice()],
+	)
+	.map_err(|err| anyhow!("Failed to verify timestamp storage proof: {err:#?}"))?
+	.remove(&key)
+	.flatten()
+	.ok_or_else(|| anyhow!("Timestamp not found in storage proof"))?;
+	let timestamp: u64 = codec::Decode::decode(&mut &*value)
+		.map_err(|err| anyhow!("Failed to decode timestamp: {err}"))?;
+	Ok(timestamp)
+}
+
 /// This will verify that the connection delay has elapsed for a given [`ibc::Height`]
 pub fn verify_delay_passed<H, C>(
 	ctx: &C,
@@ -235,3 +413,129 @@ where
 
 	Ok(())
 }
+
+/// Maximum number of expired consensus states removed by a single client update.
+pub const MAX_PRUNED_CONSENSUS_STATES: usize = 10;
+
+/// Returns the heights of the consensus states that expired at `current_time`, given the heights
+/// below the latest one in ascending order together with the time they were processed at.
+/// Iteration stops at the first consensus state that has not expired yet and at most
+/// [`MAX_PRUNED_CONSENSUS_STATES`] heights are returned. States without a processed time are
+/// skipped.
+pub fn expired_consensus_state_heights<I, F>(
+	processed_times: I,
+	current_time: u64,
+	is_expired: F,
+) -> Vec<Height>
+where
+	I: IntoIterator<Item = (Height, Option<u64>)>,
+	F: Fn(Duration) -> bool,
+{
+	processed_times
+		.into_iter()
+		.filter_map(|(height, processed_time)| Some((height, processed_time?)))
+		.take_while(|(_, processed_time)| {
+			is_expired(Duration::from_nanos(current_time.saturating_sub(*processed_time)))
+		})
+		.take(MAX_PRUNED_CONSENSUS_STATES)
+		.map(|(height, _)| height)
+		.collect()
+}
+
+/// Storage of the consensus states of a light client, which keeps track of the time each
+/// consensus state was processed at.
+pub trait ProcessedConsensusStates {
+	/// Iterates over the heights of the consensus states below `height` in ascending order,
+	/// together with the time they were processed at.
+	fn processed_times_below(
+		&self,
+		height: Height,
+	) -> Box<dyn Iterator<Item = (Height, Option<u64>)> + '_>;
+
+	/// Removes the consensus state at `height` along with its processed time and height.
+	fn remove_consensus_state(&mut self, height: Height);
+}
+
+/// Removes the consensus states that expired at `current_time`, based on the time they were
+/// processed at, and returns their heights in ascending order. See
+/// [`expired_consensus_state_heights`] for which states are removed per call; the consensus state
+/// at `latest_height` is never removed.
+pub fn prune_expired_consensus_states<S, F>(
+	states: &mut S,
+	latest_height: Height,
+	current_time: u64,
+	is_expired: F,
+) -> Vec<Height>
+where
+	S: ProcessedConsensusStates,
+	F: Fn(Duration) -> bool,
+{
+	let expired_heights = expired_consensus_state_heights(
+		states.processed_times_below(latest_height),
+		current_time,
+		is_expired,
+	);
+	for height in &expired_heights {
+		states.remove_consensus_state(*height);
+	}
+	expired_heights
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use codec::Encode;
+	use sp_runtime::traits::BlakeTwo256;
+	use sp_trie::{LayoutV0, MemoryDB, TrieDBMutBuilder, TrieMut};
+
+	/// Builds a state trie out of `entries`, returning its root and a storage proof of all of its
+	/// nodes.
+	fn state_trie(entries: &[(Vec<u8>, Vec<u8>)]) -> (H256, Vec<Vec<u8>>) {
+		let mut db = MemoryDB::<BlakeTwo256>::default();
+		let mut root = Default::default();
+		let root = {
+			let mut trie =
+				TrieDBMutBuilder::<LayoutV0<BlakeTwo256>>::new(&mut db, &mut root).build();
+			for (key, value) in entries {
+				trie.insert(key, value).unwrap();
+			}
+			*trie.root()
+		};
+		let proof = db.drain().into_iter().map(|(_, (node, ..))| node.to_vec()).collect();
+		(root, proof)
+	}
+
+	#[test]
+	fn verifies_timestamp_storage_proof() {
+		let (root, proof) = state_trie(&[
+			(timestamp_storage_key(), 1_650_894_363_000u64.encode()),
+			(b"other".to_vec(), vec![1, 2, 3]),
+		]);
+		assert_eq!(
+			verify_timestamp_storage_proof::<BlakeTwo256>(&root, proof).unwrap(),
+			1_650_894_363_000
+		);
+	}
+
+	#[test]
+	fn rejects_timestamp_storage_proof_without_timestamp() {
+		let (root, proof) = state_trie(&[(b"other".to_vec(), 1_650_894_363_000u64.encode())]);
+		assert!(verify_timestamp_storage_proof::<BlakeTwo256>(&root, proof).is_err());
+	}
+
+	#[test]
+	fn rejects_timestamp_storage_proof_for_another_root() {
+		let (_, proof) = state_trie(&[(timestamp_storage_key(), 1_650_894_363_000u64.encode())]);
+		let (root, _) = state_trie(&[(timestamp_storage_key(), 1_650_894_369_000u64.encode())]);
+		assert!(verify_timestamp_storage_proof::<BlakeTwo256>(&root, proof).is_err());
+	}
+
+	#[test]
+	fn timestamp_proof_type_round_trips_through_protobuf_enum() {
+		assert_eq!(TimestampProofType::default(), TimestampProofType::Extrinsic);
+		for proof_type in [TimestampProofType::Extrinsic, TimestampProofType::Storage] {
+			assert_eq!(TimestampProofType::from_i32(proof_type.to_i32()).unwrap(), proof_type);
+		}
+		assert!(TimestampProofType::from_i32(2).is_err());
+	}
+}
@@ -110,3 +110,32 @@ where
 
 	Ok(result)
 }
+
+/// Same as [`read_proof_check`], but returns the values as they are stored, without decoding them.
+pub fn read_raw_proof_check<H, I>(
+	root: &H::Out,
+	proof: StorageProof,
+	keys: I,
+) -> Result<BTreeMap<Vec<u8>, Option<Vec<u8>>>, Error<H>>
+where
+	H: Hasher,
+	H::Out: Debug,
+	I: IntoIterator,
+	I::Item: AsRef<[u8]>,
+{
+	let db = proof.into_memory_db();
+
+	if !db.contains(root, EMPTY_PREFIX) {
+		Err(Error::InvalidProof)?
+	}
+
+	let trie = TrieDBBuilder::<LayoutV0<H>>::new(&db, root).build();
+	let mut result = BTreeMap::new();
+
+	for key in keys.into_iter() {
+		let value = trie.get(key.as_ref())?;
+		result.insert(key.as_ref().to_vec(), value);
+	}
+
+	Ok(result)
+}
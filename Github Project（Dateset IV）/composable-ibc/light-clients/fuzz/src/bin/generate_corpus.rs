@@ -0,0 +1,22 @@
+// Copyright (C) 2022 ComposableFi.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Writes the seed corpus of every fuzz target to `corpus/<target>`.
+
+use std::path::Path;
+
+fn main() -> std::io::Result<()> {
+	light_clients_fuzz::seeds::write_corpus(Path::new(env!("CARGO_MANIFEST_DIR")))
+}
@@ -0,0 +1,117 @@
+// Copyright (C) 2022 ComposableFi.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Trusted client states the fuzzed client messages are verified against.
+
+use crate::{
+	mock::{AnyClientMessage, AnyClientState, AnyConsensusState, MockClientTypes},
+	seeds,
+};
+use ibc::{
+	core::{
+		ics02_client::{
+			client_state::ClientState as _, context::ClientKeeper, trust_threshold::TrustThreshold,
+		},
+		ics23_commitment::specs::ProofSpecs,
+		ics24_host::identifier::{ChainId, ClientId},
+	},
+	mock::{context::MockContext, host::MockHostType},
+	Height,
+};
+use ics07_tendermint::client_message::ClientMessage as TendermintClientMessage;
+use pallet_ibc::light_clients::HostFunctionsManager;
+use std::time::Duration;
+use tendermint::time::Time;
+
+/// A tendermint client of the chain the test vectors come from. The trusting period is long
+/// enough for the headers of the test vectors to never expire.
+pub fn tendermint_client_state() -> ics07_tendermint::client_state::ClientState<HostFunctionsManager>
+{
+	ics07_tendermint::client_state::ClientState::new(
+		ChainId::from_string("test-chain-01"),
+		TrustThreshold::ONE_THIRD,
+		Duration::from_secs(100 * 365 * 24 * 60 * 60),
+		Duration::from_secs(101 * 365 * 24 * 60 * 60),
+		Duration::from_secs(3),
+		Height::new(0, 1),
+		ProofSpecs::default(),
+		vec!["".to_string()],
+	)
+	.expect("the client state is valid")
+}
+
+/// A trusted client and consensus state of the client `message` is meant for, `None` for mock
+/// client messages.
+pub fn trusted_states(message: &AnyClientMessage) -> Option<(AnyClientState, AnyConsensusState)> {
+	let genesis = || Time::from_unix_timestamp(0, 0).expect("the unix epoch is a valid time");
+	let states = match message {
+		AnyClientMessage::Tendermint(message) => {
+			let header = match message {
+				TendermintClientMessage::Header(header) => header.clone(),
+				TendermintClientMessage::Misbehaviour(_) => seeds::tendermint_header(),
+			};
+			(
+				AnyClientState::Tendermint(tendermint_client_state()),
+				AnyConsensusState::Tendermint(header.into()),
+			)
+		},
+		AnyClientMessage::Grandpa(_) => (
+			AnyClientState::Grandpa(ics10_grandpa::client_state::ClientState {
+				latest_para_height: 1,
+				..Default::default()
+			}),
+			AnyConsensusState::Grandpa(ics10_grandpa::consensus_state::ConsensusState::new(
+				vec![0; 32],
+				genesis(),
+			)),
+		),
+		AnyClientMessage::Beefy(_) => (
+			AnyClientState::Beefy(ics11_beefy::client_state::ClientState {
+				latest_para_height: 1,
+				..Default::default()
+			}),
+			AnyConsensusState::Beefy(ics11_beefy::consensus_state::ConsensusState::new(
+				vec![0; 32],
+				genesis(),
+			)),
+		),
+		AnyClientMessage::Wasm(message) => {
+			let (client_state, consensus_state) = trusted_states(&message.inner)?;
+			(AnyClientState::wasm(client_state), AnyConsensusState::wasm(consensus_state))
+		},
+		AnyClientMessage::Mock(_) => return None,
+	};
+	Some(states)
+}
+
+/// A [`MockContext`] holding the [`trusted_states`] of `message`, along with the id of the client
+/// and its state.
+pub fn mock_context(
+	message: &AnyClientMessage,
+) -> Option<(MockContext<MockClientTypes>, ClientId, AnyClientState)> {
+	let (client_state, consensus_state) = trusted_states(message)?;
+	let mut ctx = MockContext::<MockClientTypes>::new(
+		ChainId::new("mockgaiaA".to_string(), 1),
+		MockHostType::Mock,
+		5,
+		Height::new(1, 5),
+	);
+	let client_id = ClientId::new(&client_state.client_type(), 0).ok()?;
+	ctx.store_client_type(client_id.clone(), client_state.client_type()).ok()?;
+	ctx.store_client_state(client_id.clone(), client_state.clone()).ok()?;
+	ctx.store_consensus_state(client_id.clone(), client_state.latest_height(), consensus_state)
+		.ok()?;
+	Some((ctx, client_id, client_state))
+}
@@ -0,0 +1,231 @@
+// Copyright (C) 2022 ComposableFi.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Fuzzing harness for the light clients. Every fuzz target in `fuzz_targets` is a thin wrapper
+//! around one of the functions below, which must never panic, whatever the input.
+
+pub mod fixtures;
+pub mod mock;
+pub mod near;
+pub mod seeds;
+
+use crate::{
+	mock::{AnyClientMessage, AnyClientState, MockClientTypes},
+	near::NearHostFunctions,
+};
+use borsh::{BorshDeserialize, BorshSerialize};
+use codec::{Decode, Encode};
+use ibc::{
+	core::{
+		ics02_client::{client_def::ClientDef, client_state::ClientState},
+		ics23_commitment::commitment::{CommitmentPrefix, CommitmentProofBytes, CommitmentRoot},
+		ics24_host::{
+			identifier::{ChainId, ClientId},
+			Path,
+		},
+	},
+	mock::context::MockContext,
+};
+use ibc_proto::google::protobuf::Any;
+use ics07_tendermint::client_message::{
+	TENDERMINT_CLIENT_MESSAGE_TYPE_URL, TENDERMINT_HEADER_TYPE_URL,
+	TENDERMINT_MISBEHAVIOUR_TYPE_URL,
+};
+use ics08_wasm::client_message::WASM_CLIENT_MESSAGE_TYPE_URL;
+use ics10_grandpa::client_message::{
+	GRANDPA_CLIENT_MESSAGE_TYPE_URL, GRANDPA_HEADER_TYPE_URL, GRANDPA_MISBEHAVIOUR_TYPE_URL,
+};
+use ics11_beefy::client_message::BEEFY_CLIENT_MESSAGE_TYPE_URL;
+use ics13_near::{
+	client_def::validate_light_block,
+	client_state::NearClientState,
+	header::NearHeader,
+	types::{LightClientBlockView, ValidatorStakeView},
+};
+use pallet_ibc::light_clients::{AnyClientMessage as PalletClientMessage, HostFunctionsManager};
+use std::str::FromStr;
+
+/// Type urls of the client messages a relayer can submit. The first byte of a client message
+/// input selects one of them, the remaining bytes are the encoded message.
+pub const CLIENT_MESSAGE_TYPE_URLS: [&str; 8] = [
+	GRANDPA_CLIENT_MESSAGE_TYPE_URL,
+	GRANDPA_HEADER_TYPE_URL,
+	GRANDPA_MISBEHAVIOUR_TYPE_URL,
+	BEEFY_CLIENT_MESSAGE_TYPE_URL,
+	TENDERMINT_CLIENT_MESSAGE_TYPE_URL,
+	TENDERMINT_HEADER_TYPE_URL,
+	TENDERMINT_MISBEHAVIOUR_TYPE_URL,
+	WASM_CLIENT_MESSAGE_TYPE_URL,
+];
+
+/// Converts a fuzzer input into an [`Any`] client message.
+pub fn any_from_input(data: &[u8]) -> Option<Any> {
+	let (selector, value) = data.split_first()?;
+	let type_url = CLIENT_MESSAGE_TYPE_URLS[*selector as usize % CLIENT_MESSAGE_TYPE_URLS.len()];
+	Some(Any { type_url: type_url.to_string(), value: value.to_vec() })
+}
+
+/// Converts an [`Any`] client message into a fuzzer input, the inverse of [`any_from_input`].
+pub fn any_to_input(any: &Any) -> Option<Vec<u8>> {
+	let selector = CLIENT_MESSAGE_TYPE_URLS.iter().position(|url| *url == any.type_url)?;
+	Some([&[selector as u8][..], &any.value].concat())
+}
+
+/// Input of the ICS-23 membership fuzzer.
+#[derive(Clone, Debug, Encode, Decode)]
+pub struct MembershipInput {
+	pub prefix: Vec<u8>,
+	pub proof: Vec<u8>,
+	pub root: Vec<u8>,
+	pub path: String,
+	pub value: Vec<u8>,
+}
+
+/// Input of the NEAR header fuzzer, borsh encoded.
+#[derive(Clone, Debug, BorshSerialize, BorshDeserialize)]
+pub struct NearHeaderInput {
+	/// Head of the trusted client.
+	pub head: LightClientBlockView,
+	/// Block producers of the epoch of `head`.
+	pub validators: Vec<ValidatorStakeView>,
+	/// Header to verify.
+	pub header: LightClientBlockView,
+}
+
+/// Decodes a client message the way `pallet-ibc` does. Every message that decodes must also
+/// survive a round trip through [`Any`].
+pub fn decode_client_message(data: &[u8]) {
+	let message = match any_from_input(data).map(PalletClientMessage::try_from) {
+		Some(Ok(message)) => message,
+		_ => return,
+	};
+	PalletClientMessage::try_from(Any::from(message))
+		.expect("a decoded client message must decode again once re-encoded");
+}
+
+/// Verifies a client message against a trusted client of the matching type.
+pub fn verify_client_message(data: &[u8]) {
+	with_trusted_client(data, |ctx, client_id, client_state, message| {
+		let _ =
+			client_state
+				.client_def()
+				.verify_client_message(ctx, client_id, client_state, message);
+	})
+}
+
+/// Checks a client message for misbehaviour against a trusted client of the matching type.
+pub fn check_for_misbehaviour(data: &[u8]) {
+	with_trusted_client(data, |ctx, client_id, client_state, message| {
+		let _ =
+			client_state
+				.client_def()
+				.check_for_misbehaviour(ctx, client_id, client_state, message);
+	})
+}
+
+/// Verifies an ICS-23 (non-)membership proof the way the tendermint client does.
+pub fn verify_membership(data: &[u8]) {
+	let input = match MembershipInput::decode(&mut &*data) {
+		Ok(input) => input,
+		Err(_) => return,
+	};
+	let (prefix, proof, path) = match (
+		CommitmentPrefix::try_from(input.prefix),
+		CommitmentProofBytes::try_from(input.proof),
+		Path::from_str(&input.path),
+	) {
+		(Ok(prefix), Ok(proof), Ok(path)) => (prefix, proof, path),
+		_ => return,
+	};
+	let root = CommitmentRoot::from_bytes(&input.root);
+	let client_state = fixtures::tendermint_client_state();
+
+	let _ = ics07_tendermint::client_def::verify_membership::<HostFunctionsManager, _>(
+		&client_state,
+		&prefix,
+		&proof,
+		&root,
+		path.clone(),
+		input.value,
+	);
+	let _ = ics07_tendermint::client_def::verify_non_membership::<HostFunctionsManager, _>(
+		&client_state,
+		&prefix,
+		&proof,
+		&root,
+		path,
+	);
+}
+
+/// Verifies a NEAR header against a client trusting the head of the input, the way
+/// `NearClient::verify_client_message` does.
+pub fn verify_near_header(data: &[u8]) {
+	let input = match NearHeaderInput::try_from_slice(data) {
+		Ok(input) => input,
+		Err(_) => return,
+	};
+	let client_state =
+		NearClientState::<NearHostFunctions>::new(ChainId::default(), input.head, input.validators);
+	let header = NearHeader::new(input.header);
+	sp_io::TestExternalities::default().execute_with(|| {
+		let _ = validate_light_block::<NearHostFunctions>(&header, client_state);
+	})
+}
+
+/// Decodes the client message in `data` and runs `f` on a [`MockContext`] holding a trusted
+/// client of the same type. The GRANDPA host functions keep the relay chain header hashes in
+/// the runtime storage, so `f` is executed with test externalities.
+fn with_trusted_client<F>(data: &[u8], f: F)
+where
+	F: FnOnce(&MockContext<MockClientTypes>, ClientId, AnyClientState, AnyClientMessage),
+{
+	let message = match any_from_input(data).map(AnyClientMessage::try_from) {
+		Some(Ok(message)) => message,
+		_ => return,
+	};
+	let (ctx, client_id, client_state) = match fixtures::mock_context(&message) {
+		Some(trusted_client) => trusted_client,
+		None => return,
+	};
+	sp_io::TestExternalities::default().execute_with(|| f(&ctx, client_id, client_state, message))
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn targets_accept_the_seed_corpus() {
+		let messages = seeds::client_messages();
+		assert!(!messages.is_empty());
+		for input in messages.iter().map(|any| any_to_input(any).unwrap()) {
+			decode_client_message(&input);
+			verify_client_message(&input);
+			check_for_misbehaviour(&input);
+		}
+
+		let membership_inputs = seeds::membership_inputs();
+		assert!(!membership_inputs.is_empty());
+		for input in membership_inputs {
+			verify_membership(&input.encode());
+		}
+
+		let near_header_inputs = seeds::near_header_inputs();
+		assert!(!near_header_inputs.is_empty());
+		for input in near_header_inputs {
+			verify_near_header(&input.try_to_vec().unwrap());
+		}
+	}
+}
@@ -0,0 +1,209 @@
+// Copyright (C) 2022 ComposableFi.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Client types of the [`MockContext`](ibc::mock::context::MockContext) the fuzzers run against.
+//! Client messages are decoded with the same code as in `pallet-ibc`.
+
+use ibc::{
+	core::{
+		ics02_client,
+		ics02_client::{
+			client_consensus::ConsensusState, client_state::ClientState, context::ClientTypes,
+		},
+	},
+	mock::{
+		client_def::MockClient,
+		client_state::{MockClientState, MockConsensusState},
+		context::HostBlockType,
+		header::MockClientMessage,
+		host::MockHostBlock,
+	},
+	prelude::*,
+};
+use ibc_derive::{ClientDef, ClientMessage, ClientState, ConsensusState, Protobuf};
+use ibc_proto::google::protobuf::Any;
+use ics07_tendermint::{
+	client_state::TENDERMINT_CLIENT_STATE_TYPE_URL,
+	consensus_state::TENDERMINT_CONSENSUS_STATE_TYPE_URL,
+};
+use ics08_wasm::{
+	client_message::WASM_CLIENT_MESSAGE_TYPE_URL, client_state::WASM_CLIENT_STATE_TYPE_URL,
+	consensus_state::WASM_CONSENSUS_STATE_TYPE_URL,
+};
+use ics10_grandpa::{
+	client_state::GRANDPA_CLIENT_STATE_TYPE_URL, consensus_state::GRANDPA_CONSENSUS_STATE_TYPE_URL,
+};
+use ics11_beefy::{
+	client_state::BEEFY_CLIENT_STATE_TYPE_URL, consensus_state::BEEFY_CONSENSUS_STATE_TYPE_URL,
+};
+use pallet_ibc::light_clients::{AnyClientMessage as PalletClientMessage, HostFunctionsManager};
+use tendermint_proto::Protobuf;
+
+pub const MOCK_CLIENT_STATE_TYPE_URL: &str = "/ibc.mock.ClientState";
+pub const MOCK_CONSENSUS_STATE_TYPE_URL: &str = "/ibc.mock.ConsensusState";
+
+#[derive(Clone, Debug, PartialEq, Eq, ClientDef)]
+pub enum AnyClient {
+	Mock(MockClient),
+	Grandpa(ics10_grandpa::client_def::GrandpaClient<HostFunctionsManager>),
+	Beefy(ics11_beefy::client_def::BeefyClient<HostFunctionsManager>),
+	Tendermint(ics07_tendermint::client_def::TendermintClient<HostFunctionsManager>),
+	Wasm(ics08_wasm::client_def::WasmClient<AnyClient, AnyClientState, AnyConsensusState>),
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum AnyUpgradeOptions {
+	Mock(()),
+	Grandpa(ics10_grandpa::client_state::UpgradeOptions),
+	Beefy(ics11_beefy::client_state::UpgradeOptions),
+	Tendermint(ics07_tendermint::client_state::UpgradeOptions),
+	Wasm(Box<Self>),
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, ClientState, Protobuf)]
+pub enum AnyClientState {
+	#[ibc(proto_url = "MOCK_CLIENT_STATE_TYPE_URL")]
+	Mock(MockClientState),
+	#[ibc(proto_url = "GRANDPA_CLIENT_STATE_TYPE_URL")]
+	Grandpa(ics10_grandpa::client_state::ClientState<HostFunctionsManager>),
+	#[ibc(proto_url = "BEEFY_CLIENT_STATE_TYPE_URL")]
+	Beefy(ics11_beefy::client_state::ClientState<HostFunctionsManager>),
+	#[ibc(proto_url = "TENDERMINT_CLIENT_STATE_TYPE_URL")]
+	Tendermint(ics07_tendermint::client_state::ClientState<HostFunctionsManager>),
+	#[ibc(proto_url = "WASM_CLIENT_STATE_TYPE_URL")]
+	Wasm(ics08_wasm::client_state::ClientState<AnyClient, Self, AnyConsensusState>),
+}
+
+impl AnyClientState {
+	pub fn wasm(inner: Self) -> Self {
+		Self::Wasm(ics08_wasm::client_state::ClientState::<AnyClient, Self, AnyConsensusState> {
+			data: inner.encode_to_vec().expect("encode_to_vec failed"),
+			latest_height: inner.latest_height(),
+			inner: Box::new(inner),
+			checksum: vec![0; 32],
+			_phantom: Default::default(),
+		})
+	}
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, ConsensusState, Protobuf)]
+pub enum AnyConsensusState {
+	#[ibc(proto_url = "MOCK_CONSENSUS_STATE_TYPE_URL")]
+	Mock(MockConsensusState),
+	#[ibc(proto_url = "GRANDPA_CONSENSUS_STATE_TYPE_URL")]
+	Grandpa(ics10_grandpa::consensus_state::ConsensusState),
+	#[ibc(proto_url = "BEEFY_CONSENSUS_STATE_TYPE_URL")]
+	Beefy(ics11_beefy::consensus_state::ConsensusState),
+	#[ibc(proto_url = "TENDERMINT_CONSENSUS_STATE_TYPE_URL")]
+	Tendermint(ics07_tendermint::consensus_state::ConsensusState),
+	#[ibc(proto_url = "WASM_CONSENSUS_STATE_TYPE_URL")]
+	Wasm(ics08_wasm::consensus_state::ConsensusState<Self>),
+}
+
+impl AnyConsensusState {
+	pub fn wasm(inner: Self) -> Self {
+		Self::Wasm(ics08_wasm::consensus_state::ConsensusState {
+			data: inner.encode_to_vec().expect("encode_to_vec failed"),
+			inner: Box::new(inner),
+		})
+	}
+}
+
+#[derive(Clone, Debug, ClientMessage)]
+#[allow(clippy::large_enum_variant)]
+pub enum AnyClientMessage {
+	Mock(MockClientMessage),
+	Grandpa(ics10_grandpa::client_message::ClientMessage),
+	Beefy(ics11_beefy::client_message::ClientMessage),
+	Tendermint(ics07_tendermint::client_message::ClientMessage),
+	Wasm(ics08_wasm::client_message::ClientMessage<Self>),
+}
+
+impl Protobuf<Any> for AnyClientMessage {}
+
+impl TryFrom<Any> for AnyClientMessage {
+	type Error = ics02_client::error::Error;
+
+	fn try_from(value: Any) -> Result<Self, Self::Error> {
+		PalletClientMessage::try_from(value)?.try_into()
+	}
+}
+
+impl TryFrom<PalletClientMessage> for AnyClientMessage {
+	type Error = ics02_client::error::Error;
+
+	fn try_from(message: PalletClientMessage) -> Result<Self, Self::Error> {
+		let message = match message {
+			PalletClientMessage::Grandpa(message) => Self::Grandpa(message),
+			PalletClientMessage::Beefy(message) => Self::Beefy(message),
+			PalletClientMessage::Tendermint(message) => Self::Tendermint(message),
+			PalletClientMessage::Wasm(message) =>
+				Self::Wasm(ics08_wasm::client_message::ClientMessage {
+					inner: Box::new((*message.inner).try_into()?),
+					data: message.data,
+				}),
+			PalletClientMessage::SoloMachine(_) =>
+				return Err(ics02_client::error::Error::unknown_header_type(
+					"solomachine client messages are not fuzzed".to_string(),
+				)),
+		};
+		Ok(message)
+	}
+}
+
+impl From<AnyClientMessage> for Any {
+	fn from(client_msg: AnyClientMessage) -> Self {
+		match client_msg {
+			AnyClientMessage::Mock(_mock) => {
+				panic!("MockClientMessage doesn't implement Protobuf");
+			},
+			AnyClientMessage::Grandpa(msg) => PalletClientMessage::Grandpa(msg).into(),
+			AnyClientMessage::Beefy(msg) => PalletClientMessage::Beefy(msg).into(),
+			AnyClientMessage::Tendermint(msg) => PalletClientMessage::Tendermint(msg).into(),
+			AnyClientMessage::Wasm(msg) => Any {
+				type_url: WASM_CLIENT_MESSAGE_TYPE_URL.to_string(),
+				value: msg.encode_vec().expect("encode_vec failed"),
+			},
+		}
+	}
+}
+
+#[derive(Debug, Eq, PartialEq, Clone, Default)]
+pub struct MockClientTypes;
+
+impl ClientTypes for MockClientTypes {
+	type AnyClientMessage = AnyClientMessage;
+	type AnyClientState = AnyClientState;
+	type AnyConsensusState = AnyConsensusState;
+	type ClientDef = AnyClient;
+}
+
+impl HostBlockType for MockClientTypes {
+	type HostBlock = MockHostBlock;
+}
+
+impl From<MockHostBlock> for AnyClientMessage {
+	fn from(block: MockHostBlock) -> Self {
+		let MockHostBlock::Mock(header) = block;
+		AnyClientMessage::Mock(MockClientMessage::Header(header))
+	}
+}
+
+impl From<MockHostBlock> for AnyConsensusState {
+	fn from(block: MockHostBlock) -> Self {
+		let MockHostBlock::Mock(header) = block;
+		AnyConsensusState::Mock(MockConsensusState::new(header))
+	}
+}
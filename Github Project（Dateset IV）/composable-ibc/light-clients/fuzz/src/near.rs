@@ -0,0 +1,125 @@
+// Copyright (C) 2022 ComposableFi.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Host functions of the NEAR client. Its [`ClientDef`](ibc::core::ics02_client::client_def)
+//! has no message encoding yet, so NEAR headers are fuzzed on their own rather than through
+//! the [`MockContext`](ibc::mock::context::MockContext) of the other clients.
+
+use ibc::core::ics02_client::error::Error;
+use ics13_near::client_def::{HostFunctions, HostFunctionsTrait};
+use pallet_ibc::light_clients::HostFunctionsManager;
+use sp_core::{ed25519, ByteArray};
+
+#[derive(Clone, Debug, PartialEq, Eq, Default)]
+pub struct NearHostFunctions;
+
+impl HostFunctions for NearHostFunctions {
+	fn keccak_256(input: &[u8]) -> [u8; 32] {
+		sp_io::hashing::keccak_256(input)
+	}
+
+	fn secp256k1_ecdsa_recover_compressed(
+		signature: &[u8; 65],
+		value: &[u8; 32],
+	) -> Option<Vec<u8>> {
+		sp_io::crypto::secp256k1_ecdsa_recover_compressed(signature, value)
+			.ok()
+			.map(|public| public.to_vec())
+	}
+
+	fn ed25519_verify(signature: &[u8; 64], msg: &[u8], pubkey: &[u8]) -> bool {
+		match ed25519::Public::from_slice(pubkey) {
+			Ok(pubkey) => sp_io::crypto::ed25519_verify(
+				&ed25519::Signature::from_raw(*signature),
+				msg,
+				&pubkey,
+			),
+			Err(_) => false,
+		}
+	}
+
+	fn verify_membership_trie_proof(
+		_root: &[u8; 32],
+		_proof: &[Vec<u8>],
+		_key: &[u8],
+		_value: &[u8],
+	) -> Result<(), Error> {
+		Err(Error::implementation_specific("trie proofs are not fuzzed".to_string()))
+	}
+
+	fn verify_non_membership_trie_proof(
+		_root: &[u8; 32],
+		_proof: &[Vec<u8>],
+		_key: &[u8],
+	) -> Result<(), Error> {
+		Err(Error::implementation_specific("trie proofs are not fuzzed".to_string()))
+	}
+
+	fn verify_timestamp_extrinsic(
+		_root: &[u8; 32],
+		_proof: &[Vec<u8>],
+		_value: &[u8],
+	) -> Result<(), Error> {
+		Err(Error::implementation_specific("trie proofs are not fuzzed".to_string()))
+	}
+
+	fn sha256_digest(data: &[u8]) -> [u8; 32] {
+		sp_io::hashing::sha2_256(data)
+	}
+
+	fn sha2_256(message: &[u8]) -> [u8; 32] {
+		sp_io::hashing::sha2_256(message)
+	}
+
+	fn sha2_512(message: &[u8]) -> [u8; 64] {
+		<HostFunctionsManager as ics23::HostFunctionsProvider>::sha2_512(message)
+	}
+
+	fn sha2_512_truncated(message: &[u8]) -> [u8; 32] {
+		<HostFunctionsManager as ics23::HostFunctionsProvider>::sha2_512_truncated(message)
+	}
+
+	fn sha3_512(message: &[u8]) -> [u8; 64] {
+		<HostFunctionsManager as ics23::HostFunctionsProvider>::sha3_512(message)
+	}
+
+	fn ripemd160(message: &[u8]) -> [u8; 20] {
+		<HostFunctionsManager as ics23::HostFunctionsProvider>::ripemd160(message)
+	}
+}
+
+impl ics23::HostFunctionsProvider for NearHostFunctions {
+	fn sha2_256(message: &[u8]) -> [u8; 32] {
+		<HostFunctionsManager as ics23::HostFunctionsProvider>::sha2_256(message)
+	}
+
+	fn sha2_512(message: &[u8]) -> [u8; 64] {
+		<HostFunctionsManager as ics23::HostFunctionsProvider>::sha2_512(message)
+	}
+
+	fn sha2_512_truncated(message: &[u8]) -> [u8; 32] {
+		<HostFunctionsManager as ics23::HostFunctionsProvider>::sha2_512_truncated(message)
+	}
+
+	fn sha3_512(message: &[u8]) -> [u8; 64] {
+		<HostFunctionsManager as ics23::HostFunctionsProvider>::sha3_512(message)
+	}
+
+	fn ripemd160(message: &[u8]) -> [u8; 20] {
+		<HostFunctionsManager as ics23::HostFunctionsProvider>::ripemd160(message)
+	}
+}
+
+impl HostFunctionsTrait for NearHostFunctions {}
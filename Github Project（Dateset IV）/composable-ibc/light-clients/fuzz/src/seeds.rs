@@ -0,0 +1,256 @@
+// Copyright (C) 2022 ComposableFi.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Seed inputs of the fuzzers, built from the test vectors of the light clients.
+
+use crate::{any_to_input, MembershipInput, NearHeaderInput};
+use borsh::BorshSerialize;
+use codec::Encode;
+use grandpa_client_primitives::FinalityProof;
+use ibc::{core::ics24_host::identifier::ClientId, Height};
+use ibc_proto::{
+	cosmos::ics23::v1::CommitmentProof as RawCommitmentProof, google::protobuf::Any,
+	ibc::core::commitment::v1::MerkleProof as RawMerkleProof,
+};
+use ics07_tendermint::{
+	client_message::{ClientMessage as TendermintClientMessage, Header, Misbehaviour},
+	merkle::convert_tm_to_ics_merkle_proof,
+};
+use ics10_grandpa::client_message::{
+	ClientMessage as GrandpaClientMessage, Header as GrandpaHeader,
+	Misbehaviour as GrandpaMisbehaviour,
+};
+use ics11_beefy::client_message::{BeefyHeader, ClientMessage as BeefyClientMessage};
+use ics13_near::types::{
+	BlockHeaderInnerLiteView, CryptoHash, LightClientBlockView, PublicKey as NearPublicKey,
+	ValidatorStakeView, ValidatorStakeViewV1,
+};
+use ics23::{
+	calculate_existence_root, commitment_proof::Proof, CommitmentProof, ExistenceProof, HashOp,
+	LeafOp, LengthOp,
+};
+use pallet_ibc::light_clients::{AnyClientMessage as PalletClientMessage, HostFunctionsManager};
+use prost::Message;
+use sp_core::H256;
+use std::{fs, io, path::Path};
+use tendermint::{
+	block::signed_header::SignedHeader,
+	validator::{Info as ValidatorInfo, Set as ValidatorSet},
+	PublicKey,
+};
+use tendermint_rpc::endpoint::abci_query::AbciQuery;
+
+/// The ics07 header of the tendermint test vectors, see `ics07_tendermint::client_message`.
+pub fn tendermint_header() -> Header {
+	let signed_header = serde_json::from_str::<SignedHeader>(include_str!(
+		"../../ics07-tendermint/src/mock/signed_header.json"
+	))
+	.expect("the test vector is valid");
+
+	let validator = ValidatorInfo::new(
+		PublicKey::from_raw_ed25519(
+			&hex::decode_upper("F349539C7E5EF7C49549B09C4BFC2335318AB0FE51FBFAA2433B4F13E816F4A7")
+				.expect("the test vector is valid"),
+		)
+		.expect("the test vector is valid"),
+		281_815_u64.try_into().expect("the test vector is valid"),
+	);
+	let validator_set = ValidatorSet::new(vec![validator.clone()], Some(validator));
+
+	Header {
+		signed_header,
+		validator_set: validator_set.clone(),
+		trusted_height: Height::new(0, 1),
+		trusted_validator_set: validator_set,
+	}
+}
+
+/// Client messages of every fuzzed light client, both as they are and wrapped in a wasm client
+/// message.
+pub fn client_messages() -> Vec<Any> {
+	let header = tendermint_header();
+	let misbehaviour = Misbehaviour {
+		client_id: ClientId::new("07-tendermint", 0).expect("client id is valid"),
+		header1: header.clone(),
+		header2: header.clone(),
+	};
+	let finality_proof =
+		|| FinalityProof { block: H256::zero(), justification: vec![], unknown_headers: vec![] };
+
+	let messages = vec![
+		PalletClientMessage::Tendermint(TendermintClientMessage::Header(header)),
+		PalletClientMessage::Tendermint(TendermintClientMessage::Misbehaviour(misbehaviour)),
+		PalletClientMessage::Grandpa(GrandpaClientMessage::Header(GrandpaHeader {
+			finality_proof: finality_proof(),
+			parachain_headers: Default::default(),
+			solochain_headers: Default::default(),
+			height: Height::new(0, 1),
+		})),
+		PalletClientMessage::Grandpa(GrandpaClientMessage::Misbehaviour(GrandpaMisbehaviour {
+			first_finality_proof: finality_proof(),
+			second_finality_proof: finality_proof(),
+		})),
+		PalletClientMessage::Beefy(BeefyClientMessage::Header(BeefyHeader {
+			headers_with_proof: None,
+			mmr_update_proof: None,
+		})),
+	];
+
+	messages
+		.into_iter()
+		.flat_map(|message| {
+			let wasm = PalletClientMessage::wasm(message.clone()).expect("encode_vec failed");
+			[Any::from(message), Any::from(wasm)]
+		})
+		.collect()
+}
+
+/// Membership proofs of the tendermint test vectors, and a valid proof built the way the IAVL
+/// store and the multistore do.
+pub fn membership_inputs() -> Vec<MembershipInput> {
+	let test_vectors = [
+		(
+			include_str!(
+				"../../ics07-tendermint/src/mock/query/serialization/client_state_proof.json"
+			),
+			"clients/ibconeclient/clientState",
+		),
+		(
+			include_str!(
+				"../../ics07-tendermint/src/mock/query/serialization/consensus_state_proof.json"
+			),
+			"clients/ibconeclient/consensusStates/0-22",
+		),
+	];
+	let mut inputs = test_vectors
+		.into_iter()
+		.filter_map(|(json, path)| {
+			let query = serde_json::from_str::<AbciQuery>(json).ok()?;
+			let proof =
+				convert_tm_to_ics_merkle_proof::<HostFunctionsManager>(&query.proof?).ok()?;
+			Some(MembershipInput {
+				prefix: b"ibc".to_vec(),
+				proof: RawMerkleProof::from(proof).encode_to_vec(),
+				// the app hash the proofs were queried at is not part of the test vectors
+				root: vec![0; 32],
+				path: path.to_string(),
+				value: query.value,
+			})
+		})
+		.collect::<Vec<_>>();
+
+	let path = "clients/07-tendermint-0/clientState";
+	let value = b"client state".to_vec();
+	let (store_proof, store_root) = existence_proof(path.as_bytes(), value.clone(), vec![0, 2, 2]);
+	let (multistore_proof, root) = existence_proof(b"ibc", store_root, vec![0]);
+	inputs.push(MembershipInput {
+		prefix: b"ibc".to_vec(),
+		proof: RawMerkleProof { proofs: vec![store_proof, multistore_proof] }.encode_to_vec(),
+		root,
+		path: path.to_string(),
+		value,
+	});
+	inputs
+}
+
+/// A single leaf existence proof and the root it proves, `prefix` is the prefix of the leaf.
+fn existence_proof(key: &[u8], value: Vec<u8>, prefix: Vec<u8>) -> (RawCommitmentProof, Vec<u8>) {
+	let proof = ExistenceProof {
+		key: key.to_vec(),
+		value,
+		leaf: Some(LeafOp {
+			hash: HashOp::Sha256.into(),
+			prehash_key: HashOp::NoHash.into(),
+			prehash_value: HashOp::Sha256.into(),
+			length: LengthOp::VarProto.into(),
+			prefix,
+		}),
+		path: vec![],
+	};
+	let root = calculate_existence_root::<HostFunctionsManager>(&proof)
+		.expect("the existence proof is valid");
+	let proof = CommitmentProof { proof: Some(Proof::Exist(proof)) };
+	let proof = RawCommitmentProof::decode(&*proof.encode_to_vec())
+		.expect("ics23 and ibc-proto commitment proofs have the same encoding");
+	(proof, root)
+}
+
+/// NEAR headers of the epoch of the trusted head and of the next one. There are no NEAR test
+/// vectors, so the blocks are made up and their approvals are missing.
+pub fn near_header_inputs() -> Vec<NearHeaderInput> {
+	let block = |height, epoch_id, next_epoch_id, next_bps| LightClientBlockView {
+		prev_block_hash: CryptoHash([0; 32]),
+		next_block_inner_hash: CryptoHash([0; 32]),
+		inner_lite: BlockHeaderInnerLiteView {
+			height,
+			epoch_id: CryptoHash(epoch_id),
+			next_epoch_id: CryptoHash(next_epoch_id),
+			prev_state_root: CryptoHash([0; 32]),
+			outcome_root: CryptoHash([0; 32]),
+			timestamp: height,
+			timestamp_nanosec: height,
+			next_bp_hash: CryptoHash([0; 32]),
+			block_merkle_root: CryptoHash([0; 32]),
+		},
+		inner_rest_hash: CryptoHash([0; 32]),
+		next_bps,
+		approvals_after_next: vec![None],
+	};
+	let validators = vec![ValidatorStakeView::V1(ValidatorStakeViewV1 {
+		account_id: "validator.near".to_string(),
+		public_key: NearPublicKey([1; 32]),
+		stake: 100,
+	})];
+	let head = block(1, [1; 32], [2; 32], Some(validators.clone()));
+
+	vec![
+		NearHeaderInput {
+			head: head.clone(),
+			validators: validators.clone(),
+			header: block(2, [1; 32], [2; 32], None),
+		},
+		NearHeaderInput {
+			head,
+			validators: validators.clone(),
+			header: block(3, [2; 32], [3; 32], Some(validators)),
+		},
+	]
+}
+
+/// Writes the seeds of every fuzz target to `corpus/<target>` under `dir`.
+pub fn write_corpus(dir: &Path) -> io::Result<()> {
+	let client_messages = client_messages().iter().filter_map(any_to_input).collect::<Vec<_>>();
+	let membership_inputs =
+		membership_inputs().iter().map(|input| input.encode()).collect::<Vec<_>>();
+	let near_header_inputs = near_header_inputs()
+		.iter()
+		.map(|input| input.try_to_vec())
+		.collect::<io::Result<Vec<_>>>()?;
+
+	for (target, seeds) in [
+		("client_message_decode", &client_messages),
+		("verify_client_message", &client_messages),
+		("check_for_misbehaviour", &client_messages),
+		("verify_membership", &membership_inputs),
+		("verify_near_header", &near_header_inputs),
+	] {
+		let target_dir = dir.join("corpus").join(target);
+		fs::create_dir_all(&target_dir)?;
+		for (i, seed) in seeds.iter().enumerate() {
+			fs::write(target_dir.join(format!("seed-{i}")), seed)?;
+		}
+	}
+	Ok(())
+}
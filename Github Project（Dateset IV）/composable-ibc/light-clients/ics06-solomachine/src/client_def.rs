@@ -0,0 +1,433 @@
+// Copyright (C) 2022 ComposableFi.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::{
+	client_message::ClientMessage, client_state::ClientState, consensus_state::ConsensusState,
+	error::Error, HostFunctions,
+};
+use alloc::vec::Vec;
+use core::marker::PhantomData;
+use ibc::{
+	core::{
+		ics02_client::{
+			client_consensus::ConsensusState as _,
+			client_def::{ClientDef, ConsensusUpdateResult},
+			client_state::ClientState as _,
+			error::Error as Ics02Error,
+		},
+		ics03_connection::connection::ConnectionEnd,
+		ics04_channel::{
+			channel::ChannelEnd,
+			commitment::{AcknowledgementCommitment, PacketCommitment},
+			packet::{Receipt, Sequence},
+			upgrade::{ErrorReceipt, Upgrade},
+		},
+		ics23_commitment::commitment::{CommitmentPrefix, CommitmentProofBytes, CommitmentRoot},
+		ics24_host::{
+			identifier::{ChannelId, ClientId, ConnectionId, PortId},
+			path::{
+				AcksPath, ChannelEndsPath, ChannelUpgradeErrorPath, ChannelUpgradePath,
+				ClientConsensusStatePath, ClientStatePath, CommitmentsPath, ConnectionsPath,
+				ReceiptsPath, SeqRecvsPath,
+			},
+			Path,
+		},
+		ics26_routing::context::ReaderContext,
+	},
+	Height,
+};
+use tendermint_proto::Protobuf;
+
+#[derive(Clone, Debug, PartialEq, Eq, Default)]
+pub struct SoloMachineClient<H>(PhantomData<H>);
+
+impl<H> ClientDef for SoloMachineClient<H>
+where
+	H: HostFunctions,
+{
+	type ClientMessage = ClientMessage;
+	type ClientState = ClientState<H>;
+	type ConsensusState = ConsensusState;
+
+	fn verify_client_message<Ctx: ReaderContext>(
+		&self,
+		_ctx: &Ctx,
+		_client_id: ClientId,
+		client_state: Self::ClientState,
+		client_message: Self::ClientMessage,
+	) -> Result<(), Ics02Error> {
+		match client_message {
+			ClientMessage::Header(header) => client_state.verify_header(&header)?,
+			ClientMessage::Misbehaviour(misbehaviour) =>
+				client_state.verify_misbehaviour(&misbehaviour)?,
+		}
+		Ok(())
+	}
+
+	fn update_state<Ctx: ReaderContext>(
+		&self,
+		_ctx: &Ctx,
+		_client_id: ClientId,
+		client_state: Self::ClientState,
+		client_message: Self::ClientMessage,
+	) -> Result<(Self::ClientState, ConsensusUpdateResult<Ctx>), Ics02Error> {
+		let header = match client_message {
+			ClientMessage::Header(header) => header,
+			ClientMessage::Misbehaviour(_) =>
+				Err(Ics02Error::implementation_specific("Misbehaviour is not a header".into()))?,
+		};
+
+		let consensus_state =
+			ConsensusState::new(header.new_public_key, header.new_diversifier, header.timestamp);
+		let client_state = ClientState {
+			sequence: client_state.sequence + 1,
+			consensus_state: consensus_state.clone(),
+			..client_state
+		};
+
+		Ok((
+			client_state,
+			ConsensusUpdateResult::Single(
+				Ctx::AnyConsensusState::wrap(&consensus_state)
+					.expect("AnyConsensusState is type-checked; qed"),
+			),
+		))
+	}
+
+	fn update_state_on_misbehaviour(
+		&self,
+		mut client_state: Self::ClientState,
+		_client_message: Self::ClientMessage,
+	) -> Result<Self::ClientState, Ics02Error> {
+		client_state.is_frozen = true;
+		Ok(client_state)
+	}
+
+	fn check_for_misbehaviour<Ctx: ReaderContext>(
+		&self,
+		_ctx: &Ctx,
+		_client_id: ClientId,
+		_client_state: Self::ClientState,
+		client_message: Self::ClientMessage,
+	) -> Result<bool, Ics02Error> {
+		// headers are signed over the current sequence, so two headers at the same sequence can
+		// only be submitted as misbehaviour
+		Ok(matches!(client_message, ClientMessage::Misbehaviour(_)))
+	}
+
+	fn verify_upgrade_and_update_state<Ctx: ReaderContext>(
+		&self,
+		_ctx: &Ctx,
+		_client_id: ClientId,
+		_old_client_state: &Self::ClientState,
+		_upgrade_client_state: &Self::ClientState,
+		_upgrade_consensus_state: &Self::ConsensusState,
+		_proof_upgrade_client: Vec<u8>,
+		_proof_upgrade_consensus_state: Vec<u8>,
+	) -> Result<(Self::ClientState, ConsensusUpdateResult<Ctx>), Ics02Error> {
+		Err(Error::upgrade_not_supported().into())
+	}
+
+	/// Replaces the public key, diversifier and sequence of the subject client with the ones of
+	/// the substitute and unfreezes it. The substitute must use a different public key.
+	fn check_substitute_and_update_state<Ctx: ReaderContext>(
+		&self,
+		_ctx: &Ctx,
+		_subject_client_id: ClientId,
+		_substitute_client_id: ClientId,
+		old_client_state: Self::ClientState,
+		substitute_client_state: Self::ClientState,
+	) -> Result<(Self::ClientState, ConsensusUpdateResult<Ctx>), Ics02Error> {
+		if old_client_state.consensus_state.public_key ==
+			substitute_client_state.consensus_state.public_key
+		{
+			return Err(Error::substitute_same_public_key().into())
+		}
+
+		let consensus_state = substitute_client_state.consensus_state.clone();
+		let client_state = ClientState { is_frozen: false, ..substitute_client_state };
+
+		Ok((
+			client_state,
+			ConsensusUpdateResult::Single(
+				Ctx::AnyConsensusState::wrap(&consensus_state)
+					.expect("AnyConsensusState is type-checked; qed"),
+			),
+		))
+	}
+
+	fn verify_client_consensus_state<Ctx: ReaderContext>(
+		&self,
+		_ctx: &Ctx,
+		client_state: &Self::ClientState,
+		_height: Height,
+		prefix: &CommitmentPrefix,
+		proof: &CommitmentProofBytes,
+		_root: &CommitmentRoot,
+		client_id: &ClientId,
+		consensus_height: Height,
+		expected_consensus_state: &Ctx::AnyConsensusState,
+	) -> Result<(), Ics02Error> {
+		let path = ClientConsensusStatePath {
+			client_id: client_id.clone(),
+			epoch: consensus_height.revision_number,
+			height: consensus_height.revision_height,
+		};
+		let value = expected_consensus_state.encode_to_vec().map_err(Ics02Error::encode)?;
+		client_state.verify_signature(prefix, proof, path, value)?;
+		Ok(())
+	}
+
+	fn verify_connection_state<Ctx: ReaderContext>(
+		&self,
+		_ctx: &Ctx,
+		_client_id: &ClientId,
+		client_state: &Self::ClientState,
+		_height: Height,
+		prefix: &CommitmentPrefix,
+		proof: &CommitmentProofBytes,
+		_root: &CommitmentRoot,
+		connection_id: &ConnectionId,
+		expected_connection_end: &ConnectionEnd,
+	) -> Result<(), Ics02Error> {
+		let path = ConnectionsPath(connection_id.clone());
+		let value = expected_connection_end.encode_vec().map_err(Ics02Error::encode)?;
+		client_state.verify_signature(prefix, proof, path, value)?;
+		Ok(())
+	}
+
+	fn verify_channel_state<Ctx: ReaderContext>(
+		&self,
+		_ctx: &Ctx,
+		_client_id: &ClientId,
+		client_state: &Self::ClientState,
+		_height: Height,
+		prefix: &CommitmentPrefix,
+		proof: &CommitmentProofBytes,
+		_root: &CommitmentRoot,
+		port_id: &PortId,
+		channel_id: &ChannelId,
+		expected_channel_end: &ChannelEnd,
+	) -> Result<(), Ics02Error> {
+		let path = ChannelEndsPath(port_id.clone(), *channel_id);
+		let value = expected_channel_end.encode_vec().map_err(Ics02Error::encode)?;
+		client_state.verify_signature(prefix, proof, path, value)?;
+		Ok(())
+	}
+
+	fn verify_channel_upgrade<Ctx: ReaderContext>(
+		&self,
+		_ctx: &Ctx,
+		_client_id: &ClientId,
+		client_state: &Self::ClientState,
+		_height: Height,
+		prefix: &CommitmentPrefix,
+		proof: &CommitmentProofBytes,
+		_root: &CommitmentRoot,
+		port_id: &PortId,
+		channel_id: &ChannelId,
+		expected_upgrade: &Upgrade,
+	) -> Result<(), Ics02Error> {
+		let path = ChannelUpgradePath(port_id.clone(), *channel_id);
+		let value = expected_upgrade.encode_vec().map_err(Ics02Error::encode)?;
+		client_state.verify_signature(prefix, proof, path, value)?;
+		Ok(())
+	}
+
+	fn verify_channel_upgrade_error<Ctx: ReaderContext>(
+		&self,
+		_ctx: &Ctx,
+		_client_id: &ClientId,
+		client_state: &Self::ClientState,
+		_height: Height,
+		prefix: &CommitmentPrefix,
+		proof: &CommitmentProofBytes,
+		_root: &CommitmentRoot,
+		port_id: &PortId,
+		channel_id: &ChannelId,
+		expected_error_receipt: &ErrorReceipt,
+	) -> Result<(), Ics02Error> {
+		let path = ChannelUpgradeErrorPath(port_id.clone(), *channel_id);
+		let value = expected_error_receipt.encode_vec().map_err(Ics02Error::encode)?;
+		client_state.verify_signature(prefix, proof, path, value)?;
+		Ok(())
+	}
+
+	fn verify_client_full_state<Ctx: ReaderContext>(
+		&self,
+		_ctx: &Ctx,
+		client_state: &Self::ClientState,
+		_height: Height,
+		prefix: &CommitmentPrefix,
+		proof: &CommitmentProofBytes,
+		_root: &CommitmentRoot,
+		client_id: &ClientId,
+		expected_client_state: &Ctx::AnyClientState,
+	) -> Result<(), Ics02Error> {
+		let path = ClientStatePath(client_id.clone());
+		let value = expected_client_state.encode_to_vec().map_err(Ics02Error::encode)?;
+		client_state.verify_signature(prefix, proof, path, value)?;
+		Ok(())
+	}
+
+	fn verify_packet_data<Ctx: ReaderContext>(
+		&self,
+		_ctx: &Ctx,
+		_client_id: &ClientId,
+		client_state: &Self::ClientState,
+		_height: Height,
+		connection_end: &ConnectionEnd,
+		proof: &CommitmentProofBytes,
+		_root: &CommitmentRoot,
+		port_id: &PortId,
+		channel_id: &ChannelId,
+		sequence: Sequence,
+		commitment: PacketCommitment,
+	) -> Result<(), Ics02Error> {
+		let path = CommitmentsPath { port_id: port_id.clone(), channel_id: *channel_id, sequence };
+		client_state.verify_signature(
+			connection_end.counterparty().prefix(),
+			proof,
+			path,
+			commitment.into_vec(),
+		)?;
+		Ok(())
+	}
+
+	fn verify_packet_acknowledgement<Ctx: ReaderContext>(
+		&self,
+		_ctx: &Ctx,
+		_client_id: &ClientId,
+		client_state: &Self::ClientState,
+		_height: Height,
+		connection_end: &ConnectionEnd,
+		proof: &CommitmentProofBytes,
+		_root: &CommitmentRoot,
+		port_id: &PortId,
+		channel_id: &ChannelId,
+		sequence: Sequence,
+		ack: AcknowledgementCommitment,
+	) -> Result<(), Ics02Error> {
+		let path = AcksPath { port_id: port_id.clone(), channel_id: *channel_id, sequence };
+		client_state.verify_signature(
+			connection_end.counterparty().prefix(),
+			proof,
+			path,
+			ack.into_vec(),
+		)?;
+		Ok(())
+	}
+
+	fn verify_next_sequence_recv<Ctx: ReaderContext>(
+		&self,
+		_ctx: &Ctx,
+		_client_id: &ClientId,
+		client_state: &Self::ClientState,
+		_height: Height,
+		connection_end: &ConnectionEnd,
+		proof: &CommitmentProofBytes,
+		_root: &CommitmentRoot,
+		port_id: &PortId,
+		channel_id: &ChannelId,
+		sequence: Sequence,
+	) -> Result<(), Ics02Error> {
+		let path = SeqRecvsPath(port_id.clone(), *channel_id);
+		client_state.verify_signature(
+			connection_end.counterparty().prefix(),
+			proof,
+			path,
+			u64::from(sequence).to_be_bytes().to_vec(),
+		)?;
+		Ok(())
+	}
+
+	fn verify_packet_receipt<Ctx: ReaderContext>(
+		&self,
+		_ctx: &Ctx,
+		_client_id: &ClientId,
+		client_state: &Self::ClientState,
+		_height: Height,
+		connection_end: &ConnectionEnd,
+		proof: &CommitmentProofBytes,
+		_root: &CommitmentRoot,
+		port_id: &PortId,
+		channel_id: &ChannelId,
+		sequence: Sequence,
+		receipt: Receipt,
+	) -> Result<(), Ics02Error> {
+		let path = ReceiptsPath { port_id: port_id.clone(), channel_id: *channel_id, sequence };
+		client_state.verify_signature(
+			connection_end.counterparty().prefix(),
+			proof,
+			path,
+			receipt.as_bytes().to_vec(),
+		)?;
+		Ok(())
+	}
+
+	fn verify_batch_membership<Ctx: ReaderContext>(
+		&self,
+		_ctx: &Ctx,
+		_client_id: &ClientId,
+		_client_state: &Self::ClientState,
+		_height: Height,
+		_connection_end: &ConnectionEnd,
+		_proof: &CommitmentProofBytes,
+		_root: &CommitmentRoot,
+		_items: Vec<(Path, Vec<u8>)>,
+	) -> Result<(), Ics02Error> {
+		Err(Error::batch_proof_not_supported().into())
+	}
+
+	fn verify_packet_receipt_absence<Ctx: ReaderContext>(
+		&self,
+		_ctx: &Ctx,
+		_client_id: &ClientId,
+		client_state: &Self::ClientState,
+		_height: Height,
+		connection_end: &ConnectionEnd,
+		proof: &CommitmentProofBytes,
+		_root: &CommitmentRoot,
+		port_id: &PortId,
+		channel_id: &ChannelId,
+		sequence: Sequence,
+	) -> Result<(), Ics02Error> {
+		let path = ReceiptsPath { port_id: port_id.clone(), channel_id: *channel_id, sequence };
+		client_state.verify_signature(
+			connection_end.counterparty().prefix(),
+			proof,
+			path,
+			Vec::new(),
+		)?;
+		Ok(())
+	}
+
+	fn update_state_on_proof_verified<Ctx: ReaderContext>(
+		&self,
+		_ctx: &Ctx,
+		client_state: Self::ClientState,
+		proof: &CommitmentProofBytes,
+	) -> Result<Option<(Self::ClientState, ConsensusUpdateResult<Ctx>)>, Ics02Error> {
+		if client_state.is_frozen {
+			return Ok(None)
+		}
+		// the consensus state is stored again at the advanced height, which later proofs are
+		// checked against
+		let client_state = client_state.on_proof_verified(proof)?;
+		let consensus_state = Ctx::AnyConsensusState::wrap(&client_state.consensus_state)
+			.expect("AnyConsensusState is type-checked; qed");
+		Ok(Some((client_state, ConsensusUpdateResult::Single(consensus_state))))
+	}
+}
@@ -0,0 +1,271 @@
+// Copyright (C) 2022 ComposableFi.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::{
+	consensus_state::PublicKey,
+	error::Error,
+	proto::{
+		Header as RawHeader, HeaderData as RawHeaderData, Misbehaviour as RawMisbehaviour,
+		SignBytes as RawSignBytes, SignatureAndData as RawSignatureAndData,
+	},
+};
+use alloc::{
+	format,
+	string::{String, ToString},
+	vec::Vec,
+};
+use ibc::core::ics02_client;
+use ibc_proto::google::protobuf::Any;
+use prost::Message;
+use tendermint_proto::Protobuf;
+
+pub const SOLOMACHINE_HEADER_TYPE_URL: &str = "/ibc.lightclients.solomachine.v3.Header";
+pub const SOLOMACHINE_MISBEHAVIOUR_TYPE_URL: &str = "/ibc.lightclients.solomachine.v3.Misbehaviour";
+pub const SOLOMACHINE_CLIENT_MESSAGE_TYPE_URL: &str =
+	"/ibc.lightclients.solomachine.v3.ClientMessage";
+
+/// Path the solo machine signs headers over
+pub const SENTINEL_HEADER_PATH: &[u8] = b"solomachine:header";
+
+/// Bytes signed by the solo machine, the signature is verified against their protobuf encoding
+pub fn sign_bytes(
+	sequence: u64,
+	timestamp: u64,
+	diversifier: String,
+	path: Vec<u8>,
+	data: Vec<u8>,
+) -> Vec<u8> {
+	RawSignBytes { sequence, timestamp, diversifier, path, data }.encode_to_vec()
+}
+
+/// Rotates the public key and diversifier of the solo machine, incrementing its sequence.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Header {
+	/// Timestamp of the new consensus state in nanoseconds
+	pub timestamp: u64,
+	/// Signature of the header data by the current public key
+	pub signature: Vec<u8>,
+	pub new_public_key: PublicKey,
+	pub new_diversifier: String,
+}
+
+impl Header {
+	/// Bytes signed by the solo machine over this header at the given `sequence` with the current
+	/// `diversifier`
+	pub fn sign_bytes(&self, sequence: u64, diversifier: String) -> Vec<u8> {
+		let data = RawHeaderData {
+			new_pub_key: Some(self.new_public_key.clone().into()),
+			new_diversifier: self.new_diversifier.clone(),
+		}
+		.encode_to_vec();
+		sign_bytes(sequence, self.timestamp, diversifier, SENTINEL_HEADER_PATH.to_vec(), data)
+	}
+}
+
+/// A signature and the data signed over to create it.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SignatureAndData {
+	pub signature: Vec<u8>,
+	pub path: Vec<u8>,
+	pub data: Vec<u8>,
+	pub timestamp: u64,
+}
+
+impl SignatureAndData {
+	pub fn sign_bytes(&self, sequence: u64, diversifier: String) -> Vec<u8> {
+		sign_bytes(sequence, self.timestamp, diversifier, self.path.clone(), self.data.clone())
+	}
+}
+
+/// Two signatures over different messages at the same sequence.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Misbehaviour {
+	pub sequence: u64,
+	pub signature_one: SignatureAndData,
+	pub signature_two: SignatureAndData,
+}
+
+impl Misbehaviour {
+	pub fn validate_basic(&self) -> Result<(), Error> {
+		if self.sequence == 0 {
+			return Err(Error::invalid_sequence(self.sequence))
+		}
+		if self.signature_one.signature == self.signature_two.signature {
+			return Err(Error::equal_misbehaviour_signatures())
+		}
+		if self.signature_one.path == self.signature_two.path &&
+			self.signature_one.data == self.signature_two.data
+		{
+			return Err(Error::equal_misbehaviour_data())
+		}
+		Ok(())
+	}
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ClientMessage {
+	Header(Header),
+	Misbehaviour(Misbehaviour),
+}
+
+impl ics02_client::client_message::ClientMessage for ClientMessage {
+	fn encode_to_vec(&self) -> Result<Vec<u8>, tendermint_proto::Error> {
+		self.encode_vec()
+	}
+}
+
+impl Protobuf<Any> for ClientMessage {}
+
+impl TryFrom<Any> for ClientMessage {
+	type Error = Error;
+
+	fn try_from(any: Any) -> Result<Self, Self::Error> {
+		let msg = match &*any.type_url {
+			SOLOMACHINE_HEADER_TYPE_URL => Self::Header(
+				Header::decode_vec(&any.value).map_err(|e| Error::protobuf(format!("{e}")))?,
+			),
+			SOLOMACHINE_MISBEHAVIOUR_TYPE_URL => Self::Misbehaviour(
+				Misbehaviour::decode_vec(&any.value)
+					.map_err(|e| Error::protobuf(format!("{e}")))?,
+			),
+			_ => Err(Error::unknown_message_type(any.type_url))?,
+		};
+
+		Ok(msg)
+	}
+}
+
+impl From<ClientMessage> for Any {
+	fn from(msg: ClientMessage) -> Self {
+		match msg {
+			ClientMessage::Header(header) => Any {
+				value: header.encode_vec().expect("failed to encode ClientMessage.header"),
+				type_url: SOLOMACHINE_HEADER_TYPE_URL.to_string(),
+			},
+			ClientMessage::Misbehaviour(misbehaviour) => Any {
+				value: misbehaviour
+					.encode_vec()
+					.expect("failed to encode ClientMessage.misbehaviour"),
+				type_url: SOLOMACHINE_MISBEHAVIOUR_TYPE_URL.to_string(),
+			},
+		}
+	}
+}
+
+impl Protobuf<RawHeader> for Header {}
+
+impl TryFrom<RawHeader> for Header {
+	type Error = Error;
+
+	fn try_from(raw: RawHeader) -> Result<Self, Self::Error> {
+		if raw.timestamp == 0 {
+			return Err(Error::missing_field("timestamp".to_string()))
+		}
+		if raw.signature.is_empty() {
+			return Err(Error::missing_field("signature".to_string()))
+		}
+		if raw.new_diversifier.trim().is_empty() {
+			return Err(Error::empty_diversifier())
+		}
+		let new_public_key = raw
+			.new_public_key
+			.ok_or_else(|| Error::missing_field("new_public_key".to_string()))?
+			.try_into()?;
+
+		Ok(Header {
+			timestamp: raw.timestamp,
+			signature: raw.signature,
+			new_public_key,
+			new_diversifier: raw.new_diversifier,
+		})
+	}
+}
+
+impl From<Header> for RawHeader {
+	fn from(header: Header) -> Self {
+		RawHeader {
+			timestamp: header.timestamp,
+			signature: header.signature,
+			new_public_key: Some(header.new_public_key.into()),
+			new_diversifier: header.new_diversifier,
+		}
+	}
+}
+
+impl TryFrom<RawSignatureAndData> for SignatureAndData {
+	type Error = Error;
+
+	fn try_from(raw: RawSignatureAndData) -> Result<Self, Self::Error> {
+		if raw.signature.is_empty() {
+			return Err(Error::missing_field("signature".to_string()))
+		}
+		if raw.path.is_empty() {
+			return Err(Error::missing_field("path".to_string()))
+		}
+		if raw.timestamp == 0 {
+			return Err(Error::missing_field("timestamp".to_string()))
+		}
+
+		Ok(SignatureAndData {
+			signature: raw.signature,
+			path: raw.path,
+			data: raw.data,
+			timestamp: raw.timestamp,
+		})
+	}
+}
+
+impl From<SignatureAndData> for RawSignatureAndData {
+	fn from(value: SignatureAndData) -> Self {
+		RawSignatureAndData {
+			signature: value.signature,
+			path: value.path,
+			data: value.data,
+			timestamp: value.timestamp,
+		}
+	}
+}
+
+impl Protobuf<RawMisbehaviour> for Misbehaviour {}
+
+impl TryFrom<RawMisbehaviour> for Misbehaviour {
+	type Error = Error;
+
+	fn try_from(raw: RawMisbehaviour) -> Result<Self, Self::Error> {
+		let misbehaviour = Misbehaviour {
+			sequence: raw.sequence,
+			signature_one: raw
+				.signature_one
+				.ok_or_else(|| Error::missing_field("signature_one".to_string()))?
+				.try_into()?,
+			signature_two: raw
+				.signature_two
+				.ok_or_else(|| Error::missing_field("signature_two".to_string()))?
+				.try_into()?,
+		};
+		misbehaviour.validate_basic()?;
+		Ok(misbehaviour)
+	}
+}
+
+impl From<Misbehaviour> for RawMisbehaviour {
+	fn from(value: Misbehaviour) -> Self {
+		RawMisbehaviour {
+			sequence: value.sequence,
+			signature_one: Some(value.signature_one.into()),
+			signature_two: Some(value.signature_two.into()),
+		}
+	}
+}
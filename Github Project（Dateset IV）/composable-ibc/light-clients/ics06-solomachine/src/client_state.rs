@@ -0,0 +1,240 @@
+// Copyright (C) 2022 ComposableFi.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::{
+	client_def::SoloMachineClient,
+	client_message::{sign_bytes, Header, Misbehaviour},
+	consensus_state::ConsensusState,
+	error::Error,
+	proto::{ClientState as RawClientState, TimestampedSignatureData},
+	HostFunctions,
+};
+use alloc::{
+	string::{String, ToString},
+	vec::Vec,
+};
+use core::{marker::PhantomData, time::Duration};
+use ibc::{
+	core::{
+		ics02_client::client_state::ClientType,
+		ics23_commitment::commitment::{CommitmentPrefix, CommitmentProofBytes},
+		ics24_host::{identifier::ChainId, path::Path},
+	},
+	Height,
+};
+use ibc_proto::google::protobuf::Any;
+use prost::Message;
+use serde::{Deserialize, Serialize};
+use tendermint_proto::Protobuf;
+
+/// Protobuf type url for the solo machine ClientState
+pub const SOLOMACHINE_CLIENT_STATE_TYPE_URL: &str = "/ibc.lightclients.solomachine.v3.ClientState";
+
+/// Solo machines don't have revisions, all heights use this revision number.
+pub const SOLOMACHINE_REVISION_NUMBER: u64 = 0;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ClientState<H> {
+	/// Sequence of the latest header, used as the height of the client
+	pub sequence: u64,
+	/// Whether misbehaviour of the solo machine was submitted
+	pub is_frozen: bool,
+	/// Current public key, diversifier and timestamp of the solo machine
+	pub consensus_state: ConsensusState,
+	pub _phantom: PhantomData<H>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct UpgradeOptions;
+
+impl<H> ClientState<H> {
+	pub fn new(sequence: u64, consensus_state: ConsensusState) -> Self {
+		Self { sequence, is_frozen: false, consensus_state, _phantom: PhantomData }
+	}
+
+	pub fn latest_height(&self) -> Height {
+		Height::new(SOLOMACHINE_REVISION_NUMBER, self.sequence)
+	}
+
+	pub fn chain_id(&self) -> ChainId {
+		ChainId::new(self.consensus_state.diversifier.clone(), SOLOMACHINE_REVISION_NUMBER)
+	}
+
+	pub fn client_type() -> ClientType {
+		"06-solomachine".to_string()
+	}
+
+	pub fn frozen_height(&self) -> Option<Height> {
+		// solo machines are frozen at a sentinel height, like in ibc-go
+		self.is_frozen.then(|| Height::new(SOLOMACHINE_REVISION_NUMBER, 1))
+	}
+}
+
+impl<H: HostFunctions> ClientState<H> {
+	pub fn to_any(&self) -> Any {
+		Any {
+			type_url: SOLOMACHINE_CLIENT_STATE_TYPE_URL.to_string(),
+			value: self.encode_vec().expect("encode ClientState"),
+		}
+	}
+
+	/// Verify the header is signed by the current public key of the solo machine
+	pub fn verify_header(&self, header: &Header) -> Result<(), Error> {
+		if header.timestamp < self.consensus_state.timestamp {
+			return Err(Error::timestamp_too_old(header.timestamp, self.consensus_state.timestamp))
+		}
+		let sign_bytes = header.sign_bytes(self.sequence, self.consensus_state.diversifier.clone());
+		self.consensus_state.public_key.verify::<H>(&header.signature, &sign_bytes)
+	}
+
+	/// Verify both signatures of the misbehaviour are valid signatures of the solo machine
+	pub fn verify_misbehaviour(&self, misbehaviour: &Misbehaviour) -> Result<(), Error> {
+		misbehaviour.validate_basic()?;
+		if misbehaviour.sequence != self.sequence {
+			return Err(Error::sequence_mismatch(self.sequence, misbehaviour.sequence))
+		}
+		for signature in [&misbehaviour.signature_one, &misbehaviour.signature_two] {
+			let sign_bytes = signature
+				.sign_bytes(misbehaviour.sequence, self.consensus_state.diversifier.clone());
+			self.consensus_state.public_key.verify::<H>(&signature.signature, &sign_bytes)?;
+		}
+		Ok(())
+	}
+
+	/// Verify the solo machine signed `value` at `path`. For non-membership proofs `value` is
+	/// empty.
+	pub fn verify_signature(
+		&self,
+		prefix: &CommitmentPrefix,
+		proof: &CommitmentProofBytes,
+		path: impl Into<Path>,
+		value: Vec<u8>,
+	) -> Result<(), Error> {
+		if self.is_frozen {
+			return Err(Error::frozen_client())
+		}
+		let proof = TimestampedSignatureData::decode(proof.as_bytes()).map_err(Error::decode)?;
+		if proof.timestamp < self.consensus_state.timestamp {
+			return Err(Error::timestamp_too_old(proof.timestamp, self.consensus_state.timestamp))
+		}
+		let sign_bytes = sign_bytes(
+			self.sequence,
+			proof.timestamp,
+			self.consensus_state.diversifier.clone(),
+			signature_path(prefix, path),
+			value,
+		);
+		self.consensus_state.public_key.verify::<H>(&proof.signature_data, &sign_bytes)
+	}
+
+	/// Returns the client state after `proof` was verified with [`Self::verify_signature`]. The
+	/// sequence is advanced so the signature cannot be replayed, and the consensus timestamp is
+	/// moved to the timestamp of the proof.
+	pub fn on_proof_verified(&self, proof: &CommitmentProofBytes) -> Result<Self, Error> {
+		let proof = TimestampedSignatureData::decode(proof.as_bytes()).map_err(Error::decode)?;
+		let mut client_state = self.clone();
+		client_state.sequence += 1;
+		client_state.consensus_state.timestamp = proof.timestamp;
+		Ok(client_state)
+	}
+}
+
+/// The path signed over by the solo machine, the `String` representation of the ICS-23 merkle
+/// path of the key under the commitment prefix.
+pub fn signature_path(prefix: &CommitmentPrefix, path: impl Into<Path>) -> Vec<u8> {
+	let path: Path = path.into();
+	let prefix = String::from_utf8_lossy(prefix.as_bytes());
+	let key = path.to_string().replace('/', "%2F");
+	alloc::format!("/{prefix}/{key}").into_bytes()
+}
+
+impl<H> ibc::core::ics02_client::client_state::ClientState for ClientState<H>
+where
+	H: HostFunctions,
+{
+	type UpgradeOptions = UpgradeOptions;
+	type ClientDef = SoloMachineClient<H>;
+
+	fn chain_id(&self) -> ChainId {
+		self.chain_id()
+	}
+
+	fn client_def(&self) -> Self::ClientDef {
+		SoloMachineClient::default()
+	}
+
+	fn client_type(&self) -> ClientType {
+		Self::client_type()
+	}
+
+	fn latest_height(&self) -> Height {
+		self.latest_height()
+	}
+
+	fn frozen_height(&self) -> Option<Height> {
+		self.frozen_height()
+	}
+
+	fn upgrade(
+		self,
+		_upgrade_height: Height,
+		_upgrade_options: UpgradeOptions,
+		_chain_id: ChainId,
+	) -> Self {
+		self
+	}
+
+	fn expired(&self, _elapsed: Duration) -> bool {
+		// solo machines have no trusting period
+		false
+	}
+
+	fn encode_to_vec(&self) -> Result<Vec<u8>, tendermint_proto::Error> {
+		self.encode_vec()
+	}
+}
+
+impl<H: Clone> Protobuf<RawClientState> for ClientState<H> {}
+
+impl<H> TryFrom<RawClientState> for ClientState<H> {
+	type Error = Error;
+
+	fn try_from(raw: RawClientState) -> Result<Self, Self::Error> {
+		if raw.sequence == 0 {
+			return Err(Error::invalid_sequence(raw.sequence))
+		}
+		let consensus_state = raw
+			.consensus_state
+			.ok_or_else(|| Error::missing_field("consensus_state".to_string()))?
+			.try_into()?;
+
+		Ok(Self {
+			sequence: raw.sequence,
+			is_frozen: raw.is_frozen,
+			consensus_state,
+			_phantom: PhantomData,
+		})
+	}
+}
+
+impl<H> From<ClientState<H>> for RawClientState {
+	fn from(client_state: ClientState<H>) -> Self {
+		RawClientState {
+			sequence: client_state.sequence,
+			is_frozen: client_state.is_frozen,
+			consensus_state: Some(client_state.consensus_state.into()),
+		}
+	}
+}
@@ -0,0 +1,176 @@
+// Copyright (C) 2022 ComposableFi.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::{
+	error::Error,
+	proto::{
+		ConsensusState as RawConsensusState, PubKey as RawPubKey, RawSignatureData, RawSignatureSum,
+	},
+	HostFunctions,
+};
+use alloc::{
+	string::{String, ToString},
+	vec::Vec,
+};
+use core::convert::Infallible;
+use ibc::{core::ics23_commitment::commitment::CommitmentRoot, timestamp::Timestamp};
+use ibc_proto::google::protobuf::Any;
+use prost::Message;
+use tendermint_proto::Protobuf;
+
+/// Protobuf type url for the solo machine Consensus State
+pub const SOLOMACHINE_CONSENSUS_STATE_TYPE_URL: &str =
+	"/ibc.lightclients.solomachine.v3.ConsensusState";
+/// Protobuf type url of ed25519 public keys
+pub const ED25519_PUB_KEY_TYPE_URL: &str = "/cosmos.crypto.ed25519.PubKey";
+/// Protobuf type url of secp256k1 public keys
+pub const SECP256K1_PUB_KEY_TYPE_URL: &str = "/cosmos.crypto.secp256k1.PubKey";
+
+/// Public key the solo machine signs with
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum PublicKey {
+	Ed25519([u8; 32]),
+	Secp256k1([u8; 33]),
+}
+
+impl PublicKey {
+	pub fn as_bytes(&self) -> &[u8] {
+		match self {
+			PublicKey::Ed25519(key) => key,
+			PublicKey::Secp256k1(key) => key,
+		}
+	}
+
+	/// Verify the signature of `msg`. The signature is a protobuf encoded single
+	/// `cosmos.tx.signing.v1beta1.SignatureDescriptor.Data`, as produced by the cosmos-sdk
+	/// keyring.
+	pub fn verify<H: HostFunctions>(&self, signature_data: &[u8], msg: &[u8]) -> Result<(), Error> {
+		let data = RawSignatureData::decode(signature_data).map_err(Error::decode)?;
+		let signature = match data.sum {
+			Some(RawSignatureSum::Single(single)) => single.signature,
+			_ => return Err(Error::unsupported_signature()),
+		};
+		let valid = match self {
+			PublicKey::Ed25519(key) => H::ed25519_verify(&signature, msg, key),
+			PublicKey::Secp256k1(key) => H::secp256k1_verify(&signature, msg, key),
+		};
+		if !valid {
+			return Err(Error::invalid_signature())
+		}
+		Ok(())
+	}
+}
+
+impl TryFrom<Any> for PublicKey {
+	type Error = Error;
+
+	fn try_from(any: Any) -> Result<Self, Self::Error> {
+		let key = RawPubKey::decode(&*any.value).map_err(Error::decode)?.key;
+		match any.type_url.as_str() {
+			ED25519_PUB_KEY_TYPE_URL =>
+				Ok(PublicKey::Ed25519(key.try_into().map_err(|_| Error::invalid_public_key())?)),
+			SECP256K1_PUB_KEY_TYPE_URL =>
+				Ok(PublicKey::Secp256k1(key.try_into().map_err(|_| Error::invalid_public_key())?)),
+			_ => Err(Error::unsupported_public_key(any.type_url)),
+		}
+	}
+}
+
+impl From<PublicKey> for Any {
+	fn from(public_key: PublicKey) -> Self {
+		let type_url = match public_key {
+			PublicKey::Ed25519(_) => ED25519_PUB_KEY_TYPE_URL,
+			PublicKey::Secp256k1(_) => SECP256K1_PUB_KEY_TYPE_URL,
+		};
+		Any {
+			type_url: type_url.to_string(),
+			value: RawPubKey { key: public_key.as_bytes().to_vec() }.encode_to_vec(),
+		}
+	}
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ConsensusState {
+	/// Public key of the solo machine
+	pub public_key: PublicKey,
+	/// Allows the same public key to be re-used across different solo machine clients
+	/// without being considered misbehaviour.
+	pub diversifier: String,
+	/// Timestamp in nanoseconds
+	pub timestamp: u64,
+	/// Solo machines have no commitment root, the public key is used in its place.
+	root: CommitmentRoot,
+}
+
+impl ConsensusState {
+	pub fn new(public_key: PublicKey, diversifier: String, timestamp: u64) -> Self {
+		let root = CommitmentRoot::from_bytes(public_key.as_bytes());
+		Self { public_key, diversifier, timestamp, root }
+	}
+
+	pub fn to_any(&self) -> Any {
+		Any {
+			type_url: SOLOMACHINE_CONSENSUS_STATE_TYPE_URL.to_string(),
+			value: self.encode_vec().expect("encode ConsensusState"),
+		}
+	}
+}
+
+impl ibc::core::ics02_client::client_consensus::ConsensusState for ConsensusState {
+	type Error = Infallible;
+
+	fn root(&self) -> &CommitmentRoot {
+		&self.root
+	}
+
+	fn timestamp(&self) -> Timestamp {
+		Timestamp::from_nanoseconds(self.timestamp).unwrap_or_else(|_| Timestamp::none())
+	}
+
+	fn encode_to_vec(&self) -> Result<Vec<u8>, tendermint_proto::Error> {
+		self.encode_vec()
+	}
+}
+
+impl Protobuf<RawConsensusState> for ConsensusState {}
+
+impl TryFrom<RawConsensusState> for ConsensusState {
+	type Error = Error;
+
+	fn try_from(raw: RawConsensusState) -> Result<Self, Self::Error> {
+		let public_key = raw
+			.public_key
+			.ok_or_else(|| Error::missing_field("public_key".to_string()))?
+			.try_into()?;
+		if raw.diversifier.trim().is_empty() {
+			return Err(Error::empty_diversifier())
+		}
+		if raw.timestamp == 0 {
+			return Err(Error::missing_field("timestamp".to_string()))
+		}
+
+		Ok(Self::new(public_key, raw.diversifier, raw.timestamp))
+	}
+}
+
+impl From<ConsensusState> for RawConsensusState {
+	fn from(value: ConsensusState) -> Self {
+		RawConsensusState {
+			public_key: Some(value.public_key.into()),
+			diversifier: value.diversifier,
+			timestamp: value.timestamp,
+		}
+	}
+}
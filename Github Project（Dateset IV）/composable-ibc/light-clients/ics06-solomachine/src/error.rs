@@ -0,0 +1,100 @@
+// Copyright (C) 2022 ComposableFi.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::client_state::ClientState;
+use alloc::{
+	borrow::ToOwned,
+	string::{String, ToString},
+};
+use flex_error::{define_error, TraceError};
+use ibc::core::ics02_client::error::Error as Ics02Error;
+
+define_error! {
+	#[derive(Debug, PartialEq, Eq)]
+	Error {
+		Decode
+			[ TraceError<prost::DecodeError> ]
+			|_| { "failed to decode protobuf message" },
+
+		Protobuf
+			{ reason: String }
+			|e| { format_args!("failed to decode protobuf message: {}", e.reason) },
+
+		UnknownMessageType
+			{ type_url: String }
+			|e| { format_args!("unknown client message type: {}", e.type_url) },
+
+		MissingField
+			{ field: String }
+			|e| { format_args!("missing field: {}", e.field) },
+
+		UnsupportedPublicKey
+			{ type_url: String }
+			|e| { format_args!("unsupported public key type: {}", e.type_url) },
+
+		InvalidPublicKey
+			|_| { "invalid public key length" },
+
+		UnsupportedSignature
+			|_| { "only single signatures are supported" },
+
+		InvalidSignature
+			|_| { "signature verification failed" },
+
+		EmptyDiversifier
+			|_| { "diversifier cannot be empty" },
+
+		InvalidSequence
+			{ sequence: u64 }
+			|e| { format_args!("invalid sequence: {}", e.sequence) },
+
+		SequenceMismatch
+			{ expected: u64, got: u64 }
+			|e| { format_args!("sequence mismatch, expected {} got {}", e.expected, e.got) },
+
+		TimestampTooOld
+			{ timestamp: u64, consensus_timestamp: u64 }
+			|e| {
+				format_args!(
+					"timestamp {} is less than the consensus state timestamp {}",
+					e.timestamp, e.consensus_timestamp
+				)
+			},
+
+		EqualMisbehaviourSignatures
+			|_| { "misbehaviour signatures cannot be equal" },
+
+		EqualMisbehaviourData
+			|_| { "misbehaviour signatures must be over different data" },
+
+		FrozenClient
+			|_| { "client is frozen" },
+
+		SubstituteSamePublicKey
+			|_| { "subject and substitute clients have the same public key" },
+
+		UpgradeNotSupported
+			|_| { "solo machine clients cannot be upgraded" },
+
+		BatchProofNotSupported
+			|_| { "solo machine signatures only cover a single path" },
+	}
+}
+
+impl From<Error> for Ics02Error {
+	fn from(e: Error) -> Self {
+		Ics02Error::client_error(ClientState::<()>::client_type().to_owned(), e.to_string())
+	}
+}
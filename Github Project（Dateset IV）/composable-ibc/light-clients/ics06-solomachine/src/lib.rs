@@ -0,0 +1,50 @@
+// Copyright (C) 2022 ComposableFi.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+#![allow(clippy::all)]
+
+//! ICS-06: Solo machine IBC light client protocol implementation
+//!
+//! A solo machine is a standalone process (e.g. an off-chain service or a custodial bridge)
+//! identified by a public key. Instead of headers and commitment proofs it signs the values it
+//! wants to prove together with its current sequence, timestamp and diversifier.
+//!
+//! The sequence of the client is only advanced by headers (i.e. on every public key or
+//! diversifier rotation), so all proofs are verified against the sequence of the latest header.
+
+extern crate alloc;
+
+use core::fmt::Debug;
+
+pub mod client_def;
+pub mod client_message;
+pub mod client_state;
+pub mod consensus_state;
+pub mod error;
+pub mod proto;
+
+/// Host functions that allow the light client verify signatures in native.
+pub trait HostFunctions: Clone + Send + Sync + Eq + Debug + Default {
+	/// Verify an ed25519 `signature` of `msg` by the 32 byte `pubkey`
+	fn ed25519_verify(signature: &[u8], msg: &[u8], pubkey: &[u8]) -> bool;
+
+	/// Verify a 64 byte secp256k1 `signature` of the sha256 hash of `msg` by the 33 byte
+	/// compressed `pubkey`
+	fn secp256k1_verify(signature: &[u8], msg: &[u8], pubkey: &[u8]) -> bool;
+}
+
+#[cfg(test)]
+mod tests;
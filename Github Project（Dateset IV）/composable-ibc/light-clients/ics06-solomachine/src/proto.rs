@@ -0,0 +1,33 @@
+// Copyright (C) 2022 ComposableFi.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+pub use ibc_proto::{
+	cosmos::tx::signing::v1beta1::signature_descriptor::{
+		data::{Single as RawSingleSignature, Sum as RawSignatureSum},
+		Data as RawSignatureData,
+	},
+	ibc::lightclients::solomachine::v1::{
+		ClientState, ConsensusState, Header, HeaderData, Misbehaviour, SignBytes, SignatureAndData,
+		TimestampedSignatureData,
+	},
+};
+
+/// Public key of the solo machine, `cosmos.crypto.ed25519.PubKey` and
+/// `cosmos.crypto.secp256k1.PubKey` share this layout.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct PubKey {
+	#[prost(bytes = "vec", tag = "1")]
+	pub key: ::prost::alloc::vec::Vec<u8>,
+}
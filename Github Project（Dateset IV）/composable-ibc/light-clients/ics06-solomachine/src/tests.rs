@@ -0,0 +1,201 @@
+// Copyright (C) 2022 ComposableFi.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::{
+	client_message::{sign_bytes, Header, Misbehaviour, SignatureAndData},
+	client_state::{signature_path, ClientState},
+	consensus_state::{ConsensusState, PublicKey},
+	proto::{RawSignatureData, RawSignatureSum, RawSingleSignature, TimestampedSignatureData},
+	HostFunctions,
+};
+use ibc::core::{
+	ics23_commitment::commitment::{CommitmentPrefix, CommitmentProofBytes},
+	ics24_host::{identifier::ConnectionId, path::ConnectionsPath},
+};
+use prost::Message;
+use sp_core::{ed25519, Pair};
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+struct HostFunctionsManager;
+
+impl HostFunctions for HostFunctionsManager {
+	fn ed25519_verify(signature: &[u8], msg: &[u8], pubkey: &[u8]) -> bool {
+		match (ed25519::Signature::try_from(signature), ed25519::Public::try_from(pubkey)) {
+			(Ok(signature), Ok(pubkey)) => ed25519::Pair::verify(&signature, msg, &pubkey),
+			_ => false,
+		}
+	}
+
+	fn secp256k1_verify(_signature: &[u8], _msg: &[u8], _pubkey: &[u8]) -> bool {
+		unimplemented!()
+	}
+}
+
+fn sign(pair: &ed25519::Pair, msg: &[u8]) -> Vec<u8> {
+	let signature = pair.sign(msg).0.to_vec();
+	RawSignatureData {
+		sum: Some(RawSignatureSum::Single(RawSingleSignature { mode: 1, signature })),
+	}
+	.encode_to_vec()
+}
+
+fn public_key(pair: &ed25519::Pair) -> PublicKey {
+	PublicKey::Ed25519(pair.public().0)
+}
+
+fn client_state(pair: &ed25519::Pair) -> ClientState<HostFunctionsManager> {
+	ClientState::new(1, ConsensusState::new(public_key(pair), "solo".to_string(), 10))
+}
+
+#[test]
+fn header_must_be_signed_by_current_public_key() {
+	let pair = ed25519::Pair::from_seed(&[1; 32]);
+	let new_pair = ed25519::Pair::from_seed(&[2; 32]);
+	let client_state = client_state(&pair);
+
+	let mut header = Header {
+		timestamp: 20,
+		signature: vec![],
+		new_public_key: public_key(&new_pair),
+		new_diversifier: "solo-2".to_string(),
+	};
+	let sign_bytes = header.sign_bytes(1, "solo".to_string());
+	header.signature = sign(&pair, &sign_bytes);
+	client_state.verify_header(&header).unwrap();
+
+	// signed by the new key instead of the current one
+	header.signature = sign(&new_pair, &sign_bytes);
+	assert!(client_state.verify_header(&header).is_err());
+
+	// signed over a stale sequence
+	header.signature = sign(&pair, &header.sign_bytes(0, "solo".to_string()));
+	assert!(client_state.verify_header(&header).is_err());
+
+	// older than the current consensus state
+	header.timestamp = 5;
+	header.signature = sign(&pair, &header.sign_bytes(1, "solo".to_string()));
+	assert!(client_state.verify_header(&header).is_err());
+}
+
+#[test]
+fn misbehaviour_requires_two_valid_signatures_over_different_data() {
+	let pair = ed25519::Pair::from_seed(&[1; 32]);
+	let client_state = client_state(&pair);
+
+	let signature_and_data = |sequence, data: &[u8]| {
+		let mut signature_and_data = SignatureAndData {
+			signature: vec![],
+			path: b"/ibc/connections%2Fconnection-0".to_vec(),
+			data: data.to_vec(),
+			timestamp: 20,
+		};
+		signature_and_data.signature =
+			sign(&pair, &signature_and_data.sign_bytes(sequence, "solo".to_string()));
+		signature_and_data
+	};
+
+	let misbehaviour = Misbehaviour {
+		sequence: 1,
+		signature_one: signature_and_data(1, b"one"),
+		signature_two: signature_and_data(1, b"two"),
+	};
+	client_state.verify_misbehaviour(&misbehaviour).unwrap();
+
+	let same_data =
+		Misbehaviour { signature_two: signature_and_data(1, b"one"), ..misbehaviour.clone() };
+	assert!(client_state.verify_misbehaviour(&same_data).is_err());
+
+	// validly signed, but at a sequence the client is not at
+	let other_sequence = Misbehaviour {
+		sequence: 2,
+		signature_one: signature_and_data(2, b"one"),
+		signature_two: signature_and_data(2, b"two"),
+	};
+	assert!(client_state.verify_misbehaviour(&other_sequence).is_err());
+}
+
+#[test]
+fn membership_proofs_are_signatures_over_the_prefixed_path() {
+	let pair = ed25519::Pair::from_seed(&[1; 32]);
+	let client_state = client_state(&pair);
+	let prefix = CommitmentPrefix::try_from(b"ibc".to_vec()).unwrap();
+	let path = ConnectionsPath(ConnectionId::new(0));
+	assert_eq!(signature_path(&prefix, path.clone()), b"/ibc/connections%2Fconnection-0".to_vec());
+
+	let proof = |value: &[u8], timestamp| {
+		let sign_bytes = sign_bytes(
+			1,
+			timestamp,
+			"solo".to_string(),
+			signature_path(&prefix, path.clone()),
+			value.to_vec(),
+		);
+		let proof =
+			TimestampedSignatureData { signature_data: sign(&pair, &sign_bytes), timestamp };
+		CommitmentProofBytes::try_from(proof.encode_to_vec()).unwrap()
+	};
+
+	client_state
+		.verify_signature(&prefix, &proof(b"value", 20), path.clone(), b"value".to_vec())
+		.unwrap();
+	assert!(client_state
+		.verify_signature(&prefix, &proof(b"value", 20), path.clone(), b"other".to_vec())
+		.is_err());
+	assert!(client_state
+		.verify_signature(&prefix, &proof(b"value", 5), path.clone(), b"value".to_vec())
+		.is_err());
+
+	let frozen = ClientState { is_frozen: true, ..client_state };
+	assert!(frozen
+		.verify_signature(&prefix, &proof(b"value", 20), path, b"value".to_vec())
+		.is_err());
+}
+
+#[test]
+fn verified_proofs_advance_the_sequence() {
+	let pair = ed25519::Pair::from_seed(&[1; 32]);
+	let client_state = client_state(&pair);
+	let prefix = CommitmentPrefix::try_from(b"ibc".to_vec()).unwrap();
+	let path = ConnectionsPath(ConnectionId::new(0));
+
+	let proof = |sequence, timestamp| {
+		let sign_bytes = sign_bytes(
+			sequence,
+			timestamp,
+			"solo".to_string(),
+			signature_path(&prefix, path.clone()),
+			b"value".to_vec(),
+		);
+		let proof =
+			TimestampedSignatureData { signature_data: sign(&pair, &sign_bytes), timestamp };
+		CommitmentProofBytes::try_from(proof.encode_to_vec()).unwrap()
+	};
+
+	let first = proof(1, 20);
+	client_state
+		.verify_signature(&prefix, &first, path.clone(), b"value".to_vec())
+		.unwrap();
+	let client_state = client_state.on_proof_verified(&first).unwrap();
+	assert_eq!(client_state.sequence, 2);
+	assert_eq!(client_state.consensus_state.timestamp, 20);
+
+	// the same proof cannot be replayed
+	assert!(client_state
+		.verify_signature(&prefix, &first, path.clone(), b"value".to_vec())
+		.is_err());
+	client_state
+		.verify_signature(&prefix, &proof(2, 20), path, b"value".to_vec())
+		.unwrap();
+}
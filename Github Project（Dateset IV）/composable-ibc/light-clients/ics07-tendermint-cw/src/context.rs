@@ -15,8 +15,8 @@
 
 use crate::{
 	ics23::{
-		ClientStates, ConsensusStates, FakeInner, ReadonlyClientStates, ReadonlyConsensusStates,
-		ReadonlyProcessedStates,
+		ClientStates, ConsensusStates, FakeInner, ProcessedStates, ReadonlyClientStates,
+		ReadonlyConsensusStates, ReadonlyProcessedStates,
 	},
 	ContractError,
 };
@@ -33,6 +33,7 @@ use ibc_proto::google::protobuf::Any;
 use ics07_tendermint::{
 	client_state::ClientState, consensus_state::ConsensusState, HostFunctionsProvider,
 };
+use light_client_common::ProcessedConsensusStates;
 use std::{fmt, fmt::Debug, marker::PhantomData};
 
 pub struct Context<'a, H> {
@@ -203,4 +204,23 @@ where
 	}
 }
 
+impl<'a, H> ProcessedConsensusStates for Context<'a, H> {
+	fn processed_times_below(
+		&self,
+		height: Height,
+	) -> Box<dyn Iterator<Item = (Height, Option<u64>)> + '_> {
+		let processed_states = ReadonlyProcessedStates::new(self.storage());
+		Box::new(
+			processed_states
+				.get_heights_below(height)
+				.map(move |height| (height, processed_states.get_processed_time(height, ""))),
+		)
+	}
+
+	fn remove_consensus_state(&mut self, height: Height) {
+		ProcessedStates::new(self.storage_mut()).remove_states_at_height(height);
+		ConsensusStates::new(self.storage_mut()).remove(height);
+	}
+}
+
 impl<'a, H: HostFunctionsProvider + 'static> ReaderContext for Context<'a, H> {}
@@ -17,8 +17,7 @@ use crate::{
 	context::Context,
 	error::ContractError,
 	helpers::{
-		check_substitute_and_update_state, prune_oldest_consensus_state, verify_delay_passed,
-		verify_upgrade_and_update_state,
+		check_substitute_and_update_state, verify_delay_passed, verify_upgrade_and_update_state,
 	},
 	ics23::ReadonlyProcessedStates,
 	msg::{
@@ -50,8 +49,11 @@ use ics07_tendermint::{
 	HostFunctionsProvider,
 };
 use ics08_wasm::{instantiate::InstantiateMessage, SUBJECT_PREFIX};
+use light_client_common::prune_expired_consensus_states;
 use prost::Message;
-use sha2::{Digest, Sha256};
+use ripemd::Ripemd160;
+use sha2::{Digest, Sha256, Sha512};
+use sha3::Sha3_512;
 use std::str::FromStr;
 use tendermint::{
 	crypto::{
@@ -71,23 +73,32 @@ impl ics23::HostFunctionsProvider for HostFunctions {
 	fn sha2_256(message: &[u8]) -> [u8; 32] {
 		let mut hasher = Sha256::default();
 		hasher.update(message);
-		hasher.finalize().as_slice().try_into().expect("slice with incorrect length")
+		hasher.finalize().into()
 	}
 
-	fn sha2_512(_message: &[u8]) -> [u8; 64] {
-		unimplemented!()
+	fn sha2_512(message: &[u8]) -> [u8; 64] {
+		let mut hasher = Sha512::default();
+		hasher.update(message);
+		hasher.finalize().into()
 	}
 
-	fn sha2_512_truncated(_message: &[u8]) -> [u8; 32] {
-		unimplemented!()
+	fn sha2_512_truncated(message: &[u8]) -> [u8; 32] {
+		let hash = Self::sha2_512(message);
+		let mut res = [0u8; 32];
+		res.copy_from_slice(&hash[..32]);
+		res
 	}
 
-	fn sha3_512(_message: &[u8]) -> [u8; 64] {
-		unimplemented!()
+	fn sha3_512(message: &[u8]) -> [u8; 64] {
+		let mut hasher = Sha3_512::default();
+		hasher.update(message);
+		hasher.finalize().into()
 	}
 
-	fn ripemd160(_message: &[u8]) -> [u8; 20] {
-		unimplemented!()
+	fn ripemd160(message: &[u8]) -> [u8; 20] {
+		let mut hasher = Ripemd160::default();
+		hasher.update(message);
+		hasher.finalize().into()
 	}
 }
 
@@ -117,7 +128,7 @@ impl Verifier for HostFunctions {
 		msg: &[u8],
 		signature: &Signature,
 	) -> Result<(), TendermintCryptoError> {
-		let vk = pubkey.ed25519().expect("");
+		let vk = pubkey.ed25519().ok_or(TendermintCryptoError::UnsupportedKeyType)?;
 		let pubkey2 = VerificationKey::try_from(vk.as_bytes())
 			.map_err(|_| TendermintCryptoError::MalformedPublicKey)?;
 		let sig = ed25519_consensus::Signature::try_from(signature.as_bytes())
@@ -206,7 +217,12 @@ fn process_message(
 				.map_err(|e| ContractError::Tendermint(e.to_string()))?;
 			let msg = UpdateStateMsg::try_from(msg_raw)?;
 			let latest_revision_height = client_state.latest_height().revision_height;
-			prune_oldest_consensus_state(ctx, &client_state, ctx.host_timestamp().nanoseconds());
+			prune_expired_consensus_states(
+				ctx,
+				client_state.latest_height,
+				ctx.host_timestamp().nanoseconds(),
+				|elapsed| client_state.expired(elapsed),
+			);
 			client
 				.update_state(ctx, client_id.clone(), client_state, msg.client_message)
 				.map_err(|e| ContractError::Tendermint(e.to_string()))
@@ -387,3 +403,176 @@ pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
 		},
 	}
 }
+
+#[cfg(test)]
+mod tests {
+	use super::HostFunctions;
+	use crate::{
+		context::Context,
+		ics23::{
+			ConsensusStates, ProcessedStates, ReadonlyConsensusStates, ReadonlyProcessedStates,
+		},
+	};
+	use core::{marker::PhantomData, time::Duration};
+	use cosmwasm_std::testing::{mock_dependencies, mock_env};
+	use ibc::{
+		core::{
+			ics02_client::trust_threshold::TrustThreshold, ics23_commitment::specs::ProofSpecs,
+			ics24_host::identifier::ChainId,
+		},
+		Height,
+	};
+	use ics07_tendermint::client_state::ClientState;
+	use ics23::{
+		calculate_existence_root, commitment_proof::Proof, verify_membership, CommitmentProof,
+		ExistenceProof, HashOp, HostFunctionsProvider, InnerSpec, LeafOp, LengthOp, ProofSpec,
+	};
+	use light_client_common::{prune_expired_consensus_states, MAX_PRUNED_CONSENSUS_STATES};
+	use pallet_ibc::light_clients::HostFunctionsManager;
+
+	const HASH_OPS: [HashOp; 6] = [
+		HashOp::Sha256,
+		HashOp::Sha512,
+		HashOp::Keccak,
+		HashOp::Ripemd160,
+		HashOp::Bitcoin,
+		HashOp::Sha512256,
+	];
+
+	fn leaf_op(hash: HashOp) -> LeafOp {
+		LeafOp {
+			hash: hash.into(),
+			prehash_key: HashOp::NoHash.into(),
+			prehash_value: hash.into(),
+			length: LengthOp::VarProto.into(),
+			prefix: vec![0],
+		}
+	}
+
+	fn proof_spec(hash: HashOp) -> ProofSpec {
+		ProofSpec {
+			leaf_spec: Some(leaf_op(hash)),
+			inner_spec: Some(InnerSpec {
+				child_order: vec![0, 1],
+				child_size: 33,
+				min_prefix_length: 4,
+				max_prefix_length: 12,
+				empty_child: vec![],
+				hash: hash.into(),
+			}),
+			max_depth: 0,
+			min_depth: 0,
+			prehash_key_before_comparison: false,
+		}
+	}
+
+	#[test]
+	fn hash_functions_match_native() {
+		for message in [&b""[..], b"ics07-tendermint-cw", &[0xffu8; 300]] {
+			assert_eq!(HostFunctions::sha2_256(message), HostFunctionsManager::sha2_256(message));
+			assert_eq!(HostFunctions::sha2_512(message), HostFunctionsManager::sha2_512(message));
+			assert_eq!(
+				HostFunctions::sha2_512_truncated(message),
+				HostFunctionsManager::sha2_512_truncated(message)
+			);
+			assert_eq!(HostFunctions::sha3_512(message), HostFunctionsManager::sha3_512(message));
+			assert_eq!(HostFunctions::ripemd160(message), HostFunctionsManager::ripemd160(message));
+		}
+	}
+
+	#[test]
+	fn existence_proofs_match_native_for_every_hash_op() {
+		let (key, value) = (b"clients/07-tendermint-0/clientState".to_vec(), b"value".to_vec());
+		for hash in HASH_OPS {
+			let proof = ExistenceProof {
+				key: key.clone(),
+				value: value.clone(),
+				leaf: Some(leaf_op(hash)),
+				path: vec![],
+			};
+			let expected = calculate_existence_root::<HostFunctionsManager>(&proof).unwrap();
+			let root = calculate_existence_root::<HostFunctions>(&proof).unwrap();
+			assert_eq!(root, expected, "root mismatch for {hash:?}");
+
+			let proof = CommitmentProof { proof: Some(Proof::Exist(proof)) };
+			assert!(
+				verify_membership::<HostFunctions>(&proof, &proof_spec(hash), &root, &key, &value),
+				"membership verification failed for {hash:?}"
+			);
+			assert!(
+				!verify_membership::<HostFunctions>(&proof, &proof_spec(hash), &root, &key, b"x"),
+				"membership verification succeeded with a wrong value for {hash:?}"
+			);
+		}
+	}
+
+	#[test]
+	fn test_prune_expired_consensus_states() {
+		let mut deps = mock_dependencies();
+		let env = mock_env();
+		let now = env.block.time.nanos();
+		let trusting_period = Duration::from_secs(100);
+		let client_state = ClientState::<HostFunctions> {
+			chain_id: ChainId::default(),
+			trust_level: TrustThreshold::ONE_THIRD,
+			trusting_period,
+			unbonding_period: trusting_period * 2,
+			max_clock_drift: Duration::from_secs(1),
+			latest_height: Height::new(0, 30),
+			proof_specs: ProofSpecs::default(),
+			upgrade_path: vec![],
+			frozen_height: None,
+			_phantom: PhantomData,
+		};
+		let expired_time = now - trusting_period.as_nanos() as u64 - 1;
+
+		// states at heights 1..=25 and the latest one are expired, 26..=29 are still trusted
+		let mut ctx = Context::<HostFunctions>::new(deps.as_mut(), env);
+		for revision_height in 1..=30 {
+			let height = Height::new(0, revision_height);
+			let processed_time =
+				if (26..30).contains(&revision_height) { now } else { expired_time };
+			ConsensusStates::new(ctx.storage_mut()).insert(height, vec![1]);
+			let mut processed_states = ProcessedStates::new(ctx.storage_mut());
+			processed_states.set_processed_time(height, processed_time, "");
+			processed_states.set_processed_height(height, revision_height, "");
+			processed_states.set_iteration_key(height, "");
+		}
+		let stored_states = |ctx: &Context<HostFunctions>| {
+			let consensus_states = ReadonlyConsensusStates::new(ctx.storage());
+			(1..=30).filter(|h| consensus_states.get(Height::new(0, *h)).is_some()).count()
+		};
+
+		let prune = |ctx: &mut Context<HostFunctions>| {
+			prune_expired_consensus_states(ctx, client_state.latest_height, now, |elapsed| {
+				client_state.expired(elapsed)
+			})
+		};
+
+		let pruned = prune(&mut ctx);
+		assert_eq!(pruned, (1..=10).map(|h| Height::new(0, h)).collect::<Vec<_>>());
+		assert_eq!(stored_states(&ctx), 30 - MAX_PRUNED_CONSENSUS_STATES);
+
+		let pruned = prune(&mut ctx);
+		assert_eq!(pruned.len(), MAX_PRUNED_CONSENSUS_STATES);
+		let pruned = prune(&mut ctx);
+		assert_eq!(pruned, (21..=25).map(|h| Height::new(0, h)).collect::<Vec<_>>());
+
+		// only the trusted states and the latest one are left
+		assert_eq!(stored_states(&ctx), 5);
+		assert!(prune(&mut ctx).is_empty());
+
+		// pruning stops at the first trusted state, even if a later one has expired
+		ProcessedStates::new(ctx.storage_mut()).set_processed_time(
+			Height::new(0, 28),
+			expired_time,
+			"",
+		);
+		assert!(prune(&mut ctx).is_empty());
+		assert_eq!(stored_states(&ctx), 5);
+
+		let processed_states = ReadonlyProcessedStates::new(ctx.storage());
+		assert!(processed_states.get_processed_time(Height::new(0, 1), "").is_none());
+		assert!(processed_states.get_processed_time(Height::new(0, 30), "").is_some());
+	}
+}
@@ -13,16 +13,10 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use std::time::Duration;
-
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
-use crate::{
-	context::Context,
-	ics23::{ConsensusStates, ProcessedStates},
-	msg::SudoMsg,
-};
+use crate::{context::Context, ics23::ProcessedStates, msg::SudoMsg};
 use cosmwasm_std::{to_binary, Addr, CosmosMsg, StdResult, WasmMsg};
 use ibc::core::{
 	ics02_client::{
@@ -214,21 +208,3 @@ pub fn check_substitute_and_update_state<H: HostFunctionsProvider + 'static>(
 
 	Ok((subject_client_state, substitute_consensus_state))
 }
-
-pub fn prune_oldest_consensus_state<H: HostFunctionsProvider + 'static>(
-	ctx: &mut Context<H>,
-	client_state: &ClientState<H>,
-	current_time: u64,
-) {
-	let mut processed_states = ProcessedStates::new(ctx.storage_mut());
-	if let Some(earliest_height) = processed_states.get_earliest_height(client_state.latest_height)
-	{
-		let processed_time = processed_states.get_processed_time(earliest_height, "").unwrap();
-		let elapsed = Duration::from_nanos(current_time - processed_time);
-		if client_state.expired(elapsed) {
-			processed_states.remove_states_at_height(earliest_height);
-			let mut consensus_states = ConsensusStates::new(ctx.storage_mut());
-			consensus_states.remove(earliest_height);
-		}
-	}
-}
@@ -28,10 +28,14 @@ use ibc::{
 		ics04_channel::{
 			channel::ChannelEnd,
 			commitment::{AcknowledgementCommitment, PacketCommitment},
-			packet::Sequence,
+			packet::{Receipt, Sequence},
+			upgrade::{ErrorReceipt, Upgrade},
 		},
 		ics23_commitment::commitment::{CommitmentPrefix, CommitmentProofBytes, CommitmentRoot},
-		ics24_host::identifier::{ChainId, ChannelId, ClientId, ConnectionId, PortId},
+		ics24_host::{
+			identifier::{ChainId, ChannelId, ClientId, ConnectionId, PortId},
+			Path,
+		},
 		ics26_routing::context::ReaderContext,
 	},
 	timestamp::Timestamp,
@@ -310,6 +314,38 @@ impl ClientDef for FakeInner {
 		unimplemented!()
 	}
 
+	fn verify_channel_upgrade<Ctx: ReaderContext>(
+		&self,
+		_ctx: &Ctx,
+		_client_id: &ClientId,
+		_client_state: &Self::ClientState,
+		_height: Height,
+		_prefix: &CommitmentPrefix,
+		_proof: &CommitmentProofBytes,
+		_root: &CommitmentRoot,
+		_port_id: &PortId,
+		_channel_id: &ChannelId,
+		_expected_upgrade: &Upgrade,
+	) -> Result<(), Error> {
+		unimplemented!()
+	}
+
+	fn verify_channel_upgrade_error<Ctx: ReaderContext>(
+		&self,
+		_ctx: &Ctx,
+		_client_id: &ClientId,
+		_client_state: &Self::ClientState,
+		_height: Height,
+		_prefix: &CommitmentPrefix,
+		_proof: &CommitmentProofBytes,
+		_root: &CommitmentRoot,
+		_port_id: &PortId,
+		_channel_id: &ChannelId,
+		_expected_error_receipt: &ErrorReceipt,
+	) -> Result<(), Error> {
+		unimplemented!()
+	}
+
 	fn verify_client_full_state<Ctx: ReaderContext>(
 		&self,
 		_ctx: &Ctx,
@@ -374,6 +410,37 @@ impl ClientDef for FakeInner {
 		unimplemented!()
 	}
 
+	fn verify_packet_receipt<Ctx: ReaderContext>(
+		&self,
+		_ctx: &Ctx,
+		_client_id: &ClientId,
+		_client_state: &Self::ClientState,
+		_height: Height,
+		_connection_end: &ConnectionEnd,
+		_proof: &CommitmentProofBytes,
+		_root: &CommitmentRoot,
+		_port_id: &PortId,
+		_channel_id: &ChannelId,
+		_sequence: Sequence,
+		_receipt: Receipt,
+	) -> Result<(), Error> {
+		unimplemented!()
+	}
+
+	fn verify_batch_membership<Ctx: ReaderContext>(
+		&self,
+		_ctx: &Ctx,
+		_client_id: &ClientId,
+		_client_state: &Self::ClientState,
+		_height: Height,
+		_connection_end: &ConnectionEnd,
+		_proof: &CommitmentProofBytes,
+		_root: &CommitmentRoot,
+		_items: Vec<(Path, Vec<u8>)>,
+	) -> Result<(), Error> {
+		unimplemented!()
+	}
+
 	fn verify_packet_receipt_absence<Ctx: ReaderContext>(
 		&self,
 		_ctx: &Ctx,
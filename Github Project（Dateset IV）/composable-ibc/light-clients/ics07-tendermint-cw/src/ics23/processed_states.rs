@@ -166,6 +166,20 @@ impl<'a> ReadonlyProcessedStates<'a> {
 		}
 	}
 
+	/// Iterates over the heights of the stored consensus states that are lower than
+	/// `current_height`, in ascending order.
+	pub fn get_heights_below(&self, current_height: Height) -> impl Iterator<Item = Height> + 'a {
+		let full_key = ProcessedStates::iteration_key(current_height, "");
+		let start_key = &b"iterateConsensusStates"[..];
+		self.0
+			.range(Some(start_key), Some(&full_key), Order::Ascending)
+			.map_while(|(_, height)| {
+				std::str::from_utf8(height.as_slice())
+					.ok()
+					.and_then(|height_str| Height::try_from(height_str).ok())
+			})
+	}
+
 	pub fn get_metadata(&self) -> Option<Vec<GenesisMetadata>> {
 		let mut gm: Vec<GenesisMetadata> = Vec::<GenesisMetadata>::new();
 
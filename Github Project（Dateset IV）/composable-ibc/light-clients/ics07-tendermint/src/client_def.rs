@@ -25,7 +25,8 @@ use ibc::core::{
 	ics04_channel::{
 		channel::ChannelEnd,
 		commitment::{AcknowledgementCommitment, PacketCommitment},
-		packet::Sequence,
+		packet::{Receipt, Sequence},
+		upgrade::{ErrorReceipt, Upgrade},
 	},
 	ics23_commitment::{
 		commitment::{CommitmentPrefix, CommitmentProofBytes, CommitmentRoot},
@@ -34,8 +35,9 @@ use ibc::core::{
 	ics24_host::{
 		identifier::{ChainId, ChannelId, ClientId, ConnectionId, PortId},
 		path::{
-			AcksPath, ChannelEndsPath, ClientConsensusStatePath, ClientStatePath, CommitmentsPath,
-			ConnectionsPath, ReceiptsPath, SeqRecvsPath,
+			AcksPath, ChannelEndsPath, ChannelUpgradeErrorPath, ChannelUpgradePath,
+			ClientConsensusStatePath, ClientStatePath, CommitmentsPath, ConnectionsPath,
+			ReceiptsPath, SeqRecvsPath,
 		},
 		Path,
 	},
@@ -409,6 +411,46 @@ where
 		verify_membership::<H, _>(client_state, prefix, proof, root, path, value)
 	}
 
+	fn verify_channel_upgrade<Ctx: ReaderContext>(
+		&self,
+		_ctx: &Ctx,
+		_client_id: &ClientId,
+		client_state: &Self::ClientState,
+		height: Height,
+		prefix: &CommitmentPrefix,
+		proof: &CommitmentProofBytes,
+		root: &CommitmentRoot,
+		port_id: &PortId,
+		channel_id: &ChannelId,
+		expected_upgrade: &Upgrade,
+	) -> Result<(), Ics02Error> {
+		client_state.verify_height(height)?;
+
+		let path = ChannelUpgradePath(port_id.clone(), *channel_id);
+		let value = expected_upgrade.encode_vec().map_err(Ics02Error::encode)?;
+		verify_membership::<H, _>(client_state, prefix, proof, root, path, value)
+	}
+
+	fn verify_channel_upgrade_error<Ctx: ReaderContext>(
+		&self,
+		_ctx: &Ctx,
+		_client_id: &ClientId,
+		client_state: &Self::ClientState,
+		height: Height,
+		prefix: &CommitmentPrefix,
+		proof: &CommitmentProofBytes,
+		root: &CommitmentRoot,
+		port_id: &PortId,
+		channel_id: &ChannelId,
+		expected_error_receipt: &ErrorReceipt,
+	) -> Result<(), Ics02Error> {
+		client_state.verify_height(height)?;
+
+		let path = ChannelUpgradeErrorPath(port_id.clone(), *channel_id);
+		let value = expected_error_receipt.encode_vec().map_err(Ics02Error::encode)?;
+		verify_membership::<H, _>(client_state, prefix, proof, root, path, value)
+	}
+
 	fn verify_client_full_state<Ctx: ReaderContext>(
 		&self,
 		_ctx: &Ctx,
@@ -516,6 +558,58 @@ where
 		)
 	}
 
+	fn verify_packet_receipt<Ctx: ReaderContext>(
+		&self,
+		ctx: &Ctx,
+		_client_id: &ClientId,
+		client_state: &Self::ClientState,
+		height: Height,
+		connection_end: &ConnectionEnd,
+		proof: &CommitmentProofBytes,
+		root: &CommitmentRoot,
+		port_id: &PortId,
+		channel_id: &ChannelId,
+		sequence: Sequence,
+		receipt: Receipt,
+	) -> Result<(), Ics02Error> {
+		client_state.verify_height(height)?;
+		verify_delay_passed(ctx, height, connection_end)?;
+
+		let receipt_path =
+			ReceiptsPath { port_id: port_id.clone(), channel_id: *channel_id, sequence };
+		verify_membership::<H, _>(
+			client_state,
+			connection_end.counterparty().prefix(),
+			proof,
+			root,
+			receipt_path,
+			receipt.as_bytes().to_vec(),
+		)
+	}
+
+	fn verify_batch_membership<Ctx: ReaderContext>(
+		&self,
+		ctx: &Ctx,
+		_client_id: &ClientId,
+		client_state: &Self::ClientState,
+		height: Height,
+		connection_end: &ConnectionEnd,
+		proof: &CommitmentProofBytes,
+		root: &CommitmentRoot,
+		items: Vec<(Path, Vec<u8>)>,
+	) -> Result<(), Ics02Error> {
+		client_state.verify_height(height)?;
+		verify_delay_passed(ctx, height, connection_end)?;
+
+		verify_batch_membership::<H>(
+			client_state,
+			connection_end.counterparty().prefix(),
+			proof,
+			root,
+			items,
+		)
+	}
+
 	fn verify_packet_receipt_absence<Ctx: ReaderContext>(
 		&self,
 		ctx: &Ctx,
@@ -566,6 +660,26 @@ where
 		.map_err(|e| Error::ics23_error(e).into())
 }
 
+pub fn verify_batch_membership<H>(
+	client_state: &ClientState<H>,
+	prefix: &CommitmentPrefix,
+	proof: &CommitmentProofBytes,
+	root: &CommitmentRoot,
+	items: Vec<(Path, Vec<u8>)>,
+) -> Result<(), Ics02Error>
+where
+	H: ics23::HostFunctionsProvider,
+{
+	let items = items.into_iter().map(|(path, value)| (path.to_string(), value)).collect();
+	let merkle_proof: MerkleProof<H> = RawMerkleProof::try_from(proof.clone())
+		.map_err(Ics02Error::invalid_commitment_proof)?
+		.into();
+
+	merkle_proof
+		.verify_batch_membership(&client_state.proof_specs, root.clone().into(), prefix, items)
+		.map_err(|e| Error::ics23_error(e).into())
+}
+
 pub fn verify_non_membership<H, P>(
 	client_state: &ClientState<H>,
 	prefix: &CommitmentPrefix,
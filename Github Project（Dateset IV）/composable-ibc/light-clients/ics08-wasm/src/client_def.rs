@@ -14,12 +14,24 @@
 // limitations under the License.
 
 use crate::{
-	client_message::ClientMessage, client_state::ClientState, consensus_state::ConsensusState,
+	client_message::ClientMessage,
+	client_state::ClientState,
+	consensus_state::ConsensusState,
+	executor::{
+		self, CheckForMisbehaviourMsg, MerklePath, QueryMsg, SudoMsg, UpdateStateMsg,
+		VerifyClientMessageMsg, VerifyMembershipMsg, VerifyNonMembershipMsg,
+		VerifyUpgradeAndUpdateStateMsg,
+	},
+};
+use alloc::{
+	boxed::Box,
+	string::{String, ToString},
+	vec::Vec,
 };
-use alloc::{boxed::Box, vec::Vec};
 use core::{
 	fmt::{Debug, Display},
 	marker::PhantomData,
+	time::Duration,
 };
 use ibc::{
 	core::{
@@ -33,16 +45,29 @@ use ibc::{
 		ics04_channel::{
 			channel::ChannelEnd,
 			commitment::{AcknowledgementCommitment, PacketCommitment},
-			packet::Sequence,
+			context::ChannelReader,
+			packet::{Receipt, Sequence},
+			upgrade::{ErrorReceipt, Upgrade},
 		},
 		ics23_commitment::commitment::{CommitmentPrefix, CommitmentProofBytes, CommitmentRoot},
-		ics24_host::identifier::{ChannelId, ClientId, ConnectionId, PortId},
+		ics24_host::{
+			identifier::{ChannelId, ClientId, ConnectionId, PortId},
+			path::{
+				AcksPath, ChannelEndsPath, ChannelUpgradeErrorPath, ChannelUpgradePath,
+				CommitmentsPath, ConnectionsPath, ReceiptsPath, SeqRecvsPath,
+			},
+			Path,
+		},
 		ics26_routing::context::ReaderContext,
 	},
 	Height,
 };
 use ibc_proto::google::protobuf::Any;
 
+/// Wasm light client. If the host stores the code of the client, see [`executor`], the contract
+/// verifies the client messages, upgrades and proofs of the counterparty's ibc store. Freezing,
+/// client substitution and proofs of the counterparty's clients, whose verification isn't given
+/// the id of this client, are always handled by the inner client.
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct WasmClient<AnyClient, AnyClientState, AnyConsensusState> {
 	pub inner: Box<AnyClient>,
@@ -65,6 +90,9 @@ where
 	<AnyConsensusState as TryFrom<Any>>::Error: Display,
 	AnyClient::ClientMessage: TryFrom<Any>,
 	<AnyClient::ClientMessage as TryFrom<Any>>::Error: Display,
+	AnyClient: 'static,
+	AnyClientState: 'static,
+	AnyConsensusState: 'static,
 {
 	type ClientMessage = ClientMessage<AnyClient::ClientMessage>;
 	type ClientState = ClientState<AnyClient, AnyClientState, AnyConsensusState>;
@@ -77,6 +105,18 @@ where
 		client_state: Self::ClientState,
 		client_msg: Self::ClientMessage,
 	) -> Result<(), Error> {
+		if executor::has_code(&client_state.checksum) {
+			let msg = QueryMsg::VerifyClientMessage(VerifyClientMessageMsg {
+				client_message: client_msg.data,
+			});
+			let response = executor::query(&client_state.checksum, &client_id, msg)?;
+			if !response.is_valid {
+				return Err(Error::header_verification_failure(
+					"client message rejected by the wasm client".into(),
+				))
+			}
+			return Ok(())
+		}
 		self.inner
 			.verify_client_message(ctx, client_id, *client_state.inner, *client_msg.inner)
 	}
@@ -88,6 +128,11 @@ where
 		client_state: Self::ClientState,
 		client_msg: Self::ClientMessage,
 	) -> Result<(Self::ClientState, ConsensusUpdateResult<Ctx>), Error> {
+		if executor::has_code(&client_state.checksum) {
+			let msg = SudoMsg::UpdateState(UpdateStateMsg { client_message: client_msg.data });
+			let result = executor::sudo(&client_state.checksum, &client_id, msg)?;
+			return stored_states(ctx, &client_id, result.heights)
+		}
 		let (inner_client_state, inner_consensus_update_result) =
 			self.inner
 				.update_state(ctx, client_id, *client_state.inner, *client_msg.inner)?;
@@ -125,6 +170,13 @@ where
 		client_state: Self::ClientState,
 		client_msg: Self::ClientMessage,
 	) -> Result<bool, Error> {
+		if executor::has_code(&client_state.checksum) {
+			let msg = QueryMsg::CheckForMisbehaviour(CheckForMisbehaviourMsg {
+				client_message: client_msg.data,
+			});
+			let response = executor::query(&client_state.checksum, &client_id, msg)?;
+			return Ok(response.found_misbehaviour.unwrap_or(false))
+		}
 		self.inner
 			.check_for_misbehaviour(ctx, client_id, *client_state.inner, *client_msg.inner)
 	}
@@ -139,6 +191,16 @@ where
 		proof_upgrade_client: Vec<u8>,
 		proof_upgrade_consensus_state: Vec<u8>,
 	) -> Result<(Self::ClientState, ConsensusUpdateResult<Ctx>), Error> {
+		if executor::has_code(&old_client_state.checksum) {
+			let msg = SudoMsg::VerifyUpgradeAndUpdateState(VerifyUpgradeAndUpdateStateMsg {
+				upgrade_client_state: upgrade_client_state.data.clone(),
+				upgrade_consensus_state: upgrade_consensus_state.data.clone(),
+				proof_upgrade_client,
+				proof_upgrade_consensus_state,
+			});
+			let result = executor::sudo(&old_client_state.checksum, &client_id, msg)?;
+			return stored_states(ctx, &client_id, result.heights)
+		}
 		self.inner
 			.verify_upgrade_and_update_state(
 				ctx,
@@ -226,6 +288,19 @@ where
 		connection_id: &ConnectionId,
 		expected_connection_end: &ConnectionEnd,
 	) -> Result<(), Error> {
+		if executor::has_code(&client_state.checksum) {
+			return verify_membership(
+				ctx,
+				client_id,
+				client_state,
+				height,
+				prefix,
+				proof,
+				ConnectionsPath(connection_id.clone()),
+				Some(expected_connection_end.encode_vec().map_err(Error::encode)?),
+				Duration::ZERO,
+			)
+		}
 		self.inner.verify_connection_state(
 			ctx,
 			client_id,
@@ -252,6 +327,19 @@ where
 		channel_id: &ChannelId,
 		expected_channel_end: &ChannelEnd,
 	) -> Result<(), Error> {
+		if executor::has_code(&client_state.checksum) {
+			return verify_membership(
+				ctx,
+				client_id,
+				client_state,
+				height,
+				prefix,
+				proof,
+				ChannelEndsPath(port_id.clone(), *channel_id),
+				Some(expected_channel_end.encode_vec().map_err(Error::encode)?),
+				Duration::ZERO,
+			)
+		}
 		self.inner.verify_channel_state(
 			ctx,
 			client_id,
@@ -266,6 +354,86 @@ where
 		)
 	}
 
+	fn verify_channel_upgrade<Ctx: ReaderContext>(
+		&self,
+		ctx: &Ctx,
+		client_id: &ClientId,
+		client_state: &Self::ClientState,
+		height: Height,
+		prefix: &CommitmentPrefix,
+		proof: &CommitmentProofBytes,
+		root: &CommitmentRoot,
+		port_id: &PortId,
+		channel_id: &ChannelId,
+		expected_upgrade: &Upgrade,
+	) -> Result<(), Error> {
+		if executor::has_code(&client_state.checksum) {
+			return verify_membership(
+				ctx,
+				client_id,
+				client_state,
+				height,
+				prefix,
+				proof,
+				ChannelUpgradePath(port_id.clone(), *channel_id),
+				Some(expected_upgrade.encode_vec().map_err(Error::encode)?),
+				Duration::ZERO,
+			)
+		}
+		self.inner.verify_channel_upgrade(
+			ctx,
+			client_id,
+			&client_state.inner,
+			height,
+			prefix,
+			proof,
+			root,
+			port_id,
+			channel_id,
+			expected_upgrade,
+		)
+	}
+
+	fn verify_channel_upgrade_error<Ctx: ReaderContext>(
+		&self,
+		ctx: &Ctx,
+		client_id: &ClientId,
+		client_state: &Self::ClientState,
+		height: Height,
+		prefix: &CommitmentPrefix,
+		proof: &CommitmentProofBytes,
+		root: &CommitmentRoot,
+		port_id: &PortId,
+		channel_id: &ChannelId,
+		expected_error_receipt: &ErrorReceipt,
+	) -> Result<(), Error> {
+		if executor::has_code(&client_state.checksum) {
+			return verify_membership(
+				ctx,
+				client_id,
+				client_state,
+				height,
+				prefix,
+				proof,
+				ChannelUpgradeErrorPath(port_id.clone(), *channel_id),
+				Some(expected_error_receipt.encode_vec().map_err(Error::encode)?),
+				Duration::ZERO,
+			)
+		}
+		self.inner.verify_channel_upgrade_error(
+			ctx,
+			client_id,
+			&client_state.inner,
+			height,
+			prefix,
+			proof,
+			root,
+			port_id,
+			channel_id,
+			expected_error_receipt,
+		)
+	}
+
 	fn verify_client_full_state<Ctx: ReaderContext>(
 		&self,
 		ctx: &Ctx,
@@ -303,6 +471,19 @@ where
 		sequence: Sequence,
 		commitment: PacketCommitment,
 	) -> Result<(), Error> {
+		if executor::has_code(&client_state.checksum) {
+			return verify_membership(
+				ctx,
+				client_id,
+				client_state,
+				height,
+				connection_end.counterparty().prefix(),
+				proof,
+				CommitmentsPath { port_id: port_id.clone(), channel_id: *channel_id, sequence },
+				Some(commitment.into_vec()),
+				connection_end.delay_period(),
+			)
+		}
 		self.inner.verify_packet_data(
 			ctx,
 			client_id,
@@ -332,6 +513,19 @@ where
 		sequence: Sequence,
 		ack: AcknowledgementCommitment,
 	) -> Result<(), Error> {
+		if executor::has_code(&client_state.checksum) {
+			return verify_membership(
+				ctx,
+				client_id,
+				client_state,
+				height,
+				connection_end.counterparty().prefix(),
+				proof,
+				AcksPath { port_id: port_id.clone(), channel_id: *channel_id, sequence },
+				Some(ack.into_vec()),
+				connection_end.delay_period(),
+			)
+		}
 		self.inner.verify_packet_acknowledgement(
 			ctx,
 			client_id,
@@ -360,6 +554,19 @@ where
 		channel_id: &ChannelId,
 		sequence: Sequence,
 	) -> Result<(), Error> {
+		if executor::has_code(&client_state.checksum) {
+			return verify_membership(
+				ctx,
+				client_id,
+				client_state,
+				height,
+				connection_end.counterparty().prefix(),
+				proof,
+				SeqRecvsPath(port_id.clone(), *channel_id),
+				Some(u64::from(sequence).to_be_bytes().to_vec()),
+				connection_end.delay_period(),
+			)
+		}
 		self.inner.verify_next_sequence_recv(
 			ctx,
 			client_id,
@@ -374,6 +581,87 @@ where
 		)
 	}
 
+	fn verify_packet_receipt<Ctx: ReaderContext>(
+		&self,
+		ctx: &Ctx,
+		client_id: &ClientId,
+		client_state: &Self::ClientState,
+		height: Height,
+		connection_end: &ConnectionEnd,
+		proof: &CommitmentProofBytes,
+		root: &CommitmentRoot,
+		port_id: &PortId,
+		channel_id: &ChannelId,
+		sequence: Sequence,
+		receipt: Receipt,
+	) -> Result<(), Error> {
+		if executor::has_code(&client_state.checksum) {
+			return verify_membership(
+				ctx,
+				client_id,
+				client_state,
+				height,
+				connection_end.counterparty().prefix(),
+				proof,
+				ReceiptsPath { port_id: port_id.clone(), channel_id: *channel_id, sequence },
+				Some(receipt.as_bytes().to_vec()),
+				connection_end.delay_period(),
+			)
+		}
+		self.inner.verify_packet_receipt(
+			ctx,
+			client_id,
+			&client_state.inner,
+			height,
+			connection_end,
+			proof,
+			root,
+			port_id,
+			channel_id,
+			sequence,
+			receipt,
+		)
+	}
+
+	fn verify_batch_membership<Ctx: ReaderContext>(
+		&self,
+		ctx: &Ctx,
+		client_id: &ClientId,
+		client_state: &Self::ClientState,
+		height: Height,
+		connection_end: &ConnectionEnd,
+		proof: &CommitmentProofBytes,
+		root: &CommitmentRoot,
+		items: Vec<(Path, Vec<u8>)>,
+	) -> Result<(), Error> {
+		// the contracts verify a single path at a time, the batch proof is checked for every item
+		if executor::has_code(&client_state.checksum) {
+			return items.into_iter().try_for_each(|(path, value)| {
+				verify_membership(
+					ctx,
+					client_id,
+					client_state,
+					height,
+					connection_end.counterparty().prefix(),
+					proof,
+					path,
+					Some(value),
+					connection_end.delay_period(),
+				)
+			})
+		}
+		self.inner.verify_batch_membership(
+			ctx,
+			client_id,
+			&client_state.inner,
+			height,
+			connection_end,
+			proof,
+			root,
+			items,
+		)
+	}
+
 	fn verify_packet_receipt_absence<Ctx: ReaderContext>(
 		&self,
 		ctx: &Ctx,
@@ -387,6 +675,19 @@ where
 		channel_id: &ChannelId,
 		sequence: Sequence,
 	) -> Result<(), Error> {
+		if executor::has_code(&client_state.checksum) {
+			return verify_membership(
+				ctx,
+				client_id,
+				client_state,
+				height,
+				connection_end.counterparty().prefix(),
+				proof,
+				ReceiptsPath { port_id: port_id.clone(), channel_id: *channel_id, sequence },
+				None,
+				connection_end.delay_period(),
+			)
+		}
 		self.inner.verify_packet_receipt_absence(
 			ctx,
 			client_id,
@@ -401,3 +702,69 @@ where
 		)
 	}
 }
+
+/// Reads back the client state and the consensus states at `heights` that the contract wrote to
+/// the client store, the consensus state at the latest height is read if no height is returned.
+fn stored_states<Ctx, S>(
+	ctx: &Ctx,
+	client_id: &ClientId,
+	heights: Option<Vec<Height>>,
+) -> Result<(S, ConsensusUpdateResult<Ctx>), Error>
+where
+	Ctx: ReaderContext,
+	S: IbcClientState + Clone + 'static,
+{
+	let client_state = ctx.client_state(client_id)?.downcast::<S>().ok_or_else(|| {
+		Error::implementation_specific("the contract stored a foreign client state".into())
+	})?;
+	let heights = heights.unwrap_or_else(|| vec![client_state.latest_height()]);
+	let consensus_states = heights
+		.into_iter()
+		.map(|height| Ok((height, ctx.consensus_state(client_id, height)?)))
+		.collect::<Result<Vec<_>, Error>>()?;
+	Ok((client_state, ConsensusUpdateResult::Batch(consensus_states)))
+}
+
+/// Verifies a proof of `path` with the contract of the client, `value` is `None` for a proof of
+/// absence.
+#[allow(clippy::too_many_arguments)]
+fn verify_membership<Ctx, AnyClient, AnyClientState, AnyConsensusState>(
+	ctx: &Ctx,
+	client_id: &ClientId,
+	client_state: &ClientState<AnyClient, AnyClientState, AnyConsensusState>,
+	height: Height,
+	prefix: &CommitmentPrefix,
+	proof: &CommitmentProofBytes,
+	path: impl Into<Path>,
+	value: Option<Vec<u8>>,
+	delay_period: Duration,
+) -> Result<(), Error>
+where
+	Ctx: ReaderContext,
+{
+	let prefix = String::from_utf8(prefix.as_bytes().to_vec()).map_err(|_| {
+		Error::implementation_specific("commitment prefix is not valid utf8".into())
+	})?;
+	let path = MerklePath { key_path: vec![prefix, path.into().to_string()] };
+	let proof = proof.as_bytes().to_vec();
+	let delay_time_period = delay_period.as_nanos() as u64;
+	let delay_block_period = ctx.block_delay(delay_period);
+	let msg = match value {
+		Some(value) => SudoMsg::VerifyMembership(VerifyMembershipMsg {
+			proof,
+			path,
+			value,
+			height,
+			delay_block_period,
+			delay_time_period,
+		}),
+		None => SudoMsg::VerifyNonMembership(VerifyNonMembershipMsg {
+			proof,
+			path,
+			height,
+			delay_block_period,
+			delay_time_period,
+		}),
+	};
+	executor::sudo(&client_state.checksum, client_id, msg).map(|_| ())
+}
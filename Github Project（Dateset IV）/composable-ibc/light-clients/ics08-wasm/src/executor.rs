@@ -0,0 +1,199 @@
+// Copyright (C) 2022 ComposableFi.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Host side of the wasm light clients.
+//!
+//! A host that stores the code pushed with [`MsgPushNewWasmCode`](crate::msg::MsgPushNewWasmCode)
+//! installs a [`WasmExecutor`] with [`using`] while it handles ibc messages. [`WasmClient`]
+//! then runs the contract of the client through the `SudoMsg` and `QueryMsg` interface of the
+//! 08-wasm light clients instead of the inner native client.
+//!
+//! [`WasmClient`]: crate::client_def::WasmClient
+
+#[cfg(feature = "serde")]
+use crate::msg::Base64;
+use crate::Bytes;
+use alloc::{string::String, vec::Vec};
+use ibc::{
+	core::{ics02_client::error::Error, ics24_host::identifier::ClientId},
+	Height,
+};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Messages of the contract that may write to the client store.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum SudoMsg {
+	UpdateState(UpdateStateMsg),
+	VerifyMembership(VerifyMembershipMsg),
+	VerifyNonMembership(VerifyNonMembershipMsg),
+	VerifyUpgradeAndUpdateState(VerifyUpgradeAndUpdateStateMsg),
+}
+
+/// Messages of the contract that only read the client store.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum QueryMsg {
+	CheckForMisbehaviour(CheckForMisbehaviourMsg),
+	VerifyClientMessage(VerifyClientMessageMsg),
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub struct UpdateStateMsg {
+	/// The `data` of the wasm client message
+	#[cfg_attr(feature = "serde", serde(with = "Base64"))]
+	pub client_message: Bytes,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub struct CheckForMisbehaviourMsg {
+	/// The `data` of the wasm client message
+	#[cfg_attr(feature = "serde", serde(with = "Base64"))]
+	pub client_message: Bytes,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub struct VerifyClientMessageMsg {
+	/// The `data` of the wasm client message
+	#[cfg_attr(feature = "serde", serde(with = "Base64"))]
+	pub client_message: Bytes,
+}
+
+/// Key path of a proof, the commitment prefix followed by the ics24 path.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub struct MerklePath {
+	pub key_path: Vec<String>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub struct VerifyMembershipMsg {
+	#[cfg_attr(feature = "serde", serde(with = "Base64"))]
+	pub proof: Bytes,
+	pub path: MerklePath,
+	#[cfg_attr(feature = "serde", serde(with = "Base64"))]
+	pub value: Bytes,
+	pub height: Height,
+	pub delay_block_period: u64,
+	pub delay_time_period: u64,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub struct VerifyNonMembershipMsg {
+	#[cfg_attr(feature = "serde", serde(with = "Base64"))]
+	pub proof: Bytes,
+	pub path: MerklePath,
+	pub height: Height,
+	pub delay_block_period: u64,
+	pub delay_time_period: u64,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub struct VerifyUpgradeAndUpdateStateMsg {
+	/// The `data` of the upgraded wasm client state
+	#[cfg_attr(feature = "serde", serde(with = "Base64"))]
+	pub upgrade_client_state: Bytes,
+	/// The `data` of the upgraded wasm consensus state
+	#[cfg_attr(feature = "serde", serde(with = "Base64"))]
+	pub upgrade_consensus_state: Bytes,
+	#[cfg_attr(feature = "serde", serde(with = "Base64"))]
+	pub proof_upgrade_client: Bytes,
+	#[cfg_attr(feature = "serde", serde(with = "Base64"))]
+	pub proof_upgrade_consensus_state: Bytes,
+}
+
+/// Data of the response to a [`SudoMsg`].
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Deserialize))]
+pub struct ContractResult {
+	/// Heights of the consensus states written by `update_state`
+	#[cfg_attr(feature = "serde", serde(default))]
+	pub heights: Option<Vec<Height>>,
+}
+
+/// Response to a [`QueryMsg`].
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Deserialize))]
+pub struct QueryResponse {
+	pub is_valid: bool,
+	#[cfg_attr(feature = "serde", serde(default))]
+	pub found_misbehaviour: Option<bool>,
+}
+
+/// Runs the contracts of the wasm light clients whose code is stored on the host. The contract
+/// of a client reads and writes the store of that client only.
+pub trait WasmExecutor {
+	/// Returns true if the code with the given checksum is stored on the host.
+	fn has_code(&self, checksum: &[u8]) -> bool;
+
+	/// Calls the `sudo` entry point of the contract.
+	fn sudo(
+		&mut self,
+		checksum: &[u8],
+		client_id: &ClientId,
+		msg: &SudoMsg,
+	) -> Result<ContractResult, Error>;
+
+	/// Calls the `query` entry point of the contract.
+	fn query(
+		&mut self,
+		checksum: &[u8],
+		client_id: &ClientId,
+		msg: &QueryMsg,
+	) -> Result<QueryResponse, Error>;
+}
+
+environmental::environmental!(wasm_executor: trait WasmExecutor);
+
+/// Makes `executor` available to the wasm clients for the duration of `f`.
+pub fn using<R, F: FnOnce() -> R>(executor: &mut dyn WasmExecutor, f: F) -> R {
+	wasm_executor::using(executor, f)
+}
+
+/// Returns true if an executor is installed and stores the code with the given checksum.
+pub(crate) fn has_code(checksum: &[u8]) -> bool {
+	wasm_executor::with(|executor| executor.has_code(checksum)).unwrap_or(false)
+}
+
+pub(crate) fn sudo(
+	checksum: &[u8],
+	client_id: &ClientId,
+	msg: SudoMsg,
+) -> Result<ContractResult, Error> {
+	wasm_executor::with(|executor| executor.sudo(checksum, client_id, &msg))
+		.unwrap_or_else(|| Err(no_executor()))
+}
+
+pub(crate) fn query(
+	checksum: &[u8],
+	client_id: &ClientId,
+	msg: QueryMsg,
+) -> Result<QueryResponse, Error> {
+	wasm_executor::with(|executor| executor.query(checksum, client_id, &msg))
+		.unwrap_or_else(|| Err(no_executor()))
+}
+
+fn no_executor() -> Error {
+	Error::implementation_specific("no wasm executor is installed".into())
+}
@@ -24,6 +24,7 @@ pub mod client_def;
 pub mod client_message;
 pub mod client_state;
 pub mod consensus_state;
+pub mod executor;
 pub mod instantiate;
 pub mod msg;
 
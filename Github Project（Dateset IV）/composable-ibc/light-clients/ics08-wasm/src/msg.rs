@@ -23,7 +23,7 @@ use ibc::{
 use ibc_proto::{
 	google::protobuf::Any, ibc::lightclients::wasm::v1::MsgPushNewWasmCode as RawMsgPushNewWasmCode,
 };
-#[cfg(feature = "cosmwasm")]
+#[cfg(feature = "serde")]
 use serde::{Deserializer, Serializer};
 
 pub const WASM_PUSH_WASM_CODE_TYPE_URL: &str = "/ibc.lightclients.wasm.v1.MsgPushNewWasmCode";
@@ -61,7 +61,7 @@ impl From<MsgPushNewWasmCode> for Any {
 
 pub struct Base64;
 
-#[cfg(feature = "cosmwasm")]
+#[cfg(feature = "serde")]
 impl Base64 {
 	pub fn serialize<S: Serializer>(v: &[u8], serializer: S) -> Result<S::Ok, S::Error> {
 		ibc_proto::base64::serialize(v, serializer)
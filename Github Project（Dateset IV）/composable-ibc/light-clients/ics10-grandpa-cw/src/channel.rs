@@ -23,6 +23,7 @@ use ibc::{
 			context::ChannelReader,
 			error::Error,
 			packet::{Receipt, Sequence},
+			upgrade::{ErrorReceipt, Upgrade},
 		},
 		ics24_host::identifier::{ChannelId, ClientId, ConnectionId, PortId},
 	},
@@ -138,4 +139,34 @@ impl<'a, H: HostFunctions> ChannelReader for Context<'a, H> {
 	fn max_expected_time_per_block(&self) -> Duration {
 		unimplemented!("'max_expected_time_per_block' is unavailable from the client")
 	}
+
+	fn channel_upgrade(&self, _port_channel_id: &(PortId, ChannelId)) -> Result<Upgrade, Error> {
+		Err(Error::implementation_specific(
+			"'channel_upgrade' is unavailable from the client".to_string(),
+		))
+	}
+
+	fn counterparty_channel_upgrade(
+		&self,
+		_port_channel_id: &(PortId, ChannelId),
+	) -> Result<Upgrade, Error> {
+		Err(Error::implementation_specific(
+			"'counterparty_channel_upgrade' is unavailable from the client".to_string(),
+		))
+	}
+
+	fn upgrade_error_receipt(
+		&self,
+		_port_channel_id: &(PortId, ChannelId),
+	) -> Result<ErrorReceipt, Error> {
+		Err(Error::implementation_specific(
+			"'upgrade_error_receipt' is unavailable from the client".to_string(),
+		))
+	}
+
+	fn has_inflight_packets(&self, _port_channel_id: &(PortId, ChannelId)) -> Result<bool, Error> {
+		Err(Error::implementation_specific(
+			"'has_inflight_packets' is unavailable from the client".to_string(),
+		))
+	}
 }
@@ -17,8 +17,8 @@ use crate::{
 	context::Context,
 	contract::{CLIENT_COUNTER, CONSENSUS_STATES_HEIGHTS, HOST_CONSENSUS_STATE},
 	ics23::{
-		ClientStates, ConsensusStates, FakeInner, ReadonlyClientStates, ReadonlyClients,
-		ReadonlyConsensusStates,
+		ClientStates, ConsensusStates, FakeInner, ProcessedStates, ReadonlyClientStates,
+		ReadonlyClients, ReadonlyConsensusStates,
 	},
 };
 use grandpa_light_client_primitives::HostFunctions;
@@ -201,13 +201,17 @@ impl<'a, H: HostFunctions<Header = RelayChainHeader>> ClientKeeper for Context<'
 
 	fn store_consensus_state(
 		&mut self,
-		_client_id: ClientId,
+		client_id: ClientId,
 		height: Height,
 		consensus_state: Self::AnyConsensusState,
 	) -> Result<(), Error> {
 		let encoded = Self::encode_consensus_state(consensus_state);
 		let mut consensus_states = ConsensusStates::new(self.storage_mut());
 		consensus_states.insert(height, encoded);
+
+		self.store_update_time(client_id.clone(), height, self.host_timestamp())?;
+		self.store_update_height(client_id, height, self.host_height())?;
+
 		Ok(())
 	}
 
@@ -218,19 +222,27 @@ impl<'a, H: HostFunctions<Header = RelayChainHeader>> ClientKeeper for Context<'
 	fn store_update_time(
 		&mut self,
 		_client_id: ClientId,
-		_height: Height,
-		_timestamp: Timestamp,
+		height: Height,
+		timestamp: Timestamp,
 	) -> Result<(), Error> {
-		unimplemented!()
+		let mut processed_state = ProcessedStates::new(self.storage_mut());
+		processed_state.set_processed_time(height, timestamp.nanoseconds(), "");
+
+		Ok(())
 	}
 
 	fn store_update_height(
 		&mut self,
 		_client_id: ClientId,
-		_height: Height,
+		height: Height,
 		_host_height: Height,
 	) -> Result<(), Error> {
-		unimplemented!()
+		// `host_height` carries the block height as its revision number
+		let block_height = self.env.block.height;
+		let mut processed_state = ProcessedStates::new(self.storage_mut());
+		processed_state.set_processed_height(height, block_height, "");
+		processed_state.set_iteration_key(height, "");
+		Ok(())
 	}
 
 	fn validate_self_client(&self, _client_state: &Self::AnyClientState) -> Result<(), Error> {
@@ -15,11 +15,12 @@
 
 use crate::{
 	contract::{
-		GRANDPA_BLOCK_HASHES_CACHE_SIZE, GRANDPA_HEADER_HASHES_SET_STORAGE,
-		GRANDPA_HEADER_HASHES_STORAGE,
+		GRANDPA_BLOCK_HASHES_CACHE_SIZE, GRANDPA_HEADER_HASHES_BOUNDS,
+		GRANDPA_HEADER_HASHES_SET_STORAGE, GRANDPA_HEADER_HASHES_STORAGE,
 	},
 	ics23::{
-		ClientStates, ConsensusStates, FakeInner, ReadonlyClientStates, ReadonlyConsensusStates,
+		ClientStates, ConsensusStates, FakeInner, ProcessedStates, ReadonlyClientStates,
+		ReadonlyConsensusStates, ReadonlyProcessedStates,
 	},
 	ContractError,
 };
@@ -37,6 +38,7 @@ use ibc_proto::google::protobuf::Any;
 use ics10_grandpa::{
 	client_message::RelayChainHeader, client_state::ClientState, consensus_state::ConsensusState,
 };
+use light_client_common::ProcessedConsensusStates;
 use sp_core::H256;
 use std::{fmt, fmt::Debug, marker::PhantomData};
 
@@ -101,37 +103,71 @@ impl<'a, H> Context<'a, H> {
 		}
 	}
 
-	pub fn insert_relay_header_hashes(&mut self, headers: &[H256]) {
+	/// Appends finalized relay header hashes to the ring buffer, tagging them with the height of
+	/// the latest consensus state they were finalized with. Once the buffer holds
+	/// [`GRANDPA_BLOCK_HASHES_CACHE_SIZE`] hashes, the oldest ones are evicted.
+	pub fn insert_relay_header_hashes(&mut self, headers: &[H256], height: Height) {
 		if headers.is_empty() {
 			return
 		}
 
-		let mut xs = GRANDPA_HEADER_HASHES_STORAGE.load(self.storage()).unwrap_or_default();
-		xs.reserve(headers.len());
+		let (mut head, mut tail) =
+			GRANDPA_HEADER_HASHES_BOUNDS.load(self.storage()).unwrap_or_default();
 		for header in headers {
-			xs.push(*header);
-			let _ = GRANDPA_HEADER_HASHES_SET_STORAGE
+			if self.contains_relay_header_hash(*header) {
+				self.log(&format!("relay header hash {header:?} is already stored"));
+				continue
+			}
+			if tail - head >= GRANDPA_BLOCK_HASHES_CACHE_SIZE {
+				self.remove_relay_header_hash(head);
+				head += 1;
+			}
+			GRANDPA_HEADER_HASHES_STORAGE
+				.save(
+					self.storage_mut(),
+					tail % GRANDPA_BLOCK_HASHES_CACHE_SIZE,
+					&(height.revision_height, *header),
+				)
+				.expect("error saving header hash");
+			GRANDPA_HEADER_HASHES_SET_STORAGE
 				.save(self.storage_mut(), header.0.to_vec(), &())
-				.map_err(|e| {
-					self.log(&format!("error saving hash to set: {e:?}"));
-				});
+				.expect("error saving header hash to set");
+			tail += 1;
 		}
-		if xs.len() > GRANDPA_BLOCK_HASHES_CACHE_SIZE {
-			let drained = xs.drain(0..xs.len() - GRANDPA_BLOCK_HASHES_CACHE_SIZE);
-			for hash in drained {
-				GRANDPA_HEADER_HASHES_SET_STORAGE.remove(self.storage_mut(), hash.0.to_vec());
+		GRANDPA_HEADER_HASHES_BOUNDS
+			.save(self.storage_mut(), &(head, tail))
+			.expect("error saving header hashes bounds");
+	}
+
+	/// Removes the relay header hashes that were finalized with consensus states at or below
+	/// `height`. Should be called whenever these consensus states are pruned.
+	pub fn prune_relay_header_hashes(&mut self, height: Height) {
+		let (mut head, tail) =
+			GRANDPA_HEADER_HASHES_BOUNDS.load(self.storage()).unwrap_or_default();
+		while head < tail {
+			let slot = head % GRANDPA_BLOCK_HASHES_CACHE_SIZE;
+			match GRANDPA_HEADER_HASHES_STORAGE.may_load(self.storage(), slot) {
+				Ok(Some((para_height, _))) if para_height > height.revision_height => break,
+				_ => {},
 			}
+			self.remove_relay_header_hash(head);
+			head += 1;
 		}
-		GRANDPA_HEADER_HASHES_STORAGE
-			.save(self.storage_mut(), &xs)
-			.expect("error saving header hashes");
+		GRANDPA_HEADER_HASHES_BOUNDS
+			.save(self.storage_mut(), &(head, tail))
+			.expect("error saving header hashes bounds");
 	}
 
 	pub fn contains_relay_header_hash(&self, hash: H256) -> bool {
-		GRANDPA_HEADER_HASHES_STORAGE
-			.load(self.storage())
-			.unwrap_or_default()
-			.contains(&hash)
+		GRANDPA_HEADER_HASHES_SET_STORAGE.has(self.storage(), hash.0.to_vec())
+	}
+
+	fn remove_relay_header_hash(&mut self, index: u64) {
+		let slot = index % GRANDPA_BLOCK_HASHES_CACHE_SIZE;
+		if let Ok(Some((_, hash))) = GRANDPA_HEADER_HASHES_STORAGE.may_load(self.storage(), slot) {
+			GRANDPA_HEADER_HASHES_SET_STORAGE.remove(self.storage_mut(), hash.0.to_vec());
+		}
+		GRANDPA_HEADER_HASHES_STORAGE.remove(self.storage_mut(), slot);
 	}
 }
 
@@ -219,4 +255,23 @@ where
 	}
 }
 
+impl<'a, H> ProcessedConsensusStates for Context<'a, H> {
+	fn processed_times_below(
+		&self,
+		height: Height,
+	) -> Box<dyn Iterator<Item = (Height, Option<u64>)> + '_> {
+		let processed_states = ReadonlyProcessedStates::new(self.storage());
+		Box::new(
+			processed_states
+				.get_heights_below(height)
+				.map(move |height| (height, processed_states.get_processed_time(height, ""))),
+		)
+	}
+
+	fn remove_consensus_state(&mut self, height: Height) {
+		ProcessedStates::new(self.storage_mut()).remove_states_at_height(height);
+		ConsensusStates::new(self.storage_mut()).remove(height);
+	}
+}
+
 impl<'a, H: HostFunctions<Header = RelayChainHeader>> ReaderContext for Context<'a, H> {}
@@ -16,11 +16,13 @@
 use crate::{
 	context::Context,
 	error::ContractError,
+	ics23::ReadonlyProcessedStates,
 	log,
 	msg::{
-		CheckForMisbehaviourMsg, ContractResult, ExportMetadataMsg, QueryMsg, QueryResponse,
-		StatusMsg, SudoMsg, UpdateStateMsg, UpdateStateOnMisbehaviourMsg, VerifyClientMessage,
-		VerifyMembershipMsg, VerifyNonMembershipMsg, VerifyUpgradeAndUpdateStateMsg,
+		CheckForMisbehaviourMsg, ContractResult, ExportMetadataMsg, MigrateMsg, QueryMsg,
+		QueryResponse, StatusMsg, SudoMsg, UpdateStateMsg, UpdateStateOnMisbehaviourMsg,
+		VerifyClientMessage, VerifyMembershipMsg, VerifyNonMembershipMsg,
+		VerifyUpgradeAndUpdateStateMsg,
 	},
 	state::{get_client_state, get_consensus_state},
 	Bytes,
@@ -34,7 +36,6 @@ use cosmwasm_std::{
 };
 use cw_storage_plus::{Item, Map};
 use digest::Digest;
-use grandpa_light_client_primitives::justification::AncestryChain;
 use ibc::core::{
 	ics02_client::{
 		client_def::{ClientDef, ConsensusUpdateResult},
@@ -51,12 +52,14 @@ use ics10_grandpa::{
 	client_state::ClientState,
 	consensus_state::ConsensusState,
 };
-use light_client_common::{verify_membership, verify_non_membership};
+use light_client_common::{
+	prune_expired_consensus_states, verify_membership, verify_non_membership,
+};
 use prost::Message;
 use sp_core::H256;
 use sp_runtime::traits::{BlakeTwo256, Header};
 use sp_runtime_interface::unpack_ptr_and_len;
-use std::{collections::BTreeSet, str::FromStr};
+use std::{cell::RefCell, collections::BTreeSet, str::FromStr};
 use tendermint_proto::Protobuf;
 /*
 // version info for migration info
@@ -75,11 +78,29 @@ pub const CLIENT_COUNTER: Item<u32> = Item::new("client_counter");
 pub const HOST_CONSENSUS_STATE: Map<u64, ConsensusState> = Map::new("host_consensus_state");
 pub const CONSENSUS_STATES_HEIGHTS: Map<Bytes, BTreeSet<Height>> =
 	Map::new("consensus_states_heights");
-pub const GRANDPA_HEADER_HASHES_STORAGE: Item<Vec<H256>> = Item::new("grandpa_header_hashes");
+/// Ring buffer of finalized relay header hashes: slot => (parachain height, relay header hash)
+pub const GRANDPA_HEADER_HASHES_STORAGE: Map<u64, (u64, H256)> =
+	Map::new("grandpa_header_hashes_ring");
+/// Index of the oldest and one past the newest entry of [`GRANDPA_HEADER_HASHES_STORAGE`]
+pub const GRANDPA_HEADER_HASHES_BOUNDS: Item<(u64, u64)> =
+	Item::new("grandpa_header_hashes_bounds");
 pub const GRANDPA_HEADER_HASHES_SET_STORAGE: Map<Vec<u8>, ()> =
 	Map::new("grandpa_header_hashes_set");
 
-pub const GRANDPA_BLOCK_HASHES_CACHE_SIZE: usize = 500;
+/// Relay header hashes stored by previous versions of the contract, superseded by
+/// [`GRANDPA_HEADER_HASHES_STORAGE`]. Only read by [`migrate`].
+const LEGACY_GRANDPA_HEADER_HASHES_STORAGE: Item<Vec<H256>> = Item::new("grandpa_header_hashes");
+
+/// Maximum number of relay header hashes to keep (oldest pruned first).
+pub const GRANDPA_BLOCK_HASHES_CACHE_SIZE: u64 = 500;
+
+thread_local! {
+	/// Relay header hashes finalized by the client during the current call. They are persisted
+	/// with [`Context::insert_relay_header_hashes`] once the update has been applied.
+	static FINALIZED_RELAY_HEADER_HASHES: RefCell<Vec<H256>> = RefCell::new(Vec::new());
+	/// Relay header hashes loaded from the contract storage for the current call.
+	static KNOWN_RELAY_HEADER_HASHES: RefCell<BTreeSet<H256>> = RefCell::new(BTreeSet::new());
+}
 
 #[derive(Clone, Copy, Debug, PartialEq, Default, Eq)]
 pub struct HostFunctions;
@@ -107,16 +128,36 @@ impl grandpa_light_client_primitives::HostFunctions for HostFunctions {
 		pub_key.verify(&sig, msg).is_ok()
 	}
 
-	fn insert_relay_header_hashes(_headers: &[<Self::Header as Header>::Hash]) {
-		// implementation of this method is in `Context`
+	fn insert_relay_header_hashes(headers: &[<Self::Header as Header>::Hash]) {
+		FINALIZED_RELAY_HEADER_HASHES.with(|hashes| hashes.borrow_mut().extend_from_slice(headers));
 	}
 
-	fn contains_relay_header_hash(_hash: <Self::Header as Header>::Hash) -> bool {
-		// implementation of this method is in `Context`
-		true
+	fn contains_relay_header_hash(hash: <Self::Header as Header>::Hash) -> bool {
+		KNOWN_RELAY_HEADER_HASHES.with(|hashes| hashes.borrow().contains(&hash))
 	}
 }
 
+/// Returns the relay header hashes finalized by the client since the last call.
+fn take_finalized_relay_header_hashes() -> Vec<H256> {
+	FINALIZED_RELAY_HEADER_HASHES.with(|hashes| core::mem::take(&mut *hashes.borrow_mut()))
+}
+
+/// Makes the stored relay header hashes that `client_message` builds upon visible to
+/// [`HostFunctions::contains_relay_header_hash`].
+fn load_known_relay_header_hashes(ctx: &Context<HostFunctions>, client_message: &ClientMessage) {
+	let mut known = BTreeSet::new();
+	if let ClientMessage::Misbehaviour(misbehaviour) = client_message {
+		for proof in [&misbehaviour.first_finality_proof, &misbehaviour.second_finality_proof] {
+			let parent =
+				proof.unknown_headers.iter().min_by_key(|h| *h.number()).map(|h| h.parent_hash);
+			if let Some(parent) = parent.filter(|parent| ctx.contains_relay_header_hash(*parent)) {
+				known.insert(parent);
+			}
+		}
+	}
+	KNOWN_RELAY_HEADER_HASHES.with(|hashes| *hashes.borrow_mut() = known);
+}
+
 fn process_instantiate_msg(
 	msg: InstantiateMessage,
 	ctx: &mut Context<HostFunctions>,
@@ -163,6 +204,38 @@ pub fn sudo(deps: DepsMut, env: Env, msg: SudoMsg) -> Result<Response, ContractE
 	Ok(response)
 }
 
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn migrate(deps: DepsMut, env: Env, _msg: MigrateMsg) -> Result<Response, ContractError> {
+	let client_id = ClientId::from_str(env.contract.address.as_str()).expect("client id is valid");
+	let mut ctx = Context::<HostFunctions>::new(deps, env);
+	migrate_legacy_relay_header_hashes(&mut ctx, &client_id)?;
+	Ok(Response::default())
+}
+
+/// Moves the relay header hashes kept by previous versions of the contract into the ring buffer,
+/// tagging them with the latest height of the client, and removes the legacy item.
+fn migrate_legacy_relay_header_hashes(
+	ctx: &mut Context<HostFunctions>,
+	client_id: &ClientId,
+) -> Result<(), ContractError> {
+	let legacy_hashes = match LEGACY_GRANDPA_HEADER_HASHES_STORAGE.may_load(ctx.storage()) {
+		Ok(Some(hashes)) => hashes,
+		// nothing to migrate, but an undecodable item is still removed below
+		Ok(None) => return Ok(()),
+		Err(_) => Vec::new(),
+	};
+	let height = ctx
+		.client_state(client_id)
+		.map(|client_state| client_state.latest_height())
+		.unwrap_or_else(|_| Height::zero());
+	// the legacy item was unbounded, so only the most recent hashes fit in the ring buffer
+	let skip = legacy_hashes.len().saturating_sub(GRANDPA_BLOCK_HASHES_CACHE_SIZE as usize);
+	ctx.insert_relay_header_hashes(&legacy_hashes[skip..], height);
+	LEGACY_GRANDPA_HEADER_HASHES_STORAGE.remove(ctx.storage_mut());
+	log!(ctx, "migrated {} legacy relay header hashes", legacy_hashes.len() - skip);
+	Ok(())
+}
+
 fn process_message(
 	msg: SudoMsg,
 	client: GrandpaClient<HostFunctions>,
@@ -190,29 +263,23 @@ fn process_message(
 				.client_state(&client_id)
 				.map_err(|e| ContractError::Grandpa(e.to_string()))?;
 			let msg = UpdateStateMsg::try_from(msg_raw)?;
+			let pruned_heights = prune_expired_consensus_states(
+				ctx,
+				client_state.latest_height(),
+				ctx.host_timestamp().nanoseconds(),
+				|elapsed| client_state.expired(elapsed),
+			);
+			if let Some(height) = pruned_heights.last() {
+				ctx.prune_relay_header_hashes(*height);
+			}
 
-			let finalized_headers = match &msg.client_message {
-				ClientMessage::Header(header) => {
-					use finality_grandpa::Chain;
-					let ancestry = AncestryChain::<RelayChainHeader>::new(
-						&header.finality_proof.unknown_headers,
-					);
-					let from = client_state.latest_relay_hash;
-					let mut finalized =
-						ancestry.ancestry(from, header.finality_proof.block).map_err(|_| {
-							ContractError::Grandpa("[update_state] Invalid ancestry!".to_string())
-						})?;
-					finalized.reverse();
-					finalized
-				},
-				_ => Vec::new(),
-			};
-
+			take_finalized_relay_header_hashes();
 			client
 				.update_state(ctx, client_id.clone(), client_state, msg.client_message)
 				.map_err(|e| ContractError::Grandpa(e.to_string()))
 				.and_then(|(cs, cu)| {
-					ctx.insert_relay_header_hashes(&finalized_headers);
+					let finalized_headers = take_finalized_relay_header_hashes();
+					ctx.insert_relay_header_hashes(&finalized_headers, cs.latest_height());
 					store_client_and_consensus_states(ctx, client_id.clone(), cs, cu)
 				})
 		},
@@ -316,8 +383,10 @@ pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
 		},
 		QueryMsg::ClientTypeMsg(_) => unimplemented!("ClientTypeMsg"),
 		QueryMsg::GetLatestHeightsMsg(_) => unimplemented!("GetLatestHeightsMsg"),
-		QueryMsg::ExportMetadata(ExportMetadataMsg {}) =>
-			to_binary(&QueryResponse::success().genesis_metadata(None)),
+		QueryMsg::ExportMetadata(ExportMetadataMsg {}) => {
+			let ro_proceeded_state = ReadonlyProcessedStates::new(deps.storage);
+			to_binary(&QueryResponse::success().genesis_metadata(ro_proceeded_state.get_metadata()))
+		},
 		QueryMsg::Status(StatusMsg {}) => {
 			let client_state = match get_client_state::<HostFunctions>(deps, client_id.clone()) {
 				Ok(client_state) => client_state,
@@ -368,26 +437,7 @@ pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
 				.map_err(|e| ContractError::Grandpa(e.to_string()))?;
 			let msg = VerifyClientMessage::try_from(msg)?;
 
-			match &msg.client_message {
-				ClientMessage::Misbehaviour(misbehavior) => {
-					let first_proof = &misbehavior.first_finality_proof;
-					let first_base = first_proof
-						.unknown_headers
-						.iter()
-						.min_by_key(|h| *h.number())
-						.ok_or_else(|| {
-							ContractError::Grandpa("Unknown headers can't be empty!".to_string())
-						})?;
-					let first_parent = first_base.parent_hash;
-					if !ctx.contains_relay_header_hash(first_parent) {
-						Err(ContractError::Grandpa(
-							"Could not find the known header for first finality proof".to_string(),
-						))?
-					}
-				},
-				_ => {},
-			}
-
+			load_known_relay_header_hashes(&ctx, &msg.client_message);
 			let f = client
 				.verify_client_message(&ctx, client_id, client_state, msg.client_message)
 				.map_err(|e| ContractError::Grandpa(format!("{e:?}")))
@@ -484,7 +534,8 @@ mod tests {
 	use ibc::core::ics02_client::client_state::ClientState;
 	use tendermint::Time;
 
-	use crate::ics23::ClientStates;
+	use crate::ics23::{ClientStates, ReadonlyConsensusStates};
+	use light_client_common::{prune_expired_consensus_states, MAX_PRUNED_CONSENSUS_STATES};
 
 	use super::*;
 	#[test]
@@ -529,4 +580,135 @@ mod tests {
 			assert_eq!(resp, QueryResponse::success().status(expected.to_string()));
 		}
 	}
+
+	#[test]
+	fn test_relay_header_hashes_ring_buffer() {
+		let mut deps = mock_dependencies();
+		let mut context = Context::<HostFunctions>::new(deps.as_mut(), mock_env());
+		let hashes = (0..GRANDPA_BLOCK_HASHES_CACHE_SIZE + 10)
+			.map(|i| H256::from_low_u64_be(i + 1))
+			.collect::<Vec<_>>();
+		let height = |revision_height| Height { revision_number: 0, revision_height };
+
+		context.insert_relay_header_hashes(&hashes[..10], height(1));
+		context.insert_relay_header_hashes(&hashes[10..], height(2));
+
+		// the oldest hashes are evicted once the buffer is full
+		assert!(hashes[..10].iter().all(|hash| !context.contains_relay_header_hash(*hash)));
+		assert!(hashes[10..].iter().all(|hash| context.contains_relay_header_hash(*hash)));
+		assert_eq!(
+			GRANDPA_HEADER_HASHES_BOUNDS.load(context.storage()).unwrap(),
+			(10, GRANDPA_BLOCK_HASHES_CACHE_SIZE + 10)
+		);
+
+		// hashes are pruned together with the consensus states they were finalized with
+		context.prune_relay_header_hashes(height(1));
+		assert!(hashes[10..].iter().all(|hash| context.contains_relay_header_hash(*hash)));
+		context.prune_relay_header_hashes(height(2));
+		assert!(hashes.iter().all(|hash| !context.contains_relay_header_hash(*hash)));
+		assert!(GRANDPA_HEADER_HASHES_STORAGE
+			.range(context.storage(), None, None, cosmwasm_std::Order::Ascending)
+			.next()
+			.is_none());
+	}
+
+	#[test]
+	fn test_migrate_legacy_relay_header_hashes() {
+		let mut deps = mock_dependencies();
+		let env = mock_env();
+		let hashes = (0..GRANDPA_BLOCK_HASHES_CACHE_SIZE + 10)
+			.map(|i| H256::from_low_u64_be(i + 1))
+			.collect::<Vec<_>>();
+		LEGACY_GRANDPA_HEADER_HASHES_STORAGE
+			.save(deps.as_mut().storage, &hashes)
+			.unwrap();
+
+		migrate(deps.as_mut(), env.clone(), MigrateMsg {}).unwrap();
+
+		let context = Context::<HostFunctions>::new_ro(deps.as_ref(), env.clone());
+		// only the most recent hashes fit in the ring buffer
+		assert!(hashes[..10].iter().all(|hash| !context.contains_relay_header_hash(*hash)));
+		assert!(hashes[10..].iter().all(|hash| context.contains_relay_header_hash(*hash)));
+		assert!(LEGACY_GRANDPA_HEADER_HASHES_STORAGE
+			.may_load(context.storage())
+			.unwrap()
+			.is_none());
+
+		// migrating again is a no-op
+		migrate(deps.as_mut(), env, MigrateMsg {}).unwrap();
+		assert_eq!(
+			GRANDPA_HEADER_HASHES_BOUNDS.load(deps.as_ref().storage).unwrap(),
+			(0, GRANDPA_BLOCK_HASHES_CACHE_SIZE)
+		);
+	}
+
+	#[test]
+	fn test_prune_expired_consensus_states() {
+		let mut deps = mock_dependencies();
+		let env = mock_env();
+		let now = env.block.time.nanos();
+		let mut client_state = ics10_grandpa::client_state::ClientState::<HostFunctions>::default();
+		client_state.latest_para_height = 20;
+		let height = |revision_height| Height::new(client_state.para_id.into(), revision_height);
+		let consensus_state = ics10_grandpa::consensus_state::ConsensusState::new(
+			vec![],
+			Time::from_unix_timestamp(0, 0).unwrap(),
+		);
+
+		// consensus states at heights 1..=15 were processed before the trusting period
+		let mut old_env = env.clone();
+		old_env.block.time = cosmwasm_std::Timestamp::from_nanos(
+			now - client_state.relay_chain.trusting_period().as_nanos() as u64 - 1,
+		);
+		let mut context = Context::<HostFunctions>::new(deps.as_mut(), old_env);
+		for revision_height in 1..=15 {
+			context
+				.store_consensus_state(
+					ClientId::default(),
+					height(revision_height),
+					consensus_state.clone(),
+				)
+				.unwrap();
+			context.insert_relay_header_hashes(
+				&[H256::from_low_u64_be(revision_height)],
+				height(revision_height),
+			);
+		}
+		let mut context = Context::<HostFunctions>::new(deps.as_mut(), env);
+		for revision_height in 16..=20 {
+			context
+				.store_consensus_state(
+					ClientId::default(),
+					height(revision_height),
+					consensus_state.clone(),
+				)
+				.unwrap();
+		}
+		let stored_states = |context: &Context<HostFunctions>| {
+			let consensus_states = ReadonlyConsensusStates::new(context.storage());
+			(1..=20).filter(|h| consensus_states.get(height(*h)).is_some()).count()
+		};
+
+		let prune = |context: &mut Context<HostFunctions>| {
+			prune_expired_consensus_states(context, client_state.latest_height(), now, |elapsed| {
+				client_state.expired(elapsed)
+			})
+		};
+
+		let pruned = prune(&mut context);
+		assert_eq!(pruned, (1..=10).map(height).collect::<Vec<_>>());
+		assert_eq!(stored_states(&context), 20 - MAX_PRUNED_CONSENSUS_STATES);
+		context.prune_relay_header_hashes(*pruned.last().unwrap());
+		assert!(!context.contains_relay_header_hash(H256::from_low_u64_be(10)));
+		assert!(context.contains_relay_header_hash(H256::from_low_u64_be(11)));
+
+		let pruned = prune(&mut context);
+		assert_eq!(pruned, (11..=15).map(height).collect::<Vec<_>>());
+		assert_eq!(stored_states(&context), 5);
+		assert!(prune(&mut context).is_empty());
+
+		let processed_states = ReadonlyProcessedStates::new(context.storage());
+		assert!(processed_states.get_processed_time(height(1), "").is_none());
+		assert_eq!(processed_states.get_heights_below(height(21)).count(), 5);
+	}
 }
@@ -16,9 +16,11 @@
 mod client_states;
 mod clients;
 mod consensus_states;
+mod processed_states;
 
 pub use self::{
 	client_states::{ClientStates, ReadonlyClientStates},
 	clients::{Clients, ReadonlyClients},
 	consensus_states::{ConsensusStates, FakeInner, ReadonlyConsensusStates},
+	processed_states::{ProcessedStates, ReadonlyProcessedStates},
 };
@@ -148,6 +148,9 @@ pub enum SudoMsg {
 	VerifyUpgradeAndUpdateState(VerifyUpgradeAndUpdateStateMsgRaw),
 }
 
+#[cw_serde]
+pub struct MigrateMsg {}
+
 #[cw_serde]
 pub enum QueryMsg {
 	CheckForMisbehaviour(CheckForMisbehaviourMsgRaw),
@@ -13,12 +13,16 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use crate::{client_state::ClientState, consensus_state::ConsensusState, error::Error};
+use crate::{
+	client_state::{ChainType, ClientState},
+	consensus_state::ConsensusState,
+	error::Error,
+};
 use ibc::core::ics02_client::{
 	client_consensus::ConsensusState as _, client_state::ClientState as _,
 };
 
-use crate::client_message::{ClientMessage, RelayChainHeader};
+use crate::client_message::{ClientMessage, Header as GrandpaHeader, RelayChainHeader};
 use alloc::{format, string::ToString, vec, vec::Vec};
 use codec::Decode;
 use core::marker::PhantomData;
@@ -27,7 +31,7 @@ use grandpa_client_primitives::{
 	justification::{
 		find_forced_change, find_scheduled_change, AncestryChain, GrandpaJustification,
 	},
-	ParachainHeadersWithFinalityProof,
+	ParachainHeadersWithFinalityProof, SolochainHeadersWithFinalityProof,
 };
 use ibc::{
 	core::{
@@ -39,22 +43,26 @@ use ibc::{
 		ics04_channel::{
 			channel::ChannelEnd,
 			commitment::{AcknowledgementCommitment, PacketCommitment},
-			packet::Sequence,
+			packet::{Receipt, Sequence},
+			upgrade::{ErrorReceipt, Upgrade},
 		},
 		ics23_commitment::commitment::{CommitmentPrefix, CommitmentProofBytes, CommitmentRoot},
 		ics24_host::{
 			identifier::{ChannelId, ClientId, ConnectionId, PortId},
 			path::{
-				AcksPath, ChannelEndsPath, ClientConsensusStatePath, ClientStatePath,
-				CommitmentsPath, ConnectionsPath, ReceiptsPath, SeqRecvsPath,
+				AcksPath, ChannelEndsPath, ChannelUpgradeErrorPath, ChannelUpgradePath,
+				ClientConsensusStatePath, ClientStatePath, CommitmentsPath, ConnectionsPath,
+				ReceiptsPath, SeqRecvsPath,
 			},
+			Path,
 		},
 		ics26_routing::context::ReaderContext,
 	},
 	Height,
 };
 use light_client_common::{
-	state_machine, verify_delay_passed, verify_membership, verify_non_membership,
+	state_machine, verify_batch_membership, verify_delay_passed, verify_membership,
+	verify_non_membership,
 };
 use sp_core::H256;
 use sp_runtime::traits::Header;
@@ -91,17 +99,47 @@ where
 					))
 					.into())
 				}
-				let headers_with_finality_proof = ParachainHeadersWithFinalityProof {
-					finality_proof: header.finality_proof,
-					parachain_headers: header.parachain_headers,
-					latest_para_height: header.height.revision_height as u32,
-				};
-
-				grandpa_client::verify_parachain_headers_with_grandpa_finality_proof::<
-					RelayChainHeader,
-					H,
-				>(client_state.into(), headers_with_finality_proof)
-				.map_err(Error::GrandpaPrimitives)?;
+				match client_state.chain_type {
+					ChainType::Parachain => {
+						if !header.solochain_headers.is_empty() {
+							return Err(Error::Custom(
+								"Solochain headers can't be used to update a parachain client"
+									.to_string(),
+							)
+							.into())
+						}
+						let headers_with_finality_proof = ParachainHeadersWithFinalityProof {
+							finality_proof: header.finality_proof,
+							parachain_headers: header.parachain_headers,
+							latest_para_height: header.height.revision_height as u32,
+						};
+
+						grandpa_client::verify_parachain_headers_with_grandpa_finality_proof::<
+							RelayChainHeader,
+							H,
+						>(client_state.into(), headers_with_finality_proof)
+						.map_err(Error::GrandpaPrimitives)?;
+					},
+					ChainType::Solochain => {
+						if !header.parachain_headers.is_empty() {
+							return Err(Error::Custom(
+								"Parachain headers can't be used to update a solochain client"
+									.to_string(),
+							)
+							.into())
+						}
+						let headers_with_finality_proof = SolochainHeadersWithFinalityProof {
+							finality_proof: header.finality_proof,
+							solochain_headers: header.solochain_headers,
+						};
+
+						grandpa_client::verify_solochain_headers_with_grandpa_finality_proof::<
+							RelayChainHeader,
+							H,
+						>(client_state.into(), headers_with_finality_proof)
+						.map_err(Error::GrandpaPrimitives)?;
+					},
+				}
 			},
 			ClientMessage::Misbehaviour(misbehavior) => {
 				let first_proof = misbehavior.first_finality_proof;
@@ -230,23 +268,14 @@ where
 		let mut finalized_sorted = finalized.clone();
 		finalized_sorted.sort();
 
-		for (relay_hash, parachain_header_proof) in header.parachain_headers {
-			// we really shouldn't set consensus states for parachain headers not in the finalized
-			// chain.
-			if finalized_sorted.binary_search(&relay_hash).is_err() {
-				continue
-			}
-
-			let header = ancestry.header(&relay_hash).ok_or_else(|| {
-				Error::Custom(format!("No relay chain header found for hash: {relay_hash:?}"))
+		let target_hash = header.finality_proof.block;
+		// we really shouldn't set consensus states for headers not in the finalized chain.
+		let new_consensus_states =
+			header_consensus_states(&client_state, &ancestry, header, |relay_hash| {
+				finalized_sorted.binary_search(relay_hash).is_err()
 			})?;
 
-			let (height, consensus_state) = ConsensusState::from_header::<H>(
-				parachain_header_proof,
-				client_state.para_id,
-				header.state_root.clone(),
-			)?;
-
+		for (_, height, consensus_state) in new_consensus_states {
 			// Skip duplicate consensus states
 			if ctx.consensus_state(&client_id, height).is_ok() {
 				continue
@@ -259,7 +288,7 @@ where
 
 		// updates
 		let target = ancestry
-			.header(&header.finality_proof.block)
+			.header(&target_hash)
 			.expect("target header has already been checked in verify_client_message; qed");
 
 		// can't try to rewind relay chain
@@ -289,7 +318,7 @@ where
 			client_state.latest_para_height = *max_height
 		}
 
-		client_state.latest_relay_hash = header.finality_proof.block;
+		client_state.latest_relay_hash = target_hash;
 		client_state.latest_relay_height = target.number;
 
 		if let Some(scheduled_change) = find_scheduled_change(target) {
@@ -334,21 +363,17 @@ where
 		let ancestry =
 			AncestryChain::<RelayChainHeader>::new(&header.finality_proof.unknown_headers);
 
-		for (relay_hash, parachain_header_proof) in header.parachain_headers {
-			let header = ancestry.header(&relay_hash).ok_or_else(|| {
-				Error::Custom(format!("No relay chain header found for hash: {relay_hash:?}"))
-			})?;
+		for (relay_hash, height, consensus_state) in
+			header_consensus_states(&client_state, &ancestry, header, |_| false)?
+		{
+			let header = ancestry
+				.header(&relay_hash)
+				.expect("header has been found by header_consensus_states; qed");
 
 			if find_forced_change(header).is_some() {
 				return Ok(true)
 			}
 
-			let (height, consensus_state) = ConsensusState::from_header::<H>(
-				parachain_header_proof,
-				client_state.para_id,
-				header.state_root.clone(),
-			)?;
-
 			match ctx.maybe_consensus_state(&client_id, height)? {
 				Some(cs) => {
 					let cs: ConsensusState = cs
@@ -536,6 +561,48 @@ where
 		Ok(())
 	}
 
+	fn verify_channel_upgrade<Ctx: ReaderContext>(
+		&self,
+		_ctx: &Ctx,
+		_client_id: &ClientId,
+		client_state: &Self::ClientState,
+		height: Height,
+		prefix: &CommitmentPrefix,
+		proof: &CommitmentProofBytes,
+		root: &CommitmentRoot,
+		port_id: &PortId,
+		channel_id: &ChannelId,
+		expected_upgrade: &Upgrade,
+	) -> Result<(), Ics02Error> {
+		client_state.verify_height(height)?;
+		let path = ChannelUpgradePath(port_id.clone(), *channel_id);
+		let value = expected_upgrade.encode_vec().map_err(Ics02Error::encode)?;
+		verify_membership::<H::BlakeTwo256, _>(prefix, proof, root, path, value)
+			.map_err(Error::Anyhow)?;
+		Ok(())
+	}
+
+	fn verify_channel_upgrade_error<Ctx: ReaderContext>(
+		&self,
+		_ctx: &Ctx,
+		_client_id: &ClientId,
+		client_state: &Self::ClientState,
+		height: Height,
+		prefix: &CommitmentPrefix,
+		proof: &CommitmentProofBytes,
+		root: &CommitmentRoot,
+		port_id: &PortId,
+		channel_id: &ChannelId,
+		expected_error_receipt: &ErrorReceipt,
+	) -> Result<(), Ics02Error> {
+		client_state.verify_height(height)?;
+		let path = ChannelUpgradeErrorPath(port_id.clone(), *channel_id);
+		let value = expected_error_receipt.encode_vec().map_err(Ics02Error::encode)?;
+		verify_membership::<H::BlakeTwo256, _>(prefix, proof, root, path, value)
+			.map_err(Error::Anyhow)?;
+		Ok(())
+	}
+
 	fn verify_client_full_state<Ctx: ReaderContext>(
 		&self,
 		_ctx: &Ctx,
@@ -645,6 +712,60 @@ where
 		Ok(())
 	}
 
+	fn verify_packet_receipt<Ctx: ReaderContext>(
+		&self,
+		ctx: &Ctx,
+		_client_id: &ClientId,
+		client_state: &Self::ClientState,
+		height: Height,
+		connection_end: &ConnectionEnd,
+		proof: &CommitmentProofBytes,
+		root: &CommitmentRoot,
+		port_id: &PortId,
+		channel_id: &ChannelId,
+		sequence: Sequence,
+		receipt: Receipt,
+	) -> Result<(), Ics02Error> {
+		client_state.verify_height(height)?;
+		verify_delay_passed::<H, _>(ctx, height, connection_end).map_err(Error::Anyhow)?;
+
+		let receipt_path =
+			ReceiptsPath { port_id: port_id.clone(), channel_id: *channel_id, sequence };
+		verify_membership::<H::BlakeTwo256, _>(
+			connection_end.counterparty().prefix(),
+			proof,
+			root,
+			receipt_path,
+			receipt.as_bytes().to_vec(),
+		)
+		.map_err(Error::Anyhow)?;
+		Ok(())
+	}
+
+	fn verify_batch_membership<Ctx: ReaderContext>(
+		&self,
+		ctx: &Ctx,
+		_client_id: &ClientId,
+		client_state: &Self::ClientState,
+		height: Height,
+		connection_end: &ConnectionEnd,
+		proof: &CommitmentProofBytes,
+		root: &CommitmentRoot,
+		items: Vec<(Path, Vec<u8>)>,
+	) -> Result<(), Ics02Error> {
+		client_state.verify_height(height)?;
+		verify_delay_passed::<H, _>(ctx, height, connection_end).map_err(Error::Anyhow)?;
+
+		verify_batch_membership::<H::BlakeTwo256>(
+			connection_end.counterparty().prefix(),
+			proof,
+			root,
+			items,
+		)
+		.map_err(Error::Anyhow)?;
+		Ok(())
+	}
+
 	fn verify_packet_receipt_absence<Ctx: ReaderContext>(
 		&self,
 		ctx: &Ctx,
@@ -673,3 +794,264 @@ where
 		Ok(())
 	}
 }
+
+/// Computes the consensus states of the parachain or standalone chain headers carried by `header`,
+/// depending on the chain type of the client. Each consensus state is returned with the hash of
+/// the finalized header it was proven at, headers for which `skip` returns true are ignored.
+fn header_consensus_states<H>(
+	client_state: &ClientState<H>,
+	ancestry: &AncestryChain<RelayChainHeader>,
+	header: GrandpaHeader,
+	skip: impl Fn(&H256) -> bool,
+) -> Result<Vec<(H256, Height, ConsensusState)>, Error>
+where
+	H: grandpa_client_primitives::HostFunctions<Header = RelayChainHeader>,
+{
+	let finalized_header = |hash: &H256| {
+		ancestry
+			.header(hash)
+			.ok_or_else(|| Error::Custom(format!("No relay chain header found for hash: {hash:?}")))
+	};
+
+	match client_state.chain_type {
+		ChainType::Parachain => header
+			.parachain_headers
+			.into_iter()
+			.filter(|(relay_hash, _)| !skip(relay_hash))
+			.map(|(relay_hash, parachain_header_proof)| {
+				let (height, consensus_state) = ConsensusState::from_header::<H>(
+					parachain_header_proof,
+					client_state.para_id,
+					finalized_header(&relay_hash)?.state_root.clone(),
+					client_state.timestamp_proof_type,
+				)?;
+				Ok((relay_hash, height, consensus_state))
+			})
+			.collect(),
+		ChainType::Solochain => header
+			.solochain_headers
+			.into_iter()
+			.filter(|(hash, _)| !skip(hash))
+			.map(|(hash, solochain_header_proof)| {
+				let (height, consensus_state) = ConsensusState::from_solochain_header::<H>(
+					solochain_header_proof,
+					client_state.para_id,
+					finalized_header(&hash)?,
+					client_state.timestamp_proof_type,
+				)?;
+				Ok((hash, height, consensus_state))
+			})
+			.collect(),
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::mock::{state_trie, HostFunctionsManager};
+	use alloc::collections::BTreeMap;
+	use codec::Encode;
+	use finality_grandpa::{Precommit, SignedPrecommit};
+	use grandpa_client_primitives::{Commit, FinalityProof, SolochainHeaderProofs};
+	use ibc::timestamp::Timestamp;
+	use light_client_common::{timestamp_storage_key, TimestampProofType};
+	use sp_consensus_grandpa::{AuthorityId, AuthoritySignature};
+	use sp_core::{ed25519, Pair};
+
+	const ROUND: u64 = 1;
+	const SET_ID: u64 = 0;
+
+	fn timestamp_at(number: u32) -> u64 {
+		1_650_894_363_000 + number as u64 * 6_000
+	}
+
+	/// Builds a state trie that only holds `pallet_timestamp::Now`, returning its root and the
+	/// storage proof of the timestamp.
+	fn timestamp_state(timestamp: u64) -> (H256, Vec<Vec<u8>>) {
+		state_trie(&[(timestamp_storage_key(), timestamp.encode())])
+	}
+
+	fn solochain_header(parent_hash: H256, number: u32) -> RelayChainHeader {
+		RelayChainHeader {
+			parent_hash,
+			number,
+			state_root: timestamp_state(timestamp_at(number)).0,
+			extrinsics_root: Default::default(),
+			digest: Default::default(),
+		}
+	}
+
+	/// Finality proof of the last of `headers`, signed by `authority` alone.
+	fn finality_proof(
+		headers: Vec<RelayChainHeader>,
+		authority: &ed25519::Pair,
+	) -> FinalityProof<RelayChainHeader> {
+		let target = headers.last().unwrap().clone();
+		let precommit = Precommit { target_hash: target.hash(), target_number: target.number };
+		let message = finality_grandpa::Message::Precommit(precommit.clone());
+		let payload = sp_consensus_grandpa::localized_payload(ROUND, SET_ID, &message);
+		let commit = Commit::<RelayChainHeader> {
+			target_hash: target.hash(),
+			target_number: target.number,
+			precommits: vec![SignedPrecommit {
+				precommit,
+				signature: AuthoritySignature::from(authority.sign(&payload)),
+				id: AuthorityId::from(authority.public()),
+			}],
+		};
+		let justification = GrandpaJustification::<RelayChainHeader> {
+			round: ROUND,
+			commit,
+			votes_ancestries: vec![],
+		};
+		FinalityProof {
+			block: target.hash(),
+			justification: justification.encode(),
+			unknown_headers: headers,
+		}
+	}
+
+	/// A solochain client which knows the header at height 1, and an update finalizing the
+	/// headers at heights 2 and 3 with their timestamp storage proofs.
+	fn solochain_update(
+		authority: &ed25519::Pair,
+	) -> (ClientState<HostFunctionsManager>, GrandpaHeader) {
+		let known = solochain_header(H256::zero(), 1);
+		let client_state = ClientState {
+			latest_relay_height: known.number,
+			latest_relay_hash: known.hash(),
+			latest_para_height: known.number,
+			current_authorities: vec![(AuthorityId::from(authority.public()), 1)],
+			chain_type: ChainType::Solochain,
+			timestamp_proof_type: TimestampProofType::Storage,
+			..Default::default()
+		};
+
+		let mut headers = vec![solochain_header(known.hash(), 2)];
+		headers.push(solochain_header(headers[0].hash(), 3));
+		let solochain_headers = headers
+			.iter()
+			.map(|header| {
+				let proofs = SolochainHeaderProofs {
+					extrinsic: vec![],
+					extrinsic_proof: vec![],
+					timestamp_storage_proof: timestamp_state(timestamp_at(header.number)).1,
+				};
+				(header.hash(), proofs)
+			})
+			.collect::<BTreeMap<_, _>>();
+		let header = GrandpaHeader {
+			finality_proof: finality_proof(headers, authority),
+			parachain_headers: Default::default(),
+			solochain_headers,
+			height: Height::new(0, 3),
+		};
+		(client_state, header)
+	}
+
+	fn verify(
+		client_state: &ClientState<HostFunctionsManager>,
+		header: &GrandpaHeader,
+	) -> Result<grandpa_client_primitives::ClientState, grandpa_client_primitives::error::Error> {
+		grandpa_client::verify_solochain_headers_with_grandpa_finality_proof::<
+			RelayChainHeader,
+			HostFunctionsManager,
+		>(
+			client_state.clone().into(),
+			SolochainHeadersWithFinalityProof {
+				finality_proof: header.finality_proof.clone(),
+				solochain_headers: header.solochain_headers.clone(),
+			},
+		)
+	}
+
+	#[test]
+	fn verifies_solochain_headers_with_valid_justification() {
+		let authority = ed25519::Pair::from_seed(&[1; 32]);
+		let (client_state, header) = solochain_update(&authority);
+		let target = header.finality_proof.unknown_headers.last().unwrap().clone();
+
+		let updated = verify(&client_state, &header).unwrap();
+		assert_eq!(updated.latest_relay_hash, target.hash());
+		assert_eq!(updated.latest_relay_height, 3);
+		assert_eq!(updated.latest_para_height, 3);
+		assert_eq!(updated.current_set_id, SET_ID);
+	}
+
+	#[test]
+	fn rejects_solochain_headers_without_valid_justification() {
+		let authority = ed25519::Pair::from_seed(&[1; 32]);
+		let (client_state, header) = solochain_update(&authority);
+
+		// signed by an unknown authority
+		let mut forged = header.clone();
+		forged.finality_proof = finality_proof(
+			header.finality_proof.unknown_headers.clone(),
+			&ed25519::Pair::from_seed(&[2; 32]),
+		);
+		assert!(verify(&client_state, &forged).is_err());
+
+		// a tampered header breaks the ancestry from the known header to the finalized one
+		let mut headers = header.finality_proof.unknown_headers.clone();
+		headers[0].state_root = H256::repeat_byte(0xff);
+		let mut tampered = header.clone();
+		tampered.finality_proof = finality_proof(headers, &authority);
+		assert!(verify(&client_state, &tampered).is_err());
+
+		// the headers don't descend from the header known to the client
+		let unknown_ancestry =
+			ClientState { latest_relay_hash: H256::repeat_byte(0xff), ..client_state.clone() };
+		assert!(verify(&unknown_ancestry, &header).is_err());
+
+		// a timestamp proof that doesn't match the state root of its header
+		let mut wrong_timestamp = header.clone();
+		let hash = header.finality_proof.block;
+		wrong_timestamp
+			.solochain_headers
+			.get_mut(&hash)
+			.unwrap()
+			.timestamp_storage_proof = timestamp_state(timestamp_at(1)).1;
+		assert!(verify(&client_state, &wrong_timestamp).is_err());
+	}
+
+	#[test]
+	fn solochain_consensus_states_are_taken_from_finalized_headers() {
+		let authority = ed25519::Pair::from_seed(&[1; 32]);
+		let (client_state, header) = solochain_update(&authority);
+		let ancestry =
+			AncestryChain::<RelayChainHeader>::new(&header.finality_proof.unknown_headers);
+
+		let consensus_states =
+			header_consensus_states(&client_state, &ancestry, header.clone(), |_| false).unwrap();
+		assert_eq!(consensus_states.len(), 2);
+		for (hash, height, consensus_state) in consensus_states {
+			let finalized = ancestry.header(&hash).unwrap();
+			assert_eq!(height, Height::new(0, finalized.number as u64));
+			assert_eq!(consensus_state.root.as_bytes(), finalized.state_root.as_bytes());
+			assert_eq!(
+				Timestamp::from(consensus_state.timestamp),
+				Timestamp::from_nanoseconds(timestamp_at(finalized.number) * 1_000_000).unwrap()
+			);
+		}
+
+		let target = header.finality_proof.block;
+		let consensus_states =
+			header_consensus_states(&client_state, &ancestry, header.clone(), |hash| {
+				*hash != target
+			})
+			.unwrap();
+		assert_eq!(consensus_states.len(), 1);
+		assert_eq!(consensus_states[0].0, target);
+
+		// the proven timestamp must belong to the state root of the finalized header
+		let mut proofs = header.solochain_headers[&target].clone();
+		proofs.timestamp_storage_proof = timestamp_state(timestamp_at(2)).1;
+		assert!(ConsensusState::from_solochain_header::<HostFunctionsManager>(
+			proofs,
+			client_state.para_id,
+			ancestry.header(&target).unwrap(),
+			client_state.timestamp_proof_type,
+		)
+		.is_err());
+	}
+}
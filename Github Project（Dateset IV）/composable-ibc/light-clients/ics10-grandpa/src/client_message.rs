@@ -23,7 +23,7 @@ use crate::{
 use alloc::{collections::BTreeMap, vec::Vec};
 use anyhow::anyhow;
 use codec::{Decode, Encode};
-use grandpa_client_primitives::{FinalityProof, ParachainHeaderProofs};
+use grandpa_client_primitives::{FinalityProof, ParachainHeaderProofs, SolochainHeaderProofs};
 use ibc::Height;
 use sp_core::H256;
 use sp_runtime::traits::BlakeTwo256;
@@ -47,6 +47,9 @@ pub struct Header {
 	/// finalzed at the relay chain height. We check for this parachain header finalization
 	/// via state proofs. Also contains extrinsic proof for timestamp.
 	pub parachain_headers: BTreeMap<H256, ParachainHeaderProofs>,
+	/// Contains a map of standalone chain header hashes to the timestamp extrinsic proofs of these
+	/// headers, only used by clients of standalone chains.
+	pub solochain_headers: BTreeMap<H256, SolochainHeaderProofs>,
 	/// Lazily initialized height
 	pub height: Height,
 }
@@ -107,16 +110,47 @@ impl TryFrom<RawHeader> for Header {
 				} else {
 					Err(anyhow!("Invalid hash type with length: {}", header.relay_hash.len()))?
 				};
-				let proto::ParachainHeaderProofs { state_proof, extrinsic_proof, extrinsic } =
-					header
-						.parachain_header
-						.ok_or_else(|| anyhow!("Parachain header is required!"))?;
-				let parachain_header_proofs =
-					ParachainHeaderProofs { state_proof, extrinsic, extrinsic_proof };
+				let proto::ParachainHeaderProofs {
+					state_proof,
+					extrinsic_proof,
+					extrinsic,
+					timestamp_storage_proof,
+				} = header
+					.parachain_header
+					.ok_or_else(|| anyhow!("Parachain header is required!"))?;
+				let parachain_header_proofs = ParachainHeaderProofs {
+					state_proof,
+					extrinsic,
+					extrinsic_proof,
+					timestamp_storage_proof,
+				};
 				Ok((block, parachain_header_proofs))
 			})
 			.collect::<Result<_, Error>>()?;
 
+		let solochain_headers = raw_header
+			.solochain_headers
+			.into_iter()
+			.map(|header| {
+				let block = if header.hash.len() == 32 {
+					H256::from_slice(&*header.hash)
+				} else {
+					Err(anyhow!("Invalid hash type with length: {}", header.hash.len()))?
+				};
+				let proto::SolochainHeaderProofs {
+					extrinsic,
+					extrinsic_proof,
+					timestamp_storage_proof,
+				} = header
+					.solochain_header
+					.ok_or_else(|| anyhow!("Solochain header is required!"))?;
+				Ok((
+					block,
+					SolochainHeaderProofs { extrinsic, extrinsic_proof, timestamp_storage_proof },
+				))
+			})
+			.collect::<Result<_, Error>>()?;
+
 		let unknown_headers = finality_proof
 			.unknown_headers
 			.into_iter()
@@ -133,6 +167,7 @@ impl TryFrom<RawHeader> for Header {
 				unknown_headers,
 			},
 			parachain_headers,
+			solochain_headers,
 			height: Height::new(raw_header.para_id as u64, raw_header.para_height as u64),
 		})
 	}
@@ -149,6 +184,19 @@ impl From<Header> for RawHeader {
 					state_proof: parachain_header_proofs.state_proof,
 					extrinsic: parachain_header_proofs.extrinsic,
 					extrinsic_proof: parachain_header_proofs.extrinsic_proof,
+					timestamp_storage_proof: parachain_header_proofs.timestamp_storage_proof,
+				}),
+			})
+			.collect();
+		let solochain_headers = header
+			.solochain_headers
+			.into_iter()
+			.map(|(hash, solochain_header_proofs)| proto::SolochainHeaderWithHash {
+				hash: hash.as_bytes().to_vec(),
+				solochain_header: Some(proto::SolochainHeaderProofs {
+					extrinsic: solochain_header_proofs.extrinsic,
+					extrinsic_proof: solochain_header_proofs.extrinsic_proof,
+					timestamp_storage_proof: solochain_header_proofs.timestamp_storage_proof,
 				}),
 			})
 			.collect();
@@ -168,6 +216,7 @@ impl From<Header> for RawHeader {
 			parachain_headers,
 			para_id: header.height.revision_number as u32,
 			para_height: header.height.revision_height as u32,
+			solochain_headers,
 		}
 	}
 }
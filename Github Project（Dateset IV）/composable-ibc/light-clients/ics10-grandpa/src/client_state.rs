@@ -17,7 +17,10 @@ use crate::{
 	client_def::GrandpaClient,
 	client_message::RelayChainHeader,
 	error::Error,
-	proto::{Authority as RawAuthority, ClientState as RawClientState},
+	proto::{
+		Authority as RawAuthority, ClientState as RawClientState,
+		CustomRelayChain as RawCustomRelayChain,
+	},
 };
 use alloc::{format, string::ToString, vec::Vec};
 use anyhow::anyhow;
@@ -34,7 +37,7 @@ use ibc::{
 	Height,
 };
 use ibc_proto::google::protobuf::Any;
-use light_client_common::RelayChain;
+use light_client_common::{CustomRelayChain, RelayChain, TimestampProofType};
 use serde::{Deserialize, Serialize};
 use sp_consensus_grandpa::AuthorityList;
 use sp_core::{ed25519::Public, H256};
@@ -43,6 +46,28 @@ use tendermint_proto::Protobuf;
 /// Protobuf type url for GRANDPA ClientState
 pub const GRANDPA_CLIENT_STATE_TYPE_URL: &str = "/ibc.lightclients.grandpa.v1.ClientState";
 
+/// Kind of chain tracked by the GRANDPA client.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ChainType {
+	/// A parachain, whose headers are proven through the `Paras::Heads` storage of its relay
+	/// chain.
+	#[default]
+	Parachain,
+	/// A standalone chain, whose own headers are finalized by GRANDPA. The relay chain fields of
+	/// the client state then describe the chain itself.
+	Solochain,
+}
+
+impl ChainType {
+	pub fn from_i32(nr: i32) -> Result<Self, Error> {
+		match nr {
+			0 => Ok(Self::Parachain),
+			1 => Ok(Self::Solochain),
+			id => Err(anyhow!("Unknown chain type {id}"))?,
+		}
+	}
+}
+
 #[derive(PartialEq, Clone, Debug, Default, Eq)]
 pub struct ClientState<H> {
 	/// Relay chain
@@ -61,6 +86,10 @@ pub struct ClientState<H> {
 	pub current_set_id: u64,
 	/// authorities for the current round
 	pub current_authorities: AuthorityList,
+	/// Kind of chain tracked by the client
+	pub chain_type: ChainType,
+	/// How the timestamps of the headers are proven
+	pub timestamp_proof_type: TimestampProofType,
 	/// phantom type.
 	pub _phantom: PhantomData<H>,
 }
@@ -74,6 +103,7 @@ impl<H> From<ClientState<H>> for grandpa_client_primitives::ClientState {
 			latest_relay_height: client_state.latest_relay_height,
 			latest_para_height: client_state.latest_para_height,
 			para_id: client_state.para_id,
+			timestamp_proof_type: client_state.timestamp_proof_type,
 		}
 	}
 }
@@ -242,7 +272,15 @@ impl<H> TryFrom<RawClientState> for ClientState<H> {
 			})
 			.collect::<Result<_, Error>>()?;
 
-		let relay_chain = RelayChain::from_i32(raw.relay_chain)?;
+		let relay_chain = match raw.custom_relay_chain {
+			Some(custom) if raw.relay_chain == RelayChain::CUSTOM =>
+				RelayChain::Custom(CustomRelayChain::new(
+					custom.name,
+					Duration::from_secs(custom.unbonding_period),
+					Duration::from_secs(custom.trusting_period),
+				)?),
+			_ => RelayChain::from_i32(raw.relay_chain)?,
+		};
 		if raw.latest_relay_hash.len() != 32 {
 			Err(anyhow!("Invalid ed25519 public key lenght: {}", raw.latest_relay_hash.len()))?
 		}
@@ -259,6 +297,8 @@ impl<H> TryFrom<RawClientState> for ClientState<H> {
 			current_authorities,
 			latest_relay_hash,
 			latest_relay_height: raw.latest_relay_height,
+			chain_type: ChainType::from_i32(raw.chain_type)?,
+			timestamp_proof_type: TimestampProofType::from_i32(raw.timestamp_proof_type)?,
 			_phantom: Default::default(),
 		})
 	}
@@ -273,7 +313,14 @@ impl<H> From<ClientState<H>> for RawClientState {
 			frozen_height: client_state
 				.frozen_height
 				.map(|frozen_height| frozen_height.revision_height),
-			relay_chain: client_state.relay_chain as i32,
+			relay_chain: client_state.relay_chain.to_i32(),
+			custom_relay_chain: client_state.relay_chain.custom().map(|custom| {
+				RawCustomRelayChain {
+					name: custom.name.clone(),
+					unbonding_period: custom.unbonding_period.as_secs(),
+					trusting_period: custom.trusting_period.as_secs(),
+				}
+			}),
 			para_id: client_state.para_id,
 			latest_para_height: client_state.latest_para_height,
 			current_authorities: client_state
@@ -285,6 +332,157 @@ impl<H> From<ClientState<H>> for RawClientState {
 					weight,
 				})
 				.collect(),
+			chain_type: client_state.chain_type as i32,
+			timestamp_proof_type: client_state.timestamp_proof_type.to_i32(),
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::mock::HostFunctionsManager;
+	use prost::Message;
+
+	/// Client state as encoded before custom relay chains, chain types and timestamp proof types
+	/// were supported.
+	#[derive(Clone, PartialEq, Message)]
+	struct LegacyClientState {
+		#[prost(bytes = "vec", tag = "1")]
+		latest_relay_hash: Vec<u8>,
+		#[prost(uint32, tag = "2")]
+		latest_relay_height: u32,
+		#[prost(uint64, tag = "3")]
+		current_set_id: u64,
+		#[prost(uint64, optional, tag = "4")]
+		frozen_height: Option<u64>,
+		#[prost(int32, tag = "5")]
+		relay_chain: i32,
+		#[prost(uint32, tag = "6")]
+		para_id: u32,
+		#[prost(uint32, tag = "7")]
+		latest_para_height: u32,
+		#[prost(message, repeated, tag = "8")]
+		current_authorities: Vec<RawAuthority>,
+	}
+
+	fn raw_client_state() -> RawClientState {
+		RawClientState {
+			latest_relay_hash: H256::repeat_byte(1).as_bytes().to_vec(),
+			latest_relay_height: 100,
+			current_set_id: 3,
+			frozen_height: None,
+			relay_chain: RelayChain::Rococo.to_i32(),
+			para_id: 2000,
+			latest_para_height: 50,
+			current_authorities: vec![RawAuthority { public_key: vec![7; 32], weight: 1 }],
+			custom_relay_chain: None,
+			chain_type: 0,
+			timestamp_proof_type: 0,
 		}
 	}
+
+	fn custom_relay_chain() -> RawCustomRelayChain {
+		RawCustomRelayChain {
+			name: "westend".to_string(),
+			unbonding_period: 28 * 24 * 60 * 60,
+			trusting_period: 14 * 24 * 60 * 60,
+		}
+	}
+
+	#[test]
+	fn custom_relay_chain_round_trips_through_protobuf() {
+		let raw = RawClientState {
+			relay_chain: RelayChain::CUSTOM,
+			custom_relay_chain: Some(custom_relay_chain()),
+			..raw_client_state()
+		};
+		let client_state = ClientState::<HostFunctionsManager>::try_from(raw.clone()).unwrap();
+		let custom = client_state.relay_chain.custom().unwrap();
+		assert_eq!(custom.name, "westend");
+		assert_eq!(custom.unbonding_period, Duration::from_secs(28 * 24 * 60 * 60));
+		assert_eq!(custom.trusting_period, Duration::from_secs(14 * 24 * 60 * 60));
+		assert_eq!(RawClientState::from(client_state), raw);
+	}
+
+	#[test]
+	fn invalid_custom_relay_chain_is_rejected() {
+		// The parameters of a custom relay chain are required.
+		let raw = RawClientState { relay_chain: RelayChain::CUSTOM, ..raw_client_state() };
+		assert!(ClientState::<HostFunctionsManager>::try_from(raw).is_err());
+
+		let raw = RawClientState {
+			relay_chain: RelayChain::CUSTOM,
+			custom_relay_chain: Some(RawCustomRelayChain {
+				trusting_period: 28 * 24 * 60 * 60,
+				..custom_relay_chain()
+			}),
+			..raw_client_state()
+		};
+		assert!(ClientState::<HostFunctionsManager>::try_from(raw).is_err());
+	}
+
+	#[test]
+	fn decodes_client_state_encoded_before_custom_relay_chains() {
+		let raw = raw_client_state();
+		let legacy = LegacyClientState {
+			latest_relay_hash: raw.latest_relay_hash.clone(),
+			latest_relay_height: raw.latest_relay_height,
+			current_set_id: raw.current_set_id,
+			frozen_height: raw.frozen_height,
+			relay_chain: raw.relay_chain,
+			para_id: raw.para_id,
+			latest_para_height: raw.latest_para_height,
+			current_authorities: raw.current_authorities.clone(),
+		};
+		let decoded = RawClientState::decode(&*legacy.encode_to_vec()).unwrap();
+		assert_eq!(decoded, raw);
+		let client_state = ClientState::<HostFunctionsManager>::try_from(decoded).unwrap();
+		assert_eq!(client_state.relay_chain, RelayChain::Rococo);
+		assert_eq!(client_state.chain_type, ChainType::Parachain);
+		assert_eq!(client_state.timestamp_proof_type, TimestampProofType::Extrinsic);
+		assert_eq!(client_state.current_authorities.len(), 1);
+	}
+
+	#[test]
+	fn timestamp_proof_type_round_trips_through_protobuf() {
+		let raw = RawClientState { timestamp_proof_type: 1, ..raw_client_state() };
+		let client_state = ClientState::<HostFunctionsManager>::try_from(raw.clone()).unwrap();
+		assert_eq!(client_state.timestamp_proof_type, TimestampProofType::Storage);
+		assert_eq!(RawClientState::from(client_state), raw);
+
+		let raw = RawClientState { timestamp_proof_type: 2, ..raw_client_state() };
+		assert!(ClientState::<HostFunctionsManager>::try_from(raw).is_err());
+	}
+
+	#[test]
+	fn chain_type_round_trips_through_protobuf() {
+		let raw = RawClientState { chain_type: 1, ..raw_client_state() };
+		let client_state = ClientState::<HostFunctionsManager>::try_from(raw.clone()).unwrap();
+		assert_eq!(client_state.chain_type, ChainType::Solochain);
+		assert_eq!(RawClientState::from(client_state), raw);
+
+		let raw = RawClientState { chain_type: 2, ..raw_client_state() };
+		assert!(ClientState::<HostFunctionsManager>::try_from(raw).is_err());
+	}
+
+	#[test]
+	fn custom_relay_chain_deserialization_is_validated() {
+		let relay_chain = RelayChain::Custom(
+			CustomRelayChain::new(
+				"westend".to_string(),
+				Duration::from_secs(28 * 24 * 60 * 60),
+				Duration::from_secs(14 * 24 * 60 * 60),
+			)
+			.unwrap(),
+		);
+		let mut value = json::to_value(&relay_chain).unwrap();
+		assert_eq!(json::from_value::<RelayChain>(value.clone()).unwrap(), relay_chain);
+
+		value["Custom"]["trusting_period"] = value["Custom"]["unbonding_period"].clone();
+		assert!(json::from_value::<RelayChain>(value.clone()).is_err());
+
+		value["Custom"]["name"] = json::Value::String(String::new());
+		assert!(json::from_value::<RelayChain>(value).is_err());
+	}
 }
@@ -24,10 +24,14 @@ use tendermint_proto::{google::protobuf as tpb, Protobuf};
 use crate::proto::ConsensusState as RawConsensusState;
 
 use crate::{alloc::string::ToString, error::Error};
-use grandpa_client_primitives::{parachain_header_storage_key, ParachainHeaderProofs};
+use grandpa_client_primitives::{
+	parachain_header_storage_key, ParachainHeaderProofs, SolochainHeaderProofs,
+};
 use ibc::{core::ics23_commitment::commitment::CommitmentRoot, timestamp::Timestamp, Height};
 use ibc_proto::google::protobuf::Any;
-use light_client_common::{decode_timestamp_extrinsic, state_machine};
+use light_client_common::{
+	decode_timestamp_extrinsic, state_machine, verify_timestamp_storage_proof, TimestampProofType,
+};
 use sp_core::H256;
 use sp_runtime::{generic, traits::BlakeTwo256, SaturatedConversion};
 use sp_trie::StorageProof;
@@ -57,6 +61,7 @@ impl ConsensusState {
 		parachain_header_proof: ParachainHeaderProofs,
 		para_id: u32,
 		relay_state_root: H256,
+		timestamp_proof_type: TimestampProofType,
 	) -> Result<(Height, Self), Error>
 	where
 		H: grandpa_client_primitives::HostFunctions,
@@ -77,17 +82,59 @@ impl ConsensusState {
 			generic::Header::<u32, BlakeTwo256>::decode(&mut &parachain_header_bytes[..])?;
 		let root = parachain_header.state_root.0.to_vec();
 
-		let timestamp = decode_timestamp_extrinsic(&parachain_header_proof.extrinsic)?;
-		let duration = core::time::Duration::from_millis(timestamp);
-		let timestamp = Timestamp::from_nanoseconds(duration.as_nanos().saturated_into::<u64>())?
-			.into_tm_time()
-			.ok_or_else(|| anyhow!("Error decoding Timestamp, timestamp cannot be zero"))?;
+		let timestamp = match timestamp_proof_type {
+			TimestampProofType::Extrinsic =>
+				decode_timestamp_extrinsic(&parachain_header_proof.extrinsic)?,
+			TimestampProofType::Storage => verify_timestamp_storage_proof::<H::BlakeTwo256>(
+				&parachain_header.state_root,
+				parachain_header_proof.timestamp_storage_proof,
+			)?,
+		};
+		let timestamp = timestamp_from_millis(timestamp)?;
 
 		Ok((
 			Height::new(para_id as u64, parachain_header.number as u64),
 			Self { root: root.into(), timestamp },
 		))
 	}
+
+	/// Builds the consensus state of a finalized standalone chain header, whose state root is
+	/// taken directly from the header. `revision_number` is the revision of the returned height.
+	pub fn from_solochain_header<H>(
+		solochain_header_proof: SolochainHeaderProofs,
+		revision_number: u32,
+		header: &generic::Header<u32, BlakeTwo256>,
+		timestamp_proof_type: TimestampProofType,
+	) -> Result<(Height, Self), Error>
+	where
+		H: grandpa_client_primitives::HostFunctions,
+	{
+		let root = header.state_root.0.to_vec();
+		let timestamp = match timestamp_proof_type {
+			TimestampProofType::Extrinsic =>
+				decode_timestamp_extrinsic(&solochain_header_proof.extrinsic)?,
+			TimestampProofType::Storage => verify_timestamp_storage_proof::<H::BlakeTwo256>(
+				&header.state_root,
+				solochain_header_proof.timestamp_storage_proof,
+			)?,
+		};
+		let timestamp = timestamp_from_millis(timestamp)?;
+
+		Ok((
+			Height::new(revision_number as u64, header.number as u64),
+			Self { root: root.into(), timestamp },
+		))
+	}
+}
+
+/// Converts a timestamp in milliseconds, as set by `pallet_timestamp`, to a [`Time`].
+fn timestamp_from_millis(timestamp: u64) -> Result<Time, Error> {
+	let duration = core::time::Duration::from_millis(timestamp);
+	let timestamp = Timestamp::from_nanoseconds(duration.as_nanos().saturated_into::<u64>())?
+		.into_tm_time()
+		.ok_or_else(|| anyhow!("Error decoding Timestamp, timestamp cannot be zero"))?;
+
+	Ok(timestamp)
 }
 
 impl ibc::core::ics02_client::client_consensus::ConsensusState for ConsensusState {
@@ -145,3 +192,68 @@ pub mod test_util {
 		})
 	}
 }
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::mock::{state_trie, HostFunctionsManager};
+	use codec::Encode;
+	use light_client_common::timestamp_storage_key;
+
+	const TIMESTAMP: u64 = 1_650_894_363_000;
+
+	#[test]
+	fn builds_consensus_state_from_timestamp_storage_proof() {
+		let para_id = 2000;
+		let (para_state_root, timestamp_storage_proof) =
+			state_trie(&[(timestamp_storage_key(), TIMESTAMP.encode())]);
+		let parachain_header = generic::Header::<u32, BlakeTwo256> {
+			parent_hash: Default::default(),
+			number: 5,
+			state_root: para_state_root,
+			extrinsics_root: Default::default(),
+			digest: Default::default(),
+		};
+		let (relay_state_root, state_proof) = state_trie(&[(
+			parachain_header_storage_key(para_id).as_ref().to_vec(),
+			parachain_header.encode().encode(),
+		)]);
+		let proofs = ParachainHeaderProofs {
+			state_proof,
+			extrinsic: vec![],
+			extrinsic_proof: vec![],
+			timestamp_storage_proof,
+		};
+
+		let (height, consensus_state) = ConsensusState::from_header::<HostFunctionsManager>(
+			proofs.clone(),
+			para_id,
+			relay_state_root,
+			TimestampProofType::Storage,
+		)
+		.unwrap();
+		assert_eq!(height, Height::new(2000, 5));
+		assert_eq!(consensus_state.root.as_bytes(), para_state_root.as_bytes());
+		assert_eq!(
+			Timestamp::from(consensus_state.timestamp),
+			Timestamp::from_nanoseconds(TIMESTAMP * 1_000_000).unwrap()
+		);
+
+		// the timestamp has to be proven against the state root of the parachain header
+		let wrong_timestamp = ParachainHeaderProofs {
+			timestamp_storage_proof: state_trie(&[(
+				timestamp_storage_key(),
+				(TIMESTAMP + 6_000).encode(),
+			)])
+			.1,
+			..proofs
+		};
+		assert!(ConsensusState::from_header::<HostFunctionsManager>(
+			wrong_timestamp,
+			para_id,
+			relay_state_root,
+			TimestampProofType::Storage,
+		)
+		.is_err());
+	}
+}
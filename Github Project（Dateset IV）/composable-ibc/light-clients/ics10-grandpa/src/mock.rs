@@ -45,6 +45,7 @@ use sp_runtime::{
 	app_crypto::RuntimePublic,
 	traits::{BlakeTwo256, Header},
 };
+use sp_trie::{LayoutV0, MemoryDB, TrieDBMutBuilder, TrieMut};
 use std::{cell::RefCell, collections::BTreeSet};
 use tendermint_proto::Protobuf;
 
@@ -185,3 +186,19 @@ impl From<MockHostBlock> for AnyConsensusState {
 		AnyConsensusState::Mock(MockConsensusState::new(header))
 	}
 }
+
+/// Builds a state trie out of `entries`, returning its root and a storage proof of all of its
+/// nodes.
+pub fn state_trie(entries: &[(Vec<u8>, Vec<u8>)]) -> (sp_core::H256, Vec<Vec<u8>>) {
+	let mut db = MemoryDB::<BlakeTwo256>::default();
+	let mut root = Default::default();
+	let root = {
+		let mut trie = TrieDBMutBuilder::<LayoutV0<BlakeTwo256>>::new(&mut db, &mut root).build();
+		for (key, value) in entries {
+			trie.insert(key, value).unwrap();
+		}
+		*trie.root()
+	};
+	let proof = db.drain().into_iter().map(|(_, (node, ..))| node.to_vec()).collect();
+	(root, proof)
+}
@@ -146,6 +146,8 @@ async fn test_continuous_update_of_grandpa_client() {
 			para_id: prover.para_id,
 			current_set_id: client_state.current_set_id,
 			current_authorities: client_state.current_authorities,
+			chain_type: Default::default(),
+			timestamp_proof_type: Default::default(),
 			_phantom: Default::default(),
 		};
 		let subxt_block_number: subxt::rpc::types::BlockNumber = decoded_para_head.number.into();
@@ -170,13 +172,18 @@ async fn test_continuous_update_of_grandpa_client() {
 			.map(|bytes| bytes.0)
 			.collect();
 
-		let header_proof =
-			ParachainHeaderProofs { state_proof, extrinsic: timestamp_extrinsic, extrinsic_proof };
+		let header_proof = ParachainHeaderProofs {
+			state_proof,
+			extrinsic: timestamp_extrinsic,
+			extrinsic_proof,
+			timestamp_storage_proof: vec![],
+		};
 
 		let (_, consensus_state) = ConsensusState::from_header::<HostFunctionsManager>(
 			header_proof,
 			prover.para_id,
 			latest_relay_header.state_root,
+			client_state.timestamp_proof_type,
 		)
 		.unwrap();
 
@@ -255,6 +262,7 @@ async fn test_continuous_update_of_grandpa_client() {
 		let header = Header {
 			finality_proof: proof.finality_proof,
 			parachain_headers: proof.parachain_headers.clone(),
+			solochain_headers: Default::default(),
 			height: Height::new(prover.para_id as u64, finalized_para_header.number as u64),
 		};
 		let msg = MsgUpdateAnyClient {
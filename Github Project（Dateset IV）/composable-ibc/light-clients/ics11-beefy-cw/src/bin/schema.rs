@@ -0,0 +1,10 @@
+// use cosmwasm_schema::write_api;
+// use ics11_beefy_cw::msg::{ExecuteMsg, InstantiateMsg, QueryMsg};
+
+fn main() {
+	// write_api! {
+	// 	instantiate: InstantiateMsg,
+	// 	execute: ExecuteMsg,
+	// 	query: QueryMsg,
+	// }
+}
@@ -0,0 +1,54 @@
+// Copyright (C) 2022 ComposableFi.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::context::Context;
+use beefy_light_client_primitives::HostFunctions;
+use ibc::{
+	core::{
+		ics03_connection::{connection::ConnectionEnd, context::ConnectionReader, error::Error},
+		ics23_commitment::commitment::CommitmentPrefix,
+		ics24_host::identifier::ConnectionId,
+	},
+	Height,
+};
+use std::time::Duration;
+
+impl<'a, H: HostFunctions> ConnectionReader for Context<'a, H> {
+	fn minimum_delay_period(&self) -> Duration {
+		// connection delays are enforced by the host chain, not by the client
+		Duration::ZERO
+	}
+
+	fn connection_end(&self, _conn_id: &ConnectionId) -> Result<ConnectionEnd, Error> {
+		Err(Error::implementation_specific(
+			"'connection_end' is unavailable from the client".to_string(),
+		))
+	}
+
+	fn host_oldest_height(&self) -> Height {
+		Height::zero()
+	}
+
+	fn commitment_prefix(&self) -> CommitmentPrefix {
+		// proofs are verified against the prefix passed along with them in the sudo messages
+		CommitmentPrefix::default()
+	}
+
+	fn connection_counter(&self) -> Result<u64, Error> {
+		Err(Error::implementation_specific(
+			"'connection_counter' is unavailable from the client".to_string(),
+		))
+	}
+}
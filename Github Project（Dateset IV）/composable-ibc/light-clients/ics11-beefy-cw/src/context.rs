@@ -0,0 +1,189 @@
+// Copyright (C) 2022 ComposableFi.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::{
+	ics23::{
+		ClientStates, ConsensusStates, FakeInner, ReadonlyClientStates, ReadonlyConsensusStates,
+	},
+	ContractError,
+};
+use beefy_light_client_primitives::HostFunctions;
+use cosmwasm_std::{Deps, DepsMut, Env, Storage};
+use ibc::{
+	core::{
+		ics02_client::{error::Error, events::Checksum},
+		ics24_host::identifier::ClientId,
+		ics26_routing::context::ReaderContext,
+	},
+	Height,
+};
+use ibc_proto::google::protobuf::Any;
+use ics11_beefy::{client_state::ClientState, consensus_state::ConsensusState};
+use std::{fmt, fmt::Debug, marker::PhantomData};
+
+/// Storage and api access of a [`Context`], queries only get read access.
+pub enum ContextDeps<'a> {
+	Mut(DepsMut<'a>),
+	Ro(Deps<'a>),
+}
+
+pub struct Context<'a, H> {
+	pub deps: ContextDeps<'a>,
+	pub env: Env,
+	pub checksum: Option<Checksum>,
+	_phantom: PhantomData<H>,
+}
+
+impl<'a, H> PartialEq for Context<'a, H> {
+	fn eq(&self, _other: &Self) -> bool {
+		true
+	}
+}
+
+impl<'a, H> Eq for Context<'a, H> {}
+
+impl<'a, H> Debug for Context<'a, H> {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		write!(f, "Context {{ deps: DepsMut }}")
+	}
+}
+
+impl<'a, H> Clone for Context<'a, H> {
+	fn clone(&self) -> Self {
+		panic!("Context is not cloneable")
+	}
+}
+
+impl<'a, H> Context<'a, H> {
+	pub fn new(deps: DepsMut<'a>, env: Env) -> Self {
+		Self { deps: ContextDeps::Mut(deps), _phantom: Default::default(), env, checksum: None }
+	}
+
+	pub fn new_ro(deps: Deps<'a>, env: Env) -> Self {
+		Self { deps: ContextDeps::Ro(deps), _phantom: Default::default(), env, checksum: None }
+	}
+
+	pub fn log(&self, msg: &str) {
+		match &self.deps {
+			ContextDeps::Mut(deps_mut) => deps_mut.api.debug(msg),
+			ContextDeps::Ro(deps) => deps.api.debug(msg),
+		}
+	}
+
+	pub fn storage(&self) -> &dyn Storage {
+		match &self.deps {
+			ContextDeps::Mut(deps_mut) => deps_mut.storage,
+			ContextDeps::Ro(deps) => deps.storage,
+		}
+	}
+
+	pub fn storage_mut(&mut self) -> Result<&mut dyn Storage, Error> {
+		match &mut self.deps {
+			ContextDeps::Mut(deps_mut) => Ok(deps_mut.storage),
+			ContextDeps::Ro(_) => Err(Error::implementation_specific(
+				"[storage_mut]: storage of a read-only context can't be written".to_string(),
+			)),
+		}
+	}
+}
+
+impl<'a, H> Context<'a, H>
+where
+	H: Clone,
+{
+	pub fn consensus_state_prefixed(
+		&self,
+		height: Height,
+		prefix: &[u8],
+	) -> Result<ConsensusState, ContractError> {
+		let bytes = ReadonlyConsensusStates::new(self.storage())
+			.get_prefixed(height, prefix)
+			.ok_or_else(|| {
+				ContractError::Beefy(format!(
+					"no consensus state found for height {height} and prefix {prefix:?}",
+				))
+			})?;
+		Context::<H>::decode_consensus_state(&bytes)
+			.map_err(|e| ContractError::Beefy(format!("error decoding consensus state: {e:?}")))
+	}
+
+	pub fn store_consensus_state_prefixed(
+		&mut self,
+		height: Height,
+		consensus_state: ConsensusState,
+		prefix: &[u8],
+	) -> Result<(), ContractError> {
+		let encoded = Context::<H>::encode_consensus_state(consensus_state);
+		let storage = self.storage_mut().map_err(|e| ContractError::Beefy(e.to_string()))?;
+		let mut consensus_states = ConsensusStates::new(storage);
+		consensus_states.insert_prefixed(height, encoded, prefix);
+		Ok(())
+	}
+
+	pub fn client_state_prefixed(&self, prefix: &[u8]) -> Result<ClientState<H>, ContractError> {
+		let bytes =
+			ReadonlyClientStates::new(self.storage()).get_prefixed(prefix).ok_or_else(|| {
+				ContractError::Beefy(format!("no client state found for prefix {prefix:?}",))
+			})?;
+		Context::decode_client_state(&bytes)
+			.map_err(|e| ContractError::Beefy(format!("error decoding client state: {e:?}")))
+	}
+
+	pub fn store_client_state_prefixed(
+		&mut self,
+		client_state: ClientState<H>,
+		prefix: &[u8],
+		client_id: ClientId,
+	) -> Result<(), ContractError> {
+		use prost::Message;
+		use tendermint_proto::Protobuf;
+		let client_states = ReadonlyClientStates::new(self.storage());
+		let checksum = match self.checksum.clone() {
+			None => {
+				let encoded_wasm_client_state =
+					client_states.get_prefixed(prefix).ok_or_else(|| {
+						ContractError::Beefy(Error::client_not_found(client_id).to_string())
+					})?;
+				let any = Any::decode(&*encoded_wasm_client_state)
+					.map_err(Error::decode)
+					.map_err(|e| ContractError::Beefy(e.to_string()))?;
+				let wasm_client_state = ics08_wasm::client_state::ClientState::<
+					FakeInner,
+					FakeInner,
+					FakeInner,
+				>::decode_vec(&any.value)
+				.map_err(|e| {
+					ContractError::Beefy(
+						Error::implementation_specific(format!(
+								"[client_state]: error decoding client state bytes to WasmConsensusState {}",
+								e
+							))
+						.to_string(),
+					)
+				})?;
+				wasm_client_state.checksum
+			},
+			Some(x) => x,
+		};
+		let encoded = Context::<H>::encode_client_state(client_state, checksum)
+			.map_err(|e| ContractError::Beefy(format!("error encoding client state: {:?}", e)))?;
+		let storage = self.storage_mut().map_err(|e| ContractError::Beefy(e.to_string()))?;
+		let mut client_states = ClientStates::new(storage);
+		client_states.insert_prefixed(encoded, prefix);
+		Ok(())
+	}
+}
+
+impl<'a, H: HostFunctions> ReaderContext for Context<'a, H> {}
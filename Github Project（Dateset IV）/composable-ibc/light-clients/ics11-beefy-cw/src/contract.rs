@@ -0,0 +1,762 @@
+// Copyright (C) 2022 ComposableFi.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::{
+	context::Context,
+	error::ContractError,
+	log,
+	msg::{
+		CheckForMisbehaviourMsg, ClientTypeMsg, ContractResult, ExportMetadataMsg,
+		GetLatestHeightsMsg, QueryMsg, QueryResponse, StatusMsg, SudoMsg, UpdateStateMsg,
+		UpdateStateOnMisbehaviourMsg, VerifyClientMessage, VerifyMembershipMsg,
+		VerifyNonMembershipMsg, VerifyUpgradeAndUpdateStateMsg,
+	},
+	state::{get_client_state, get_consensus_state},
+	Bytes,
+};
+use byteorder::{ByteOrder, LittleEndian};
+use core::hash::Hasher;
+#[cfg(not(feature = "library"))]
+use cosmwasm_std::entry_point;
+use cosmwasm_std::{
+	to_binary, Binary, Deps, DepsMut, Env, MessageInfo, Response, StdError, StdResult,
+};
+use cw_storage_plus::{Item, Map};
+use digest::Digest;
+use ibc::core::{
+	ics02_client::{
+		client_def::{ClientDef, ConsensusUpdateResult},
+		context::{ClientKeeper, ClientReader},
+		height::Height,
+	},
+	ics24_host::identifier::ClientId,
+};
+use ibc_proto::google::protobuf::Any;
+use ics08_wasm::{instantiate::InstantiateMessage, SUBJECT_PREFIX, SUBSTITUTE_PREFIX};
+use ics11_beefy::{
+	client_def::BeefyClient, client_message::ClientMessage, client_state::ClientState,
+	consensus_state::ConsensusState,
+};
+use light_client_common::{verify_membership, verify_non_membership};
+use prost::Message;
+use sp_runtime::traits::BlakeTwo256;
+use sp_runtime_interface::unpack_ptr_and_len;
+use std::{collections::BTreeSet, str::FromStr};
+use tendermint_proto::Protobuf;
+/*
+// version info for migration info
+const CONTRACT_NAME: &str = "crates.io:ics11-beefy-cw";
+const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+*/
+
+pub const CHANNELS_CONNECTION: Map<Bytes, Vec<(Bytes, Bytes)>> = Map::new("channels_connection");
+pub const CLIENT_UPDATE_TIME: Map<(Bytes, Bytes), u64> = Map::new("client_update_time");
+pub const CLIENT_UPDATE_HEIGHT: Map<(Bytes, Bytes), Bytes> = Map::new("client_update_height");
+pub const CHANNEL_COUNTER: Item<u32> = Item::new("channel_counter");
+pub const EXPECTED_BLOCK_TIME: Item<u64> = Item::new("expected_block_time");
+pub const CONNECTION_PREFIX: Item<Vec<u8>> = Item::new("connection_prefix");
+pub const CONNECTION_COUNTER: Item<u32> = Item::new("connection_counter");
+pub const CLIENT_COUNTER: Item<u32> = Item::new("client_counter");
+pub const HOST_CONSENSUS_STATE: Map<u64, ConsensusState> = Map::new("host_consensus_state");
+pub const CONSENSUS_STATES_HEIGHTS: Map<Bytes, BTreeSet<Height>> =
+	Map::new("consensus_states_heights");
+
+#[derive(Clone, Copy, Debug, PartialEq, Default, Eq)]
+pub struct HostFunctions;
+
+impl light_client_common::HostFunctions for HostFunctions {
+	type BlakeTwo256 = BlakeTwo256;
+}
+
+impl beefy_light_client_primitives::HostFunctions for HostFunctions {
+	fn keccak_256(input: &[u8]) -> [u8; 32] {
+		sha3::Keccak256::digest(input).into()
+	}
+
+	fn secp256k1_ecdsa_recover_compressed(
+		signature: &[u8; 65],
+		value: &[u8; 32],
+	) -> Option<Vec<u8>> {
+		use libsecp256k1::{recover, Message, RecoveryId, Signature};
+		let recovery_id = if signature[64] > 26 { signature[64] - 27 } else { signature[64] };
+		let recovery_id = RecoveryId::parse(recovery_id).ok()?;
+		let signature = Signature::parse_overflowing_slice(&signature[..64]).ok()?;
+		let public = recover(&Message::parse(value), &signature, &recovery_id).ok()?;
+		Some(public.serialize_compressed().to_vec())
+	}
+}
+
+fn process_instantiate_msg(
+	msg: InstantiateMessage,
+	ctx: &mut Context<HostFunctions>,
+	client_id: ClientId,
+) -> Result<Binary, ContractError> {
+	let any = Any::decode(&mut msg.client_state.as_slice())?;
+	let client_state = ClientState::decode_vec(&any.value)?;
+	let any = Any::decode(&mut msg.consensus_state.as_slice())?;
+	let consensus_state = ConsensusState::decode_vec(&any.value)?;
+
+	let height = client_state.latest_height();
+	ctx.checksum = Some(msg.checksum);
+	ctx.store_client_state(client_id.clone(), client_state)
+		.map_err(|e| ContractError::Beefy(e.to_string()))?;
+	ctx.store_consensus_state(client_id, height, consensus_state)
+		.map_err(|e| ContractError::Beefy(e.to_string()))?;
+	Ok(to_binary(&ContractResult::success())?)
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn instantiate(
+	deps: DepsMut,
+	env: Env,
+	_info: MessageInfo,
+	msg: InstantiateMessage,
+) -> Result<Response, ContractError> {
+	let client_id = ClientId::from_str(env.contract.address.as_str()).expect("client id is valid");
+	let mut ctx = Context::<HostFunctions>::new(deps, env);
+	let data = process_instantiate_msg(msg, &mut ctx, client_id.clone())?;
+
+	let mut response = Response::default();
+	response.data = Some(data);
+	Ok(response)
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn sudo(deps: DepsMut, env: Env, msg: SudoMsg) -> Result<Response, ContractError> {
+	let client = BeefyClient::<HostFunctions>::default();
+	let client_id = ClientId::from_str(env.contract.address.as_str()).expect("client id is valid");
+	let mut ctx = Context::<HostFunctions>::new(deps, env);
+	let data = process_message(msg, client, &mut ctx, client_id)?;
+	let mut response = Response::default();
+	response.data = Some(data);
+	Ok(response)
+}
+
+fn process_message(
+	msg: SudoMsg,
+	client: BeefyClient<HostFunctions>,
+	ctx: &mut Context<HostFunctions>,
+	client_id: ClientId,
+) -> Result<Binary, ContractError> {
+	// log!(ctx, "process_message: {:?}", msg);
+	let result = match msg {
+		SudoMsg::UpdateStateOnMisbehaviour(msg_raw) => {
+			let client_state =
+				ctx.client_state(&client_id).map_err(|e| ContractError::Beefy(e.to_string()))?;
+			let msg = UpdateStateOnMisbehaviourMsg::try_from(msg_raw)?;
+			client
+				.update_state_on_misbehaviour(client_state, msg.client_message)
+				.map_err(|e| ContractError::Beefy(e.to_string()))
+				.and_then(|cs| {
+					ctx.store_client_state(client_id, cs)
+						.map_err(|e| ContractError::Beefy(e.to_string()))?;
+					Ok(to_binary(&ContractResult::success()))
+				})
+		},
+		SudoMsg::UpdateState(msg_raw) => {
+			let client_state =
+				ctx.client_state(&client_id).map_err(|e| ContractError::Beefy(e.to_string()))?;
+			let msg = UpdateStateMsg::try_from(msg_raw)?;
+			// misbehaviour is handled by `UpdateStateOnMisbehaviour`, `update_state` only takes
+			// headers
+			if matches!(msg.client_message, ClientMessage::Misbehaviour(_)) {
+				return Err(ContractError::Beefy(
+					"misbehaviour can't be used to update the client state".to_string(),
+				))
+			}
+			client
+				.update_state(ctx, client_id.clone(), client_state, msg.client_message)
+				.map_err(|e| ContractError::Beefy(e.to_string()))
+				.and_then(|(cs, cu)| {
+					store_client_and_consensus_states(ctx, client_id.clone(), cs, cu)
+				})
+		},
+		SudoMsg::MigrateClientStore(_msg) => {
+			// load the substitute client state from the combined storage using the appropriate
+			// prefix
+			let subject_client_state = ctx
+				.client_state_prefixed(SUBJECT_PREFIX.as_bytes())
+				.map_err(|e| ContractError::Beefy(e.to_string()))?;
+			let substitute_client_state = ctx
+				.client_state_prefixed(SUBSTITUTE_PREFIX.as_bytes())
+				.map_err(|e| ContractError::Beefy(e.to_string()))?;
+
+			// the substitute must track the same parachain on the same relay chain
+			if subject_client_state.para_id != substitute_client_state.para_id ||
+				subject_client_state.relay_chain != substitute_client_state.relay_chain
+			{
+				return Err(ContractError::Beefy(
+					"subject and substitute client states do not track the same parachain"
+						.to_string(),
+				))
+			}
+
+			let height = substitute_client_state.latest_height();
+			// consensus state should be replaced as well
+			let substitute_consensus_state =
+				ctx.consensus_state_prefixed(height, SUBSTITUTE_PREFIX.as_bytes())?;
+			ctx.store_consensus_state_prefixed(
+				height,
+				substitute_consensus_state,
+				SUBJECT_PREFIX.as_bytes(),
+			)?;
+			ctx.store_client_state_prefixed(
+				substitute_client_state,
+				SUBJECT_PREFIX.as_bytes(),
+				client_id,
+			)
+			.map_err(|e| ContractError::Beefy(e.to_string()))?;
+
+			Ok(()).map(|_| to_binary(&ContractResult::success()))
+		},
+		SudoMsg::VerifyMembership(msg) => {
+			let msg = VerifyMembershipMsg::try_from(msg)?;
+			let consensus_state = ctx
+				.consensus_state(&client_id, msg.height)
+				.map_err(|e| ContractError::Beefy(e.to_string()))?;
+			verify_membership::<BlakeTwo256, _>(
+				&msg.prefix,
+				&msg.proof,
+				&consensus_state.root,
+				msg.path,
+				msg.value,
+			)
+			.map_err(|e| ContractError::Beefy(e.to_string()))
+			.map(|_| to_binary(&ContractResult::success()))
+		},
+		SudoMsg::VerifyNonMembership(msg) => {
+			let msg = VerifyNonMembershipMsg::try_from(msg)?;
+			let consensus_state = ctx
+				.consensus_state(&client_id, msg.height)
+				.map_err(|e| ContractError::Beefy(e.to_string()))?;
+
+			verify_non_membership::<BlakeTwo256, _>(
+				&msg.prefix,
+				&msg.proof,
+				&consensus_state.root,
+				msg.path,
+			)
+			.map_err(|e| ContractError::Beefy(e.to_string()))
+			.map(|_| to_binary(&ContractResult::success()))
+		},
+		SudoMsg::VerifyUpgradeAndUpdateState(msg) => {
+			let old_client_state =
+				ctx.client_state(&client_id).map_err(|e| ContractError::Beefy(e.to_string()))?;
+			let msg: VerifyUpgradeAndUpdateStateMsg<HostFunctions> =
+				VerifyUpgradeAndUpdateStateMsg::try_from(msg)?;
+			client
+				.verify_upgrade_and_update_state(
+					ctx,
+					client_id.clone(),
+					&old_client_state,
+					&msg.upgrade_client_state,
+					&msg.upgrade_consensus_state,
+					msg.proof_upgrade_client,
+					msg.proof_upgrade_consensus_state,
+				)
+				.map_err(|e| ContractError::Beefy(e.to_string()))
+				.and_then(|(cs, cu)| {
+					store_client_and_consensus_states(ctx, client_id.clone(), cs, cu)
+				})
+		},
+	};
+	Ok(result??)
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
+	let client_id = ClientId::from_str(env.contract.address.as_str()).expect("client id is valid");
+	match msg {
+		QueryMsg::CheckForMisbehaviour(msg) => {
+			let ctx = Context::<HostFunctions>::new_ro(deps, env);
+			let client = BeefyClient::<HostFunctions>::default();
+			let client_state =
+				ctx.client_state(&client_id).map_err(|e| ContractError::Beefy(e.to_string()))?;
+			let msg = CheckForMisbehaviourMsg::try_from(msg)?;
+			client
+				.check_for_misbehaviour(&ctx, client_id, client_state, msg.client_message)
+				.map_err(|e| ContractError::Beefy(e.to_string()))
+				.map(|result| to_binary(&QueryResponse::success().misbehaviour(result)))?
+		},
+		QueryMsg::ClientTypeMsg(ClientTypeMsg {}) => to_binary(
+			&QueryResponse::success().client_type(ClientState::<HostFunctions>::client_type()),
+		),
+		QueryMsg::GetLatestHeightsMsg(GetLatestHeightsMsg {}) => {
+			// heights of the stored consensus states, latest first
+			let heights = CONSENSUS_STATES_HEIGHTS
+				.may_load(deps.storage, client_id.as_bytes().to_owned())?
+				.unwrap_or_default();
+			to_binary(&QueryResponse::success().heights(heights.into_iter().rev().collect()))
+		},
+		QueryMsg::ExportMetadata(ExportMetadataMsg {}) =>
+			to_binary(&QueryResponse::success().genesis_metadata(None)),
+		QueryMsg::Status(StatusMsg {}) => {
+			let client_state = match get_client_state::<HostFunctions>(deps, client_id.clone()) {
+				Ok(client_state) => client_state,
+				Err(_) => return to_binary(&QueryResponse::success().status("Unknown".to_string())),
+			};
+
+			if client_state.frozen_height().is_some() {
+				to_binary(&QueryResponse::success().status("Frozen".to_string()))
+			} else {
+				let height = client_state.latest_height();
+				match get_consensus_state(deps, &client_id, height) {
+					Ok(consensus_state_raw) => {
+						let consensus_state =
+							Context::<HostFunctions>::decode_consensus_state(&consensus_state_raw)
+								.map_err(|e| {
+									StdError::serialize_err(e.to_string(), e.to_string())
+								})?;
+						if client_state.expired(core::time::Duration::from_secs(
+							env.block.time.seconds() -
+								consensus_state.timestamp.unix_timestamp() as u64,
+						)) {
+							return to_binary(
+								&QueryResponse::success().status("Expired".to_string()),
+							)
+						}
+						to_binary(&QueryResponse::success().status("Active".to_string()))
+					},
+					Err(_) => to_binary(&QueryResponse::success().status("Expired".to_string())),
+				}
+			}
+		},
+		QueryMsg::TimestampAtHeight(msg) => {
+			let ctx = Context::<HostFunctions>::new_ro(deps, env);
+			let consensus_state = ctx
+				.consensus_state(&client_id, msg.height)
+				.map_err(|e| ContractError::Beefy(e.to_string()))?;
+			to_binary(
+				&QueryResponse::success().timestamp(
+					consensus_state.timestamp.unix_timestamp_nanos().unsigned_abs() as u64,
+				),
+			)
+		},
+		QueryMsg::VerifyClientMessage(msg) => {
+			let ctx = Context::<HostFunctions>::new_ro(deps, env);
+			let client = BeefyClient::<HostFunctions>::default();
+			let client_state =
+				ctx.client_state(&client_id).map_err(|e| ContractError::Beefy(e.to_string()))?;
+			let msg = VerifyClientMessage::try_from(msg)?;
+
+			let f = client
+				.verify_client_message(&ctx, client_id, client_state, msg.client_message)
+				.map_err(|e| ContractError::Beefy(format!("{e:?}")))
+				.map(|_| to_binary(&QueryResponse::success()))?;
+			f
+		},
+	}
+}
+
+fn store_client_and_consensus_states<H>(
+	ctx: &mut Context<H>,
+	client_id: ClientId,
+	client_state: ClientState<H>,
+	consensus_update: ConsensusUpdateResult<Context<H>>,
+) -> Result<StdResult<Binary>, ContractError>
+where
+	H: beefy_light_client_primitives::HostFunctions,
+{
+	let height = client_state.latest_height();
+	let mut heights: Vec<Height> = vec![];
+	match consensus_update {
+		ConsensusUpdateResult::Single(cs) => {
+			log!(ctx, "Storing consensus state: {:?}", height);
+			heights.push(height);
+			ctx.store_consensus_state(client_id.clone(), height, cs)
+				.map_err(|e| ContractError::Beefy(e.to_string()))?;
+		},
+		ConsensusUpdateResult::Batch(css) =>
+			for (height, cs) in css {
+				log!(ctx, "Storing consensus state: {:?}", height);
+				heights.push(height);
+				ctx.store_consensus_state(client_id.clone(), height, cs)
+					.map_err(|e| ContractError::Beefy(e.to_string()))?;
+			},
+	}
+	log!(ctx, "Storing client state with height: {:?}", height);
+	ctx.store_client_state(client_id, client_state)
+		.map_err(|e| ContractError::Beefy(e.to_string()))?;
+	Ok(to_binary(&ContractResult::success().heights(heights)))
+}
+
+// The FFIs below are required because of sp-io dependency that expects the functions to be
+// available on the host.
+#[no_mangle]
+// &[u8] -> [u8; 32]
+pub extern "C" fn ext_hashing_blake2_256_version_1(data: i64) -> i32 {
+	let (ptr, len) = unpack_ptr_and_len(data as _);
+	let data = unsafe { std::slice::from_raw_parts(ptr as *const u8, len as _) };
+	let hash = blake2_rfc::blake2b::blake2b(32, &[], data);
+	let out_ptr = hash.as_ref().to_vec().leak().as_ptr();
+	out_ptr as i32
+}
+
+#[no_mangle]
+// &[u8] -> [u8; 32]
+pub extern "C" fn ext_hashing_keccak_256_version_1(data: i64) -> i32 {
+	let (ptr, len) = unpack_ptr_and_len(data as _);
+	let data = unsafe { std::slice::from_raw_parts(ptr as *const u8, len as _) };
+	let hash =
+		Box::new(<HostFunctions as beefy_light_client_primitives::HostFunctions>::keccak_256(data));
+	let out_ptr = Box::leak(hash).as_ptr();
+	out_ptr as i32
+}
+
+pub fn twox_64_into(data: &[u8], dest: &mut [u8; 8]) {
+	let r0 = twox_hash::XxHash::with_seed(0).chain_update(data).finish();
+	LittleEndian::write_u64(&mut dest[0..8], r0);
+}
+
+pub fn twox_128_into(data: &[u8], dest: &mut [u8; 16]) {
+	let r0 = twox_hash::XxHash::with_seed(0).chain_update(data).finish();
+	let r1 = twox_hash::XxHash::with_seed(1).chain_update(data).finish();
+	LittleEndian::write_u64(&mut dest[0..8], r0);
+	LittleEndian::write_u64(&mut dest[8..16], r1);
+}
+
+#[no_mangle]
+// &[u8] -> [u8; 16]
+pub extern "C" fn ext_hashing_twox_128_version_1(data: i64) -> i32 {
+	let (ptr, len) = unpack_ptr_and_len(data as _);
+	let data = unsafe { std::slice::from_raw_parts(ptr as *const u8, len as _) };
+	let mut hash = Box::new([0u8; 16]);
+	twox_128_into(data, hash.as_mut());
+	let out_ptr = Box::leak(hash).as_ptr();
+	out_ptr as i32
+}
+
+#[no_mangle]
+// &[u8] -> [u8; 8]
+pub extern "C" fn ext_hashing_twox_64_version_1(data: i64) -> i32 {
+	let (ptr, len) = unpack_ptr_and_len(data as _);
+	let data = unsafe { std::slice::from_raw_parts(ptr as *const u8, len as _) };
+	let mut hash = Box::new([0u8; 8]);
+	twox_64_into(data, hash.as_mut());
+	let out_ptr = Box::leak(hash).as_ptr();
+	out_ptr as i32
+}
+
+#[cfg(test)]
+mod tests {
+	use cosmwasm_std::{
+		from_binary,
+		testing::{mock_dependencies, mock_env},
+	};
+	use ibc::core::ics02_client::client_state::ClientState;
+	use ibc_proto::ibc::core::client::v1::Height as HeightRaw;
+	use ics11_beefy::client_message::{BeefyHeader, BEEFY_CLIENT_MESSAGE_TYPE_URL};
+	use tendermint::Time;
+
+	use crate::{
+		ics23::ClientStates,
+		msg::{
+			CheckForMisbehaviourMsgRaw, MerklePath, MigrateClientStoreMsgRaw, TimestampAtHeightMsg,
+			UpdateStateMsgRaw, UpdateStateOnMisbehaviourMsgRaw, VerifyClientMessageRaw,
+			VerifyMembershipMsgRaw, VerifyNonMembershipMsgRaw, VerifyUpgradeAndUpdateStateMsgRaw,
+		},
+	};
+
+	use super::*;
+
+	type BeefyClientState = ics11_beefy::client_state::ClientState<HostFunctions>;
+
+	const PARA_ID: u32 = 2000;
+
+	fn beefy_client_state(latest_para_height: u32) -> BeefyClientState {
+		let mut client_state = BeefyClientState::default();
+		client_state.para_id = PARA_ID;
+		client_state.latest_para_height = latest_para_height;
+		client_state
+	}
+
+	fn beefy_consensus_state(timestamp: i64) -> ConsensusState {
+		ConsensusState::new(vec![1; 32], Time::from_unix_timestamp(timestamp, 0).unwrap())
+	}
+
+	fn encode_client_message(client_message: ics11_beefy::client_message::ClientMessage) -> Bytes {
+		Any {
+			type_url: BEEFY_CLIENT_MESSAGE_TYPE_URL.to_string(),
+			value: client_message.encode_vec().unwrap(),
+		}
+		.encode_to_vec()
+	}
+
+	fn empty_header() -> Bytes {
+		encode_client_message(ics11_beefy::client_message::ClientMessage::Header(BeefyHeader {
+			headers_with_proof: None,
+			mmr_update_proof: None,
+		}))
+	}
+
+	fn misbehaviour() -> Bytes {
+		encode_client_message(ics11_beefy::client_message::ClientMessage::Misbehaviour(()))
+	}
+
+	/// Stores a client tracking [`PARA_ID`] with consensus states at `heights`, returns its
+	/// latest height
+	fn setup_client(deps: DepsMut, env: Env, heights: &[u64]) -> Height {
+		let timestamp = env.block.time.seconds() as i64;
+		let latest_para_height = *heights.iter().max().unwrap();
+		let client_id = ClientId::from_str(env.contract.address.as_str()).unwrap();
+		let mut context = Context::<HostFunctions>::new(deps, env);
+		context.checksum = Some(vec![0; 32]);
+		context
+			.store_client_state(client_id.clone(), beefy_client_state(latest_para_height as _))
+			.unwrap();
+		for revision_height in heights {
+			context
+				.store_consensus_state(
+					client_id.clone(),
+					Height::new(PARA_ID.into(), *revision_height),
+					beefy_consensus_state(timestamp),
+				)
+				.unwrap();
+		}
+		Height::new(PARA_ID.into(), latest_para_height)
+	}
+
+	fn query_response(deps: Deps, msg: QueryMsg) -> QueryResponse {
+		from_binary(&query(deps, mock_env(), msg).unwrap()).unwrap()
+	}
+
+	fn sudo_result(deps: DepsMut, msg: SudoMsg) -> Result<ContractResult, ContractError> {
+		let response = sudo(deps, mock_env(), msg)?;
+		Ok(from_binary(&response.data.unwrap()).unwrap())
+	}
+	#[test]
+	fn test_query() {
+		let mut deps = mock_dependencies();
+		let env = mock_env();
+
+		for (expected, offset) in
+			[("Active", 0i64), ("Expired", env.block.time.seconds() as i64 - 10), ("Frozen", 0i64)]
+		{
+			let mut client_state =
+				ics11_beefy::client_state::ClientState::<HostFunctions>::default();
+			let mut consensus_state = ics11_beefy::consensus_state::ConsensusState::new(
+				vec![],
+				Time::from_unix_timestamp(0, 0).unwrap(),
+			);
+			let height = Height { revision_number: 0, revision_height: 1000 };
+			client_state.latest_para_height = height.revision_height as _;
+
+			consensus_state.timestamp =
+				Time::from_unix_timestamp(env.block.time.seconds() as i64 - offset, 0).unwrap();
+			let deps_mut = deps.as_mut();
+			if expected == "Frozen" {
+				let height =
+					Height { revision_number: 0, revision_height: height.revision_height - 100 };
+				client_state = client_state.with_frozen_height(height.clone()).unwrap();
+			}
+
+			let mut client_states = ClientStates::new(deps_mut.storage);
+			client_states.insert(client_state.encode_to_vec().unwrap());
+
+			let mut context = Context::new(deps_mut, env.clone());
+			context.store_client_state(ClientId::default(), client_state).unwrap();
+			context
+				.store_consensus_state(ClientId::default(), height, consensus_state)
+				.unwrap();
+
+			let resp = query(deps.as_ref(), mock_env(), QueryMsg::Status(StatusMsg {})).unwrap();
+
+			let resp: QueryResponse = from_binary(&resp).unwrap();
+
+			assert_eq!(resp, QueryResponse::success().status(expected.to_string()));
+		}
+	}
+
+	#[test]
+	fn test_query_client_type_and_latest_heights() {
+		let mut deps = mock_dependencies();
+		setup_client(deps.as_mut(), mock_env(), &[1000, 1001]);
+
+		let resp = query_response(deps.as_ref(), QueryMsg::ClientTypeMsg(ClientTypeMsg {}));
+		assert_eq!(resp, QueryResponse::success().client_type("11-beefy".to_string()));
+
+		let resp =
+			query_response(deps.as_ref(), QueryMsg::GetLatestHeightsMsg(GetLatestHeightsMsg {}));
+		let heights = vec![Height::new(PARA_ID.into(), 1001), Height::new(PARA_ID.into(), 1000)];
+		assert_eq!(resp, QueryResponse::success().heights(heights));
+	}
+
+	#[test]
+	fn test_query_export_metadata_and_timestamp_at_height() {
+		let mut deps = mock_dependencies();
+		let env = mock_env();
+		let height = setup_client(deps.as_mut(), env.clone(), &[1000]);
+
+		let resp = query_response(deps.as_ref(), QueryMsg::ExportMetadata(ExportMetadataMsg {}));
+		assert_eq!(resp, QueryResponse::success().genesis_metadata(None));
+
+		let resp = query_response(
+			deps.as_ref(),
+			QueryMsg::TimestampAtHeight(TimestampAtHeightMsg { height }),
+		);
+		assert_eq!(
+			resp,
+			QueryResponse::success().timestamp(env.block.time.seconds() * 1_000_000_000)
+		);
+
+		let unknown_height = Height::new(PARA_ID.into(), 999);
+		let msg = QueryMsg::TimestampAtHeight(TimestampAtHeightMsg { height: unknown_height });
+		assert!(query(deps.as_ref(), mock_env(), msg).is_err());
+	}
+
+	#[test]
+	fn test_query_client_messages() {
+		let mut deps = mock_dependencies();
+		setup_client(deps.as_mut(), mock_env(), &[1000]);
+
+		// a header without mmr update or parachain headers carries nothing to verify
+		let msg = VerifyClientMessageRaw { client_message: empty_header() };
+		let resp = query_response(deps.as_ref(), QueryMsg::VerifyClientMessage(msg));
+		assert_eq!(resp, QueryResponse::success());
+
+		let msg = VerifyClientMessageRaw { client_message: misbehaviour() };
+		assert!(query(deps.as_ref(), mock_env(), QueryMsg::VerifyClientMessage(msg)).is_err());
+
+		let msg = CheckForMisbehaviourMsgRaw { client_message: empty_header() };
+		let resp = query_response(deps.as_ref(), QueryMsg::CheckForMisbehaviour(msg));
+		assert_eq!(resp, QueryResponse::success().misbehaviour(false));
+
+		let client_message =
+			Any { type_url: "/unknown".to_string(), value: vec![] }.encode_to_vec();
+		let msg = CheckForMisbehaviourMsgRaw { client_message };
+		assert!(query(deps.as_ref(), mock_env(), QueryMsg::CheckForMisbehaviour(msg)).is_err());
+	}
+
+	#[test]
+	fn test_sudo_update_state() {
+		let mut deps = mock_dependencies();
+		let height = setup_client(deps.as_mut(), mock_env(), &[1000]);
+
+		let msg = UpdateStateMsgRaw { client_message: empty_header() };
+		let result = sudo_result(deps.as_mut(), SudoMsg::UpdateState(msg)).unwrap();
+		assert_eq!(result, ContractResult::success().heights(vec![]));
+		let client_state = get_client_state::<HostFunctions>(deps.as_ref(), ClientId::default());
+		assert_eq!(client_state.unwrap().latest_height(), height);
+
+		let msg = UpdateStateMsgRaw { client_message: misbehaviour() };
+		assert!(sudo_result(deps.as_mut(), SudoMsg::UpdateState(msg)).is_err());
+	}
+
+	#[test]
+	fn test_sudo_update_state_on_misbehaviour() {
+		let mut deps = mock_dependencies();
+		let height = setup_client(deps.as_mut(), mock_env(), &[1000]);
+
+		let msg = UpdateStateOnMisbehaviourMsgRaw { client_message: misbehaviour() };
+		let result = sudo_result(deps.as_mut(), SudoMsg::UpdateStateOnMisbehaviour(msg)).unwrap();
+		assert_eq!(result, ContractResult::success());
+
+		let client_state = get_client_state::<HostFunctions>(deps.as_ref(), ClientId::default());
+		assert_eq!(client_state.unwrap().frozen_height(), Some(height));
+		let resp = query_response(deps.as_ref(), QueryMsg::Status(StatusMsg {}));
+		assert_eq!(resp, QueryResponse::success().status("Frozen".to_string()));
+	}
+
+	#[test]
+	fn test_sudo_verify_membership_rejects_invalid_proofs() {
+		let mut deps = mock_dependencies();
+		let height = setup_client(deps.as_mut(), mock_env(), &[1000]);
+		let path = MerklePath {
+			key_path: vec!["ibc/".to_string(), "clients/07-tendermint-0/clientState".to_string()],
+		};
+		let height = HeightRaw {
+			revision_number: height.revision_number,
+			revision_height: height.revision_height,
+		};
+
+		let msg = VerifyMembershipMsgRaw {
+			proof: vec![1, 2, 3],
+			path: path.clone(),
+			value: vec![1],
+			height: height.clone(),
+			delay_block_period: 0,
+			delay_time_period: 0,
+		};
+		assert!(sudo_result(deps.as_mut(), SudoMsg::VerifyMembership(msg)).is_err());
+
+		let msg = VerifyNonMembershipMsgRaw {
+			proof: vec![1, 2, 3],
+			path,
+			height,
+			delay_block_period: 0,
+			delay_time_period: 0,
+		};
+		assert!(sudo_result(deps.as_mut(), SudoMsg::VerifyNonMembership(msg)).is_err());
+	}
+
+	#[test]
+	fn test_sudo_verify_upgrade_is_unsupported() {
+		let mut deps = mock_dependencies();
+		let env = mock_env();
+		setup_client(deps.as_mut(), env.clone(), &[1000]);
+
+		let msg = VerifyUpgradeAndUpdateStateMsgRaw {
+			upgrade_client_state: beefy_client_state(1001).to_any().encode_to_vec(),
+			upgrade_consensus_state: beefy_consensus_state(env.block.time.seconds() as i64)
+				.to_any()
+				.encode_to_vec(),
+			proof_upgrade_client: vec![],
+			proof_upgrade_consensus_state: vec![],
+		};
+		assert!(sudo_result(deps.as_mut(), SudoMsg::VerifyUpgradeAndUpdateState(msg)).is_err());
+	}
+
+	#[test]
+	fn test_sudo_migrate_client_store() {
+		let mut deps = mock_dependencies();
+		let timestamp = mock_env().block.time.seconds() as i64;
+		let substitute_height = Height::new(PARA_ID.into(), 1500);
+		let mut context = Context::<HostFunctions>::new(deps.as_mut(), mock_env());
+		context.checksum = Some(vec![0; 32]);
+		let store_client = |context: &mut Context<HostFunctions>, client_state, prefix: &str| {
+			context
+				.store_client_state_prefixed(client_state, prefix.as_bytes(), ClientId::default())
+				.unwrap();
+		};
+		store_client(&mut context, beefy_client_state(1000), SUBJECT_PREFIX);
+		store_client(&mut context, beefy_client_state(1500), SUBSTITUTE_PREFIX);
+		context
+			.store_consensus_state_prefixed(
+				substitute_height,
+				beefy_consensus_state(timestamp),
+				SUBSTITUTE_PREFIX.as_bytes(),
+			)
+			.unwrap();
+
+		let msg = SudoMsg::MigrateClientStore(MigrateClientStoreMsgRaw {});
+		assert_eq!(sudo_result(deps.as_mut(), msg).unwrap(), ContractResult::success());
+
+		let context = Context::<HostFunctions>::new_ro(deps.as_ref(), mock_env());
+		let subject = context.client_state_prefixed(SUBJECT_PREFIX.as_bytes()).unwrap();
+		assert_eq!(subject.latest_height(), substitute_height);
+		let consensus_state = context
+			.consensus_state_prefixed(substitute_height, SUBJECT_PREFIX.as_bytes())
+			.unwrap();
+		assert_eq!(consensus_state, beefy_consensus_state(timestamp));
+
+		// the substitute must track the same parachain
+		let mut context = Context::<HostFunctions>::new(deps.as_mut(), mock_env());
+		let mut other_parachain = beefy_client_state(2000);
+		other_parachain.para_id = PARA_ID + 1;
+		store_client(&mut context, other_parachain, SUBSTITUTE_PREFIX);
+		let msg = SudoMsg::MigrateClientStore(MigrateClientStoreMsgRaw {});
+		assert!(sudo_result(deps.as_mut(), msg).is_err());
+	}
+}
@@ -38,22 +38,27 @@ use ibc::{
 		ics04_channel::{
 			channel::ChannelEnd,
 			commitment::{AcknowledgementCommitment, PacketCommitment},
-			packet::Sequence,
+			packet::{Receipt, Sequence},
+			upgrade::{ErrorReceipt, Upgrade},
 		},
 		ics23_commitment::commitment::{CommitmentPrefix, CommitmentProofBytes, CommitmentRoot},
 		ics24_host::{
 			identifier::{ChannelId, ClientId, ConnectionId, PortId},
 			path::{
-				AcksPath, ChannelEndsPath, ClientConsensusStatePath, ClientStatePath,
-				CommitmentsPath, ConnectionsPath, ReceiptsPath, SeqRecvsPath,
+				AcksPath, ChannelEndsPath, ChannelUpgradeErrorPath, ChannelUpgradePath,
+				ClientConsensusStatePath, ClientStatePath, CommitmentsPath, ConnectionsPath,
+				ReceiptsPath, SeqRecvsPath,
 			},
+			Path,
 		},
 		ics26_routing::context::ReaderContext,
 	},
 	prelude::*,
 	Height,
 };
-use light_client_common::{verify_delay_passed, verify_membership, verify_non_membership};
+use light_client_common::{
+	verify_batch_membership, verify_delay_passed, verify_membership, verify_non_membership,
+};
 
 #[derive(Clone, Debug, PartialEq, Eq, Default)]
 pub struct BeefyClient<T>(PhantomData<T>);
@@ -104,6 +109,7 @@ where
 							heads_total_count: header.heads_total_count,
 							extrinsic_proof: header.extrinsic_proof,
 							timestamp_extrinsic: header.timestamp_extrinsic,
+							timestamp_storage_proof: header.timestamp_storage_proof,
 						})
 						.collect::<Vec<_>>();
 
@@ -125,11 +131,17 @@ where
 					beefy_client::verify_parachain_headers::<H>(
 						light_client_state,
 						parachain_update_proof,
+						client_state.timestamp_proof_type,
 					)
 					.map_err(Error::from)?
 				}
 			},
-			ClientMessage::Misbehaviour(_) => unimplemented!(),
+			// Misbehaviour is reported with a header carrying competing consensus states, see
+			// `check_for_misbehaviour`
+			ClientMessage::Misbehaviour(_) =>
+				return Err(
+					Error::Custom("Beefy misbehaviour messages are not supported".into()).into()
+				),
 		}
 		Ok(())
 	}
@@ -170,7 +182,11 @@ where
 				parachain_cs_states.push((
 					height,
 					Ctx::AnyConsensusState::wrap(
-						&ConsensusState::from_header(header).map_err(Error::from)?,
+						&ConsensusState::from_header::<H>(
+							header,
+							client_state.timestamp_proof_type,
+						)
+						.map_err(Error::from)?,
 					)
 					.ok_or_else(|| Error::Custom("Ctx::AnyConsensusState".to_string()))?,
 				))
@@ -210,8 +226,11 @@ where
 							header.parachain_header.number as u64,
 						);
 
-						let consensus_state =
-							ConsensusState::from_header(header).map_err(Error::from)?;
+						let consensus_state = ConsensusState::from_header::<H>(
+							header,
+							client_state.timestamp_proof_type,
+						)
+						.map_err(Error::from)?;
 						match ctx.maybe_consensus_state(&client_id, height)? {
 							Some(cs) => {
 								let cs: ConsensusState =
@@ -327,6 +346,48 @@ where
 		Ok(())
 	}
 
+	fn verify_channel_upgrade<Ctx: ReaderContext>(
+		&self,
+		_ctx: &Ctx,
+		_client_id: &ClientId,
+		client_state: &Self::ClientState,
+		height: Height,
+		prefix: &CommitmentPrefix,
+		proof: &CommitmentProofBytes,
+		root: &CommitmentRoot,
+		port_id: &PortId,
+		channel_id: &ChannelId,
+		expected_upgrade: &Upgrade,
+	) -> Result<(), Ics02Error> {
+		client_state.verify_height(height)?;
+		let path = ChannelUpgradePath(port_id.clone(), *channel_id);
+		let value = expected_upgrade.encode_vec().map_err(Ics02Error::encode)?;
+		verify_membership::<H::BlakeTwo256, _>(prefix, proof, root, path, value)
+			.map_err(Error::Anyhow)?;
+		Ok(())
+	}
+
+	fn verify_channel_upgrade_error<Ctx: ReaderContext>(
+		&self,
+		_ctx: &Ctx,
+		_client_id: &ClientId,
+		client_state: &Self::ClientState,
+		height: Height,
+		prefix: &CommitmentPrefix,
+		proof: &CommitmentProofBytes,
+		root: &CommitmentRoot,
+		port_id: &PortId,
+		channel_id: &ChannelId,
+		expected_error_receipt: &ErrorReceipt,
+	) -> Result<(), Ics02Error> {
+		client_state.verify_height(height)?;
+		let path = ChannelUpgradeErrorPath(port_id.clone(), *channel_id);
+		let value = expected_error_receipt.encode_vec().map_err(Ics02Error::encode)?;
+		verify_membership::<H::BlakeTwo256, _>(prefix, proof, root, path, value)
+			.map_err(Error::Anyhow)?;
+		Ok(())
+	}
+
 	fn verify_client_full_state<Ctx: ReaderContext>(
 		&self,
 		_ctx: &Ctx,
@@ -436,6 +497,60 @@ where
 		Ok(())
 	}
 
+	fn verify_packet_receipt<Ctx: ReaderContext>(
+		&self,
+		ctx: &Ctx,
+		_client_id: &ClientId,
+		client_state: &Self::ClientState,
+		height: Height,
+		connection_end: &ConnectionEnd,
+		proof: &CommitmentProofBytes,
+		root: &CommitmentRoot,
+		port_id: &PortId,
+		channel_id: &ChannelId,
+		sequence: Sequence,
+		receipt: Receipt,
+	) -> Result<(), Ics02Error> {
+		client_state.verify_height(height)?;
+		verify_delay_passed::<H, _>(ctx, height, connection_end).map_err(Error::Anyhow)?;
+
+		let receipt_path =
+			ReceiptsPath { port_id: port_id.clone(), channel_id: *channel_id, sequence };
+		verify_membership::<H::BlakeTwo256, _>(
+			connection_end.counterparty().prefix(),
+			proof,
+			root,
+			receipt_path,
+			receipt.as_bytes().to_vec(),
+		)
+		.map_err(Error::Anyhow)?;
+		Ok(())
+	}
+
+	fn verify_batch_membership<Ctx: ReaderContext>(
+		&self,
+		ctx: &Ctx,
+		_client_id: &ClientId,
+		client_state: &Self::ClientState,
+		height: Height,
+		connection_end: &ConnectionEnd,
+		proof: &CommitmentProofBytes,
+		root: &CommitmentRoot,
+		items: Vec<(Path, Vec<u8>)>,
+	) -> Result<(), Ics02Error> {
+		client_state.verify_height(height)?;
+		verify_delay_passed::<H, _>(ctx, height, connection_end).map_err(Error::Anyhow)?;
+
+		verify_batch_membership::<H::BlakeTwo256>(
+			connection_end.counterparty().prefix(),
+			proof,
+			root,
+			items,
+		)
+		.map_err(Error::Anyhow)?;
+		Ok(())
+	}
+
 	fn verify_packet_receipt_absence<Ctx: ReaderContext>(
 		&self,
 		ctx: &Ctx,
@@ -93,6 +93,8 @@ pub struct ParachainHeader {
 	pub extrinsic_proof: Vec<Vec<u8>>,
 	/// this already encodes the actual extrinsic
 	pub timestamp_extrinsic: Vec<u8>,
+	/// Storage proof of `pallet_timestamp::Now` against the state root of the parachain header
+	pub timestamp_storage_proof: Vec<Vec<u8>>,
 }
 
 pub fn split_leaf_version(version: u8) -> (u8, u8) {
@@ -129,7 +131,7 @@ impl TryFrom<RawClientMessage> for ClientMessage {
 									})?;
 								let parent_hash =
 									H256::decode(&mut mmr_partial_leaf.parent_hash.as_slice())
-										.unwrap();
+										.map_err(|e| Error::Custom(format!("{e}")))?;
 								let beefy_next_authority_set = if let Some(next_set) =
 									mmr_partial_leaf.beefy_next_authority_set
 								{
@@ -179,6 +181,8 @@ impl TryFrom<RawClientMessage> for ClientMessage {
 									heads_total_count: raw_para_header.heads_total_count,
 									extrinsic_proof: raw_para_header.extrinsic_proof,
 									timestamp_extrinsic: raw_para_header.timestamp_extrinsic,
+									timestamp_storage_proof: raw_para_header
+										.timestamp_storage_proof,
 								})
 							})
 							.collect::<Result<Vec<_>, Error>>()
@@ -288,7 +292,10 @@ impl TryFrom<RawClientMessage> for ClientMessage {
 						},
 						mmr_proof: Proof {
 							leaf_indices: vec![mmr_update.mmr_leaf_index], // TODO(blas): fix this
-							leaf_count: mmr_update.mmr_leaf_index + 1,
+							leaf_count: mmr_update
+								.mmr_leaf_index
+								.checked_add(1)
+								.ok_or_else(|| Error::Custom(format!("Invalid mmr leaf index")))?,
 							items: mmr_update
 								.mmr_proof
 								.into_iter()
@@ -378,6 +385,7 @@ impl From<ClientMessage> for RawClientMessage {
 								heads_total_count: para_header.heads_total_count,
 								extrinsic_proof: para_header.extrinsic_proof,
 								timestamp_extrinsic: para_header.timestamp_extrinsic,
+								timestamp_storage_proof: para_header.timestamp_storage_proof,
 							})
 							.collect();
 						ConsensusStateUpdateProof {
@@ -23,7 +23,9 @@ use sp_core::H256;
 use sp_runtime::SaturatedConversion;
 use tendermint_proto::Protobuf;
 
-use crate::proto::{BeefyAuthoritySet, ClientState as RawClientState};
+use crate::proto::{
+	BeefyAuthoritySet, ClientState as RawClientState, CustomRelayChain as RawCustomRelayChain,
+};
 
 use crate::{client_message::BeefyHeader, error::Error};
 
@@ -33,7 +35,7 @@ use ibc::{
 	timestamp::Timestamp,
 	Height,
 };
-use light_client_common::RelayChain;
+use light_client_common::{CustomRelayChain, RelayChain, TimestampProofType};
 
 /// Protobuf type url for Beefy ClientState
 pub const BEEFY_CLIENT_STATE_TYPE_URL: &str = "/ibc.lightclients.beefy.v1.ClientState";
@@ -58,6 +60,8 @@ pub struct ClientState<H> {
 	pub authority: BeefyNextAuthoritySet<H256>,
 	/// authorities for the next round
 	pub next_authority_set: BeefyNextAuthoritySet<H256>,
+	/// How the timestamps of the parachain headers are proven
+	pub timestamp_proof_type: TimestampProofType,
 	/// Phantom type
 	pub _phantom: PhantomData<H>,
 }
@@ -93,6 +97,7 @@ impl<H: Clone> ClientState<H> {
 			relay_chain,
 			latest_para_height,
 			para_id,
+			timestamp_proof_type: Default::default(),
 			_phantom: PhantomData,
 		})
 	}
@@ -293,7 +298,15 @@ impl<H> TryFrom<RawClientState> for ClientState<H> {
 			.ok_or_else(|| Error::Custom(format!("Next authority set is missing")))?;
 
 		let mmr_root_hash = H256::decode(&mut &*raw.mmr_root_hash)?;
-		let relay_chain = RelayChain::from_i32(raw.relay_chain)?;
+		let relay_chain = match raw.custom_relay_chain {
+			Some(custom) if raw.relay_chain == RelayChain::CUSTOM =>
+				RelayChain::Custom(CustomRelayChain::new(
+					custom.name,
+					Duration::from_secs(custom.unbonding_period),
+					Duration::from_secs(custom.trusting_period),
+				)?),
+			_ => RelayChain::from_i32(raw.relay_chain)?,
+		};
 		let chain_id = ChainId::new(relay_chain.to_string(), raw.para_id.into());
 
 		Ok(Self {
@@ -306,6 +319,7 @@ impl<H> TryFrom<RawClientState> for ClientState<H> {
 			relay_chain,
 			latest_para_height: raw.latest_para_height,
 			para_id: raw.para_id,
+			timestamp_proof_type: TimestampProofType::from_i32(raw.timestamp_proof_type)?,
 			_phantom: Default::default(),
 		})
 	}
@@ -329,9 +343,17 @@ impl<H> From<ClientState<H>> for RawClientState {
 				len: client_state.next_authority_set.len,
 				authority_root: client_state.next_authority_set.root.encode(),
 			}),
-			relay_chain: client_state.relay_chain as i32,
+			relay_chain: client_state.relay_chain.to_i32(),
+			custom_relay_chain: client_state.relay_chain.custom().map(|custom| {
+				RawCustomRelayChain {
+					name: custom.name.clone(),
+					unbonding_period: custom.unbonding_period.as_secs(),
+					trusting_period: custom.trusting_period.as_secs(),
+				}
+			}),
 			para_id: client_state.para_id,
 			latest_para_height: client_state.latest_para_height,
+			timestamp_proof_type: client_state.timestamp_proof_type.to_i32(),
 		}
 	}
 }
@@ -356,3 +378,127 @@ pub mod test_util {
 		)
 	}
 }
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::mock::HostFunctionsManager;
+	use prost::Message;
+
+	/// Client state as encoded before custom relay chains and timestamp proof types were supported.
+	#[derive(Clone, PartialEq, Message)]
+	struct LegacyClientState {
+		#[prost(bytes = "vec", tag = "1")]
+		mmr_root_hash: Vec<u8>,
+		#[prost(uint32, tag = "2")]
+		latest_beefy_height: u32,
+		#[prost(uint64, optional, tag = "3")]
+		frozen_height: Option<u64>,
+		#[prost(int32, tag = "4")]
+		relay_chain: i32,
+		#[prost(uint32, tag = "5")]
+		para_id: u32,
+		#[prost(uint32, tag = "6")]
+		latest_para_height: u32,
+		#[prost(message, optional, tag = "8")]
+		authority: Option<BeefyAuthoritySet>,
+		#[prost(message, optional, tag = "9")]
+		next_authority_set: Option<BeefyAuthoritySet>,
+	}
+
+	fn raw_client_state() -> RawClientState {
+		RawClientState {
+			mmr_root_hash: H256::repeat_byte(1).encode(),
+			latest_beefy_height: 100,
+			frozen_height: None,
+			relay_chain: RelayChain::Rococo.to_i32(),
+			para_id: 2000,
+			latest_para_height: 50,
+			authority: Some(BeefyAuthoritySet {
+				id: 3,
+				len: 5,
+				authority_root: H256::repeat_byte(2).encode(),
+			}),
+			next_authority_set: Some(BeefyAuthoritySet {
+				id: 4,
+				len: 5,
+				authority_root: H256::repeat_byte(3).encode(),
+			}),
+			custom_relay_chain: None,
+			timestamp_proof_type: 0,
+		}
+	}
+
+	fn custom_relay_chain() -> RawCustomRelayChain {
+		RawCustomRelayChain {
+			name: "westend".to_string(),
+			unbonding_period: 28 * 24 * 60 * 60,
+			trusting_period: 14 * 24 * 60 * 60,
+		}
+	}
+
+	#[test]
+	fn custom_relay_chain_round_trips_through_protobuf() {
+		let raw = RawClientState {
+			relay_chain: RelayChain::CUSTOM,
+			custom_relay_chain: Some(custom_relay_chain()),
+			..raw_client_state()
+		};
+		let client_state = ClientState::<HostFunctionsManager>::try_from(raw.clone()).unwrap();
+		let custom = client_state.relay_chain.custom().unwrap();
+		assert_eq!(custom.name, "westend");
+		assert_eq!(custom.unbonding_period, Duration::from_secs(28 * 24 * 60 * 60));
+		assert_eq!(custom.trusting_period, Duration::from_secs(14 * 24 * 60 * 60));
+		assert_eq!(client_state.chain_id, ChainId::new("westend".to_string(), 2000));
+		assert_eq!(RawClientState::from(client_state), raw);
+	}
+
+	#[test]
+	fn invalid_custom_relay_chain_is_rejected() {
+		// The parameters of a custom relay chain are required.
+		let raw = RawClientState { relay_chain: RelayChain::CUSTOM, ..raw_client_state() };
+		assert!(ClientState::<HostFunctionsManager>::try_from(raw).is_err());
+
+		let raw = RawClientState {
+			relay_chain: RelayChain::CUSTOM,
+			custom_relay_chain: Some(RawCustomRelayChain {
+				name: String::new(),
+				..custom_relay_chain()
+			}),
+			..raw_client_state()
+		};
+		assert!(ClientState::<HostFunctionsManager>::try_from(raw).is_err());
+	}
+
+	#[test]
+	fn decodes_client_state_encoded_before_custom_relay_chains() {
+		let raw = raw_client_state();
+		let legacy = LegacyClientState {
+			mmr_root_hash: raw.mmr_root_hash.clone(),
+			latest_beefy_height: raw.latest_beefy_height,
+			frozen_height: raw.frozen_height,
+			relay_chain: raw.relay_chain,
+			para_id: raw.para_id,
+			latest_para_height: raw.latest_para_height,
+			authority: raw.authority.clone(),
+			next_authority_set: raw.next_authority_set.clone(),
+		};
+		let decoded = RawClientState::decode(&*legacy.encode_to_vec()).unwrap();
+		assert_eq!(decoded, raw);
+		let client_state = ClientState::<HostFunctionsManager>::try_from(decoded).unwrap();
+		assert_eq!(client_state.relay_chain, RelayChain::Rococo);
+		assert_eq!(client_state.timestamp_proof_type, TimestampProofType::Extrinsic);
+		assert_eq!(client_state.next_authority_set.id, 4);
+	}
+
+	#[test]
+	fn timestamp_proof_type_round_trips_through_protobuf() {
+		let raw = RawClientState { timestamp_proof_type: 1, ..raw_client_state() };
+		let client_state = ClientState::<HostFunctionsManager>::try_from(raw.clone()).unwrap();
+		assert_eq!(client_state.timestamp_proof_type, TimestampProofType::Storage);
+		assert_eq!(RawClientState::from(client_state), raw);
+
+		let raw = RawClientState { timestamp_proof_type: 2, ..raw_client_state() };
+		assert!(ClientState::<HostFunctionsManager>::try_from(raw).is_err());
+	}
+}
@@ -24,7 +24,9 @@ use crate::proto::ConsensusState as RawConsensusState;
 
 use crate::{client_message::ParachainHeader, error::Error};
 use ibc::{core::ics23_commitment::commitment::CommitmentRoot, timestamp::Timestamp};
-use light_client_common::decode_timestamp_extrinsic;
+use light_client_common::{
+	decode_timestamp_extrinsic, verify_timestamp_storage_proof, TimestampProofType,
+};
 
 /// Protobuf type url for Beefy Consensus State
 pub const BEEFY_CONSENSUS_STATE_TYPE_URL: &str = "/ibc.lightclients.beefy.v1.ConsensusState";
@@ -40,11 +42,24 @@ impl ConsensusState {
 		Self { timestamp, root: root.into() }
 	}
 
-	pub fn from_header(header: ParachainHeader) -> Result<Self, Error> {
+	pub fn from_header<H>(
+		header: ParachainHeader,
+		timestamp_proof_type: TimestampProofType,
+	) -> Result<Self, Error>
+	where
+		H: light_client_common::HostFunctions,
+	{
 		use sp_runtime::SaturatedConversion;
 		let root = header.parachain_header.state_root.0.to_vec();
 
-		let timestamp = decode_timestamp_extrinsic(&header.timestamp_extrinsic)?;
+		let timestamp = match timestamp_proof_type {
+			TimestampProofType::Extrinsic =>
+				decode_timestamp_extrinsic(&header.timestamp_extrinsic)?,
+			TimestampProofType::Storage => verify_timestamp_storage_proof::<H::BlakeTwo256>(
+				&header.parachain_header.state_root,
+				header.timestamp_storage_proof,
+			)?,
+		};
 		let duration = core::time::Duration::from_millis(timestamp);
 		let timestamp = Timestamp::from_nanoseconds(duration.as_nanos().saturated_into::<u64>())?
 			.into_tm_time()
@@ -111,3 +126,91 @@ pub mod test_util {
 		})
 	}
 }
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::mock::HostFunctionsManager;
+	use beefy_light_client_primitives::PartialMmrLeaf;
+	use codec::Encode;
+	use light_client_common::timestamp_storage_key;
+	use sp_core::H256;
+	use sp_runtime::{generic::Header as SubstrateHeader, traits::BlakeTwo256};
+	use sp_trie::{LayoutV0, MemoryDB, TrieDBMutBuilder, TrieMut};
+
+	const TIMESTAMP: u64 = 1_650_894_363_000;
+
+	/// Builds a state trie that only holds `pallet_timestamp::Now`, returning its root and the
+	/// storage proof of the timestamp.
+	fn timestamp_state(timestamp: u64) -> (H256, Vec<Vec<u8>>) {
+		let mut db = MemoryDB::<BlakeTwo256>::default();
+		let mut root = Default::default();
+		let root = {
+			let mut trie =
+				TrieDBMutBuilder::<LayoutV0<BlakeTwo256>>::new(&mut db, &mut root).build();
+			trie.insert(&timestamp_storage_key(), &timestamp.encode()).unwrap();
+			*trie.root()
+		};
+		let proof = db.drain().into_iter().map(|(_, (node, ..))| node.to_vec()).collect();
+		(root, proof)
+	}
+
+	fn parachain_header(
+		state_root: H256,
+		timestamp_storage_proof: Vec<Vec<u8>>,
+	) -> ParachainHeader {
+		ParachainHeader {
+			parachain_header: SubstrateHeader::<u32, BlakeTwo256> {
+				parent_hash: Default::default(),
+				number: 5,
+				state_root,
+				extrinsics_root: Default::default(),
+				digest: Default::default(),
+			},
+			partial_mmr_leaf: PartialMmrLeaf {
+				version: Default::default(),
+				parent_number_and_hash: Default::default(),
+				beefy_next_authority_set: Default::default(),
+			},
+			parachain_heads_proof: vec![],
+			heads_leaf_index: 0,
+			heads_total_count: 0,
+			extrinsic_proof: vec![],
+			timestamp_extrinsic: vec![],
+			timestamp_storage_proof,
+		}
+	}
+
+	#[test]
+	fn builds_consensus_state_from_timestamp_storage_proof() {
+		let (state_root, proof) = timestamp_state(TIMESTAMP);
+		let consensus_state = ConsensusState::from_header::<HostFunctionsManager>(
+			parachain_header(state_root, proof),
+			TimestampProofType::Storage,
+		)
+		.unwrap();
+		assert_eq!(consensus_state.root.as_bytes(), state_root.as_bytes());
+		assert_eq!(
+			Timestamp::from(consensus_state.timestamp),
+			Timestamp::from_nanoseconds(TIMESTAMP * 1_000_000).unwrap()
+		);
+	}
+
+	#[test]
+	fn rejects_timestamp_storage_proof_of_another_state() {
+		let (state_root, _) = timestamp_state(TIMESTAMP);
+		let (_, proof) = timestamp_state(TIMESTAMP + 6_000);
+		assert!(ConsensusState::from_header::<HostFunctionsManager>(
+			parachain_header(state_root, proof),
+			TimestampProofType::Storage,
+		)
+		.is_err());
+
+		// no proof at all
+		assert!(ConsensusState::from_header::<HostFunctionsManager>(
+			parachain_header(state_root, vec![]),
+			TimestampProofType::Storage,
+		)
+		.is_err());
+	}
+}
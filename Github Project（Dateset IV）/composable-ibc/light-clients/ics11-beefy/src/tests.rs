@@ -92,6 +92,7 @@ async fn test_continuous_update_of_beefy_client() {
 		relay_client: relay_client.clone(),
 		para_client: para_client.clone(),
 		para_id: 2000,
+		timestamp_proof_type: Default::default(),
 	};
 
 	println!("Waiting for parachain to start producing blocks");
@@ -162,9 +163,14 @@ async fn test_continuous_update_of_beefy_client() {
 			heads_total_count: 0,
 			extrinsic_proof,
 			timestamp_extrinsic,
+			timestamp_storage_proof: vec![],
 		};
 
-		let consensus_state = ConsensusState::from_header(parachain_header).unwrap();
+		let consensus_state = ConsensusState::from_header::<HostFunctionsManager>(
+			parachain_header,
+			client_state.timestamp_proof_type,
+		)
+		.unwrap();
 
 		break (AnyClientState::Beefy(client_state), AnyConsensusState::Beefy(consensus_state))
 	};
@@ -255,6 +261,7 @@ async fn test_continuous_update_of_beefy_client() {
 						heads_total_count: header.heads_total_count,
 						extrinsic_proof: header.extrinsic_proof,
 						timestamp_extrinsic: header.timestamp_extrinsic,
+						timestamp_storage_proof: header.timestamp_storage_proof,
 					})
 					.collect(),
 				mmr_proofs: batch_proof.items.into_iter().map(|item| item.encode()).collect(),
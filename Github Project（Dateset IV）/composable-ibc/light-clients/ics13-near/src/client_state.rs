@@ -42,6 +42,24 @@ pub struct NearClientState<H> {
 pub struct NearUpgradeOptions {}
 
 impl<H: HostFunctionsTrait> NearClientState<H> {
+	/// Creates a client trusting `head`, whose epoch is produced by `current_validators`. The
+	/// block producers of the next epoch are taken from `head`.
+	pub fn new(
+		chain_id: ChainId,
+		head: LightClientBlockView,
+		current_validators: Vec<ValidatorStakeView>,
+	) -> Self {
+		Self {
+			chain_id,
+			current_epoch: head.inner_lite.epoch_id,
+			next_epoch: head.inner_lite.next_epoch_id,
+			next_validators: head.next_bps.clone().unwrap_or_default(),
+			current_validators,
+			head,
+			_phantom: PhantomData,
+		}
+	}
+
 	pub fn get_validators_by_epoch(
 		&self,
 		epoch_id: &CryptoHash,
@@ -161,8 +161,15 @@ pub type Executive = frame_executive::Executive<
 	frame_system::ChainContext<Runtime>,
 	Runtime,
 	AllPalletsWithSystem,
+	Migrations,
 >;
 
+/// Migrations to apply on runtime upgrade.
+pub type Migrations = (
+	pallet_ibc::migrations::SeedEscrowedAmounts<Runtime>,
+	pallet_ibc::migrations::MigrateSequenceFees<Runtime>,
+);
+
 /// Handles converting a weight scalar to a fee value, based on the scale and granularity of the
 /// node's balance type.
 ///
@@ -511,6 +518,38 @@ impl pallet_ibc_ping::Config for Runtime {
 	type IbcHandler = Ibc;
 }
 
+parameter_types! {
+	pub const Ics29FeePalletId: PalletId = PalletId(*b"ics29fee");
+	pub const MaxPacketFees: u32 = 16;
+}
+
+impl pallet_ibc::ics29_fee::Config for Runtime {
+	type RuntimeEvent = RuntimeEvent;
+	type PalletId = Ics29FeePalletId;
+	type MaxPacketFees = MaxPacketFees;
+}
+
+/// Runtime calls that interchain accounts hosted on this chain are allowed to dispatch
+pub struct IcaCallFilter;
+
+impl frame_support::traits::Contains<RuntimeCall> for IcaCallFilter {
+	fn contains(call: &RuntimeCall) -> bool {
+		matches!(
+			call,
+			RuntimeCall::System(frame_system::Call::remark_with_event { .. }) |
+				RuntimeCall::Balances(pallet_balances::Call::transfer_keep_alive { .. })
+		)
+	}
+}
+
+impl pallet_ibc::ics27::Config for Runtime {
+	type RuntimeEvent = RuntimeEvent;
+	type RuntimeCall = RuntimeCall;
+	type CallFilter = IcaCallFilter;
+	type IsHostEnabled = sp_core::ConstBool<true>;
+	type IsControllerEnabled = sp_core::ConstBool<true>;
+}
+
 impl asset_registry::Config for Runtime {
 	type RuntimeEvent = RuntimeEvent;
 	type AssetId = AssetId;
@@ -562,24 +601,45 @@ parameter_types! {
 
 #[derive(Clone, Debug, Eq, PartialEq, Default)]
 pub struct Router {
-	pallet_ibc_ping: pallet_ibc_ping::IbcModule<Runtime>,
+	pallet_ibc_ping: pallet_ibc::ics29_fee::Ics29Fee<Runtime, pallet_ibc_ping::IbcModule<Runtime>>,
+	ics20: pallet_ibc::ics29_fee::Ics29Fee<Runtime, pallet_ibc::ics20::IbcModule<Runtime>>,
+	ica_host: pallet_ibc::ics29_fee::Ics29Fee<Runtime, pallet_ibc::ics27::IcaHost<Runtime>>,
+	ica_controller:
+		pallet_ibc::ics29_fee::Ics29Fee<Runtime, pallet_ibc::ics27::IcaController<Runtime>>,
 }
 
 impl ModuleRouter for Router {
 	fn get_route_mut(&mut self, module_id: &ModuleId) -> Option<&mut dyn Module> {
 		match module_id.as_ref() {
 			pallet_ibc_ping::MODULE_ID => Some(&mut self.pallet_ibc_ping),
+			ibc::applications::transfer::MODULE_ID_STR => Some(&mut self.ics20),
+			ibc::applications::interchain_accounts::HOST_MODULE_ID_STR => Some(&mut self.ica_host),
+			ibc::applications::interchain_accounts::CONTROLLER_MODULE_ID_STR =>
+				Some(&mut self.ica_controller),
 			_ => None,
 		}
 	}
 
 	fn has_route(module_id: &ModuleId) -> bool {
-		matches!(module_id.as_ref(), pallet_ibc_ping::MODULE_ID)
+		matches!(
+			module_id.as_ref(),
+			pallet_ibc_ping::MODULE_ID |
+				ibc::applications::transfer::MODULE_ID_STR |
+				ibc::applications::interchain_accounts::HOST_MODULE_ID_STR |
+				ibc::applications::interchain_accounts::CONTROLLER_MODULE_ID_STR
+		)
 	}
 
 	fn lookup_module_by_port(port_id: &PortId) -> Option<ModuleId> {
 		match port_id.as_str() {
 			pallet_ibc_ping::PORT_ID => ModuleId::from_str(pallet_ibc_ping::MODULE_ID).ok(),
+			ibc::applications::transfer::PORT_ID_STR =>
+				ModuleId::from_str(ibc::applications::transfer::MODULE_ID_STR).ok(),
+			ibc::applications::interchain_accounts::HOST_PORT_ID_STR =>
+				ModuleId::from_str(ibc::applications::interchain_accounts::HOST_MODULE_ID_STR).ok(),
+			_ if ibc::applications::interchain_accounts::is_controller_port(port_id) =>
+				ModuleId::from_str(ibc::applications::interchain_accounts::CONTROLLER_MODULE_ID_STR)
+					.ok(),
 			_ => None,
 		}
 	}
@@ -721,6 +781,11 @@ parameter_types! {
 	pub const IbcTriePrefix : &'static [u8] = b"ibc/";
 	pub FeeAccount: <Runtime as pallet_ibc::Config>::AccountIdConversion = create_alice_key();
 	pub const CleanUpPacketsPeriod: BlockNumber = 100;
+	pub const MaxWasmCodeSize: u32 = 3 * 1024 * 1024;
+	pub const WasmGasLimit: u64 = 50_000_000;
+	pub const IcaMaxTxWeight: Weight = Weight::from_parts(WEIGHT_REF_TIME_PER_SECOND / 10, 64 * 1024);
+	pub const MaxForwardAttempts: u32 = 8;
+	pub const ForwardStatusRetention: BlockNumber = 7 * DAYS;
 	pub AssetIdUSDT: AssetId = 0;
 	pub FlatFeeUSDTAmount: Balance = 0;
 	pub IbcIcs20ServiceCharge: Perbill = Perbill::from_rational(0_u32, 1000_u32 );
@@ -815,6 +880,11 @@ impl pallet_ibc::Config for Runtime {
 	type FlatFeeConverter = NonFlatFeeConverter<Runtime>;
 	type FlatFeeAssetId = AssetIdUSDT;
 	type FlatFeeAmount = FlatFeeUSDTAmount;
+	type MaxWasmCodeSize = MaxWasmCodeSize;
+	type WasmGasLimit = WasmGasLimit;
+	type IcaMaxTxWeight = IcaMaxTxWeight;
+	type MaxForwardAttempts = MaxForwardAttempts;
+	type ForwardStatusRetention = ForwardStatusRetention;
 	type SubstrateMultihopXcmHandler = SubstrateMultihopXcmHandlerNone<Runtime>;
 }
 
@@ -852,6 +922,8 @@ construct_runtime!(
 		IbcPing: pallet_ibc_ping = 36,
 		Assets: pallet_assets = 37,
 		AssetRegistry: asset_registry = 38,
+		Ics29Fee: pallet_ibc::ics29_fee = 39,
+		Ics27: pallet_ibc::ics27 = 40,
 		// pallet-ibc, should be the last module in your runtime
 		Ibc: pallet_ibc = 255,
 	}
@@ -1033,6 +1105,10 @@ impl_runtime_apis! {
 			Some(Ibc::clients())
 		}
 
+		fn clients_paginated(pagination: ibc_primitives::PageRequest) -> Option<(ibc_primitives::QueryClientsResponse, ibc_primitives::PageResponse)> {
+			Some(Ibc::clients_paginated(pagination))
+		}
+
 		fn connection(connection_id: Vec<u8>) -> Option<ibc_primitives::QueryConnectionResponse>{
 			Ibc::connection(connection_id).ok()
 		}
@@ -1041,6 +1117,10 @@ impl_runtime_apis! {
 			Ibc::connections().ok()
 		}
 
+		fn connections_paginated(pagination: ibc_primitives::PageRequest) -> Option<(ibc_primitives::QueryConnectionsResponse, ibc_primitives::PageResponse)> {
+			Ibc::connections_paginated(pagination).ok()
+		}
+
 		fn connection_using_client(client_id: Vec<u8>) -> Option<Vec<ibc_primitives::IdentifiedConnection>>{
 			Ibc::connection_using_client(client_id).ok()
 		}
@@ -1053,6 +1133,14 @@ impl_runtime_apis! {
 			Ibc::channel(channel_id, port_id).ok()
 		}
 
+		fn channel_upgrade(channel_id: Vec<u8>, port_id: Vec<u8>) -> Option<ibc_primitives::QueryChannelUpgradeResponse> {
+			Ibc::channel_upgrade(channel_id, port_id).ok()
+		}
+
+		fn channel_upgrade_error(channel_id: Vec<u8>, port_id: Vec<u8>) -> Option<ibc_primitives::QueryUpgradeErrorResponse> {
+			Ibc::channel_upgrade_error(channel_id, port_id).ok()
+		}
+
 		fn channel_client(channel_id: Vec<u8>, port_id: Vec<u8>) -> Option<ibc_primitives::IdentifiedClientState> {
 			Ibc::channel_client(channel_id, port_id).ok()
 		}
@@ -1065,14 +1153,26 @@ impl_runtime_apis! {
 			Ibc::channels().ok()
 		}
 
+		fn channels_paginated(pagination: ibc_primitives::PageRequest) -> Option<(ibc_primitives::QueryChannelsResponse, ibc_primitives::PageResponse)> {
+			Ibc::channels_paginated(pagination).ok()
+		}
+
 		fn packet_commitments(channel_id: Vec<u8>, port_id: Vec<u8>) -> Option<ibc_primitives::QueryPacketCommitmentsResponse> {
 			Ibc::packet_commitments(channel_id, port_id).ok()
 		}
 
+		fn packet_commitments_paginated(channel_id: Vec<u8>, port_id: Vec<u8>, pagination: ibc_primitives::PageRequest) -> Option<(ibc_primitives::QueryPacketCommitmentsResponse, ibc_primitives::PageResponse)> {
+			Ibc::packet_commitments_paginated(channel_id, port_id, pagination).ok()
+		}
+
 		fn packet_acknowledgements(channel_id: Vec<u8>, port_id: Vec<u8>) -> Option<ibc_primitives::QueryPacketAcknowledgementsResponse>{
 			Ibc::packet_acknowledgements(channel_id, port_id).ok()
 		}
 
+		fn packet_acknowledgements_paginated(channel_id: Vec<u8>, port_id: Vec<u8>, pagination: ibc_primitives::PageRequest) -> Option<(ibc_primitives::QueryPacketAcknowledgementsResponse, ibc_primitives::PageResponse)> {
+			Ibc::packet_acknowledgements_paginated(channel_id, port_id, pagination).ok()
+		}
+
 		fn unreceived_packets(channel_id: Vec<u8>, port_id: Vec<u8>, seqs: Vec<u64>) -> Option<Vec<u64>> {
 			Ibc::unreceived_packets(channel_id, port_id, seqs).ok()
 		}
@@ -1106,6 +1206,14 @@ impl_runtime_apis! {
 			Ibc::get_denom_traces(key, limit, count_total)
 		}
 
+		fn escrow_invariant() -> Option<ibc_primitives::QueryEscrowInvariantResponse> {
+			Some(Ibc::escrow_invariant())
+		}
+
+		fn forward_status(port_id: Vec<u8>, channel_id: Vec<u8>, seq: u64) -> Option<ibc_primitives::QueryForwardStatusResponse> {
+			Some(Ibc::forward_status(port_id, channel_id, seq))
+		}
+
 		fn block_events(extrinsic_index: Option<u32>) -> Vec<Result<pallet_ibc::events::IbcEvent, pallet_ibc::errors::IbcError>> {
 			let mut raw_events = frame_system::Pallet::<Self>::read_events_no_consensus();
 			if let Some(idx) = extrinsic_index {
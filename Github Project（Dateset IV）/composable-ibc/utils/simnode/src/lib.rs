@@ -141,6 +141,8 @@ mod tests {
 				para_id: 100,
 				current_set_id: 1,
 				current_authorities: Default::default(),
+				chain_type: Default::default(),
+				timestamp_proof_type: Default::default(),
 				_phantom: Default::default(),
 			};
 
@@ -169,6 +171,7 @@ mod tests {
 					unknown_headers: Default::default(),
 				},
 				parachain_headers: Default::default(),
+				solochain_headers: Default::default(),
 			};
 			let msg_create_client = MsgCreateAnyClient::<LocalClientTypes> {
 				client_state: AnyClientState::Grandpa(client_state),